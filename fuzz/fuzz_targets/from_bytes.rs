@@ -0,0 +1,40 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use xmp_toolkit::{FileFormat, OpenFileOptions, XmpFile};
+
+// Exercises the C++ toolkit's smart handlers on in-memory files, including
+// rewriting the file with the metadata that was read from it.
+fuzz_target!(|data: &[u8]| {
+    let Ok(mut f) = XmpFile::from_bytes(
+        data,
+        FileFormat::Unknown,
+        OpenFileOptions::default().for_update(),
+    ) else {
+        return;
+    };
+
+    let xmp = f.xmp().filter(|m| f.can_put_xmp(m));
+
+    match xmp {
+        Some(m) if f.put_xmp(&m).is_ok() => {
+            let _ = f.into_bytes();
+        }
+        _ => {
+            let _ = f.close();
+        }
+    }
+});
@@ -0,0 +1,34 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use xmp_toolkit::{FromStrOptions, ToStringOptions, XmpMeta};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(s) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    if let Ok(m) = XmpMeta::from_str_with_options(s, FromStrOptions::default()) {
+        let _ = m.to_string_with_options(ToStringOptions::default());
+        let _ = format!("{m:#}");
+    }
+
+    if let Ok(blocks) = XmpMeta::description_blocks_from_str(s, FromStrOptions::default()) {
+        for block in blocks {
+            let _ = block.to_string();
+        }
+    }
+});
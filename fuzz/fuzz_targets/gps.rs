@@ -0,0 +1,22 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use xmp_toolkit::xmp_gps;
+
+fuzz_target!(|s: &str| {
+    let _ = xmp_gps::exif_latitude_to_decimal(s);
+    let _ = xmp_gps::exif_longitude_to_decimal(s);
+});
@@ -0,0 +1,49 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+#![no_main]
+
+use std::io::Write;
+
+use libfuzzer_sys::fuzz_target;
+use xmp_toolkit::{OpenFileOptions, XmpFile};
+
+// Exercises the C++ toolkit's packet scanner, which is used for file
+// formats that have no smart handler.
+fuzz_target!(|data: &[u8]| {
+    let Ok(mut file) = tempfile::Builder::new().suffix(".bin").tempfile() else {
+        return;
+    };
+
+    if file.write_all(data).is_err() || file.flush().is_err() {
+        return;
+    }
+
+    let Ok(mut f) = XmpFile::new() else {
+        return;
+    };
+
+    if f.open_file(
+        file.path(),
+        OpenFileOptions::default()
+            .for_read()
+            .only_xmp()
+            .use_packet_scanning(),
+    )
+    .is_ok()
+    {
+        if let Some(m) = f.xmp() {
+            let _ = m.to_string();
+        }
+    }
+});
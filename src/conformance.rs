@@ -0,0 +1,666 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Contains a conformance suite for checking that XMP processing preserves
+//! the meaning of XMP data.
+//!
+//! The suite consists of the RDF fixtures that this crate uses to exercise
+//! the C++ XMP Toolkit (adapted from the toolkit's own `XMPCoreCoverage`
+//! sample), each paired with property values that any correct XMP
+//! implementation must report after parsing it. The fixtures cover simple
+//! properties, all array forms, structs, qualifiers (including
+//! `xml:lang`), deeply nested namespaces, and date/time values.
+//!
+//! Use [`run`] to check a pipeline that reads and rewrites XMP, such as
+//! a metadata editor, a format converter, or an alternative XMP backend.
+//! Use [`cases`] and [`ConformanceCase::check`] for finer control.
+//!
+//! ## Example
+//!
+//! ```
+//! use std::str::FromStr;
+//!
+//! use xmp_toolkit::{conformance, XmpMeta};
+//!
+//! // A pipeline that parses and reserializes XMP.
+//! let result = conformance::run(|input| Ok(XmpMeta::from_str(input)?.to_string()));
+//!
+//! assert!(result.is_ok());
+//! ```
+
+use std::{fmt, str::FromStr};
+
+use crate::{XmpMeta, XmpResult};
+
+const NS1: &str = "ns:test1/";
+const NS2: &str = "ns:test2/";
+
+/// Prefixes used by the paths in [`ExpectedValue`]. These are registered
+/// before each case is checked.
+const PREFIXES: &[(&str, &str)] = &[
+    (NS1, "ns1"),
+    (NS2, "ns2"),
+    ("ns:test3/", "ns3"),
+    ("ns:test4/", "ns4"),
+    ("ns:test5/", "ns5"),
+    ("ns:test6/", "ns6"),
+];
+
+/// A single input for the conformance suite.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ConformanceCase {
+    /// Short, unique name for this case.
+    pub name: &'static str,
+
+    /// The RDF/XML input.
+    pub input: &'static str,
+
+    /// Property values that must be present after processing `input`.
+    pub expected: &'static [ExpectedValue],
+}
+
+/// A property value that must be present after processing a
+/// [`ConformanceCase`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ExpectedValue {
+    /// Namespace URI of the top-level property.
+    pub namespace: &'static str,
+
+    /// Path to the value, in the syntax accepted by [`XmpMeta::property`].
+    /// Paths may address array items, struct fields, and qualifiers, and
+    /// use the prefixes `ns1` through `ns6` for the namespaces
+    /// `ns:test1/` through `ns:test6/`.
+    pub path: &'static str,
+
+    /// The expected value.
+    pub value: &'static str,
+}
+
+/// Describes one way in which a pipeline did not conform.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConformanceFailure {
+    /// Name of the [`ConformanceCase`] that failed.
+    pub case: &'static str,
+
+    /// Human-readable description of the failure.
+    pub description: String,
+}
+
+impl fmt::Display for ConformanceFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.case, self.description)
+    }
+}
+
+impl ConformanceCase {
+    /// Checks that `meta` contains every expected value for this case.
+    ///
+    /// Returns an empty list if `meta` conforms.
+    pub fn check(&self, meta: &XmpMeta) -> Vec<ConformanceFailure> {
+        register_prefixes();
+
+        self.expected
+            .iter()
+            .filter_map(|expected| {
+                let actual = meta
+                    .property(expected.namespace, expected.path)
+                    .map(|v| v.value);
+
+                if actual.as_deref() == Some(expected.value) {
+                    return None;
+                }
+
+                let prefix = XmpMeta::namespace_prefix(expected.namespace).unwrap_or_default();
+
+                Some(ConformanceFailure {
+                    case: self.name,
+                    description: match actual {
+                        Some(actual) => format!(
+                            "{prefix}{} is {actual:?}; expected {:?}",
+                            expected.path, expected.value
+                        ),
+                        None => format!(
+                            "{prefix}{} is missing; expected {:?}",
+                            expected.path, expected.value
+                        ),
+                    },
+                })
+            })
+            .collect()
+    }
+}
+
+/// Returns all cases in the conformance suite.
+pub fn cases() -> &'static [ConformanceCase] {
+    CASES
+}
+
+/// Runs the conformance suite against an XMP processing pipeline.
+///
+/// `pipeline` is called with the RDF/XML input of each case and must return
+/// the XMP it produces, serialized as RDF/XML. The output is parsed with
+/// [`XmpMeta::from_str`] and checked against the case's expected values.
+///
+/// Returns `Ok(())` if every case conforms, or a list of all failures
+/// otherwise. An error returned by `pipeline` or by parsing its output is
+/// reported as a failure of that case.
+pub fn run<F>(mut pipeline: F) -> Result<(), Vec<ConformanceFailure>>
+where
+    F: FnMut(&str) -> XmpResult<String>,
+{
+    let failures: Vec<ConformanceFailure> = cases()
+        .iter()
+        .flat_map(
+            |case| match pipeline(case.input).and_then(|output| XmpMeta::from_str(&output)) {
+                Ok(meta) => case.check(&meta),
+                Err(err) => vec![ConformanceFailure {
+                    case: case.name,
+                    description: err.to_string(),
+                }],
+            },
+        )
+        .collect();
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures)
+    }
+}
+
+fn register_prefixes() {
+    for (uri, prefix) in PREFIXES {
+        // A conflicting registration will surface as a failed lookup.
+        let _ = XmpMeta::register_namespace(uri, prefix);
+    }
+}
+
+const CASES: &[ConformanceCase] = &[RDF_COVERAGE, SIMPLE_RDF, NAMESPACE_RDF, DATE_TIME_RDF];
+
+/// Covers all of the RDF syntax forms for simple properties, arrays,
+/// structs, and qualifiers.
+pub const RDF_COVERAGE: ConformanceCase = ConformanceCase {
+    name: "rdf_coverage",
+    input: r#"<rdf:RDF xmlns:rdf='http://www.w3.org/1999/02/22-rdf-syntax-ns#'>
+      <rdf:Description rdf:about='Test:XMPCoreCoverage/kRDFCoverage' xmlns:ns1='ns:test1/' xmlns:ns2='ns:test2/'>
+
+        <ns1:SimpleProp1>Simple1 value</ns1:SimpleProp1>
+        <ns1:SimpleProp2 xml:lang='x-default'>Simple2 value</ns1:SimpleProp2>
+
+        <ns1:ArrayProp1>
+          <rdf:Bag>
+            <rdf:li>Item1.1 value</rdf:li>
+            <rdf:li>Item1.2 value</rdf:li>
+          </rdf:Bag>
+        </ns1:ArrayProp1>
+
+        <ns1:ArrayProp2>
+          <rdf:Alt>
+            <rdf:li xml:lang='x-one'>Item2.1 value</rdf:li>
+            <rdf:li xml:lang='x-two'>Item2.2 value</rdf:li>
+          </rdf:Alt>
+        </ns1:ArrayProp2>
+
+        <ns1:ArrayProp3>
+          <rdf:Alt>
+            <rdf:li xml:lang='x-one'>Item3.1 value</rdf:li>
+            <rdf:li>Item3.2 value</rdf:li>
+          </rdf:Alt>
+        </ns1:ArrayProp3>
+
+        <ns1:ArrayProp4>
+          <rdf:Alt>
+            <rdf:li>Item4.1 value</rdf:li>
+            <rdf:li xml:lang='x-two'>Item4.2 value</rdf:li>
+          </rdf:Alt>
+        </ns1:ArrayProp4>
+
+        <ns1:ArrayProp5>
+          <rdf:Alt>
+            <rdf:li xml:lang='x-xxx'>Item5.1 value</rdf:li>
+            <rdf:li xml:lang='x-xxx'>Item5.2 value</rdf:li>
+          </rdf:Alt>
+        </ns1:ArrayProp5>
+
+        <ns1:StructProp rdf:parseType='Resource'>
+          <ns2:Field1>Field1 value</ns2:Field1>
+          <ns2:Field2>Field2 value</ns2:Field2>
+        </ns1:StructProp>
+
+        <ns1:QualProp1 rdf:parseType='Resource'>
+          <rdf:value>Prop value</rdf:value>
+          <ns2:Qual>Qual value</ns2:Qual>
+        </ns1:QualProp1>
+
+        <ns1:QualProp2 rdf:parseType='Resource'>
+          <rdf:value xml:lang='x-default'>Prop value</rdf:value>
+          <ns2:Qual>Qual value</ns2:Qual>
+        </ns1:QualProp2>
+
+        <!-- NOTE: QualProp3 is not quite kosher. Normally a qualifier on a struct is attached to the -->
+        <!-- struct node in the XMP tree, and the same for an array. See QualProp4 and QualProp5. But -->
+        <!-- for the pseudo-struct of a qualified simple property there is no final struct node that  -->
+        <!-- can own the qualifier. Instead the qualifier is attached to the value. The alternative   -->
+        <!-- of attaching the qualifier to the value and all other qualifiers is not compelling. This -->
+        <!-- issue only arises for xml:lang, it is the only qualifier that RDF has as an attribute.   -->
+
+        <ns1:QualProp3 xml:lang='x-default' rdf:parseType='Resource'>
+          <rdf:value>Prop value</rdf:value>
+          <ns2:Qual>Qual value</ns2:Qual>
+        </ns1:QualProp3>
+
+        <ns1:QualProp4 xml:lang='x-default' rdf:parseType='Resource'>
+          <ns2:Field1>Field1 value</ns2:Field1>
+          <ns2:Field2>Field2 value</ns2:Field2>
+        </ns1:QualProp4>
+
+        <ns1:QualProp5 xml:lang='x-default'>
+          <rdf:Bag>
+            <rdf:li>Item1.1 value</rdf:li>
+            <rdf:li>Item1.2 value</rdf:li>
+          </rdf:Bag>
+        </ns1:QualProp5>
+
+        <ns2:NestedStructProp rdf:parseType='Resource'>
+          <ns1:Outer rdf:parseType='Resource'>
+            <ns1:Middle rdf:parseType='Resource'>
+              <ns1:Inner rdf:parseType='Resource'>
+                <ns1:Field1>Field1 value</ns1:Field1>
+                <ns2:Field2>Field2 value</ns2:Field2>
+              </ns1:Inner>
+            </ns1:Middle>
+          </ns1:Outer>
+        </ns2:NestedStructProp>
+
+      </rdf:Description>
+    </rdf:RDF>"#,
+    expected: &[
+        ExpectedValue {
+            namespace: NS1,
+            path: "SimpleProp1",
+            value: "Simple1 value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "SimpleProp2",
+            value: "Simple2 value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "SimpleProp2/?xml:lang",
+            value: "x-default",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "ArrayProp1[1]",
+            value: "Item1.1 value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "ArrayProp1[2]",
+            value: "Item1.2 value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "ArrayProp2[1]",
+            value: "Item2.1 value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "ArrayProp2[1]/?xml:lang",
+            value: "x-one",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "ArrayProp2[2]",
+            value: "Item2.2 value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "ArrayProp2[2]/?xml:lang",
+            value: "x-two",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "ArrayProp3[1]/?xml:lang",
+            value: "x-one",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "ArrayProp3[2]",
+            value: "Item3.2 value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "ArrayProp4[1]",
+            value: "Item4.1 value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "ArrayProp4[2]/?xml:lang",
+            value: "x-two",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "ArrayProp5[1]",
+            value: "Item5.1 value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "ArrayProp5[2]",
+            value: "Item5.2 value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "StructProp/ns2:Field1",
+            value: "Field1 value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "StructProp/ns2:Field2",
+            value: "Field2 value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "QualProp1",
+            value: "Prop value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "QualProp1/?ns2:Qual",
+            value: "Qual value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "QualProp2/?xml:lang",
+            value: "x-default",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "QualProp2/?ns2:Qual",
+            value: "Qual value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "QualProp3",
+            value: "Prop value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "QualProp3/?xml:lang",
+            value: "x-default",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "QualProp4/ns2:Field1",
+            value: "Field1 value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "QualProp4/?xml:lang",
+            value: "x-default",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "QualProp5[1]",
+            value: "Item1.1 value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "QualProp5/?xml:lang",
+            value: "x-default",
+        },
+        ExpectedValue {
+            namespace: NS2,
+            path: "NestedStructProp/ns1:Outer/ns1:Middle/ns1:Inner/ns1:Field1",
+            value: "Field1 value",
+        },
+        ExpectedValue {
+            namespace: NS2,
+            path: "NestedStructProp/ns1:Outer/ns1:Middle/ns1:Inner/ns2:Field2",
+            value: "Field2 value",
+        },
+    ],
+};
+
+/// Covers one of each common kind of property.
+pub const SIMPLE_RDF: ConformanceCase = ConformanceCase {
+    name: "simple_rdf",
+    input: r#"<rdf:RDF xmlns:rdf='http://www.w3.org/1999/02/22-rdf-syntax-ns#'>
+      <rdf:Description rdf:about='Test:XMPCoreCoverage/kSimpleRDF' xmlns:ns1='ns:test1/' xmlns:ns2='ns:test2/'>
+
+        <ns1:SimpleProp>Simple value</ns1:SimpleProp>
+
+        <ns1:ArrayProp>
+          <rdf:Bag>
+            <rdf:li>Item1 value</rdf:li>
+            <rdf:li>Item2 value</rdf:li>
+          </rdf:Bag>
+        </ns1:ArrayProp>
+
+        <ns1:StructProp rdf:parseType='Resource'>
+          <ns2:Field1>Field1 value</ns2:Field1>
+          <ns2:Field2>Field2 value</ns2:Field2>
+        </ns1:StructProp>
+
+        <ns1:QualProp rdf:parseType='Resource'>
+          <rdf:value>Prop value</rdf:value>
+          <ns2:Qual>Qual value</ns2:Qual>
+        </ns1:QualProp>
+
+        <ns1:AltTextProp>
+          <rdf:Alt>
+            <rdf:li xml:lang='x-one'>x-one value</rdf:li>
+            <rdf:li xml:lang='x-two'>x-two value</rdf:li>
+          </rdf:Alt>
+        </ns1:AltTextProp>
+
+        <ns1:ArrayOfStructProp>
+          <rdf:Bag>
+            <rdf:li rdf:parseType='Resource'>
+              <ns2:Field1>Item-1</ns2:Field1>
+              <ns2:Field2>Field 1.2 value</ns2:Field2>
+            </rdf:li>
+            <rdf:li rdf:parseType='Resource'>
+              <ns2:Field1>Item-2</ns2:Field1>
+              <ns2:Field2>Field 2.2 value</ns2:Field2>
+            </rdf:li>
+          </rdf:Bag>
+        </ns1:ArrayOfStructProp>
+
+      </rdf:Description>
+    </rdf:RDF>"#,
+    expected: &[
+        ExpectedValue {
+            namespace: NS1,
+            path: "SimpleProp",
+            value: "Simple value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "ArrayProp[1]",
+            value: "Item1 value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "ArrayProp[2]",
+            value: "Item2 value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "StructProp/ns2:Field1",
+            value: "Field1 value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "StructProp/ns2:Field2",
+            value: "Field2 value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "QualProp",
+            value: "Prop value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "QualProp/?ns2:Qual",
+            value: "Qual value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "AltTextProp[1]",
+            value: "x-one value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "AltTextProp[1]/?xml:lang",
+            value: "x-one",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "AltTextProp[2]",
+            value: "x-two value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "AltTextProp[2]/?xml:lang",
+            value: "x-two",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "ArrayOfStructProp[1]/ns2:Field1",
+            value: "Item-1",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "ArrayOfStructProp[1]/ns2:Field2",
+            value: "Field 1.2 value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "ArrayOfStructProp[2]/ns2:Field1",
+            value: "Item-2",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "ArrayOfStructProp[2]/ns2:Field2",
+            value: "Field 2.2 value",
+        },
+    ],
+};
+
+/// Covers namespaces declared at many levels of a nested struct.
+pub const NAMESPACE_RDF: ConformanceCase = ConformanceCase {
+    name: "namespace_rdf",
+    input: r#"<rdf:RDF xmlns:rdf='http://www.w3.org/1999/02/22-rdf-syntax-ns#'>
+      <rdf:Description rdf:about='Test:XMPCoreCoverage/kNamespaceRDF' xmlns:ns1='ns:test1/'>
+
+        <ns1:NestedStructProp rdf:parseType='Resource'>
+          <ns2:Outer rdf:parseType='Resource' xmlns:ns2='ns:test2/' xmlns:ns3='ns:test3/'>
+            <ns3:Middle rdf:parseType='Resource' xmlns:ns4='ns:test4/'>
+              <ns4:Inner rdf:parseType='Resource' xmlns:ns5='ns:test5/' xmlns:ns6='ns:test6/'>
+                <ns5:Field1>Field1 value</ns5:Field1>
+                <ns6:Field2>Field2 value</ns6:Field2>
+              </ns4:Inner>
+            </ns3:Middle>
+          </ns2:Outer>
+        </ns1:NestedStructProp>
+
+      </rdf:Description>
+    </rdf:RDF>"#,
+    expected: &[
+        ExpectedValue {
+            namespace: NS1,
+            path: "NestedStructProp/ns2:Outer/ns3:Middle/ns4:Inner/ns5:Field1",
+            value: "Field1 value",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "NestedStructProp/ns2:Outer/ns3:Middle/ns4:Inner/ns6:Field2",
+            value: "Field2 value",
+        },
+    ],
+};
+
+/// Covers date/time values of every supported precision and time zone
+/// form.
+pub const DATE_TIME_RDF: ConformanceCase = ConformanceCase {
+    name: "date_time_rdf",
+    input: r#"<rdf:RDF xmlns:rdf='http://www.w3.org/1999/02/22-rdf-syntax-ns#'>
+      <rdf:Description rdf:about='Test:XMPCoreCoverage/kDateTimeRDF' xmlns:ns1='ns:test1/'>
+
+        <ns1:Date1>2003</ns1:Date1>
+        <ns1:Date2>2003-12</ns1:Date2>
+        <ns1:Date3>2003-12-31</ns1:Date3>
+
+        <ns1:Date4>2003-12-31T12:34Z</ns1:Date4>
+        <ns1:Date5>2003-12-31T12:34:56Z</ns1:Date5>
+
+        <ns1:Date6>2003-12-31T12:34:56.001Z</ns1:Date6>
+        <ns1:Date7>2003-12-31T12:34:56.000000001Z</ns1:Date7>
+
+        <ns1:Date8>2003-12-31T10:04:56-02:30</ns1:Date8>
+        <ns1:Date9>2003-12-31T15:49:56+03:15</ns1:Date9>
+
+      </rdf:Description>
+    </rdf:RDF>"#,
+    expected: &[
+        ExpectedValue {
+            namespace: NS1,
+            path: "Date1",
+            value: "2003",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "Date2",
+            value: "2003-12",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "Date3",
+            value: "2003-12-31",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "Date4",
+            value: "2003-12-31T12:34Z",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "Date5",
+            value: "2003-12-31T12:34:56Z",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "Date6",
+            value: "2003-12-31T12:34:56.001Z",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "Date7",
+            value: "2003-12-31T12:34:56.000000001Z",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "Date8",
+            value: "2003-12-31T10:04:56-02:30",
+        },
+        ExpectedValue {
+            namespace: NS1,
+            path: "Date9",
+            value: "2003-12-31T15:49:56+03:15",
+        },
+    ],
+};
@@ -11,9 +11,12 @@
 // specific language governing permissions and limitations under
 // each license.
 
+#include <algorithm>
 #include <cstring>
+#include <memory>
 #include <mutex>
 #include <string>
+#include <vector>
 
 #define TXMP_STRING_TYPE std::string
 #define XMP_INCLUDE_XMPFILES 1
@@ -25,12 +28,17 @@
 
 std::once_flag xmp_init_flag;
 static volatile bool xmp_init_succeeded = false;
+static std::string xmp_plugin_folder;
 
 inline void init_xmp_fn() {
     #ifndef NOOP_FFI
         try {
             SXMPMeta::Initialize();
-            SXMPFiles::Initialize(kXMPFiles_IgnoreLocalText);
+            if (xmp_plugin_folder.empty()) {
+                SXMPFiles::Initialize(kXMPFiles_IgnoreLocalText);
+            } else {
+                SXMPFiles::Initialize(kXMPFiles_IgnoreLocalText, xmp_plugin_folder.c_str());
+            }
             xmp_init_succeeded = true;
         }
         catch (XMP_Error& e) {
@@ -123,11 +131,268 @@ static bool xmpFileErrorCallback(void* context,
 }
 
 
+typedef void (*CXmpParseWarningProc)(void* context,
+                                     AdobeXMPCommon::uint32 severity,
+                                     AdobeXMPCommon::int32 cause,
+                                     const char* message);
+
+typedef struct CXmpParseWarningContext {
+    void* rustContext;
+    CXmpParseWarningProc proc;
+} CXmpParseWarningContext;
+
+static bool xmpMetaParseWarningCallback(void* context,
+                                        XMP_ErrorSeverity severity,
+                                        AdobeXMPCommon::int32 cause,
+                                        XMP_StringPtr message) {
+    CXmpParseWarningContext* ctx = (CXmpParseWarningContext*) context;
+    if (ctx && ctx->proc) {
+        (*ctx->proc)(ctx->rustContext, severity, cause, message);
+    }
+
+    // True means recover from the problem and keep parsing, as the
+    // toolkit would have done without a callback installed.
+    return true;
+}
+
+typedef void (*CXmpStringListProc)(void* context, const char* str);
+
+typedef AdobeXMPCommon::int32 (*CXmpStreamReadProc)(void* stream,
+                                                    void* buffer,
+                                                    AdobeXMPCommon::uint32 count);
+
+typedef AdobeXMPCommon::int64 (*CXmpStreamSeekProc)(void* stream,
+                                                    AdobeXMPCommon::int64 offset,
+                                                    AdobeXMPCommon::uint32 mode);
+
+typedef AdobeXMPCommon::int32 (*CXmpStreamWriteProc)(void* stream,
+                                                     const void* buffer,
+                                                     AdobeXMPCommon::uint32 count);
+
+typedef AdobeXMPCommon::int32 (*CXmpStreamTruncateProc)(void* stream,
+                                                        AdobeXMPCommon::int64 length);
+
+#ifndef NOOP_FFI
+    // Holds the entire content of a file in memory for XMPFiles.
+    class MemoryIO : public XMP_IO {
+    public:
+        MemoryIO(): pos(0), temp(NULL) {}
+
+        MemoryIO(const char* data, size_t len):
+            buffer(data, len), pos(0), temp(NULL) {}
+
+        virtual ~MemoryIO() {
+            delete temp;
+        }
+
+        virtual XMP_Uns32 Read(void* outBuffer, XMP_Uns32 count, bool readAll = false) {
+            XMP_Int64 available = (XMP_Int64) buffer.size() - pos;
+            XMP_Uns32 n = available <= 0 ? 0 : (XMP_Uns32) std::min<XMP_Int64>(count, available);
+
+            if (readAll && n < count) {
+                throw XMP_Error(kXMPErr_EnforceFailure, "Not enough data in buffer");
+            }
+
+            memcpy(outBuffer, buffer.data() + pos, n);
+            pos += n;
+            return n;
+        }
+
+        virtual void Write(const void* inBuffer, XMP_Uns32 count) {
+            if ((size_t) pos + count > buffer.size()) {
+                buffer.resize((size_t) pos + count);
+            }
+
+            memcpy(&buffer[(size_t) pos], inBuffer, count);
+            pos += count;
+        }
+
+        virtual XMP_Int64 Seek(XMP_Int64 offset, SeekMode mode) {
+            XMP_Int64 base = 0;
+            if (mode == kXMP_SeekFromCurrent) {
+                base = pos;
+            } else if (mode == kXMP_SeekFromEnd) {
+                base = (XMP_Int64) buffer.size();
+            }
+
+            if (base + offset < 0) {
+                throw XMP_Error(kXMPErr_BadParam, "Seek before start of buffer");
+            }
+
+            pos = base + offset;
+            return pos;
+        }
+
+        virtual XMP_Int64 Length() {
+            return (XMP_Int64) buffer.size();
+        }
+
+        virtual void Truncate(XMP_Int64 length) {
+            buffer.resize((size_t) length);
+            if (pos > length) {
+                pos = length;
+            }
+        }
+
+        virtual XMP_IO* DeriveTemp() {
+            if (!temp) {
+                temp = new MemoryIO;
+            }
+            return temp;
+        }
+
+        virtual void AbsorbTemp() {
+            if (!temp) {
+                throw XMP_Error(kXMPErr_InternalFailure, "No temporary buffer to absorb");
+            }
+
+            buffer.swap(temp->buffer);
+            pos = 0;
+            DeleteTemp();
+        }
+
+        virtual void DeleteTemp() {
+            delete temp;
+            temp = NULL;
+        }
+
+        const std::string& Contents() const {
+            return buffer;
+        }
+
+    private:
+        std::string buffer;
+        XMP_Int64 pos;
+        MemoryIO* temp;
+    };
+
+    // Adapts a Rust stream for use by XMPFiles. `writeProc` and
+    // `truncateProc` are NULL for streams opened for reading.
+    //
+    // When a format handler rewrites the file, it does so in a temporary
+    // MemoryIO, whose content is then copied back to the stream.
+    class RustStreamIO : public XMP_IO {
+    public:
+        RustStreamIO(void* stream,
+                     CXmpStreamReadProc readProc,
+                     CXmpStreamSeekProc seekProc,
+                     CXmpStreamWriteProc writeProc,
+                     CXmpStreamTruncateProc truncateProc):
+            stream(stream),
+            readProc(readProc),
+            seekProc(seekProc),
+            writeProc(writeProc),
+            truncateProc(truncateProc),
+            temp(NULL) {}
+
+        virtual ~RustStreamIO() {
+            delete temp;
+        }
+
+        virtual XMP_Uns32 Read(void* buffer, XMP_Uns32 count, bool readAll = false) {
+            XMP_Uns32 total = 0;
+            while (total < count) {
+                AdobeXMPCommon::int32 n = (*readProc)(stream, (char*) buffer + total, count - total);
+                if (n < 0) {
+                    throw XMP_Error(kXMPErr_ReadError, "Stream read failed");
+                }
+                if (n == 0) {
+                    break;
+                }
+                total += (XMP_Uns32) n;
+            }
+
+            if (readAll && total < count) {
+                throw XMP_Error(kXMPErr_EnforceFailure, "Not enough data in stream");
+            }
+
+            return total;
+        }
+
+        virtual void Write(const void* buffer, XMP_Uns32 count) {
+            if (!writeProc) {
+                throw XMP_Error(kXMPErr_FilePermission, "Stream is read-only");
+            }
+            if ((*writeProc)(stream, (const char*) buffer, count) != 0) {
+                throw XMP_Error(kXMPErr_WriteError, "Stream write failed");
+            }
+        }
+
+        virtual XMP_Int64 Seek(XMP_Int64 offset, SeekMode mode) {
+            AdobeXMPCommon::int64 pos = (*seekProc)(stream, offset, (AdobeXMPCommon::uint32) mode);
+            if (pos < 0) {
+                throw XMP_Error(kXMPErr_ReadError, "Stream seek failed");
+            }
+            return pos;
+        }
+
+        virtual XMP_Int64 Length() {
+            XMP_Int64 current = Seek(0, kXMP_SeekFromCurrent);
+            XMP_Int64 length = Seek(0, kXMP_SeekFromEnd);
+            Seek(current, kXMP_SeekFromStart);
+            return length;
+        }
+
+        virtual void Truncate(XMP_Int64 length) {
+            if (!truncateProc) {
+                throw XMP_Error(kXMPErr_FilePermission, "Stream is read-only");
+            }
+            if ((*truncateProc)(stream, length) != 0) {
+                throw XMP_Error(kXMPErr_WriteError, "Stream truncate failed");
+            }
+        }
+
+        virtual XMP_IO* DeriveTemp() {
+            if (!writeProc) {
+                throw XMP_Error(kXMPErr_FilePermission, "Stream is read-only");
+            }
+            if (!temp) {
+                temp = new MemoryIO;
+            }
+            return temp;
+        }
+
+        virtual void AbsorbTemp() {
+            if (!temp) {
+                throw XMP_Error(kXMPErr_InternalFailure, "No temporary buffer to absorb");
+            }
+
+            const std::string& contents = temp->Contents();
+            const size_t chunkSize = 1024 * 1024;
+
+            Seek(0, kXMP_SeekFromStart);
+            for (size_t offset = 0; offset < contents.size(); offset += chunkSize) {
+                Write(contents.data() + offset, (XMP_Uns32) std::min(chunkSize, contents.size() - offset));
+            }
+            Truncate((XMP_Int64) contents.size());
+            Seek(0, kXMP_SeekFromStart);
+
+            DeleteTemp();
+        }
+
+        virtual void DeleteTemp() {
+            delete temp;
+            temp = NULL;
+        }
+
+    private:
+        void* stream;
+        CXmpStreamReadProc readProc;
+        CXmpStreamSeekProc seekProc;
+        CXmpStreamWriteProc writeProc;
+        CXmpStreamTruncateProc truncateProc;
+        MemoryIO* temp;
+    };
+#endif
+
 extern "C" {
     typedef struct CXmpFile {
         #ifdef NOOP_FFI
             int x;
         #else
+            // Declared before `f` so that the file is closed
+            // before the stream it reads from is released.
+            std::unique_ptr<XMP_IO> io;
             SXMPFiles f;
             CXmpError err;
         #endif
@@ -139,6 +404,15 @@ extern "C" {
         }
     } CXmpFile;
 
+    typedef struct CXmpPacketInfo {
+        AdobeXMPCommon::int64 offset;
+        AdobeXMPCommon::int32 length;
+        AdobeXMPCommon::int32 padSize;
+        AdobeXMPCommon::uint32 charForm;
+        AdobeXMPCommon::uint32 writeable;
+        AdobeXMPCommon::uint32 hasWrapper;
+    } CXmpPacketInfo;
+
     typedef struct CXmpMeta {
         #ifdef NOOP_FFI
             int x;
@@ -147,15 +421,70 @@ extern "C" {
         #endif
     } CXmpMeta;
 
+    // Operation kinds for CXmpMetaBatch.
+    // Must be kept in sync with CXmpBatchOp in ffi.rs.
+    enum {
+        kCXmpBatch_GetProperty = 0,
+        kCXmpBatch_SetProperty = 1,
+        kCXmpBatch_DeleteProperty = 2,
+        kCXmpBatch_Skip = 3
+    };
+
+    typedef struct CXmpBatchOp {
+        AdobeXMPCommon::uint32 kind;
+        const char* schemaNS;
+        const char* propName;
+        const char* propValue;
+        AdobeXMPCommon::uint32 options;
+    } CXmpBatchOp;
+
+    typedef struct CXmpBatchResult {
+        const char* value;
+        AdobeXMPCommon::uint32 options;
+        CXmpError error;
+    } CXmpBatchResult;
+
     typedef struct CXmpIterator {
         #ifdef NOOP_FFI
             int x;
         #else
-            CXmpIterator(SXMPIterator i): i(i) {}
+            CXmpIterator(SXMPIterator i, AdobeXMPCommon::uint32 kinds): i(i), kinds(kinds) {}
             SXMPIterator i;
+
+            // Kinds of nodes to return (kIterKind... flags); 0 for all.
+            AdobeXMPCommon::uint32 kinds;
         #endif
     } CXmpIterator;
 
+    #ifndef NOOP_FFI
+        // Must match the values of `IterOptions::KIND_...` in xmp_iterator.rs.
+        const AdobeXMPCommon::uint32 kIterKindArrays = 0x1;
+        const AdobeXMPCommon::uint32 kIterKindStructs = 0x2;
+        const AdobeXMPCommon::uint32 kIterKindSimpleValues = 0x4;
+        const AdobeXMPCommon::uint32 kIterKindQualified = 0x8;
+
+        static bool isKindIncluded(XMP_OptionBits options, AdobeXMPCommon::uint32 kinds) {
+            if (kinds == 0) {
+                return true;
+            }
+
+            if ((kinds & kIterKindArrays) && (options & kXMP_PropValueIsArray)) {
+                return true;
+            }
+
+            if ((kinds & kIterKindStructs) && (options & kXMP_PropValueIsStruct)) {
+                return true;
+            }
+
+            if ((kinds & kIterKindSimpleValues) &&
+                !(options & (kXMP_PropCompositeMask | kXMP_SchemaNode))) {
+                return true;
+            }
+
+            return (kinds & kIterKindQualified) && (options & kXMP_PropHasQualifiers);
+        }
+    #endif
+
     const char* CXmpStringCopy(const char* str) {
         // This function should be used *only* to test FFI behavior.
         // It copies a Rust-originated string so that it can subsequently
@@ -171,6 +500,28 @@ extern "C" {
 
     // --- CXmpFile ---
 
+    int CXmpFileLoadPlugins(CXmpError* outError, const char* pluginFolder) {
+        // Plugins are loaded when XMPFiles is initialized, which can only
+        // happen once. Returns 0 if that has already happened.
+        bool initializedHere = false;
+
+        std::call_once(xmp_init_flag, [&]() {
+            xmp_plugin_folder = pluginFolder;
+            init_xmp_fn();
+            initializedHere = true;
+        });
+
+        if (!initializedHere) {
+            return 0;
+        }
+
+        if (!xmp_init_succeeded) {
+            signalXmpInitFailure(outError);
+        }
+
+        return 1;
+    }
+
     CXmpFile* CXmpFileNew(CXmpError* outError) {
         #ifndef NOOP_FFI
             if (!init_xmp()) {
@@ -200,15 +551,74 @@ extern "C" {
     void CXmpFileOpen(CXmpFile* f,
                       CXmpError* outError,
                       const char* filePath,
+                      AdobeXMPCommon::uint32 format,
                       AdobeXMPCommon::uint32 openFlags) {
         #ifndef NOOP_FFI
-            // TO DO: Bridge file format parameter.
-            // For my purposes at the moment,
-            // kXMP_UnknownFile always suffices.
             try {
                 f->err.reset();
-                if (!f->f.OpenFile(filePath, kXMP_UnknownFile, openFlags)) {
+                if (!f->f.OpenFile(filePath, format, openFlags)) {
+                    *outError = f->err;
+                    f->err.reset();
+                }
+            }
+            catch (XMP_Error& e) {
+                copyErrorForResult(e, outError);
+            }
+            catch (...) {
+                signalUnknownError(outError);
+            }
+        #endif
+    }
+
+    void CXmpFileOpenStream(CXmpFile* f,
+                            CXmpError* outError,
+                            void* stream,
+                            CXmpStreamReadProc readProc,
+                            CXmpStreamSeekProc seekProc,
+                            CXmpStreamWriteProc writeProc,
+                            CXmpStreamTruncateProc truncateProc,
+                            AdobeXMPCommon::uint32 format,
+                            AdobeXMPCommon::uint32 openFlags) {
+        #ifndef NOOP_FFI
+            std::unique_ptr<RustStreamIO> io(new RustStreamIO(stream, readProc, seekProc, writeProc, truncateProc));
+
+            try {
+                f->err.reset();
+                if (f->f.OpenFile(io.get(), format, openFlags)) {
+                    f->io = std::move(io);
+                } else {
+                    // Transfer ownership of the message to outError.
+                    *outError = f->err;
+                    f->err.debugMessage = NULL;
+                    f->err.reset();
+                }
+            }
+            catch (XMP_Error& e) {
+                copyErrorForResult(e, outError);
+            }
+            catch (...) {
+                signalUnknownError(outError);
+            }
+        #endif
+    }
+
+    void CXmpFileOpenBytes(CXmpFile* f,
+                           CXmpError* outError,
+                           const char* data,
+                           size_t len,
+                           AdobeXMPCommon::uint32 format,
+                           AdobeXMPCommon::uint32 openFlags) {
+        #ifndef NOOP_FFI
+            try {
+                std::unique_ptr<MemoryIO> io(new MemoryIO(data, len));
+
+                f->err.reset();
+                if (f->f.OpenFile(io.get(), format, openFlags)) {
+                    f->io = std::move(io);
+                } else {
+                    // Transfer ownership of the message to outError.
                     *outError = f->err;
+                    f->err.debugMessage = NULL;
                     f->err.reset();
                 }
             }
@@ -221,6 +631,185 @@ extern "C" {
         #endif
     }
 
+    const char* CXmpFileGetBytes(const CXmpFile* f,
+                                 size_t* outLen) {
+        *outLen = 0;
+
+        #ifndef NOOP_FFI
+            MemoryIO* io = dynamic_cast<MemoryIO*>(f->io.get());
+            if (io) {
+                const std::string& contents = io->Contents();
+                void* bytes = malloc(contents.size() + 1);
+                if (bytes) {
+                    memcpy(bytes, contents.data(), contents.size());
+                    *outLen = contents.size();
+                    return (const char*) bytes;
+                }
+            }
+        #endif
+
+        return NULL;
+    }
+
+    AdobeXMPCommon::uint32 CXmpFileCheckFormat(CXmpError* outError,
+                                               const char* filePath) {
+        #ifndef NOOP_FFI
+            if (!init_xmp()) {
+                signalXmpInitFailure(outError);
+                return kXMP_UnknownFile;
+            }
+
+            try {
+                return SXMPFiles::CheckFileFormat(filePath);
+            }
+            catch (XMP_Error& e) {
+                copyErrorForResult(e, outError);
+            }
+            catch (...) {
+                signalUnknownError(outError);
+            }
+        #endif
+
+        return kXMP_UnknownFile;
+    }
+
+    AdobeXMPCommon::uint32 CXmpFileCheckBufferFormat(CXmpError* outError,
+                                                     const char* data,
+                                                     size_t len) {
+        #ifndef NOOP_FFI
+            if (!init_xmp()) {
+                signalXmpInitFailure(outError);
+                return kXMP_UnknownFile;
+            }
+
+            try {
+                // There is no CheckFileFormat for XMP_IO, so ask for a
+                // smart handler without reading any legacy metadata.
+                MemoryIO io(data, len);
+                SXMPFiles f;
+
+                XMP_FileFormat format = kXMP_UnknownFile;
+                if (f.OpenFile(&io, kXMP_UnknownFile, kXMPFiles_OpenForRead | kXMPFiles_OpenOnlyXMP | kXMPFiles_OpenUseSmartHandler)) {
+                    f.GetFileInfo(0, 0, &format, 0);
+                    f.CloseFile();
+                }
+
+                return format;
+            }
+            catch (XMP_Error& e) {
+                copyErrorForResult(e, outError);
+            }
+            catch (...) {
+                signalUnknownError(outError);
+            }
+        #endif
+
+        return kXMP_UnknownFile;
+    }
+
+    int CXmpFileGetFormatInfo(CXmpError* outError,
+                              AdobeXMPCommon::uint32 format,
+                              AdobeXMPCommon::uint32* outHandlerFlags) {
+        *outHandlerFlags = 0;
+
+        #ifndef NOOP_FFI
+            if (!init_xmp()) {
+                signalXmpInitFailure(outError);
+                return 0;
+            }
+
+            try {
+                XMP_OptionBits flags = 0;
+                bool found = SXMPFiles::GetFormatInfo(format, &flags);
+                *outHandlerFlags = flags;
+                return found ? 1 : 0;
+            }
+            catch (XMP_Error& e) {
+                copyErrorForResult(e, outError);
+            }
+            catch (...) {
+                signalUnknownError(outError);
+            }
+        #endif
+
+        return 0;
+    }
+
+    int CXmpFileGetModDate(CXmpError* outError,
+                           const char* filePath,
+                           XMP_DateTime* outDate) {
+        #ifndef NOOP_FFI
+            if (!init_xmp()) {
+                signalXmpInitFailure(outError);
+                return 0;
+            }
+
+            try {
+                return SXMPFiles::GetFileModDate(filePath, outDate) ? 1 : 0;
+            }
+            catch (XMP_Error& e) {
+                copyErrorForResult(e, outError);
+            }
+            catch (...) {
+                signalUnknownError(outError);
+            }
+        #endif
+
+        return 0;
+    }
+
+    int CXmpFileIsMetadataWritable(CXmpError* outError,
+                                   const char* filePath) {
+        #ifndef NOOP_FFI
+            if (!init_xmp()) {
+                signalXmpInitFailure(outError);
+                return 0;
+            }
+
+            try {
+                bool writable = false;
+                if (SXMPFiles::IsMetadataWritable(filePath, &writable)) {
+                    return writable ? 1 : 0;
+                }
+            }
+            catch (XMP_Error& e) {
+                copyErrorForResult(e, outError);
+            }
+            catch (...) {
+                signalUnknownError(outError);
+            }
+        #endif
+
+        return 0;
+    }
+
+    void CXmpFileGetAssociatedResources(CXmpError* outError,
+                                        const char* filePath,
+                                        void* context,
+                                        CXmpStringListProc proc) {
+        #ifndef NOOP_FFI
+            if (!init_xmp()) {
+                signalXmpInitFailure(outError);
+                return;
+            }
+
+            try {
+                std::vector<std::string> resources;
+                if (SXMPFiles::GetAssociatedResources(filePath, &resources)) {
+                    for (const std::string& resource : resources) {
+                        (*proc)(context, resource.c_str());
+                    }
+                }
+            }
+            catch (XMP_Error& e) {
+                copyErrorForResult(e, outError);
+            }
+            catch (...) {
+                signalUnknownError(outError);
+            }
+        #endif
+    }
+
     void CXmpFileClose(CXmpFile* f,
                        CXmpError* outError) {
         #ifndef NOOP_FFI
@@ -239,6 +828,65 @@ extern "C" {
         #endif
     }
 
+    void CXmpFileSetProgressCallback(CXmpFile* f,
+                                     CXmpError* outError,
+                                     void* context,
+                                     XMP_ProgressReportProc proc,
+                                     float interval,
+                                     int sendStartStop) {
+        #ifndef NOOP_FFI
+            try {
+                f->f.SetProgressCallback(proc, context, interval, sendStartStop != 0);
+            }
+            catch (XMP_Error& e) {
+                copyErrorForResult(e, outError);
+            }
+            catch (...) {
+                signalUnknownError(outError);
+            }
+        #endif
+    }
+
+    void CXmpFileSetAbortProc(CXmpFile* f,
+                              CXmpError* outError,
+                              XMP_AbortProc proc,
+                              void* arg) {
+        #ifndef NOOP_FFI
+            try {
+                f->f.SetAbortProc(proc, arg);
+            }
+            catch (XMP_Error& e) {
+                copyErrorForResult(e, outError);
+            }
+            catch (...) {
+                signalUnknownError(outError);
+            }
+        #endif
+    }
+
+    void CXmpFileSetDefaultProgressCallback(CXmpError* outError,
+                                            void* context,
+                                            XMP_ProgressReportProc proc,
+                                            float interval,
+                                            int sendStartStop) {
+        #ifndef NOOP_FFI
+            if (!init_xmp()) {
+                signalXmpInitFailure(outError);
+                return;
+            }
+
+            try {
+                SXMPFiles::SetDefaultProgressCallback(proc, context, interval, sendStartStop != 0);
+            }
+            catch (XMP_Error& e) {
+                copyErrorForResult(e, outError);
+            }
+            catch (...) {
+                signalUnknownError(outError);
+            }
+        #endif
+    }
+
     CXmpMeta* CXmpFileGetXmp(CXmpFile* f) {
         #ifndef NOOP_FFI
             try {
@@ -262,6 +910,55 @@ extern "C" {
         return NULL;
     }
 
+    const char* CXmpFileGetXmpPacket(CXmpFile* f,
+                                     size_t* outLen) {
+        *outLen = 0;
+
+        #ifndef NOOP_FFI
+            try {
+                std::string packet;
+                if (f->f.GetXMP(0, &packet, 0)) {
+                    void* bytes = malloc(packet.size() + 1);
+                    if (bytes) {
+                        memcpy(bytes, packet.data(), packet.size());
+                        *outLen = packet.size();
+                        return (const char*) bytes;
+                    }
+                }
+            }
+            catch (...) {
+                // Intentional no-op.
+            }
+        #endif
+
+        // No metadata or exception occurred.
+        return NULL;
+    }
+
+    int CXmpFileGetPacketInfo(CXmpFile* f,
+                              CXmpPacketInfo* outInfo) {
+        #ifndef NOOP_FFI
+            try {
+                XMP_PacketInfo info;
+                if (f->f.GetXMP(0, 0, &info)) {
+                    outInfo->offset = info.offset;
+                    outInfo->length = info.length;
+                    outInfo->padSize = info.padSize;
+                    outInfo->charForm = info.charForm;
+                    outInfo->writeable = info.writeable ? 1 : 0;
+                    outInfo->hasWrapper = info.hasWrapper ? 1 : 0;
+                    return 1;
+                }
+            }
+            catch (...) {
+                // Intentional no-op.
+            }
+        #endif
+
+        // No metadata or exception occurred.
+        return 0;
+    }
+
     void CXmpFilePutXmp(CXmpFile* f,
                         CXmpError* outError,
                         const CXmpMeta* m) {
@@ -278,6 +975,25 @@ extern "C" {
         #endif
     }
 
+    int CXmpFileGetOpenFlags(const CXmpFile* f,
+                             AdobeXMPCommon::uint32* outOpenFlags) {
+        #ifndef NOOP_FFI
+            try {
+                XMP_OptionBits openFlags = 0;
+                if (f->f.GetFileInfo(0, &openFlags)) {
+                    *outOpenFlags = openFlags;
+                    return 1;
+                }
+            }
+            catch (...) {
+                // Intentional no-op.
+            }
+        #endif
+
+        // File not open or exception occurred.
+        return 0;
+    }
+
     int CXmpFileCanPutXmp(const CXmpFile* f,
                           const CXmpMeta* m) {
         #ifndef NOOP_FFI
@@ -320,6 +1036,27 @@ extern "C" {
         #endif
     }
 
+    CXmpMeta* CXmpMetaShare(CXmpMeta* m,
+                            CXmpError* outError) {
+        #ifndef NOOP_FFI
+            try {
+                // Assigning an SXMPMeta shares the underlying (reference
+                // counted) XMPMeta object rather than copying it.
+                CXmpMeta* result = new CXmpMeta;
+                result->m = m->m;
+                return result;
+            }
+            catch (XMP_Error& e) {
+                copyErrorForResult(e, outError);
+            }
+            catch (...) {
+                signalUnknownError(outError);
+            }
+        #endif
+
+        return NULL;
+    }
+
     CXmpMeta* CXmpMetaClone(CXmpMeta* m,
                             CXmpError* outError) {
         #ifndef NOOP_FFI
@@ -367,6 +1104,39 @@ extern "C" {
         return NULL;
     }
 
+    CXmpMeta* CXmpMetaParseFromBufferWithWarnings(CXmpError* outError,
+                                                  const char* buffer,
+                                                  AdobeXMPCommon::uint32 buffer_size,
+                                                  AdobeXMPCommon::uint32 options,
+                                                  void* warningContext,
+                                                  CXmpParseWarningProc warningProc) {
+        #ifndef NOOP_FFI
+            if (!init_xmp()) {
+                signalXmpInitFailure(outError);
+                return NULL;
+            }
+            CXmpMeta* result = new CXmpMeta;
+            CXmpParseWarningContext ctx = { warningContext, warningProc };
+
+            try {
+                result->m.SetErrorCallback(xmpMetaParseWarningCallback, &ctx, 0xffffffff);
+                result->m.ParseFromBuffer(buffer, buffer_size, options);
+                result->m.SetErrorCallback(NULL);
+                return result;
+            }
+            catch (XMP_Error& e) {
+                delete result;
+                copyErrorForResult(e, outError);
+            }
+            catch (...) {
+                delete result;
+                signalUnknownError(outError);
+            }
+        #endif
+
+        return NULL;
+    }
+
     const char* CXmpMetaSerializeToBuffer(CXmpMeta* m,
                                           CXmpError* outError,
                                           AdobeXMPCommon::uint32 options,
@@ -510,6 +1280,42 @@ extern "C" {
         return NULL;
     }
 
+    bool CXmpMetaGetPropertyInto(CXmpMeta* m,
+                                 CXmpError* outError,
+                                 const char* schemaNS,
+                                 const char* propName,
+                                 void* rustString,
+                                 SetClientStringProc callback,
+                                 AdobeXMPCommon::uint32* outOptions) {
+        *outOptions = 0;
+
+        #ifndef NOOP_FFI
+            try {
+                // Call the client glue directly so that the value is
+                // copied straight into the Rust string rather than into a
+                // temporary std::string first.
+                WXMP_Result wResult;
+                WXMPMeta_GetProperty_1(m->m.GetInternalRef(),
+                                       schemaNS,
+                                       propName,
+                                       rustString,
+                                       outOptions,
+                                       callback,
+                                       &wResult);
+                PropagateException(wResult);
+                return wResult.int32Result != 0;
+            }
+            catch (XMP_Error& e) {
+                copyErrorForResult(e, outError);
+            }
+            catch (...) {
+                signalUnknownError(outError);
+            }
+        #endif
+
+        return false;
+    }
+
     bool CXmpMetaGetProperty_Bool(CXmpMeta* m,
                                   CXmpError* outError,
                                   const char* schemaNS,
@@ -658,6 +1464,49 @@ extern "C" {
         #endif
     }
 
+    void CXmpMetaBatch(CXmpMeta* m,
+                       const CXmpBatchOp* ops,
+                       CXmpBatchResult* results,
+                       AdobeXMPCommon::uint32 count) {
+        // Each operation reports its own error in the corresponding result,
+        // so a failure does not prevent later operations from running.
+        #ifndef NOOP_FFI
+            for (AdobeXMPCommon::uint32 i = 0; i < count; i++) {
+                const CXmpBatchOp& op = ops[i];
+                CXmpBatchResult& result = results[i];
+
+                try {
+                    switch (op.kind) {
+                        case kCXmpBatch_GetProperty: {
+                            std::string propValue;
+                            if (m->m.GetProperty(op.schemaNS, op.propName, &propValue, &result.options)) {
+                                result.value = copyStringForResult(propValue);
+                            }
+                            break;
+                        }
+
+                        case kCXmpBatch_SetProperty:
+                            m->m.SetProperty(op.schemaNS, op.propName, op.propValue, op.options);
+                            break;
+
+                        case kCXmpBatch_DeleteProperty:
+                            m->m.DeleteProperty(op.schemaNS, op.propName);
+                            break;
+
+                        default:
+                            break;
+                    }
+                }
+                catch (XMP_Error& e) {
+                    copyErrorForResult(e, &result.error);
+                }
+                catch (...) {
+                    signalUnknownError(&result.error);
+                }
+            }
+        #endif
+    }
+
     void CXmpMetaSetProperty_Bool(CXmpMeta* m,
                                   CXmpError* outError,
                                   const char* schemaNS,
@@ -770,6 +1619,81 @@ extern "C" {
         #endif
     }
 
+    void CXmpMetaDuplicateSubtree(const CXmpMeta* source,
+                                  CXmpMeta* dest,
+                                  CXmpError* outError,
+                                  const char* sourceNS,
+                                  const char* sourceRoot,
+                                  const char* destNS,
+                                  const char* destRoot,
+                                  AdobeXMPCommon::uint32 options) {
+        #ifndef NOOP_FFI
+            try {
+                SXMPUtils::DuplicateSubtree(source->m,
+                                            &dest->m,
+                                            sourceNS,
+                                            sourceRoot,
+                                            destNS,
+                                            destRoot,
+                                            options);
+            }
+            catch (XMP_Error& e) {
+                copyErrorForResult(e, outError);
+            }
+            catch (...) {
+                signalUnknownError(outError);
+            }
+        #endif
+    }
+
+    const char* CXmpMetaPackageForJPEG(const CXmpMeta* m,
+                                       CXmpError* outError,
+                                       const char** outExtended,
+                                       const char** outDigest) {
+        *outExtended = NULL;
+        *outDigest = NULL;
+
+        #ifndef NOOP_FFI
+            try {
+                std::string standardXMP;
+                std::string extendedXMP;
+                std::string extendedDigest;
+                SXMPUtils::PackageForJPEG(m->m, &standardXMP, &extendedXMP, &extendedDigest);
+
+                if (!extendedXMP.empty()) {
+                    *outExtended = copyStringForResult(extendedXMP);
+                    *outDigest = copyStringForResult(extendedDigest);
+                }
+
+                return copyStringForResult(standardXMP);
+            }
+            catch (XMP_Error& e) {
+                copyErrorForResult(e, outError);
+            }
+            catch (...) {
+                signalUnknownError(outError);
+            }
+        #endif
+
+        return NULL;
+    }
+
+    void CXmpMetaMergeFromJPEG(CXmpMeta* full,
+                               const CXmpMeta* extended,
+                               CXmpError* outError) {
+        #ifndef NOOP_FFI
+            try {
+                SXMPUtils::MergeFromJPEG(&full->m, extended->m);
+            }
+            catch (XMP_Error& e) {
+                copyErrorForResult(e, outError);
+            }
+            catch (...) {
+                signalUnknownError(outError);
+            }
+        #endif
+    }
+
     void CXmpMetaSetArrayItem(CXmpMeta* m,
                               CXmpError* outError,
                               const char* schemaNS,
@@ -1069,6 +1993,42 @@ extern "C" {
         #endif
     }
 
+    bool CXmpMetaGetArrayItemInto(CXmpMeta* m,
+                                  CXmpError* outError,
+                                  const char* schemaNS,
+                                  const char* propName,
+                                  AdobeXMPCommon::uint32 index,
+                                  void* rustString,
+                                  SetClientStringProc callback,
+                                  AdobeXMPCommon::uint32* outOptions) {
+        *outOptions = 0;
+
+        #ifndef NOOP_FFI
+            try {
+                // See CXmpMetaGetPropertyInto.
+                WXMP_Result wResult;
+                WXMPMeta_GetArrayItem_1(m->m.GetInternalRef(),
+                                        schemaNS,
+                                        propName,
+                                        index,
+                                        rustString,
+                                        outOptions,
+                                        callback,
+                                        &wResult);
+                PropagateException(wResult);
+                return wResult.int32Result != 0;
+            }
+            catch (XMP_Error& e) {
+                copyErrorForResult(e, outError);
+            }
+            catch (...) {
+                signalUnknownError(outError);
+            }
+        #endif
+
+        return false;
+    }
+
     const char* CXmpMetaGetLocalizedText(CXmpMeta* m,
                                          CXmpError* outError,
                                          const char* schemaNS,
@@ -1356,11 +2316,12 @@ extern "C" {
                                   CXmpError* outError,
                                   const char* schemaNS,
                                   const char* propName,
-                                  AdobeXMPCommon::uint32 options) {
+                                  AdobeXMPCommon::uint32 options,
+                                  AdobeXMPCommon::uint32 kinds) {
         #ifndef NOOP_FFI
             try {
                 if (m) {
-                    return new CXmpIterator(SXMPIterator(m->m, schemaNS, propName, options));
+                    return new CXmpIterator(SXMPIterator(m->m, schemaNS, propName, options), kinds);
                 }
             }
             catch (XMP_Error& e) {
@@ -1392,7 +2353,14 @@ extern "C" {
                     std::string schemaNS;
                     std::string propPath;
                     std::string propValue;
-                    if (i->i.Next(&schemaNS, &propPath, &propValue, outOptions)) {
+
+                    // Nodes of other kinds are skipped here, so that their
+                    // strings aren't copied for Rust.
+                    while (i->i.Next(&schemaNS, &propPath, &propValue, outOptions)) {
+                        if (!isKindIncluded(*outOptions, i->kinds)) {
+                            continue;
+                        }
+
                         *outSchemaNS = copyStringForResult(schemaNS);
                         *outPropPath = copyStringForResult(propPath);
                         *outPropValue = copyStringForResult(propValue);
@@ -1534,4 +2502,25 @@ extern "C" {
 
         return NULL;
     }
+
+    void CXmpDateTimeFromString(const char* str, XMP_DateTime* dt, CXmpError* outError) {
+        #ifndef NOOP_FFI
+            if (!init_xmp()) {
+                signalXmpInitFailure(outError);
+                return;
+            }
+
+            try {
+                if (str && dt) {
+                    SXMPUtils::ConvertToDate(str, dt);
+                }
+            }
+            catch (XMP_Error& e) {
+                copyErrorForResult(e, outError);
+            }
+            catch (...) {
+                signalUnknownError(outError);
+            }
+        #endif
+    }
 }
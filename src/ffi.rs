@@ -12,11 +12,18 @@
 // each license.
 
 use std::{
+    any::Any,
     ffi::{CStr, CString},
-    os::raw::{c_char, c_int, c_void},
+    io,
+    os::raw::{c_char, c_int, c_ulong, c_void},
+    panic::{self, AssertUnwindSafe},
     slice,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
 };
 
+use crate::{ParseWarning, Progress, UpdatableStream, XmpErrorType};
+
 pub(crate) struct CXmpString {
     pub(crate) s: *const c_char,
 }
@@ -63,6 +70,318 @@ pub(crate) extern "C" fn xmp_dump_to_string(s: *mut c_void, buffer: *const u8, l
     0
 }
 
+type CXmpSetStringProc = extern "C" fn(s: *mut c_void, buffer: *const u8, len: u32);
+
+// Implementation of the C++ XMP Toolkit's `SetClientStringProc` that
+// replaces the contents of a Rust String with buffer, reusing its
+// allocation.
+pub(crate) extern "C" fn xmp_set_string(s: *mut c_void, buffer: *const u8, len: u32) {
+    unsafe {
+        let bytes = slice::from_raw_parts(buffer, len as usize);
+        let s = &mut *s.cast::<String>();
+        s.clear();
+        s.push_str(String::from_utf8_lossy(bytes).as_ref());
+    }
+}
+
+// Context for `xmp_dump_to_writer`. Records the first I/O error, if any.
+pub(crate) struct DumpWriter<'a> {
+    pub(crate) writer: &'a mut dyn io::Write,
+    pub(crate) error: Option<io::Error>,
+    pub(crate) panic: Option<Box<dyn Any + Send>>,
+}
+
+// Implementation of CXmpTextOutputProc that writes buffer to a DumpWriter.
+// A non-zero return value tells the C++ XMP Toolkit to stop dumping.
+//
+// A panic in the writer must not unwind into C++, so it is caught here and
+// kept in the DumpWriter for the caller to resume once the toolkit returns.
+pub(crate) extern "C" fn xmp_dump_to_writer(s: *mut c_void, buffer: *const u8, len: u32) -> i32 {
+    let (bytes, w) = unsafe {
+        (
+            slice::from_raw_parts(buffer, len as usize),
+            &mut *s.cast::<DumpWriter>(),
+        )
+    };
+
+    match panic::catch_unwind(AssertUnwindSafe(|| w.writer.write_all(bytes))) {
+        Ok(Ok(())) => 0,
+        Ok(Err(err)) => {
+            w.error = Some(err);
+            -1
+        }
+        Err(payload) => {
+            w.panic = Some(payload);
+            -1
+        }
+    }
+}
+
+type CXmpStringListProc = extern "C" fn(context: *mut c_void, s: *const c_char);
+
+// Implementation of CXmpStringListProc that appends each string to a Rust
+// Vec<String>.
+pub(crate) extern "C" fn xmp_collect_string(context: *mut c_void, s: *const c_char) {
+    unsafe {
+        let strings = &mut *context.cast::<Vec<String>>();
+        if !s.is_null() {
+            strings.push(CStr::from_ptr(s).to_string_lossy().into_owned());
+        }
+    }
+}
+
+type CXmpStreamReadProc = extern "C" fn(stream: *mut c_void, buffer: *mut u8, count: u32) -> i32;
+type CXmpStreamSeekProc = extern "C" fn(stream: *mut c_void, offset: i64, mode: u32) -> i64;
+type CXmpStreamWriteProc = extern "C" fn(stream: *mut c_void, buffer: *const u8, count: u32) -> i32;
+type CXmpStreamTruncateProc = extern "C" fn(stream: *mut c_void, length: i64) -> i32;
+
+// A stream that can be handed to the C++ XMP Toolkit in place of a file.
+pub(crate) trait StreamIo: Send {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize>;
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64>;
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()>;
+    fn set_len(&mut self, len: u64) -> io::Result<()>;
+    fn flush(&mut self) -> io::Result<()>;
+}
+
+// A stream passed to `XmpFile::open_stream`. The C++ XMP Toolkit is not
+// given the write procs for these, so the write operations are never used.
+pub(crate) struct ReadOnlyStream<R>(pub(crate) R);
+
+impl<R: io::Read + io::Seek + Send> StreamIo for ReadOnlyStream<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        self.0.seek(pos)
+    }
+
+    fn write_all(&mut self, _buf: &[u8]) -> io::Result<()> {
+        Err(io::ErrorKind::PermissionDenied.into())
+    }
+
+    fn set_len(&mut self, _len: u64) -> io::Result<()> {
+        Err(io::ErrorKind::PermissionDenied.into())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// A stream passed to `XmpFile::open_stream_for_update`.
+pub(crate) struct UpdatableStreamIo<S>(pub(crate) S);
+
+impl<S: UpdatableStream> StreamIo for UpdatableStreamIo<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        self.0.seek(pos)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.0.write_all(buf)
+    }
+
+    fn set_len(&mut self, len: u64) -> io::Result<()> {
+        self.0.set_len(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+// Reads a StreamIo through the standard I/O traits.
+pub(crate) struct StreamReader<'a>(pub(crate) &'a mut dyn StreamIo);
+
+impl io::Read for StreamReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl io::Seek for StreamReader<'_> {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        self.0.seek(pos)
+    }
+}
+
+// Implementation of CXmpStreamReadProc for a Rust Box<dyn StreamIo>.
+// Returns the number of bytes read, 0 at end of stream, or -1 on error.
+// A panic in the stream is reported as an error rather than unwinding into
+// C++.
+pub(crate) extern "C" fn xmp_stream_read(stream: *mut c_void, buffer: *mut u8, count: u32) -> i32 {
+    let stream = unsafe { &mut *stream.cast::<Box<dyn StreamIo>>() };
+    let count = count.min(i32::MAX as u32) as usize;
+    let buffer = unsafe { slice::from_raw_parts_mut(buffer, count) };
+
+    panic::catch_unwind(AssertUnwindSafe(|| loop {
+        match stream.read(buffer) {
+            Ok(n) => return n as i32,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(_) => return -1,
+        }
+    }))
+    .unwrap_or(-1)
+}
+
+// Implementation of CXmpStreamSeekProc for a Rust Box<dyn StreamIo>.
+// `mode` is one of the C++ SDK's `kXMP_SeekFrom*` constants. Returns the
+// new position or -1 on error.
+pub(crate) extern "C" fn xmp_stream_seek(stream: *mut c_void, offset: i64, mode: u32) -> i64 {
+    let stream = unsafe { &mut *stream.cast::<Box<dyn StreamIo>>() };
+
+    let from = match mode {
+        0 => match u64::try_from(offset) {
+            Ok(offset) => io::SeekFrom::Start(offset),
+            Err(_) => return -1,
+        },
+        1 => io::SeekFrom::Current(offset),
+        2 => io::SeekFrom::End(offset),
+        _ => return -1,
+    };
+
+    panic::catch_unwind(AssertUnwindSafe(|| stream.seek(from)))
+        .ok()
+        .and_then(Result::ok)
+        .and_then(|pos| i64::try_from(pos).ok())
+        .unwrap_or(-1)
+}
+
+// Implementation of CXmpStreamWriteProc for a Rust Box<dyn StreamIo>.
+// Returns 0 if all of `buffer` was written or -1 on error.
+pub(crate) extern "C" fn xmp_stream_write(
+    stream: *mut c_void,
+    buffer: *const u8,
+    count: u32,
+) -> i32 {
+    let stream = unsafe { &mut *stream.cast::<Box<dyn StreamIo>>() };
+    let buffer = unsafe { slice::from_raw_parts(buffer, count as usize) };
+
+    match panic::catch_unwind(AssertUnwindSafe(|| stream.write_all(buffer))) {
+        Ok(Ok(())) => 0,
+        _ => -1,
+    }
+}
+
+// Implementation of CXmpStreamTruncateProc for a Rust Box<dyn StreamIo>.
+// Returns 0 on success or -1 on error.
+pub(crate) extern "C" fn xmp_stream_truncate(stream: *mut c_void, length: i64) -> i32 {
+    let stream = unsafe { &mut *stream.cast::<Box<dyn StreamIo>>() };
+
+    let Ok(length) = u64::try_from(length) else {
+        return -1;
+    };
+
+    match panic::catch_unwind(AssertUnwindSafe(|| stream.set_len(length))) {
+        Ok(Ok(())) => 0,
+        _ => -1,
+    }
+}
+
+type CXmpParseWarningProc =
+    extern "C" fn(context: *mut c_void, severity: u32, cause: i32, message: *const c_char);
+
+// Implementation of CXmpParseWarningProc that appends a ParseWarning to a
+// Rust Vec<ParseWarning>.
+pub(crate) extern "C" fn xmp_collect_parse_warning(
+    context: *mut c_void,
+    _severity: u32,
+    cause: i32,
+    message: *const c_char,
+) {
+    unsafe {
+        let warnings = &mut *context.cast::<Vec<ParseWarning>>();
+        warnings.push(ParseWarning {
+            error_type: XmpErrorType::from(cause),
+            message: if message.is_null() {
+                String::default()
+            } else {
+                CStr::from_ptr(message).to_string_lossy().into_owned()
+            },
+        });
+    }
+}
+
+pub(crate) type CXmpProgressProc = extern "C" fn(
+    context: *mut c_void,
+    elapsed_time: f32,
+    fraction_done: f32,
+    seconds_to_go: f32,
+) -> bool;
+
+// A progress callback attached to a single file.
+pub(crate) type FileProgressFn = dyn FnMut(&Progress) -> bool + Send;
+
+// A progress callback shared by every file opened after it is installed,
+// possibly on several threads at once.
+pub(crate) type DefaultProgressFn = dyn Fn(&Progress) -> bool + Send + Sync;
+
+// Implementation of CXmpProgressProc for a Rust Box<FileProgressFn>.
+// Returning false asks the C++ toolkit to abort the operation. A panic in
+// the callback is treated the same way rather than unwinding into C++.
+pub(crate) extern "C" fn xmp_file_progress(
+    context: *mut c_void,
+    elapsed_time: f32,
+    fraction_done: f32,
+    seconds_to_go: f32,
+) -> bool {
+    let callback = unsafe { &mut *context.cast::<Box<FileProgressFn>>() };
+    let progress = progress_from_c(elapsed_time, fraction_done, seconds_to_go);
+
+    panic::catch_unwind(AssertUnwindSafe(|| callback(&progress))).unwrap_or(false)
+}
+
+// Implementation of CXmpProgressProc for a Rust Box<DefaultProgressFn>.
+pub(crate) extern "C" fn xmp_default_progress(
+    context: *mut c_void,
+    elapsed_time: f32,
+    fraction_done: f32,
+    seconds_to_go: f32,
+) -> bool {
+    let callback = unsafe { &*context.cast::<Box<DefaultProgressFn>>() };
+    let progress = progress_from_c(elapsed_time, fraction_done, seconds_to_go);
+
+    panic::catch_unwind(AssertUnwindSafe(|| callback(&progress))).unwrap_or(false)
+}
+
+// The C++ toolkit reports zero (or, in some handlers, a negative value)
+// for quantities it can't estimate.
+fn progress_from_c(elapsed_time: f32, fraction_done: f32, seconds_to_go: f32) -> Progress {
+    Progress {
+        elapsed: Duration::try_from_secs_f32(elapsed_time).unwrap_or_default(),
+        fraction_done: if fraction_done.is_finite() {
+            fraction_done.clamp(0.0, 1.0)
+        } else {
+            0.0
+        },
+        remaining: Duration::try_from_secs_f32(seconds_to_go).unwrap_or_default(),
+    }
+}
+
+pub(crate) type CXmpAbortProc = extern "C" fn(arg: *mut c_void) -> bool;
+
+// Implementation of CXmpAbortProc for the flag inside a CancellationToken.
+pub(crate) extern "C" fn xmp_file_abort(arg: *mut c_void) -> bool {
+    let cancelled = unsafe { &*arg.cast::<AtomicBool>() };
+    cancelled.load(Ordering::Acquire)
+}
+
+#[derive(Default)]
+#[repr(C)]
+pub(crate) struct CXmpPacketInfo {
+    pub(crate) offset: i64,
+    pub(crate) length: i32,
+    pub(crate) pad_size: i32,
+    pub(crate) char_form: u32,
+    pub(crate) writeable: u32,
+    pub(crate) has_wrapper: u32,
+}
+
 #[repr(C)]
 pub(crate) struct CXmpError {
     pub(crate) had_error: u32,
@@ -110,6 +429,39 @@ impl Drop for CXmpError {
     }
 }
 
+// Operation kinds for CXmpMetaBatch.
+// Must be kept in sync with the enum in ffi.cpp.
+pub(crate) const BATCH_GET_PROPERTY: u32 = 0;
+pub(crate) const BATCH_SET_PROPERTY: u32 = 1;
+pub(crate) const BATCH_DELETE_PROPERTY: u32 = 2;
+pub(crate) const BATCH_SKIP: u32 = 3;
+
+#[repr(C)]
+pub(crate) struct CXmpBatchOp {
+    pub(crate) kind: u32,
+    pub(crate) schema_ns: *const c_char,
+    pub(crate) prop_name: *const c_char,
+    pub(crate) prop_value: *const c_char,
+    pub(crate) options: u32,
+}
+
+#[repr(C)]
+pub(crate) struct CXmpBatchResult {
+    pub(crate) value: *const c_char,
+    pub(crate) options: u32,
+    pub(crate) error: CXmpError,
+}
+
+impl Default for CXmpBatchResult {
+    fn default() -> Self {
+        Self {
+            value: std::ptr::null(),
+            options: 0,
+            error: CXmpError::default(),
+        }
+    }
+}
+
 #[derive(Debug, Default, Eq, PartialEq)]
 #[repr(C)]
 pub(crate) struct CXmpDateTime {
@@ -138,6 +490,11 @@ extern "C" {
 
     // --- CXmpFile ---
 
+    pub(crate) fn CXmpFileLoadPlugins(
+        out_error: *mut CXmpError,
+        plugin_folder: *const c_char,
+    ) -> c_int;
+
     pub(crate) fn CXmpFileNew(out_error: *mut CXmpError) -> *mut CXmpFile;
     pub(crate) fn CXmpFileDrop(file: *mut CXmpFile);
 
@@ -145,18 +502,107 @@ extern "C" {
         file: *mut CXmpFile,
         out_error: *mut CXmpError,
         path: *const c_char,
+        format: u32,
+        flags: u32,
+    );
+
+    pub(crate) fn CXmpFileOpenStream(
+        file: *mut CXmpFile,
+        out_error: *mut CXmpError,
+        stream: *mut c_void,
+        read_proc: CXmpStreamReadProc,
+        seek_proc: CXmpStreamSeekProc,
+        write_proc: Option<CXmpStreamWriteProc>,
+        truncate_proc: Option<CXmpStreamTruncateProc>,
+        format: u32,
+        flags: u32,
+    );
+
+    pub(crate) fn CXmpFileOpenBytes(
+        file: *mut CXmpFile,
+        out_error: *mut CXmpError,
+        data: *const u8,
+        len: usize,
+        format: u32,
         flags: u32,
     );
 
+    pub(crate) fn CXmpFileGetBytes(file: *const CXmpFile, out_len: *mut usize) -> *const u8;
+
+    pub(crate) fn CXmpFileSetProgressCallback(
+        file: *mut CXmpFile,
+        out_error: *mut CXmpError,
+        context: *mut c_void,
+        proc_: Option<CXmpProgressProc>,
+        interval: f32,
+        send_start_stop: c_int,
+    );
+
+    pub(crate) fn CXmpFileSetAbortProc(
+        file: *mut CXmpFile,
+        out_error: *mut CXmpError,
+        proc_: Option<CXmpAbortProc>,
+        arg: *mut c_void,
+    );
+
+    pub(crate) fn CXmpFileSetDefaultProgressCallback(
+        out_error: *mut CXmpError,
+        context: *mut c_void,
+        proc_: Option<CXmpProgressProc>,
+        interval: f32,
+        send_start_stop: c_int,
+    );
+
+    pub(crate) fn CXmpFileCheckFormat(out_error: *mut CXmpError, path: *const c_char) -> u32;
+
+    pub(crate) fn CXmpFileCheckBufferFormat(
+        out_error: *mut CXmpError,
+        data: *const u8,
+        len: usize,
+    ) -> u32;
+
+    pub(crate) fn CXmpFileGetFormatInfo(
+        out_error: *mut CXmpError,
+        format: u32,
+        out_handler_flags: *mut u32,
+    ) -> c_int;
+
+    pub(crate) fn CXmpFileGetModDate(
+        out_error: *mut CXmpError,
+        path: *const c_char,
+        out_date: *mut CXmpDateTime,
+    ) -> c_int;
+
+    pub(crate) fn CXmpFileIsMetadataWritable(
+        out_error: *mut CXmpError,
+        path: *const c_char,
+    ) -> c_int;
+
+    pub(crate) fn CXmpFileGetAssociatedResources(
+        out_error: *mut CXmpError,
+        path: *const c_char,
+        context: *mut c_void,
+        proc_: CXmpStringListProc,
+    );
+
     pub(crate) fn CXmpFileClose(file: *mut CXmpFile, out_error: *mut CXmpError);
     pub(crate) fn CXmpFileGetXmp(file: *mut CXmpFile) -> *mut CXmpMeta;
 
+    pub(crate) fn CXmpFileGetXmpPacket(file: *mut CXmpFile, out_len: *mut usize) -> *const u8;
+
+    pub(crate) fn CXmpFileGetPacketInfo(
+        file: *mut CXmpFile,
+        out_info: *mut CXmpPacketInfo,
+    ) -> c_int;
+
     pub(crate) fn CXmpFilePutXmp(
         file: *mut CXmpFile,
         out_error: *mut CXmpError,
         meta: *const CXmpMeta,
     );
 
+    pub(crate) fn CXmpFileGetOpenFlags(file: *const CXmpFile, out_open_flags: *mut u32) -> c_int;
+
     pub(crate) fn CXmpFileCanPutXmp(file: *const CXmpFile, meta: *const CXmpMeta) -> c_int;
 
     // --- CXmpMeta ---
@@ -166,6 +612,8 @@ extern "C" {
 
     pub(crate) fn CXmpMetaClone(m: *const CXmpMeta, out_error: *mut CXmpError) -> *mut CXmpMeta;
 
+    pub(crate) fn CXmpMetaShare(m: *const CXmpMeta, out_error: *mut CXmpError) -> *mut CXmpMeta;
+
     pub(crate) fn CXmpMetaParseFromBuffer(
         out_error: *mut CXmpError,
         buffer: *const u8,
@@ -173,6 +621,15 @@ extern "C" {
         options: u32,
     ) -> *mut CXmpMeta;
 
+    pub(crate) fn CXmpMetaParseFromBufferWithWarnings(
+        out_error: *mut CXmpError,
+        buffer: *const u8,
+        buffer_size: u32,
+        options: u32,
+        warning_context: *mut c_void,
+        warning_proc: CXmpParseWarningProc,
+    ) -> *mut CXmpMeta;
+
     pub(crate) fn CXmpMetaSerializeToBuffer(
         meta: *const CXmpMeta,
         out_error: *mut CXmpError,
@@ -209,6 +666,16 @@ extern "C" {
         out_options: *mut u32,
     ) -> *const c_char;
 
+    pub(crate) fn CXmpMetaGetPropertyInto(
+        meta: *mut CXmpMeta,
+        out_error: *mut CXmpError,
+        schema_ns: *const c_char,
+        prop_name: *const c_char,
+        out_string: *mut c_void,
+        callback: CXmpSetStringProc,
+        out_options: *mut u32,
+    ) -> bool;
+
     pub(crate) fn CXmpMetaGetProperty_Bool(
         meta: *mut CXmpMeta,
         out_error: *mut CXmpError,
@@ -273,6 +740,13 @@ extern "C" {
         options: u32,
     );
 
+    pub(crate) fn CXmpMetaBatch(
+        meta: *mut CXmpMeta,
+        ops: *const CXmpBatchOp,
+        results: *mut CXmpBatchResult,
+        count: u32,
+    );
+
     pub(crate) fn CXmpMetaSetProperty_Bool(
         meta: *mut CXmpMeta,
         out_error: *mut CXmpError,
@@ -325,6 +799,30 @@ extern "C" {
         prop_name: *const c_char,
     );
 
+    pub(crate) fn CXmpMetaDuplicateSubtree(
+        source: *const CXmpMeta,
+        dest: *mut CXmpMeta,
+        out_error: *mut CXmpError,
+        source_ns: *const c_char,
+        source_root: *const c_char,
+        dest_ns: *const c_char,
+        dest_root: *const c_char,
+        options: u32,
+    );
+
+    pub(crate) fn CXmpMetaPackageForJPEG(
+        meta: *const CXmpMeta,
+        out_error: *mut CXmpError,
+        out_extended: *mut *const c_char,
+        out_digest: *mut *const c_char,
+    ) -> *const c_char;
+
+    pub(crate) fn CXmpMetaMergeFromJPEG(
+        full: *mut CXmpMeta,
+        extended: *const CXmpMeta,
+        out_error: *mut CXmpError,
+    );
+
     pub(crate) fn CXmpMetaSetArrayItem(
         meta: *mut CXmpMeta,
         out_error: *mut CXmpError,
@@ -442,6 +940,17 @@ extern "C" {
         out_options: *mut u32,
     ) -> *const c_char;
 
+    pub(crate) fn CXmpMetaGetArrayItemInto(
+        meta: *mut CXmpMeta,
+        out_error: *mut CXmpError,
+        schema_ns: *const c_char,
+        prop_name: *const c_char,
+        index: i32,
+        out_string: *mut c_void,
+        callback: CXmpSetStringProc,
+        out_options: *mut u32,
+    ) -> bool;
+
     pub(crate) fn CXmpMetaGetLocalizedText(
         meta: *mut CXmpMeta,
         out_error: *mut CXmpError,
@@ -530,6 +1039,7 @@ extern "C" {
         schema_ns: *const c_char,
         prop_name: *const c_char,
         options: u32,
+        kinds: u32,
     ) -> *mut CXmpIterator;
 
     pub(crate) fn CXmpIteratorDrop(i: *mut CXmpIterator);
@@ -557,4 +1067,19 @@ extern "C" {
         dt: *const CXmpDateTime,
         out_error: *mut CXmpError,
     ) -> *const c_char;
+
+    pub(crate) fn CXmpDateTimeFromString(
+        s: *const c_char,
+        dt: *mut CXmpDateTime,
+        out_error: *mut CXmpError,
+    );
+
+    // --- zlib, which is built along with the C++ XMP Toolkit ---
+
+    pub(crate) fn uncompress(
+        dest: *mut u8,
+        dest_len: *mut c_ulong,
+        source: *const u8,
+        source_len: c_ulong,
+    ) -> c_int;
 }
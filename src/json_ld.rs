@@ -0,0 +1,377 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Maps JSON-LD onto the XMP data model.
+//!
+//! Only the subset of JSON-LD that corresponds to XMP's data model is
+//! supported: a single node (or a `@graph` of nodes, which are merged)
+//! whose properties are literals, URI references, nested nodes (XMP
+//! structs), `@list` and `@set` values (ordered and unordered arrays), and
+//! language-tagged values (alternative-language text). Contexts must be
+//! given inline; remote contexts are not fetched.
+
+use std::collections::HashMap;
+
+use serde_json::{Map, Value};
+
+use crate::{XmpError, XmpErrorType, XmpMeta, XmpResult, XmpValue};
+
+/// Parses a JSON-LD document into a new `XmpMeta` struct.
+pub(crate) fn parse(s: &str) -> XmpResult<XmpMeta> {
+    let doc: Value = serde_json::from_str(s).map_err(|e| bad_rdf(format!("Invalid JSON: {e}")))?;
+
+    let mut meta = XmpMeta::new()?;
+    let context = Context::default();
+
+    match &doc {
+        Value::Array(nodes) => {
+            for node in nodes {
+                write_node(&mut meta, &context, node)?;
+            }
+        }
+        node => write_node(&mut meta, &context, node)?,
+    }
+
+    Ok(meta)
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Container {
+    None,
+    List,
+    Set,
+    Language,
+}
+
+#[derive(Clone, Debug)]
+struct Term {
+    iri: String,
+    container: Container,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Context {
+    terms: HashMap<String, Term>,
+    vocab: Option<String>,
+}
+
+impl Context {
+    /// Returns a copy of this context extended by the `@context` entry (if
+    /// any) of `node`.
+    fn extend(&self, node: &Map<String, Value>) -> XmpResult<Self> {
+        let mut result = self.clone();
+
+        match node.get("@context") {
+            None | Some(Value::Null) => {}
+            Some(Value::Array(contexts)) => {
+                for context in contexts {
+                    result.add(context)?;
+                }
+            }
+            Some(context) => result.add(context)?,
+        }
+
+        Ok(result)
+    }
+
+    fn add(&mut self, context: &Value) -> XmpResult<()> {
+        let definitions = match context {
+            Value::Object(definitions) => definitions,
+            Value::String(url) => {
+                return Err(bad_rdf(format!(
+                    "Remote JSON-LD contexts are not supported: {url}"
+                )));
+            }
+            _ => return Err(bad_rdf("Invalid JSON-LD context".to_owned())),
+        };
+
+        for (key, definition) in definitions {
+            match (key.as_str(), definition) {
+                ("@vocab", Value::String(vocab)) => {
+                    self.vocab = Some(vocab.clone());
+                }
+                (key, _) if key.starts_with('@') => {}
+                (key, Value::String(iri)) => {
+                    self.terms.insert(
+                        key.to_owned(),
+                        Term {
+                            iri: iri.clone(),
+                            container: Container::None,
+                        },
+                    );
+                }
+                (key, Value::Object(definition)) => {
+                    let iri = match definition.get("@id") {
+                        Some(Value::String(iri)) => iri.clone(),
+                        _ => match &self.vocab {
+                            Some(vocab) => format!("{vocab}{key}"),
+                            None => key.to_owned(),
+                        },
+                    };
+
+                    let container = match definition.get("@container").and_then(Value::as_str) {
+                        Some("@list") => Container::List,
+                        Some("@set") => Container::Set,
+                        Some("@language") => Container::Language,
+                        _ => Container::None,
+                    };
+
+                    self.terms.insert(key.to_owned(), Term { iri, container });
+                }
+                (key, Value::Null) => {
+                    self.terms.remove(key);
+                }
+                (key, _) => {
+                    return Err(bad_rdf(format!("Invalid definition for term {key:?}")));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Expands a compact IRI (`prefix:name`) if `prefix` is a known term
+    /// or a namespace prefix registered with the XMP Toolkit. Returns
+    /// `None` if `iri` is neither a compact IRI nor an absolute IRI.
+    fn expand(&self, iri: &str) -> Option<String> {
+        let (prefix, name) = iri.split_once(':')?;
+
+        if name.starts_with("//") {
+            Some(iri.to_owned())
+        } else if let Some(term) = self.terms.get(prefix) {
+            Some(format!("{}{name}", term.iri))
+        } else {
+            XmpMeta::namespace_uri(prefix).map(|ns| format!("{ns}{name}"))
+        }
+    }
+
+    /// Resolves a property key to its XMP namespace URI and local name,
+    /// registering a prefix for the namespace if necessary.
+    fn resolve(&self, key: &str) -> XmpResult<(String, String, Container)> {
+        let (iri, container) = if let Some(term) = self.terms.get(key) {
+            (
+                self.expand(&term.iri).unwrap_or_else(|| term.iri.clone()),
+                term.container,
+            )
+        } else if let Some(iri) = self.expand(key) {
+            (iri, Container::None)
+        } else if let (Some(vocab), false) = (&self.vocab, key.contains(':')) {
+            (format!("{vocab}{key}"), Container::None)
+        } else {
+            return Err(unknown_term(key));
+        };
+
+        let split = iri
+            .rfind(['/', '#', ':'])
+            .map(|i| i + 1)
+            .filter(|i| *i < iri.len())
+            .ok_or_else(|| unknown_term(key))?;
+
+        let (ns, name) = iri.split_at(split);
+
+        if XmpMeta::namespace_prefix(ns).is_none() {
+            let suggested_prefix = self
+                .terms
+                .iter()
+                .find(|(_, term)| term.iri == ns)
+                .map_or("ns", |(prefix, _)| prefix.as_str());
+
+            XmpMeta::register_namespace(ns, suggested_prefix)?;
+        }
+
+        Ok((ns.to_owned(), name.to_owned(), container))
+    }
+}
+
+fn write_node(meta: &mut XmpMeta, context: &Context, node: &Value) -> XmpResult<()> {
+    let Value::Object(node) = node else {
+        return Err(bad_rdf("Expected a JSON-LD node object".to_owned()));
+    };
+
+    let context = context.extend(node)?;
+
+    if let Some(graph) = node.get("@graph") {
+        let nodes = match graph {
+            Value::Array(nodes) => nodes.iter().collect(),
+            node => vec![node],
+        };
+
+        for node in nodes {
+            write_node(meta, &context, node)?;
+        }
+    }
+
+    for (key, value) in node {
+        if key.starts_with('@') {
+            continue;
+        }
+
+        let (ns, name, container) = context.resolve(key)?;
+        let prefix = XmpMeta::namespace_prefix(&ns).unwrap_or_default();
+        let path = format!("{prefix}{name}");
+
+        write_value(meta, &context, &ns, &path, value, container)?;
+    }
+
+    Ok(())
+}
+
+fn write_value(
+    meta: &mut XmpMeta,
+    context: &Context,
+    ns: &str,
+    path: &str,
+    value: &Value,
+    container: Container,
+) -> XmpResult<()> {
+    match value {
+        Value::Null => Ok(()),
+
+        Value::Bool(_) | Value::Number(_) | Value::String(_) => {
+            meta.set_property(ns, path, &XmpValue::new(literal(value)?))
+        }
+
+        Value::Array(items) => {
+            if !items.is_empty() && items.iter().all(is_language_tagged) {
+                for item in items {
+                    write_language_tagged(meta, ns, path, item)?;
+                }
+                Ok(())
+            } else {
+                write_array(meta, context, ns, path, items, container == Container::List)
+            }
+        }
+
+        Value::Object(obj) if container == Container::Language => {
+            for (lang, text) in obj {
+                if let Some(text) = text.as_str() {
+                    meta.set_localized_text(ns, path, None, lang, text)?;
+                }
+            }
+            Ok(())
+        }
+
+        Value::Object(obj) => {
+            if let Some(list) = obj.get("@list") {
+                write_array(meta, context, ns, path, as_slice(list), true)
+            } else if let Some(set) = obj.get("@set") {
+                write_array(meta, context, ns, path, as_slice(set), false)
+            } else if obj.contains_key("@value") {
+                if is_language_tagged(value) {
+                    write_language_tagged(meta, ns, path, value)
+                } else {
+                    meta.set_property(ns, path, &XmpValue::new(literal(&obj["@value"])?))
+                }
+            } else if let (Some(Value::String(id)), 1) = (obj.get("@id"), obj.len()) {
+                meta.set_property(ns, path, &XmpValue::new(id.clone()).set_is_uri(true))
+            } else {
+                write_struct(meta, context, ns, path, obj)
+            }
+        }
+    }
+}
+
+fn write_array(
+    meta: &mut XmpMeta,
+    context: &Context,
+    ns: &str,
+    path: &str,
+    items: &[Value],
+    ordered: bool,
+) -> XmpResult<()> {
+    meta.set_property(
+        ns,
+        path,
+        &XmpValue::default()
+            .set_is_array(true)
+            .set_is_ordered(ordered),
+    )?;
+
+    for (index, item) in items.iter().filter(|item| !item.is_null()).enumerate() {
+        let item_path = XmpMeta::compose_array_item_path(ns, path, index as i32 + 1)?;
+        write_value(meta, context, ns, &item_path, item, Container::None)?;
+    }
+
+    Ok(())
+}
+
+fn write_struct(
+    meta: &mut XmpMeta,
+    context: &Context,
+    ns: &str,
+    path: &str,
+    node: &Map<String, Value>,
+) -> XmpResult<()> {
+    let context = context.extend(node)?;
+
+    meta.set_property(ns, path, &XmpValue::default().set_is_struct(true))?;
+
+    for (key, value) in node {
+        if key.starts_with('@') {
+            continue;
+        }
+
+        let (field_ns, field_name, container) = context.resolve(key)?;
+        let field_path = XmpMeta::compose_struct_field_path(ns, path, &field_ns, &field_name)?;
+
+        write_value(meta, &context, ns, &field_path, value, container)?;
+    }
+
+    Ok(())
+}
+
+fn write_language_tagged(meta: &mut XmpMeta, ns: &str, path: &str, value: &Value) -> XmpResult<()> {
+    let (Some(lang), Some(text)) = (
+        value.get("@language").and_then(Value::as_str),
+        value.get("@value").and_then(Value::as_str),
+    ) else {
+        return Err(bad_rdf("Invalid language-tagged value".to_owned()));
+    };
+
+    meta.set_localized_text(ns, path, None, lang, text)
+}
+
+fn is_language_tagged(value: &Value) -> bool {
+    value.get("@language").is_some() && value.get("@value").is_some()
+}
+
+fn as_slice(value: &Value) -> &[Value] {
+    match value {
+        Value::Array(items) => items,
+        value => std::slice::from_ref(value),
+    }
+}
+
+fn literal(value: &Value) -> XmpResult<String> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Bool(true) => Ok("True".to_owned()),
+        Value::Bool(false) => Ok("False".to_owned()),
+        Value::Number(n) => Ok(n.to_string()),
+        _ => Err(bad_rdf("Expected a literal value".to_owned())),
+    }
+}
+
+fn unknown_term(key: &str) -> XmpError {
+    XmpError {
+        error_type: XmpErrorType::BadSchema,
+        debug_message: format!("Unable to map JSON-LD term {key:?} to a namespace"),
+    }
+}
+
+fn bad_rdf(debug_message: String) -> XmpError {
+    XmpError {
+        error_type: XmpErrorType::BadRdf,
+        debug_message,
+    }
+}
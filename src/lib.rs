@@ -18,23 +18,113 @@
 #![deny(warnings)]
 #![doc = include_str!("../README.md")]
 
+// Allows code generated by `xmp_toolkit_derive` to refer to this crate
+// by name, even from within this crate.
+extern crate self as xmp_toolkit;
+
+pub mod conformance;
 mod ffi;
+#[cfg(feature = "json_ld")]
+mod json_ld;
+mod rdf_canonical;
+mod rdf_scan;
+mod rdf_triples;
+mod xmp_access_stats;
+mod xmp_alias;
+#[cfg(feature = "arbitrary")]
+mod xmp_arbitrary;
+#[cfg(feature = "audit")]
+pub mod xmp_audit;
+mod xmp_batch;
+mod xmp_bmff;
+mod xmp_change;
+mod xmp_compound;
+mod xmp_cr3;
+pub mod xmp_crs;
 mod xmp_date_time;
+mod xmp_diff;
+pub mod xmp_dm;
+mod xmp_edit;
 mod xmp_error;
 mod xmp_file;
+#[cfg(feature = "async")]
+mod xmp_file_async;
+mod xmp_file_batch;
+mod xmp_file_format;
+mod xmp_file_handler;
+mod xmp_file_lock;
+mod xmp_flac;
+pub mod xmp_gpano;
 pub mod xmp_gps;
+pub mod xmp_id;
 mod xmp_iterator;
+mod xmp_jpeg;
+mod xmp_jxl;
+mod xmp_keywords;
+pub mod xmp_lang;
+pub mod xmp_lineage;
+mod xmp_merge;
 mod xmp_meta;
+mod xmp_meta_builder;
+pub mod xmp_mm;
+mod xmp_mp3;
+mod xmp_mpeg4;
+pub mod xmp_mwg;
+mod xmp_mxf;
 pub mod xmp_ns;
+mod xmp_ogg;
+mod xmp_ordering;
+pub mod xmp_packet;
+mod xmp_parse_warning;
+mod xmp_property_type;
+mod xmp_psd;
+mod xmp_redact;
+pub mod xmp_sidecar;
+mod xmp_struct;
+pub mod xmp_tiff;
 mod xmp_value;
+mod xmp_vorbis_comment;
+mod xmp_wav;
+mod xmp_webp;
 
+pub use xmp_access_stats::{AccessCount, AccessStats};
+pub use xmp_alias::{AliasForm, XmpAlias};
+pub use xmp_batch::XmpBatch;
+pub use xmp_change::{ChangeEvent, ChangeKind};
+pub use xmp_compound::{EmbeddedXmp, XmpContainer};
 #[cfg(feature = "chrono")]
 pub use xmp_date_time::DateTimeConvertError;
 pub use xmp_date_time::{XmpDate, XmpDateTime, XmpTime, XmpTimeZone};
-pub use xmp_error::{XmpError, XmpErrorType, XmpResult};
-pub use xmp_file::{OpenFileOptions, XmpFile};
+pub use xmp_diff::{DiffKind, PropertyDiff, XmpDiff};
+pub use xmp_edit::EditGuard;
+pub use xmp_error::{XmpError, XmpErrorCategory, XmpErrorType, XmpResult};
+pub use xmp_file::{
+    CancellationToken, CloseReport, FileLocking, FormatCapabilities, OpenFileOptions,
+    PacketEncoding, PacketInfo, PacketPadding, Progress, PutXmpBlocker, SharingRetry,
+    UpdatableStream, XmpFile, XmpWithPacket,
+};
+#[cfg(feature = "async")]
+pub use xmp_file_async::XmpFileAsync;
+pub use xmp_file_batch::{FileBatchEntry, FileBatchOutcome, FileBatchReport, XmpFileBatch};
+pub use xmp_file_format::FileFormat;
+pub use xmp_file_handler::XmpFileHandler;
 pub use xmp_iterator::{IterOptions, XmpIterator, XmpProperty};
-pub use xmp_meta::{ArrayProperty, FromStrOptions, ItemPlacement, ToStringOptions, XmpMeta};
+pub use xmp_jpeg::JpegXmpPackets;
+pub use xmp_keywords::{KeywordNode, KeywordTree};
+pub use xmp_merge::{MergeConflict, MergePolicy, MergeResult, MergeSide};
+pub use xmp_meta::{
+    ArrayForm, ArrayProperty, FromStrOptions, ItemPlacement, ToStringOptions, XmpMeta,
+    XmpMetaReadHandle, XmpMetaSnapshot,
+};
+pub use xmp_meta_builder::XmpMetaBuilder;
+pub use xmp_ordering::{NamespaceOrder, OutputOrdering};
+pub use xmp_parse_warning::ParseWarning;
+pub use xmp_property_type::XmpPropertyType;
+pub use xmp_psd::{PsdImageResource, PsdLayer};
+pub use xmp_redact::{RedactedProperty, RedactionProfile, RedactionReport};
+pub use xmp_struct::XmpStruct;
+#[cfg(feature = "derive")]
+pub use xmp_toolkit_derive::XmpStruct;
 pub use xmp_value::XmpValue;
 
 #[cfg(test)]
@@ -0,0 +1,230 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Implements [`ToStringOptions::canonical_for_hashing`].
+//!
+//! The C++ XMP Toolkit writes namespace prefixes as registered in the
+//! current process and identifies its own version in the `x:xmptk`
+//! attribute. Both are rewritten here so the output depends only on the
+//! data model.
+
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{xmp_ns, ToStringOptions, XmpMeta, XmpResult};
+
+/// The value written in the `x:xmptk` attribute of canonical output.
+///
+/// Change the trailing number if the canonical form ever changes.
+const CANONICAL_XMPTK: &str = "xmp_toolkit canonical 1";
+
+/// Prefixes used in canonical output for well-known namespaces. All other
+/// namespaces are written as `ns1`, `ns2`, and so on, in order of
+/// namespace URI.
+const FIXED_PREFIXES: &[(&str, &str)] = &[
+    ("adobe:ns:meta/", "x"),
+    (xmp_ns::RDF, "rdf"),
+    (xmp_ns::XMP, "xmp"),
+    (xmp_ns::XMP_RIGHTS, "xmpRights"),
+    (xmp_ns::XMP_MM, "xmpMM"),
+    (xmp_ns::XMP_NOTE, "xmpNote"),
+    (xmp_ns::XMP_BJ, "xmpBJ"),
+    (xmp_ns::PDF, "pdf"),
+    (xmp_ns::PHOTOSHOP, "photoshop"),
+    (xmp_ns::EXIF, "exif"),
+    (xmp_ns::EXIF_EX, "exifEX"),
+    (xmp_ns::TIFF, "tiff"),
+    (xmp_ns::EXIF_AUX, "aux"),
+    (xmp_ns::IDENTIFIER_QUAL, "xmpidq"),
+    (xmp_ns::DIMENSIONS, "stDim"),
+    (xmp_ns::IMAGE, "xmpGImg"),
+    (xmp_ns::RESOURCE_EVENT, "stEvt"),
+    (xmp_ns::RESOURCE_REF, "stRef"),
+    (xmp_ns::ST_VERSION, "stVer"),
+    (xmp_ns::ST_JOB, "stJob"),
+    (xmp_ns::CAMERA_RAW, "crs"),
+    (xmp_ns::DYNAMIC_MEDIA, "xmpDM"),
+    (xmp_ns::LIGHTROOM, "lr"),
+    (xmp_ns::DC, "dc"),
+    (xmp_ns::IPTC_CORE, "Iptc4xmpCore"),
+    (xmp_ns::IPTC_EXT, "Iptc4xmpExt"),
+    (xmp_ns::MWG_REGIONS, "mwg-rs"),
+    (xmp_ns::MICROSOFT_PHOTO, "MP"),
+    (xmp_ns::GPANO, "GPano"),
+];
+
+pub(crate) fn to_string(meta: &XmpMeta) -> XmpResult<String> {
+    let mut sorted = meta.clone();
+    sorted.sort()?;
+
+    let options = ToStringOptions::default()
+        .omit_packet_wrapper()
+        .use_canonical_format()
+        .set_newline("\n".to_owned())
+        .set_indent_string(" ".to_owned());
+
+    let xml = sorted.to_string_with_options(options)?;
+
+    // Namespace registrations are global, so each prefix in the output
+    // stands for the same URI wherever it appears.
+    let mut declared: HashMap<String, String> = HashMap::new();
+    let mut last_name = String::new();
+    walk_tags(&xml, |token| {
+        match token {
+            Token::Name(name) => last_name = name.to_owned(),
+            Token::Value(value) => {
+                if let Some(prefix) = last_name.strip_prefix("xmlns:") {
+                    declared.insert(prefix.to_owned(), value.to_owned());
+                }
+            }
+        }
+        None
+    });
+
+    let renames = canonical_prefixes(&declared);
+
+    let mut last_name = String::new();
+    Ok(walk_tags(&xml, |token| match token {
+        Token::Name(name) => {
+            let renamed = rename(name, &renames);
+            last_name.clone_from(&renamed);
+            Some(renamed)
+        }
+        Token::Value(_) if last_name == "x:xmptk" => Some(CANONICAL_XMPTK.to_owned()),
+        Token::Value(_) => None,
+    }))
+}
+
+/// Assigns the canonical prefix for each declared prefix.
+fn canonical_prefixes(declared: &HashMap<String, String>) -> HashMap<String, String> {
+    let fixed = |uri: &str| {
+        FIXED_PREFIXES
+            .iter()
+            .find(|(ns, _)| *ns == uri)
+            .map(|(_, prefix)| *prefix)
+    };
+
+    let others: BTreeSet<&str> = declared
+        .values()
+        .map(String::as_str)
+        .filter(|uri| fixed(uri).is_none())
+        .collect();
+
+    declared
+        .iter()
+        .map(|(prefix, uri)| {
+            let canonical = match fixed(uri) {
+                Some(fixed) => fixed.to_owned(),
+                None => {
+                    let index = others.iter().position(|o| o == uri).unwrap_or_default();
+                    format!("ns{}", index + 1)
+                }
+            };
+
+            (prefix.clone(), canonical)
+        })
+        .collect()
+}
+
+fn rename(name: &str, renames: &HashMap<String, String>) -> String {
+    let lookup = |prefix: &str| renames.get(prefix).map_or(prefix.to_owned(), Clone::clone);
+
+    if let Some(prefix) = name.strip_prefix("xmlns:") {
+        format!("xmlns:{}", lookup(prefix))
+    } else if let Some((prefix, local)) = name.split_once(':') {
+        format!("{}:{local}", lookup(prefix))
+    } else {
+        name.to_owned()
+    }
+}
+
+enum Token<'a> {
+    /// An element or attribute name.
+    Name(&'a str),
+
+    /// An attribute value, without the quotes.
+    Value(&'a str),
+}
+
+/// Calls `f` for each name and attribute value inside the tags of `xml`
+/// and replaces the token with the result, if any.
+///
+/// This only handles the subset of XML produced by the C++ XMP Toolkit's
+/// serializer: there are no comments, CDATA sections, or DTDs, and text
+/// content never contains `<`.
+fn walk_tags(xml: &str, mut f: impl FnMut(Token) -> Option<String>) -> String {
+    let bytes = xml.as_bytes();
+    let mut out = String::with_capacity(xml.len());
+    let mut i = 0;
+    let mut in_tag = false;
+
+    while i < bytes.len() {
+        if !in_tag {
+            let end = xml[i..].find('<').map_or(bytes.len(), |p| i + p);
+            out.push_str(&xml[i..end]);
+            i = end;
+            in_tag = i < bytes.len();
+            if in_tag {
+                out.push('<');
+                i += 1;
+            }
+            continue;
+        }
+
+        match bytes[i] {
+            b'>' => {
+                out.push('>');
+                in_tag = false;
+                i += 1;
+            }
+            quote @ (b'"' | b'\'') => {
+                let end = xml[i + 1..]
+                    .find(quote as char)
+                    .map_or(bytes.len(), |p| i + 1 + p);
+                let value = &xml[i + 1..end];
+
+                out.push(quote as char);
+                match f(Token::Value(value)) {
+                    Some(replacement) => out.push_str(&replacement),
+                    None => out.push_str(value),
+                }
+                if end < bytes.len() {
+                    out.push(quote as char);
+                }
+                i = end + 1;
+            }
+            b if is_delimiter(b) => {
+                out.push(b as char);
+                i += 1;
+            }
+            _ => {
+                let end = bytes[i..]
+                    .iter()
+                    .position(|b| is_delimiter(*b) || matches!(b, b'>' | b'"' | b'\''))
+                    .map_or(bytes.len(), |p| i + p);
+                let name = &xml[i..end];
+
+                match f(Token::Name(name)) {
+                    Some(replacement) => out.push_str(&replacement),
+                    None => out.push_str(name),
+                }
+                i = end;
+            }
+        }
+    }
+
+    out
+}
+
+fn is_delimiter(b: u8) -> bool {
+    b.is_ascii_whitespace() || matches!(b, b'=' | b'/' | b'?' | b'!')
+}
@@ -0,0 +1,250 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! A minimal, non-validating scanner for the top-level structure of
+//! serialized RDF.
+//!
+//! The C++ XMP Toolkit does the real parsing. This scanner only locates
+//! element boundaries so that portions of a packet can be handed back to
+//! the toolkit separately.
+
+const RDF_NS: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+
+/// Splits an RDF packet into its top-level `rdf:Description` elements.
+///
+/// Each returned string is a self-contained `rdf:RDF` document wrapping one
+/// `rdf:Description` element, with all namespace declarations that were in
+/// scope for that element copied onto the wrapper.
+///
+/// Malformed input yields whatever blocks could be found before the problem.
+pub(crate) fn description_blocks(s: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut stack: Vec<Element> = Vec::new();
+
+    // Start offset of the current block and the index of the enclosing
+    // `rdf:RDF` element in `stack`.
+    let mut block_start: Option<(usize, usize)> = None;
+
+    let mut pos = 0;
+    while let Some(offset) = s[pos..].find('<') {
+        let start = pos + offset;
+        let rest = &s[start..];
+
+        let end = if rest.starts_with("<!--") {
+            find_end(s, start, "-->")
+        } else if rest.starts_with("<![CDATA[") {
+            find_end(s, start, "]]>")
+        } else if rest.starts_with("<?") {
+            find_end(s, start, "?>")
+        } else if rest.starts_with("<!") {
+            find_end(s, start, ">")
+        } else if rest.starts_with("</") {
+            let Some(end) = find_end(s, start, ">") else {
+                break;
+            };
+
+            if stack.pop().is_none() {
+                break;
+            }
+
+            if let Some((block_start_pos, rdf_index)) = block_start {
+                if stack.len() == rdf_index + 1 {
+                    blocks.push(wrap_block(&stack, rdf_index, &s[block_start_pos..end]));
+                    block_start = None;
+                }
+            }
+
+            Some(end)
+        } else {
+            let Some(end) = find_tag_end(s, start) else {
+                break;
+            };
+
+            let tag = &s[start + 1..end - 1];
+            let empty = tag.ends_with('/');
+            let element = Element::parse(tag.trim_end_matches('/'));
+
+            let is_description = block_start.is_none()
+                && stack
+                    .last()
+                    .is_some_and(|parent| parent.is(&stack, RDF_NS, "RDF"))
+                && element.is_with_parents(&stack, RDF_NS, "Description");
+
+            if is_description {
+                let rdf_index = stack.len() - 1;
+                if empty {
+                    blocks.push(wrap_block(&stack, rdf_index, &s[start..end]));
+                } else {
+                    block_start = Some((start, rdf_index));
+                }
+            }
+
+            if !empty {
+                stack.push(element);
+            }
+
+            Some(end)
+        };
+
+        match end {
+            Some(end) => pos = end,
+            None => break,
+        }
+    }
+
+    blocks
+}
+
+struct Element {
+    name: String,
+    ns_decls: Vec<(String, String)>,
+}
+
+impl Element {
+    fn parse(tag: &str) -> Self {
+        let name_end = tag
+            .find(|c: char| c.is_ascii_whitespace())
+            .unwrap_or(tag.len());
+
+        let name = tag[..name_end].to_owned();
+        let mut ns_decls = Vec::new();
+
+        let mut attrs = &tag[name_end..];
+        while let Some(eq) = attrs.find('=') {
+            let attr_name = attrs[..eq].trim();
+            let value_part = attrs[eq + 1..].trim_start();
+
+            let Some(quote) = value_part.chars().next() else {
+                break;
+            };
+            let Some(value_len) = value_part[1..].find(quote) else {
+                break;
+            };
+
+            let raw_value = &value_part[..value_len + 2];
+            if attr_name == "xmlns" || attr_name.starts_with("xmlns:") {
+                ns_decls.push((attr_name.to_owned(), raw_value.to_owned()));
+            }
+
+            attrs = &value_part[value_len + 2..];
+        }
+
+        Self { name, ns_decls }
+    }
+
+    /// Returns true if this element (already on `stack`) has the given
+    /// namespace and local name.
+    fn is(&self, stack: &[Element], ns: &str, local_name: &str) -> bool {
+        let (prefix, name) = split_name(&self.name);
+        name == local_name && resolve_prefix(stack, prefix).as_deref() == Some(ns)
+    }
+
+    /// Returns true if this element (not yet pushed onto `stack`) has the
+    /// given namespace and local name.
+    fn is_with_parents(&self, stack: &[Element], ns: &str, local_name: &str) -> bool {
+        let (prefix, name) = split_name(&self.name);
+        if name != local_name {
+            return false;
+        }
+
+        let attr = xmlns_attr(prefix);
+        let uri = self
+            .ns_decls
+            .iter()
+            .rev()
+            .find(|(decl, _)| *decl == attr)
+            .map(|(_, value)| unquote(value))
+            .or_else(|| resolve_prefix(stack, prefix));
+
+        uri.as_deref() == Some(ns)
+    }
+}
+
+fn split_name(name: &str) -> (&str, &str) {
+    name.split_once(':').unwrap_or(("", name))
+}
+
+fn xmlns_attr(prefix: &str) -> String {
+    if prefix.is_empty() {
+        "xmlns".to_owned()
+    } else {
+        format!("xmlns:{prefix}")
+    }
+}
+
+fn unquote(raw_value: &str) -> String {
+    raw_value[1..raw_value.len() - 1].to_owned()
+}
+
+fn resolve_prefix(stack: &[Element], prefix: &str) -> Option<String> {
+    let attr = xmlns_attr(prefix);
+
+    stack.iter().rev().find_map(|element| {
+        element
+            .ns_decls
+            .iter()
+            .rev()
+            .find(|(decl, _)| *decl == attr)
+            .map(|(_, value)| unquote(value))
+    })
+}
+
+fn wrap_block(stack: &[Element], rdf_index: usize, block: &str) -> String {
+    let rdf_name = &stack[rdf_index].name;
+
+    // Later (inner) declarations override earlier ones.
+    let mut decls: Vec<&(String, String)> = Vec::new();
+    for element in &stack[..=rdf_index] {
+        for decl in &element.ns_decls {
+            decls.retain(|(name, _)| *name != decl.0);
+            decls.push(decl);
+        }
+    }
+
+    let mut result = format!("<{rdf_name}");
+    for (name, value) in decls {
+        result.push(' ');
+        result.push_str(name);
+        result.push('=');
+        result.push_str(value);
+    }
+    result.push('>');
+    result.push_str(block);
+    result.push_str("</");
+    result.push_str(rdf_name);
+    result.push('>');
+
+    result
+}
+
+fn find_end(s: &str, start: usize, terminator: &str) -> Option<usize> {
+    s[start..]
+        .find(terminator)
+        .map(|offset| start + offset + terminator.len())
+}
+
+fn find_tag_end(s: &str, start: usize) -> Option<usize> {
+    let mut quote: Option<char> = None;
+
+    for (offset, c) in s[start..].char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => (),
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(start + offset + 1),
+            _ => (),
+        }
+    }
+
+    None
+}
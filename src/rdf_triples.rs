@@ -0,0 +1,418 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Maps the XMP data model onto RDF triples, following the RDF/XML
+//! serialization rules in part 1 of the XMP specification, and writes them
+//! as N-Triples or Turtle.
+
+use std::{collections::BTreeMap, fmt::Write};
+
+use crate::{xmp_ns, IterOptions, XmpMeta, XmpProperty};
+
+/// Returns the triples of `meta` in N-Triples format.
+pub(crate) fn to_ntriples(meta: &XmpMeta) -> String {
+    let mut out = String::new();
+
+    for triple in triples(meta) {
+        let _ = writeln!(
+            out,
+            "{} <{}> {} .",
+            triple.subject.to_ntriples(),
+            triple.predicate,
+            triple.object.to_ntriples()
+        );
+    }
+
+    out
+}
+
+/// Returns the triples of `meta` in Turtle format, using the namespace
+/// prefixes registered with the XMP Toolkit.
+pub(crate) fn to_turtle(meta: &XmpMeta) -> String {
+    let triples = triples(meta);
+
+    let mut prefixes = BTreeMap::new();
+    for triple in &triples {
+        for iri in [
+            triple.subject.iri(),
+            Some(&triple.predicate),
+            triple.object.iri(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if let Some((ns, prefix, _)) = prefixed_name(iri) {
+                prefixes.insert(prefix, ns);
+            }
+        }
+    }
+
+    let mut out = String::new();
+    for (prefix, ns) in &prefixes {
+        let _ = writeln!(out, "@prefix {prefix}: <{ns}> .");
+    }
+
+    let mut subject: Option<&Term> = None;
+    for triple in &triples {
+        let predicate = turtle_iri(&triple.predicate);
+        let object = triple.object.to_turtle();
+
+        if subject == Some(&triple.subject) {
+            let _ = write!(out, " ;\n    {predicate} {object}");
+        } else {
+            if subject.is_some() {
+                out.push_str(" .\n");
+            }
+
+            let _ = write!(
+                out,
+                "\n{}\n    {predicate} {object}",
+                triple.subject.to_turtle()
+            );
+            subject = Some(&triple.subject);
+        }
+    }
+
+    if subject.is_some() {
+        out.push_str(" .\n");
+    }
+
+    out
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Term {
+    Iri(String),
+    Blank(usize),
+    Literal { value: String, lang: Option<String> },
+}
+
+impl Term {
+    fn iri(&self) -> Option<&String> {
+        match self {
+            Self::Iri(iri) => Some(iri),
+            _ => None,
+        }
+    }
+
+    fn to_ntriples(&self) -> String {
+        match self {
+            Self::Iri(iri) => format!("<{}>", escape_iri(iri)),
+            Self::Blank(id) => format!("_:b{id}"),
+            Self::Literal { value, lang } => literal(value, lang.as_deref()),
+        }
+    }
+
+    fn to_turtle(&self) -> String {
+        match self {
+            Self::Iri(iri) => turtle_iri(iri),
+            _ => self.to_ntriples(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Triple {
+    subject: Term,
+    predicate: String,
+    object: Term,
+}
+
+/// A node of the XMP data model, rebuilt from the depth-first iteration
+/// order.
+struct Node {
+    prop: XmpProperty,
+    children: Vec<Node>,
+}
+
+struct Builder {
+    triples: Vec<Triple>,
+    next_blank: usize,
+}
+
+impl Builder {
+    fn blank(&mut self) -> Term {
+        self.next_blank += 1;
+        Term::Blank(self.next_blank)
+    }
+
+    fn add(&mut self, subject: &Term, predicate: String, object: Term) {
+        self.triples.push(Triple {
+            subject: subject.clone(),
+            predicate,
+            object,
+        });
+    }
+
+    /// Adds the triples describing `node` and returns the term that
+    /// represents its value.
+    fn value(&mut self, node: &Node) -> Term {
+        let value = &node.prop.value;
+
+        let (qualifiers, children): (Vec<&Node>, Vec<&Node>) = node
+            .children
+            .iter()
+            .partition(|child| child.prop.value.is_qualifier());
+
+        let lang = qualifiers
+            .iter()
+            .find(|q| q.prop.name.ends_with("/?xml:lang"))
+            .map(|q| q.prop.value.value.clone());
+
+        let other_qualifiers: Vec<&Node> = qualifiers
+            .into_iter()
+            .filter(|q| !q.prop.name.ends_with("/?xml:lang"))
+            .collect();
+
+        let term = if value.is_array() {
+            let array = self.blank();
+            let form = if value.is_alternate() {
+                "Alt"
+            } else if value.is_ordered() {
+                "Seq"
+            } else {
+                "Bag"
+            };
+
+            self.add(
+                &array,
+                format!("{}type", xmp_ns::RDF),
+                Term::Iri(format!("{}{form}", xmp_ns::RDF)),
+            );
+
+            for (index, item) in children.iter().enumerate() {
+                let object = self.value(item);
+                self.add(&array, format!("{}_{}", xmp_ns::RDF, index + 1), object);
+            }
+
+            array
+        } else if value.is_struct() {
+            let fields = self.blank();
+            for field in &children {
+                if let Some(predicate) = predicate(&field.prop.name) {
+                    let object = self.value(field);
+                    self.add(&fields, predicate, object);
+                }
+            }
+
+            fields
+        } else if value.is_uri() {
+            Term::Iri(value.value.clone())
+        } else {
+            Term::Literal {
+                value: value.value.clone(),
+                lang: lang.clone(),
+            }
+        };
+
+        let lang_applied = matches!(term, Term::Literal { .. });
+        if other_qualifiers.is_empty() && (lang.is_none() || lang_applied) {
+            return term;
+        }
+
+        // A value with general qualifiers is written as a node whose
+        // `rdf:value` is the actual value.
+        let qualified = self.blank();
+        self.add(&qualified, format!("{}value", xmp_ns::RDF), term);
+
+        if let (Some(lang), false) = (lang, lang_applied) {
+            self.add(
+                &qualified,
+                format!("{}lang", xmp_ns::XML),
+                Term::Literal {
+                    value: lang,
+                    lang: None,
+                },
+            );
+        }
+
+        for qualifier in other_qualifiers {
+            if let Some(predicate) = predicate(&qualifier.prop.name) {
+                let object = self.value(qualifier);
+                self.add(&qualified, predicate, object);
+            }
+        }
+
+        qualified
+    }
+}
+
+fn triples(meta: &XmpMeta) -> Vec<Triple> {
+    let name = meta.name();
+    let mut builder = Builder {
+        triples: Vec::new(),
+        next_blank: 0,
+    };
+
+    let subject = if name.is_empty() {
+        Term::Blank(0)
+    } else {
+        Term::Iri(name)
+    };
+
+    for node in tree(meta) {
+        if let Some(predicate) = predicate(&node.prop.name) {
+            let object = builder.value(&node);
+            builder.add(&subject, predicate, object);
+        }
+    }
+
+    // Nested values are described before the triples that refer to them.
+    // Group the triples by subject, in the order the subjects were created,
+    // without changing the order of the properties of each subject.
+    let mut triples = builder.triples;
+    triples.sort_by_key(|triple| match triple.subject {
+        Term::Blank(id) => id,
+        _ => 0,
+    });
+
+    triples
+}
+
+/// Rebuilds the tree of top-level properties from the iteration order.
+fn tree(meta: &XmpMeta) -> Vec<Node> {
+    let mut roots: Vec<Node> = Vec::new();
+    let mut stack: Vec<Node> = Vec::new();
+
+    let props = meta
+        .iter(IterOptions::default())
+        .filter(|prop| !prop.value.is_schema_node() && !prop.name.is_empty());
+
+    for prop in props {
+        while let Some(top) = stack.pop() {
+            if is_descendant(&prop.name, &top.prop.name) {
+                stack.push(top);
+                break;
+            }
+
+            attach(&mut stack, &mut roots, top);
+        }
+
+        stack.push(Node {
+            prop,
+            children: Vec::new(),
+        });
+    }
+
+    while let Some(top) = stack.pop() {
+        attach(&mut stack, &mut roots, top);
+    }
+
+    roots
+}
+
+fn attach(stack: &mut [Node], roots: &mut Vec<Node>, node: Node) {
+    match stack.last_mut() {
+        Some(parent) => parent.children.push(node),
+        None => roots.push(node),
+    }
+}
+
+fn is_descendant(path: &str, ancestor: &str) -> bool {
+    path.strip_prefix(ancestor)
+        .is_some_and(|rest| rest.starts_with('/') || rest.starts_with('['))
+}
+
+/// Returns the IRI for the last step of `path` if it is a property, struct
+/// field, or qualifier name.
+fn predicate(path: &str) -> Option<String> {
+    if path.ends_with(']') {
+        return None;
+    }
+
+    let step = path.rsplit('/').next().unwrap_or(path);
+    let step = step.strip_prefix('?').unwrap_or(step);
+    let (prefix, local) = step.split_once(':')?;
+
+    XmpMeta::namespace_uri(prefix).map(|ns| format!("{ns}{local}"))
+}
+
+/// Splits `iri` into a registered namespace, its prefix, and a local name
+/// that can be written as a Turtle prefixed name.
+fn prefixed_name(iri: &str) -> Option<(String, String, String)> {
+    let split = iri.rfind(['/', '#', ':']).map(|i| i + 1)?;
+    let (ns, local) = iri.split_at(split);
+
+    let prefix = XmpMeta::namespace_prefix(ns)?;
+    let prefix = prefix.trim_end_matches(':');
+
+    let valid_prefix = prefix
+        .bytes()
+        .next()
+        .is_some_and(|b| b.is_ascii_alphabetic())
+        && prefix
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+
+    let valid_local = local
+        .bytes()
+        .next()
+        .is_some_and(|b| b.is_ascii_alphabetic() || b == b'_')
+        && local
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+
+    (valid_prefix && valid_local).then(|| (ns.to_owned(), prefix.to_owned(), local.to_owned()))
+}
+
+fn turtle_iri(iri: &str) -> String {
+    if iri == format!("{}type", xmp_ns::RDF) {
+        return "a".to_owned();
+    }
+
+    match prefixed_name(iri) {
+        Some((_, prefix, local)) => format!("{prefix}:{local}"),
+        None => format!("<{}>", escape_iri(iri)),
+    }
+}
+
+fn escape_iri(iri: &str) -> String {
+    let mut out = String::with_capacity(iri.len());
+    for c in iri.chars() {
+        match c {
+            '<' | '>' | '"' | '{' | '}' | '|' | '^' | '`' | '\\' | '\0'..=' ' => {
+                let _ = write!(out, "\\u{:04X}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn literal(value: &str, lang: Option<&str>) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04X}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+
+    if let Some(lang) = lang {
+        out.push('@');
+        out.push_str(lang);
+    }
+
+    out
+}
@@ -0,0 +1,88 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::str::FromStr;
+
+use crate::{conformance, XmpErrorType, XmpMeta};
+
+#[test]
+fn round_trip_conforms() {
+    assert_eq!(
+        conformance::run(|input| Ok(XmpMeta::from_str(input)?.to_string())),
+        Ok(())
+    );
+}
+
+#[test]
+fn cases_conform_after_parsing() {
+    assert!(!conformance::cases().is_empty());
+
+    for case in conformance::cases() {
+        let meta = XmpMeta::from_str(case.input).unwrap();
+        assert_eq!(case.check(&meta), vec![], "case {}", case.name);
+    }
+}
+
+#[test]
+fn lossy_pipeline_fails() {
+    let failures = conformance::run(|input| {
+        let mut meta = XmpMeta::from_str(input)?;
+        meta.delete_property("ns:test1/", "SimpleProp")?;
+        Ok(meta.to_string())
+    })
+    .unwrap_err();
+
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].case, "simple_rdf");
+    assert_eq!(
+        failures[0].to_string(),
+        "simple_rdf: ns1:SimpleProp is missing; expected \"Simple value\""
+    );
+}
+
+#[test]
+fn changed_value_fails() {
+    let failures = conformance::run(|input| {
+        let mut meta = XmpMeta::from_str(input)?;
+        if meta.contains_property("ns:test1/", "Date1") {
+            meta.set_property("ns:test1/", "Date1", &"2004".into())?;
+        }
+        Ok(meta.to_string())
+    })
+    .unwrap_err();
+
+    assert_eq!(
+        failures
+            .iter()
+            .map(|f| f.to_string())
+            .collect::<Vec<String>>(),
+        vec!["date_time_rdf: ns1:Date1 is \"2004\"; expected \"2003\""]
+    );
+}
+
+#[test]
+fn pipeline_error() {
+    let failures = conformance::run(|_| {
+        Err(crate::XmpError {
+            error_type: XmpErrorType::BadXml,
+            debug_message: "broken".to_owned(),
+        })
+    })
+    .unwrap_err();
+
+    assert_eq!(failures.len(), conformance::cases().len());
+    assert_eq!(
+        failures[0].description,
+        "XmpError(XML format error, broken)"
+    );
+}
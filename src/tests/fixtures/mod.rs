@@ -16,6 +16,8 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use crate::{xmp_ns, FileFormat, OpenFileOptions, XmpFile, XmpMeta, XmpPropertyType};
+
 pub(crate) fn fixture_path(name: &str) -> String {
     let root_dir = &env::var("CARGO_MANIFEST_DIR").unwrap();
 
@@ -37,6 +39,90 @@ pub(crate) fn temp_copy_of_fixture(tempdir: &Path, name: &str) -> String {
     fixture_copy.display().to_string()
 }
 
+/// Returns metadata whose only property is `xmp:Label`.
+pub(crate) fn meta_with_label(label: &str) -> XmpMeta {
+    let mut meta = XmpMeta::new().unwrap();
+    meta.set_property(xmp_ns::XMP, "Label", &label.into())
+        .unwrap();
+    meta
+}
+
+/// Updates the in-memory `file`, opened with `options`, to contain
+/// [`meta_with_label`] and returns the new version.
+pub(crate) fn put_label_with_options(
+    file: &[u8],
+    label: &str,
+    options: OpenFileOptions,
+) -> Vec<u8> {
+    let mut f = XmpFile::from_bytes(file, FileFormat::Unknown, options.for_update()).unwrap();
+    f.put_xmp(&meta_with_label(label)).unwrap();
+    f.into_bytes().unwrap()
+}
+
+pub(crate) fn put_label(file: &[u8], label: &str) -> Vec<u8> {
+    put_label_with_options(file, label, OpenFileOptions::default())
+}
+
+/// Returns the `xmp:Label` of the in-memory `file`, opened as `format` with
+/// `options`.
+pub(crate) fn label_with_options(
+    file: &[u8],
+    format: FileFormat,
+    options: OpenFileOptions,
+) -> Option<String> {
+    XmpFile::from_bytes(file, format, options)
+        .unwrap()
+        .xmp()
+        .and_then(|m| m.property(xmp_ns::XMP, "Label"))
+        .map(|v| v.value)
+}
+
+pub(crate) fn label(file: &[u8], format: FileFormat) -> Option<String> {
+    label_with_options(file, format, OpenFileOptions::default())
+}
+
+/// Returns the `xmp:Label` of the file at `path`.
+pub(crate) fn label_of_file(path: &str) -> Option<String> {
+    let mut f = XmpFile::new().unwrap();
+    f.open_file(path, OpenFileOptions::default().for_read())
+        .unwrap();
+
+    f.xmp()
+        .and_then(|m| m.property(xmp_ns::XMP, "Label"))
+        .map(|v| v.value)
+}
+
+/// Sets `xmp:Label` in the file at `path`, which is updated in place.
+pub(crate) fn put_label_in_file(path: &str, label: &str) {
+    let mut f = XmpFile::new().unwrap();
+    f.open_file(path, OpenFileOptions::default().for_update())
+        .unwrap();
+
+    f.put_xmp(&meta_with_label(label)).unwrap();
+    f.close().unwrap();
+}
+
+/// A custom value type for exercising [`XmpPropertyType`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Rational {
+    pub(crate) num: i64,
+    pub(crate) den: i64,
+}
+
+impl XmpPropertyType for Rational {
+    fn from_xmp_str(s: &str) -> Option<Self> {
+        let (num, den) = s.split_once('/')?;
+        Some(Self {
+            num: num.parse().ok()?,
+            den: den.parse().ok()?,
+        })
+    }
+
+    fn to_xmp_string(&self) -> String {
+        format!("{}/{}", self.num, self.den)
+    }
+}
+
 pub(crate) const PURPLE_SQUARE_XMP: &str = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/" x:xmptk="Adobe XMP Core 4.0-c003 (debug), build -num-, -date-">
         <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
         <rdf:Description rdf:about=""
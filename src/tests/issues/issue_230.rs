@@ -135,7 +135,7 @@ fn update(meta: &mut XmpMeta, flip: u8) {
 
 fn write_to_file(xmp_file: &mut XmpFile, meta: &XmpMeta) {
     xmp_file.put_xmp(meta).unwrap();
-    xmp_file.close();
+    let _ = xmp_file.close();
 }
 
 fn write_to_file_try_close(xmp_file: &mut XmpFile, meta: &XmpMeta) {
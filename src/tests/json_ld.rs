@@ -0,0 +1,173 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use crate::{xmp_ns, XmpErrorType, XmpMeta};
+
+const DAM_EXPORT: &str = r#"{
+    "@context": {
+        "dc": "http://purl.org/dc/elements/1.1/",
+        "xmp": "http://ns.adobe.com/xap/1.0/",
+        "Iptc4xmpCore": "http://iptc.org/std/Iptc4xmpCore/1.0/xmlns/",
+        "ex": "http://ns.example.com/dam/1.0/",
+        "creator": { "@id": "dc:creator", "@container": "@list" },
+        "rights": { "@id": "dc:rights", "@container": "@language" }
+    },
+    "@id": "urn:example:asset:1234",
+    "@type": "ex:Asset",
+    "xmp:CreatorTool": "Example DAM",
+    "xmp:Rating": 4,
+    "ex:Approved": true,
+    "dc:title": [
+        { "@value": "Purple square", "@language": "x-default" },
+        { "@value": "Carré violet", "@language": "fr" }
+    ],
+    "rights": { "en": "All rights reserved" },
+    "creator": ["Alice", "Bob"],
+    "dc:subject": ["purple", "square"],
+    "ex:Source": { "@id": "https://example.com/assets/1234" },
+    "Iptc4xmpCore:CreatorContactInfo": {
+        "Iptc4xmpCore:CiAdrCity": "Seattle",
+        "Iptc4xmpCore:CiAdrPcode": "98110"
+    }
+}"#;
+
+#[test]
+fn simple_properties() {
+    let m = XmpMeta::from_json_ld(DAM_EXPORT).unwrap();
+
+    assert_eq!(
+        m.property(xmp_ns::XMP, "CreatorTool").unwrap().value,
+        "Example DAM"
+    );
+    assert_eq!(m.property_i32(xmp_ns::XMP, "Rating").unwrap().value, 4);
+    assert!(
+        m.property_bool("http://ns.example.com/dam/1.0/", "Approved")
+            .unwrap()
+            .value
+    );
+}
+
+#[test]
+fn uri_property() {
+    let m = XmpMeta::from_json_ld(DAM_EXPORT).unwrap();
+
+    let source = m
+        .property("http://ns.example.com/dam/1.0/", "Source")
+        .unwrap();
+    assert_eq!(source.value, "https://example.com/assets/1234");
+    assert!(source.is_uri());
+}
+
+#[test]
+fn language_tagged_values() {
+    let m = XmpMeta::from_json_ld(DAM_EXPORT).unwrap();
+
+    let (title, _) = m.localized_text(xmp_ns::DC, "title", None, "fr").unwrap();
+    assert_eq!(title.value, "Carré violet");
+
+    let (title, _) = m
+        .localized_text(xmp_ns::DC, "title", None, "x-default")
+        .unwrap();
+    assert_eq!(title.value, "Purple square");
+
+    let (rights, _) = m.localized_text(xmp_ns::DC, "rights", None, "en").unwrap();
+    assert_eq!(rights.value, "All rights reserved");
+}
+
+#[test]
+fn arrays() {
+    let m = XmpMeta::from_json_ld(DAM_EXPORT).unwrap();
+
+    let creator = m.property(xmp_ns::DC, "creator").unwrap();
+    assert!(creator.is_array());
+    assert!(creator.is_ordered());
+
+    let creators: Vec<String> = m
+        .property_array(xmp_ns::DC, "creator")
+        .map(|v| v.value)
+        .collect();
+    assert_eq!(creators, ["Alice", "Bob"]);
+
+    let subject = m.property(xmp_ns::DC, "subject").unwrap();
+    assert!(subject.is_array());
+    assert!(!subject.is_ordered());
+    assert_eq!(m.array_len(xmp_ns::DC, "subject"), 2);
+}
+
+#[test]
+fn structs() {
+    let m = XmpMeta::from_json_ld(DAM_EXPORT).unwrap();
+
+    assert_eq!(
+        m.struct_field(
+            xmp_ns::IPTC_CORE,
+            "CreatorContactInfo",
+            xmp_ns::IPTC_CORE,
+            "CiAdrPcode"
+        )
+        .unwrap()
+        .value,
+        "98110"
+    );
+}
+
+#[test]
+fn graph_and_full_iris() {
+    let m = XmpMeta::from_json_ld(
+        r#"{
+            "@context": { "@vocab": "http://ns.example.com/vocab/" },
+            "@graph": [
+                { "Headline": "First node" },
+                { "http://purl.org/dc/elements/1.1/format": "image/jpeg" }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        m.property("http://ns.example.com/vocab/", "Headline")
+            .unwrap()
+            .value,
+        "First node"
+    );
+    assert_eq!(
+        m.property(xmp_ns::DC, "format").unwrap().value,
+        "image/jpeg"
+    );
+}
+
+#[test]
+fn registered_prefix_without_context() {
+    let m = XmpMeta::from_json_ld(r#"{ "dc:format": "image/png" }"#).unwrap();
+    assert_eq!(m.property(xmp_ns::DC, "format").unwrap().value, "image/png");
+}
+
+#[test]
+fn invalid_json() {
+    let err = XmpMeta::from_json_ld("{ not json").unwrap_err();
+    assert_eq!(err.error_type, XmpErrorType::BadRdf);
+}
+
+#[test]
+fn remote_context() {
+    let err = XmpMeta::from_json_ld(r#"{ "@context": "https://schema.org/", "name": "Remote" }"#)
+        .unwrap_err();
+
+    assert_eq!(err.error_type, XmpErrorType::BadRdf);
+}
+
+#[test]
+fn unknown_term() {
+    let err = XmpMeta::from_json_ld(r#"{ "unmapped": "value" }"#).unwrap_err();
+    assert_eq!(err.error_type, XmpErrorType::BadSchema);
+}
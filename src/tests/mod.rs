@@ -18,16 +18,65 @@
 #![allow(clippy::panic)]
 #![allow(clippy::unwrap_used)]
 
+mod conformance;
 mod fixtures;
 mod issues;
+#[cfg(feature = "json_ld")]
+mod json_ld;
+mod rdf_scan;
+mod rdf_triples;
+mod xmp_access_stats;
+mod xmp_alias;
+#[cfg(feature = "arbitrary")]
+mod xmp_arbitrary;
+#[cfg(feature = "audit")]
+mod xmp_audit;
+mod xmp_batch;
+mod xmp_compound;
 mod xmp_core_coverage;
+mod xmp_cr3;
+mod xmp_crs;
 mod xmp_date_time;
 #[cfg(feature = "chrono")]
 mod xmp_date_time_chrono;
+mod xmp_diff;
+mod xmp_dm;
+mod xmp_edit;
 mod xmp_error;
 mod xmp_error_type;
 mod xmp_file;
+#[cfg(feature = "async")]
+mod xmp_file_async;
+mod xmp_file_batch;
+mod xmp_file_handler;
+mod xmp_flac;
+mod xmp_gpano;
 mod xmp_gps;
+mod xmp_id;
 mod xmp_iterator;
+mod xmp_jpeg;
+mod xmp_jxl;
+mod xmp_keywords;
+mod xmp_lang;
+mod xmp_lineage;
+mod xmp_merge;
 mod xmp_meta;
+mod xmp_meta_builder;
+mod xmp_mm;
+mod xmp_mp3;
+mod xmp_mpeg4;
+mod xmp_mwg;
+mod xmp_mxf;
+mod xmp_ogg;
+mod xmp_ordering;
+mod xmp_packet;
+mod xmp_property_type;
+mod xmp_psd;
+mod xmp_redact;
+mod xmp_sidecar;
+#[cfg(feature = "derive")]
+mod xmp_struct;
+mod xmp_tiff;
 mod xmp_value;
+mod xmp_wav;
+mod xmp_webp;
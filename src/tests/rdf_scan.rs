@@ -0,0 +1,78 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use crate::rdf_scan::description_blocks;
+
+#[test]
+fn single_block() {
+    let blocks = description_blocks(
+        r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"><rdf:Description rdf:about="" xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:format>a</dc:format></rdf:Description></rdf:RDF>"#,
+    );
+
+    assert_eq!(
+        blocks,
+        [
+            r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"><rdf:Description rdf:about="" xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:format>a</dc:format></rdf:Description></rdf:RDF>"#
+        ]
+    );
+}
+
+#[test]
+fn inherits_outer_namespaces() {
+    let blocks = description_blocks(
+        r#"<?xpacket begin="" id="W5M0MpCehiHzreSzNTczkc9d"?>
+        <x:xmpmeta xmlns:x="adobe:ns:meta/" xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <!-- <rdf:Description> in a comment is ignored -->
+        <r:RDF xmlns:r='http://www.w3.org/1999/02/22-rdf-syntax-ns#'>
+            <r:Description r:about=""><dc:format>a</dc:format></r:Description>
+            <r:Description r:about="" dc:title="x > y"/>
+        </r:RDF>
+        </x:xmpmeta>"#,
+    );
+
+    assert_eq!(
+        blocks,
+        [
+            r#"<r:RDF xmlns:x="adobe:ns:meta/" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:r='http://www.w3.org/1999/02/22-rdf-syntax-ns#'><r:Description r:about=""><dc:format>a</dc:format></r:Description></r:RDF>"#,
+            r#"<r:RDF xmlns:x="adobe:ns:meta/" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:r='http://www.w3.org/1999/02/22-rdf-syntax-ns#'><r:Description r:about="" dc:title="x > y"/></r:RDF>"#,
+        ]
+    );
+}
+
+#[test]
+fn nested_description_not_split() {
+    let blocks = description_blocks(
+        r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"><rdf:Description><ns:a xmlns:ns="ns:x/"><rdf:Description/></ns:a></rdf:Description></rdf:RDF>"#,
+    );
+
+    assert_eq!(blocks.len(), 1);
+    assert!(blocks[0].ends_with("</ns:a></rdf:Description></rdf:RDF>"));
+}
+
+#[test]
+fn wrong_namespace() {
+    let blocks =
+        description_blocks(r#"<rdf:RDF xmlns:rdf="ns:not-rdf/"><rdf:Description/></rdf:RDF>"#);
+
+    assert!(blocks.is_empty());
+}
+
+#[test]
+fn malformed() {
+    assert!(description_blocks("").is_empty());
+    assert!(description_blocks("this is not XMP").is_empty());
+    assert!(description_blocks(
+        "<rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\"><rdf:Description"
+    )
+    .is_empty());
+}
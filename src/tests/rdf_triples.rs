@@ -0,0 +1,118 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::str::FromStr;
+
+use crate::XmpMeta;
+
+const RDF: &str = r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <rdf:Description rdf:about="http://example.com/image.jpg"
+        xmlns:dc="http://purl.org/dc/elements/1.1/"
+        xmlns:xmp="http://ns.adobe.com/xap/1.0/"
+        xmlns:xmpRights="http://ns.adobe.com/xap/1.0/rights/"
+        xmlns:xmpMM="http://ns.adobe.com/xap/1.0/mm/"
+        xmlns:stRef="http://ns.adobe.com/xap/1.0/sType/ResourceRef#">
+        <dc:title>
+            <rdf:Alt>
+                <rdf:li xml:lang="x-default">Say "hi"</rdf:li>
+            </rdf:Alt>
+        </dc:title>
+        <xmp:Label>Red</xmp:Label>
+        <xmpRights:WebStatement rdf:resource="http://example.com/rights"/>
+        <xmpMM:DerivedFrom rdf:parseType="Resource">
+            <stRef:documentID>xmp.did:1</stRef:documentID>
+        </xmpMM:DerivedFrom>
+    </rdf:Description>
+</rdf:RDF>"#;
+
+#[test]
+fn ntriples() {
+    let m = XmpMeta::from_str(RDF).unwrap();
+
+    assert_eq!(
+        m.to_ntriples().unwrap(),
+        "<http://example.com/image.jpg> <http://purl.org/dc/elements/1.1/title> _:b1 .\n\
+         <http://example.com/image.jpg> <http://ns.adobe.com/xap/1.0/Label> \"Red\" .\n\
+         <http://example.com/image.jpg> <http://ns.adobe.com/xap/1.0/rights/WebStatement> <http://example.com/rights> .\n\
+         <http://example.com/image.jpg> <http://ns.adobe.com/xap/1.0/mm/DerivedFrom> _:b2 .\n\
+         _:b1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/1999/02/22-rdf-syntax-ns#Alt> .\n\
+         _:b1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#_1> \"Say \\\"hi\\\"\"@x-default .\n\
+         _:b2 <http://ns.adobe.com/xap/1.0/sType/ResourceRef#documentID> \"xmp.did:1\" .\n"
+    );
+}
+
+#[test]
+fn turtle() {
+    let m = XmpMeta::from_str(RDF).unwrap();
+
+    assert_eq!(
+        m.to_turtle().unwrap(),
+        "@prefix dc: <http://purl.org/dc/elements/1.1/> .\n\
+         @prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .\n\
+         @prefix stRef: <http://ns.adobe.com/xap/1.0/sType/ResourceRef#> .\n\
+         @prefix xmp: <http://ns.adobe.com/xap/1.0/> .\n\
+         @prefix xmpMM: <http://ns.adobe.com/xap/1.0/mm/> .\n\
+         @prefix xmpRights: <http://ns.adobe.com/xap/1.0/rights/> .\n\
+         \n\
+         <http://example.com/image.jpg>\n\
+         \x20   dc:title _:b1 ;\n\
+         \x20   xmp:Label \"Red\" ;\n\
+         \x20   xmpRights:WebStatement <http://example.com/rights> ;\n\
+         \x20   xmpMM:DerivedFrom _:b2 .\n\
+         \n\
+         _:b1\n\
+         \x20   a rdf:Alt ;\n\
+         \x20   rdf:_1 \"Say \\\"hi\\\"\"@x-default .\n\
+         \n\
+         _:b2\n\
+         \x20   stRef:documentID \"xmp.did:1\" .\n"
+    );
+}
+
+#[test]
+fn qualifiers() {
+    let m = XmpMeta::from_str(
+        r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+            <rdf:Description rdf:about=""
+                xmlns:dc="http://purl.org/dc/elements/1.1/"
+                xmlns:ns1="ns:test1/">
+                <dc:creator>
+                    <rdf:Seq>
+                        <rdf:li rdf:parseType="Resource">
+                            <rdf:value>Alice</rdf:value>
+                            <ns1:role>photographer</ns1:role>
+                        </rdf:li>
+                    </rdf:Seq>
+                </dc:creator>
+            </rdf:Description>
+        </rdf:RDF>"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        m.to_ntriples().unwrap(),
+        "_:b0 <http://purl.org/dc/elements/1.1/creator> _:b1 .\n\
+         _:b1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/1999/02/22-rdf-syntax-ns#Seq> .\n\
+         _:b1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#_1> _:b2 .\n\
+         _:b2 <http://www.w3.org/1999/02/22-rdf-syntax-ns#value> \"Alice\" .\n\
+         _:b2 <ns:test1/role> \"photographer\" .\n"
+    );
+}
+
+#[test]
+fn empty() {
+    let m = XmpMeta::new().unwrap();
+
+    assert_eq!(m.to_ntriples().unwrap(), "");
+    assert_eq!(m.to_turtle().unwrap(), "");
+}
@@ -0,0 +1,110 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use crate::{xmp_ns, AccessCount, AccessStats};
+
+fn example() -> AccessStats {
+    let mut stats = AccessStats::default();
+
+    stats.record_read(xmp_ns::XMP, "CreatorTool");
+    stats.record_read(xmp_ns::DC, "title");
+    stats.record_read(xmp_ns::DC, "title");
+    stats.record_write(xmp_ns::DC, "title");
+    stats.record_write("http://example.com/unregistered/", "Prop");
+
+    stats
+}
+
+#[test]
+fn default() {
+    let stats = AccessStats::default();
+    assert!(stats.is_empty());
+    assert_eq!(
+        stats.get(xmp_ns::XMP, "CreatorTool"),
+        AccessCount::default()
+    );
+}
+
+#[test]
+fn get() {
+    let stats = example();
+    assert!(!stats.is_empty());
+
+    assert_eq!(
+        stats.get(xmp_ns::DC, "title"),
+        AccessCount {
+            reads: 2,
+            writes: 1
+        }
+    );
+
+    assert_eq!(
+        stats.get(xmp_ns::XMP, "CreatorTool"),
+        AccessCount {
+            reads: 1,
+            writes: 0
+        }
+    );
+}
+
+#[test]
+fn iter() {
+    let stats = example();
+
+    let entries: Vec<(&str, &str, AccessCount)> = stats.iter().collect();
+
+    assert_eq!(
+        entries,
+        vec![
+            (
+                "http://example.com/unregistered/",
+                "Prop",
+                AccessCount {
+                    reads: 0,
+                    writes: 1
+                }
+            ),
+            (
+                xmp_ns::XMP,
+                "CreatorTool",
+                AccessCount {
+                    reads: 1,
+                    writes: 0
+                }
+            ),
+            (
+                xmp_ns::DC,
+                "title",
+                AccessCount {
+                    reads: 2,
+                    writes: 1
+                }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn impl_display() {
+    let stats = example();
+
+    assert_eq!(
+        stats.to_string(),
+        concat!(
+            "     reads     writes  property\n",
+            "         2          1  dc:title\n",
+            "         0          1  {http://example.com/unregistered/}Prop\n",
+            "         1          0  xmp:CreatorTool\n",
+        )
+    );
+}
@@ -0,0 +1,119 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::{collections::HashSet, str::FromStr};
+
+use crate::{xmp_ns, AliasForm, XmpMeta, XmpValue};
+
+#[test]
+fn aliases_resolve_to_actual_property() {
+    for alias in XmpMeta::aliases() {
+        let mut meta = XmpMeta::new().unwrap();
+
+        // Aliases of whole arrays are given an item, so that the value can
+        // be found below the path the alias stands for.
+        let mut value_path = alias.actual_path();
+
+        match alias.form {
+            AliasForm::FirstItem => {
+                meta.append_array_item(
+                    alias.actual_ns,
+                    &XmpValue::from(alias.actual_name).set_is_ordered(true),
+                    &"First".into(),
+                )
+                .unwrap();
+            }
+            AliasForm::DefaultLanguage => {
+                meta.set_localized_text(
+                    alias.actual_ns,
+                    alias.actual_name,
+                    None,
+                    "x-default",
+                    "First",
+                )
+                .unwrap();
+            }
+            AliasForm::Direct if alias.actual_ns == xmp_ns::DC && alias.actual_name != "format" => {
+                meta.append_array_item(
+                    alias.actual_ns,
+                    &XmpValue::from(alias.actual_name).set_is_array(true),
+                    &"First".into(),
+                )
+                .unwrap();
+                value_path.push_str("[1]");
+            }
+            AliasForm::Direct => {
+                meta.set_property(alias.actual_ns, alias.actual_name, &"First".into())
+                    .unwrap();
+            }
+        }
+
+        assert!(
+            meta.contains_property(alias.alias_ns, alias.alias_name),
+            "{alias:?}"
+        );
+
+        assert_eq!(
+            meta.property(alias.actual_ns, &value_path).unwrap().value,
+            "First",
+            "{alias:?}"
+        );
+    }
+}
+
+#[test]
+fn parsed_alias_moves_to_actual_property() {
+    let meta = XmpMeta::from_str(
+        r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+            <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+              <rdf:Description rdf:about=""
+                  xmlns:photoshop="http://ns.adobe.com/photoshop/1.0/"
+                  photoshop:Caption="A caption"/>
+            </rdf:RDF>
+          </x:xmpmeta>"#,
+    )
+    .unwrap();
+
+    let alias = XmpMeta::aliases()
+        .find(|alias| alias.alias_ns == xmp_ns::PHOTOSHOP && alias.alias_name == "Caption")
+        .unwrap();
+
+    assert_eq!(alias.actual_ns, xmp_ns::DC);
+    assert_eq!(alias.form, AliasForm::DefaultLanguage);
+    assert_eq!(
+        meta.property(alias.actual_ns, &alias.actual_path())
+            .unwrap()
+            .value,
+        "A caption"
+    );
+}
+
+#[test]
+fn unique_aliases() {
+    let names: HashSet<(&str, &str)> = XmpMeta::aliases()
+        .map(|alias| (alias.alias_ns, alias.alias_name))
+        .collect();
+
+    assert_eq!(names.len(), XmpMeta::aliases().count());
+}
+
+#[test]
+fn actual_path() {
+    let paths: Vec<String> = XmpMeta::aliases()
+        .filter(|alias| alias.alias_ns == xmp_ns::TIFF)
+        .map(|alias| alias.actual_path())
+        .collect();
+
+    assert!(paths.contains(&"creator[1]".to_owned()));
+    assert!(paths.contains(&"ModifyDate".to_owned()));
+}
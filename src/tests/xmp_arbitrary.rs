@@ -0,0 +1,77 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::str::FromStr;
+
+use arbitrary::{Arbitrary, Unstructured};
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+use crate::{XmpDateTime, XmpMeta, XmpValue};
+
+fn random_bytes(seed: u64) -> Vec<u8> {
+    let mut bytes = vec![0; 4096];
+    StdRng::seed_from_u64(seed).fill_bytes(&mut bytes);
+    bytes
+}
+
+#[test]
+fn date_time_round_trips() {
+    for seed in 0..200 {
+        let bytes = random_bytes(seed);
+        let dt = XmpDateTime::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+
+        if dt.date.is_none() {
+            assert!(dt.time.is_none());
+            continue;
+        }
+
+        let parsed: XmpDateTime = dt.to_string().parse().unwrap();
+        assert_eq!(parsed.date, dt.date);
+    }
+}
+
+#[test]
+fn value_has_no_control_characters() {
+    for seed in 0..200 {
+        let bytes = random_bytes(seed);
+        let value = XmpValue::<String>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+
+        assert!(!value
+            .value
+            .chars()
+            .any(|c| c.is_control() && c != '\t' && c != '\n'));
+    }
+}
+
+#[test]
+fn meta_round_trips() {
+    for seed in 0..50 {
+        let bytes = random_bytes(seed);
+        let meta = XmpMeta::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+
+        let xmp = meta.to_string();
+        let reparsed = XmpMeta::from_str(&xmp).unwrap();
+
+        assert_eq!(
+            meta.iter(Default::default()).count(),
+            reparsed.iter(Default::default()).count(),
+            "seed {seed}"
+        );
+    }
+}
+
+#[test]
+fn empty_input() {
+    let meta = XmpMeta::arbitrary(&mut Unstructured::new(&[])).unwrap();
+    assert_eq!(meta.iter(Default::default()).count(), 0);
+}
@@ -0,0 +1,199 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::fs;
+
+use tempfile::tempdir;
+
+use crate::{
+    xmp_audit::{xmp_hash, AuditEntry, AuditLog},
+    xmp_id::DeterministicMode,
+    xmp_ns, XmpDate, XmpDateTime, XmpErrorType, XmpMeta,
+};
+
+fn entry(path: &str) -> AuditEntry {
+    AuditEntry {
+        operation: "put_xmp".to_owned(),
+        path: path.into(),
+        before: None,
+        after: Some("ab".repeat(32)),
+    }
+}
+
+fn frozen() -> DeterministicMode {
+    DeterministicMode::new(XmpDateTime {
+        date: Some(XmpDate {
+            year: 2026,
+            month: 1,
+            day: 2,
+        }),
+        time: None,
+    })
+}
+
+#[test]
+fn new_log() {
+    let tempdir = tempdir().unwrap();
+    let log_path = tempdir.path().join("audit.log");
+    let _guard = frozen();
+
+    let log = AuditLog::open(&log_path).unwrap();
+    log.record(&entry("a.jpg")).unwrap();
+    log.record(&entry("b.jpg")).unwrap();
+
+    assert_eq!(AuditLog::verify(&log_path).unwrap(), 2);
+
+    let contents = fs::read_to_string(&log_path).unwrap();
+    let lines: Vec<Vec<&str>> = contents
+        .lines()
+        .map(|line| line.split('\t').collect())
+        .collect();
+
+    assert_eq!(lines.len(), 2);
+
+    assert_eq!(lines[0][0], "1");
+    assert_eq!(lines[0][1], "2026-01-02");
+    assert_eq!(lines[0][2], "put_xmp");
+    assert_eq!(lines[0][3], "a.jpg");
+    assert_eq!(lines[0][4], "-");
+    assert_eq!(lines[0][5], "ab".repeat(32));
+    assert_eq!(lines[0][6], "0".repeat(64));
+
+    assert_eq!(lines[1][0], "2");
+    assert_eq!(lines[1][6], lines[0][7]);
+}
+
+#[test]
+fn reopen_continues_chain() {
+    let tempdir = tempdir().unwrap();
+    let log_path = tempdir.path().join("audit.log");
+
+    AuditLog::open(&log_path)
+        .unwrap()
+        .record(&entry("a.jpg"))
+        .unwrap();
+
+    AuditLog::open(&log_path)
+        .unwrap()
+        .record(&entry("b.jpg"))
+        .unwrap();
+
+    assert_eq!(AuditLog::verify(&log_path).unwrap(), 2);
+}
+
+#[test]
+fn clones_share_chain() {
+    let tempdir = tempdir().unwrap();
+    let log_path = tempdir.path().join("audit.log");
+
+    let log = AuditLog::open(&log_path).unwrap();
+    let log2 = log.clone();
+
+    log.record(&entry("a.jpg")).unwrap();
+    log2.record(&entry("b.jpg")).unwrap();
+
+    assert_eq!(AuditLog::verify(&log_path).unwrap(), 2);
+}
+
+#[test]
+fn escapes_special_characters() {
+    let tempdir = tempdir().unwrap();
+    let log_path = tempdir.path().join("audit.log");
+
+    let log = AuditLog::open(&log_path).unwrap();
+    log.record(&entry("odd\tname\n100%.jpg")).unwrap();
+
+    assert_eq!(AuditLog::verify(&log_path).unwrap(), 1);
+
+    let contents = fs::read_to_string(&log_path).unwrap();
+    assert_eq!(contents.lines().count(), 1);
+    assert!(contents.contains("\todd%09name%0A100%25.jpg\t"));
+}
+
+#[test]
+fn tampered_entry() {
+    let tempdir = tempdir().unwrap();
+    let log_path = tempdir.path().join("audit.log");
+
+    let log = AuditLog::open(&log_path).unwrap();
+    log.record(&entry("a.jpg")).unwrap();
+    log.record(&entry("b.jpg")).unwrap();
+    drop(log);
+
+    let contents = fs::read_to_string(&log_path).unwrap();
+    fs::write(&log_path, contents.replace("b.jpg", "c.jpg")).unwrap();
+
+    let err = AuditLog::verify(&log_path).unwrap_err();
+    assert_eq!(err.error_type, XmpErrorType::BadFileFormat);
+    assert_eq!(
+        err.debug_message,
+        "Audit log hash chain is broken at entry 2"
+    );
+
+    let err = AuditLog::open(&log_path).unwrap_err();
+    assert_eq!(err.error_type, XmpErrorType::BadFileFormat);
+}
+
+#[test]
+fn removed_entry() {
+    let tempdir = tempdir().unwrap();
+    let log_path = tempdir.path().join("audit.log");
+
+    let log = AuditLog::open(&log_path).unwrap();
+    log.record(&entry("a.jpg")).unwrap();
+    log.record(&entry("b.jpg")).unwrap();
+    log.record(&entry("c.jpg")).unwrap();
+    drop(log);
+
+    let contents = fs::read_to_string(&log_path).unwrap();
+    let without_second: Vec<&str> = contents
+        .lines()
+        .enumerate()
+        .filter(|(index, _)| *index != 1)
+        .map(|(_, line)| line)
+        .collect();
+    fs::write(&log_path, without_second.join("\n")).unwrap();
+
+    let err = AuditLog::verify(&log_path).unwrap_err();
+    assert_eq!(
+        err.debug_message,
+        "Audit log hash chain is broken at entry 2"
+    );
+}
+
+#[test]
+fn verify_missing_file() {
+    let tempdir = tempdir().unwrap();
+
+    let err = AuditLog::verify(tempdir.path().join("missing.log")).unwrap_err();
+    assert_eq!(err.error_type, XmpErrorType::ReadError);
+}
+
+#[test]
+fn hash_depends_on_content() {
+    let mut m = XmpMeta::new().unwrap();
+    let empty = xmp_hash(&m).unwrap();
+
+    assert_eq!(empty.len(), 64);
+    assert_eq!(empty, xmp_hash(&XmpMeta::new().unwrap()).unwrap());
+
+    m.set_property(xmp_ns::XMP, "CreatorTool", &"x".into())
+        .unwrap();
+    assert_ne!(empty, xmp_hash(&m).unwrap());
+}
+
+#[test]
+fn hash_init_fail() {
+    let err = xmp_hash(&XmpMeta::new_fail()).unwrap_err();
+    assert_eq!(err.error_type, XmpErrorType::NoCppToolkit);
+}
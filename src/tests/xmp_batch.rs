@@ -0,0 +1,140 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::str::FromStr;
+
+use crate::{tests::fixtures::*, xmp_ns, XmpErrorType, XmpMeta, XmpValue};
+
+#[test]
+fn happy_path() {
+    let mut m = XmpMeta::from_str(PURPLE_SQUARE_XMP).unwrap();
+
+    let results = m
+        .batch()
+        .property(xmp_ns::XMP, "CreatorTool")
+        .set_property(xmp_ns::XMP, "CreatorTool", &"Batch".into())
+        .property(xmp_ns::XMP, "CreatorTool")
+        .delete_property(xmp_ns::XMP, "CreatorTool")
+        .property(xmp_ns::XMP, "CreatorTool")
+        .execute()
+        .unwrap();
+
+    assert_eq!(
+        results,
+        vec![
+            Ok(Some(XmpValue::from("Adobe Photoshop CS2 Windows"))),
+            Ok(None),
+            Ok(Some(XmpValue::from("Batch"))),
+            Ok(None),
+            Ok(None),
+        ]
+    );
+
+    assert!(!m.contains_property(xmp_ns::XMP, "CreatorTool"));
+}
+
+#[test]
+fn many_operations() {
+    let mut m = XmpMeta::new().unwrap();
+
+    let mut batch = m.batch();
+    for i in 0..200 {
+        batch.set_property(xmp_ns::DC, &format!("test{i}"), &i.to_string().into());
+    }
+    assert_eq!(batch.len(), 200);
+
+    let results = batch.execute().unwrap();
+    assert_eq!(results.len(), 200);
+    assert!(results.iter().all(|r| r == &Ok(None)));
+    assert!(batch.is_empty());
+
+    assert_eq!(m.property(xmp_ns::DC, "test123").unwrap().value, "123");
+}
+
+#[test]
+fn options() {
+    let mut m = XmpMeta::new().unwrap();
+
+    let results = m
+        .batch()
+        .set_property(
+            xmp_ns::XMP,
+            "BaseURL",
+            &XmpValue::from("https://example.com").set_is_uri(true),
+        )
+        .property(xmp_ns::XMP, "BaseURL")
+        .execute()
+        .unwrap();
+
+    assert!(results[1].as_ref().unwrap().as_ref().unwrap().is_uri());
+}
+
+#[test]
+fn errors_are_per_operation() {
+    let mut m = XmpMeta::new().unwrap();
+
+    let results = m
+        .batch()
+        .set_property(xmp_ns::XMP, "", &"empty name".into())
+        .set_property(xmp_ns::XMP, "x\0x", &"nul".into())
+        .set_property(xmp_ns::XMP, "CreatorTool", &"ok".into())
+        .execute()
+        .unwrap();
+
+    assert_eq!(
+        results[0].as_ref().unwrap_err().error_type,
+        XmpErrorType::BadXPath
+    );
+    assert_eq!(
+        results[1].as_ref().unwrap_err().error_type,
+        XmpErrorType::NulInRustString
+    );
+    assert_eq!(results[2], Ok(None));
+
+    assert_eq!(m.property(xmp_ns::XMP, "CreatorTool").unwrap().value, "ok");
+}
+
+#[test]
+fn empty_batch() {
+    let mut m = XmpMeta::new().unwrap();
+    assert_eq!(m.batch().execute().unwrap(), vec![]);
+}
+
+#[test]
+fn access_stats() {
+    let mut m = XmpMeta::from_str(PURPLE_SQUARE_XMP).unwrap();
+    m.enable_access_stats();
+
+    m.batch()
+        .property(xmp_ns::XMP, "CreatorTool")
+        .set_property(xmp_ns::XMP, "CreatorTool", &"Batch".into())
+        .execute()
+        .unwrap();
+
+    let stats = m.access_stats().unwrap();
+    assert_eq!(stats.get(xmp_ns::XMP, "CreatorTool").reads, 1);
+    assert_eq!(stats.get(xmp_ns::XMP, "CreatorTool").writes, 1);
+}
+
+#[test]
+fn init_fail() {
+    let mut m = XmpMeta::new_fail();
+
+    let err = m
+        .batch()
+        .property(xmp_ns::XMP, "CreatorTool")
+        .execute()
+        .unwrap_err();
+
+    assert_eq!(err.error_type, XmpErrorType::NoCppToolkit);
+}
@@ -0,0 +1,215 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::fs;
+
+use tempfile::tempdir;
+
+use crate::{
+    tests::fixtures::*, xmp_compound, xmp_ns, FileFormat, OpenFileOptions, XmpContainer,
+    XmpErrorType, XmpFile,
+};
+
+fn packet(label: &str) -> String {
+    format!(
+        r#"<?xpacket begin="" id="W5M0MpCehiHzreSzNTczkc9d"?>
+<x:xmpmeta xmlns:x="adobe:ns:meta/">
+  <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <rdf:Description rdf:about="" xmlns:xmp="http://ns.adobe.com/xap/1.0/">
+      <xmp:Label>{label}</xmp:Label>
+    </rdf:Description>
+  </rdf:RDF>
+</x:xmpmeta>
+<?xpacket end="w"?>"#
+    )
+}
+
+fn label(packet: &crate::EmbeddedXmp) -> String {
+    packet
+        .parse()
+        .unwrap()
+        .property(xmp_ns::XMP, "Label")
+        .unwrap()
+        .value
+}
+
+// An InDesign contiguous object holding `data`.
+fn indd_object(uid: u32, data: &[u8]) -> Vec<u8> {
+    let mut object = b"\xde\x39\x39\x79\x51\x88\x4b\x6c\x8e\x63\xee\xf8\xae\xe0\xdd\x38".to_vec();
+    object.extend_from_slice(&uid.to_le_bytes());
+    object.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    object.extend_from_slice(&[0xff; 4]);
+    object.extend_from_slice(&[0; 4]);
+    object.extend_from_slice(data);
+    object
+}
+
+// The zlib-compressed text of an `x:xmpmeta` element whose `xmp:Label`
+// is "Compressed".
+const COMPRESSED_XMP: &[u8] = b"\
+    \x78\xda\x3d\x8f\xc1\x0e\x82\x30\x10\x44\x7f\x85\xac\x67\xba\x80\x27\x1a\xca\x45\xe2\xc9\
+    \x93\x7f\x50\xa0\x2a\x09\x6d\x37\x6d\x4d\xeb\xdf\x5b\x0c\x72\x9b\xd9\xbc\xcc\xce\x74\x89\
+    \x27\x4d\x5a\x05\x59\x24\xbd\x1a\xcf\x93\x00\x39\xdb\x51\xf1\xac\xb7\x33\x42\xdf\xb9\xf9\
+    \xc1\xef\xc3\x75\x27\xb2\x13\xf0\x0a\x81\x38\x62\x8c\x91\xc5\x33\xb3\xee\x89\x75\xdb\xb6\
+    \x58\x35\xd8\x34\x65\x26\x4a\xff\x31\x41\xa6\xd2\xf8\xd3\x1e\x30\x28\x3f\xb9\x85\xc2\x62\
+    \x4d\xb1\x79\x39\xda\x77\x10\x00\xff\xbf\x9a\x8e\x54\xe3\xd9\xaf\x03\x9b\xac\xc6\x24\x09\
+    \x6b\x56\xe1\x06\x12\xbf\xc9\x51\xad\x02\x2e\x56\x93\x53\xde\xab\x19\xb0\xef\x70\x2f\x98\
+    \xd5\x31\xa7\xff\x02\xb0\x57\x49\x73";
+
+#[test]
+fn indesign_objects() {
+    let mut file = vec![0; 4096];
+    file.extend(indd_object(17, packet("Document").as_bytes()));
+    file.extend_from_slice(&[0; 100]);
+    file.extend(indd_object(260, packet("Story").as_bytes()));
+    file.extend_from_slice(&[0; 100]);
+
+    let packets = xmp_compound::embedded_xmp(&file);
+    assert_eq!(packets.len(), 2);
+
+    assert_eq!(label(&packets[0]), "Document");
+    assert_eq!(
+        packets[0].container,
+        XmpContainer::InDesignObject { uid: 17 }
+    );
+    assert_eq!(packets[0].range.start, 4096 + 32);
+
+    assert_eq!(label(&packets[1]), "Story");
+    assert_eq!(
+        packets[1].container,
+        XmpContainer::InDesignObject { uid: 260 }
+    );
+
+    assert!(packets.iter().all(|packet| !packet.main));
+}
+
+#[test]
+fn packet_after_indesign_object() {
+    let mut file = indd_object(5, b"not XMP");
+    file.extend_from_slice(packet("Loose").as_bytes());
+
+    let packets = xmp_compound::embedded_xmp(&file);
+    assert_eq!(packets.len(), 1);
+    assert_eq!(packets[0].container, XmpContainer::Unknown);
+}
+
+#[test]
+fn pdf_objects() {
+    let mut file = b"%PDF-1.6\n%\xe2\xe3\xcf\xd3\n".to_vec();
+
+    file.extend_from_slice(b"1 0 obj\n<</Type/Catalog/Metadata 3 0 R>>\nendobj\n");
+    file.extend_from_slice(b"3 0 obj\n<</Type/Metadata/Subtype/XML/Length 0>>stream\n");
+    file.extend_from_slice(packet("Document").as_bytes());
+    file.extend_from_slice(b"\nendstream\nendobj\n");
+
+    file.extend_from_slice(
+        b"12 0 obj\n<</Type/Metadata/Subtype/XML/Filter/FlateDecode/Length 163>>stream\r\n",
+    );
+    let compressed_start = file.len() as u64;
+    file.extend_from_slice(COMPRESSED_XMP);
+    file.extend_from_slice(b"\r\nendstream\nendobj\n");
+
+    file.extend_from_slice(b"14 0 obj\n<</Type/Metadata/Subtype/XML/Length 0>>stream\n");
+    file.extend_from_slice(packet("Placed").as_bytes());
+    file.extend_from_slice(b"\nendstream\nendobj\n%%EOF\n");
+
+    let packets = xmp_compound::embedded_xmp(&file);
+    assert_eq!(packets.len(), 3);
+
+    assert_eq!(label(&packets[0]), "Document");
+    assert_eq!(
+        packets[0].container,
+        XmpContainer::PdfObject {
+            number: 3,
+            compressed: false
+        }
+    );
+
+    assert_eq!(label(&packets[1]), "Compressed");
+    assert_eq!(
+        packets[1].container,
+        XmpContainer::PdfObject {
+            number: 12,
+            compressed: true
+        }
+    );
+    assert_eq!(packets[1].range.start, compressed_start);
+
+    assert_eq!(label(&packets[2]), "Placed");
+    assert_eq!(
+        packets[2].container,
+        XmpContainer::PdfObject {
+            number: 14,
+            compressed: false
+        }
+    );
+}
+
+#[test]
+fn damaged_compressed_stream() {
+    let mut file = b"%PDF-1.6\n".to_vec();
+    file.extend_from_slice(b"4 0 obj\n<</Type/Metadata/Filter/FlateDecode>>stream\n");
+    file.extend_from_slice(&COMPRESSED_XMP[..40]);
+    file.extend_from_slice(b"\nendstream\nendobj\n");
+
+    assert!(xmp_compound::embedded_xmp(&file).is_empty());
+}
+
+#[test]
+fn xmp_file_marks_main_packet() {
+    let mut f = XmpFile::new().unwrap();
+    f.open_file(
+        fixture_path("Purple Square.psd"),
+        OpenFileOptions::default(),
+    )
+    .unwrap();
+
+    let packets = f.embedded_xmp().unwrap();
+    let main: Vec<_> = packets.iter().filter(|packet| packet.main).collect();
+    assert_eq!(main.len(), 1);
+    assert_eq!(
+        main[0].range.start,
+        f.packet_info().unwrap().offset.unwrap()
+    );
+    assert_eq!(main[0].container, XmpContainer::Unknown);
+}
+
+#[test]
+fn xmp_file_several_packets() {
+    let tempdir = tempdir().unwrap();
+    let path = tempdir.path().join("several.txt");
+    fs::write(
+        &path,
+        format!("{}\n\n{}\n", packet("First"), packet("Second")),
+    )
+    .unwrap();
+
+    let mut f = XmpFile::new().unwrap();
+    f.open_file(&path, OpenFileOptions::default().use_packet_scanning())
+        .unwrap();
+
+    let packets = f.embedded_xmp().unwrap();
+    let labels: Vec<String> = packets.iter().map(label).collect();
+    assert_eq!(labels, ["First", "Second"]);
+    assert_eq!(packets.iter().filter(|packet| packet.main).count(), 1);
+}
+
+#[test]
+fn xmp_file_not_opened_from_path() {
+    let bytes = fs::read(fixture_path("Purple Square.psd")).unwrap();
+    let mut f =
+        XmpFile::from_bytes(&bytes, FileFormat::Photoshop, OpenFileOptions::default()).unwrap();
+
+    let err = f.embedded_xmp().unwrap_err();
+    assert_eq!(err.error_type, XmpErrorType::BadParam);
+}
@@ -0,0 +1,202 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::fs;
+
+use tempfile::tempdir;
+
+use crate::{tests::fixtures::*, xmp_ns, FileFormat, OpenFileOptions, XmpFile};
+
+const XMP_UUID: [u8; 16] = [
+    0xbe, 0x7a, 0xcf, 0xcb, 0x97, 0xa9, 0x42, 0xe8, 0x9c, 0x71, 0x99, 0x94, 0x91, 0xe3, 0xaf, 0xac,
+];
+
+fn bmff(boxes: &[(&[u8; 4], &[u8])]) -> Vec<u8> {
+    let mut file = Vec::new();
+
+    for (box_type, data) in boxes {
+        file.extend_from_slice(&(data.len() as u32 + 8).to_be_bytes());
+        file.extend_from_slice(*box_type);
+        file.extend_from_slice(data);
+    }
+
+    file
+}
+
+const FTYP: &[u8] = b"crx \0\0\0\x01crx isom";
+
+/// Returns a CR3 file with a `moov` box and image data, but no XMP.
+fn simple_cr3() -> Vec<u8> {
+    bmff(&[(b"ftyp", FTYP), (b"moov", &[1; 16]), (b"mdat", &[2; 32])])
+}
+
+/// Returns the types of the boxes in `file` and their positions.
+fn boxes(file: &[u8]) -> Vec<(String, usize)> {
+    let mut boxes = Vec::new();
+    let mut pos = 0;
+
+    while pos < file.len() {
+        let len = u32::from_be_bytes(file[pos..pos + 4].try_into().unwrap()) as usize;
+        let box_type = String::from_utf8_lossy(&file[pos + 4..pos + 8]).into_owned();
+        boxes.push((box_type, pos));
+        pos += len;
+    }
+
+    assert_eq!(pos, file.len());
+    boxes
+}
+
+#[test]
+fn check_format() {
+    assert_eq!(
+        XmpFile::check_buffer_format(&simple_cr3()).unwrap(),
+        Some(FileFormat::Cr3)
+    );
+}
+
+#[test]
+fn no_xmp() {
+    assert_eq!(label(&simple_cr3(), FileFormat::Cr3), None);
+}
+
+#[test]
+fn read_xmp() {
+    let packet = meta_with_label("Embedded").to_string();
+    let mut uuid = XMP_UUID.to_vec();
+    uuid.extend_from_slice(packet.as_bytes());
+
+    let cr3 = bmff(&[
+        (b"ftyp", FTYP),
+        (b"moov", &[1; 16]),
+        (b"uuid", &uuid),
+        (b"mdat", &[2; 32]),
+    ]);
+
+    assert_eq!(label(&cr3, FileFormat::Cr3).as_deref(), Some("Embedded"));
+}
+
+#[test]
+fn add_xmp_at_end() {
+    let original = simple_cr3();
+    let cr3 = put_label(&original, "Added");
+
+    // The image data doesn't move.
+    assert_eq!(cr3[..original.len()], original[..]);
+
+    let names: Vec<String> = boxes(&cr3).into_iter().map(|(name, _)| name).collect();
+    assert_eq!(names, ["ftyp", "moov", "mdat", "uuid"]);
+
+    assert_eq!(label(&cr3, FileFormat::Cr3).as_deref(), Some("Added"));
+}
+
+#[test]
+fn replace_xmp_in_place() {
+    let mut meta = meta_with_label("A much longer label than the next one");
+    meta.set_property(xmp_ns::XMP, "Rating", &"5".into())
+        .unwrap();
+
+    let packet = meta.to_string();
+    let mut uuid = XMP_UUID.to_vec();
+    uuid.extend_from_slice(packet.as_bytes());
+
+    let original = bmff(&[
+        (b"ftyp", FTYP),
+        (b"uuid", &uuid),
+        (b"moov", &[1; 16]),
+        (b"mdat", &[2; 32]),
+    ]);
+
+    let cr3 = put_label(&original, "Short");
+
+    assert_eq!(cr3.len(), original.len());
+    assert_eq!(boxes(&cr3), boxes(&original));
+    assert_eq!(label(&cr3, FileFormat::Cr3).as_deref(), Some("Short"));
+}
+
+#[test]
+fn move_xmp_that_does_not_fit() {
+    let packet = meta_with_label("A").to_string();
+    let mut uuid = XMP_UUID.to_vec();
+    uuid.extend_from_slice(packet.as_bytes());
+
+    let original = bmff(&[
+        (b"ftyp", FTYP),
+        (b"uuid", &uuid),
+        (b"moov", &[1; 16]),
+        (b"mdat", &[2; 32]),
+    ]);
+
+    let mut meta = meta_with_label("A label that is too long to fit in the old packet");
+    meta.set_property(xmp_ns::XMP, "Rating", &"5".into())
+        .unwrap();
+
+    let mut f = XmpFile::from_bytes(
+        &original,
+        FileFormat::Cr3,
+        OpenFileOptions::default().for_update(),
+    )
+    .unwrap();
+    f.put_xmp(&meta).unwrap();
+    let cr3 = f.into_bytes().unwrap();
+
+    let before = boxes(&original);
+    let after = boxes(&cr3);
+    let names: Vec<&str> = after.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["ftyp", "free", "moov", "mdat", "uuid"]);
+
+    // Boxes keep their positions.
+    assert_eq!(after[2], before[2]);
+    assert_eq!(after[3], before[3]);
+
+    assert_eq!(
+        label(&cr3, FileFormat::Cr3).as_deref(),
+        Some("A label that is too long to fit in the old packet")
+    );
+}
+
+#[test]
+fn last_box_without_size() {
+    let mut original = simple_cr3();
+    let mdat = boxes(&original)[2].1;
+    original[mdat..mdat + 4].copy_from_slice(&[0; 4]);
+
+    let cr3 = put_label(&original, "Sized");
+
+    let names: Vec<String> = boxes(&cr3).into_iter().map(|(name, _)| name).collect();
+    assert_eq!(names, ["ftyp", "moov", "mdat", "uuid"]);
+    assert_eq!(label(&cr3, FileFormat::Cr3).as_deref(), Some("Sized"));
+}
+
+#[test]
+fn truncated_box() {
+    let mut cr3 = simple_cr3();
+    cr3.truncate(cr3.len() - 1);
+
+    assert!(XmpFile::from_bytes(&cr3, FileFormat::Cr3, OpenFileOptions::default()).is_err());
+}
+
+#[test]
+fn sample_file() {
+    let tempdir = tempdir().unwrap();
+    let sample = temp_copy_of_fixture(tempdir.path(), "sample.cr3");
+
+    assert_eq!(label_of_file(&sample).as_deref(), Some("Sample"));
+
+    put_label_in_file(&sample, "Updated");
+    assert_eq!(label_of_file(&sample).as_deref(), Some("Updated"));
+
+    // The boxes before the XMP don't move.
+    let original = fs::read(fixture_path("sample.cr3")).unwrap();
+    let xmp_pos = boxes(&original)[2].1;
+    assert_eq!(fs::read(&sample).unwrap()[..xmp_pos], original[..xmp_pos]);
+}
@@ -0,0 +1,128 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::str::FromStr;
+
+use crate::{
+    xmp_crs::{self, CameraRawSettings},
+    xmp_ns, XmpMeta,
+};
+
+const SIDECAR: &str = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+    <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+        <rdf:Description rdf:about=""
+            xmlns:crs="http://ns.adobe.com/camera-raw-settings/1.0/"
+            xmlns:xmp="http://ns.adobe.com/xap/1.0/"
+            xmp:Rating="3"
+            crs:Version="15.0"
+            crs:ProcessVersion="11.0"
+            crs:WhiteBalance="Custom"
+            crs:Temperature="5450"
+            crs:Tint="+12"
+            crs:Exposure2012="+0.35"
+            crs:Contrast2012="-8"
+            crs:HasSettings="True"
+            crs:RawFileName="IMG_0001.CR2"
+            crs:ToneCurveName2012="Custom">
+            <crs:ToneCurvePV2012>
+                <rdf:Seq>
+                    <rdf:li>0, 0</rdf:li>
+                    <rdf:li>128, 140</rdf:li>
+                    <rdf:li>255, 255</rdf:li>
+                </rdf:Seq>
+            </crs:ToneCurvePV2012>
+            <crs:ToneCurvePV2012Red>
+                <rdf:Seq>
+                    <rdf:li>bogus</rdf:li>
+                </rdf:Seq>
+            </crs:ToneCurvePV2012Red>
+        </rdf:Description>
+    </rdf:RDF>
+</x:xmpmeta>"#;
+
+#[test]
+fn read_from() {
+    let m = XmpMeta::from_str(SIDECAR).unwrap();
+    let settings = CameraRawSettings::read_from(&m).unwrap();
+
+    assert_eq!(
+        settings,
+        CameraRawSettings {
+            version: Some("15.0".to_owned()),
+            process_version: Some("11.0".to_owned()),
+            has_settings: Some(true),
+            raw_file_name: Some("IMG_0001.CR2".to_owned()),
+            white_balance: Some("Custom".to_owned()),
+            temperature: Some(5450),
+            tint: Some(12),
+            exposure_2012: Some(0.35),
+            contrast_2012: Some(-8),
+            tone_curve_name_2012: Some("Custom".to_owned()),
+            tone_curve_pv2012: Some(vec![(0, 0), (128, 140), (255, 255)]),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn read_from_empty() {
+    let m = XmpMeta::new().unwrap();
+    assert_eq!(CameraRawSettings::read_from(&m), None);
+    assert!(!xmp_crs::has_settings(&m));
+}
+
+#[test]
+fn has_settings() {
+    let m = XmpMeta::from_str(SIDECAR).unwrap();
+    assert!(xmp_crs::has_settings(&m));
+}
+
+#[test]
+fn copy_settings() {
+    let from = XmpMeta::from_str(SIDECAR).unwrap();
+
+    let mut to = XmpMeta::new().unwrap();
+    to.set_property(xmp_ns::CAMERA_RAW, "Sharpness", &"40".into())
+        .unwrap();
+    to.set_property(xmp_ns::XMP, "CreatorTool", &"Other tool".into())
+        .unwrap();
+
+    xmp_crs::copy_settings(&from, &mut to).unwrap();
+
+    assert_eq!(
+        CameraRawSettings::read_from(&to),
+        CameraRawSettings::read_from(&from)
+    );
+
+    // Properties not in the typed view are copied too.
+    assert_eq!(to.array_len(xmp_ns::CAMERA_RAW, "ToneCurvePV2012Red"), 1);
+
+    // Stale settings are removed; other namespaces are untouched.
+    assert!(!to.contains_property(xmp_ns::CAMERA_RAW, "Sharpness"));
+    assert!(!to.contains_property(xmp_ns::XMP, "Rating"));
+    assert_eq!(
+        to.property(xmp_ns::XMP, "CreatorTool").unwrap().value,
+        "Other tool"
+    );
+}
+
+#[test]
+fn copy_empty_settings() {
+    let from = XmpMeta::new().unwrap();
+    let mut to = XmpMeta::from_str(SIDECAR).unwrap();
+
+    xmp_crs::copy_settings(&from, &mut to).unwrap();
+
+    assert!(!xmp_crs::has_settings(&to));
+    assert!(to.contains_property(xmp_ns::XMP, "Rating"));
+}
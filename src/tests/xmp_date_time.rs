@@ -554,6 +554,63 @@ mod from_ffi {
     }
 }
 
+mod from_system_time {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::{XmpDate, XmpDateTime, XmpTime, XmpTimeZone};
+
+    #[test]
+    fn epoch() {
+        assert_eq!(
+            XmpDateTime::from_system_time(UNIX_EPOCH).unwrap(),
+            XmpDateTime {
+                date: Some(XmpDate {
+                    year: 1970,
+                    month: 1,
+                    day: 1,
+                }),
+                time: Some(XmpTime {
+                    hour: 0,
+                    minute: 0,
+                    second: 0,
+                    nanosecond: 0,
+                    time_zone: Some(XmpTimeZone { hour: 0, minute: 0 }),
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn leap_day() {
+        // 2024-02-29T13:45:30.5Z
+        let t = UNIX_EPOCH + Duration::new(1_709_214_330, 500_000_000);
+
+        assert_eq!(
+            XmpDateTime::from_system_time(t).unwrap(),
+            XmpDateTime {
+                date: Some(XmpDate {
+                    year: 2024,
+                    month: 2,
+                    day: 29,
+                }),
+                time: Some(XmpTime {
+                    hour: 13,
+                    minute: 45,
+                    second: 30,
+                    nanosecond: 500_000_000,
+                    time_zone: Some(XmpTimeZone { hour: 0, minute: 0 }),
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn before_epoch() {
+        let t = UNIX_EPOCH - Duration::from_secs(1);
+        assert!(XmpDateTime::from_system_time(t).is_none());
+    }
+}
+
 mod as_ffi {
     use crate::{ffi, XmpDate, XmpDateTime, XmpTime, XmpTimeZone};
 
@@ -980,3 +1037,63 @@ mod fmt {
         assert_eq!(format!("{}", dt), "0000");
     }
 }
+
+mod from_str {
+    use crate::{XmpDate, XmpDateTime, XmpErrorType, XmpTime, XmpTimeZone};
+
+    #[test]
+    fn fully_populated() {
+        let dt: XmpDateTime = "2022-10-19T18:09:20.000000042+05:30".parse().unwrap();
+
+        assert_eq!(
+            dt,
+            XmpDateTime {
+                date: Some(XmpDate {
+                    year: 2022,
+                    month: 10,
+                    day: 19,
+                }),
+                time: Some(XmpTime {
+                    hour: 18,
+                    minute: 9,
+                    second: 20,
+                    nanosecond: 42,
+                    time_zone: Some(XmpTimeZone {
+                        hour: 5,
+                        minute: 30,
+                    }),
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn date_only() {
+        let dt: XmpDateTime = "2022-10-19".parse().unwrap();
+
+        assert_eq!(
+            dt,
+            XmpDateTime {
+                date: Some(XmpDate {
+                    year: 2022,
+                    month: 10,
+                    day: 19,
+                }),
+                time: None,
+            }
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let s = "2022-10-19T18:09:20-07:00";
+        let dt: XmpDateTime = s.parse().unwrap();
+        assert_eq!(dt.to_string(), s);
+    }
+
+    #[test]
+    fn invalid() {
+        let err = "not a date".parse::<XmpDateTime>().unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::BadValue);
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::str::FromStr;
+
+use crate::{xmp_ns, ArrayForm, DiffKind, XmpMeta, XmpValue};
+
+const OLD: &str = r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <rdf:Description rdf:about=""
+        xmlns:dc="http://purl.org/dc/elements/1.1/"
+        xmlns:xmp="http://ns.adobe.com/xap/1.0/">
+        <dc:title>
+            <rdf:Alt>
+                <rdf:li xml:lang="x-default">Sunset</rdf:li>
+                <rdf:li xml:lang="en-US">Sunset</rdf:li>
+                <rdf:li xml:lang="fr-FR">Coucher de soleil</rdf:li>
+            </rdf:Alt>
+        </dc:title>
+        <dc:subject>
+            <rdf:Bag>
+                <rdf:li>sun</rdf:li>
+                <rdf:li>sea</rdf:li>
+            </rdf:Bag>
+        </dc:subject>
+        <xmp:Label>Red</xmp:Label>
+        <xmp:Rating>3</xmp:Rating>
+    </rdf:Description>
+</rdf:RDF>"#;
+
+// Same as OLD, but with the languages of `dc:title` in a different order.
+const REORDERED: &str = r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <rdf:Description rdf:about=""
+        xmlns:dc="http://purl.org/dc/elements/1.1/"
+        xmlns:xmp="http://ns.adobe.com/xap/1.0/">
+        <dc:title>
+            <rdf:Alt>
+                <rdf:li xml:lang="x-default">Sunset</rdf:li>
+                <rdf:li xml:lang="fr-FR">Coucher de soleil</rdf:li>
+                <rdf:li xml:lang="en-US">Sunset</rdf:li>
+            </rdf:Alt>
+        </dc:title>
+        <dc:subject>
+            <rdf:Bag>
+                <rdf:li>sun</rdf:li>
+                <rdf:li>sea</rdf:li>
+            </rdf:Bag>
+        </dc:subject>
+        <xmp:Label>Red</xmp:Label>
+        <xmp:Rating>3</xmp:Rating>
+    </rdf:Description>
+</rdf:RDF>"#;
+
+fn edited() -> XmpMeta {
+    let mut m = XmpMeta::from_str(OLD).unwrap();
+
+    m.set_localized_text(xmp_ns::DC, "title", None, "fr-FR", "Lever de soleil")
+        .unwrap();
+    m.append_array_item(
+        xmp_ns::DC,
+        &XmpValue::from("subject").set_is_array(true),
+        &"sky".into(),
+    )
+    .unwrap();
+    m.set_property(xmp_ns::XMP, "Label", &"Green".into())
+        .unwrap();
+    m.delete_property(xmp_ns::XMP, "Rating").unwrap();
+
+    m
+}
+
+#[test]
+fn identical() {
+    let m = XmpMeta::from_str(OLD).unwrap();
+    let diff = m.diff(&m.clone());
+
+    assert!(diff.is_empty());
+    assert_eq!(diff.to_text(), "No differences\n");
+    assert_eq!(diff.to_markdown(), "No differences\n");
+}
+
+#[test]
+fn alt_text_reordered() {
+    let old = XmpMeta::from_str(OLD).unwrap();
+    let new = XmpMeta::from_str(REORDERED).unwrap();
+
+    assert!(old.diff(&new).is_empty());
+}
+
+#[test]
+fn differences() {
+    let old = XmpMeta::from_str(OLD).unwrap();
+    let diff = old.diff(&edited());
+
+    let summary: Vec<_> = diff
+        .differences
+        .iter()
+        .map(|d| {
+            (
+                d.kind,
+                d.namespace.as_str(),
+                d.path.as_str(),
+                d.old_value.as_deref(),
+                d.new_value.as_deref(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        summary,
+        [
+            (
+                DiffKind::Changed,
+                xmp_ns::XMP,
+                "xmp:Label",
+                Some("Red"),
+                Some("Green")
+            ),
+            (
+                DiffKind::Removed,
+                xmp_ns::XMP,
+                "xmp:Rating",
+                Some("3"),
+                None
+            ),
+            (
+                DiffKind::Added,
+                xmp_ns::DC,
+                "dc:subject[3]",
+                None,
+                Some("sky")
+            ),
+            (
+                DiffKind::Changed,
+                xmp_ns::DC,
+                "dc:title[?xml:lang=\"fr-FR\"]",
+                Some("Coucher de soleil"),
+                Some("Lever de soleil")
+            ),
+        ]
+    );
+}
+
+#[test]
+fn to_text() {
+    let old = XmpMeta::from_str(OLD).unwrap();
+
+    assert_eq!(
+        old.diff(&edited()).to_text(),
+        "4 differences\n\
+         \n\
+         xmp (http://ns.adobe.com/xap/1.0/)\n\
+         \x20 ~ xmp:Label = \"Red\" -> \"Green\"\n\
+         \x20 - xmp:Rating = \"3\"\n\
+         \n\
+         dc (http://purl.org/dc/elements/1.1/)\n\
+         \x20 + dc:subject[3] = \"sky\"\n\
+         \x20 ~ dc:title[?xml:lang=\"fr-FR\"] = \"Coucher de soleil\" -> \"Lever de soleil\"\n"
+    );
+}
+
+#[test]
+fn to_markdown() {
+    let old = XmpMeta::from_str(OLD).unwrap();
+    let mut new = edited();
+    new.set_property(xmp_ns::XMP, "Nickname", &"a|b".into())
+        .unwrap();
+
+    assert_eq!(
+        old.diff(&new).to_markdown(),
+        "5 differences\n\
+         \n\
+         ### xmp (`http://ns.adobe.com/xap/1.0/`)\n\
+         \n\
+         | Change | Path | Old value | New value |\n\
+         |--------|------|-----------|-----------|\n\
+         | Changed | `xmp:Label` | \"Red\" | \"Green\" |\n\
+         | Added | `xmp:Nickname` |  | \"a\\|b\" |\n\
+         | Removed | `xmp:Rating` | \"3\" |  |\n\
+         \n\
+         ### dc (`http://purl.org/dc/elements/1.1/`)\n\
+         \n\
+         | Change | Path | Old value | New value |\n\
+         |--------|------|-----------|-----------|\n\
+         | Added | `dc:subject[3]` |  | \"sky\" |\n\
+         | Changed | `dc:title[?xml:lang=\"fr-FR\"]` | \"Coucher de soleil\" | \"Lever de soleil\" |\n"
+    );
+}
+
+#[test]
+fn empty_array() {
+    let old = XmpMeta::new().unwrap();
+    let mut new = XmpMeta::new().unwrap();
+    new.set_array(xmp_ns::DC, "subject", ArrayForm::Bag, &[])
+        .unwrap();
+
+    let diff = old.diff(&new);
+
+    assert_eq!(diff.differences.len(), 1);
+    assert_eq!(diff.differences[0].kind, DiffKind::Added);
+    assert_eq!(diff.differences[0].path, "dc:subject");
+    assert_eq!(diff.differences[0].new_value.as_deref(), Some("[]"));
+}
@@ -0,0 +1,262 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::str::FromStr;
+
+use crate::{
+    xmp_dm::{self, Marker, Time, Timecode, TimecodeFormat, Track},
+    xmp_ns, XmpErrorType, XmpMeta,
+};
+
+const VIDEO: &str = r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <rdf:Description rdf:about="" xmlns:xmpDM="http://ns.adobe.com/xmp/1.0/DynamicMedia/"
+        xmpDM:tempo="120.5"
+        xmpDM:key="F#">
+        <xmpDM:startTimecode xmpDM:timeValue="01:00:00;00" xmpDM:timeFormat="2997DropTimecode"/>
+        <xmpDM:duration xmpDM:value="96000" xmpDM:scale="1/48000"/>
+        <xmpDM:Tracks>
+            <rdf:Bag>
+                <rdf:li rdf:parseType="Resource">
+                    <xmpDM:trackName>Chapters</xmpDM:trackName>
+                    <xmpDM:trackType>Chapter</xmpDM:trackType>
+                    <xmpDM:frameRate>f30</xmpDM:frameRate>
+                    <xmpDM:markers>
+                        <rdf:Seq>
+                            <rdf:li xmpDM:startTime="0" xmpDM:name="Intro"/>
+                            <rdf:li xmpDM:startTime="900" xmpDM:name="Main" xmpDM:comment="Act one"/>
+                        </rdf:Seq>
+                    </xmpDM:markers>
+                </rdf:li>
+            </rdf:Bag>
+        </xmpDM:Tracks>
+    </rdf:Description>
+</rdf:RDF>"#;
+
+fn chapters() -> Track {
+    Track {
+        track_name: Some("Chapters".to_owned()),
+        track_type: Some("Chapter".to_owned()),
+        frame_rate: Some("f30".to_owned()),
+        markers: vec![
+            Marker {
+                name: Some("Intro".to_owned()),
+                start_time: Some("0".to_owned()),
+                ..Default::default()
+            },
+            Marker {
+                name: Some("Main".to_owned()),
+                comment: Some("Act one".to_owned()),
+                start_time: Some("900".to_owned()),
+                ..Default::default()
+            },
+        ],
+    }
+}
+
+#[test]
+fn timecode_format() {
+    for format in [
+        TimecodeFormat::Fps23976,
+        TimecodeFormat::Fps24,
+        TimecodeFormat::Fps25,
+        TimecodeFormat::Fps2997Drop,
+        TimecodeFormat::Fps2997NonDrop,
+        TimecodeFormat::Fps30,
+        TimecodeFormat::Fps50,
+        TimecodeFormat::Fps5994Drop,
+        TimecodeFormat::Fps5994NonDrop,
+        TimecodeFormat::Fps60,
+    ] {
+        assert_eq!(TimecodeFormat::from_str(format.as_str()).unwrap(), format);
+        assert_eq!(format.to_string(), format.as_str());
+    }
+
+    assert_eq!(
+        TimecodeFormat::from_str("30fps").unwrap_err().error_type,
+        XmpErrorType::BadValue
+    );
+}
+
+#[test]
+fn timecode_new() {
+    let tc = Timecode::new("01:02:03:04", TimecodeFormat::Fps25).unwrap();
+    assert_eq!(tc.time_value(), "01:02:03:04");
+    assert_eq!(tc.time_format(), TimecodeFormat::Fps25);
+
+    Timecode::new("01;02;03;04", TimecodeFormat::Fps2997Drop).unwrap();
+
+    for bad in [
+        "1:02:03:04",
+        "01:02:03",
+        "01:02:03:04:05",
+        "aa:bb:cc:dd",
+        "",
+    ] {
+        assert_eq!(
+            Timecode::new(bad, TimecodeFormat::Fps25)
+                .unwrap_err()
+                .error_type,
+            XmpErrorType::BadValue
+        );
+    }
+
+    assert_eq!(
+        Timecode::new("01;02;03;04", TimecodeFormat::Fps30)
+            .unwrap_err()
+            .error_type,
+        XmpErrorType::BadValue
+    );
+}
+
+#[test]
+fn start_timecode() {
+    let m = XmpMeta::from_str(VIDEO).unwrap();
+    assert_eq!(
+        xmp_dm::start_timecode(&m),
+        Some(Timecode::new("01:00:00;00", TimecodeFormat::Fps2997Drop).unwrap())
+    );
+
+    let m = XmpMeta::new().unwrap();
+    assert_eq!(xmp_dm::start_timecode(&m), None);
+}
+
+#[test]
+fn set_start_timecode() {
+    let mut m = XmpMeta::new().unwrap();
+    let tc = Timecode::new("00:59:58:00", TimecodeFormat::Fps24).unwrap();
+
+    xmp_dm::set_start_timecode(&mut m, &tc).unwrap();
+
+    assert_eq!(xmp_dm::start_timecode(&m), Some(tc));
+    assert_eq!(
+        m.struct_field(
+            xmp_ns::DYNAMIC_MEDIA,
+            "startTimecode",
+            xmp_ns::DYNAMIC_MEDIA,
+            "timeFormat"
+        )
+        .unwrap()
+        .value,
+        "24Timecode"
+    );
+}
+
+#[test]
+fn duration() {
+    let m = XmpMeta::from_str(VIDEO).unwrap();
+    let duration = xmp_dm::duration(&m).unwrap();
+
+    assert_eq!(
+        duration,
+        Time {
+            value: 96000,
+            scale_numerator: 1,
+            scale_denominator: 48000
+        }
+    );
+    assert_eq!(duration.seconds(), Some(2.0));
+}
+
+#[test]
+fn set_duration() {
+    let mut m = XmpMeta::new().unwrap();
+    let duration = Time {
+        value: 300,
+        scale_numerator: 1001,
+        scale_denominator: 30000,
+    };
+
+    xmp_dm::set_duration(&mut m, &duration).unwrap();
+    assert_eq!(xmp_dm::duration(&m), Some(duration));
+
+    let err = xmp_dm::set_duration(
+        &mut m,
+        &Time {
+            value: 1,
+            scale_numerator: 1,
+            scale_denominator: 0,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err.error_type, XmpErrorType::BadValue);
+    assert_eq!(xmp_dm::duration(&m), Some(duration));
+}
+
+#[test]
+fn tracks() {
+    let m = XmpMeta::from_str(VIDEO).unwrap();
+    assert_eq!(xmp_dm::tracks(&m), [chapters()]);
+
+    let m = XmpMeta::new().unwrap();
+    assert!(xmp_dm::tracks(&m).is_empty());
+}
+
+#[test]
+fn append_track() {
+    let mut m = XmpMeta::new().unwrap();
+
+    let cues = Track {
+        track_name: Some("Cues".to_owned()),
+        ..Default::default()
+    };
+
+    xmp_dm::append_track(&mut m, &chapters()).unwrap();
+    xmp_dm::append_track(&mut m, &cues).unwrap();
+
+    assert_eq!(xmp_dm::tracks(&m), [chapters(), cues]);
+}
+
+#[test]
+fn append_track_init_fail() {
+    let mut m = XmpMeta::new_fail();
+    assert_eq!(
+        xmp_dm::append_track(&mut m, &chapters())
+            .unwrap_err()
+            .error_type,
+        XmpErrorType::NoCppToolkit
+    );
+}
+
+#[test]
+fn tempo() {
+    let mut m = XmpMeta::from_str(VIDEO).unwrap();
+    assert_eq!(xmp_dm::tempo(&m), Some(120.5));
+
+    xmp_dm::set_tempo(&mut m, 90.0).unwrap();
+    assert_eq!(xmp_dm::tempo(&m), Some(90.0));
+
+    for bad in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        assert_eq!(
+            xmp_dm::set_tempo(&mut m, bad).unwrap_err().error_type,
+            XmpErrorType::BadValue
+        );
+    }
+}
+
+#[test]
+fn key() {
+    let mut m = XmpMeta::from_str(VIDEO).unwrap();
+    assert_eq!(xmp_dm::key(&m).as_deref(), Some("F#"));
+
+    xmp_dm::set_key(&mut m, "A").unwrap();
+    assert_eq!(xmp_dm::key(&m).as_deref(), Some("A"));
+
+    assert_eq!(
+        xmp_dm::set_key(&mut m, "H").unwrap_err().error_type,
+        XmpErrorType::BadValue
+    );
+
+    m.set_property(xmp_ns::DYNAMIC_MEDIA, "key", &"Bb".into())
+        .unwrap();
+    assert_eq!(xmp_dm::key(&m), None);
+}
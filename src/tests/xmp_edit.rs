@@ -0,0 +1,132 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use crate::{tests::fixtures::*, xmp_ns, XmpMeta};
+
+fn label(m: &XmpMeta) -> Option<String> {
+    m.property(xmp_ns::XMP, "Label").map(|v| v.value)
+}
+
+#[test]
+fn commit() {
+    let mut m = meta_with_label("Red");
+
+    let mut edit = m.begin_edit();
+    edit.set_property(xmp_ns::XMP, "Label", &"Green".into())
+        .unwrap();
+    edit.set_property(xmp_ns::XMP, "CreatorTool", &"Example".into())
+        .unwrap();
+    assert_eq!(label(&edit).as_deref(), Some("Green"));
+    edit.commit();
+
+    assert_eq!(label(&m).as_deref(), Some("Green"));
+    assert!(m.contains_property(xmp_ns::XMP, "CreatorTool"));
+}
+
+#[test]
+fn rollback() {
+    let mut m = meta_with_label("Red");
+
+    let mut edit = m.begin_edit();
+    edit.delete_property(xmp_ns::XMP, "Label").unwrap();
+    assert_eq!(label(&edit), None);
+    edit.rollback();
+
+    assert_eq!(label(&m).as_deref(), Some("Red"));
+}
+
+#[test]
+fn drop_rolls_back() {
+    let mut m = meta_with_label("Red");
+
+    {
+        let mut edit = m.begin_edit();
+        edit.set_property(xmp_ns::XMP, "Label", &"Green".into())
+            .unwrap();
+    }
+
+    assert_eq!(label(&m).as_deref(), Some("Red"));
+}
+
+#[test]
+fn nested() {
+    let mut m = meta_with_label("Red");
+
+    let mut outer = m.begin_edit();
+    outer
+        .set_property(xmp_ns::XMP, "Label", &"Green".into())
+        .unwrap();
+
+    let mut inner = outer.begin_edit();
+    inner
+        .set_property(xmp_ns::XMP, "Label", &"Blue".into())
+        .unwrap();
+    inner.rollback();
+
+    assert_eq!(label(&outer).as_deref(), Some("Green"));
+    outer.commit();
+
+    assert_eq!(label(&m).as_deref(), Some("Green"));
+}
+
+#[test]
+fn listeners_notified_on_commit_only() {
+    let mut m = XmpMeta::new().unwrap();
+
+    let count = Arc::new(AtomicUsize::new(0));
+    let listener_count = count.clone();
+    m.on_change(move |_| {
+        listener_count.fetch_add(1, Ordering::Relaxed);
+    });
+
+    let mut edit = m.begin_edit();
+    edit.set_property(xmp_ns::XMP, "Label", &"Red".into())
+        .unwrap();
+    edit.rollback();
+    assert_eq!(count.load(Ordering::Relaxed), 0);
+
+    let mut edit = m.begin_edit();
+    edit.set_property(xmp_ns::XMP, "Label", &"Green".into())
+        .unwrap();
+    assert_eq!(count.load(Ordering::Relaxed), 0);
+    edit.commit();
+    assert_eq!(count.load(Ordering::Relaxed), 1);
+
+    // Listeners survive the commit.
+    m.delete_property(xmp_ns::XMP, "Label").unwrap();
+    assert_eq!(count.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn access_stats() {
+    let mut m = XmpMeta::new().unwrap();
+    m.enable_access_stats();
+
+    let mut edit = m.begin_edit();
+    edit.set_property(xmp_ns::XMP, "Label", &"Red".into())
+        .unwrap();
+    edit.rollback();
+
+    let mut edit = m.begin_edit();
+    edit.set_property(xmp_ns::XMP, "Label", &"Green".into())
+        .unwrap();
+    edit.commit();
+
+    let stats = m.access_stats().unwrap();
+    assert_eq!(stats.get(xmp_ns::XMP, "Label").writes, 2);
+}
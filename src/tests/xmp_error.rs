@@ -115,3 +115,44 @@ mod impl_error {
         );
     }
 }
+
+mod category {
+    use crate::{XmpError, XmpErrorCategory, XmpErrorType};
+
+    fn err(error_type: XmpErrorType) -> XmpError {
+        XmpError {
+            error_type,
+            debug_message: "".to_owned(),
+        }
+    }
+
+    #[test]
+    fn category() {
+        assert_eq!(
+            err(XmpErrorType::BadRdf).category(),
+            XmpErrorCategory::CorruptData
+        );
+        assert_eq!(
+            err(XmpErrorType::FilePermission).category(),
+            XmpErrorCategory::UserError
+        );
+    }
+
+    #[test]
+    fn is_transient() {
+        assert!(err(XmpErrorType::DiskSpace).is_transient());
+        assert!(err(XmpErrorType::FileLocked).is_transient());
+        assert!(!err(XmpErrorType::FilePermission).is_transient());
+        assert!(!err(XmpErrorType::BadParam).is_transient());
+        assert!(!err(XmpErrorType::BadXml).is_transient());
+    }
+
+    #[test]
+    fn is_recoverable() {
+        assert!(err(XmpErrorType::BadParam).is_recoverable());
+        assert!(err(XmpErrorType::BadXml).is_recoverable());
+        assert!(err(XmpErrorType::WriteError).is_recoverable());
+        assert!(!err(XmpErrorType::NoCppToolkit).is_recoverable());
+        assert!(!err(XmpErrorType::InternalFailure).is_recoverable());
+    }
+}
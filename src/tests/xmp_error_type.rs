@@ -62,3 +62,64 @@ mod impl_error {
         assert_eq!(format!("{}", XmpErrorType::BadPng), "PNG format error");
     }
 }
+
+mod category {
+    use crate::{XmpErrorCategory, XmpErrorType};
+
+    #[test]
+    fn samples() {
+        assert_eq!(
+            XmpErrorType::BadXPath.category(),
+            XmpErrorCategory::UserError
+        );
+        assert_eq!(
+            XmpErrorType::FileLocked.category(),
+            XmpErrorCategory::Transient
+        );
+        assert_eq!(
+            XmpErrorType::BadJpeg.category(),
+            XmpErrorCategory::CorruptData
+        );
+        assert_eq!(
+            XmpErrorType::Unimplemented.category(),
+            XmpErrorCategory::Unsupported
+        );
+        assert_eq!(
+            XmpErrorType::ProgressAbort.category(),
+            XmpErrorCategory::Cancelled
+        );
+        assert_eq!(
+            XmpErrorType::NoCppToolkit.category(),
+            XmpErrorCategory::ToolkitUnavailable
+        );
+        assert_eq!(
+            XmpErrorType::FileTooLarge.category(),
+            XmpErrorCategory::UserError
+        );
+        assert_eq!(
+            XmpErrorType::FileSignatureMismatch.category(),
+            XmpErrorCategory::CorruptData
+        );
+        assert_eq!(
+            XmpErrorType::SvgMetadataElementMissing.category(),
+            XmpErrorCategory::UserError
+        );
+        assert_eq!(
+            XmpErrorType::ToolkitAlreadyInitialized.category(),
+            XmpErrorCategory::UserError
+        );
+        assert_eq!(
+            XmpErrorType::XmpUpdateNotInPlace.category(),
+            XmpErrorCategory::Unsupported
+        );
+        assert_eq!(
+            XmpErrorType::FileSharingViolation.category(),
+            XmpErrorCategory::Transient
+        );
+        assert_eq!(
+            XmpErrorType::FileLocked.category(),
+            XmpErrorCategory::Transient
+        );
+        assert_eq!(XmpErrorType::Unknown.category(), XmpErrorCategory::Internal);
+    }
+}
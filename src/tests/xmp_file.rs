@@ -48,7 +48,7 @@ fn open_and_edit_file() {
         assert!(f.can_put_xmp(&m));
         f.put_xmp(&m).unwrap();
 
-        f.close();
+        f.close().unwrap();
     }
 
     // Let's make sure we actually wrote to the file.
@@ -107,104 +107,2631 @@ mod open_file {
     }
 }
 
+mod open_stream {
+    use std::{
+        fs::{self, File},
+        io::{self, Cursor, Read, Seek, SeekFrom},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+    };
+
+    use crate::{tests::fixtures::*, xmp_ns, FileFormat, OpenFileOptions, XmpErrorType, XmpFile};
+
+    #[test]
+    fn from_file() {
+        let mut f = XmpFile::new().unwrap();
+        let file = File::open(fixture_path("Purple Square.psd")).unwrap();
+
+        f.open_stream(
+            file,
+            FileFormat::Photoshop,
+            OpenFileOptions::default().for_read(),
+        )
+        .unwrap();
+
+        let m = f.xmp().unwrap();
+        assert_eq!(
+            m.property(xmp_ns::XMP, "CreatorTool").unwrap().value,
+            "Adobe Photoshop CS2 Windows"
+        );
+
+        f.close().unwrap();
+    }
+
+    #[test]
+    fn from_memory_without_hint() {
+        let mut f = XmpFile::new().unwrap();
+        let bytes = fs::read(fixture_path("image2.jpg")).unwrap();
+
+        f.open_stream(
+            Cursor::new(bytes),
+            FileFormat::Unknown,
+            OpenFileOptions::default().for_read(),
+        )
+        .unwrap();
+
+        assert!(f.xmp().is_some());
+    }
+
+    #[test]
+    fn no_xmp() {
+        let mut f = XmpFile::new().unwrap();
+        let bytes = fs::read(fixture_path("no_xmp.txt")).unwrap();
+
+        f.open_stream(
+            Cursor::new(bytes),
+            FileFormat::Text,
+            OpenFileOptions::default().for_read(),
+        )
+        .unwrap();
+
+        assert!(f.xmp().is_none());
+    }
+
+    #[test]
+    fn for_update_not_allowed() {
+        let mut f = XmpFile::new().unwrap();
+
+        let err = f
+            .open_stream(
+                Cursor::new(Vec::new()),
+                FileFormat::Jpeg,
+                OpenFileOptions::default().for_update(),
+            )
+            .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::BadParam);
+    }
+
+    #[test]
+    fn too_large() {
+        let mut f = XmpFile::new().unwrap();
+        let bytes = fs::read(fixture_path("Purple Square.psd")).unwrap();
+
+        let err = f
+            .open_stream(
+                Cursor::new(bytes),
+                FileFormat::Photoshop,
+                OpenFileOptions::default().for_read().max_file_size(1000),
+            )
+            .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::FileTooLarge);
+    }
+
+    // Claims to be 4096 bytes long, but panics when read.
+    struct PanickingReader;
+
+    impl Read for PanickingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            panic!("read failed");
+        }
+    }
+
+    impl Seek for PanickingReader {
+        fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+            match pos {
+                SeekFrom::End(_) => Ok(4096),
+                _ => Ok(0),
+            }
+        }
+    }
+
+    #[test]
+    fn reader_panics() {
+        let mut f = XmpFile::new().unwrap();
+
+        let err = f
+            .open_stream(
+                PanickingReader,
+                FileFormat::Jpeg,
+                OpenFileOptions::default().for_read().use_smart_handler(),
+            )
+            .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::ReadError);
+    }
+
+    // Sets `dropped` when dropped.
+    struct TrackedReader {
+        inner: File,
+        dropped: Arc<AtomicBool>,
+    }
+
+    impl Read for TrackedReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.inner.read(buf)
+        }
+    }
+
+    impl Seek for TrackedReader {
+        fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    impl Drop for TrackedReader {
+        fn drop(&mut self) {
+            self.dropped.store(true, Ordering::Release);
+        }
+    }
+
+    #[test]
+    fn reopen_without_close() {
+        let dropped = Arc::new(AtomicBool::new(false));
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_stream(
+            TrackedReader {
+                inner: File::open(fixture_path("Purple Square.psd")).unwrap(),
+                dropped: dropped.clone(),
+            },
+            FileFormat::Photoshop,
+            OpenFileOptions::default().for_read(),
+        )
+        .unwrap();
+
+        assert!(!dropped.load(Ordering::Acquire));
+
+        f.open_file(
+            fixture_path("image2.jpg"),
+            OpenFileOptions::default().for_read(),
+        )
+        .unwrap();
+
+        // The stream is released once another file is opened.
+        assert!(dropped.load(Ordering::Acquire));
+        assert!(f.xmp().is_some());
+    }
+}
+
+mod open_stream_for_update {
+    use std::fs::{self, OpenOptions};
+
+    use tempfile::tempdir;
+
+    use crate::{tests::fixtures::*, xmp_ns, FileFormat, OpenFileOptions, XmpFile, XmpMeta};
+
+    fn update_label(format: FileFormat, fixture: &str, label: &str) {
+        let tempdir = tempdir().unwrap();
+        let path = temp_copy_of_fixture(tempdir.path(), fixture);
+
+        let stream = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&path)
+            .unwrap();
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_stream_for_update(stream, format, OpenFileOptions::default())
+            .unwrap();
+
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &label.into()).unwrap();
+        f.put_xmp(&m).unwrap();
+        f.close().unwrap();
+
+        let m = XmpMeta::from_file(&path).unwrap();
+        assert_eq!(m.property(xmp_ns::XMP, "Label").unwrap().value, label);
+
+        // The file is still readable by the C++ XMP Toolkit.
+        let bytes = fs::read(&path).unwrap();
+        let mut f = XmpFile::from_bytes(&bytes, format, OpenFileOptions::default()).unwrap();
+        assert!(f.xmp().is_some());
+    }
+
+    #[test]
+    fn in_place() {
+        update_label(FileFormat::Photoshop, "Purple Square.psd", "Short");
+    }
+
+    #[test]
+    fn rewrite() {
+        // A label this long doesn't fit in the existing padding, so the
+        // file is rewritten through a temporary buffer.
+        update_label(FileFormat::Jpeg, "image2.jpg", &"Long label ".repeat(1000));
+    }
+
+    #[test]
+    fn without_update() {
+        let tempdir = tempdir().unwrap();
+        let path = temp_copy_of_fixture(tempdir.path(), "image2.jpg");
+        let original = fs::read(&path).unwrap();
+
+        let stream = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&path)
+            .unwrap();
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_stream_for_update(stream, FileFormat::Jpeg, OpenFileOptions::default())
+            .unwrap();
+        f.close().unwrap();
+
+        assert_eq!(fs::read(&path).unwrap(), original);
+    }
+}
+
+mod from_bytes {
+    use std::fs;
+
+    use crate::{tests::fixtures::*, xmp_ns, FileFormat, OpenFileOptions, XmpErrorType, XmpFile};
+
+    #[test]
+    fn read() {
+        let bytes = fs::read(fixture_path("Purple Square.psd")).unwrap();
+
+        let mut f =
+            XmpFile::from_bytes(&bytes, FileFormat::Photoshop, OpenFileOptions::default()).unwrap();
+
+        let m = f.xmp().unwrap();
+        assert_eq!(
+            m.property(xmp_ns::XMP, "CreatorTool").unwrap().value,
+            "Adobe Photoshop CS2 Windows"
+        );
+
+        // Reading doesn't change the content.
+        assert_eq!(f.into_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn update() {
+        let bytes = fs::read(fixture_path("Purple Square.psd")).unwrap();
+
+        let mut f = XmpFile::from_bytes(
+            &bytes,
+            FileFormat::Unknown,
+            OpenFileOptions::default().for_update(),
+        )
+        .unwrap();
+
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &"in memory".into())
+            .unwrap();
+        f.put_xmp(&m).unwrap();
+
+        let updated = f.into_bytes().unwrap();
+        assert_ne!(updated, bytes);
+
+        let mut f =
+            XmpFile::from_bytes(&updated, FileFormat::Photoshop, OpenFileOptions::default())
+                .unwrap();
+
+        let m = f.xmp().unwrap();
+        assert_eq!(m.property(xmp_ns::XMP, "Label").unwrap().value, "in memory");
+    }
+
+    #[test]
+    fn too_large() {
+        let bytes = fs::read(fixture_path("Purple Square.psd")).unwrap();
+
+        let err = XmpFile::from_bytes(
+            &bytes,
+            FileFormat::Photoshop,
+            OpenFileOptions::default().max_file_size(1000),
+        )
+        .err()
+        .unwrap();
+
+        assert_eq!(err.error_type, XmpErrorType::FileTooLarge);
+    }
+
+    #[test]
+    fn into_bytes_not_in_memory() {
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            fixture_path("Purple Square.psd"),
+            OpenFileOptions::default(),
+        )
+        .unwrap();
+
+        let err = f.into_bytes().unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::BadParam);
+    }
+}
+
+mod check_file_format {
+    use std::fs;
+
+    use crate::{tests::fixtures::*, FileFormat, XmpFile};
+
+    #[test]
+    fn smart_handler() {
+        assert_eq!(
+            XmpFile::check_file_format(fixture_path("Purple Square.psd")).unwrap(),
+            Some(FileFormat::Photoshop)
+        );
+
+        assert_eq!(
+            XmpFile::check_file_format(fixture_path("image2.jpg")).unwrap(),
+            Some(FileFormat::Jpeg)
+        );
+    }
+
+    #[test]
+    fn packet_scanning_only() {
+        assert_eq!(
+            XmpFile::check_file_format(fixture_path("no_xmp.txt")).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn buffer() {
+        let bytes = fs::read(fixture_path("Purple Square.psd")).unwrap();
+        assert_eq!(
+            XmpFile::check_buffer_format(&bytes).unwrap(),
+            Some(FileFormat::Photoshop)
+        );
+
+        let bytes = fs::read(fixture_path("no_xmp.txt")).unwrap();
+        assert_eq!(XmpFile::check_buffer_format(&bytes).unwrap(), None);
+    }
+
+    #[test]
+    fn format_codes() {
+        assert_eq!(
+            FileFormat::try_from(u32::from_be_bytes(*b"JPEG")).unwrap(),
+            FileFormat::Jpeg
+        );
+        assert!(FileFormat::try_from(u32::from_be_bytes(*b"????")).is_err());
+    }
+
+    #[test]
+    fn folder_based() {
+        assert_eq!(
+            FileFormat::try_from(u32::from_be_bytes(*b"P2  ")).unwrap(),
+            FileFormat::P2
+        );
+        assert!(FileFormat::P2.is_folder_based());
+        assert!(FileFormat::XdcamEx.is_folder_based());
+        assert!(!FileFormat::Mxf.is_folder_based());
+    }
+}
+
+mod format_info {
+    use crate::{FileFormat, XmpFile};
+
+    #[test]
+    fn smart_handler() {
+        let jpeg = XmpFile::format_info(FileFormat::Jpeg).unwrap().unwrap();
+        assert!(jpeg.can_inject_xmp);
+        assert!(jpeg.can_add_xmp());
+        assert!(!jpeg.folder_based_format);
+    }
+
+    #[test]
+    fn native_handler() {
+        let webp = XmpFile::format_info(FileFormat::WebP).unwrap().unwrap();
+        assert!(webp.can_add_xmp());
+        assert!(webp.needs_preloading);
+
+        let mxf = XmpFile::format_info(FileFormat::Mxf).unwrap().unwrap();
+        assert!(mxf.prefers_in_place);
+        assert!(!mxf.can_add_xmp());
+    }
+
+    #[test]
+    fn no_smart_handler() {
+        assert_eq!(XmpFile::format_info(FileFormat::Text).unwrap(), None);
+    }
+}
+
+mod file_mod_date {
+    use crate::{tests::fixtures::*, XmpErrorType, XmpFile};
+
+    #[test]
+    fn main_file_only() {
+        let dt = XmpFile::file_mod_date(fixture_path("Purple Square.psd"), false)
+            .unwrap()
+            .unwrap();
+
+        assert!(dt.date.is_some());
+        assert!(dt.time.is_some());
+    }
+
+    #[test]
+    fn associated_resources() {
+        let dt = XmpFile::file_mod_date(fixture_path("Purple Square.psd"), true)
+            .unwrap()
+            .unwrap();
+
+        assert!(dt.date.unwrap().year >= 2020);
+    }
+
+    #[test]
+    fn no_smart_handler() {
+        assert!(XmpFile::file_mod_date(fixture_path("no_xmp.txt"), true)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn missing_file() {
+        let err = XmpFile::file_mod_date(fixture_path("doesnt_exist.jpg"), false).unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::ReadError);
+    }
+}
+
+mod is_metadata_writable {
+    use tempfile::tempdir;
+
+    use crate::{tests::fixtures::*, XmpFile};
+
+    #[test]
+    fn writable() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        assert!(XmpFile::is_metadata_writable(purple_square).unwrap());
+    }
+}
+
+mod associated_resources {
+    use crate::{tests::fixtures::*, XmpFile};
+
+    #[test]
+    fn single_file() {
+        let resources = XmpFile::associated_resources(fixture_path("Purple Square.psd")).unwrap();
+
+        assert_eq!(resources.len(), 1);
+        assert!(resources[0].ends_with("Purple Square.psd"));
+    }
+
+    #[test]
+    fn no_smart_handler() {
+        let resources = XmpFile::associated_resources(fixture_path("no_xmp.txt")).unwrap();
+        assert!(resources.is_empty());
+    }
+}
+
+mod register_extension {
+    use std::{fs, path::Path};
+
+    use tempfile::tempdir;
+
+    use crate::{
+        tests::fixtures::*, xmp_file_format::registered_format, FileFormat, OpenFileOptions,
+        XmpFile,
+    };
+
+    #[test]
+    fn open_file() {
+        XmpFile::register_extension(".VendorJpg", FileFormat::Jpeg);
+
+        let tempdir = tempdir().unwrap();
+        let renamed = tempdir.path().join("image2.vendorjpg");
+        fs::copy(fixture_path("image2.jpg"), &renamed).unwrap();
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            &renamed,
+            OpenFileOptions::default().for_read().use_smart_handler(),
+        )
+        .unwrap();
+
+        assert!(f.xmp().is_some());
+        f.close().unwrap();
+    }
+
+    #[test]
+    fn registration() {
+        let path = Path::new("scan.VNDTIF");
+        assert_eq!(registered_format(path), FileFormat::Unknown);
+
+        XmpFile::register_extension("vndtif", FileFormat::Tiff);
+        assert_eq!(registered_format(path), FileFormat::Tiff);
+
+        XmpFile::register_extension("vndtif", FileFormat::Jpeg);
+        assert_eq!(registered_format(path), FileFormat::Jpeg);
+
+        XmpFile::register_extension("vndtif", FileFormat::Unknown);
+        assert_eq!(registered_format(path), FileFormat::Unknown);
+    }
+}
+
+mod load_plugins {
+    use tempfile::tempdir;
+
+    use crate::{tests::fixtures::*, XmpErrorType, XmpFile, XmpMeta};
+
+    #[test]
+    fn after_initialization() {
+        XmpMeta::new().unwrap();
+
+        let plugins = tempdir().unwrap();
+        let err = XmpFile::load_plugins(plugins.path()).unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::ToolkitAlreadyInitialized);
+    }
+
+    #[test]
+    fn not_a_folder() {
+        let err = XmpFile::load_plugins(fixture_path("no_xmp.txt")).unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::NoFile);
+    }
+}
+
 mod get_xmp {
     use crate::{tests::fixtures::*, OpenFileOptions, XmpFile};
 
     #[test]
-    fn no_xmp_in_file() {
+    fn no_xmp_in_file() {
+        let mut f = XmpFile::new().unwrap();
+
+        let no_xmp = fixture_path("no_xmp.txt");
+        assert!(f.open_file(&no_xmp, OpenFileOptions::default()).is_ok());
+
+        let opt_m = f.xmp();
+        assert!(opt_m.is_none());
+    }
+}
+
+mod packet_info {
+    use std::fs;
+
+    use crate::{tests::fixtures::*, OpenFileOptions, PacketEncoding, XmpFile};
+
+    #[test]
+    fn wrapped_packet() {
+        let path = fixture_path("Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&path, OpenFileOptions::default()).unwrap();
+        assert!(f.xmp().is_some());
+
+        let info = f.packet_info().unwrap();
+        assert_eq!(info.encoding, PacketEncoding::Utf8);
+        assert!(info.writable);
+        assert!(info.has_wrapper);
+
+        let range = info.range().unwrap();
+        let bytes = fs::read(&path).unwrap();
+        let packet = &bytes[range.start as usize..range.end as usize];
+
+        assert!(packet.starts_with(b"<?xpacket begin="));
+        assert!(packet.ends_with(b"?>"));
+        assert!(info.padding < info.length.unwrap());
+    }
+
+    #[test]
+    fn no_xmp() {
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(fixture_path("no_xmp.txt"), OpenFileOptions::default())
+            .unwrap();
+
+        assert!(f.packet_info().is_none());
+    }
+}
+
+mod xmp_with_packet {
+    use crate::{tests::fixtures::*, xmp_ns, OpenFileOptions, XmpFile};
+
+    #[test]
+    fn wrapped_packet() {
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            fixture_path("Purple Square.psd"),
+            OpenFileOptions::default(),
+        )
+        .unwrap();
+
+        let with_packet = f.xmp_with_packet().unwrap();
+
+        assert!(with_packet.packet.starts_with(b"<?xpacket begin="));
+        assert_eq!(with_packet.packet, f.raw_xmp_packet().unwrap());
+        assert_eq!(with_packet.info.offset, f.packet_info().unwrap().offset);
+        assert!(with_packet.info.offset.is_some());
+
+        assert_eq!(
+            with_packet
+                .xmp
+                .property(xmp_ns::XMP, "CreatorTool")
+                .unwrap()
+                .value,
+            "Adobe Photoshop CS2 Windows"
+        );
+    }
+
+    #[test]
+    fn no_xmp() {
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(fixture_path("no_xmp.txt"), OpenFileOptions::default())
+            .unwrap();
+
+        assert!(f.xmp_with_packet().is_none());
+    }
+}
+
+mod raw_xmp_packet {
+    use std::fs;
+
+    use crate::{tests::fixtures::*, OpenFileOptions, XmpFile};
+
+    #[test]
+    fn matches_file_bytes() {
+        let path = fixture_path("Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&path, OpenFileOptions::default()).unwrap();
+
+        let packet = f.raw_xmp_packet().unwrap();
+        assert!(packet.starts_with(b"<?xpacket begin="));
+
+        let range = f.packet_info().unwrap().range().unwrap();
+        let file = fs::read(&path).unwrap();
+        assert_eq!(
+            packet,
+            file[range.start as usize..range.end as usize].to_vec()
+        );
+    }
+
+    #[test]
+    fn no_xmp() {
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(fixture_path("no_xmp.txt"), OpenFileOptions::default())
+            .unwrap();
+
+        assert!(f.raw_xmp_packet().is_none());
+    }
+}
+
+mod can_put_xmp {
+    use tempfile::tempdir;
+
+    use crate::{tests::fixtures::*, OpenFileOptions, XmpFile, XmpMeta};
+
+    #[test]
+    fn no_xmp_in_file() {
+        let tempdir = tempdir().unwrap();
+        let no_xmp = temp_copy_of_fixture(tempdir.path(), "no_xmp.txt");
+
+        let mut f = XmpFile::new().unwrap();
+        assert!(f
+            .open_file(&no_xmp, OpenFileOptions::default().for_update())
+            .is_ok());
+
+        XmpMeta::register_namespace("http://purl.org/dc/terms/", "dcterms").unwrap();
+
+        let mut m = XmpMeta::new().unwrap();
+        m.set_property("http://purl.org/dc/terms/", "provenance", &"blah".into())
+            .unwrap();
+
+        assert!(!f.can_put_xmp(&m));
+    }
+
+    #[test]
+    fn init_fail() {
+        let tempdir = tempdir().unwrap();
+        let no_xmp = temp_copy_of_fixture(tempdir.path(), "no_xmp.txt");
+
+        let mut f = XmpFile::new().unwrap();
+        assert!(f
+            .open_file(&no_xmp, OpenFileOptions::default().for_update())
+            .is_ok());
+
+        XmpMeta::register_namespace("http://purl.org/dc/terms/", "dcterms").unwrap();
+
+        let m = XmpMeta::new_fail();
+        assert!(!f.can_put_xmp(&m));
+    }
+}
+
+mod put_xmp_blocker {
+    use tempfile::tempdir;
+
+    use crate::{tests::fixtures::*, OpenFileOptions, PutXmpBlocker, XmpFile, XmpMeta};
+
+    #[test]
+    fn can_update() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&purple_square, OpenFileOptions::default().for_update())
+            .unwrap();
+
+        let m = f.xmp().unwrap();
+        assert_eq!(f.put_xmp_blocker(&m), None);
+    }
+
+    #[test]
+    fn not_opened_for_update() {
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            fixture_path("Purple Square.psd"),
+            OpenFileOptions::default(),
+        )
+        .unwrap();
+
+        let m = f.xmp().unwrap();
+        assert_eq!(
+            f.put_xmp_blocker(&m),
+            Some(PutXmpBlocker::NotOpenedForUpdate)
+        );
+        assert!(!f.can_put_xmp(&m));
+    }
+
+    #[test]
+    fn read_only_format() {
+        let tempdir = tempdir().unwrap();
+        let no_xmp = temp_copy_of_fixture(tempdir.path(), "no_xmp.txt");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&no_xmp, OpenFileOptions::default().for_update())
+            .unwrap();
+
+        let m = XmpMeta::new().unwrap();
+        assert_eq!(f.put_xmp_blocker(&m), Some(PutXmpBlocker::ReadOnlyFormat));
+    }
+
+    #[test]
+    fn init_fail() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&purple_square, OpenFileOptions::default().for_update())
+            .unwrap();
+
+        let m = XmpMeta::new_fail();
+        assert_eq!(f.put_xmp_blocker(&m), Some(PutXmpBlocker::Other));
+    }
+}
+
+mod put_xmp {
+    use tempfile::tempdir;
+
+    use crate::{
+        tests::fixtures::*, OpenFileOptions, PacketPadding, XmpErrorType, XmpFile, XmpMeta,
+    };
+
+    #[test]
+    fn no_xmp_in_file() {
+        let tempdir = tempdir().unwrap();
+        let no_xmp = temp_copy_of_fixture(tempdir.path(), "no_xmp.txt");
+
+        let mut f = XmpFile::new().unwrap();
+        assert!(f
+            .open_file(&no_xmp, OpenFileOptions::default().for_update())
+            .is_ok());
+
+        XmpMeta::register_namespace("http://purl.org/dc/terms/", "dcterms").unwrap();
+
+        let mut m = XmpMeta::new().unwrap();
+        m.set_property("http://purl.org/dc/terms/", "provenance", &"blah".into())
+            .unwrap();
+
+        let err = f.put_xmp(&m).unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::Unavailable);
+        assert_eq!(err.debug_message, "XMPFiles::PutXMP - Can't inject XMP");
+    }
+
+    #[test]
+    fn init_fail() {
+        let tempdir = tempdir().unwrap();
+        let no_xmp = temp_copy_of_fixture(tempdir.path(), "no_xmp.txt");
+
+        let mut f = XmpFile::new().unwrap();
+        assert!(f
+            .open_file(&no_xmp, OpenFileOptions::default().for_update())
+            .is_ok());
+
+        XmpMeta::register_namespace("http://purl.org/dc/terms/", "dcterms").unwrap();
+
+        let m = XmpMeta::new_fail();
+        let err = f.put_xmp(&m).unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::NoCppToolkit);
+    }
+
+    #[test]
+    fn raw_packet_for_cpp_handler() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&purple_square, OpenFileOptions::default().for_update())
+            .unwrap();
+
+        let packet = f.raw_xmp_packet().unwrap();
+        let err = f.put_raw_packet(&packet).unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::Unimplemented);
+        assert!(!f.has_pending_update());
+    }
+
+    #[test]
+    fn padding_for_cpp_handler() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&purple_square, OpenFileOptions::default().for_update())
+            .unwrap();
+
+        let m = f.xmp().unwrap();
+        let err = f
+            .put_xmp_with_padding(&m, PacketPadding::Exact)
+            .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::Unimplemented);
+
+        f.put_xmp_with_padding(&m, PacketPadding::Default).unwrap();
+        f.close().unwrap();
+    }
+}
+
+mod put_xmp_from_str {
+    use tempfile::tempdir;
+
+    use crate::{tests::fixtures::*, xmp_ns, OpenFileOptions, XmpFile, XmpMeta};
+
+    fn label(path: &str) -> String {
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(path, OpenFileOptions::default().for_read())
+            .unwrap();
+
+        let m = f.xmp().unwrap();
+        m.property(xmp_ns::XMP, "Label").unwrap().value
+    }
+
+    #[test]
+    fn packet() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&purple_square, OpenFileOptions::default().for_update())
+            .unwrap();
+
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &"From string".into())
+            .unwrap();
+        f.put_xmp_from_str(&m.to_string()).unwrap();
+        f.close().unwrap();
+
+        assert_eq!(label(&purple_square), "From string");
+    }
+
+    #[test]
+    fn invalid_xmp() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&purple_square, OpenFileOptions::default().for_update())
+            .unwrap();
+
+        // Mismatched tags.
+        assert!(f
+            .put_xmp_from_str("<x:xmpmeta xmlns:x=\"adobe:ns:meta/\"></rdf:RDF>")
+            .is_err());
+        assert!(!f.has_pending_update());
+
+        f.close().unwrap();
+    }
+
+    #[test]
+    fn not_opened_for_update() {
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            fixture_path("Purple Square.psd"),
+            OpenFileOptions::default(),
+        )
+        .unwrap();
+
+        let xmp = XmpMeta::new().unwrap().to_string();
+        assert!(f.put_xmp_from_str(&xmp).is_err());
+        f.close().unwrap();
+    }
+}
+
+mod keep_legacy_metadata {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use crate::{
+        tests::fixtures::*, xmp_jpeg, xmp_ns, FileFormat, OpenFileOptions, XmpErrorType, XmpFile,
+    };
+
+    #[test]
+    fn jpeg_file() {
+        let tempdir = tempdir().unwrap();
+        let image2 = temp_copy_of_fixture(tempdir.path(), "image2.jpg");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            &image2,
+            OpenFileOptions::default()
+                .for_update()
+                .keep_legacy_metadata(),
+        )
+        .unwrap();
+
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::DC, "rights", &"Changed".into())
+            .unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &"Kept".into())
+            .unwrap();
+        f.put_xmp(&m).unwrap();
+        f.close().unwrap();
+
+        let original = fs::read(fixture_path("image2.jpg")).unwrap();
+        let updated = fs::read(&image2).unwrap();
+
+        assert_eq!(
+            xmp_jpeg::legacy_segments(&updated).unwrap(),
+            xmp_jpeg::legacy_segments(&original).unwrap()
+        );
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&image2, OpenFileOptions::default().only_xmp())
+            .unwrap();
+
+        let m = f.xmp().unwrap();
+        assert_eq!(m.property(xmp_ns::XMP, "Label").unwrap().value, "Kept");
+    }
+
+    #[test]
+    fn jpeg_bytes() {
+        let original = fs::read(fixture_path("image2.jpg")).unwrap();
+
+        let mut f = XmpFile::from_bytes(
+            &original,
+            FileFormat::Jpeg,
+            OpenFileOptions::default()
+                .for_update()
+                .keep_legacy_metadata(),
+        )
+        .unwrap();
+
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::DC, "rights", &"Changed".into())
+            .unwrap();
+        f.put_xmp(&m).unwrap();
+
+        let updated = f.into_bytes().unwrap();
+
+        assert_eq!(
+            xmp_jpeg::legacy_segments(&updated).unwrap(),
+            xmp_jpeg::legacy_segments(&original).unwrap()
+        );
+    }
+
+    #[test]
+    fn other_format() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        let err = f
+            .open_file(
+                &purple_square,
+                OpenFileOptions::default()
+                    .for_update()
+                    .keep_legacy_metadata(),
+            )
+            .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::Unimplemented);
+    }
+
+    #[test]
+    fn ignored_for_read() {
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            fixture_path("Purple Square.psd"),
+            OpenFileOptions::default().keep_legacy_metadata(),
+        )
+        .unwrap();
+
+        assert!(f.xmp().is_some());
+    }
+}
+
+mod update_xmp_in_place {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use crate::{tests::fixtures::*, xmp_ns, FileFormat, OpenFileOptions, XmpErrorType, XmpFile};
+
+    #[test]
+    fn small_change() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            &purple_square,
+            OpenFileOptions::default()
+                .for_update()
+                .update_xmp_in_place(),
+        )
+        .unwrap();
+
+        let range = f.packet_info().unwrap().range().unwrap();
+
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &"In place".into())
+            .unwrap();
+        f.put_xmp(&m).unwrap();
+        f.close().unwrap();
+
+        let original = fs::read(fixture_path("Purple Square.psd")).unwrap();
+        let updated = fs::read(&purple_square).unwrap();
+
+        let (start, end) = (range.start as usize, range.end as usize);
+        assert_eq!(updated.len(), original.len());
+        assert_eq!(updated[..start], original[..start]);
+        assert_eq!(updated[end..], original[end..]);
+        assert_ne!(updated[start..end], original[start..end]);
+    }
+
+    #[test]
+    fn too_large() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            &purple_square,
+            OpenFileOptions::default()
+                .for_update()
+                .update_xmp_in_place(),
+        )
+        .unwrap();
+
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &"x".repeat(100_000).into())
+            .unwrap();
+        f.put_xmp(&m).unwrap();
+
+        let err = f.close().unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::XmpUpdateNotInPlace);
+
+        assert_eq!(
+            fs::read(&purple_square).unwrap(),
+            fs::read(fixture_path("Purple Square.psd")).unwrap()
+        );
+        assert_eq!(fs::read_dir(tempdir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn too_large_bytes() {
+        let original = fs::read(fixture_path("Purple Square.psd")).unwrap();
+
+        let mut f = XmpFile::from_bytes(
+            &original,
+            FileFormat::Unknown,
+            OpenFileOptions::default()
+                .for_update()
+                .update_xmp_in_place(),
+        )
+        .unwrap();
+
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &"x".repeat(100_000).into())
+            .unwrap();
+        f.put_xmp(&m).unwrap();
+
+        let err = f.into_bytes().unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::XmpUpdateNotInPlace);
+    }
+
+    #[test]
+    fn no_packet() {
+        let tempdir = tempdir().unwrap();
+        let no_xmp = temp_copy_of_fixture(tempdir.path(), "no_xmp.txt");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            &no_xmp,
+            OpenFileOptions::default()
+                .for_update()
+                .update_xmp_in_place(),
+        )
+        .unwrap();
+
+        let m = crate::XmpMeta::new().unwrap();
+        let err = f.put_xmp(&m).unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::XmpUpdateNotInPlace);
+    }
+}
+
+mod repair_xmp {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use crate::{xmp_ns, xmp_packet::PacketRepair, FileFormat, OpenFileOptions, XmpFile, XmpMeta};
+
+    // A text file whose XMP packet was cut off after the label, followed
+    // by enough room for a complete packet.
+    fn truncated_packet() -> Vec<u8> {
+        let mut m = XmpMeta::new().unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &"Damaged".into())
+            .unwrap();
+
+        let packet = m.to_string();
+        let cut = packet.find("</rdf:Description>").unwrap();
+
+        let mut bytes = b"Text before the packet\n".to_vec();
+        bytes.extend_from_slice(&packet.as_bytes()[..cut]);
+        bytes.extend_from_slice(" ".repeat(4000).as_bytes());
+        bytes
+    }
+
+    #[test]
+    fn read() {
+        let tempdir = tempdir().unwrap();
+        let path = tempdir.path().join("damaged.txt");
+        fs::write(&path, truncated_packet()).unwrap();
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&path, OpenFileOptions::default()).unwrap();
+        assert!(f.xmp().is_none());
+        assert!(f.repairs().is_empty());
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&path, OpenFileOptions::default().repair_xmp())
+            .unwrap();
+
+        let m = f.xmp().unwrap();
+        assert_eq!(m.property(xmp_ns::XMP, "Label").unwrap().value, "Damaged");
+        assert_eq!(f.repairs(), [PacketRepair::Truncated]);
+
+        // The raw packet is the damaged one.
+        let packet = f.raw_xmp_packet().unwrap();
+        assert!(packet.starts_with(b"<?xpacket begin="));
+        assert!(!packet.windows(7).any(|w| w == b"</rdf:D"));
+    }
+
+    #[test]
+    fn update() {
+        let tempdir = tempdir().unwrap();
+        let path = tempdir.path().join("damaged.txt");
+        let original = truncated_packet();
+        fs::write(&path, &original).unwrap();
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&path, OpenFileOptions::default().for_update().repair_xmp())
+            .unwrap();
+
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &"Repaired".into())
+            .unwrap();
+        f.put_xmp(&m).unwrap();
+        f.close().unwrap();
+
+        let updated = fs::read(&path).unwrap();
+        assert_eq!(updated.len(), original.len());
+        assert!(updated.starts_with(b"Text before the packet\n"));
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&path, OpenFileOptions::default()).unwrap();
+
+        let m = f.xmp().unwrap();
+        assert_eq!(m.property(xmp_ns::XMP, "Label").unwrap().value, "Repaired");
+        assert!(f.repairs().is_empty());
+    }
+
+    #[test]
+    fn from_bytes() {
+        let mut f = XmpFile::from_bytes(
+            &truncated_packet(),
+            FileFormat::Unknown,
+            OpenFileOptions::default().repair_xmp(),
+        )
+        .unwrap();
+
+        let m = f.xmp().unwrap();
+        assert_eq!(m.property(xmp_ns::XMP, "Label").unwrap().value, "Damaged");
+        assert_eq!(f.repairs(), [PacketRepair::Truncated]);
+    }
+}
+
+mod preserve_file_times {
+    use std::fs;
+
+    use filetime::FileTime;
+    use tempfile::tempdir;
+
+    use crate::{tests::fixtures::*, xmp_ns, OpenFileOptions, XmpFile};
+
+    #[test]
+    fn restores_times_after_update() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let accessed = FileTime::from_unix_time(1_600_000_000, 0);
+        let modified = FileTime::from_unix_time(1_500_000_000, 0);
+        filetime::set_file_times(&purple_square, accessed, modified).unwrap();
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            &purple_square,
+            OpenFileOptions::default()
+                .for_update()
+                .preserve_file_times(),
+        )
+        .unwrap();
+
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &"Updated".into())
+            .unwrap();
+        f.put_xmp(&m).unwrap();
+        f.close().unwrap();
+
+        let metadata = fs::metadata(&purple_square).unwrap();
+        assert_eq!(FileTime::from_last_modification_time(&metadata), modified);
+        assert_eq!(FileTime::from_last_access_time(&metadata), accessed);
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&purple_square, OpenFileOptions::default().for_read())
+            .unwrap();
+        let m = f.xmp().unwrap();
+        assert_eq!(m.property(xmp_ns::XMP, "Label").unwrap().value, "Updated");
+    }
+}
+
+mod update_safely {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use crate::{tests::fixtures::*, xmp_ns, OpenFileOptions, XmpFile};
+
+    #[test]
+    fn replaces_file() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            &purple_square,
+            OpenFileOptions::default().for_update().update_safely(),
+        )
+        .unwrap();
+
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &"Safely".into())
+            .unwrap();
+        f.put_xmp(&m).unwrap();
+
+        // The original is untouched until the file is closed.
+        assert_eq!(
+            fs::read(&purple_square).unwrap(),
+            fs::read(fixture_path("Purple Square.psd")).unwrap()
+        );
+
+        f.close().unwrap();
+
+        // No temporary file is left behind.
+        assert_eq!(fs::read_dir(tempdir.path()).unwrap().count(), 1);
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&purple_square, OpenFileOptions::default().for_read())
+            .unwrap();
+
+        let m = f.xmp().unwrap();
+        assert_eq!(m.property(xmp_ns::XMP, "Label").unwrap().value, "Safely");
+    }
+
+    #[test]
+    fn unchanged_without_update() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            &purple_square,
+            OpenFileOptions::default().for_update().update_safely(),
+        )
+        .unwrap();
+        f.close().unwrap();
+
+        assert_eq!(fs::read_dir(tempdir.path()).unwrap().count(), 1);
+        assert_eq!(
+            fs::read(&purple_square).unwrap(),
+            fs::read(fixture_path("Purple Square.psd")).unwrap()
+        );
+    }
+
+    #[test]
+    fn temp_file_removed_on_drop() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            &purple_square,
+            OpenFileOptions::default().for_update().update_safely(),
+        )
+        .unwrap();
+        drop(f);
+
+        assert_eq!(fs::read_dir(tempdir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn concurrent_updates() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut first = XmpFile::new().unwrap();
+        first
+            .open_file(
+                &purple_square,
+                OpenFileOptions::default().for_update().update_safely(),
+            )
+            .unwrap();
+
+        let mut second = XmpFile::new().unwrap();
+        second
+            .open_file(
+                &purple_square,
+                OpenFileOptions::default().for_update().update_safely(),
+            )
+            .unwrap();
+
+        // Each file has its own temporary copy.
+        assert_eq!(fs::read_dir(tempdir.path()).unwrap().count(), 3);
+
+        let mut m = second.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &"Second".into())
+            .unwrap();
+        second.put_xmp(&m).unwrap();
+
+        first.close().unwrap();
+        second.close().unwrap();
+
+        assert_eq!(fs::read_dir(tempdir.path()).unwrap().count(), 1);
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&purple_square, OpenFileOptions::default().for_read())
+            .unwrap();
+
+        let m = f.xmp().unwrap();
+        assert_eq!(m.property(xmp_ns::XMP, "Label").unwrap().value, "Second");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn planted_symlink() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let outside = tempdir.path().join("outside.txt");
+        fs::write(&outside, "untouched").unwrap();
+        std::os::unix::fs::symlink(&outside, tempdir.path().join(".xmptmp-Purple Square.psd"))
+            .unwrap();
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            &purple_square,
+            OpenFileOptions::default().for_update().update_safely(),
+        )
+        .unwrap();
+
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &"Safely".into())
+            .unwrap();
+        f.put_xmp(&m).unwrap();
+        f.close().unwrap();
+
+        assert_eq!(fs::read_to_string(&outside).unwrap(), "untouched");
+    }
+}
+
+mod temp_folder {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use crate::{tests::fixtures::*, xmp_ns, OpenFileOptions, XmpErrorType, XmpFile};
+
+    #[test]
+    fn safe_save() {
+        let scratch = tempdir().unwrap();
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            &purple_square,
+            OpenFileOptions::default()
+                .for_update()
+                .update_safely()
+                .temp_folder(scratch.path()),
+        )
+        .unwrap();
+
+        // The copy being updated is in the scratch folder.
+        assert_eq!(fs::read_dir(tempdir.path()).unwrap().count(), 1);
+        assert_eq!(fs::read_dir(scratch.path()).unwrap().count(), 1);
+
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &"Scratch".into())
+            .unwrap();
+        f.put_xmp(&m).unwrap();
+        f.close().unwrap();
+
+        assert_eq!(fs::read_dir(tempdir.path()).unwrap().count(), 1);
+        assert_eq!(fs::read_dir(scratch.path()).unwrap().count(), 0);
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&purple_square, OpenFileOptions::default().for_read())
+            .unwrap();
+
+        let m = f.xmp().unwrap();
+        assert_eq!(m.property(xmp_ns::XMP, "Label").unwrap().value, "Scratch");
+    }
+
+    #[test]
+    fn missing_folder() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        let err = f
+            .open_file(
+                &purple_square,
+                OpenFileOptions::default()
+                    .for_update()
+                    .update_safely()
+                    .temp_folder(tempdir.path().join("missing")),
+            )
+            .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::WriteError);
+    }
+}
+
+mod sharing_retry {
+    use std::time::Duration;
+
+    use crate::{tests::fixtures::*, OpenFileOptions, SharingRetry, XmpErrorType, XmpFile};
+
+    #[test]
+    fn default() {
+        let retry = SharingRetry::default();
+
+        assert_eq!(retry.retries(), 5);
+        assert_eq!(retry.initial_delay(), Duration::from_millis(100));
+        assert_eq!(retry.max_delay(), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn backoff() {
+        let retry =
+            SharingRetry::new(6, Duration::from_millis(300)).with_max_delay(Duration::from_secs(2));
+
+        let delays: Vec<Option<Duration>> = (0..7).map(|n| retry.delay(n)).collect();
+        assert_eq!(
+            delays,
+            [
+                Some(Duration::from_millis(300)),
+                Some(Duration::from_millis(600)),
+                Some(Duration::from_millis(1200)),
+                Some(Duration::from_secs(2)),
+                Some(Duration::from_secs(2)),
+                Some(Duration::from_secs(2)),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn no_retries() {
+        let retry = SharingRetry::new(0, Duration::from_millis(100));
+        assert_eq!(retry.delay(0), None);
+    }
+
+    #[test]
+    fn other_errors_not_retried() {
+        let mut f = XmpFile::new().unwrap();
+        let err = f
+            .open_file(
+                fixture_path("Purple Square.psd").replace(".psd", ".missing"),
+                OpenFileOptions::default()
+                    .retry_on_sharing_violation(SharingRetry::new(3, Duration::from_secs(60))),
+            )
+            .unwrap_err();
+
+        assert_ne!(err.error_type, XmpErrorType::FileSharingViolation);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn file_in_use() {
+        use std::{fs::OpenOptions, os::windows::fs::OpenOptionsExt, thread};
+
+        use tempfile::tempdir;
+
+        let tempdir = tempdir().unwrap();
+        let path = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let locked = OpenOptions::new()
+            .read(true)
+            .share_mode(0)
+            .open(&path)
+            .unwrap();
+
+        let mut f = XmpFile::new().unwrap();
+        let err = f
+            .open_file(&path, OpenFileOptions::default())
+            .err()
+            .unwrap();
+        assert_eq!(err.error_type, XmpErrorType::FileSharingViolation);
+        assert!(err.is_transient());
+
+        let release = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(200));
+            drop(locked);
+        });
+
+        f.open_file(
+            &path,
+            OpenFileOptions::default()
+                .retry_on_sharing_violation(SharingRetry::new(10, Duration::from_millis(100))),
+        )
+        .unwrap();
+        assert!(f.xmp().is_some());
+
+        release.join().unwrap();
+    }
+}
+
+mod lock_file {
+    use tempfile::tempdir;
+
+    use crate::{
+        tests::fixtures::*, xmp_ns, FileLocking, OpenFileOptions, PutXmpBlocker, XmpErrorType,
+        XmpFile,
+    };
+
+    #[test]
+    fn update_excludes_other_locks() {
+        let tempdir = tempdir().unwrap();
+        let path = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut writer = XmpFile::new().unwrap();
+        writer
+            .open_file(
+                &path,
+                OpenFileOptions::default()
+                    .for_update()
+                    .lock_file(FileLocking::Wait),
+            )
+            .unwrap();
+
+        let mut reader = XmpFile::new().unwrap();
+        let err = reader
+            .open_file(
+                &path,
+                OpenFileOptions::default().lock_file(FileLocking::FailIfLocked),
+            )
+            .err()
+            .unwrap();
+        assert_eq!(err.error_type, XmpErrorType::FileLocked);
+        assert!(err.is_transient());
+
+        // Readers that don't lock aren't held up.
+        reader.open_file(&path, OpenFileOptions::default()).unwrap();
+        assert!(reader.xmp().is_some());
+
+        let mut m = writer.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &"Locked".into())
+            .unwrap();
+        writer.put_xmp(&m).unwrap();
+        writer.close().unwrap();
+
+        reader
+            .open_file(
+                &path,
+                OpenFileOptions::default().lock_file(FileLocking::FailIfLocked),
+            )
+            .unwrap();
+        assert_eq!(
+            reader
+                .xmp()
+                .unwrap()
+                .property(xmp_ns::XMP, "Label")
+                .unwrap()
+                .value,
+            "Locked"
+        );
+    }
+
+    #[test]
+    fn put_xmp_blocker_reports_lock() {
+        let tempdir = tempdir().unwrap();
+        let path = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut writer = XmpFile::new().unwrap();
+        writer
+            .open_file(
+                &path,
+                OpenFileOptions::default()
+                    .for_update()
+                    .lock_file(FileLocking::Wait),
+            )
+            .unwrap();
+
+        let m = writer.xmp().unwrap();
+
+        // The lock held by this file doesn't block its own update.
+        assert_eq!(writer.put_xmp_blocker(&m), None);
+
+        let mut other = XmpFile::new().unwrap();
+        other
+            .open_file(&path, OpenFileOptions::default().for_update())
+            .unwrap();
+        assert_eq!(other.put_xmp_blocker(&m), Some(PutXmpBlocker::FileLocked));
+
+        writer.close().unwrap();
+        assert_eq!(other.put_xmp_blocker(&m), None);
+    }
+
+    #[test]
+    fn read_lock_released_after_open() {
+        let tempdir = tempdir().unwrap();
+        let path = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut reader = XmpFile::new().unwrap();
+        reader
+            .open_file(
+                &path,
+                OpenFileOptions::default().lock_file(FileLocking::FailIfLocked),
+            )
+            .unwrap();
+
+        let mut writer = XmpFile::new().unwrap();
+        writer
+            .open_file(
+                &path,
+                OpenFileOptions::default()
+                    .for_update()
+                    .lock_file(FileLocking::FailIfLocked),
+            )
+            .unwrap();
+        writer.close().unwrap();
+    }
+
+    #[test]
+    fn drop_releases_lock() {
+        let tempdir = tempdir().unwrap();
+        let path = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+        let flags = OpenFileOptions::default()
+            .for_update()
+            .lock_file(FileLocking::FailIfLocked);
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&path, flags.clone()).unwrap();
+        drop(f);
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&path, flags).unwrap();
+        f.close().unwrap();
+    }
+
+    #[test]
+    fn missing_file() {
+        let tempdir = tempdir().unwrap();
+
+        let mut f = XmpFile::new().unwrap();
+        let err = f
+            .open_file(
+                tempdir.path().join("missing.psd"),
+                OpenFileOptions::default()
+                    .for_update()
+                    .lock_file(FileLocking::Wait),
+            )
+            .err()
+            .unwrap();
+
+        assert_ne!(err.error_type, XmpErrorType::FileLocked);
+    }
+}
+
+mod sidecar_fallback {
+    use std::{fs, path::PathBuf};
+
+    use tempfile::tempdir;
+
+    use crate::{
+        tests::fixtures::*, xmp_ns, xmp_sidecar::Sidecar, OpenFileOptions, XmpFile, XmpMeta,
+    };
+
+    #[test]
+    fn read_only_format() {
+        let tempdir = tempdir().unwrap();
+        let no_xmp = temp_copy_of_fixture(tempdir.path(), "no_xmp.txt");
+        let original = fs::read(&no_xmp).unwrap();
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            &no_xmp,
+            OpenFileOptions::default().for_update().sidecar_fallback(),
+        )
+        .unwrap();
+
+        let mut m = XmpMeta::new().unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &"Sidecar".into())
+            .unwrap();
+        f.put_xmp(&m).unwrap();
+
+        let sidecar_path = tempdir.path().join("no_xmp.xmp");
+        assert_eq!(f.sidecar_for_update(), Some(sidecar_path.clone()));
+        assert!(f.has_pending_update());
+        assert_eq!(
+            f.xmp()
+                .unwrap()
+                .property(xmp_ns::XMP, "Label")
+                .unwrap()
+                .value,
+            "Sidecar"
+        );
+
+        let report = f.try_close_into_report().unwrap();
+        assert!(report.was_updated());
+        assert_eq!(report.path, Some(PathBuf::from(&no_xmp)));
+        assert_eq!(report.sidecar_path, Some(sidecar_path.clone()));
+
+        assert_eq!(fs::read(&no_xmp).unwrap(), original);
+
+        let m = Sidecar::at(&sidecar_path).read().unwrap().unwrap();
+        assert_eq!(m.property(xmp_ns::XMP, "Label").unwrap().value, "Sidecar");
+    }
+
+    #[test]
+    fn embedded_when_possible() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            &purple_square,
+            OpenFileOptions::default().for_update().sidecar_fallback(),
+        )
+        .unwrap();
+
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &"Embedded".into())
+            .unwrap();
+        f.put_xmp(&m).unwrap();
+
+        assert_eq!(f.sidecar_for_update(), None);
+
+        let report = f.try_close_into_report().unwrap();
+        assert!(report.was_updated());
+        assert_eq!(report.sidecar_path, None);
+        assert!(!tempdir.path().join("Purple Square.xmp").exists());
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&purple_square, OpenFileOptions::default().for_read())
+            .unwrap();
+
+        let m = f.xmp().unwrap();
+        assert_eq!(m.property(xmp_ns::XMP, "Label").unwrap().value, "Embedded");
+    }
+
+    #[test]
+    fn not_opened_for_update() {
+        let tempdir = tempdir().unwrap();
+        let no_xmp = temp_copy_of_fixture(tempdir.path(), "no_xmp.txt");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&no_xmp, OpenFileOptions::default().sidecar_fallback())
+            .unwrap();
+
+        let m = XmpMeta::new().unwrap();
+        assert!(f.put_xmp(&m).is_err());
+        assert_eq!(f.sidecar_for_update(), None);
+
+        f.close().unwrap();
+        assert!(!tempdir.path().join("no_xmp.xmp").exists());
+    }
+}
+
+mod buffered_read {
+    use tempfile::tempdir;
+
+    use crate::{tests::fixtures::*, xmp_ns, OpenFileOptions, XmpErrorType, XmpFile};
+
+    #[test]
+    fn same_xmp() {
+        for name in ["Purple Square.psd", "image2.jpg"] {
+            let path = fixture_path(name);
+
+            let mut f = XmpFile::new().unwrap();
+            f.open_file(&path, OpenFileOptions::default()).unwrap();
+            let expected = f.xmp().unwrap();
+
+            let mut f = XmpFile::new().unwrap();
+            f.open_file(&path, OpenFileOptions::default().buffered_read())
+                .unwrap();
+            let m = f.xmp().unwrap();
+
+            assert_eq!(m.to_string(), expected.to_string(), "{name}");
+        }
+    }
+
+    #[test]
+    fn max_file_size() {
+        let mut f = XmpFile::new().unwrap();
+        let err = f
+            .open_file(
+                fixture_path("Purple Square.psd"),
+                OpenFileOptions::default().buffered_read().max_file_size(16),
+            )
+            .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::FileTooLarge);
+    }
+
+    #[test]
+    fn no_xmp() {
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            fixture_path("no_xmp.txt"),
+            OpenFileOptions::default().buffered_read(),
+        )
+        .unwrap();
+
+        assert!(f.xmp().is_none());
+    }
+
+    #[test]
+    fn ignored_for_update() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            &purple_square,
+            OpenFileOptions::default().for_update().buffered_read(),
+        )
+        .unwrap();
+
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &"Buffered".into())
+            .unwrap();
+        f.put_xmp(&m).unwrap();
+        f.close().unwrap();
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&purple_square, OpenFileOptions::default())
+            .unwrap();
+
+        let m = f.xmp().unwrap();
+        assert_eq!(m.property(xmp_ns::XMP, "Label").unwrap().value, "Buffered");
+    }
+}
+
+mod max_scan_bytes {
+    use std::{fs, path::Path, str::FromStr};
+
+    use tempfile::tempdir;
+
+    use crate::{xmp_ns, OpenFileOptions, XmpFile, XmpMeta};
+
+    // Writes a text file with an XMP packet after `offset` bytes of padding.
+    fn write_text_file(path: &Path, offset: usize) {
+        let mut m = XmpMeta::from_str("<x:xmpmeta xmlns:x=\"adobe:ns:meta/\"/>").unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &"Scanned".into())
+            .unwrap();
+
+        let mut bytes = vec![b' '; offset];
+        bytes.extend_from_slice(m.to_string().as_bytes());
+        fs::write(path, bytes).unwrap();
+    }
+
+    #[test]
+    fn packet_within_limit() {
+        let tempdir = tempdir().unwrap();
+        let path = tempdir.path().join("scan.txt");
+        write_text_file(&path, 1000);
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&path, OpenFileOptions::default().max_scan_bytes(100_000))
+            .unwrap();
+
+        let m = f.xmp().unwrap();
+        assert_eq!(m.property(xmp_ns::XMP, "Label").unwrap().value, "Scanned");
+    }
+
+    #[test]
+    fn packet_beyond_limit() {
+        let tempdir = tempdir().unwrap();
+        let path = tempdir.path().join("scan.txt");
+        write_text_file(&path, 100_000);
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&path, OpenFileOptions::default().max_scan_bytes(1000))
+            .unwrap();
+
+        assert!(f.xmp().is_none());
+    }
+
+    #[test]
+    fn no_limit() {
+        let tempdir = tempdir().unwrap();
+        let path = tempdir.path().join("scan.txt");
+        write_text_file(&path, 100_000);
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&path, OpenFileOptions::default()).unwrap();
+
+        let m = f.xmp().unwrap();
+        assert_eq!(m.property(xmp_ns::XMP, "Label").unwrap().value, "Scanned");
+    }
+
+    #[test]
+    fn ignored_for_update() {
+        let tempdir = tempdir().unwrap();
+        let path = tempdir.path().join("scan.txt");
+        write_text_file(&path, 100_000);
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            &path,
+            OpenFileOptions::default().for_update().max_scan_bytes(1000),
+        )
+        .unwrap();
+
+        assert!(f.xmp().is_some());
+    }
+}
+
+mod close {
+    use std::{fs, path::Path};
+
+    use tempfile::tempdir;
+
+    use crate::{tests::fixtures::*, xmp_ns, OpenFileOptions, XmpFile};
+
+    #[test]
+    fn happy_path() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&purple_square, OpenFileOptions::safe_update())
+            .unwrap();
+
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "CreatorTool", &"close test".into())
+            .unwrap();
+
+        f.put_xmp(&m).unwrap();
+        assert!(f.has_pending_update());
+
+        f.close().unwrap();
+        assert!(!f.has_pending_update());
+    }
+
+    #[test]
+    fn failed_close_clears_pending_update() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            &purple_square,
+            OpenFileOptions::default().for_update().update_safely(),
+        )
+        .unwrap();
+
+        let m = f.xmp().unwrap();
+        f.put_xmp(&m).unwrap();
+
+        // Replacing the file fails if it has been turned into a folder.
+        fs::remove_file(&purple_square).unwrap();
+        fs::create_dir(&purple_square).unwrap();
+        fs::write(Path::new(&purple_square).join("blocker"), b"").unwrap();
+
+        assert!(f.close().is_err());
+
+        // The error was reported, so dropping the struct doesn't warn.
+        assert!(!f.has_pending_update());
+    }
+
+    #[test]
+    fn drop_without_close() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+        let original = fs::read(&purple_square).unwrap();
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&purple_square, OpenFileOptions::safe_update())
+            .unwrap();
+
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "CreatorTool", &"never written".into())
+            .unwrap();
+        f.put_xmp(&m).unwrap();
+
+        // Dropping the struct discards the update without panicking.
+        drop(f);
+        assert_eq!(fs::read(&purple_square).unwrap(), original);
+    }
+
+    #[test]
+    fn drop_read_only_without_close() {
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            fixture_path("Purple Square.psd"),
+            OpenFileOptions::default(),
+        )
+        .unwrap();
+
+        assert!(f.xmp().is_some());
+    }
+}
+
+mod try_close_into_report {
+    use std::path::PathBuf;
+
+    use tempfile::tempdir;
+
+    use crate::{tests::fixtures::*, xmp_ns, OpenFileOptions, XmpFile};
+
+    #[test]
+    fn updated() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&purple_square, OpenFileOptions::safe_update())
+            .unwrap();
+
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "CreatorTool", &"report test".into())
+            .unwrap();
+        f.put_xmp(&m).unwrap();
+
+        let report = f.try_close_into_report().unwrap();
+
+        assert!(report.was_updated());
+        assert_eq!(report.path, Some(PathBuf::from(&purple_square)));
+        assert_eq!(report.sidecar_path, None);
+        assert_eq!(
+            report
+                .written_xmp
+                .unwrap()
+                .property(xmp_ns::XMP, "CreatorTool")
+                .unwrap()
+                .value,
+            "report test"
+        );
+    }
+
+    #[test]
+    fn not_updated() {
         let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            fixture_path("Purple Square.psd"),
+            OpenFileOptions::default(),
+        )
+        .unwrap();
 
-        let no_xmp = fixture_path("no_xmp.txt");
-        assert!(f.open_file(&no_xmp, OpenFileOptions::default()).is_ok());
+        let report = f.try_close_into_report().unwrap();
 
-        let opt_m = f.xmp();
-        assert!(opt_m.is_none());
+        assert!(!report.was_updated());
+        assert_eq!(
+            report.path,
+            Some(PathBuf::from(fixture_path("Purple Square.psd")))
+        );
     }
 }
 
-mod can_put_xmp {
+mod progress_callback {
+    use std::time::Duration;
+
     use tempfile::tempdir;
 
-    use crate::{tests::fixtures::*, OpenFileOptions, XmpFile, XmpMeta};
+    use crate::{tests::fixtures::*, xmp_ns, OpenFileOptions, XmpFile};
 
     #[test]
-    fn no_xmp_in_file() {
+    fn update_with_callback() {
         let tempdir = tempdir().unwrap();
-        let no_xmp = temp_copy_of_fixture(tempdir.path(), "no_xmp.txt");
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
 
         let mut f = XmpFile::new().unwrap();
-        assert!(f
-            .open_file(&no_xmp, OpenFileOptions::default().for_update())
-            .is_ok());
+        f.set_progress_callback(Duration::from_millis(10), true, |progress| {
+            (0.0..=1.0).contains(&progress.fraction_done)
+        })
+        .unwrap();
 
-        XmpMeta::register_namespace("http://purl.org/dc/terms/", "dcterms").unwrap();
+        f.open_file(&purple_square, OpenFileOptions::safe_update())
+            .unwrap();
 
-        let mut m = XmpMeta::new().unwrap();
-        m.set_property("http://purl.org/dc/terms/", "provenance", &"blah".into())
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "CreatorTool", &"progress test".into())
             .unwrap();
 
-        assert!(!f.can_put_xmp(&m));
+        f.put_xmp(&m).unwrap();
+        f.close().unwrap();
+
+        // The PSD handler doesn't necessarily report progress, so this
+        // only checks that the update succeeds with a callback attached.
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&purple_square, OpenFileOptions::default())
+            .unwrap();
+
+        assert_eq!(
+            f.xmp()
+                .unwrap()
+                .property(xmp_ns::XMP, "CreatorTool")
+                .unwrap()
+                .value,
+            "progress test"
+        );
     }
 
     #[test]
-    fn init_fail() {
+    fn replace_and_clear() {
+        let mut f = XmpFile::new().unwrap();
+
+        f.set_progress_callback(Duration::from_secs(1), false, |_| true)
+            .unwrap();
+        f.set_progress_callback(Duration::from_secs(2), true, |_| false)
+            .unwrap();
+        f.clear_progress_callback().unwrap();
+
+        f.open_file(
+            fixture_path("Purple Square.psd"),
+            OpenFileOptions::default(),
+        )
+        .unwrap();
+
+        assert!(f.xmp().is_some());
+    }
+
+    #[test]
+    fn default_callback() {
+        XmpFile::set_default_progress_callback(Duration::from_secs(1), false, |_| true).unwrap();
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            fixture_path("Purple Square.psd"),
+            OpenFileOptions::default(),
+        )
+        .unwrap();
+
+        assert!(f.xmp().is_some());
+    }
+}
+
+mod cancellation_token {
+    use tempfile::tempdir;
+
+    use crate::{tests::fixtures::*, xmp_ns, CancellationToken, OpenFileOptions, XmpFile};
+
+    #[test]
+    fn clones_share_state() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+
+        assert!(!token.is_cancelled());
+        assert!(!clone.is_cancelled());
+
+        clone.cancel();
+
+        assert!(token.is_cancelled());
+        assert!(clone.is_cancelled());
+    }
+
+    #[test]
+    fn update_not_cancelled() {
         let tempdir = tempdir().unwrap();
-        let no_xmp = temp_copy_of_fixture(tempdir.path(), "no_xmp.txt");
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let token = CancellationToken::new();
 
         let mut f = XmpFile::new().unwrap();
-        assert!(f
-            .open_file(&no_xmp, OpenFileOptions::default().for_update())
-            .is_ok());
+        f.set_cancellation_token(&token).unwrap();
+        f.open_file(&purple_square, OpenFileOptions::safe_update())
+            .unwrap();
 
-        XmpMeta::register_namespace("http://purl.org/dc/terms/", "dcterms").unwrap();
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "CreatorTool", &"cancel test".into())
+            .unwrap();
 
-        let m = XmpMeta::new_fail();
-        assert!(!f.can_put_xmp(&m));
+        f.put_xmp(&m).unwrap();
+        f.close().unwrap();
+
+        assert!(!token.is_cancelled());
+    }
+
+    #[test]
+    fn clear_after_cancel() {
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let mut f = XmpFile::new().unwrap();
+        f.set_cancellation_token(&token).unwrap();
+        f.clear_cancellation_token().unwrap();
+
+        f.open_file(
+            fixture_path("Purple Square.psd"),
+            OpenFileOptions::default(),
+        )
+        .unwrap();
+
+        assert!(f.xmp().is_some());
     }
 }
 
-mod put_xmp {
+mod open_file_options {
     use tempfile::tempdir;
 
-    use crate::{tests::fixtures::*, OpenFileOptions, XmpErrorType, XmpFile, XmpMeta};
+    use crate::{tests::fixtures::*, xmp_ns, OpenFileOptions, XmpFile};
 
     #[test]
-    fn no_xmp_in_file() {
+    fn read_only_fast_scan() {
+        assert_eq!(OpenFileOptions::read_only_fast_scan().options, 0x85);
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            fixture_path("Purple Square.psd"),
+            OpenFileOptions::read_only_fast_scan(),
+        )
+        .unwrap();
+
+        assert!(f.xmp().is_some());
+    }
+
+    #[test]
+    fn safe_update() {
+        assert_eq!(OpenFileOptions::safe_update().options, 0x22);
+
         let tempdir = tempdir().unwrap();
-        let no_xmp = temp_copy_of_fixture(tempdir.path(), "no_xmp.txt");
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
 
         let mut f = XmpFile::new().unwrap();
-        assert!(f
-            .open_file(&no_xmp, OpenFileOptions::default().for_update())
-            .is_ok());
+        f.open_file(&purple_square, OpenFileOptions::safe_update())
+            .unwrap();
 
-        XmpMeta::register_namespace("http://purl.org/dc/terms/", "dcterms").unwrap();
+        let m = f.xmp().unwrap();
+        assert!(f.can_put_xmp(&m));
+    }
 
-        let mut m = XmpMeta::new().unwrap();
-        m.set_property("http://purl.org/dc/terms/", "provenance", &"blah".into())
+    #[test]
+    fn repair_mode() {
+        assert_eq!(OpenFileOptions::repair_mode().options, 0x102);
+    }
+
+    #[test]
+    fn preset_can_be_extended() {
+        assert_eq!(
+            OpenFileOptions::safe_update()
+                .optimize_file_layout()
+                .options,
+            0x222
+        );
+    }
+
+    #[test]
+    fn optimize_file_layout() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            &purple_square,
+            OpenFileOptions::default()
+                .for_update()
+                .optimize_file_layout(),
+        )
+        .unwrap();
+
+        let mut m = f.xmp().unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &"Delivery".into())
             .unwrap();
+        f.put_xmp(&m).unwrap();
+        f.close().unwrap();
 
-        let err = f.put_xmp(&m).unwrap_err();
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&purple_square, OpenFileOptions::default().for_read())
+            .unwrap();
 
-        assert_eq!(err.error_type, XmpErrorType::Unavailable);
-        assert_eq!(err.debug_message, "XMPFiles::PutXMP - Can't inject XMP");
+        let m = f.xmp().unwrap();
+        assert_eq!(m.property(xmp_ns::XMP, "Label").unwrap().value, "Delivery");
     }
+}
+
+mod max_file_size {
+    use crate::{tests::fixtures::*, OpenFileOptions, XmpErrorType, XmpFile};
 
     #[test]
-    fn init_fail() {
+    fn within_limit() {
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            fixture_path("Purple Square.psd"),
+            OpenFileOptions::default().for_read().max_file_size(1 << 30),
+        )
+        .unwrap();
+
+        assert!(f.xmp().is_some());
+    }
+
+    #[test]
+    fn too_large() {
+        let mut f = XmpFile::new().unwrap();
+        let err = f
+            .open_file(
+                fixture_path("Purple Square.psd"),
+                OpenFileOptions::default().for_read().max_file_size(1000),
+            )
+            .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::FileTooLarge);
+    }
+
+    #[test]
+    fn file_not_found() {
+        let mut f = XmpFile::new().unwrap();
+        let err = f
+            .open_file(
+                "doesnotexist.jpg",
+                OpenFileOptions::default().max_file_size(1000),
+            )
+            .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::NoFile);
+    }
+}
+
+mod check_file_signature {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use crate::{tests::fixtures::*, OpenFileOptions, XmpErrorType, XmpFile};
+
+    #[test]
+    fn matching_signature() {
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            fixture_path("image2.jpg"),
+            OpenFileOptions::default().for_read().check_file_signature(),
+        )
+        .unwrap();
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            fixture_path("Purple Square.psd"),
+            OpenFileOptions::default().for_read().check_file_signature(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn mislabeled_file() {
         let tempdir = tempdir().unwrap();
-        let no_xmp = temp_copy_of_fixture(tempdir.path(), "no_xmp.txt");
+        let mislabeled = tempdir.path().join("not_really.jpg");
+        fs::copy(fixture_path("no_xmp.txt"), &mislabeled).unwrap();
 
         let mut f = XmpFile::new().unwrap();
-        assert!(f
-            .open_file(&no_xmp, OpenFileOptions::default().for_update())
-            .is_ok());
+        let err = f
+            .open_file(
+                &mislabeled,
+                OpenFileOptions::default().for_read().check_file_signature(),
+            )
+            .unwrap_err();
 
-        XmpMeta::register_namespace("http://purl.org/dc/terms/", "dcterms").unwrap();
+        assert_eq!(err.error_type, XmpErrorType::FileSignatureMismatch);
+    }
 
-        let m = XmpMeta::new_fail();
-        let err = f.put_xmp(&m).unwrap_err();
+    #[test]
+    fn unknown_extension() {
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            fixture_path("no_xmp.txt"),
+            OpenFileOptions::default().for_read().check_file_signature(),
+        )
+        .unwrap();
+    }
 
-        assert_eq!(err.error_type, XmpErrorType::NoCppToolkit);
+    #[test]
+    fn not_checked_by_default() {
+        let tempdir = tempdir().unwrap();
+        let mislabeled = tempdir.path().join("not_really.jpg");
+        fs::copy(fixture_path("no_xmp.txt"), &mislabeled).unwrap();
+
+        let mut f = XmpFile::new().unwrap();
+        let result = f.open_file(&mislabeled, OpenFileOptions::default().for_read());
+
+        assert_ne!(
+            result.err().map(|e| e.error_type),
+            Some(XmpErrorType::FileSignatureMismatch)
+        );
+    }
+}
+
+mod create_svg_metadata {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use crate::{xmp_ns, OpenFileOptions, XmpErrorType, XmpFile, XmpMeta};
+
+    const WITHOUT_METADATA: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+  <rect width="10" height="10" fill="purple"/>
+</svg>
+"#;
+
+    const WITH_METADATA: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+  <metadata></metadata>
+  <rect width="10" height="10" fill="purple"/>
+</svg>
+"#;
+
+    fn license_xmp() -> XmpMeta {
+        let mut meta = XmpMeta::new().unwrap();
+        meta.set_property(xmp_ns::XMP_RIGHTS, "Marked", &"True".into())
+            .unwrap();
+        meta
+    }
+
+    fn marked(path: &std::path::Path) -> Option<String> {
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(path, OpenFileOptions::default().for_read())
+            .unwrap();
+
+        f.xmp()
+            .and_then(|m| m.property(xmp_ns::XMP_RIGHTS, "Marked"))
+            .map(|v| v.value)
+    }
+
+    #[test]
+    fn created_by_default() {
+        let tempdir = tempdir().unwrap();
+        let path = tempdir.path().join("icon.svg");
+        fs::write(&path, WITHOUT_METADATA).unwrap();
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(&path, OpenFileOptions::default().for_update())
+            .unwrap();
+
+        assert!(f.can_put_xmp(&license_xmp()));
+        f.put_xmp(&license_xmp()).unwrap();
+        f.close().unwrap();
+
+        assert!(fs::read_to_string(&path).unwrap().contains("<metadata"));
+        assert_eq!(marked(&path).as_deref(), Some("True"));
+    }
+
+    #[test]
+    fn not_created() {
+        let tempdir = tempdir().unwrap();
+        let path = tempdir.path().join("icon.svg");
+        fs::write(&path, WITHOUT_METADATA).unwrap();
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            &path,
+            OpenFileOptions::default()
+                .for_update()
+                .create_svg_metadata(false),
+        )
+        .unwrap();
+
+        assert!(!f.can_put_xmp(&license_xmp()));
+
+        let err = f.put_xmp(&license_xmp()).unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::SvgMetadataElementMissing);
+
+        f.close().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), WITHOUT_METADATA);
+    }
+
+    #[test]
+    fn existing_element_is_used() {
+        let tempdir = tempdir().unwrap();
+        let path = tempdir.path().join("icon.svg");
+        fs::write(&path, WITH_METADATA).unwrap();
+
+        let mut f = XmpFile::new().unwrap();
+        f.open_file(
+            &path,
+            OpenFileOptions::default()
+                .for_update()
+                .create_svg_metadata(false),
+        )
+        .unwrap();
+
+        f.put_xmp(&license_xmp()).unwrap();
+        f.close().unwrap();
+
+        assert_eq!(marked(&path).as_deref(), Some("True"));
+    }
+
+    #[test]
+    fn from_bytes() {
+        let f = XmpFile::from_bytes(
+            WITHOUT_METADATA.as_bytes(),
+            crate::FileFormat::Svg,
+            OpenFileOptions::default()
+                .for_update()
+                .create_svg_metadata(false),
+        )
+        .unwrap();
+
+        assert!(!f.can_put_xmp(&license_xmp()));
+    }
+}
+
+#[cfg(feature = "audit")]
+mod audit_log {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use crate::{
+        tests::fixtures::*,
+        xmp_audit::{xmp_hash, AuditLog},
+        xmp_ns, OpenFileOptions, XmpFile,
+    };
+
+    #[test]
+    fn records_update() {
+        let tempdir = tempdir().unwrap();
+        let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+        let log_path = tempdir.path().join("audit.log");
+        let log = AuditLog::open(&log_path).unwrap();
+
+        let mut f = XmpFile::new().unwrap();
+        f.set_audit_log(&log);
+        f.open_file(&purple_square, OpenFileOptions::default().for_update())
+            .unwrap();
+
+        let mut m = f.xmp().unwrap();
+        let before = xmp_hash(&m).unwrap();
+
+        m.set_property(xmp_ns::XMP, "CreatorTool", &"audited".into())
+            .unwrap();
+        let after = xmp_hash(&m).unwrap();
+
+        f.put_xmp(&m).unwrap();
+        f.try_close().unwrap();
+
+        assert_eq!(AuditLog::verify(&log_path).unwrap(), 1);
+
+        let contents = fs::read_to_string(&log_path).unwrap();
+        let fields: Vec<&str> = contents.trim_end().split('\t').collect();
+
+        assert_eq!(fields[2], "put_xmp");
+        assert_eq!(fields[3], purple_square);
+        assert_eq!(fields[4], before);
+        assert_eq!(fields[5], after);
+    }
+
+    #[test]
+    fn read_only_not_recorded() {
+        let tempdir = tempdir().unwrap();
+        let log_path = tempdir.path().join("audit.log");
+        let log = AuditLog::open(&log_path).unwrap();
+
+        let mut f = XmpFile::new().unwrap();
+        f.set_audit_log(&log);
+        f.open_file(
+            fixture_path("Purple Square.psd"),
+            OpenFileOptions::default(),
+        )
+        .unwrap();
+        f.try_close().unwrap();
+
+        assert_eq!(AuditLog::verify(&log_path).unwrap(), 0);
     }
 }
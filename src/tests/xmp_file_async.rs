@@ -0,0 +1,130 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::sync::mpsc;
+
+use tempfile::tempdir;
+use tokio_macros::test;
+
+use crate::{
+    tests::fixtures::*, xmp_file_async::run_blocking, xmp_ns, OpenFileOptions, XmpErrorType,
+    XmpFile, XmpFileAsync,
+};
+
+#[test]
+async fn read() {
+    let f = XmpFileAsync::open(
+        fixture_path("Purple Square.psd"),
+        OpenFileOptions::default(),
+    )
+    .await
+    .unwrap();
+
+    let m = f.xmp().await.unwrap().unwrap();
+    assert_eq!(
+        m.property(xmp_ns::XMP, "CreatorTool").unwrap().value,
+        "Adobe Photoshop CS2 Windows"
+    );
+
+    f.close().await.unwrap();
+}
+
+#[test]
+async fn update() {
+    let tempdir = tempdir().unwrap();
+    let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+    {
+        let f = XmpFileAsync::open(&purple_square, OpenFileOptions::default().for_update())
+            .await
+            .unwrap();
+
+        let mut m = f.xmp().await.unwrap().unwrap();
+        m.set_property(xmp_ns::XMP, "Label", &"async".into())
+            .unwrap();
+
+        f.put_xmp(&m).await.unwrap();
+        f.close().await.unwrap();
+    }
+
+    let mut f = XmpFile::new().unwrap();
+    f.open_file(&purple_square, OpenFileOptions::default())
+        .unwrap();
+
+    let m = f.xmp().unwrap();
+    assert_eq!(m.property(xmp_ns::XMP, "Label").unwrap().value, "async");
+}
+
+#[test]
+async fn open_error() {
+    let err = XmpFileAsync::open("doesnotexist.jpg", OpenFileOptions::default())
+        .await
+        .err()
+        .unwrap();
+
+    assert_eq!(err.error_type, XmpErrorType::NoFile);
+}
+
+#[test]
+async fn from_xmp_file() {
+    let mut sync = XmpFile::new().unwrap();
+    sync.open_file(
+        fixture_path("Purple Square.psd"),
+        OpenFileOptions::default(),
+    )
+    .unwrap();
+
+    let f = XmpFileAsync::from(sync);
+    assert!(f.xmp().await.unwrap().is_some());
+}
+
+#[test]
+async fn dropped_future() {
+    let f = XmpFileAsync::open(
+        fixture_path("Purple Square.psd"),
+        OpenFileOptions::default(),
+    )
+    .await
+    .unwrap();
+
+    // Start an operation and abandon it.
+    let mut pending = Box::pin(f.xmp());
+    let _ = futures::poll!(&mut pending);
+    drop(pending);
+
+    // The file is still usable.
+    assert!(f.xmp().await.unwrap().is_some());
+    f.close().await.unwrap();
+}
+
+#[test]
+async fn dropped_future_cancels_running_operation() {
+    let (started_tx, started_rx) = mpsc::channel();
+    let (dropped_tx, dropped_rx) = mpsc::channel();
+    let (cancelled_tx, cancelled_rx) = mpsc::channel();
+
+    let mut pending = Box::pin(run_blocking(move |token| {
+        started_tx.send(()).unwrap();
+        dropped_rx.recv().unwrap();
+        cancelled_tx.send(token.is_cancelled()).unwrap();
+        Ok(())
+    }));
+
+    let _ = futures::poll!(&mut pending);
+    started_rx.recv().unwrap();
+
+    drop(pending);
+    dropped_tx.send(()).unwrap();
+
+    assert!(cancelled_rx.recv().unwrap());
+}
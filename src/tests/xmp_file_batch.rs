@@ -0,0 +1,201 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use tempfile::tempdir;
+
+use crate::{
+    tests::fixtures::*, xmp_ns, FileBatchOutcome, OpenFileOptions, XmpError, XmpErrorType, XmpFile,
+    XmpFileBatch, XmpMeta, XmpValue,
+};
+
+fn label(path: &Path) -> Option<String> {
+    let mut f = XmpFile::new().unwrap();
+    f.open_file(path, OpenFileOptions::default().for_read())
+        .unwrap();
+
+    f.xmp()
+        .and_then(|m| m.property(xmp_ns::XMP, "Label"))
+        .map(|v| v.value)
+}
+
+#[test]
+fn run() {
+    for parallelism in [1, 4] {
+        let tempdir = tempdir().unwrap();
+        let paths: Vec<_> = (0..6)
+            .map(|i| {
+                let path = tempdir.path().join(format!("image{i}.psd"));
+                fs::copy(fixture_path("Purple Square.psd"), &path).unwrap();
+                path
+            })
+            .collect();
+
+        let report = XmpFileBatch::new(&paths)
+            .parallelism(parallelism)
+            .run(|path, meta| {
+                let name = path.file_stem().unwrap().to_str().unwrap();
+                meta.set_property(xmp_ns::XMP, "Label", &name.into())
+            });
+
+        assert!(report.is_ok());
+        assert_eq!(report.updated(), 6);
+
+        for (entry, path) in report.files.iter().zip(&paths) {
+            assert_eq!(&entry.path, path);
+            assert_eq!(
+                entry.outcome.as_ref().ok(),
+                Some(&FileBatchOutcome::Updated)
+            );
+
+            let name = path.file_stem().unwrap().to_str().unwrap();
+            assert_eq!(label(path).as_deref(), Some(name));
+        }
+    }
+}
+
+#[test]
+fn unchanged() {
+    let tempdir = tempdir().unwrap();
+    let path = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+    let report = XmpFileBatch::new([&path]).run(|_path, _meta| Ok(()));
+
+    assert!(report.is_ok());
+    assert_eq!(report.updated(), 0);
+    assert_eq!(
+        report.files[0].outcome.as_ref().ok(),
+        Some(&FileBatchOutcome::Unchanged)
+    );
+    assert_eq!(
+        fs::read(&path).unwrap(),
+        fs::read(fixture_path("Purple Square.psd")).unwrap()
+    );
+}
+
+#[test]
+fn errors_are_reported_per_file() {
+    let tempdir = tempdir().unwrap();
+    let good = PathBuf::from(temp_copy_of_fixture(tempdir.path(), "Purple Square.psd"));
+    let rejected = tempdir.path().join("rejected.psd");
+    fs::copy(&good, &rejected).unwrap();
+    let missing = tempdir.path().join("missing.psd");
+
+    let report = XmpFileBatch::new([&missing, &rejected, &good])
+        .parallelism(2)
+        .run(|path, meta| {
+            if path == rejected {
+                return Err(XmpError {
+                    error_type: XmpErrorType::BadParam,
+                    debug_message: "Rejected".to_owned(),
+                });
+            }
+
+            meta.set_property(xmp_ns::XMP, "Label", &"Done".into())
+        });
+
+    assert!(!report.is_ok());
+    assert_eq!(report.updated(), 1);
+
+    let errors: Vec<_> = report.errors().collect();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].0, missing);
+    assert_eq!(errors[1].0, rejected);
+    assert_eq!(errors[1].1.error_type, XmpErrorType::BadParam);
+
+    assert_eq!(label(&good).as_deref(), Some("Done"));
+    assert_eq!(
+        fs::read(&rejected).unwrap(),
+        fs::read(fixture_path("Purple Square.psd")).unwrap()
+    );
+}
+
+#[test]
+fn apply_template() {
+    let tempdir = tempdir().unwrap();
+    let path = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+    let mut template = XmpMeta::new().unwrap();
+    template
+        .set_property(xmp_ns::XMP, "Label", &"Template".into())
+        .unwrap();
+    template
+        .append_array_item(
+            xmp_ns::DC,
+            &XmpValue::from("subject").set_is_array(true),
+            &"keyword".into(),
+        )
+        .unwrap();
+
+    let report = XmpFileBatch::new([&path]).apply_template(&template);
+    assert!(report.is_ok());
+    assert_eq!(report.updated(), 1);
+
+    let mut f = XmpFile::new().unwrap();
+    f.open_file(&path, OpenFileOptions::default().for_read())
+        .unwrap();
+    let m = f.xmp().unwrap();
+
+    assert_eq!(m.property(xmp_ns::XMP, "Label").unwrap().value, "Template");
+
+    assert_eq!(
+        m.array_item(xmp_ns::DC, "subject", 1).unwrap().value,
+        "keyword"
+    );
+
+    // Properties that aren't in the template are kept.
+    assert!(m.contains_property(xmp_ns::XMP, "CreatorTool"));
+
+    // Applying the same template again changes nothing.
+    let report = XmpFileBatch::new([&path]).apply_template(&template);
+    assert_eq!(report.updated(), 0);
+}
+
+#[cfg(feature = "audit")]
+#[test]
+fn audit_log() {
+    use crate::xmp_audit::AuditLog;
+
+    let tempdir = tempdir().unwrap();
+    let [updated, unchanged] = ["updated.psd", "unchanged.psd"].map(|name| {
+        let path = tempdir.path().join(name);
+        fs::copy(fixture_path("Purple Square.psd"), &path).unwrap();
+        path
+    });
+
+    let log_path = tempdir.path().join("audit.log");
+    let log = AuditLog::open(&log_path).unwrap();
+
+    let report = XmpFileBatch::new([&updated, &unchanged])
+        .audit_log(&log)
+        .run(|path, meta| {
+            if path == updated {
+                meta.set_property(xmp_ns::XMP, "Label", &"Reviewed".into())?;
+            }
+            Ok(())
+        });
+
+    assert!(report.is_ok());
+    assert_eq!(report.updated(), 1);
+    assert_eq!(AuditLog::verify(&log_path).unwrap(), 1);
+
+    let contents = fs::read_to_string(&log_path).unwrap();
+    let fields: Vec<&str> = contents.trim_end().split('\t').collect();
+
+    assert_eq!(fields[2], "put_xmp");
+    assert_eq!(Path::new(fields[3]), updated);
+}
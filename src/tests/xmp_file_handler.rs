@@ -0,0 +1,136 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::{fs, ops::Range, sync::Once};
+
+use tempfile::tempdir;
+
+use crate::{
+    tests::fixtures::*, xmp_ns, FileFormat, OpenFileOptions, XmpErrorType, XmpFile, XmpFileHandler,
+    XmpResult,
+};
+
+const SIGNATURE: &[u8] = b"TESTFMT\0";
+
+/// A format made of a signature and the XMP packet, if any.
+struct TestHandler;
+
+impl XmpFileHandler for TestHandler {
+    fn check_format(&self, header: &[u8]) -> bool {
+        header.starts_with(SIGNATURE)
+    }
+
+    fn read_xmp(&self, file: &[u8]) -> XmpResult<Option<Range<usize>>> {
+        Ok((file.len() > SIGNATURE.len()).then_some(SIGNATURE.len()..file.len()))
+    }
+
+    fn write_xmp(&self, _file: &[u8], packet: &[u8]) -> XmpResult<Vec<u8>> {
+        Ok([SIGNATURE, packet].concat())
+    }
+}
+
+/// A handler that reports a packet past the end of the file.
+struct BrokenHandler;
+
+impl XmpFileHandler for BrokenHandler {
+    fn check_format(&self, header: &[u8]) -> bool {
+        header.starts_with(b"BROKEN\0")
+    }
+
+    fn read_xmp(&self, file: &[u8]) -> XmpResult<Option<Range<usize>>> {
+        Ok(Some(0..file.len() + 1))
+    }
+
+    fn write_xmp(&self, file: &[u8], _packet: &[u8]) -> XmpResult<Vec<u8>> {
+        Ok(file.to_vec())
+    }
+}
+
+fn register_handlers() {
+    static REGISTER: Once = Once::new();
+
+    REGISTER.call_once(|| {
+        XmpFile::register_handler(TestHandler);
+        XmpFile::register_handler(BrokenHandler);
+    });
+}
+
+#[test]
+fn check_format() {
+    register_handlers();
+
+    assert_eq!(
+        XmpFile::check_buffer_format(SIGNATURE).unwrap(),
+        Some(FileFormat::Unknown)
+    );
+}
+
+#[test]
+fn from_bytes() {
+    register_handlers();
+
+    let mut f = XmpFile::from_bytes(
+        SIGNATURE,
+        FileFormat::Unknown,
+        OpenFileOptions::default().for_update(),
+    )
+    .unwrap();
+
+    assert!(f.xmp().is_none());
+
+    f.put_xmp(&meta_with_label("Custom")).unwrap();
+    let bytes = f.into_bytes().unwrap();
+    assert!(bytes.starts_with(SIGNATURE));
+
+    let mut f =
+        XmpFile::from_bytes(&bytes, FileFormat::Unknown, OpenFileOptions::default()).unwrap();
+
+    let m = f.xmp().unwrap();
+    assert_eq!(m.property(xmp_ns::XMP, "Label").unwrap().value, "Custom");
+}
+
+#[test]
+fn open_file() {
+    register_handlers();
+
+    let tempdir = tempdir().unwrap();
+    let path = tempdir.path().join("image.testfmt");
+    fs::write(&path, SIGNATURE).unwrap();
+
+    let mut f = XmpFile::new().unwrap();
+    f.open_file(&path, OpenFileOptions::default().for_update())
+        .unwrap();
+
+    assert!(f.can_put_xmp(&meta_with_label("Custom")));
+    f.put_xmp(&meta_with_label("Custom")).unwrap();
+    f.close().unwrap();
+
+    let mut f = XmpFile::new().unwrap();
+    f.open_file(&path, OpenFileOptions::default()).unwrap();
+
+    let m = f.xmp().unwrap();
+    assert_eq!(m.property(xmp_ns::XMP, "Label").unwrap().value, "Custom");
+}
+
+#[test]
+fn packet_out_of_bounds() {
+    register_handlers();
+
+    let Err(err) =
+        XmpFile::from_bytes(b"BROKEN\0", FileFormat::Unknown, OpenFileOptions::default())
+    else {
+        panic!("Expected an error for a packet out of bounds");
+    };
+
+    assert_eq!(err.error_type, XmpErrorType::InternalFailure);
+}
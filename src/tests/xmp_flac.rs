@@ -0,0 +1,217 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::fs;
+
+use tempfile::tempdir;
+
+use crate::{tests::fixtures::*, xmp_ns, FileFormat, OpenFileOptions, XmpFile, XmpMeta};
+
+/// Stand-in for FLAC frames. Not valid audio.
+const AUDIO: &[u8] = &[0xff, 0xf8, 1, 2, 3, 4];
+
+const STREAMINFO: u8 = 0;
+const PADDING: u8 = 1;
+const VORBIS_COMMENT: u8 = 4;
+
+fn vorbis_comments(comments: &[&str]) -> Vec<u8> {
+    let mut data = 6u32.to_le_bytes().to_vec();
+    data.extend_from_slice(b"vendor");
+    data.extend_from_slice(&(comments.len() as u32).to_le_bytes());
+
+    for comment in comments {
+        data.extend_from_slice(&(comment.len() as u32).to_le_bytes());
+        data.extend_from_slice(comment.as_bytes());
+    }
+
+    data
+}
+
+fn flac(blocks: &[(u8, &[u8])]) -> Vec<u8> {
+    let mut file = b"fLaC".to_vec();
+
+    for (index, (block_type, data)) in blocks.iter().enumerate() {
+        let last = if index + 1 == blocks.len() { 0x80 } else { 0 };
+        file.push(block_type | last);
+        file.extend_from_slice(&(data.len() as u32).to_be_bytes()[1..]);
+        file.extend_from_slice(data);
+    }
+
+    file.extend_from_slice(AUDIO);
+    file
+}
+
+/// Returns the types of the metadata blocks in `file`.
+fn block_types(file: &[u8]) -> Vec<u8> {
+    let mut types = Vec::new();
+    let mut pos = 4;
+
+    loop {
+        let header = &file[pos..pos + 4];
+        types.push(header[0] & 0x7f);
+        pos += 4 + u32::from_be_bytes([0, header[1], header[2], header[3]]) as usize;
+
+        if header[0] & 0x80 != 0 {
+            assert_eq!(&file[pos..], AUDIO);
+            return types;
+        }
+    }
+}
+
+#[test]
+fn check_format() {
+    assert_eq!(
+        XmpFile::check_buffer_format(&flac(&[(STREAMINFO, &[0; 34])])).unwrap(),
+        Some(FileFormat::Flac)
+    );
+}
+
+#[test]
+fn no_xmp() {
+    assert_eq!(
+        label(&flac(&[(STREAMINFO, &[0; 34])]), FileFormat::Flac),
+        None
+    );
+}
+
+#[test]
+fn add_xmp() {
+    let updated = put_label(&flac(&[(STREAMINFO, &[0; 34])]), "Added");
+
+    assert_eq!(block_types(&updated), [STREAMINFO, 2]);
+    assert_eq!(label(&updated, FileFormat::Flac).as_deref(), Some("Added"));
+}
+
+#[test]
+fn padding_absorbs_xmp() {
+    let comments = vorbis_comments(&["TITLE=Track"]);
+    let original = flac(&[
+        (STREAMINFO, &[0; 34]),
+        (VORBIS_COMMENT, &comments),
+        (PADDING, &[0; 8192]),
+    ]);
+
+    let updated = put_label(&original, "First");
+    assert_eq!(updated.len(), original.len());
+    assert_eq!(
+        block_types(&updated),
+        [STREAMINFO, VORBIS_COMMENT, 2, PADDING]
+    );
+
+    let updated = put_label(&updated, "Second");
+    assert_eq!(updated.len(), original.len());
+    assert_eq!(label(&updated, FileFormat::Flac).as_deref(), Some("Second"));
+}
+
+#[test]
+fn vorbis_comments_not_imported_by_default() {
+    let comments = vorbis_comments(&["TITLE=Track"]);
+    let flac = flac(&[(STREAMINFO, &[0; 34]), (VORBIS_COMMENT, &comments)]);
+
+    let mut f = XmpFile::from_bytes(&flac, FileFormat::Flac, OpenFileOptions::default()).unwrap();
+    assert!(f.xmp().is_none());
+}
+
+#[test]
+fn import_vorbis_comments() {
+    let comments = vorbis_comments(&[
+        "TITLE=Track",
+        "artist=First",
+        "ARTIST=Second",
+        "DATE=2024-05-01",
+    ]);
+    let flac = flac(&[(STREAMINFO, &[0; 34]), (VORBIS_COMMENT, &comments)]);
+
+    let mut f = XmpFile::from_bytes(
+        &flac,
+        FileFormat::Flac,
+        OpenFileOptions::default().import_vorbis_comments(),
+    )
+    .unwrap();
+
+    let xmp = f.xmp().unwrap();
+
+    let (title, _) = xmp
+        .localized_text(xmp_ns::DC, "title", None, "x-default")
+        .unwrap();
+    assert_eq!(title.value, "Track");
+
+    let creators: Vec<String> = xmp
+        .property_array(xmp_ns::DC, "creator")
+        .map(|v| v.value)
+        .collect();
+    assert_eq!(creators, ["First", "Second"]);
+
+    assert_eq!(
+        xmp.property(xmp_ns::DC, "date[1]").unwrap().value,
+        "2024-05-01"
+    );
+}
+
+#[test]
+fn xmp_takes_precedence_over_vorbis_comments() {
+    let comments = vorbis_comments(&["TITLE=From comment"]);
+    let original = flac(&[(STREAMINFO, &[0; 34]), (VORBIS_COMMENT, &comments)]);
+
+    let mut meta = XmpMeta::new().unwrap();
+    meta.set_localized_text(xmp_ns::DC, "title", None, "x-default", "From XMP")
+        .unwrap();
+
+    let mut f = XmpFile::from_bytes(
+        &original,
+        FileFormat::Flac,
+        OpenFileOptions::default().for_update(),
+    )
+    .unwrap();
+    f.put_xmp(&meta).unwrap();
+    let updated = f.into_bytes().unwrap();
+
+    let mut f = XmpFile::from_bytes(
+        &updated,
+        FileFormat::Flac,
+        OpenFileOptions::default().import_vorbis_comments(),
+    )
+    .unwrap();
+
+    let (title, _) = f
+        .xmp()
+        .unwrap()
+        .localized_text(xmp_ns::DC, "title", None, "x-default")
+        .unwrap();
+    assert_eq!(title.value, "From XMP");
+}
+
+#[test]
+fn truncated_block() {
+    let mut flac = flac(&[(STREAMINFO, &[0; 34])]);
+    flac.truncate(20);
+
+    assert!(XmpFile::from_bytes(&flac, FileFormat::Flac, OpenFileOptions::default()).is_err());
+}
+
+#[test]
+fn sample_file() {
+    let tempdir = tempdir().unwrap();
+    let sample = temp_copy_of_fixture(tempdir.path(), "sample.flac");
+
+    assert_eq!(label_of_file(&sample).as_deref(), Some("Sample"));
+
+    put_label_in_file(&sample, "Updated");
+    assert_eq!(label_of_file(&sample).as_deref(), Some("Updated"));
+
+    // The stream info is unchanged.
+    assert_eq!(
+        fs::read(&sample).unwrap()[..42],
+        fs::read(fixture_path("sample.flac")).unwrap()[..42]
+    );
+}
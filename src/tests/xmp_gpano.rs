@@ -0,0 +1,184 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::str::FromStr;
+
+use crate::{xmp_gpano::PhotoSphere, xmp_ns, XmpErrorType, XmpMeta};
+
+const PHOTO_SPHERE: &str = r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <rdf:Description rdf:about="" xmlns:GPano="http://ns.google.com/photos/1.0/panorama/"
+        GPano:ProjectionType="equirectangular"
+        GPano:UsePanoramaViewer="True"
+        GPano:CroppedAreaImageWidthPixels="4000"
+        GPano:CroppedAreaImageHeightPixels="2000"
+        GPano:FullPanoWidthPixels="8000"
+        GPano:FullPanoHeightPixels="4000"
+        GPano:CroppedAreaLeftPixels="2000"
+        GPano:CroppedAreaTopPixels="1000"
+        GPano:InitialViewHeadingDegrees="180"
+        GPano:PosePitchDegrees="-2.5"/>
+</rdf:RDF>"#;
+
+fn example() -> PhotoSphere {
+    PhotoSphere {
+        projection_type: Some("equirectangular".to_owned()),
+        use_panorama_viewer: Some(true),
+        cropped_area_image_width_pixels: Some(4000),
+        cropped_area_image_height_pixels: Some(2000),
+        full_pano_width_pixels: Some(8000),
+        full_pano_height_pixels: Some(4000),
+        cropped_area_left_pixels: Some(2000),
+        cropped_area_top_pixels: Some(1000),
+        initial_view_heading_degrees: Some(180.0),
+        pose_pitch_degrees: Some(-2.5),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn read_from() {
+    let m = XmpMeta::from_str(PHOTO_SPHERE).unwrap();
+    assert_eq!(PhotoSphere::read_from(&m), Some(example()));
+}
+
+#[test]
+fn read_from_empty() {
+    let m = XmpMeta::new().unwrap();
+    assert_eq!(PhotoSphere::read_from(&m), None);
+}
+
+#[test]
+fn read_from_bad_value() {
+    let mut m = XmpMeta::from_str(PHOTO_SPHERE).unwrap();
+    m.set_property(xmp_ns::GPANO, "FullPanoWidthPixels", &"wide".into())
+        .unwrap();
+
+    let sphere = PhotoSphere::read_from(&m).unwrap();
+    assert_eq!(sphere.full_pano_width_pixels, None);
+    assert_eq!(sphere.full_pano_height_pixels, Some(4000));
+}
+
+#[test]
+fn write_to() {
+    let mut m = XmpMeta::new().unwrap();
+    example().write_to(&mut m).unwrap();
+
+    assert_eq!(PhotoSphere::read_from(&m), Some(example()));
+    assert_eq!(
+        m.property(xmp_ns::GPANO, "UsePanoramaViewer")
+            .unwrap()
+            .value,
+        "True"
+    );
+    assert_eq!(
+        m.property(xmp_ns::GPANO, "FullPanoWidthPixels")
+            .unwrap()
+            .value,
+        "8000"
+    );
+    assert_eq!(
+        XmpMeta::namespace_prefix(xmp_ns::GPANO).as_deref(),
+        Some("GPano:")
+    );
+}
+
+#[test]
+fn write_to_removes_absent_fields() {
+    let mut m = XmpMeta::from_str(PHOTO_SPHERE).unwrap();
+
+    let sphere = PhotoSphere {
+        pose_pitch_degrees: None,
+        ..example()
+    };
+    sphere.write_to(&mut m).unwrap();
+
+    assert!(!m.contains_property(xmp_ns::GPANO, "PosePitchDegrees"));
+    assert!(m.contains_property(xmp_ns::GPANO, "ProjectionType"));
+}
+
+#[test]
+fn write_to_init_fail() {
+    let mut m = XmpMeta::new_fail();
+    assert_eq!(
+        example().write_to(&mut m).unwrap_err().error_type,
+        XmpErrorType::NoCppToolkit
+    );
+}
+
+#[test]
+fn validate() {
+    example().validate().unwrap();
+
+    let problems = [
+        PhotoSphere {
+            projection_type: None,
+            ..example()
+        },
+        PhotoSphere {
+            projection_type: Some("cylindrical".to_owned()),
+            ..example()
+        },
+        PhotoSphere {
+            full_pano_width_pixels: None,
+            ..example()
+        },
+        PhotoSphere {
+            full_pano_height_pixels: Some(0),
+            ..example()
+        },
+        PhotoSphere {
+            cropped_area_left_pixels: Some(-1),
+            ..example()
+        },
+        PhotoSphere {
+            cropped_area_left_pixels: Some(4001),
+            ..example()
+        },
+        PhotoSphere {
+            cropped_area_image_height_pixels: Some(3001),
+            ..example()
+        },
+        PhotoSphere {
+            initial_view_heading_degrees: Some(360.5),
+            ..example()
+        },
+        PhotoSphere {
+            pose_pitch_degrees: Some(-91.0),
+            ..example()
+        },
+        PhotoSphere {
+            initial_view_roll_degrees: Some(181.0),
+            ..example()
+        },
+    ];
+
+    for sphere in problems {
+        assert_eq!(
+            sphere.validate().unwrap_err().error_type,
+            XmpErrorType::BadValue,
+            "{sphere:?}"
+        );
+    }
+}
+
+#[test]
+fn validate_minimal() {
+    PhotoSphere {
+        projection_type: Some("equirectangular".to_owned()),
+        full_pano_width_pixels: Some(8000),
+        full_pano_height_pixels: Some(4000),
+        ..Default::default()
+    }
+    .validate()
+    .unwrap();
+}
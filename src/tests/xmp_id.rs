@@ -0,0 +1,158 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+mod new_uuid {
+    use crate::xmp_id;
+
+    #[test]
+    fn format() {
+        let id = xmp_id::new_uuid();
+
+        assert_eq!(id.len(), 36);
+        for (i, c) in id.char_indices() {
+            match i {
+                8 | 13 | 18 | 23 => assert_eq!(c, '-'),
+                14 => assert_eq!(c, '4'),
+                19 => assert!("89ab".contains(c)),
+                _ => assert!(c.is_ascii_hexdigit() && !c.is_ascii_uppercase()),
+            }
+        }
+    }
+
+    #[test]
+    fn unique() {
+        let a = xmp_id::new_uuid();
+        let b = xmp_id::new_uuid();
+        assert_ne!(a, b);
+    }
+}
+
+#[test]
+fn new_document_id() {
+    let id = crate::xmp_id::new_document_id();
+    assert!(id.starts_with("xmp.did:"));
+    assert_eq!(id.len(), 44);
+}
+
+#[test]
+fn new_instance_id() {
+    let id = crate::xmp_id::new_instance_id();
+    assert!(id.starts_with("xmp.iid:"));
+    assert_eq!(id.len(), 44);
+}
+
+mod deterministic_mode {
+    use crate::{
+        xmp_id::{self, DeterministicMode},
+        xmp_ns, XmpDate, XmpDateTime, XmpMeta, XmpTime, XmpTimeZone,
+    };
+
+    fn frozen() -> XmpDateTime {
+        XmpDateTime {
+            date: Some(XmpDate {
+                year: 2026,
+                month: 1,
+                day: 2,
+            }),
+            time: Some(XmpTime {
+                hour: 3,
+                minute: 4,
+                second: 5,
+                nanosecond: 0,
+                time_zone: Some(XmpTimeZone { hour: 0, minute: 0 }),
+            }),
+        }
+    }
+
+    #[test]
+    fn freezes_time() {
+        let _guard = DeterministicMode::new(frozen());
+
+        assert_eq!(XmpDateTime::current().unwrap(), frozen());
+        assert_eq!(XmpDateTime::current().unwrap(), frozen());
+    }
+
+    #[test]
+    fn sequential_ids() {
+        let _guard = DeterministicMode::new(frozen());
+
+        assert_eq!(xmp_id::new_uuid(), "00000000-0000-4000-8000-000000000001");
+        assert_eq!(
+            xmp_id::new_document_id(),
+            "xmp.did:00000000-0000-4000-8000-000000000002"
+        );
+        assert_eq!(
+            xmp_id::new_instance_id(),
+            "xmp.iid:00000000-0000-4000-8000-000000000003"
+        );
+    }
+
+    #[test]
+    fn restored_on_drop() {
+        {
+            let _guard = DeterministicMode::new(frozen());
+            assert_eq!(XmpDateTime::current().unwrap(), frozen());
+        }
+
+        assert_ne!(XmpDateTime::current().unwrap(), frozen());
+        assert_ne!(xmp_id::new_uuid(), "00000000-0000-4000-8000-000000000001");
+    }
+
+    #[test]
+    fn nested() {
+        let _outer = DeterministicMode::new(frozen());
+        assert_eq!(xmp_id::new_uuid(), "00000000-0000-4000-8000-000000000001");
+
+        {
+            let inner_time = XmpDateTime {
+                date: Some(XmpDate {
+                    year: 2030,
+                    month: 6,
+                    day: 7,
+                }),
+                time: None,
+            };
+            let _inner = DeterministicMode::new(inner_time.clone());
+
+            assert_eq!(XmpDateTime::current().unwrap(), inner_time);
+            assert_eq!(xmp_id::new_uuid(), "00000000-0000-4000-8000-000000000001");
+        }
+
+        assert_eq!(XmpDateTime::current().unwrap(), frozen());
+        assert_eq!(xmp_id::new_uuid(), "00000000-0000-4000-8000-000000000002");
+    }
+
+    #[test]
+    fn stable_serialization() {
+        let serialize = || {
+            let _guard = DeterministicMode::new(frozen());
+
+            let mut m = XmpMeta::new().unwrap();
+            m.set_property_date(
+                xmp_ns::XMP,
+                "ModifyDate",
+                &XmpDateTime::current().unwrap().into(),
+            )
+            .unwrap();
+            m.set_property(
+                xmp_ns::XMP_MM,
+                "DocumentID",
+                &xmp_id::new_document_id().into(),
+            )
+            .unwrap();
+            m.to_string()
+        };
+
+        assert_eq!(serialize(), serialize());
+    }
+}
@@ -745,6 +745,166 @@ fn middle_property_just_children() {
     );
 }
 
+mod kind_filters {
+    use super::test_fixture;
+    use crate::{IterOptions, XmpProperty, XmpValue};
+
+    // Returns the properties that the unfiltered iterator returns and
+    // that match `filter`.
+    fn expected(filter: impl Fn(&XmpValue<String>) -> bool) -> Vec<XmpProperty> {
+        test_fixture()
+            .iter(IterOptions::default())
+            .filter(|prop| filter(&prop.value))
+            .collect()
+    }
+
+    #[test]
+    fn arrays_only() {
+        let meta = test_fixture();
+        let props: Vec<XmpProperty> = meta.iter(IterOptions::default().arrays_only()).collect();
+
+        assert!(!props.is_empty());
+        assert_eq!(props, expected(|value| value.is_array()));
+    }
+
+    #[test]
+    fn structs_only() {
+        let meta = test_fixture();
+        let props: Vec<XmpProperty> = meta.iter(IterOptions::default().structs_only()).collect();
+
+        assert!(!props.is_empty());
+        assert_eq!(props, expected(|value| value.is_struct()));
+    }
+
+    #[test]
+    fn simple_values_only() {
+        let meta = test_fixture();
+        let props: Vec<XmpProperty> = meta
+            .iter(IterOptions::default().simple_values_only())
+            .collect();
+
+        assert!(props.iter().any(|prop| prop.value.has_qualifiers()));
+        assert_eq!(
+            props,
+            expected(|value| !value.is_array() && !value.is_struct() && !value.is_schema_node())
+        );
+    }
+
+    #[test]
+    fn qualified_only() {
+        let meta = test_fixture();
+        let props: Vec<XmpProperty> = meta.iter(IterOptions::default().qualified_only()).collect();
+
+        assert!(!props.is_empty());
+        assert_eq!(props, expected(|value| value.has_qualifiers()));
+    }
+
+    #[test]
+    fn combined() {
+        let meta = test_fixture();
+        let props: Vec<XmpProperty> = meta
+            .iter(IterOptions::default().arrays_only().structs_only())
+            .collect();
+
+        assert_eq!(
+            props,
+            expected(|value| value.is_array() || value.is_struct())
+        );
+    }
+
+    #[test]
+    fn with_omit_qualifiers() {
+        let meta = test_fixture();
+        let props: Vec<XmpProperty> = meta
+            .iter(
+                IterOptions::default()
+                    .simple_values_only()
+                    .omit_qualifiers(),
+            )
+            .collect();
+
+        assert!(!props.is_empty());
+        assert!(props.iter().all(|prop| !prop.value.is_qualifier()));
+    }
+
+    #[test]
+    fn within_property() {
+        let meta = test_fixture();
+        let props: Vec<String> = meta
+            .iter(
+                IterOptions::default()
+                    .property("ns:test1/", "ArrayProp1")
+                    .simple_values_only(),
+            )
+            .map(|prop| prop.name)
+            .collect();
+
+        assert_eq!(props, ["ns1:ArrayProp1[1]", "ns1:ArrayProp1[2]"]);
+    }
+}
+
+#[test]
+fn skip_large_array() {
+    let mut meta = XmpMeta::new().unwrap();
+    meta.set_property(xmp_ns::PHOTOSHOP, "ColorMode", &"3".into())
+        .unwrap();
+
+    for i in 0..2000 {
+        meta.append_array_item(
+            xmp_ns::PHOTOSHOP,
+            &XmpValue::from("DocumentAncestors").set_is_array(true),
+            &format!("xmp.did:{i}").into(),
+        )
+        .unwrap();
+    }
+
+    meta.set_property(xmp_ns::PHOTOSHOP, "History", &"Saved".into())
+        .unwrap();
+
+    let mut prop_iter = meta.iter(IterOptions::default());
+    let mut names: Vec<String> = vec![];
+
+    while let Some(prop) = prop_iter.next() {
+        if prop.name == "photoshop:DocumentAncestors" {
+            prop_iter.skip_subtree();
+        }
+        names.push(prop.name);
+    }
+
+    assert_eq!(
+        names,
+        [
+            "",
+            "photoshop:ColorMode",
+            "photoshop:DocumentAncestors",
+            "photoshop:History"
+        ]
+    );
+}
+
+#[test]
+fn skip_rest_of_schema() {
+    let mut meta = XmpMeta::new().unwrap();
+    meta.set_property(xmp_ns::PHOTOSHOP, "ColorMode", &"3".into())
+        .unwrap();
+    meta.set_property(xmp_ns::PHOTOSHOP, "History", &"Saved".into())
+        .unwrap();
+    meta.set_property(xmp_ns::XMP, "Label", &"Final".into())
+        .unwrap();
+
+    let mut prop_iter = meta.iter(IterOptions::default());
+    let mut names: Vec<String> = vec![];
+
+    while let Some(prop) = prop_iter.next() {
+        if prop.schema_ns == xmp_ns::PHOTOSHOP && !prop.value.is_schema_node() {
+            prop_iter.skip_siblings();
+        }
+        names.push(prop.name);
+    }
+
+    assert_eq!(names, ["", "photoshop:ColorMode", "", "xmp:Label"]);
+}
+
 #[test]
 fn skip_children_and_siblings() {
     let meta = test_fixture();
@@ -0,0 +1,242 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::str::FromStr;
+
+use crate::{xmp_ns, JpegXmpPackets, XmpErrorType, XmpMeta};
+
+fn large_meta() -> XmpMeta {
+    let mut meta = XmpMeta::new().unwrap();
+    meta.set_property(xmp_ns::DC, "format", &"image/jpeg".into())
+        .unwrap();
+    meta.set_property(xmp_ns::PHOTOSHOP, "History", &"x".repeat(150_000).into())
+        .unwrap();
+    meta
+}
+
+#[test]
+fn small_metadata_fits() {
+    let mut meta = XmpMeta::new().unwrap();
+    meta.set_property(xmp_ns::DC, "format", &"image/jpeg".into())
+        .unwrap();
+
+    let packets = meta.split_for_jpeg().unwrap();
+    assert!(packets.extended.is_none());
+    assert!(packets.guid.is_none());
+    assert!(packets.extended_segments().is_empty());
+
+    let standard = XmpMeta::from_str(&packets.standard).unwrap();
+    assert!(standard.contains_property(xmp_ns::DC, "format"));
+    assert!(!standard.contains_property(xmp_ns::XMP_NOTE, "HasExtendedXMP"));
+}
+
+#[test]
+fn large_metadata_is_split() {
+    let packets = large_meta().split_for_jpeg().unwrap();
+
+    assert!(packets.standard.len() < 65000);
+
+    let guid = packets.guid.clone().unwrap();
+    assert_eq!(guid.len(), 32);
+    assert!(guid
+        .bytes()
+        .all(|b| b.is_ascii_digit() || (b'A'..=b'F').contains(&b)));
+
+    let standard = XmpMeta::from_str(&packets.standard).unwrap();
+    assert!(standard.contains_property(xmp_ns::DC, "format"));
+    assert!(!standard.contains_property(xmp_ns::PHOTOSHOP, "History"));
+    assert_eq!(
+        standard
+            .property(xmp_ns::XMP_NOTE, "HasExtendedXMP")
+            .unwrap()
+            .value,
+        guid
+    );
+
+    let extended = XmpMeta::from_str(packets.extended.as_ref().unwrap()).unwrap();
+    assert!(extended.contains_property(xmp_ns::PHOTOSHOP, "History"));
+    assert!(!extended.contains_property(xmp_ns::DC, "format"));
+}
+
+#[test]
+fn standard_segment() {
+    let packets = large_meta().split_for_jpeg().unwrap();
+    let segment = packets.standard_segment();
+
+    assert!(segment.starts_with(b"http://ns.adobe.com/xap/1.0/\0"));
+    assert_eq!(&segment[29..], packets.standard.as_bytes());
+}
+
+#[test]
+fn extended_segments() {
+    let packets = large_meta().split_for_jpeg().unwrap();
+    let extended = packets.extended.as_ref().unwrap();
+    let guid = packets.guid.as_ref().unwrap();
+
+    let segments = packets.extended_segments();
+    assert_eq!(segments.len(), (extended.len() + 64999) / 65000);
+
+    let mut offset = 0;
+    for segment in &segments {
+        assert!(segment.len() <= 65533);
+        assert!(segment.starts_with(b"http://ns.adobe.com/xmp/extension/\0"));
+        assert_eq!(&segment[35..67], guid.as_bytes());
+        assert_eq!(segment[67..71], (extended.len() as u32).to_be_bytes());
+        assert_eq!(segment[71..75], (offset as u32).to_be_bytes());
+
+        offset += segment.len() - 75;
+    }
+
+    assert_eq!(offset, extended.len());
+}
+
+#[test]
+fn reassemble_extended() {
+    let packets = large_meta().split_for_jpeg().unwrap();
+    let guid = packets.guid.as_ref().unwrap();
+
+    let mut segments = packets.extended_segments();
+    segments.reverse();
+    segments.push(packets.standard_segment());
+
+    let extended =
+        JpegXmpPackets::reassemble_extended(guid, segments.iter().map(Vec::as_slice)).unwrap();
+
+    assert_eq!(&extended, packets.extended.as_ref().unwrap());
+}
+
+#[test]
+fn reassemble_extended_wrong_guid() {
+    let packets = large_meta().split_for_jpeg().unwrap();
+    let segments = packets.extended_segments();
+
+    let err = JpegXmpPackets::reassemble_extended(
+        "00000000000000000000000000000000",
+        segments.iter().map(Vec::as_slice),
+    )
+    .unwrap_err();
+
+    assert_eq!(err.error_type, XmpErrorType::BadJpeg);
+}
+
+#[test]
+fn reassemble_extended_missing_portion() {
+    let packets = large_meta().split_for_jpeg().unwrap();
+    let guid = packets.guid.as_ref().unwrap();
+    let segments = packets.extended_segments();
+
+    let err = JpegXmpPackets::reassemble_extended(guid, segments.iter().skip(1).map(Vec::as_slice))
+        .unwrap_err();
+
+    assert_eq!(err.error_type, XmpErrorType::BadJpeg);
+}
+
+#[test]
+fn join_extended() {
+    let packets = large_meta().split_for_jpeg().unwrap();
+
+    let joined =
+        XmpMeta::join_extended(&packets.standard, packets.extended.as_ref().unwrap()).unwrap();
+
+    assert!(joined.contains_property(xmp_ns::DC, "format"));
+    assert_eq!(
+        joined
+            .property(xmp_ns::PHOTOSHOP, "History")
+            .unwrap()
+            .value
+            .len(),
+        150_000
+    );
+    assert!(!joined.contains_property(xmp_ns::XMP_NOTE, "HasExtendedXMP"));
+}
+
+#[test]
+fn join_extended_without_extension() {
+    let mut meta = XmpMeta::new().unwrap();
+    meta.set_property(xmp_ns::DC, "format", &"image/jpeg".into())
+        .unwrap();
+
+    let packets = meta.split_for_jpeg().unwrap();
+    let joined = XmpMeta::join_extended(&packets.standard, "").unwrap();
+
+    assert!(joined.contains_property(xmp_ns::DC, "format"));
+}
+
+#[test]
+fn init_fail() {
+    let meta = XmpMeta::new_fail();
+    assert_eq!(
+        meta.split_for_jpeg().unwrap_err().error_type,
+        XmpErrorType::NoCppToolkit
+    );
+}
+
+mod legacy_segments {
+    use crate::xmp_jpeg::{legacy_segments, replace_legacy_segments};
+
+    fn segment(marker: u8, data: &[u8]) -> Vec<u8> {
+        let mut segment = vec![0xff, marker];
+        segment.extend_from_slice(&(data.len() as u16 + 2).to_be_bytes());
+        segment.extend_from_slice(data);
+        segment
+    }
+
+    fn jpeg(segments: &[Vec<u8>]) -> Vec<u8> {
+        let mut jpeg = vec![0xff, 0xd8];
+        segments.iter().for_each(|s| jpeg.extend_from_slice(s));
+        jpeg.extend_from_slice(&[0xff, 0xda, 0, 2, 1, 2, 3, 0xff, 0xd9]);
+        jpeg
+    }
+
+    #[test]
+    fn finds_exif_and_iptc() {
+        let jfif = segment(0xe0, b"JFIF\0");
+        let exif = segment(0xe1, b"Exif\0\0old");
+        let xmp = segment(0xe1, b"http://ns.adobe.com/xap/1.0/\0<x/>");
+        let iptc = segment(0xed, b"Photoshop 3.0\0old");
+
+        let file = jpeg(&[jfif, exif.clone(), xmp, iptc.clone()]);
+        assert_eq!(legacy_segments(&file).unwrap(), vec![exif, iptc]);
+    }
+
+    #[test]
+    fn replaces_in_place() {
+        let jfif = segment(0xe0, b"JFIF\0");
+        let old_exif = segment(0xe1, b"Exif\0\0old");
+        let new_exif = segment(0xe1, b"Exif\0\0new");
+        let xmp = segment(0xe1, b"http://ns.adobe.com/xap/1.0/\0<x/>");
+        let new_iptc = segment(0xed, b"Photoshop 3.0\0new");
+
+        let file = jpeg(&[jfif.clone(), new_exif, xmp.clone(), new_iptc]);
+        let restored = replace_legacy_segments(&file, std::slice::from_ref(&old_exif)).unwrap();
+
+        assert_eq!(restored, jpeg(&[jfif, old_exif, xmp]));
+    }
+
+    #[test]
+    fn inserts_after_jfif() {
+        let jfif = segment(0xe0, b"JFIF\0");
+        let exif = segment(0xe1, b"Exif\0\0old");
+        let xmp = segment(0xe1, b"http://ns.adobe.com/xap/1.0/\0<x/>");
+
+        let file = jpeg(&[jfif.clone(), xmp.clone()]);
+        let restored = replace_legacy_segments(&file, std::slice::from_ref(&exif)).unwrap();
+
+        assert_eq!(restored, jpeg(&[jfif, exif, xmp]));
+    }
+
+    #[test]
+    fn not_jpeg() {
+        assert!(legacy_segments(b"GIF89a").is_err());
+    }
+}
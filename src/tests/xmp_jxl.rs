@@ -0,0 +1,177 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::fs;
+
+use tempfile::tempdir;
+
+use crate::{tests::fixtures::*, FileFormat, XmpFile};
+
+const SIGNATURE_BOX: &[u8] = b"\0\0\0\x0cJXL \x0d\x0a\x87\x0a";
+
+/// A bare codestream. The image data is not valid.
+const CODESTREAM: &[u8] = &[0xff, 0x0a, 1, 2, 3, 4, 5];
+
+fn container(boxes: &[(&[u8; 4], &[u8])]) -> Vec<u8> {
+    let mut file = SIGNATURE_BOX.to_vec();
+
+    for (box_type, data) in boxes {
+        file.extend_from_slice(&(data.len() as u32 + 8).to_be_bytes());
+        file.extend_from_slice(*box_type);
+        file.extend_from_slice(data);
+    }
+
+    file
+}
+
+/// Returns the types and payloads of the boxes in `file`.
+fn boxes(file: &[u8]) -> Vec<(String, Vec<u8>)> {
+    let mut boxes = Vec::new();
+    let mut pos = 0;
+
+    while pos < file.len() {
+        let len = u32::from_be_bytes(file[pos..pos + 4].try_into().unwrap()) as usize;
+        let box_type = String::from_utf8_lossy(&file[pos + 4..pos + 8]).into_owned();
+        boxes.push((box_type, file[pos + 8..pos + len].to_vec()));
+        pos += len;
+    }
+
+    assert_eq!(pos, file.len());
+    boxes
+}
+
+fn box_names(file: &[u8]) -> Vec<String> {
+    boxes(file).into_iter().map(|(name, _)| name).collect()
+}
+
+#[test]
+fn check_format() {
+    assert_eq!(
+        XmpFile::check_buffer_format(CODESTREAM).unwrap(),
+        Some(FileFormat::JpegXl)
+    );
+
+    assert_eq!(
+        XmpFile::check_buffer_format(&container(&[(b"jxlc", CODESTREAM)])).unwrap(),
+        Some(FileFormat::JpegXl)
+    );
+}
+
+#[test]
+fn no_xmp() {
+    assert_eq!(label(CODESTREAM, FileFormat::JpegXl), None);
+    assert_eq!(
+        label(&container(&[(b"jxlc", CODESTREAM)]), FileFormat::JpegXl),
+        None
+    );
+}
+
+#[test]
+fn read_xmp() {
+    let packet = meta_with_label("Embedded").to_string();
+    let jxl = container(&[
+        (b"ftyp", b"jxl \0\0\0\0jxl "),
+        (b"xml ", packet.as_bytes()),
+        (b"jxlc", CODESTREAM),
+    ]);
+
+    assert_eq!(label(&jxl, FileFormat::JpegXl).as_deref(), Some("Embedded"));
+}
+
+#[test]
+fn wrap_bare_codestream() {
+    let jxl = put_label(CODESTREAM, "Wrapped");
+
+    assert!(jxl.starts_with(SIGNATURE_BOX));
+    assert_eq!(box_names(&jxl), ["JXL ", "ftyp", "xml ", "jxlc"]);
+    assert_eq!(boxes(&jxl)[3].1, CODESTREAM);
+
+    assert_eq!(label(&jxl, FileFormat::JpegXl).as_deref(), Some("Wrapped"));
+}
+
+#[test]
+fn add_xmp_before_codestream() {
+    let jxl = container(&[
+        (b"ftyp", b"jxl \0\0\0\0jxl "),
+        (b"jxll", &[10]),
+        (b"jxlp", &[0, 0, 0, 0, 0xff, 0x0a]),
+        (b"jxlp", &[0x80, 0, 0, 1, 1, 2]),
+    ]);
+
+    let updated = put_label(&jxl, "Added");
+
+    assert_eq!(
+        box_names(&updated),
+        ["JXL ", "ftyp", "jxll", "xml ", "jxlp", "jxlp"]
+    );
+    assert_eq!(
+        label(&updated, FileFormat::JpegXl).as_deref(),
+        Some("Added")
+    );
+}
+
+#[test]
+fn replace_xmp() {
+    let jxl = container(&[
+        (b"ftyp", b"jxl \0\0\0\0jxl "),
+        (b"jxlc", CODESTREAM),
+        (b"Exif", &[0; 4]),
+        (b"xml ", meta_with_label("First").to_string().as_bytes()),
+    ]);
+
+    let updated = put_label(&jxl, "Second");
+
+    assert_eq!(
+        box_names(&updated),
+        ["JXL ", "ftyp", "jxlc", "Exif", "xml "]
+    );
+    assert_eq!(
+        label(&updated, FileFormat::JpegXl).as_deref(),
+        Some("Second")
+    );
+}
+
+#[test]
+fn replace_compressed_xmp() {
+    let jxl = container(&[
+        (b"ftyp", b"jxl \0\0\0\0jxl "),
+        (b"brob", b"xml \x01\x02\x03"),
+        (b"jxlc", CODESTREAM),
+    ]);
+
+    let updated = put_label(&jxl, "Uncompressed");
+
+    assert_eq!(box_names(&updated), ["JXL ", "ftyp", "xml ", "jxlc"]);
+    assert_eq!(
+        label(&updated, FileFormat::JpegXl).as_deref(),
+        Some("Uncompressed")
+    );
+}
+
+#[test]
+fn sample_file() {
+    let tempdir = tempdir().unwrap();
+    let sample = temp_copy_of_fixture(tempdir.path(), "sample.jxl");
+
+    assert_eq!(label_of_file(&sample).as_deref(), Some("Sample"));
+
+    put_label_in_file(&sample, "Updated");
+    assert_eq!(label_of_file(&sample).as_deref(), Some("Updated"));
+
+    // The codestream is unchanged.
+    let codestream = |file: &[u8]| boxes(file).into_iter().find(|(name, _)| name == "jxlc");
+    assert_eq!(
+        codestream(&fs::read(&sample).unwrap()),
+        codestream(&fs::read(fixture_path("sample.jxl")).unwrap())
+    );
+}
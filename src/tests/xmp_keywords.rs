@@ -0,0 +1,119 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use crate::{KeywordNode, KeywordTree};
+
+#[test]
+fn empty() {
+    let tree = KeywordTree::new();
+    assert!(tree.is_empty());
+    assert!(tree.paths().is_empty());
+    assert!(tree.keywords().is_empty());
+    assert_eq!(tree.to_string(), "");
+}
+
+#[test]
+fn from_paths() {
+    let tree = KeywordTree::from_paths([
+        "Animals|Birds|Heron",
+        "Animals|Birds|Egret",
+        "Animals|Mammals",
+        "Places|Seattle",
+    ]);
+
+    assert_eq!(
+        tree.roots(),
+        [
+            KeywordNode {
+                name: "Animals".to_owned(),
+                children: vec![
+                    KeywordNode {
+                        name: "Birds".to_owned(),
+                        children: vec![
+                            KeywordNode {
+                                name: "Heron".to_owned(),
+                                children: vec![],
+                            },
+                            KeywordNode {
+                                name: "Egret".to_owned(),
+                                children: vec![],
+                            },
+                        ],
+                    },
+                    KeywordNode {
+                        name: "Mammals".to_owned(),
+                        children: vec![],
+                    },
+                ],
+            },
+            KeywordNode {
+                name: "Places".to_owned(),
+                children: vec![KeywordNode {
+                    name: "Seattle".to_owned(),
+                    children: vec![],
+                }],
+            },
+        ]
+    );
+
+    assert_eq!(
+        tree.paths(),
+        [
+            "Animals|Birds|Heron",
+            "Animals|Birds|Egret",
+            "Animals|Mammals",
+            "Places|Seattle"
+        ]
+    );
+
+    assert_eq!(
+        tree.keywords(),
+        ["Animals", "Birds", "Heron", "Egret", "Mammals", "Places", "Seattle"]
+    );
+
+    assert_eq!(
+        tree.to_string(),
+        "Animals|Birds|Heron\nAnimals|Birds|Egret\nAnimals|Mammals\nPlaces|Seattle\n"
+    );
+}
+
+#[test]
+fn ancestors_are_merged() {
+    let tree = KeywordTree::from_paths(["Animals", "Animals|Birds", "Animals|Birds|Heron"]);
+    assert_eq!(tree.paths(), ["Animals|Birds|Heron"]);
+}
+
+#[test]
+fn normalizes_whitespace_and_empty_levels() {
+    let tree = KeywordTree::from_paths([" Animals || Birds ", "|", ""]);
+    assert_eq!(tree.paths(), ["Animals|Birds"]);
+}
+
+#[test]
+fn same_name_at_different_levels() {
+    let tree = KeywordTree::from_paths(["Colors|Red", "Flags|Red"]);
+    assert_eq!(tree.paths(), ["Colors|Red", "Flags|Red"]);
+    assert_eq!(tree.keywords(), ["Colors", "Red", "Flags"]);
+}
+
+#[test]
+fn contains() {
+    let tree = KeywordTree::from_paths(["Animals|Birds|Heron"]);
+
+    assert!(tree.contains("Animals"));
+    assert!(tree.contains("Animals|Birds"));
+    assert!(tree.contains("Animals|Birds|Heron"));
+    assert!(!tree.contains("Animals|Heron"));
+    assert!(!tree.contains("Birds"));
+    assert!(!tree.contains(""));
+}
@@ -0,0 +1,74 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use crate::{xmp_lang, XmpErrorType};
+
+#[test]
+fn well_formed() {
+    for tag in [
+        "en",
+        "en-US",
+        "EN-us",
+        "de-CH-1996",
+        "zh-Hant-TW",
+        "zh-yue-HK",
+        "es-419",
+        "sl-rozaj-biske",
+        "de-DE-u-co-phonebk",
+        "en-US-x-twain",
+        "x-default",
+        "x-private",
+        "i-klingon",
+        "zh-min-nan",
+        "haw",
+    ] {
+        assert!(xmp_lang::is_well_formed(tag), "{tag}");
+    }
+}
+
+#[test]
+fn malformed() {
+    for tag in [
+        "",
+        "e",
+        "en_US",
+        "en-",
+        "-en",
+        "en--US",
+        "en-US-u",
+        "en-x",
+        "x",
+        "123",
+        "en-US-a-",
+        "en-verylongsubtag",
+        "en US",
+    ] {
+        assert!(!xmp_lang::is_well_formed(tag), "{tag}");
+    }
+}
+
+#[test]
+fn normalize() {
+    assert_eq!(xmp_lang::normalize("EN-us").unwrap(), "en-US");
+    assert_eq!(xmp_lang::normalize("de-ch-1996").unwrap(), "de-CH-1996");
+    assert_eq!(xmp_lang::normalize("zh-Hant-TW").unwrap(), "zh-hant-tw");
+    assert_eq!(xmp_lang::normalize("X-DEFAULT").unwrap(), "x-default");
+}
+
+#[test]
+fn normalize_malformed() {
+    let err = xmp_lang::normalize("en_US").unwrap_err();
+
+    assert_eq!(err.error_type, XmpErrorType::BadValue);
+    assert_eq!(err.debug_message, "Malformed language tag \"en_US\"");
+}
@@ -0,0 +1,190 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use crate::{
+    xmp_lineage::{LineageEdge, LineageGraph, LineageNode, LineageRelation, UnresolvedReference},
+    xmp_ns, XmpMeta, XmpValue,
+};
+
+fn document(document_id: &str, instance_id: &str) -> XmpMeta {
+    let mut m = XmpMeta::new().unwrap();
+    m.set_property(xmp_ns::XMP_MM, "DocumentID", &document_id.into())
+        .unwrap();
+    m.set_property(xmp_ns::XMP_MM, "InstanceID", &instance_id.into())
+        .unwrap();
+    m
+}
+
+fn derived_from(m: &mut XmpMeta, document_id: &str, instance_id: Option<&str>) {
+    m.set_struct_field(
+        xmp_ns::XMP_MM,
+        "DerivedFrom",
+        xmp_ns::RESOURCE_REF,
+        "documentID",
+        &document_id.into(),
+    )
+    .unwrap();
+
+    if let Some(instance_id) = instance_id {
+        m.set_struct_field(
+            xmp_ns::XMP_MM,
+            "DerivedFrom",
+            xmp_ns::RESOURCE_REF,
+            "instanceID",
+            &instance_id.into(),
+        )
+        .unwrap();
+    }
+}
+
+fn ingredient(m: &mut XmpMeta, document_id: &str) {
+    m.append_array_item(
+        xmp_ns::XMP_MM,
+        &XmpValue::from("Ingredients").set_is_array(true),
+        &XmpValue::default().set_is_struct(true),
+    )
+    .unwrap();
+
+    m.set_struct_field(
+        xmp_ns::XMP_MM,
+        "Ingredients[last()]",
+        xmp_ns::RESOURCE_REF,
+        "documentID",
+        &document_id.into(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn empty() {
+    let graph = LineageGraph::build(Vec::<XmpMeta>::new());
+    assert!(graph.nodes().is_empty());
+    assert!(graph.edges().is_empty());
+    assert!(graph.roots().is_empty());
+    assert!(graph.orphans().is_empty());
+}
+
+#[test]
+fn nodes() {
+    let mut m = document("xmp.did:a", "xmp.iid:a1");
+    m.set_property(xmp_ns::XMP_MM, "OriginalDocumentID", &"xmp.did:orig".into())
+        .unwrap();
+
+    let graph = LineageGraph::build([m, XmpMeta::new().unwrap()]);
+
+    assert_eq!(
+        graph.nodes(),
+        [
+            LineageNode {
+                document_id: Some("xmp.did:a".to_owned()),
+                instance_id: Some("xmp.iid:a1".to_owned()),
+                original_document_id: Some("xmp.did:orig".to_owned()),
+            },
+            LineageNode::default(),
+        ]
+    );
+}
+
+#[test]
+fn derived_from_and_ingredients() {
+    let original = document("xmp.did:a", "xmp.iid:a1");
+    let later_version = document("xmp.did:a", "xmp.iid:a2");
+
+    let mut copy = document("xmp.did:b", "xmp.iid:b1");
+    derived_from(&mut copy, "xmp.did:a", Some("xmp.iid:a2"));
+
+    let mut composite = document("xmp.did:c", "xmp.iid:c1");
+    ingredient(&mut composite, "xmp.did:a");
+    ingredient(&mut composite, "xmp.did:b");
+
+    let docs = [original, later_version, copy, composite];
+    let graph = LineageGraph::build(&docs);
+
+    assert_eq!(
+        graph.edges(),
+        [
+            LineageEdge {
+                derived: 2,
+                source: 1,
+                relation: LineageRelation::DerivedFrom,
+            },
+            LineageEdge {
+                derived: 3,
+                source: 0,
+                relation: LineageRelation::Ingredient,
+            },
+            LineageEdge {
+                derived: 3,
+                source: 2,
+                relation: LineageRelation::Ingredient,
+            },
+        ]
+    );
+
+    assert_eq!(graph.sources_of(3).collect::<Vec<_>>(), [0, 2]);
+    assert_eq!(graph.derivatives_of(2).collect::<Vec<_>>(), [3]);
+    assert_eq!(graph.roots(), [0, 1]);
+    assert!(graph.unresolved().is_empty());
+    assert!(graph.orphans().is_empty());
+}
+
+#[test]
+fn falls_back_to_document_id() {
+    let original = document("xmp.did:a", "xmp.iid:a1");
+
+    let mut copy = document("xmp.did:b", "xmp.iid:b1");
+    derived_from(&mut copy, "xmp.did:a", Some("xmp.iid:unknown"));
+
+    let graph = LineageGraph::build([&original, &copy]);
+    assert_eq!(graph.sources_of(1).collect::<Vec<_>>(), [0]);
+}
+
+#[test]
+fn orphans() {
+    let mut copy = document("xmp.did:b", "xmp.iid:b1");
+    derived_from(&mut copy, "xmp.did:missing", Some("xmp.iid:missing"));
+    ingredient(&mut copy, "xmp.did:also-missing");
+
+    let graph = LineageGraph::build([copy]);
+
+    assert!(graph.edges().is_empty());
+    assert_eq!(
+        graph.unresolved(),
+        [
+            UnresolvedReference {
+                derived: 0,
+                relation: LineageRelation::DerivedFrom,
+                document_id: Some("xmp.did:missing".to_owned()),
+                instance_id: Some("xmp.iid:missing".to_owned()),
+            },
+            UnresolvedReference {
+                derived: 0,
+                relation: LineageRelation::Ingredient,
+                document_id: Some("xmp.did:also-missing".to_owned()),
+                instance_id: None,
+            },
+        ]
+    );
+    assert_eq!(graph.orphans(), [0]);
+    assert!(graph.roots().is_empty());
+}
+
+#[test]
+fn self_reference_ignored() {
+    let mut m = document("xmp.did:a", "xmp.iid:a2");
+    derived_from(&mut m, "xmp.did:a", None);
+
+    let graph = LineageGraph::build([m]);
+    assert!(graph.edges().is_empty());
+    assert!(graph.unresolved().is_empty());
+}
@@ -0,0 +1,226 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use crate::{xmp_ns, ArrayForm, MergePolicy, MergeSide, XmpMeta, XmpValue};
+
+fn base() -> XmpMeta {
+    let mut m = XmpMeta::new().unwrap();
+    m.set_property(xmp_ns::XMP, "Label", &"Red".into()).unwrap();
+    m.set_property(xmp_ns::XMP, "CreatorTool", &"Example".into())
+        .unwrap();
+    m.set_array(
+        xmp_ns::DC,
+        "subject",
+        ArrayForm::Bag,
+        &["sunset".into(), "beach".into()],
+    )
+    .unwrap();
+    m
+}
+
+fn value(m: &XmpMeta, ns: &str, name: &str) -> Option<String> {
+    m.property(ns, name).map(|v| v.value)
+}
+
+fn subjects(m: &XmpMeta) -> Vec<String> {
+    m.property_array(xmp_ns::DC, "subject")
+        .map(|item| item.value)
+        .collect()
+}
+
+#[test]
+fn independent_changes() {
+    let base = base();
+
+    let mut ours = base.clone();
+    ours.set_property(xmp_ns::XMP, "Label", &"Green".into())
+        .unwrap();
+
+    let mut theirs = base.clone();
+    theirs
+        .set_property(xmp_ns::XMP, "Rating", &"5".into())
+        .unwrap();
+    theirs.delete_property(xmp_ns::XMP, "CreatorTool").unwrap();
+
+    let result = XmpMeta::merge3(&base, &ours, &theirs, MergePolicy::default()).unwrap();
+
+    assert!(result.conflicts.is_empty());
+    assert_eq!(
+        value(&result.merged, xmp_ns::XMP, "Label").as_deref(),
+        Some("Green")
+    );
+    assert_eq!(
+        value(&result.merged, xmp_ns::XMP, "Rating").as_deref(),
+        Some("5")
+    );
+    assert!(!result.merged.contains_property(xmp_ns::XMP, "CreatorTool"));
+}
+
+#[test]
+fn same_change_on_both_sides() {
+    let base = base();
+
+    let mut ours = base.clone();
+    ours.set_property(xmp_ns::XMP, "Label", &"Green".into())
+        .unwrap();
+    let theirs = ours.clone();
+
+    let result = XmpMeta::merge3(&base, &ours, &theirs, MergePolicy::default()).unwrap();
+
+    assert!(result.conflicts.is_empty());
+    assert_eq!(
+        value(&result.merged, xmp_ns::XMP, "Label").as_deref(),
+        Some("Green")
+    );
+}
+
+#[test]
+fn conflict_prefers_ours() {
+    let base = base();
+
+    let mut ours = base.clone();
+    ours.set_property(xmp_ns::XMP, "Label", &"Green".into())
+        .unwrap();
+
+    let mut theirs = base.clone();
+    theirs
+        .set_property(xmp_ns::XMP, "Label", &"Blue".into())
+        .unwrap();
+
+    let result = XmpMeta::merge3(&base, &ours, &theirs, MergePolicy::default()).unwrap();
+
+    assert_eq!(result.conflicts.len(), 1);
+    assert_eq!(result.conflicts[0].namespace, xmp_ns::XMP);
+    assert_eq!(result.conflicts[0].name, "Label");
+    assert_eq!(result.conflicts[0].resolution, MergeSide::Ours);
+    assert_eq!(
+        value(&result.merged, xmp_ns::XMP, "Label").as_deref(),
+        Some("Green")
+    );
+}
+
+#[test]
+fn conflict_prefers_theirs() {
+    let base = base();
+
+    let mut ours = base.clone();
+    ours.delete_property(xmp_ns::XMP, "Label").unwrap();
+
+    let mut theirs = base.clone();
+    theirs
+        .set_property(xmp_ns::XMP, "Label", &"Blue".into())
+        .unwrap();
+
+    let result = XmpMeta::merge3(
+        &base,
+        &ours,
+        &theirs,
+        MergePolicy::default().prefer_theirs(),
+    )
+    .unwrap();
+
+    assert_eq!(result.conflicts.len(), 1);
+    assert_eq!(result.conflicts[0].resolution, MergeSide::Theirs);
+    assert_eq!(
+        value(&result.merged, xmp_ns::XMP, "Label").as_deref(),
+        Some("Blue")
+    );
+}
+
+#[test]
+fn unordered_arrays_conflict_by_default() {
+    let base = base();
+
+    let mut ours = base.clone();
+    ours.append_array_item(
+        xmp_ns::DC,
+        &XmpValue::from("subject").set_is_array(true),
+        &"ocean".into(),
+    )
+    .unwrap();
+
+    let mut theirs = base.clone();
+    theirs.delete_array_item(xmp_ns::DC, "subject", 1).unwrap();
+
+    let result = XmpMeta::merge3(&base, &ours, &theirs, MergePolicy::default()).unwrap();
+
+    assert_eq!(result.conflicts.len(), 1);
+    assert_eq!(result.conflicts[0].name, "subject");
+    assert_eq!(subjects(&result.merged), ["sunset", "beach", "ocean"]);
+}
+
+#[test]
+fn union_unordered_arrays() {
+    let base = base();
+
+    let mut ours = base.clone();
+    ours.append_array_item(
+        xmp_ns::DC,
+        &XmpValue::from("subject").set_is_array(true),
+        &"ocean".into(),
+    )
+    .unwrap();
+
+    let mut theirs = base.clone();
+    theirs.delete_array_item(xmp_ns::DC, "subject", 1).unwrap();
+    theirs
+        .append_array_item(
+            xmp_ns::DC,
+            &XmpValue::from("subject").set_is_array(true),
+            &"palm".into(),
+        )
+        .unwrap();
+
+    let result = XmpMeta::merge3(
+        &base,
+        &ours,
+        &theirs,
+        MergePolicy::default().union_unordered_arrays(),
+    )
+    .unwrap();
+
+    assert!(result.conflicts.is_empty());
+    assert_eq!(subjects(&result.merged), ["beach", "ocean", "palm"]);
+}
+
+#[test]
+fn ordered_arrays_are_not_unioned() {
+    let mut base = XmpMeta::new().unwrap();
+    base.set_array(xmp_ns::DC, "creator", ArrayForm::Seq, &["Alice".into()])
+        .unwrap();
+
+    let mut ours = base.clone();
+    ours.set_array(
+        xmp_ns::DC,
+        "creator",
+        ArrayForm::Seq,
+        &["Alice".into(), "Bob".into()],
+    )
+    .unwrap();
+
+    let mut theirs = base.clone();
+    theirs
+        .set_array(xmp_ns::DC, "creator", ArrayForm::Seq, &["Carol".into()])
+        .unwrap();
+
+    let result = XmpMeta::merge3(
+        &base,
+        &ours,
+        &theirs,
+        MergePolicy::default().union_unordered_arrays(),
+    )
+    .unwrap();
+
+    assert_eq!(result.conflicts.len(), 1);
+    assert_eq!(result.conflicts[0].name, "creator");
+}
@@ -354,12 +354,132 @@ mod from_str_with_options {
             .property("http://ns.adobe.com/photoshop/1.0/", "ICCProfilx")
             .is_none());
     }
+
+    const TWO_BLOCKS: &str = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+        <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+            <rdf:Description rdf:about="" xmlns:dc="http://purl.org/dc/elements/1.1/">
+                <dc:format>image/jpeg</dc:format>
+            </rdf:Description>
+            <rdf:Description rdf:about="" xmlns:xmp="http://ns.adobe.com/xap/1.0/"
+                xmp:CreatorTool="Some Tool"/>
+        </rdf:RDF>
+        </x:xmpmeta>"#;
+
+    #[test]
+    fn description_blocks_from_str() {
+        let blocks = XmpMeta::description_blocks_from_str(
+            TWO_BLOCKS,
+            FromStrOptions::default().require_xmp_meta(),
+        )
+        .unwrap();
+
+        assert_eq!(blocks.len(), 2);
+
+        assert!(blocks[0].contains_property("http://purl.org/dc/elements/1.1/", "format"));
+        assert!(!blocks[0].contains_property("http://ns.adobe.com/xap/1.0/", "CreatorTool"));
+
+        assert!(!blocks[1].contains_property("http://purl.org/dc/elements/1.1/", "format"));
+        assert_eq!(
+            blocks[1]
+                .property("http://ns.adobe.com/xap/1.0/", "CreatorTool")
+                .unwrap()
+                .value,
+            "Some Tool"
+        );
+
+        // The merged data model is unchanged.
+        let m = XmpMeta::from_str_with_options(TWO_BLOCKS, FromStrOptions::default()).unwrap();
+        assert!(m.contains_property("http://purl.org/dc/elements/1.1/", "format"));
+        assert!(m.contains_property("http://ns.adobe.com/xap/1.0/", "CreatorTool"));
+    }
+
+    #[test]
+    fn description_blocks_from_str_bad_xmp() {
+        let err = XmpMeta::description_blocks_from_str(
+            "this is not XMP",
+            FromStrOptions::default().require_xmp_meta(),
+        )
+        .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::XmpMetaElementMissing);
+    }
+}
+
+mod from_str_with_report {
+    use crate::{tests::fixtures::*, FromStrOptions, XmpErrorType, XmpMeta};
+
+    #[test]
+    fn no_warnings() {
+        let (m, warnings) =
+            XmpMeta::from_str_with_report(PURPLE_SQUARE_XMP, FromStrOptions::default()).unwrap();
+
+        assert!(warnings.is_empty());
+        assert_eq!(
+            m.property("http://ns.adobe.com/xap/1.0/", "CreatorTool")
+                .unwrap()
+                .value,
+            "Adobe Photoshop CS2 Windows"
+        );
+    }
+
+    #[test]
+    fn inconsistent_aliases() {
+        let (m, warnings) =
+            XmpMeta::from_str_with_report(INCONSISTENT_RDF, FromStrOptions::default()).unwrap();
+
+        // Same data model as from_str_with_options.
+        assert_eq!(
+            m.to_string(),
+            XmpMeta::from_str_with_options(INCONSISTENT_RDF, FromStrOptions::default())
+                .unwrap()
+                .to_string()
+        );
+
+        assert!(warnings.iter().any(|w| w.error_type == XmpErrorType::BadXmp
+            && w.message == "Mismatch between alias and base nodes"));
+    }
+
+    #[test]
+    fn not_xmp() {
+        let (m, warnings) =
+            XmpMeta::from_str_with_report("this is not XMP", FromStrOptions::default()).unwrap();
+
+        assert!(m
+            .property("http://ns.adobe.com/xap/1.0/", "CreatorTool")
+            .is_none());
+
+        let last = warnings.last().unwrap();
+        assert_eq!(last.error_type, XmpErrorType::BadXmp);
+        assert_eq!(last.message, "No XMP properties found in input");
+        assert_eq!(
+            last.to_string(),
+            "ParseWarning(XMP format error, No XMP properties found in input)"
+        );
+    }
+
+    #[test]
+    fn empty_string() {
+        let (_m, warnings) = XmpMeta::from_str_with_report("", FromStrOptions::default()).unwrap();
+
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn strict_aliasing_is_error() {
+        let err = XmpMeta::from_str_with_report(
+            INCONSISTENT_RDF,
+            FromStrOptions::default().strict_aliasing(),
+        )
+        .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::BadXmp);
+    }
 }
 
 mod to_string_with_options {
     use std::str::FromStr;
 
-    use crate::{tests::fixtures::*, ToStringOptions, XmpError, XmpErrorType, XmpMeta};
+    use crate::{tests::fixtures::*, xmp_ns, ToStringOptions, XmpError, XmpErrorType, XmpMeta};
 
     #[test]
     fn simple_case() {
@@ -542,6 +662,84 @@ mod to_string_with_options {
         );
     }
 
+    #[test]
+    fn canonical_for_hashing() {
+        let a = XmpMeta::from_str(STRUCT_EXAMPLE).unwrap();
+
+        let mut b = XmpMeta::new().unwrap();
+        b.set_struct_field(
+            xmp_ns::IPTC_CORE,
+            "CreatorContactInfo",
+            xmp_ns::IPTC_CORE,
+            "CiAdrCtry",
+            &"US".into(),
+        )
+        .unwrap();
+        b.set_struct_field(
+            xmp_ns::IPTC_CORE,
+            "CreatorContactInfo",
+            xmp_ns::IPTC_CORE,
+            "CiAdrPcode",
+            &"98110".into(),
+        )
+        .unwrap();
+        b.set_property(xmp_ns::XMP_RIGHTS, "Marked", &"True".into())
+            .unwrap();
+
+        let a = a
+            .to_string_with_options(ToStringOptions::canonical_for_hashing())
+            .unwrap();
+        let b = b
+            .to_string_with_options(ToStringOptions::canonical_for_hashing())
+            .unwrap();
+
+        assert_eq!(a, b);
+        assert!(a.starts_with(
+            "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\" x:xmptk=\"xmp_toolkit canonical 1\">\n"
+        ));
+        assert!(a.ends_with("</x:xmpmeta>\n"));
+        assert!(!a.contains("xpacket"));
+
+        // Schemas are sorted by namespace URI.
+        let iptc = a.find("<Iptc4xmpCore:CreatorContactInfo").unwrap();
+        let rights = a.find("<xmpRights:Marked>").unwrap();
+        assert!(iptc < rights);
+    }
+
+    #[test]
+    fn canonical_for_hashing_prefixes() {
+        XmpMeta::register_namespace("http://example.com/canonical/b/", "zz").unwrap();
+        XmpMeta::register_namespace("http://example.com/canonical/a/", "yy").unwrap();
+
+        let mut m = XmpMeta::new().unwrap();
+        m.set_property("http://example.com/canonical/b/", "Prop", &"b".into())
+            .unwrap();
+        m.set_property("http://example.com/canonical/a/", "Prop", &"a".into())
+            .unwrap();
+
+        let s = m
+            .to_string_with_options(ToStringOptions::canonical_for_hashing())
+            .unwrap();
+
+        assert!(s.contains("xmlns:ns1=\"http://example.com/canonical/a/\""));
+        assert!(s.contains("xmlns:ns2=\"http://example.com/canonical/b/\""));
+        assert!(s.contains("<ns1:Prop>a</ns1:Prop>"));
+        assert!(s.contains("<ns2:Prop>b</ns2:Prop>"));
+        assert!(!s.contains("yy:"));
+        assert!(!s.contains("zz:"));
+    }
+
+    #[test]
+    fn canonical_for_hashing_init_fail() {
+        let m = XmpMeta::new_fail();
+        assert_eq!(
+            m.to_string_with_options(ToStringOptions::canonical_for_hashing())
+                .unwrap_err()
+                .error_type,
+            XmpErrorType::NoCppToolkit
+        );
+    }
+
     #[test]
     fn include_rdf_hash() {
         let m = XmpMeta::from_str(STRUCT_EXAMPLE).unwrap();
@@ -554,6 +752,169 @@ mod to_string_with_options {
     }
 }
 
+mod to_string_with_ordering {
+    use crate::{xmp_ns, NamespaceOrder, ToStringOptions, XmpErrorType, XmpMeta, XmpValue};
+
+    fn sample() -> XmpMeta {
+        let mut m = XmpMeta::default();
+        m.set_property(xmp_ns::XMP, "CreatorTool", &"Tool".into())
+            .unwrap();
+        m.set_property(xmp_ns::PHOTOSHOP, "City", &"Seattle".into())
+            .unwrap();
+        m.set_property(xmp_ns::DC, "format", &"image/jpeg".into())
+            .unwrap();
+        m.set_property(
+            xmp_ns::DC,
+            "title",
+            &XmpValue::from("").set_is_alternate(true).set_is_array(true),
+        )
+        .unwrap();
+        m.set_property(xmp_ns::EXIF, "ColorSpace", &"1".into())
+            .unwrap();
+        m
+    }
+
+    fn positions(s: &str, needles: &[&str]) -> Vec<usize> {
+        needles.iter().map(|n| s.find(n).unwrap()).collect()
+    }
+
+    #[test]
+    fn namespaces_and_properties() {
+        let m = sample();
+        let before = m.to_string();
+
+        let ordering = NamespaceOrder::default()
+            .namespace(xmp_ns::DC)
+            .namespace(xmp_ns::XMP)
+            .properties(xmp_ns::DC, &["title", "format"]);
+
+        let s = m
+            .to_string_with_ordering(ToStringOptions::default().omit_packet_wrapper(), &ordering)
+            .unwrap();
+
+        let p = positions(
+            &s,
+            &[
+                "<dc:title",
+                "<dc:format",
+                "<xmp:CreatorTool",
+                "<exif:ColorSpace",
+                "<photoshop:City",
+            ],
+        );
+        assert!(p.windows(2).all(|w| w[0] < w[1]), "{s}");
+
+        // Source object is unchanged.
+        assert_eq!(m.to_string(), before);
+    }
+
+    #[test]
+    fn round_trip() {
+        let m = sample();
+
+        let s = m
+            .to_string_with_ordering(ToStringOptions::default(), &NamespaceOrder::default())
+            .unwrap();
+
+        let mut reparsed: XmpMeta = s.parse().unwrap();
+        let mut original = m.clone();
+        reparsed.sort().unwrap();
+        original.sort().unwrap();
+
+        assert_eq!(reparsed.to_string(), original.to_string());
+    }
+
+    #[test]
+    fn init_fail() {
+        let m = XmpMeta::new_fail();
+
+        let err = m
+            .to_string_with_ordering(ToStringOptions::default(), &NamespaceOrder::default())
+            .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::NoCppToolkit);
+    }
+}
+
+mod from_properties {
+    use std::str::FromStr;
+
+    use crate::{
+        tests::fixtures::*, xmp_ns, IterOptions, XmpErrorType, XmpMeta, XmpProperty, XmpValue,
+    };
+
+    fn round_trip(xmp: &str) {
+        let original = XmpMeta::from_str(xmp).unwrap();
+        let rebuilt = XmpMeta::from_properties(original.iter(IterOptions::default())).unwrap();
+
+        let original_props: Vec<XmpProperty> = original.iter(IterOptions::default()).collect();
+        let rebuilt_props: Vec<XmpProperty> = rebuilt.iter(IterOptions::default()).collect();
+
+        assert_eq!(original_props, rebuilt_props);
+    }
+
+    #[test]
+    fn round_trip_simple_and_arrays() {
+        round_trip(PURPLE_SQUARE_XMP);
+    }
+
+    #[test]
+    fn round_trip_struct() {
+        round_trip(STRUCT_EXAMPLE);
+    }
+
+    #[test]
+    fn round_trip_qualifiers() {
+        round_trip(QUAL_EXAMPLE);
+    }
+
+    #[test]
+    fn round_trip_localized_text() {
+        round_trip(LOCALIZED_TEXT_EXAMPLE);
+    }
+
+    #[test]
+    fn filtered() {
+        let original = XmpMeta::from_str(PURPLE_SQUARE_XMP).unwrap();
+
+        let m = XmpMeta::from_properties(
+            original
+                .iter(IterOptions::default())
+                .filter(|prop| prop.schema_ns != xmp_ns::DC),
+        )
+        .unwrap();
+
+        assert!(!m.contains_property(xmp_ns::DC, "subject"));
+        assert!(!m.contains_property(xmp_ns::DC, "title"));
+
+        assert_eq!(
+            m.property(xmp_ns::XMP, "CreatorTool").unwrap(),
+            XmpValue {
+                value: "Adobe Photoshop CS2 Windows".to_owned(),
+                options: 0
+            }
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let m = XmpMeta::from_properties(Vec::new()).unwrap();
+        assert_eq!(m.iter(IterOptions::default()).count(), 0);
+    }
+
+    #[test]
+    fn error_bad_path() {
+        let err = XmpMeta::from_properties(vec![XmpProperty {
+            schema_ns: xmp_ns::XMP.to_owned(),
+            name: "xmp:Bogus[".to_owned(),
+            value: XmpValue::from("x"),
+        }])
+        .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::BadXPath);
+    }
+}
+
 mod register_namespace {
     use crate::{XmpErrorType, XmpMeta};
 
@@ -608,6 +969,50 @@ mod namespace_uri {
     }
 }
 
+mod namespaces {
+    use std::collections::HashSet;
+
+    use crate::{xmp_ns, XmpMeta};
+
+    #[test]
+    fn standard_namespaces() {
+        let namespaces: Vec<(String, String)> = XmpMeta::namespaces().collect();
+
+        assert!(namespaces.contains(&("xmp:".to_owned(), xmp_ns::XMP.to_owned())));
+        assert!(namespaces.contains(&("dc:".to_owned(), xmp_ns::DC.to_owned())));
+        assert!(namespaces.contains(&("rdf:".to_owned(), xmp_ns::RDF.to_owned())));
+    }
+
+    #[test]
+    fn registered_namespace() {
+        let prefix =
+            XmpMeta::register_namespace("http://ns.example.com/namespaces/1.0/", "nsIter").unwrap();
+
+        let uri = XmpMeta::namespaces()
+            .find(|(p, _)| *p == prefix)
+            .map(|(_, uri)| uri);
+
+        assert_eq!(
+            uri.as_deref(),
+            Some("http://ns.example.com/namespaces/1.0/")
+        );
+    }
+
+    #[test]
+    fn consistent_with_lookups() {
+        let namespaces: Vec<(String, String)> = XmpMeta::namespaces().collect();
+        assert!(namespaces.len() > 20);
+
+        let prefixes: HashSet<&String> = namespaces.iter().map(|(prefix, _)| prefix).collect();
+        assert_eq!(prefixes.len(), namespaces.len());
+
+        for (prefix, uri) in &namespaces {
+            assert!(prefix.ends_with(':'));
+            assert_eq!(XmpMeta::namespace_prefix(uri).as_ref(), Some(prefix));
+        }
+    }
+}
+
 mod debug_dump_namespaces {
     use crate::XmpMeta;
 
@@ -619,6 +1024,98 @@ mod debug_dump_namespaces {
     }
 }
 
+mod dump {
+    use std::{
+        io,
+        panic::{self, AssertUnwindSafe},
+    };
+
+    use crate::{tests::fixtures::*, XmpMeta};
+
+    #[test]
+    fn happy_path() {
+        let m = XmpMeta::from_file(fixture_path("Purple Square.psd")).unwrap();
+
+        let mut out: Vec<u8> = Vec::new();
+        m.dump(&mut out).unwrap();
+
+        let s = String::from_utf8(out).unwrap();
+        assert!(s.starts_with("Dumping XMPMeta object \"\""));
+        assert!(s.contains("xmp:CreatorTool"));
+        assert!(s.contains("Adobe Photoshop CS2 Windows"));
+    }
+
+    #[test]
+    fn matches_debug() {
+        let m = XmpMeta::from_file(fixture_path("Purple Square.psd")).unwrap();
+
+        let mut out: Vec<u8> = Vec::new();
+        m.dump(&mut out).unwrap();
+
+        let s = String::from_utf8(out).unwrap();
+        assert_eq!(s.trim_start_matches("Dumping "), format!("{:?}", m));
+    }
+
+    struct FailingWriter;
+
+    impl io::Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::Other, "nope"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn writer_error() {
+        let m = XmpMeta::from_file(fixture_path("Purple Square.psd")).unwrap();
+
+        let err = m.dump(&mut FailingWriter).unwrap_err();
+        assert_eq!(err.to_string(), "nope");
+    }
+
+    struct PanickingWriter;
+
+    impl io::Write for PanickingWriter {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            panic!("writer panicked");
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn writer_panic() {
+        let m = XmpMeta::from_file(fixture_path("Purple Square.psd")).unwrap();
+
+        let payload =
+            panic::catch_unwind(AssertUnwindSafe(|| m.dump(&mut PanickingWriter))).unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"writer panicked"));
+
+        // The metadata is still usable afterwards.
+        let mut out: Vec<u8> = Vec::new();
+        m.dump(&mut out).unwrap();
+        assert!(!out.is_empty());
+    }
+
+    #[test]
+    fn init_fail() {
+        let m = XmpMeta::new_fail();
+
+        let mut out: Vec<u8> = Vec::new();
+        m.dump(&mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "(C++ XMP Toolkit unavailable)\n"
+        );
+    }
+}
+
 mod contains_property {
     use crate::{tests::fixtures::*, xmp_ns, XmpMeta};
 
@@ -825,14 +1322,122 @@ mod property {
     }
 }
 
-mod property_array {
-    use std::str::FromStr;
-
-    use crate::{tests::fixtures::*, XmpMeta, XmpValue};
+mod try_property {
+    use crate::{tests::fixtures::*, xmp_ns, XmpErrorType, XmpMeta, XmpValue};
 
     #[test]
-    fn happy_path_creator_seq() {
-        let m = XmpMeta::from_str(PURPLE_SQUARE_XMP).unwrap();
+    fn happy_path() {
+        let m = XmpMeta::from_file(fixture_path("Purple Square.psd")).unwrap();
+        assert_eq!(
+            m.try_property(xmp_ns::XMP, "CreatorTool").unwrap(),
+            Some(XmpValue {
+                value: "Adobe Photoshop CS2 Windows".to_owned(),
+                options: 0
+            })
+        );
+    }
+
+    #[test]
+    fn missing_property() {
+        let m = XmpMeta::from_file(fixture_path("Purple Square.psd")).unwrap();
+        assert_eq!(m.try_property(xmp_ns::XMP, "RandomProperty").unwrap(), None);
+    }
+
+    #[test]
+    fn init_fail() {
+        let m = XmpMeta::new_fail();
+        let err = m.try_property(xmp_ns::XMP, "CreatorTool").unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::NoCppToolkit);
+    }
+
+    #[test]
+    fn empty_prop_name() {
+        let m = XmpMeta::from_file(fixture_path("Purple Square.psd")).unwrap();
+        let err = m.try_property(xmp_ns::XMP, "").unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::BadXPath);
+    }
+
+    #[test]
+    fn invalid_prop_name() {
+        let m = XmpMeta::from_file(fixture_path("Purple Square.psd")).unwrap();
+        let err = m.try_property(xmp_ns::XMP, "\0").unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::NulInRustString);
+    }
+}
+
+mod property_into {
+    use crate::{tests::fixtures::*, xmp_ns, XmpMeta, XmpValue};
+
+    #[test]
+    fn happy_path() {
+        let m = XmpMeta::from_file(fixture_path("Purple Square.psd")).unwrap();
+        let mut buf = String::new();
+
+        assert_eq!(
+            m.property_into(xmp_ns::XMP, "CreatorTool", &mut buf),
+            Some(XmpValue {
+                value: "Adobe Photoshop CS2 Windows",
+                options: 0
+            })
+        );
+    }
+
+    #[test]
+    fn reuses_buffer() {
+        let m = XmpMeta::from_file(fixture_path("Purple Square.psd")).unwrap();
+        let mut buf = String::with_capacity(100);
+        let capacity = buf.capacity();
+
+        assert_eq!(
+            m.property_into(xmp_ns::XMP, "CreatorTool", &mut buf)
+                .unwrap()
+                .value,
+            "Adobe Photoshop CS2 Windows"
+        );
+        assert_eq!(
+            m.property_into(xmp_ns::XMP, "CreateDate", &mut buf)
+                .unwrap()
+                .value,
+            "2006-04-25T15:32:01+02:00"
+        );
+        assert_eq!(buf.capacity(), capacity);
+    }
+
+    #[test]
+    fn missing_property() {
+        let m = XmpMeta::from_file(fixture_path("Purple Square.psd")).unwrap();
+        let mut buf = "stale".to_owned();
+
+        assert_eq!(
+            m.property_into(xmp_ns::XMP, "RandomProperty", &mut buf),
+            None
+        );
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn init_fail() {
+        let m = XmpMeta::new_fail();
+        let mut buf = String::new();
+        assert_eq!(m.property_into(xmp_ns::XMP, "CreatorTool", &mut buf), None);
+    }
+
+    #[test]
+    fn invalid_prop_name() {
+        let m = XmpMeta::from_file(fixture_path("Purple Square.psd")).unwrap();
+        let mut buf = String::new();
+        assert_eq!(m.property_into(xmp_ns::XMP, "\0", &mut buf), None);
+    }
+}
+
+mod property_array {
+    use std::str::FromStr;
+
+    use crate::{tests::fixtures::*, XmpMeta, XmpValue};
+
+    #[test]
+    fn happy_path_creator_seq() {
+        let m = XmpMeta::from_str(PURPLE_SQUARE_XMP).unwrap();
 
         let mut creators: Vec<XmpValue<String>> = m
             .property_array("http://purl.org/dc/elements/1.1/", "creator")
@@ -1288,6 +1893,101 @@ mod struct_field {
     }
 }
 
+mod try_struct_field {
+    use std::str::FromStr;
+
+    use crate::{tests::fixtures::*, XmpErrorType, XmpMeta};
+
+    const IPTC_CORE: &str = "http://iptc.org/std/Iptc4xmpCore/1.0/xmlns/";
+
+    #[test]
+    fn happy_path() {
+        let m = XmpMeta::from_str(STRUCT_EXAMPLE).unwrap();
+        assert_eq!(
+            m.try_struct_field(IPTC_CORE, "CreatorContactInfo", IPTC_CORE, "CiAdrPcode")
+                .unwrap()
+                .unwrap()
+                .value,
+            "98110"
+        );
+    }
+
+    #[test]
+    fn missing_field() {
+        let m = XmpMeta::from_str(STRUCT_EXAMPLE).unwrap();
+        assert_eq!(
+            m.try_struct_field(IPTC_CORE, "CreatorContactInfo", IPTC_CORE, "CiAdrPcodx")
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn init_fail() {
+        let m = XmpMeta::new_fail();
+        let err = m
+            .try_struct_field(IPTC_CORE, "CreatorContactInfo", IPTC_CORE, "CiAdrPcode")
+            .unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::NoCppToolkit);
+    }
+
+    #[test]
+    fn invalid_field_name() {
+        let m = XmpMeta::from_str(STRUCT_EXAMPLE).unwrap();
+        let err = m
+            .try_struct_field(IPTC_CORE, "CreatorContactInfo", IPTC_CORE, "\0")
+            .unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::NulInRustString);
+    }
+}
+
+mod struct_as_map {
+    use std::{collections::BTreeMap, str::FromStr};
+
+    use crate::{tests::fixtures::*, xmp_ns, XmpMeta, XmpValue};
+
+    #[test]
+    fn happy_path() {
+        let m = XmpMeta::from_str(STRUCT_EXAMPLE).unwrap();
+
+        let map = m
+            .struct_as_map(xmp_ns::IPTC_CORE, "CreatorContactInfo")
+            .unwrap();
+
+        let mut expected = BTreeMap::new();
+        expected.insert(
+            (xmp_ns::IPTC_CORE.to_owned(), "CiAdrPcode".to_owned()),
+            XmpValue::from("98110"),
+        );
+        expected.insert(
+            (xmp_ns::IPTC_CORE.to_owned(), "CiAdrCtry".to_owned()),
+            XmpValue::from("US"),
+        );
+
+        assert_eq!(map, expected);
+    }
+
+    #[test]
+    fn not_a_struct() {
+        let m = XmpMeta::from_str(STRUCT_EXAMPLE).unwrap();
+        assert!(m.struct_as_map(xmp_ns::XMP_RIGHTS, "Marked").is_none());
+    }
+
+    #[test]
+    fn no_such_property() {
+        let m = XmpMeta::from_str(STRUCT_EXAMPLE).unwrap();
+        assert!(m.struct_as_map(xmp_ns::IPTC_CORE, "Nope").is_none());
+    }
+
+    #[test]
+    fn init_fail() {
+        let m = XmpMeta::new_fail();
+        assert!(m
+            .struct_as_map(xmp_ns::IPTC_CORE, "CreatorContactInfo")
+            .is_none());
+    }
+}
+
 mod set_property {
     use crate::{
         tests::fixtures::*, xmp_value::xmp_prop, ItemPlacement, XmpErrorType, XmpMeta, XmpValue,
@@ -1842,6 +2542,151 @@ mod set_property_date {
     }
 }
 
+mod get {
+    use crate::{tests::fixtures::*, xmp_ns, XmpDateTime, XmpMeta, XmpValue};
+
+    #[test]
+    fn happy_path() {
+        let m = XmpMeta::from_file(fixture_path("Purple Square.psd")).unwrap();
+
+        assert_eq!(
+            m.get::<String>(xmp_ns::XMP, "CreatorTool").unwrap().value,
+            "Adobe Photoshop CS2 Windows"
+        );
+        assert_eq!(
+            m.get::<bool>(xmp_ns::XMP_RIGHTS, "Marked"),
+            Some(XmpValue {
+                value: false,
+                options: 0
+            })
+        );
+        assert_eq!(
+            m.get::<i32>(xmp_ns::EXIF, "PixelXDimension").unwrap().value,
+            200
+        );
+        assert_eq!(
+            m.get::<i64>(xmp_ns::EXIF, "PixelXDimension").unwrap().value,
+            200
+        );
+        assert_eq!(
+            m.get::<f64>(xmp_ns::EXIF, "PixelXDimension").unwrap().value,
+            200.0
+        );
+        assert_eq!(
+            m.get::<XmpDateTime>(xmp_ns::XMP, "CreateDate")
+                .unwrap()
+                .value
+                .to_string(),
+            "2006-04-25T15:32:01+02:00"
+        );
+    }
+
+    #[test]
+    fn custom_type() {
+        let mut m = XmpMeta::new().unwrap();
+        m.set_property(xmp_ns::EXIF, "ExposureTime", &"1/250".into())
+            .unwrap();
+
+        assert_eq!(
+            m.get::<Rational>(xmp_ns::EXIF, "ExposureTime")
+                .unwrap()
+                .value,
+            Rational { num: 1, den: 250 }
+        );
+    }
+
+    #[test]
+    fn unrecognizable_as_type() {
+        let m = XmpMeta::from_file(fixture_path("Purple Square.psd")).unwrap();
+        assert_eq!(m.get::<bool>(xmp_ns::XMP, "CreatorTool"), None);
+        assert_eq!(m.get::<Rational>(xmp_ns::XMP, "CreatorTool"), None);
+    }
+
+    #[test]
+    fn init_fail() {
+        let m = XmpMeta::new_fail();
+        assert_eq!(m.get::<String>(xmp_ns::XMP, "CreatorTool"), None);
+        assert_eq!(m.get::<Rational>(xmp_ns::EXIF, "ExposureTime"), None);
+    }
+}
+
+mod set {
+    use crate::{tests::fixtures::*, xmp_ns, xmp_value::xmp_prop, XmpErrorType, XmpMeta, XmpValue};
+
+    #[test]
+    fn happy_path() {
+        let mut m = XmpMeta::new().unwrap();
+
+        m.set(xmp_ns::XMP, "Rating", 5i32).unwrap();
+        m.set(xmp_ns::XMP_RIGHTS, "Marked", true).unwrap();
+        m.set(xmp_ns::EXIF, "FileSize", 1i64 << 40).unwrap();
+
+        assert_eq!(m.property(xmp_ns::XMP, "Rating").unwrap().value, "5");
+        assert_eq!(
+            m.property(xmp_ns::XMP_RIGHTS, "Marked").unwrap().value,
+            "True"
+        );
+        assert_eq!(
+            m.property(xmp_ns::EXIF, "FileSize").unwrap().value,
+            "1099511627776"
+        );
+    }
+
+    #[test]
+    fn custom_type() {
+        let mut m = XmpMeta::new().unwrap();
+
+        m.set(xmp_ns::EXIF, "ExposureTime", Rational { num: 1, den: 250 })
+            .unwrap();
+
+        assert_eq!(
+            m.property(xmp_ns::EXIF, "ExposureTime").unwrap().value,
+            "1/250"
+        );
+    }
+
+    #[test]
+    fn options() {
+        let mut m = XmpMeta::new().unwrap();
+
+        m.set::<String>(
+            xmp_ns::XMP,
+            "BaseURL",
+            XmpValue::new("https://example.com".to_owned()).set_is_uri(true),
+        )
+        .unwrap();
+
+        assert_eq!(
+            m.property(xmp_ns::XMP, "BaseURL").unwrap(),
+            XmpValue {
+                value: "https://example.com".to_owned(),
+                options: xmp_prop::VALUE_IS_URI
+            }
+        );
+    }
+
+    #[test]
+    fn init_fail() {
+        let mut m = XmpMeta::new_fail();
+
+        let err = m.set(xmp_ns::XMP, "Rating", 5i32).unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::NoCppToolkit);
+
+        let err = m
+            .set(xmp_ns::EXIF, "ExposureTime", Rational { num: 1, den: 250 })
+            .unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::NoCppToolkit);
+    }
+
+    #[test]
+    fn error_empty_namespace() {
+        let mut m = XmpMeta::new().unwrap();
+
+        let err = m.set("", "Rating", 5i32).unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::BadSchema);
+    }
+}
+
 mod delete_property {
     use crate::{tests::fixtures::*, XmpError, XmpErrorType, XmpMeta, XmpValue};
 
@@ -2004,13 +2849,112 @@ mod array_item {
     }
 }
 
-mod set_array_item {
+mod try_array_item {
     use std::str::FromStr;
 
-    use crate::{
-        tests::fixtures::*, xmp_ns, xmp_value::xmp_prop, ItemPlacement, XmpError, XmpErrorType,
-        XmpMeta, XmpValue,
-    };
+    use crate::{tests::fixtures::*, xmp_ns, XmpErrorType, XmpMeta};
+
+    #[test]
+    fn happy_path() {
+        let m = XmpMeta::from_str(PURPLE_SQUARE_XMP).unwrap();
+        assert_eq!(
+            m.try_array_item(xmp_ns::DC, "subject", 1)
+                .unwrap()
+                .unwrap()
+                .value,
+            "purple"
+        );
+    }
+
+    #[test]
+    fn past_end() {
+        let m = XmpMeta::from_str(PURPLE_SQUARE_XMP).unwrap();
+        assert_eq!(m.try_array_item(xmp_ns::DC, "subject", 42).unwrap(), None);
+    }
+
+    #[test]
+    fn init_fail() {
+        let m = XmpMeta::new_fail();
+        let err = m.try_array_item(xmp_ns::DC, "subject", 1).unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::NoCppToolkit);
+    }
+
+    #[test]
+    fn bad_index() {
+        let m = XmpMeta::from_str(PURPLE_SQUARE_XMP).unwrap();
+        let err = m.try_array_item(xmp_ns::DC, "subject", 0).unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::BadXPath);
+    }
+
+    #[test]
+    fn invalid_array_name() {
+        let m = XmpMeta::from_str(PURPLE_SQUARE_XMP).unwrap();
+        let err = m.try_array_item(xmp_ns::DC, "\0", 1).unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::NulInRustString);
+    }
+}
+
+mod array_item_into {
+    use std::str::FromStr;
+
+    use crate::{tests::fixtures::*, xmp_ns, XmpMeta, XmpValue};
+
+    #[test]
+    fn happy_path() {
+        let m = XmpMeta::from_str(ARRAY_EXAMPLE).unwrap();
+        let mut buf = String::new();
+
+        assert_eq!(
+            m.array_item_into(xmp_ns::DC, "subject", 4, &mut buf),
+            Some(XmpValue {
+                value: "XMP",
+                options: 0
+            })
+        );
+    }
+
+    #[test]
+    fn all_items() {
+        let m = XmpMeta::from_str(ARRAY_EXAMPLE).unwrap();
+        let mut buf = String::new();
+
+        let items: Vec<String> = (1..=m.array_len(xmp_ns::DC, "subject") as i32)
+            .filter_map(|i| {
+                m.array_item_into(xmp_ns::DC, "subject", i, &mut buf)
+                    .map(|item| item.value.to_owned())
+            })
+            .collect();
+
+        assert_eq!(
+            items,
+            m.property_array(xmp_ns::DC, "subject")
+                .map(|item| item.value)
+                .collect::<Vec<String>>()
+        );
+    }
+
+    #[test]
+    fn past_end() {
+        let m = XmpMeta::from_str(ARRAY_EXAMPLE).unwrap();
+        let mut buf = String::new();
+        assert_eq!(m.array_item_into(xmp_ns::DC, "subject", 42, &mut buf), None);
+    }
+
+    #[test]
+    fn init_fail() {
+        let m = XmpMeta::new_fail();
+        let mut buf = String::new();
+        assert_eq!(m.array_item_into(xmp_ns::DC, "subject", 1, &mut buf), None);
+    }
+}
+
+mod set_array_item {
+    use std::str::FromStr;
+
+    use crate::{
+        tests::fixtures::*, xmp_ns, xmp_value::xmp_prop, ItemPlacement, XmpError, XmpErrorType,
+        XmpMeta, XmpValue,
+    };
 
     #[test]
     fn happy_path() {
@@ -2345,6 +3289,278 @@ mod append_array_item {
     }
 }
 
+mod set_array {
+    use crate::{xmp_ns, xmp_value::xmp_prop, ArrayForm, XmpErrorType, XmpMeta, XmpValue};
+
+    fn subjects(m: &XmpMeta) -> Vec<String> {
+        m.property_array(xmp_ns::DC, "subject")
+            .map(|v| v.value)
+            .collect()
+    }
+
+    #[test]
+    fn happy_path() {
+        let mut m = XmpMeta::default();
+
+        m.set_array(
+            xmp_ns::DC,
+            "subject",
+            ArrayForm::Bag,
+            &["cat".into(), "dog".into()],
+        )
+        .unwrap();
+
+        assert_eq!(
+            m.property(xmp_ns::DC, "subject").unwrap(),
+            XmpValue {
+                value: "".to_owned(),
+                options: xmp_prop::VALUE_IS_ARRAY
+            }
+        );
+
+        assert_eq!(subjects(&m), vec!["cat", "dog"]);
+    }
+
+    #[test]
+    fn replaces_existing() {
+        let mut m = XmpMeta::default();
+
+        m.set_array(
+            xmp_ns::DC,
+            "subject",
+            ArrayForm::Bag,
+            &["cat".into(), "dog".into(), "bird".into()],
+        )
+        .unwrap();
+
+        m.set_array(
+            xmp_ns::DC,
+            "subject",
+            ArrayForm::Seq,
+            &[
+                "fish".into(),
+                XmpValue::from("http://example.com").set_is_uri(true),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            m.property(xmp_ns::DC, "subject").unwrap(),
+            XmpValue {
+                value: "".to_owned(),
+                options: xmp_prop::VALUE_IS_ARRAY | xmp_prop::ARRAY_IS_ORDERED
+            }
+        );
+
+        let items: Vec<XmpValue<String>> = m.property_array(xmp_ns::DC, "subject").collect();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].value, "fish");
+        assert_eq!(items[1].value, "http://example.com");
+        assert!(items[1].is_uri());
+    }
+
+    #[test]
+    fn alt() {
+        let mut m = XmpMeta::default();
+
+        m.set_array(xmp_ns::DC, "subject", ArrayForm::Alt, &["a".into()])
+            .unwrap();
+
+        let array = m.property(xmp_ns::DC, "subject").unwrap();
+        assert!(array.is_array());
+        assert!(array.is_ordered());
+        assert!(array.is_alternate());
+        assert!(!array.is_alt_text());
+    }
+
+    #[test]
+    fn empty_items() {
+        let mut m = XmpMeta::default();
+
+        m.set_array(xmp_ns::DC, "subject", ArrayForm::Bag, &["a".into()])
+            .unwrap();
+        m.set_array(xmp_ns::DC, "subject", ArrayForm::Bag, &[])
+            .unwrap();
+
+        assert!(m.contains_property(xmp_ns::DC, "subject"));
+        assert_eq!(m.array_len(xmp_ns::DC, "subject"), 0);
+    }
+
+    #[test]
+    fn atomic_on_error() {
+        let mut m = XmpMeta::default();
+
+        m.set_array(
+            xmp_ns::DC,
+            "subject",
+            ArrayForm::Bag,
+            &["cat".into(), "dog".into()],
+        )
+        .unwrap();
+
+        let err = m
+            .set_array(
+                xmp_ns::DC,
+                "subject",
+                ArrayForm::Bag,
+                &["fish".into(), "x\0x".into()],
+            )
+            .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::NulInRustString);
+        assert_eq!(subjects(&m), vec!["cat", "dog"]);
+    }
+
+    #[test]
+    fn init_fail() {
+        let mut m = XmpMeta::new_fail();
+
+        let err = m
+            .set_array(xmp_ns::DC, "subject", ArrayForm::Bag, &["a".into()])
+            .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::NoCppToolkit);
+    }
+
+    #[test]
+    fn error_empty_array_name() {
+        let mut m = XmpMeta::default();
+
+        let err = m
+            .set_array(xmp_ns::DC, "", ArrayForm::Bag, &["a".into()])
+            .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::BadXPath);
+    }
+}
+
+mod convert_array_form {
+    use std::str::FromStr;
+
+    use crate::{tests::fixtures::*, xmp_ns, ArrayForm, XmpErrorType, XmpMeta, XmpValue};
+
+    #[test]
+    fn bag_to_seq() {
+        let mut m = XmpMeta::default();
+
+        m.set_array(
+            xmp_ns::DC,
+            "creator",
+            ArrayForm::Bag,
+            &[
+                "Author 1".into(),
+                XmpValue::from("Author 2").set_is_uri(true),
+            ],
+        )
+        .unwrap();
+
+        m.convert_array_form(xmp_ns::DC, "creator", ArrayForm::Seq)
+            .unwrap();
+
+        let array = m.property(xmp_ns::DC, "creator").unwrap();
+        assert!(array.is_array());
+        assert!(array.is_ordered());
+        assert!(!array.is_alternate());
+
+        let items: Vec<XmpValue<String>> = m.property_array(xmp_ns::DC, "creator").collect();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].value, "Author 1");
+        assert_eq!(items[1].value, "Author 2");
+        assert!(items[1].is_uri());
+    }
+
+    #[test]
+    fn preserves_qualifiers() {
+        let mut m = XmpMeta::from_str(QUAL_EXAMPLE).unwrap();
+
+        m.convert_array_form("ns:test1/", "QualProp5", ArrayForm::Seq)
+            .unwrap();
+
+        let array = m.property("ns:test1/", "QualProp5").unwrap();
+        assert!(array.is_ordered());
+        assert!(array.has_qualifiers());
+
+        assert_eq!(
+            m.qualifier("ns:test1/", "QualProp5", xmp_ns::XML, "lang")
+                .unwrap()
+                .value,
+            "x-default"
+        );
+
+        let items: Vec<String> = m
+            .property_array("ns:test1/", "QualProp5")
+            .map(|v| v.value)
+            .collect();
+        assert_eq!(items, vec!["Item1.1 value", "Item1.2 value"]);
+    }
+
+    #[test]
+    fn preserves_item_structure() {
+        let mut m = XmpMeta::default();
+
+        m.set_array(xmp_ns::DC, "subject", ArrayForm::Bag, &["a".into()])
+            .unwrap();
+        m.set_qualifier(
+            xmp_ns::DC,
+            "subject[1]",
+            xmp_ns::XMP,
+            "Label",
+            &"qual".into(),
+        )
+        .unwrap();
+
+        m.convert_array_form(xmp_ns::DC, "subject", ArrayForm::Alt)
+            .unwrap();
+
+        assert!(m.property(xmp_ns::DC, "subject").unwrap().is_alternate());
+        assert_eq!(
+            m.qualifier(xmp_ns::DC, "subject[1]", xmp_ns::XMP, "Label")
+                .unwrap()
+                .value,
+            "qual"
+        );
+    }
+
+    #[test]
+    fn error_not_array() {
+        let mut m = XmpMeta::default();
+        m.set_property(xmp_ns::DC, "format", &"image/jpeg".into())
+            .unwrap();
+
+        let err = m
+            .convert_array_form(xmp_ns::DC, "format", ArrayForm::Seq)
+            .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::BadXPath);
+        assert_eq!(
+            m.property(xmp_ns::DC, "format").unwrap().value,
+            "image/jpeg"
+        );
+    }
+
+    #[test]
+    fn error_missing() {
+        let mut m = XmpMeta::default();
+
+        let err = m
+            .convert_array_form(xmp_ns::DC, "subject", ArrayForm::Seq)
+            .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::BadXPath);
+    }
+
+    #[test]
+    fn init_fail() {
+        let mut m = XmpMeta::new_fail();
+
+        let err = m
+            .convert_array_form(xmp_ns::DC, "subject", ArrayForm::Seq)
+            .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::NoCppToolkit);
+    }
+}
+
 mod delete_array_item {
     use std::str::FromStr;
 
@@ -2703,14 +3919,100 @@ mod delete_struct_field {
     }
 }
 
-mod qualifier {
-    use std::str::FromStr;
+mod set_struct_from_map {
+    use std::{collections::BTreeMap, str::FromStr};
 
-    use crate::{tests::fixtures::QUAL_EXAMPLE, xmp_value::xmp_prop, XmpMeta, XmpValue};
+    use crate::{tests::fixtures::*, xmp_ns, XmpErrorType, XmpMeta, XmpValue};
 
-    #[test]
-    fn exists() {
-        let m = XmpMeta::from_str(QUAL_EXAMPLE).unwrap();
+    fn fields(entries: &[(&str, &str)]) -> BTreeMap<(String, String), XmpValue<String>> {
+        entries
+            .iter()
+            .map(|(name, value)| {
+                (
+                    (xmp_ns::IPTC_CORE.to_owned(), (*name).to_owned()),
+                    XmpValue::from(*value),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn happy_path() {
+        let mut m = XmpMeta::default();
+        let map = fields(&[("CiAdrCity", "Seattle"), ("CiAdrCtry", "US")]);
+
+        m.set_struct_from_map(xmp_ns::IPTC_CORE, "CreatorContactInfo", &map)
+            .unwrap();
+
+        assert!(m
+            .property(xmp_ns::IPTC_CORE, "CreatorContactInfo")
+            .unwrap()
+            .is_struct());
+        assert_eq!(
+            m.struct_as_map(xmp_ns::IPTC_CORE, "CreatorContactInfo")
+                .unwrap(),
+            map
+        );
+    }
+
+    #[test]
+    fn replaces_existing() {
+        let mut m = XmpMeta::from_str(STRUCT_EXAMPLE).unwrap();
+        let map = fields(&[("CiAdrCity", "Seattle")]);
+
+        m.set_struct_from_map(xmp_ns::IPTC_CORE, "CreatorContactInfo", &map)
+            .unwrap();
+
+        assert_eq!(
+            m.struct_as_map(xmp_ns::IPTC_CORE, "CreatorContactInfo")
+                .unwrap(),
+            map
+        );
+    }
+
+    #[test]
+    fn atomic_on_error() {
+        let mut m = XmpMeta::from_str(STRUCT_EXAMPLE).unwrap();
+        let before = m
+            .struct_as_map(xmp_ns::IPTC_CORE, "CreatorContactInfo")
+            .unwrap();
+
+        let err = m
+            .set_struct_from_map(
+                xmp_ns::IPTC_CORE,
+                "CreatorContactInfo",
+                &fields(&[("CiAdrCity", "Seattle"), ("CiAdrCtry", "U\0S")]),
+            )
+            .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::NulInRustString);
+        assert_eq!(
+            m.struct_as_map(xmp_ns::IPTC_CORE, "CreatorContactInfo")
+                .unwrap(),
+            before
+        );
+    }
+
+    #[test]
+    fn init_fail() {
+        let mut m = XmpMeta::new_fail();
+
+        let err = m
+            .set_struct_from_map(xmp_ns::IPTC_CORE, "CreatorContactInfo", &BTreeMap::new())
+            .unwrap_err();
+
+        assert_eq!(err.error_type, XmpErrorType::NoCppToolkit);
+    }
+}
+
+mod qualifier {
+    use std::str::FromStr;
+
+    use crate::{tests::fixtures::QUAL_EXAMPLE, xmp_value::xmp_prop, XmpMeta, XmpValue};
+
+    #[test]
+    fn exists() {
+        let m = XmpMeta::from_str(QUAL_EXAMPLE).unwrap();
 
         assert_eq!(
             m.qualifier("ns:test1/", "QualProp1", "ns:test2/", "Qual")
@@ -2766,6 +4068,129 @@ mod qualifier {
     }
 }
 
+mod try_qualifier {
+    use std::str::FromStr;
+
+    use crate::{tests::fixtures::*, XmpErrorType, XmpMeta};
+
+    #[test]
+    fn happy_path() {
+        let m = XmpMeta::from_str(QUAL_EXAMPLE).unwrap();
+        assert_eq!(
+            m.try_qualifier("ns:test1/", "QualProp1", "ns:test2/", "Qual")
+                .unwrap()
+                .unwrap()
+                .value,
+            "Qual value"
+        );
+    }
+
+    #[test]
+    fn missing_qualifier() {
+        let m = XmpMeta::from_str(QUAL_EXAMPLE).unwrap();
+        assert_eq!(
+            m.try_qualifier("ns:test1/", "QualProp1", "ns:test2/", "Qualx")
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn init_fail() {
+        let m = XmpMeta::new_fail();
+        let err = m
+            .try_qualifier("ns:test1/", "QualProp1", "ns:test2/", "Qual")
+            .unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::NoCppToolkit);
+    }
+
+    #[test]
+    fn invalid_qual_name() {
+        let m = XmpMeta::from_str(QUAL_EXAMPLE).unwrap();
+        let err = m
+            .try_qualifier("ns:test1/", "QualProp1", "ns:test2/", "\0")
+            .unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::NulInRustString);
+    }
+}
+
+mod qualifiers {
+    use std::str::FromStr;
+
+    use crate::{tests::fixtures::*, xmp_ns, xmp_value::xmp_prop, XmpMeta, XmpValue};
+
+    #[test]
+    fn happy_path() {
+        let m = XmpMeta::from_str(QUAL_EXAMPLE).unwrap();
+
+        let quals: Vec<(String, String, XmpValue<String>)> =
+            m.qualifiers("ns:test1/", "QualProp1").collect();
+
+        assert_eq!(
+            quals,
+            [(
+                "ns:test2/".to_owned(),
+                "Qual".to_owned(),
+                XmpValue {
+                    value: "Qual value".to_owned(),
+                    options: xmp_prop::IS_QUALIFIER
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn multiple() {
+        let m = XmpMeta::from_str(QUAL_EXAMPLE).unwrap();
+
+        let quals: Vec<(String, String)> = m
+            .qualifiers("ns:test1/", "QualProp2")
+            .map(|(ns, name, _)| (ns, name))
+            .collect();
+
+        assert_eq!(
+            quals,
+            [
+                (xmp_ns::XML.to_owned(), "lang".to_owned()),
+                ("ns:test2/".to_owned(), "Qual".to_owned())
+            ]
+        );
+
+        for (ns, name, value) in m.qualifiers("ns:test1/", "QualProp2") {
+            assert_eq!(
+                m.qualifier("ns:test1/", "QualProp2", &ns, &name),
+                Some(value)
+            );
+        }
+    }
+
+    #[test]
+    fn array_item() {
+        let m = XmpMeta::from_str(QUAL_EXAMPLE).unwrap();
+
+        assert_eq!(m.qualifiers("ns:test1/", "QualProp5[1]").count(), 0);
+        assert_eq!(m.qualifiers("ns:test1/", "QualProp5").count(), 1);
+    }
+
+    #[test]
+    fn no_qualifiers() {
+        let m = XmpMeta::from_str(QUAL_EXAMPLE).unwrap();
+        assert_eq!(m.qualifiers("ns:test1/", "SimpleProp1").count(), 0);
+    }
+
+    #[test]
+    fn no_such_property() {
+        let m = XmpMeta::from_str(QUAL_EXAMPLE).unwrap();
+        assert_eq!(m.qualifiers("ns:test1/", "NoSuchProp").count(), 0);
+    }
+
+    #[test]
+    fn init_fail() {
+        let m = XmpMeta::new_fail();
+        assert_eq!(m.qualifiers("ns:test1/", "QualProp1").count(), 0);
+    }
+}
+
 mod set_qualifier {
     use super::{NS1, NS2};
     use crate::{xmp_ns, XmpErrorType, XmpMeta, XmpValue};
@@ -3045,6 +4470,194 @@ mod localized_text {
     }
 }
 
+mod localized_text_lookup {
+    use std::str::FromStr;
+
+    use crate::{xmp_ns, XmpMeta};
+
+    fn titles(langs: &[(&str, &str)]) -> XmpMeta {
+        let mut m = XmpMeta::new().unwrap();
+        for (lang, title) in langs {
+            m.set_localized_text(xmp_ns::DC, "title", None, lang, title)
+                .unwrap();
+        }
+        m
+    }
+
+    fn lookup(m: &XmpMeta, languages: &[&str]) -> Option<(String, String)> {
+        m.localized_text_lookup(xmp_ns::DC, "title", languages)
+            .map(|(value, lang)| (value.value, lang))
+    }
+
+    #[test]
+    fn exact_match() {
+        let m = titles(&[("x-default", "Sunset"), ("de-CH", "Sonnenuntergang")]);
+
+        assert_eq!(
+            lookup(&m, &["de-CH"]),
+            Some(("Sonnenuntergang".to_owned(), "de-CH".to_owned()))
+        );
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let m = titles(&[("x-default", "Sunset"), ("de-CH", "Sonnenuntergang")]);
+
+        assert_eq!(
+            lookup(&m, &["DE-ch"]),
+            Some(("Sonnenuntergang".to_owned(), "de-CH".to_owned()))
+        );
+    }
+
+    #[test]
+    fn truncates_range() {
+        let m = titles(&[("x-default", "Sunset"), ("de", "Sonnenuntergang")]);
+
+        assert_eq!(
+            lookup(&m, &["de-CH-1996"]),
+            Some(("Sonnenuntergang".to_owned(), "de".to_owned()))
+        );
+    }
+
+    #[test]
+    fn removes_trailing_singleton() {
+        let m = titles(&[("x-default", "Sunset"), ("zh-Hant", "日落")]);
+
+        assert_eq!(
+            lookup(&m, &["zh-Hant-x-private"]),
+            // The C++ XMP Toolkit normalizes the capitalization of the tag.
+            Some(("日落".to_owned(), "zh-hant".to_owned()))
+        );
+    }
+
+    #[test]
+    fn priority_order() {
+        let m = titles(&[
+            ("x-default", "Sunset"),
+            ("fr", "Coucher de soleil"),
+            ("de", "Sonnenuntergang"),
+        ]);
+
+        assert_eq!(
+            lookup(&m, &["it-CH", "fr-CH", "de-CH"]),
+            Some(("Coucher de soleil".to_owned(), "fr".to_owned()))
+        );
+    }
+
+    #[test]
+    fn does_not_match_more_specific_item() {
+        let m = titles(&[("x-default", "Sunset"), ("de-DE", "Sonnenuntergang")]);
+
+        assert_eq!(
+            lookup(&m, &["de", "*"]),
+            Some(("Sunset".to_owned(), "x-default".to_owned()))
+        );
+    }
+
+    #[test]
+    fn falls_back_to_first_item() {
+        let m = XmpMeta::from_str(
+            r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+                <rdf:Description rdf:about="" xmlns:dc="http://purl.org/dc/elements/1.1/">
+                    <dc:title>
+                        <rdf:Alt>
+                            <rdf:li xml:lang="fr">Coucher de soleil</rdf:li>
+                            <rdf:li xml:lang="de">Sonnenuntergang</rdf:li>
+                        </rdf:Alt>
+                    </dc:title>
+                </rdf:Description>
+            </rdf:RDF>"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            lookup(&m, &["ja"]),
+            Some(("Coucher de soleil".to_owned(), "fr".to_owned()))
+        );
+    }
+
+    #[test]
+    fn not_alt_text() {
+        let mut m = XmpMeta::new().unwrap();
+        m.set_property(xmp_ns::DC, "title", &"Sunset".into())
+            .unwrap();
+
+        assert_eq!(lookup(&m, &["en"]), None);
+        assert_eq!(
+            m.localized_text_lookup(xmp_ns::DC, "description", &["en"]),
+            None
+        );
+    }
+}
+
+mod strict_lang_tags {
+    use crate::{xmp_ns, XmpErrorType, XmpMeta};
+
+    #[test]
+    fn default_off() {
+        let mut m = XmpMeta::new().unwrap();
+        assert!(!m.strict_lang_tags());
+
+        m.set_localized_text(xmp_ns::DC, "title", None, "en_US", "Sunset")
+            .unwrap();
+    }
+
+    #[test]
+    fn set_localized_text() {
+        let mut m = XmpMeta::new().unwrap();
+        m.set_strict_lang_tags(true);
+
+        let err = m
+            .set_localized_text(xmp_ns::DC, "title", None, "en_US", "Sunset")
+            .unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::BadValue);
+        assert!(!m.contains_property(xmp_ns::DC, "title"));
+
+        let err = m
+            .set_localized_text(xmp_ns::DC, "title", Some("e"), "en-US", "Sunset")
+            .unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::BadValue);
+
+        m.set_localized_text(xmp_ns::DC, "title", Some(""), "en-us", "Sunset")
+            .unwrap();
+        m.set_localized_text(xmp_ns::DC, "title", None, "x-default", "Sunset")
+            .unwrap();
+
+        let (_, lang) = m
+            .localized_text(xmp_ns::DC, "title", None, "en-US")
+            .unwrap();
+        assert_eq!(lang, "en-US");
+    }
+
+    #[test]
+    fn set_qualifier() {
+        let mut m = XmpMeta::new().unwrap();
+        m.set_strict_lang_tags(true);
+        m.set_property(xmp_ns::DC, "source", &"scan".into())
+            .unwrap();
+
+        let err = m
+            .set_qualifier(xmp_ns::DC, "source", xmp_ns::XML, "lang", &"en US".into())
+            .unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::BadValue);
+
+        m.set_qualifier(xmp_ns::DC, "source", xmp_ns::XML, "lang", &"fr-CA".into())
+            .unwrap();
+
+        // Other qualifiers are not checked.
+        m.set_qualifier(xmp_ns::DC, "source", xmp_ns::XMP, "Label", &"en US".into())
+            .unwrap();
+    }
+
+    #[test]
+    fn cloned() {
+        let mut m = XmpMeta::new().unwrap();
+        m.set_strict_lang_tags(true);
+
+        assert!(m.clone().strict_lang_tags());
+    }
+}
+
 mod set_localized_text {
     use std::str::FromStr;
 
@@ -3220,23 +4833,282 @@ mod name {
     }
 }
 
-mod compose_array_index_path {
+mod rating {
     use crate::{xmp_ns, XmpErrorType, XmpMeta};
 
     #[test]
     fn happy_path() {
-        assert_eq!(
-            XmpMeta::compose_array_item_path(xmp_ns::XMP, "ArrayName", 4).unwrap(),
-            "ArrayName[4]"
-        );
-    }
+        let mut m = XmpMeta::new().unwrap();
+        assert_eq!(m.rating(), None);
 
-    #[test]
-    fn last_item() {
-        assert_eq!(
-            XmpMeta::compose_array_item_path(xmp_ns::XMP, "ArrayName", XmpMeta::LAST_ITEM).unwrap(),
-            "ArrayName[last()]"
-        );
+        m.set_rating(3).unwrap();
+        assert_eq!(m.rating(), Some(3));
+        assert_eq!(m.property(xmp_ns::XMP, "Rating").unwrap().value, "3");
+
+        m.set_rating(-1).unwrap();
+        assert_eq!(m.rating(), Some(-1));
+    }
+
+    #[test]
+    fn clamped() {
+        let mut m = XmpMeta::new().unwrap();
+
+        m.set_rating(7).unwrap();
+        assert_eq!(m.rating(), Some(5));
+
+        m.set_rating(-4).unwrap();
+        assert_eq!(m.rating(), Some(-1));
+    }
+
+    #[test]
+    fn real_value() {
+        let mut m = XmpMeta::new().unwrap();
+
+        m.set_property(xmp_ns::XMP, "Rating", &"4.0".into())
+            .unwrap();
+        assert_eq!(m.rating(), Some(4));
+
+        m.set_property(xmp_ns::XMP, "Rating", &"2.6".into())
+            .unwrap();
+        assert_eq!(m.rating(), Some(3));
+    }
+
+    #[test]
+    fn not_a_number() {
+        let mut m = XmpMeta::new().unwrap();
+        m.set_property(xmp_ns::XMP, "Rating", &"five".into())
+            .unwrap();
+        assert_eq!(m.rating(), None);
+    }
+
+    #[test]
+    fn init_fail() {
+        let mut m = XmpMeta::new_fail();
+        assert_eq!(m.rating(), None);
+        assert_eq!(
+            m.set_rating(3).unwrap_err().error_type,
+            XmpErrorType::NoCppToolkit
+        );
+    }
+}
+
+mod label {
+    use crate::{xmp_ns, XmpErrorType, XmpMeta};
+
+    #[test]
+    fn happy_path() {
+        let mut m = XmpMeta::new().unwrap();
+        assert_eq!(m.label(), None);
+
+        m.set_label("Red").unwrap();
+        assert_eq!(m.label().as_deref(), Some("Red"));
+        assert!(m.contains_property(xmp_ns::XMP, "Label"));
+    }
+
+    #[test]
+    fn empty_removes() {
+        let mut m = XmpMeta::new().unwrap();
+        m.set_label("Red").unwrap();
+
+        m.set_label("").unwrap();
+        assert_eq!(m.label(), None);
+        assert!(!m.contains_property(xmp_ns::XMP, "Label"));
+    }
+
+    #[test]
+    fn init_fail() {
+        let mut m = XmpMeta::new_fail();
+        assert_eq!(m.label(), None);
+        assert_eq!(
+            m.set_label("Red").unwrap_err().error_type,
+            XmpErrorType::NoCppToolkit
+        );
+    }
+}
+
+mod hierarchical_keywords {
+    use std::str::FromStr;
+
+    use crate::{xmp_ns, KeywordTree, XmpMeta};
+
+    #[test]
+    fn empty() {
+        let m = XmpMeta::new().unwrap();
+        assert!(m.hierarchical_keywords().is_empty());
+    }
+
+    #[test]
+    fn from_lightroom() {
+        let m = XmpMeta::from_str(
+            r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+                <rdf:Description rdf:about="" xmlns:lr="http://ns.adobe.com/lightroom/1.0/">
+                    <lr:hierarchicalSubject>
+                        <rdf:Bag>
+                            <rdf:li>Animals</rdf:li>
+                            <rdf:li>Animals|Birds</rdf:li>
+                            <rdf:li>Animals|Birds|Heron</rdf:li>
+                        </rdf:Bag>
+                    </lr:hierarchicalSubject>
+                </rdf:Description>
+            </rdf:RDF>"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            m.hierarchical_keywords(),
+            KeywordTree::from_paths(["Animals|Birds|Heron"])
+        );
+        assert_eq!(m.array_len(xmp_ns::LIGHTROOM, "hierarchicalSubject"), 3);
+    }
+
+    #[test]
+    fn init_fail() {
+        let m = XmpMeta::new_fail();
+        assert!(m.hierarchical_keywords().is_empty());
+    }
+}
+
+mod set_hierarchical_keywords {
+    use crate::{xmp_ns, KeywordTree, XmpErrorType, XmpMeta, XmpValue};
+
+    fn subjects(m: &XmpMeta) -> Vec<String> {
+        m.property_array(xmp_ns::DC, "subject")
+            .map(|item| item.value)
+            .collect()
+    }
+
+    #[test]
+    fn happy_path() {
+        let mut m = XmpMeta::new().unwrap();
+        m.append_array_item(
+            xmp_ns::DC,
+            &XmpValue::from("subject").set_is_array(true),
+            &"Flat keyword".into(),
+        )
+        .unwrap();
+
+        let tree = KeywordTree::from_paths(["Animals|Birds|Heron", "Animals|Mammals"]);
+        m.set_hierarchical_keywords(&tree).unwrap();
+
+        assert_eq!(m.hierarchical_keywords(), tree);
+        assert_eq!(
+            subjects(&m),
+            ["Flat keyword", "Animals", "Birds", "Heron", "Mammals"]
+        );
+    }
+
+    #[test]
+    fn removes_stale_keywords() {
+        let mut m = XmpMeta::new().unwrap();
+
+        m.set_hierarchical_keywords(&KeywordTree::from_paths(["Animals|Birds|Heron"]))
+            .unwrap();
+        m.set_hierarchical_keywords(&KeywordTree::from_paths(["Animals|Mammals"]))
+            .unwrap();
+
+        assert_eq!(
+            m.property_array(xmp_ns::LIGHTROOM, "hierarchicalSubject")
+                .map(|item| item.value)
+                .collect::<Vec<_>>(),
+            ["Animals|Mammals"]
+        );
+        assert_eq!(subjects(&m), ["Animals", "Mammals"]);
+    }
+
+    #[test]
+    fn empty_tree() {
+        let mut m = XmpMeta::new().unwrap();
+
+        m.set_hierarchical_keywords(&KeywordTree::from_paths(["Animals|Birds"]))
+            .unwrap();
+        m.set_hierarchical_keywords(&KeywordTree::new()).unwrap();
+
+        assert!(!m.contains_property(xmp_ns::LIGHTROOM, "hierarchicalSubject"));
+        assert!(!m.contains_property(xmp_ns::DC, "subject"));
+    }
+
+    #[test]
+    fn init_fail() {
+        let mut m = XmpMeta::new_fail();
+        assert_eq!(
+            m.set_hierarchical_keywords(&KeywordTree::from_paths(["A|B"]))
+                .unwrap_err()
+                .error_type,
+            XmpErrorType::NoCppToolkit
+        );
+    }
+}
+
+mod add_hierarchical_keyword {
+    use crate::{xmp_ns, XmpErrorType, XmpMeta};
+
+    #[test]
+    fn happy_path() {
+        let mut m = XmpMeta::new().unwrap();
+
+        m.add_hierarchical_keyword("Animals|Birds|Heron").unwrap();
+        m.add_hierarchical_keyword(" Animals | Birds | Egret ")
+            .unwrap();
+
+        assert_eq!(
+            m.property_array(xmp_ns::LIGHTROOM, "hierarchicalSubject")
+                .map(|item| item.value)
+                .collect::<Vec<_>>(),
+            ["Animals|Birds|Heron", "Animals|Birds|Egret"]
+        );
+        assert_eq!(
+            m.property_array(xmp_ns::DC, "subject")
+                .map(|item| item.value)
+                .collect::<Vec<_>>(),
+            ["Animals", "Birds", "Heron", "Egret"]
+        );
+    }
+
+    #[test]
+    fn duplicate() {
+        let mut m = XmpMeta::new().unwrap();
+
+        m.add_hierarchical_keyword("Animals|Birds").unwrap();
+        m.add_hierarchical_keyword("Animals|Birds").unwrap();
+
+        assert_eq!(m.array_len(xmp_ns::LIGHTROOM, "hierarchicalSubject"), 1);
+        assert_eq!(m.array_len(xmp_ns::DC, "subject"), 2);
+    }
+
+    #[test]
+    fn empty_path() {
+        let mut m = XmpMeta::new().unwrap();
+        m.add_hierarchical_keyword(" | ").unwrap();
+        assert!(!m.contains_property(xmp_ns::LIGHTROOM, "hierarchicalSubject"));
+    }
+
+    #[test]
+    fn init_fail() {
+        let mut m = XmpMeta::new_fail();
+        assert_eq!(
+            m.add_hierarchical_keyword("A|B").unwrap_err().error_type,
+            XmpErrorType::NoCppToolkit
+        );
+    }
+}
+
+mod compose_array_index_path {
+    use crate::{xmp_ns, XmpErrorType, XmpMeta};
+
+    #[test]
+    fn happy_path() {
+        assert_eq!(
+            XmpMeta::compose_array_item_path(xmp_ns::XMP, "ArrayName", 4).unwrap(),
+            "ArrayName[4]"
+        );
+    }
+
+    #[test]
+    fn last_item() {
+        assert_eq!(
+            XmpMeta::compose_array_item_path(xmp_ns::XMP, "ArrayName", XmpMeta::LAST_ITEM).unwrap(),
+            "ArrayName[last()]"
+        );
     }
 
     #[test]
@@ -3318,6 +5190,209 @@ mod compose_struct_field_path {
     }
 }
 
+mod access_stats {
+    use std::collections::BTreeMap;
+
+    use crate::{tests::fixtures::*, xmp_ns, ArrayForm, XmpMeta, XmpValue};
+
+    #[test]
+    fn disabled_by_default() {
+        let m = XmpMeta::from_file(fixture_path("Purple Square.psd")).unwrap();
+        m.property(xmp_ns::XMP, "CreatorTool");
+
+        assert!(m.access_stats().is_none());
+    }
+
+    #[test]
+    fn happy_path() {
+        let mut m = XmpMeta::from_file(fixture_path("Purple Square.psd")).unwrap();
+        m.enable_access_stats();
+
+        m.property(xmp_ns::XMP, "CreatorTool");
+        m.get::<i32>(xmp_ns::EXIF, "PixelXDimension");
+        m.set(xmp_ns::EXIF, "PixelXDimension", 300).unwrap();
+        m.struct_field(xmp_ns::EXIF, "Flash", xmp_ns::EXIF, "Fired");
+        m.delete_property(xmp_ns::XMP, "Missing").unwrap();
+
+        let stats = m.access_stats().unwrap();
+
+        let count = stats.get(xmp_ns::XMP, "CreatorTool");
+        assert_eq!(count.reads, 1);
+        assert_eq!(count.writes, 0);
+
+        let count = stats.get(xmp_ns::EXIF, "PixelXDimension");
+        assert_eq!(count.reads, 1);
+        assert_eq!(count.writes, 1);
+
+        assert_eq!(stats.get(xmp_ns::EXIF, "Flash").reads, 1);
+        assert_eq!(stats.get(xmp_ns::XMP, "Missing").writes, 1);
+        assert_eq!(stats.get(xmp_ns::DC, "title").reads, 0);
+    }
+
+    #[test]
+    fn atomic_updates_count_once() {
+        let mut m = XmpMeta::new().unwrap();
+        m.enable_access_stats();
+
+        m.set_array(
+            xmp_ns::DC,
+            "subject",
+            ArrayForm::Bag,
+            &[XmpValue::from("a"), XmpValue::from("b")],
+        )
+        .unwrap();
+        m.convert_array_form(xmp_ns::DC, "subject", ArrayForm::Seq)
+            .unwrap();
+
+        let mut fields = BTreeMap::new();
+        fields.insert(
+            (xmp_ns::IPTC_CORE.to_owned(), "CiAdrCity".to_owned()),
+            XmpValue::from("Seattle"),
+        );
+        m.set_struct_from_map(xmp_ns::IPTC_CORE, "CreatorContactInfo", &fields)
+            .unwrap();
+
+        let stats = m.access_stats().unwrap();
+
+        let count = stats.get(xmp_ns::DC, "subject");
+        assert_eq!(count.reads, 0);
+        assert_eq!(count.writes, 2);
+
+        assert_eq!(stats.get(xmp_ns::IPTC_CORE, "CreatorContactInfo").writes, 1);
+        assert_eq!(stats.iter().count(), 2);
+    }
+
+    #[test]
+    fn enable_twice_keeps_counts() {
+        let mut m = XmpMeta::new().unwrap();
+        m.enable_access_stats();
+        m.property(xmp_ns::XMP, "CreatorTool");
+        m.enable_access_stats();
+
+        let stats = m.access_stats().unwrap();
+        assert_eq!(stats.get(xmp_ns::XMP, "CreatorTool").reads, 1);
+    }
+
+    #[test]
+    fn disable() {
+        let mut m = XmpMeta::new().unwrap();
+        assert!(m.disable_access_stats().is_none());
+
+        m.enable_access_stats();
+        m.property(xmp_ns::XMP, "CreatorTool");
+
+        let stats = m.disable_access_stats().unwrap();
+        assert_eq!(stats.get(xmp_ns::XMP, "CreatorTool").reads, 1);
+
+        m.property(xmp_ns::XMP, "CreatorTool");
+        assert!(m.access_stats().is_none());
+    }
+
+    #[test]
+    fn not_cloned() {
+        let mut m = XmpMeta::new().unwrap();
+        m.enable_access_stats();
+
+        let m2 = m.clone();
+        assert!(m2.access_stats().is_none());
+    }
+
+    #[test]
+    fn init_fail() {
+        let mut m = XmpMeta::new_fail();
+        m.enable_access_stats();
+        m.property(xmp_ns::XMP, "CreatorTool");
+
+        let stats = m.access_stats().unwrap();
+        assert_eq!(stats.get(xmp_ns::XMP, "CreatorTool").reads, 1);
+    }
+}
+
+mod snapshot {
+    use std::{str::FromStr, thread};
+
+    use crate::{tests::fixtures::*, xmp_ns, XmpMeta, XmpMetaSnapshot, XmpValue};
+
+    #[test]
+    fn happy_path() {
+        let m = XmpMeta::from_str(PURPLE_SQUARE_XMP).unwrap();
+        let snapshot = m.into_snapshot();
+
+        assert_eq!(
+            snapshot.property(xmp_ns::XMP, "CreatorTool").unwrap(),
+            XmpValue {
+                value: "Adobe Photoshop CS2 Windows".to_owned(),
+                options: 0
+            }
+        );
+    }
+
+    #[test]
+    fn to_meta() {
+        let snapshot = XmpMeta::from_str(PURPLE_SQUARE_XMP)
+            .unwrap()
+            .into_snapshot();
+
+        let mut m = snapshot.to_meta();
+        m.set_property(xmp_ns::XMP, "CreatorTool", &"Changed".into())
+            .unwrap();
+
+        assert_eq!(
+            snapshot.property(xmp_ns::XMP, "CreatorTool").unwrap().value,
+            "Adobe Photoshop CS2 Windows"
+        );
+        assert_eq!(
+            m.property(xmp_ns::XMP, "CreatorTool").unwrap().value,
+            "Changed"
+        );
+    }
+
+    #[test]
+    fn concurrent_readers() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<XmpMetaSnapshot>();
+
+        let snapshot = XmpMeta::from_str(PURPLE_SQUARE_XMP)
+            .unwrap()
+            .into_snapshot();
+
+        let readers: Vec<_> = (0..8)
+            .map(|_| {
+                let snapshot = snapshot.clone();
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        assert_eq!(
+                            snapshot.property(xmp_ns::XMP, "CreatorTool").unwrap().value,
+                            "Adobe Photoshop CS2 Windows"
+                        );
+                        assert_eq!(snapshot.array_len(xmp_ns::DC, "subject"), 6);
+                    }
+                })
+            })
+            .collect();
+
+        for reader in readers {
+            reader.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn impl_display() {
+        let m = XmpMeta::from_str(PURPLE_SQUARE_XMP).unwrap();
+        let snapshot = m.clone().into_snapshot();
+
+        assert_eq!(format!("{snapshot}"), format!("{m}"));
+        assert_eq!(format!("{snapshot:#}"), format!("{m:#}"));
+    }
+
+    #[test]
+    fn init_fail() {
+        let snapshot = XmpMeta::new_fail().into_snapshot();
+        assert_eq!(snapshot.property(xmp_ns::XMP, "CreatorTool"), None);
+        assert_eq!(format!("{snapshot:?}"), "(C++ XMP Toolkit unavailable)");
+    }
+}
+
 mod impl_clone {
     use crate::{tests::fixtures::*, xmp_ns, XmpMeta};
 
@@ -3578,3 +5653,342 @@ mod impl_send {
             .is_none());
     }
 }
+
+mod impl_sync {
+    use std::{str::FromStr, thread};
+
+    use crate::{tests::fixtures::*, xmp_ns, IterOptions, XmpMeta};
+
+    #[test]
+    fn concurrent_readers() {
+        let mut m = XmpMeta::from_str(PURPLE_SQUARE_XMP).unwrap();
+        m.enable_access_stats();
+
+        thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for _ in 0..100 {
+                        assert_eq!(
+                            m.property(xmp_ns::XMP, "CreatorTool").unwrap().value,
+                            "Adobe Photoshop CS2 Windows"
+                        );
+                        assert_eq!(m.property_array(xmp_ns::DC, "subject").count(), 6);
+                        assert!(m.iter(IterOptions::default()).count() > 0);
+                    }
+                });
+            }
+        });
+
+        let stats = m.access_stats().unwrap();
+        assert_eq!(stats.get(xmp_ns::XMP, "CreatorTool").reads, 800);
+        assert_eq!(stats.get(xmp_ns::DC, "subject").reads, 800);
+    }
+}
+
+mod read_handle {
+    use std::{str::FromStr, thread};
+
+    use crate::{tests::fixtures::*, xmp_ns, XmpMeta, XmpMetaReadHandle};
+
+    #[test]
+    fn happy_path() {
+        let m = XmpMeta::from_str(PURPLE_SQUARE_XMP).unwrap();
+        let handle = m.read_handle();
+
+        assert_eq!(
+            handle.property(xmp_ns::XMP, "CreatorTool").unwrap().value,
+            "Adobe Photoshop CS2 Windows"
+        );
+        assert_eq!(handle.array_len(xmp_ns::DC, "subject"), 6);
+    }
+
+    #[test]
+    fn outlives_meta() {
+        let handle = {
+            let m = XmpMeta::from_str(PURPLE_SQUARE_XMP).unwrap();
+            m.read_handle()
+        };
+
+        assert_eq!(
+            handle.property(xmp_ns::XMP, "CreatorTool").unwrap().value,
+            "Adobe Photoshop CS2 Windows"
+        );
+    }
+
+    #[test]
+    fn sees_later_changes() {
+        let mut m = XmpMeta::from_str(PURPLE_SQUARE_XMP).unwrap();
+        let handle = m.read_handle();
+
+        m.set_property(xmp_ns::XMP, "CreatorTool", &"Changed".into())
+            .unwrap();
+
+        assert_eq!(
+            handle.property(xmp_ns::XMP, "CreatorTool").unwrap().value,
+            "Changed"
+        );
+    }
+
+    #[test]
+    fn to_meta() {
+        let handle = XmpMeta::from_str(PURPLE_SQUARE_XMP).unwrap().read_handle();
+
+        let mut m = handle.to_meta();
+        m.set_property(xmp_ns::XMP, "CreatorTool", &"Changed".into())
+            .unwrap();
+
+        assert_eq!(
+            handle.property(xmp_ns::XMP, "CreatorTool").unwrap().value,
+            "Adobe Photoshop CS2 Windows"
+        );
+        assert_eq!(
+            m.property(xmp_ns::XMP, "CreatorTool").unwrap().value,
+            "Changed"
+        );
+    }
+
+    #[test]
+    fn concurrent_readers() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<XmpMetaReadHandle>();
+
+        let m = XmpMeta::from_str(PURPLE_SQUARE_XMP).unwrap();
+        let handle = m.read_handle();
+
+        let readers: Vec<_> = (0..8)
+            .map(|_| {
+                let handle = handle.clone();
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        assert_eq!(
+                            handle.property(xmp_ns::XMP, "CreatorTool").unwrap().value,
+                            "Adobe Photoshop CS2 Windows"
+                        );
+                        assert_eq!(handle.array_len(xmp_ns::DC, "subject"), 6);
+                    }
+                })
+            })
+            .collect();
+
+        for reader in readers {
+            reader.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn impl_display() {
+        let m = XmpMeta::from_str(PURPLE_SQUARE_XMP).unwrap();
+        let handle = m.read_handle();
+
+        assert_eq!(format!("{handle}"), format!("{m}"));
+    }
+
+    #[test]
+    fn init_fail() {
+        let handle = XmpMeta::new_fail().read_handle();
+        assert_eq!(handle.property(xmp_ns::XMP, "CreatorTool"), None);
+        assert_eq!(format!("{handle:?}"), "(C++ XMP Toolkit unavailable)");
+    }
+}
+
+mod on_change {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{xmp_ns, ArrayForm, ChangeEvent, ChangeKind, XmpMeta, XmpValue};
+
+    fn record(m: &mut XmpMeta) -> Arc<Mutex<Vec<ChangeEvent>>> {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let listener_events = events.clone();
+
+        m.on_change(move |event| listener_events.lock().unwrap().push(event.clone()));
+
+        events
+    }
+
+    fn summary(events: &Mutex<Vec<ChangeEvent>>) -> Vec<(ChangeKind, String)> {
+        events
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|event| (event.kind, event.path.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn set_append_delete() {
+        let mut m = XmpMeta::new().unwrap();
+        let events = record(&mut m);
+
+        m.set_property(xmp_ns::XMP, "CreatorTool", &"Example".into())
+            .unwrap();
+
+        let creator = XmpValue::from("creator")
+            .set_is_array(true)
+            .set_is_ordered(true);
+        m.append_array_item(xmp_ns::DC, &creator, &"Alice".into())
+            .unwrap();
+        m.append_array_item(xmp_ns::DC, &creator, &"Bob".into())
+            .unwrap();
+
+        m.delete_array_item(xmp_ns::DC, "creator", 1).unwrap();
+        m.delete_property(xmp_ns::XMP, "CreatorTool").unwrap();
+
+        assert_eq!(
+            summary(&events),
+            [
+                (ChangeKind::Set, "CreatorTool".to_owned()),
+                (ChangeKind::Append, "creator[1]".to_owned()),
+                (ChangeKind::Append, "creator[2]".to_owned()),
+                (ChangeKind::Delete, "creator[1]".to_owned()),
+                (ChangeKind::Delete, "CreatorTool".to_owned()),
+            ]
+        );
+
+        let events = events.lock().unwrap();
+        assert_eq!(events[0].namespace, xmp_ns::XMP);
+        assert_eq!(events[1].namespace, xmp_ns::DC);
+    }
+
+    #[test]
+    fn struct_fields_and_qualifiers() {
+        let mut m = XmpMeta::new().unwrap();
+        let events = record(&mut m);
+
+        m.set_struct_field(
+            xmp_ns::IPTC_CORE,
+            "CreatorContactInfo",
+            xmp_ns::IPTC_CORE,
+            "CiAdrCity",
+            &"Seattle".into(),
+        )
+        .unwrap();
+
+        m.set_property(xmp_ns::DC, "source", &"scan".into())
+            .unwrap();
+        m.set_qualifier(xmp_ns::DC, "source", xmp_ns::XMP, "Label", &"draft".into())
+            .unwrap();
+
+        m.set_localized_text(xmp_ns::DC, "title", None, "en-US", "Sunset")
+            .unwrap();
+
+        assert_eq!(
+            summary(&events),
+            [
+                (
+                    ChangeKind::Set,
+                    "CreatorContactInfo/Iptc4xmpCore:CiAdrCity".to_owned()
+                ),
+                (ChangeKind::Set, "source".to_owned()),
+                (ChangeKind::Set, "source/?xmp:Label".to_owned()),
+                (ChangeKind::Set, "title".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn typed_setters() {
+        let mut m = XmpMeta::new().unwrap();
+        let events = record(&mut m);
+
+        m.set(xmp_ns::XMP, "Rating", 3).unwrap();
+        m.set_property_bool(xmp_ns::XMP_RIGHTS, "Marked", &true.into())
+            .unwrap();
+
+        assert_eq!(
+            summary(&events),
+            [
+                (ChangeKind::Set, "Rating".to_owned()),
+                (ChangeKind::Set, "Marked".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn staged_update() {
+        let mut m = XmpMeta::new().unwrap();
+        let events = record(&mut m);
+
+        m.set_array(
+            xmp_ns::DC,
+            "subject",
+            ArrayForm::Bag,
+            &["sunset".into(), "beach".into()],
+        )
+        .unwrap();
+
+        assert_eq!(
+            summary(&events),
+            [
+                (ChangeKind::Delete, "subject".to_owned()),
+                (ChangeKind::Set, "subject".to_owned()),
+                (ChangeKind::Append, "subject[1]".to_owned()),
+                (ChangeKind::Append, "subject[2]".to_owned()),
+            ]
+        );
+
+        // Listeners survive the commit.
+        m.set_property(xmp_ns::XMP, "Label", &"Red".into()).unwrap();
+        assert_eq!(events.lock().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn batch() {
+        let mut m = XmpMeta::new().unwrap();
+        let events = record(&mut m);
+
+        m.batch()
+            .set_property(xmp_ns::XMP, "CreatorTool", &"Example".into())
+            .property(xmp_ns::XMP, "CreatorTool")
+            .delete_property(xmp_ns::XMP, "CreatorTool")
+            .execute()
+            .unwrap();
+
+        assert_eq!(
+            summary(&events),
+            [
+                (ChangeKind::Set, "CreatorTool".to_owned()),
+                (ChangeKind::Delete, "CreatorTool".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn no_event_on_error() {
+        let mut m = XmpMeta::new().unwrap();
+        let events = record(&mut m);
+
+        assert!(m
+            .set_property("", "CreatorTool", &"Example".into())
+            .is_err());
+        assert!(m
+            .convert_array_form(xmp_ns::DC, "subject", ArrayForm::Seq)
+            .is_err());
+
+        assert!(events.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn not_cloned() {
+        let mut m = XmpMeta::new().unwrap();
+        let events = record(&mut m);
+
+        let mut clone = m.clone();
+        clone
+            .set_property(xmp_ns::XMP, "CreatorTool", &"Example".into())
+            .unwrap();
+
+        assert!(events.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn clear_change_listeners() {
+        let mut m = XmpMeta::new().unwrap();
+        let events = record(&mut m);
+
+        m.clear_change_listeners();
+        m.set_property(xmp_ns::XMP, "CreatorTool", &"Example".into())
+            .unwrap();
+
+        assert!(events.lock().unwrap().is_empty());
+    }
+}
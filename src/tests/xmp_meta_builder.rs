@@ -0,0 +1,79 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use crate::{xmp_ns, XmpDateTime, XmpErrorType, XmpMeta};
+
+#[test]
+fn empty() {
+    let meta = XmpMeta::builder().build().unwrap();
+    assert_eq!(meta.iter(Default::default()).count(), 0);
+}
+
+#[test]
+fn all_properties() {
+    let date: XmpDateTime = "2026-05-01T18:30:00Z".parse().unwrap();
+
+    let meta = XmpMeta::builder()
+        .creator("Alice")
+        .creator("Bob")
+        .title("en-US", "Sunset")
+        .title("de-DE", "Sonnenuntergang")
+        .create_date(date.clone())
+        .property(xmp_ns::XMP, "CreatorTool", "Example".to_owned())
+        .property(xmp_ns::XMP, "Rating", 4)
+        .build()
+        .unwrap();
+
+    let creators: Vec<String> = meta
+        .property_array(xmp_ns::DC, "creator")
+        .map(|item| item.value)
+        .collect();
+    assert_eq!(creators, ["Alice", "Bob"]);
+
+    let (title, lang) = meta
+        .localized_text(xmp_ns::DC, "title", None, "x-default")
+        .unwrap();
+    assert_eq!(title.value, "Sunset");
+    assert_eq!(lang, "x-default");
+
+    let (title, _) = meta
+        .localized_text(xmp_ns::DC, "title", None, "de-DE")
+        .unwrap();
+    assert_eq!(title.value, "Sonnenuntergang");
+
+    assert_eq!(
+        meta.get::<XmpDateTime>(xmp_ns::XMP, "CreateDate")
+            .unwrap()
+            .value,
+        date
+    );
+
+    assert_eq!(
+        meta.property(xmp_ns::XMP, "CreatorTool").unwrap().value,
+        "Example"
+    );
+    assert_eq!(meta.get::<i32>(xmp_ns::XMP, "Rating").unwrap().value, 4);
+}
+
+#[test]
+fn first_error_is_reported() {
+    let err = XmpMeta::builder()
+        .creator("Alice")
+        .property("", "Marked", true)
+        .property(xmp_ns::XMP, "", 1)
+        .build()
+        .unwrap_err();
+
+    assert_eq!(err.error_type, XmpErrorType::BadSchema);
+    assert_eq!(err.debug_message, "Empty schema namespace URI");
+}
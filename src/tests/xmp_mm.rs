@@ -0,0 +1,147 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::str::FromStr;
+
+use crate::{
+    xmp_mm::{self, ResourceEvent, Version},
+    xmp_ns, XmpDateTime, XmpErrorType, XmpMeta,
+};
+
+const HISTORY: &str = r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <rdf:Description rdf:about=""
+        xmlns:xmpMM="http://ns.adobe.com/xap/1.0/mm/"
+        xmlns:stVer="http://ns.adobe.com/xap/1.0/sType/Version#"
+        xmlns:stEvt="http://ns.adobe.com/xap/1.0/sType/ResourceEvent#">
+        <xmpMM:Versions>
+            <rdf:Seq>
+                <rdf:li rdf:parseType="Resource">
+                    <stVer:version>1</stVer:version>
+                    <stVer:comments>First draft</stVer:comments>
+                    <stVer:modifier>Alice</stVer:modifier>
+                    <stVer:modifyDate>2026-03-01T10:00:00Z</stVer:modifyDate>
+                    <stVer:event rdf:parseType="Resource">
+                        <stEvt:action>created</stEvt:action>
+                        <stEvt:instanceID>xmp.iid:0001</stEvt:instanceID>
+                        <stEvt:when>2026-03-01T10:00:00Z</stEvt:when>
+                        <stEvt:softwareAgent>Example Editor 1.0</stEvt:softwareAgent>
+                    </stVer:event>
+                </rdf:li>
+                <rdf:li rdf:parseType="Resource">
+                    <stVer:version>2</stVer:version>
+                    <stVer:modifyDate>not a date</stVer:modifyDate>
+                </rdf:li>
+            </rdf:Seq>
+        </xmpMM:Versions>
+    </rdf:Description>
+</rdf:RDF>"#;
+
+fn first_version() -> Version {
+    Version {
+        comments: Some("First draft".to_owned()),
+        event: Some(ResourceEvent {
+            action: Some("created".to_owned()),
+            instance_id: Some("xmp.iid:0001".to_owned()),
+            software_agent: Some("Example Editor 1.0".to_owned()),
+            when: Some(XmpDateTime::from_str("2026-03-01T10:00:00Z").unwrap()),
+            ..Default::default()
+        }),
+        modifier: Some("Alice".to_owned()),
+        modify_date: Some(XmpDateTime::from_str("2026-03-01T10:00:00Z").unwrap()),
+        version: Some("1".to_owned()),
+    }
+}
+
+#[test]
+fn versions() {
+    let m = XmpMeta::from_str(HISTORY).unwrap();
+
+    assert_eq!(
+        xmp_mm::versions(&m),
+        [
+            first_version(),
+            Version {
+                version: Some("2".to_owned()),
+                ..Default::default()
+            }
+        ]
+    );
+}
+
+#[test]
+fn versions_empty() {
+    let m = XmpMeta::new().unwrap();
+    assert!(xmp_mm::versions(&m).is_empty());
+}
+
+#[test]
+fn append_version() {
+    let mut m = XmpMeta::new().unwrap();
+
+    let second = Version {
+        version: Some("2".to_owned()),
+        comments: Some("Color corrected".to_owned()),
+        event: Some(ResourceEvent {
+            action: Some("saved".to_owned()),
+            changed: Some("/metadata".to_owned()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    xmp_mm::append_version(&mut m, &first_version()).unwrap();
+    xmp_mm::append_version(&mut m, &second).unwrap();
+
+    assert_eq!(xmp_mm::versions(&m), [first_version(), second]);
+
+    let versions = m.property(xmp_ns::XMP_MM, "Versions").unwrap();
+    assert!(versions.is_array());
+    assert!(versions.is_ordered());
+
+    assert_eq!(
+        m.property(xmp_ns::XMP_MM, "Versions[2]/stVer:event/stEvt:changed")
+            .unwrap()
+            .value,
+        "/metadata"
+    );
+}
+
+#[test]
+fn append_to_existing() {
+    let mut m = XmpMeta::from_str(HISTORY).unwrap();
+
+    xmp_mm::append_version(
+        &mut m,
+        &Version {
+            version: Some("3".to_owned()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let versions = xmp_mm::versions(&m);
+    assert_eq!(versions.len(), 3);
+    assert_eq!(versions[2].version.as_deref(), Some("3"));
+    assert_eq!(versions[2].event, None);
+}
+
+#[test]
+fn append_version_init_fail() {
+    let mut m = XmpMeta::new_fail();
+    assert_eq!(
+        xmp_mm::append_version(&mut m, &first_version())
+            .unwrap_err()
+            .error_type,
+        XmpErrorType::NoCppToolkit
+    );
+}
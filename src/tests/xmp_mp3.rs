@@ -0,0 +1,120 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use crate::{tests::fixtures::*, xmp_ns, FileFormat, OpenFileOptions, XmpFile, XmpMeta};
+
+/// Stand-in for MPEG audio frames. Not valid audio.
+const AUDIO: &[u8] = &[0xff, 0xfb, 0x90, 0x64, 1, 2, 3, 4];
+
+fn syncsafe(size: usize) -> [u8; 4] {
+    [
+        (size >> 21) as u8 & 0x7f,
+        (size >> 14) as u8 & 0x7f,
+        (size >> 7) as u8 & 0x7f,
+        size as u8 & 0x7f,
+    ]
+}
+
+fn frame(version: u8, id: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut frame = id.to_vec();
+    if version == 3 {
+        frame.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    } else {
+        frame.extend_from_slice(&syncsafe(data.len()));
+    }
+    frame.extend_from_slice(&[0, 0]);
+    frame.extend_from_slice(data);
+    frame
+}
+
+/// Returns an MP3 file whose ID3v2 tag contains `frames` followed by
+/// `padding` zero bytes.
+fn mp3(version: u8, frames: &[Vec<u8>], padding: usize) -> Vec<u8> {
+    let body: Vec<u8> = frames.concat();
+
+    let mut file = b"ID3".to_vec();
+    file.extend_from_slice(&[version, 0, 0]);
+    file.extend_from_slice(&syncsafe(body.len() + padding));
+    file.extend_from_slice(&body);
+    file.resize(file.len() + padding, 0);
+    file.extend_from_slice(AUDIO);
+    file
+}
+
+fn title_frame(version: u8) -> Vec<u8> {
+    frame(version, b"TIT2", b"\0Episode 1")
+}
+
+fn xmp_frame(version: u8, label: &str) -> Vec<u8> {
+    let mut data = b"XMP\0".to_vec();
+    data.extend_from_slice(meta_with_label(label).to_string().as_bytes());
+    frame(version, b"PRIV", &data)
+}
+
+fn unreconciled() -> OpenFileOptions {
+    OpenFileOptions::default().reconcile_id3(false)
+}
+
+fn xmp(mp3: &[u8]) -> Option<XmpMeta> {
+    let mut f = XmpFile::from_bytes(mp3, FileFormat::Mp3, unreconciled()).unwrap();
+    f.xmp()
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+#[test]
+fn read_xmp_without_reconciliation() {
+    let mp3 = mp3(3, &[title_frame(3), xmp_frame(3, "Embedded")], 0);
+
+    let xmp = xmp(&mp3).unwrap();
+    assert_eq!(
+        xmp.property(xmp_ns::XMP, "Label").unwrap().value,
+        "Embedded"
+    );
+
+    // The title frame is not imported.
+    assert!(!xmp.contains_property(xmp_ns::DC, "title"));
+}
+
+#[test]
+fn no_xmp() {
+    assert!(xmp(&mp3(3, &[title_frame(3)], 0)).is_none());
+}
+
+#[test]
+fn other_frames_are_kept() {
+    let original = mp3(3, &[title_frame(3), xmp_frame(3, "First")], 0);
+    let updated = put_label_with_options(&original, "Second", unreconciled());
+
+    assert!(contains(&updated, &title_frame(3)));
+    assert!(updated.ends_with(AUDIO));
+    assert_eq!(
+        label_with_options(&updated, FileFormat::Mp3, unreconciled()).as_deref(),
+        Some("Second")
+    );
+}
+
+#[test]
+fn id3v24() {
+    let original = mp3(4, &[title_frame(4), xmp_frame(4, "First")], 0);
+    let updated = put_label_with_options(&original, "Second", unreconciled());
+
+    assert!(updated.starts_with(b"ID3\x04\0\0"));
+    assert!(contains(&updated, &title_frame(4)));
+    assert_eq!(
+        label_with_options(&updated, FileFormat::Mp3, unreconciled()).as_deref(),
+        Some("Second")
+    );
+}
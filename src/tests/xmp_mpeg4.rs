@@ -0,0 +1,208 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::{fs, io::Cursor};
+
+use tempfile::tempdir;
+
+use crate::{
+    tests::fixtures::*,
+    xmp_dm::{self, TimecodeFormat},
+    xmp_ns, FileFormat, OpenFileOptions, XmpFile, XmpMeta,
+};
+
+fn bmff(box_type: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+    let mut b = ((payload.len() + 8) as u32).to_be_bytes().to_vec();
+    b.extend_from_slice(box_type);
+    b.extend_from_slice(payload);
+    b
+}
+
+fn ftyp(brand: &[u8; 4]) -> Vec<u8> {
+    bmff(b"ftyp", &[&brand[..], &[0; 4]].concat())
+}
+
+/// Returns a QuickTime movie with a title (`©nam`) and, in a
+/// `moov/udta/XMP_` box, XMP with the given label.
+fn movie_with_title(label: &str) -> Vec<u8> {
+    let title = [&[0, 5, 0, 0][..], b"Title"].concat();
+    let udta = bmff(
+        b"udta",
+        &[
+            bmff(b"\xa9nam", &title),
+            bmff(b"XMP_", meta_with_label(label).to_string().as_bytes()),
+        ]
+        .concat(),
+    );
+
+    [
+        ftyp(b"qt  "),
+        bmff(b"mdat", &[1, 2, 3, 4]),
+        bmff(b"moov", &[bmff(b"mvhd", &[0; 100]), udta].concat()),
+    ]
+    .concat()
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+/// Returns a QuickTime movie whose only track is a timecode track with the
+/// given frame rate, starting at `frame`.
+fn movie_with_timecode(
+    time_scale: u32,
+    frame_duration: u32,
+    fps: u8,
+    flags: u32,
+    frame: u32,
+) -> Vec<u8> {
+    let ftyp = ftyp(b"qt  ");
+    let mdat = bmff(b"mdat", &frame.to_be_bytes());
+    let sample_offset = (ftyp.len() + 8) as u32;
+
+    let mut entry = 34u32.to_be_bytes().to_vec();
+    entry.extend_from_slice(b"tmcd");
+    entry.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0]);
+    entry.extend_from_slice(&flags.to_be_bytes());
+    entry.extend_from_slice(&time_scale.to_be_bytes());
+    entry.extend_from_slice(&frame_duration.to_be_bytes());
+    entry.extend_from_slice(&[fps, 0]);
+
+    let stsd = bmff(b"stsd", &[&[0, 0, 0, 0, 0, 0, 0, 1][..], &entry].concat());
+    let stco = bmff(
+        b"stco",
+        &[&[0, 0, 0, 0, 0, 0, 0, 1][..], &sample_offset.to_be_bytes()].concat(),
+    );
+    let stbl = bmff(b"stbl", &[stsd, stco].concat());
+    let minf = bmff(b"minf", &stbl);
+    let hdlr = bmff(b"hdlr", &[&[0; 8][..], b"tmcd", &[0; 13]].concat());
+    let mdia = bmff(b"mdia", &[hdlr, minf].concat());
+    let moov = bmff(b"moov", &bmff(b"trak", &mdia));
+
+    [ftyp, mdat, moov].concat()
+}
+
+fn options() -> OpenFileOptions {
+    OpenFileOptions::default().reconcile_quicktime(false)
+}
+
+fn xmp(file: &[u8], format: FileFormat) -> Option<XmpMeta> {
+    XmpFile::from_bytes(file, format, options()).unwrap().xmp()
+}
+
+#[test]
+fn read_xmp_without_reconciliation() {
+    let xmp = xmp(&movie_with_title("Embedded"), FileFormat::Mov).unwrap();
+
+    assert_eq!(
+        xmp.property(xmp_ns::XMP, "Label").unwrap().value,
+        "Embedded"
+    );
+
+    // The title is not imported.
+    assert!(!xmp.contains_property(xmp_ns::DC, "title"));
+}
+
+#[test]
+fn update_keeps_other_metadata() {
+    let original = movie_with_title("First");
+    let updated = put_label_with_options(&original, "Second", options());
+
+    assert!(contains(&updated, b"\xa9nam\0\x05\0\0Title"));
+    assert!(contains(&updated, &bmff(b"mdat", &[1, 2, 3, 4])));
+    assert_eq!(
+        label_with_options(&updated, FileFormat::Mov, options()).as_deref(),
+        Some("Second")
+    );
+}
+
+#[test]
+fn mpeg4_add_xmp() {
+    let original = [
+        ftyp(b"isom"),
+        bmff(b"moov", &bmff(b"mvhd", &[0; 100])),
+        bmff(b"mdat", &[1, 2, 3, 4]),
+    ]
+    .concat();
+
+    assert_eq!(
+        label_with_options(&original, FileFormat::Mpeg4, options()),
+        None
+    );
+
+    let updated = put_label_with_options(&original, "Added", options());
+    assert_eq!(
+        label_with_options(&updated, FileFormat::Mpeg4, options()).as_deref(),
+        Some("Added")
+    );
+
+    let updated = put_label_with_options(&updated, "Replaced", options());
+    assert_eq!(
+        label_with_options(&updated, FileFormat::Mpeg4, options()).as_deref(),
+        Some("Replaced")
+    );
+}
+
+#[test]
+fn timecode() {
+    // 29.97 fps, drop-frame.
+    let movie = movie_with_timecode(30000, 1001, 30, 1, 107892);
+
+    let timecode = xmp_dm::start_timecode(&xmp(&movie, FileFormat::Mov).unwrap()).unwrap();
+
+    assert_eq!(timecode.time_value(), "01;00;00;00");
+    assert_eq!(timecode.time_format(), TimecodeFormat::Fps2997Drop);
+
+    // 25 fps.
+    let movie = movie_with_timecode(25, 1, 25, 0, 25 * 61 + 3);
+
+    let timecode = xmp_dm::start_timecode(&xmp(&movie, FileFormat::Mov).unwrap()).unwrap();
+
+    assert_eq!(timecode.time_value(), "00:01:01:03");
+    assert_eq!(timecode.time_format(), TimecodeFormat::Fps25);
+}
+
+#[test]
+fn timecode_from_file() {
+    let tempdir = tempdir().unwrap();
+    let path = tempdir.path().join("movie.mov");
+    fs::write(&path, movie_with_timecode(25, 1, 25, 0, 25 * 3600)).unwrap();
+
+    let mut f = XmpFile::new().unwrap();
+    f.open_file(&path, options()).unwrap();
+
+    let timecode = xmp_dm::start_timecode(&f.xmp().unwrap()).unwrap();
+    assert_eq!(timecode.time_value(), "01:00:00:00");
+}
+
+#[test]
+fn timecode_from_stream() {
+    let movie = movie_with_timecode(25, 1, 25, 0, 25 * 3600);
+
+    let mut f = XmpFile::new().unwrap();
+    f.open_stream(Cursor::new(movie), FileFormat::Mov, options())
+        .unwrap();
+
+    let timecode = xmp_dm::start_timecode(&f.xmp().unwrap()).unwrap();
+    assert_eq!(timecode.time_value(), "01:00:00:00");
+}
+
+#[test]
+fn timecode_turned_off() {
+    let movie = movie_with_timecode(25, 1, 25, 0, 0);
+
+    let mut f =
+        XmpFile::from_bytes(&movie, FileFormat::Mov, options().quicktime_timecode(false)).unwrap();
+
+    assert!(f.xmp().is_none());
+}
@@ -0,0 +1,229 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::str::FromStr;
+
+use crate::{
+    xmp_mwg::{self, NativeMetadata},
+    xmp_ns, XmpDateTime, XmpMeta,
+};
+
+const XMP: &str = r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <rdf:Description rdf:about=""
+        xmlns:dc="http://purl.org/dc/elements/1.1/"
+        xmlns:photoshop="http://ns.adobe.com/photoshop/1.0/"
+        xmlns:tiff="http://ns.adobe.com/tiff/1.0/">
+        <dc:description>
+            <rdf:Alt>
+                <rdf:li xml:lang="x-default">XMP description</rdf:li>
+            </rdf:Alt>
+        </dc:description>
+        <dc:creator>
+            <rdf:Seq>
+                <rdf:li>XMP Creator</rdf:li>
+            </rdf:Seq>
+        </dc:creator>
+        <dc:subject>
+            <rdf:Bag>
+                <rdf:li>xmp</rdf:li>
+            </rdf:Bag>
+        </dc:subject>
+        <photoshop:DateCreated>2026-03-01T10:15:30.25+01:00</photoshop:DateCreated>
+        <photoshop:LegacyIPTCDigest>0123456789ABCDEF0123456789ABCDEF</photoshop:LegacyIPTCDigest>
+        <tiff:Orientation>1</tiff:Orientation>
+    </rdf:Description>
+</rdf:RDF>"#;
+
+fn native(exif: &[(&str, &str)], iptc: &[(&str, &[&str])]) -> NativeMetadata {
+    NativeMetadata {
+        exif: exif
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+        iptc: iptc
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.iter().map(|s| s.to_string()).collect()))
+            .collect(),
+        iptc_digest: None,
+    }
+}
+
+fn date(s: &str) -> Option<XmpDateTime> {
+    Some(XmpDateTime::from_str(s).unwrap())
+}
+
+#[test]
+fn xmp_only() {
+    let m = XmpMeta::from_str(XMP).unwrap();
+    let r = xmp_mwg::reconcile(&m, &NativeMetadata::default());
+
+    assert_eq!(r.date, date("2026-03-01T10:15:30.25+01:00"));
+    assert_eq!(r.description.as_deref(), Some("XMP description"));
+    assert_eq!(r.keywords, ["xmp"]);
+    assert_eq!(r.creator, ["XMP Creator"]);
+    assert_eq!(r.orientation, Some(1));
+}
+
+#[test]
+fn native_only() {
+    let m = XmpMeta::new().unwrap();
+    let n = native(
+        &[("Artist", "Alice; Bob;"), ("Orientation", "6")],
+        &[
+            ("DateCreated", &["20260301"]),
+            ("TimeCreated", &["101530-0500"]),
+            ("Caption-Abstract", &["IIM caption"]),
+            ("Keywords", &["one", "two"]),
+        ],
+    );
+
+    let r = xmp_mwg::reconcile(&m, &n);
+
+    assert_eq!(r.date, date("2026-03-01T10:15:30-05:00"));
+    assert_eq!(r.description.as_deref(), Some("IIM caption"));
+    assert_eq!(r.keywords, ["one", "two"]);
+    assert_eq!(r.creator, ["Alice", "Bob"]);
+    assert_eq!(r.orientation, Some(6));
+}
+
+#[test]
+fn exif_preferred() {
+    let m = XmpMeta::from_str(XMP).unwrap();
+    let n = native(
+        &[
+            ("DateTimeOriginal", "2026:04:02 08:00:00"),
+            ("SubSecTimeOriginal", "5"),
+            ("OffsetTimeOriginal", "+02:00"),
+            ("ImageDescription", "Exif description"),
+            ("Artist", "Exif Artist"),
+            ("Orientation", "8"),
+        ],
+        &[],
+    );
+
+    let r = xmp_mwg::reconcile(&m, &n);
+
+    assert_eq!(r.date, date("2026-04-02T08:00:00.5+02:00"));
+    assert_eq!(r.description.as_deref(), Some("Exif description"));
+    assert_eq!(r.creator, ["Exif Artist"]);
+    assert_eq!(r.orientation, Some(8));
+}
+
+#[test]
+fn exif_date_matches_xmp() {
+    let m = XmpMeta::from_str(XMP).unwrap();
+    let n = native(&[("DateTimeOriginal", "2026:03:01 10:15:30")], &[]);
+
+    assert_eq!(xmp_mwg::date(&m, &n), date("2026-03-01T10:15:30.25+01:00"));
+}
+
+#[test]
+fn exif_ignored_if_empty_or_invalid() {
+    let m = XmpMeta::from_str(XMP).unwrap();
+    let n = native(
+        &[
+            ("DateTimeOriginal", "0000:00:00 00:00:00"),
+            ("ImageDescription", "   "),
+            ("Artist", ""),
+            ("Orientation", "9"),
+        ],
+        &[],
+    );
+
+    let r = xmp_mwg::reconcile(&m, &n);
+
+    assert_eq!(r.date, date("2026-03-01T10:15:30.25+01:00"));
+    assert_eq!(r.description.as_deref(), Some("XMP description"));
+    assert_eq!(r.creator, ["XMP Creator"]);
+    assert_eq!(r.orientation, Some(1));
+}
+
+#[test]
+fn iptc_unchanged() {
+    let m = XmpMeta::from_str(XMP).unwrap();
+    let mut n = native(
+        &[],
+        &[
+            ("Caption-Abstract", &["IIM caption"]),
+            ("By-line", &["IIM By-line"]),
+            ("Keywords", &["iim"]),
+        ],
+    );
+
+    // No digest: XMP is preferred.
+    assert_eq!(
+        xmp_mwg::description(&m, &n).as_deref(),
+        Some("XMP description")
+    );
+
+    // Matching digest (case is not significant).
+    n.iptc_digest = Some("0123456789abcdef0123456789abcdef".to_owned());
+    let r = xmp_mwg::reconcile(&m, &n);
+
+    assert_eq!(r.description.as_deref(), Some("XMP description"));
+    assert_eq!(r.creator, ["XMP Creator"]);
+    assert_eq!(r.keywords, ["xmp"]);
+}
+
+#[test]
+fn iptc_changed() {
+    let m = XmpMeta::from_str(XMP).unwrap();
+    let mut n = native(
+        &[],
+        &[
+            ("DateCreated", &["20250101"]),
+            ("Caption-Abstract", &["IIM caption"]),
+            ("By-line", &["IIM By-line"]),
+            ("Keywords", &["iim"]),
+        ],
+    );
+    n.iptc_digest = Some("FEDCBA9876543210FEDCBA9876543210".to_owned());
+
+    let r = xmp_mwg::reconcile(&m, &n);
+
+    assert_eq!(r.date, date("2025-01-01"));
+    assert_eq!(r.description.as_deref(), Some("IIM caption"));
+    assert_eq!(r.creator, ["IIM By-line"]);
+    assert_eq!(r.keywords, ["iim"]);
+
+    // XMP is still used for values that are absent from IIM.
+    assert_eq!(r.orientation, Some(1));
+}
+
+#[test]
+fn nothing() {
+    let m = XmpMeta::new().unwrap();
+    let r = xmp_mwg::reconcile(&m, &NativeMetadata::default());
+
+    assert_eq!(r.date, None);
+    assert_eq!(r.description, None);
+    assert!(r.keywords.is_empty());
+    assert!(r.creator.is_empty());
+    assert_eq!(r.orientation, None);
+}
+
+#[test]
+fn exif_xmp_fallback() {
+    let mut m = XmpMeta::new().unwrap();
+    m.set_property(
+        xmp_ns::EXIF,
+        "DateTimeOriginal",
+        &"2026-05-06T07:08:09Z".into(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        xmp_mwg::date(&m, &NativeMetadata::default()),
+        date("2026-05-06T07:08:09Z")
+    );
+}
@@ -0,0 +1,160 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::fs;
+
+use tempfile::tempdir;
+
+use crate::{
+    tests::fixtures::*, xmp_ns, FileFormat, OpenFileOptions, PutXmpBlocker, ToStringOptions,
+    XmpErrorType, XmpFile,
+};
+
+/// Returns an MXF file that starts with a header partition pack followed
+/// by `body`. The partition pack's content is not valid.
+fn mxf(body: &[u8]) -> Vec<u8> {
+    let mut file = vec![
+        0x06, 0x0e, 0x2b, 0x34, 0x02, 0x05, 0x01, 0x01, 0x0d, 0x01, 0x02, 0x01, 0x01, 0x02, 0x04,
+        0x00,
+    ];
+
+    // BER-encoded length of the pack.
+    file.extend_from_slice(&[0x83, 0x00, 0x00, 0x58]);
+    file.extend_from_slice(&[0; 0x58]);
+
+    file.extend_from_slice(body);
+    file.extend_from_slice(&[0; 64]);
+    file
+}
+
+fn packet(label: &str) -> String {
+    meta_with_label(label)
+        .to_string_with_options(ToStringOptions::default().set_padding(2048))
+        .unwrap()
+}
+
+fn label(f: &mut XmpFile) -> String {
+    f.xmp()
+        .unwrap()
+        .property(xmp_ns::XMP, "Label")
+        .unwrap()
+        .value
+}
+
+#[test]
+fn check_format() {
+    let file = mxf(b"");
+    assert_eq!(
+        XmpFile::check_buffer_format(&file).unwrap(),
+        Some(FileFormat::Mxf)
+    );
+
+    let tempdir = tempdir().unwrap();
+    let path = tempdir.path().join("clip.mxf");
+    fs::write(&path, &file).unwrap();
+
+    assert_eq!(
+        XmpFile::check_file_format(&path).unwrap(),
+        Some(FileFormat::Mxf)
+    );
+    assert_eq!(XmpFile::associated_resources(&path).unwrap(), [path]);
+}
+
+#[test]
+fn read_xmp() {
+    let file = mxf(packet("original").as_bytes());
+
+    let mut f =
+        XmpFile::from_bytes(&file, FileFormat::Unknown, OpenFileOptions::default()).unwrap();
+    assert_eq!(label(&mut f), "original");
+    f.close().unwrap();
+}
+
+#[test]
+fn read_bare_xmpmeta() {
+    let xmp = meta_with_label("bare")
+        .to_string_with_options(ToStringOptions::default().omit_packet_wrapper())
+        .unwrap();
+    let file = mxf(xmp.as_bytes());
+
+    let mut f = XmpFile::from_bytes(&file, FileFormat::Mxf, OpenFileOptions::default()).unwrap();
+    assert_eq!(label(&mut f), "bare");
+    f.close().unwrap();
+}
+
+#[test]
+fn update_in_place() {
+    let file = mxf(packet("original").as_bytes());
+
+    let mut f = XmpFile::from_bytes(
+        &file,
+        FileFormat::Unknown,
+        OpenFileOptions::default().for_update(),
+    )
+    .unwrap();
+
+    let m = meta_with_label("updated");
+    assert_eq!(f.put_xmp_blocker(&m), None);
+    f.put_xmp(&m).unwrap();
+
+    let updated = f.into_bytes().unwrap();
+    assert_eq!(updated.len(), file.len());
+    assert_eq!(updated[..100], file[..100]);
+
+    let mut f =
+        XmpFile::from_bytes(&updated, FileFormat::Unknown, OpenFileOptions::default()).unwrap();
+    assert_eq!(label(&mut f), "updated");
+    f.close().unwrap();
+}
+
+#[test]
+fn no_packet() {
+    let file = mxf(b"");
+
+    let mut f = XmpFile::from_bytes(
+        &file,
+        FileFormat::Unknown,
+        OpenFileOptions::default().for_update(),
+    )
+    .unwrap();
+    assert!(f.xmp().is_none());
+
+    let m = meta_with_label("new");
+    assert_eq!(f.put_xmp_blocker(&m), Some(PutXmpBlocker::ReadOnlyFormat));
+    assert_eq!(
+        f.put_xmp(&m).unwrap_err().error_type,
+        XmpErrorType::XmpUpdateNotInPlace
+    );
+    f.close().unwrap();
+}
+
+#[test]
+fn sidecar_fallback() {
+    let tempdir = tempdir().unwrap();
+    let path = tempdir.path().join("clip.mxf");
+    let file = mxf(b"");
+    fs::write(&path, &file).unwrap();
+
+    let mut f = XmpFile::new().unwrap();
+    f.open_file(
+        &path,
+        OpenFileOptions::default().for_update().sidecar_fallback(),
+    )
+    .unwrap();
+
+    f.put_xmp(&meta_with_label("sidecar")).unwrap();
+    f.close().unwrap();
+
+    assert_eq!(fs::read(&path).unwrap(), file);
+    assert!(tempdir.path().join("clip.xmp").exists());
+}
@@ -0,0 +1,237 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::fs;
+
+use tempfile::tempdir;
+
+use crate::{tests::fixtures::*, xmp_ns, FileFormat, OpenFileOptions, XmpFile, XmpMeta};
+
+const SERIAL: u32 = 0x1234;
+
+fn crc(data: &[u8]) -> u32 {
+    data.iter().fold(0u32, |mut crc, &b| {
+        crc ^= (b as u32) << 24;
+        for _ in 0..8 {
+            crc = if crc & 0x8000_0000 != 0 {
+                (crc << 1) ^ 0x04c1_1db7
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+fn page(header_type: u8, seq: u32, packets: &[&[u8]]) -> Vec<u8> {
+    let mut lacing = Vec::new();
+    let mut data = Vec::new();
+
+    for packet in packets {
+        lacing.resize(lacing.len() + packet.len() / 255, 255);
+        lacing.push((packet.len() % 255) as u8);
+        data.extend_from_slice(packet);
+    }
+
+    let mut page = b"OggS\0".to_vec();
+    page.push(header_type);
+    page.extend_from_slice(&[0; 8]);
+    page.extend_from_slice(&SERIAL.to_le_bytes());
+    page.extend_from_slice(&seq.to_le_bytes());
+    page.extend_from_slice(&[0; 4]);
+    page.push(lacing.len() as u8);
+    page.extend_from_slice(&lacing);
+    page.extend_from_slice(&data);
+
+    let crc = crc(&page);
+    page[22..26].copy_from_slice(&crc.to_le_bytes());
+    page
+}
+
+fn comment_header(prefix: &[u8], comments: &[&str], trailer: &[u8]) -> Vec<u8> {
+    let mut data = prefix.to_vec();
+    data.extend_from_slice(&6u32.to_le_bytes());
+    data.extend_from_slice(b"vendor");
+    data.extend_from_slice(&(comments.len() as u32).to_le_bytes());
+
+    for comment in comments {
+        data.extend_from_slice(&(comment.len() as u32).to_le_bytes());
+        data.extend_from_slice(comment.as_bytes());
+    }
+
+    data.extend_from_slice(trailer);
+    data
+}
+
+/// Returns an Ogg Vorbis file with the given comments. The audio data is
+/// not valid.
+fn vorbis(comments: &[&str]) -> Vec<u8> {
+    let comments = comment_header(b"\x03vorbis", comments, b"\x01");
+
+    let mut file = page(0x02, 0, &[b"\x01vorbis identification"]);
+    file.extend(page(0, 1, &[&comments, b"\x05vorbis setup"]));
+    file.extend(page(0, 2, &[b"audio 1"]));
+    file.extend(page(0x04, 3, &[b"audio 2"]));
+    file
+}
+
+/// Checks the sequence numbers and checksums of the pages in `file`, and
+/// returns the number of pages.
+fn check_pages(file: &[u8]) -> u32 {
+    let mut pos = 0;
+    let mut seq = 0;
+
+    while pos < file.len() {
+        let segments = file[pos + 26] as usize;
+        let data_len: usize = file[pos + 27..pos + 27 + segments]
+            .iter()
+            .map(|&len| len as usize)
+            .sum();
+
+        let mut page = file[pos..pos + 27 + segments + data_len].to_vec();
+        assert_eq!(
+            u32::from_le_bytes(page[18..22].try_into().unwrap()),
+            seq,
+            "sequence number"
+        );
+
+        let stored = u32::from_le_bytes(page[22..26].try_into().unwrap());
+        page[22..26].fill(0);
+        assert_eq!(crc(&page), stored, "checksum of page {seq}");
+
+        pos += page.len();
+        seq += 1;
+    }
+
+    seq
+}
+
+fn put_xmp(ogg: &[u8], meta: &XmpMeta) -> Vec<u8> {
+    let mut f = XmpFile::from_bytes(
+        ogg,
+        FileFormat::Unknown,
+        OpenFileOptions::default().for_update(),
+    )
+    .unwrap();
+
+    f.put_xmp(meta).unwrap();
+    f.into_bytes().unwrap()
+}
+
+#[test]
+fn check_format() {
+    assert_eq!(
+        XmpFile::check_buffer_format(&vorbis(&[])).unwrap(),
+        Some(FileFormat::Ogg)
+    );
+}
+
+#[test]
+fn no_xmp() {
+    assert_eq!(label(&vorbis(&["TITLE=Track"]), FileFormat::Ogg), None);
+}
+
+#[test]
+fn add_xmp() {
+    let original = vorbis(&["TITLE=Track"]);
+    let updated = put_xmp(&original, &meta_with_label("Added"));
+
+    assert_eq!(check_pages(&updated), 4);
+    assert!(updated.ends_with(&page(0x04, 3, &[b"audio 2"])));
+    assert_eq!(label(&updated, FileFormat::Ogg).as_deref(), Some("Added"));
+
+    // The packet isn't stored contiguously in general.
+    let mut f = XmpFile::from_bytes(&updated, FileFormat::Ogg, OpenFileOptions::default()).unwrap();
+    assert_eq!(f.packet_info().unwrap().offset, None);
+}
+
+#[test]
+fn large_xmp_spans_pages() {
+    let mut meta = meta_with_label("Large");
+    meta.set_property(xmp_ns::XMP, "Nickname", &"x".repeat(100_000).into())
+        .unwrap();
+
+    let updated = put_xmp(&vorbis(&[]), &meta);
+
+    // The comment header needs more than one page, so the audio pages
+    // are renumbered.
+    assert!(check_pages(&updated) > 4);
+    assert_eq!(label(&updated, FileFormat::Ogg).as_deref(), Some("Large"));
+
+    let updated = put_xmp(&updated, &meta_with_label("Small"));
+    assert_eq!(check_pages(&updated), 4);
+    assert_eq!(label(&updated, FileFormat::Ogg).as_deref(), Some("Small"));
+}
+
+#[test]
+fn opus() {
+    let comments = comment_header(b"OpusTags", &["title=Track"], b"extra data");
+
+    let mut original = page(0x02, 0, &[b"OpusHead\x01\x02\0\0\0\0\0\0\0\0\0"]);
+    original.extend(page(0, 1, &[&comments]));
+    original.extend(page(0x04, 2, &[b"audio"]));
+
+    let updated = put_xmp(&original, &meta_with_label("Opus"));
+
+    assert_eq!(check_pages(&updated), 3);
+    assert!(updated.windows(10).any(|w| w == b"extra data"));
+    assert_eq!(label(&updated, FileFormat::Ogg).as_deref(), Some("Opus"));
+}
+
+#[test]
+fn import_vorbis_comments() {
+    let ogg = vorbis(&["TITLE=Track", "ORGANIZATION=Label"]);
+
+    let mut f = XmpFile::from_bytes(
+        &ogg,
+        FileFormat::Ogg,
+        OpenFileOptions::default().import_vorbis_comments(),
+    )
+    .unwrap();
+
+    let xmp = f.xmp().unwrap();
+
+    let (title, _) = xmp
+        .localized_text(xmp_ns::DC, "title", None, "x-default")
+        .unwrap();
+    assert_eq!(title.value, "Track");
+
+    assert_eq!(
+        xmp.property(xmp_ns::DC, "publisher[1]").unwrap().value,
+        "Label"
+    );
+}
+
+#[test]
+fn unsupported_codec() {
+    let mut ogg = page(0x02, 0, &[b"\x80theora"]);
+    ogg.extend(page(0x04, 1, &[b"video"]));
+
+    assert!(XmpFile::from_bytes(&ogg, FileFormat::Ogg, OpenFileOptions::default()).is_err());
+}
+
+#[test]
+fn sample_file() {
+    let tempdir = tempdir().unwrap();
+    let sample = temp_copy_of_fixture(tempdir.path(), "sample.opus");
+
+    assert_eq!(label_of_file(&sample).as_deref(), Some("Sample"));
+
+    put_label_in_file(&sample, "Updated");
+    assert_eq!(label_of_file(&sample).as_deref(), Some("Updated"));
+
+    // The pages are still consistent, and the audio is unchanged.
+    let updated = fs::read(&sample).unwrap();
+    assert_eq!(check_pages(&updated), 3);
+    assert!(updated.ends_with(b"\xf8\xff\xfe"));
+}
@@ -0,0 +1,79 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+mod namespace_order {
+    use std::cmp::Ordering;
+
+    use crate::{xmp_ns, NamespaceOrder, OutputOrdering};
+
+    #[test]
+    fn listed_namespaces_first() {
+        let ordering = NamespaceOrder::default()
+            .namespace(xmp_ns::DC)
+            .namespace(xmp_ns::XMP);
+
+        assert_eq!(
+            ordering.compare_namespaces(xmp_ns::DC, xmp_ns::XMP),
+            Ordering::Less
+        );
+        assert_eq!(
+            ordering.compare_namespaces(xmp_ns::XMP, xmp_ns::DC),
+            Ordering::Greater
+        );
+        assert_eq!(
+            ordering.compare_namespaces(xmp_ns::XMP, xmp_ns::EXIF),
+            Ordering::Less
+        );
+        assert_eq!(
+            ordering.compare_namespaces(xmp_ns::EXIF, xmp_ns::DC),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn unlisted_namespaces_by_prefix() {
+        let ordering = NamespaceOrder::default();
+
+        // "exif" < "photoshop" < "tiff"
+        assert_eq!(
+            ordering.compare_namespaces(xmp_ns::EXIF, xmp_ns::PHOTOSHOP),
+            Ordering::Less
+        );
+        assert_eq!(
+            ordering.compare_namespaces(xmp_ns::TIFF, xmp_ns::PHOTOSHOP),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn properties() {
+        let ordering = NamespaceOrder::default().properties(xmp_ns::DC, &["title", "creator"]);
+
+        assert_eq!(
+            ordering.compare_properties(xmp_ns::DC, "creator", "title"),
+            Ordering::Greater
+        );
+        assert_eq!(
+            ordering.compare_properties(xmp_ns::DC, "title", "format"),
+            Ordering::Less
+        );
+        assert_eq!(
+            ordering.compare_properties(xmp_ns::DC, "format", "subject"),
+            Ordering::Equal
+        );
+        assert_eq!(
+            ordering.compare_properties(xmp_ns::XMP, "title", "creator"),
+            Ordering::Equal
+        );
+    }
+}
@@ -0,0 +1,204 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use crate::{tests::fixtures::*, xmp_ns, xmp_packet, XmpErrorType};
+
+const WRAPPED: &str = r#"<?xpacket begin="" id="W5M0MpCehiHzreSzNTczkc9d"?>
+<x:xmpmeta xmlns:x="adobe:ns:meta/">
+  <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <rdf:Description rdf:about="" xmlns:xmp="http://ns.adobe.com/xap/1.0/">
+      <xmp:Label>Old</xmp:Label>
+    </rdf:Description>
+  </rdf:RDF>
+</x:xmpmeta>
+<?xpacket end="r"?>"#;
+
+const UNWRAPPED: &str = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+  <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <rdf:Description rdf:about="" xmlns:xmp="http://ns.adobe.com/xap/1.0/">
+      <xmp:Label>New</xmp:Label>
+    </rdf:Description>
+  </rdf:RDF>
+</x:xmpmeta>"#;
+
+#[test]
+fn multiple_packets() {
+    let data = format!("%PDF-1.4\n{WRAPPED}\nbinary junk\n{UNWRAPPED}\n%%EOF");
+    let packets = xmp_packet::scan(data.as_bytes());
+
+    assert_eq!(packets.len(), 2);
+
+    let start = data.find("<?xpacket").unwrap();
+    assert_eq!(packets[0].range, start..start + WRAPPED.len());
+    assert_eq!(packets[0].text, WRAPPED);
+    assert_eq!(packets[0].writable, Some(false));
+
+    let start = data.rfind("<x:xmpmeta").unwrap();
+    assert_eq!(packets[1].range, start..start + UNWRAPPED.len());
+    assert_eq!(packets[1].text, UNWRAPPED);
+    assert_eq!(packets[1].writable, None);
+
+    let labels: Vec<String> = packets
+        .iter()
+        .map(|p| {
+            p.parse()
+                .unwrap()
+                .property(xmp_ns::XMP, "Label")
+                .unwrap()
+                .value
+        })
+        .collect();
+
+    assert_eq!(labels, ["Old", "New"]);
+}
+
+#[test]
+fn writable_packet() {
+    let data = WRAPPED.replace("end=\"r\"", "end=\"w\"");
+    let packets = xmp_packet::scan(data.as_bytes());
+
+    assert_eq!(packets.len(), 1);
+    assert_eq!(packets[0].writable, Some(true));
+}
+
+#[test]
+fn wrapper_without_trailer() {
+    let truncated = WRAPPED.replace("<?xpacket end=\"r\"?>", "");
+    let data = format!("{truncated}\n{WRAPPED}");
+    let packets = xmp_packet::scan(data.as_bytes());
+
+    // The `x:xmpmeta` element of the truncated packet is still found.
+    assert_eq!(packets.len(), 2);
+    assert_eq!(packets[0].writable, None);
+    assert_eq!(packets[1].text, WRAPPED);
+}
+
+#[test]
+fn ignores_similar_names() {
+    let data = "<x:xmpmetadata>not XMP</x:xmpmetadata>";
+    assert!(xmp_packet::scan(data.as_bytes()).is_empty());
+}
+
+#[test]
+fn unterminated_root_element() {
+    let data = "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\"><rdf:RDF";
+    assert!(xmp_packet::scan(data.as_bytes()).is_empty());
+}
+
+#[test]
+fn scan_file() {
+    let packets = xmp_packet::scan_file(fixture_path("Purple Square.psd")).unwrap();
+    assert!(!packets.is_empty());
+
+    let meta = packets[0].parse().unwrap();
+    assert_eq!(
+        meta.property(xmp_ns::XMP, "CreatorTool").unwrap().value,
+        "Adobe Photoshop CS2 Windows"
+    );
+}
+
+#[test]
+fn scan_file_no_xmp() {
+    let packets = xmp_packet::scan_file(fixture_path("no_xmp.txt")).unwrap();
+    assert!(packets.is_empty());
+}
+
+#[test]
+fn scan_file_missing() {
+    let err = xmp_packet::scan_file(fixture_path("doesnt_exist.jpg")).unwrap_err();
+    assert_eq!(err.error_type, XmpErrorType::ReadError);
+}
+
+mod repair {
+    use super::WRAPPED;
+    use crate::{
+        xmp_ns,
+        xmp_packet::{self, PacketRepair},
+    };
+
+    fn label(packet: &xmp_packet::RepairedPacket) -> String {
+        packet
+            .parse()
+            .unwrap()
+            .property(xmp_ns::XMP, "Label")
+            .unwrap()
+            .value
+    }
+
+    #[test]
+    fn intact() {
+        let packet = xmp_packet::repair(WRAPPED.as_bytes()).unwrap().unwrap();
+
+        assert_eq!(packet.range, 0..WRAPPED.len());
+        assert!(packet.repairs.is_empty());
+        assert_eq!(label(&packet), "Old");
+    }
+
+    #[test]
+    fn truncated() {
+        let cut = WRAPPED.find("</rdf:Description>").unwrap() + 5;
+        let mut data = b"JUNK\0".to_vec();
+        data.extend_from_slice(&WRAPPED.as_bytes()[..cut]);
+        data.extend_from_slice(&[0, 0xff, 0xd9]);
+
+        let packet = xmp_packet::repair(&data).unwrap().unwrap();
+
+        assert_eq!(packet.range, 5..5 + cut);
+        assert_eq!(packet.repairs, [PacketRepair::Truncated]);
+        assert_eq!(label(&packet), "Old");
+    }
+
+    #[test]
+    fn missing_trailer() {
+        let data = WRAPPED.replace("<?xpacket end=\"r\"?>", "");
+        let packet = xmp_packet::repair(data.as_bytes()).unwrap().unwrap();
+
+        assert_eq!(packet.range, 0..data.len());
+        assert_eq!(packet.repairs, [PacketRepair::BrokenWrapper]);
+        assert_eq!(label(&packet), "Old");
+    }
+
+    #[test]
+    fn bad_padding() {
+        let data = WRAPPED.replace("</x:xmpmeta>\n", "</x:xmpmeta>\n  junk  \n");
+        let packet = xmp_packet::repair(data.as_bytes()).unwrap().unwrap();
+
+        assert_eq!(packet.range, 0..data.len());
+        assert_eq!(packet.repairs, [PacketRepair::BadPadding]);
+        assert_eq!(label(&packet), "Old");
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let data = WRAPPED.replace("Old", "O\u{1}d").replace('\u{1}', "\u{80}");
+        let mut data = data.into_bytes();
+        let pos = data.windows(2).position(|w| w == [0xc2, 0x80]).unwrap();
+        data.remove(pos);
+
+        let packet = xmp_packet::repair(&data).unwrap().unwrap();
+
+        assert_eq!(packet.repairs, [PacketRepair::InvalidUtf8]);
+        assert_eq!(label(&packet), "O\u{fffd}d");
+    }
+
+    #[test]
+    fn no_packet() {
+        assert!(xmp_packet::repair(b"no XMP here").unwrap().is_none());
+    }
+
+    #[test]
+    fn unrepairable() {
+        let data = WRAPPED.replace("</xmp:Label>", "</xmp:Other>");
+        assert!(xmp_packet::repair(data.as_bytes()).is_err());
+    }
+}
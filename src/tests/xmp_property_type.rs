@@ -0,0 +1,138 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+mod bool {
+    use crate::XmpPropertyType;
+
+    #[test]
+    fn from_xmp_str() {
+        assert_eq!(bool::from_xmp_str("True"), Some(true));
+        assert_eq!(bool::from_xmp_str(" t "), Some(true));
+        assert_eq!(bool::from_xmp_str("1"), Some(true));
+        assert_eq!(bool::from_xmp_str("FALSE"), Some(false));
+        assert_eq!(bool::from_xmp_str("f"), Some(false));
+        assert_eq!(bool::from_xmp_str("0"), Some(false));
+        assert_eq!(bool::from_xmp_str("yes"), None);
+        assert_eq!(bool::from_xmp_str(""), None);
+    }
+
+    #[test]
+    fn to_xmp_string() {
+        assert_eq!(true.to_xmp_string(), "True");
+        assert_eq!(false.to_xmp_string(), "False");
+    }
+}
+
+mod i32 {
+    use crate::XmpPropertyType;
+
+    #[test]
+    fn from_xmp_str() {
+        assert_eq!(i32::from_xmp_str("42"), Some(42));
+        assert_eq!(i32::from_xmp_str(" -42 "), Some(-42));
+        assert_eq!(i32::from_xmp_str("+42"), Some(42));
+        assert_eq!(i32::from_xmp_str("0x2a"), Some(42));
+        assert_eq!(i32::from_xmp_str("-0X2A"), Some(-42));
+        assert_eq!(i32::from_xmp_str("-2147483648"), Some(i32::MIN));
+        assert_eq!(i32::from_xmp_str("2147483648"), None);
+        assert_eq!(i32::from_xmp_str("--42"), None);
+        assert_eq!(i32::from_xmp_str("4.2"), None);
+        assert_eq!(i32::from_xmp_str(""), None);
+    }
+
+    #[test]
+    fn to_xmp_string() {
+        assert_eq!((-42i32).to_xmp_string(), "-42");
+    }
+}
+
+mod i64 {
+    use crate::XmpPropertyType;
+
+    #[test]
+    fn from_xmp_str() {
+        assert_eq!(i64::from_xmp_str("1099511627776"), Some(1 << 40));
+        assert_eq!(i64::from_xmp_str("-9223372036854775808"), Some(i64::MIN));
+        assert_eq!(i64::from_xmp_str("9223372036854775808"), None);
+        assert_eq!(i64::from_xmp_str("0x"), None);
+    }
+
+    #[test]
+    fn to_xmp_string() {
+        assert_eq!((1i64 << 40).to_xmp_string(), "1099511627776");
+    }
+}
+
+mod f64 {
+    use crate::XmpPropertyType;
+
+    #[test]
+    fn from_xmp_str() {
+        assert_eq!(f64::from_xmp_str(" 1.5 "), Some(1.5));
+        assert_eq!(f64::from_xmp_str("abc"), None);
+    }
+
+    #[test]
+    fn to_xmp_string() {
+        assert_eq!(1.5f64.to_xmp_string(), "1.5");
+    }
+}
+
+mod xmp_date_time {
+    use crate::{XmpDate, XmpDateTime, XmpPropertyType};
+
+    #[test]
+    fn round_trip() {
+        let dt = XmpDateTime::from_xmp_str("2022-10-19").unwrap();
+
+        assert_eq!(
+            dt,
+            XmpDateTime {
+                date: Some(XmpDate {
+                    year: 2022,
+                    month: 10,
+                    day: 19,
+                }),
+                time: None,
+            }
+        );
+
+        assert_eq!(dt.to_xmp_string(), "2022-10-19");
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(XmpDateTime::from_xmp_str("not a date"), None);
+    }
+}
+
+mod default_impl {
+    use crate::{tests::fixtures::Rational, xmp_ns, XmpMeta, XmpPropertyType, XmpValue};
+
+    #[test]
+    fn preserves_options() {
+        let mut m = XmpMeta::new().unwrap();
+
+        Rational::set_property(
+            &mut m,
+            xmp_ns::EXIF,
+            "ExposureTime",
+            &XmpValue::new(Rational { num: 1, den: 250 }).set_is_uri(true),
+        )
+        .unwrap();
+
+        let value = Rational::get_property(&m, xmp_ns::EXIF, "ExposureTime").unwrap();
+        assert_eq!(value.value, Rational { num: 1, den: 250 });
+        assert!(value.is_uri());
+    }
+}
@@ -0,0 +1,256 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::{fs, io::Cursor};
+
+use crate::{
+    tests::fixtures::*,
+    xmp_ns,
+    xmp_psd::{image_resources, layers},
+    FileFormat, OpenFileOptions, ToStringOptions, XmpErrorType, XmpFile,
+};
+
+struct Layer<'a> {
+    name: &'a str,
+    unicode_name: Option<&'a str>,
+    id: Option<u32>,
+    label: Option<&'a str>,
+}
+
+/// Returns a PSD file with the given image resources and layers. There is
+/// no image data.
+fn psd(resources: &[(u16, &[u8])], layers: &[Layer]) -> Vec<u8> {
+    let mut file = b"8BPS".to_vec();
+    file.extend_from_slice(&1u16.to_be_bytes());
+    file.extend_from_slice(&[0; 6]);
+    file.extend_from_slice(&3u16.to_be_bytes());
+    file.extend_from_slice(&1u32.to_be_bytes());
+    file.extend_from_slice(&1u32.to_be_bytes());
+    file.extend_from_slice(&8u16.to_be_bytes());
+    file.extend_from_slice(&3u16.to_be_bytes());
+
+    // Color mode data.
+    file.extend_from_slice(&0u32.to_be_bytes());
+
+    let mut section = Vec::new();
+    for (id, data) in resources {
+        section.extend_from_slice(b"8BIM");
+        section.extend_from_slice(&id.to_be_bytes());
+        section.extend_from_slice(&[0, 0]);
+        section.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        section.extend_from_slice(data);
+        if data.len() % 2 == 1 {
+            section.push(0);
+        }
+    }
+    push_section(&mut file, &section);
+
+    let mut layer_info = Vec::new();
+    if !layers.is_empty() {
+        layer_info.extend_from_slice(&(layers.len() as u16).to_be_bytes());
+        for layer in layers {
+            layer_record(&mut layer_info, layer);
+        }
+    }
+
+    let mut layer_and_mask = Vec::new();
+    push_section(&mut layer_and_mask, &layer_info);
+    push_section(&mut file, &layer_and_mask);
+
+    file
+}
+
+fn layer_record(out: &mut Vec<u8>, layer: &Layer) {
+    out.extend_from_slice(&[0; 16]);
+
+    // One channel.
+    out.extend_from_slice(&1u16.to_be_bytes());
+    out.extend_from_slice(&0u16.to_be_bytes());
+    out.extend_from_slice(&2u32.to_be_bytes());
+
+    out.extend_from_slice(b"8BIMnorm");
+    out.extend_from_slice(&[255, 0, 0, 0]);
+
+    let mut extra = Vec::new();
+    extra.extend_from_slice(&0u32.to_be_bytes());
+    extra.extend_from_slice(&0u32.to_be_bytes());
+
+    extra.push(layer.name.len() as u8);
+    extra.extend_from_slice(layer.name.as_bytes());
+    while extra.len() % 4 != 0 {
+        extra.push(0);
+    }
+
+    if let Some(name) = layer.unicode_name {
+        let units: Vec<u16> = name.encode_utf16().collect();
+        let mut data = (units.len() as u32).to_be_bytes().to_vec();
+        for unit in units {
+            data.extend_from_slice(&unit.to_be_bytes());
+        }
+        push_layer_info(&mut extra, b"luni", &data);
+    }
+
+    if let Some(id) = layer.id {
+        push_layer_info(&mut extra, b"lyid", &id.to_be_bytes());
+    }
+
+    if let Some(label) = layer.label {
+        let xmp = meta_with_label(label)
+            .to_string_with_options(ToStringOptions::default())
+            .unwrap();
+
+        let mut data = 1u32.to_be_bytes().to_vec();
+        data.extend_from_slice(b"8BIMcust");
+        data.extend_from_slice(&[0; 4]);
+        data.extend_from_slice(&(xmp.len() as u32).to_be_bytes());
+        data.extend_from_slice(xmp.as_bytes());
+        push_layer_info(&mut extra, b"shmd", &data);
+    }
+
+    push_section(out, &extra);
+
+    // The channel's image data follows the records; it isn't read.
+}
+
+fn push_layer_info(out: &mut Vec<u8>, key: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(b"8BIM");
+    out.extend_from_slice(key);
+    push_section(out, data);
+    if data.len() % 2 == 1 {
+        out.push(0);
+    }
+}
+
+fn push_section(out: &mut Vec<u8>, data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(data);
+}
+
+#[test]
+fn read_image_resources() {
+    let file = psd(&[(1005, &[1; 16]), (1060, b"<x:xmpmeta/>")], &[]);
+    let resources = image_resources(&mut Cursor::new(file)).unwrap();
+
+    assert_eq!(resources.len(), 2);
+    assert_eq!(resources[0].signature, *b"8BIM");
+    assert_eq!(resources[0].id, 1005);
+    assert_eq!(resources[0].name, "");
+    assert_eq!(resources[0].data, [1; 16]);
+    assert_eq!(resources[1].id, 1060);
+    assert_eq!(resources[1].data, b"<x:xmpmeta/>");
+}
+
+#[test]
+fn read_layers() {
+    let file = psd(
+        &[],
+        &[
+            Layer {
+                name: "Background",
+                unicode_name: None,
+                id: None,
+                label: None,
+            },
+            Layer {
+                name: "Logo",
+                unicode_name: Some("Logo \u{2122}"),
+                id: Some(7),
+                label: Some("approved"),
+            },
+        ],
+    );
+
+    let layers = layers(&mut Cursor::new(file)).unwrap();
+    assert_eq!(layers.len(), 2);
+
+    assert_eq!(layers[0].name, "Background");
+    assert_eq!(layers[0].id, None);
+    assert!(layers[0].xmp.is_none());
+
+    assert_eq!(layers[1].name, "Logo \u{2122}");
+    assert_eq!(layers[1].id, Some(7));
+    assert_eq!(
+        layers[1]
+            .xmp
+            .as_ref()
+            .unwrap()
+            .property(xmp_ns::XMP, "Label")
+            .unwrap()
+            .value,
+        "approved"
+    );
+}
+
+#[test]
+fn no_layers() {
+    let file = psd(&[], &[]);
+    assert!(layers(&mut Cursor::new(file)).unwrap().is_empty());
+}
+
+#[test]
+fn not_psd() {
+    let err = image_resources(&mut Cursor::new(b"II*\0".to_vec())).unwrap_err();
+    assert_eq!(err.error_type, XmpErrorType::BadFileFormat);
+}
+
+#[test]
+fn truncated() {
+    let mut file = psd(&[(1005, &[1; 16])], &[]);
+    file.truncate(50);
+
+    let err = image_resources(&mut Cursor::new(file)).unwrap_err();
+    assert_eq!(err.error_type, XmpErrorType::BadFileFormat);
+}
+
+#[test]
+fn xmp_file() {
+    let mut f = XmpFile::new().unwrap();
+    f.open_file(
+        fixture_path("Purple Square.psd"),
+        OpenFileOptions::default().for_read(),
+    )
+    .unwrap();
+
+    let resources = f.psd_image_resources().unwrap();
+    let xmp = resources
+        .iter()
+        .find(|resource| resource.id == 1060)
+        .unwrap();
+    assert!(xmp.data.starts_with(b"<?xpacket begin="));
+    assert!(resources.iter().any(|resource| resource.id == 1028));
+
+    assert!(f.psd_layers().unwrap().is_empty());
+}
+
+#[test]
+fn xmp_file_not_psd() {
+    let mut f = XmpFile::new().unwrap();
+    f.open_file(fixture_path("image2.jpg"), OpenFileOptions::default())
+        .unwrap();
+
+    let err = f.psd_image_resources().unwrap_err();
+    assert_eq!(err.error_type, XmpErrorType::BadFileFormat);
+}
+
+#[test]
+fn xmp_file_from_bytes() {
+    let f = XmpFile::from_bytes(
+        &fs::read(fixture_path("Purple Square.psd")).unwrap(),
+        FileFormat::Photoshop,
+        OpenFileOptions::default(),
+    )
+    .unwrap();
+
+    let err = f.psd_layers().unwrap_err();
+    assert_eq!(err.error_type, XmpErrorType::BadParam);
+}
@@ -0,0 +1,164 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::str::FromStr;
+
+use crate::{xmp_ns, RedactionProfile, XmpMeta};
+
+const PERSONAL: &str = r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <rdf:Description rdf:about=""
+        xmlns:xmp="http://ns.adobe.com/xap/1.0/"
+        xmlns:exif="http://ns.adobe.com/exif/1.0/"
+        xmlns:exifEX="http://cipa.jp/exif/1.0/"
+        xmlns:aux="http://ns.adobe.com/exif/1.0/aux/"
+        xmlns:photoshop="http://ns.adobe.com/photoshop/1.0/"
+        xmlns:mwg-rs="http://www.metadataworkinggroup.com/schemas/regions/"
+        xmlns:MP="http://ns.microsoft.com/photo/1.2/"
+        xmlns:MPRI="http://ns.microsoft.com/photo/1.2/t/RegionInfo#"
+        xmlns:MPReg="http://ns.microsoft.com/photo/1.2/t/Region#">
+        <xmp:Label>Red</xmp:Label>
+        <exif:ExposureTime>1/125</exif:ExposureTime>
+        <exif:GPSLatitude>47,0N</exif:GPSLatitude>
+        <exif:GPSLongitude>122,0W</exif:GPSLongitude>
+        <exifEX:BodySerialNumber>1234</exifEX:BodySerialNumber>
+        <exifEX:CameraOwnerName>Alice</exifEX:CameraOwnerName>
+        <aux:LensSerialNumber>5678</aux:LensSerialNumber>
+        <aux:Lens>50mm</aux:Lens>
+        <photoshop:City>Seattle</photoshop:City>
+        <photoshop:Country>USA</photoshop:Country>
+        <mwg-rs:Regions rdf:parseType="Resource">
+            <mwg-rs:RegionList>
+                <rdf:Bag>
+                    <rdf:li rdf:parseType="Resource">
+                        <mwg-rs:Type>Face</mwg-rs:Type>
+                        <mwg-rs:Name>Alice</mwg-rs:Name>
+                    </rdf:li>
+                    <rdf:li rdf:parseType="Resource">
+                        <mwg-rs:Type>Focus</mwg-rs:Type>
+                    </rdf:li>
+                    <rdf:li rdf:parseType="Resource">
+                        <mwg-rs:Type>Face</mwg-rs:Type>
+                        <mwg-rs:Name>Bob</mwg-rs:Name>
+                    </rdf:li>
+                </rdf:Bag>
+            </mwg-rs:RegionList>
+        </mwg-rs:Regions>
+        <MP:RegionInfo rdf:parseType="Resource">
+            <MPRI:Regions>
+                <rdf:Bag>
+                    <rdf:li MPReg:PersonDisplayName="Alice"/>
+                </rdf:Bag>
+            </MPRI:Regions>
+        </MP:RegionInfo>
+    </rdf:Description>
+</rdf:RDF>"#;
+
+fn removed(report: &crate::RedactionReport) -> Vec<(&str, &str)> {
+    report
+        .removed
+        .iter()
+        .map(|r| (r.namespace.as_str(), r.path.as_str()))
+        .collect()
+}
+
+#[test]
+fn default_removes_nothing() {
+    let mut m = XmpMeta::from_str(PERSONAL).unwrap();
+    let before = m.to_string();
+
+    let report = m.redact(RedactionProfile::default()).unwrap();
+
+    assert!(report.removed.is_empty());
+    assert_eq!(m.to_string(), before);
+}
+
+#[test]
+fn personal_data() {
+    let mut m = XmpMeta::from_str(PERSONAL).unwrap();
+    let report = m.redact(RedactionProfile::personal_data()).unwrap();
+
+    let removed = removed(&report);
+    assert_eq!(removed.len(), 9);
+    assert!(removed.contains(&(xmp_ns::EXIF, "GPSLatitude")));
+    assert!(removed.contains(&(xmp_ns::EXIF, "GPSLongitude")));
+    assert!(removed.contains(&(xmp_ns::EXIF_EX, "BodySerialNumber")));
+    assert!(removed.contains(&(xmp_ns::EXIF_EX, "CameraOwnerName")));
+    assert!(removed.contains(&(xmp_ns::EXIF_AUX, "LensSerialNumber")));
+    assert!(removed.contains(&(xmp_ns::PHOTOSHOP, "City")));
+    assert!(removed.contains(&(xmp_ns::MICROSOFT_PHOTO, "RegionInfo")));
+    assert!(removed.contains(&(xmp_ns::MWG_REGIONS, "Regions/mwg-rs:RegionList[1]")));
+    assert!(removed.contains(&(xmp_ns::MWG_REGIONS, "Regions/mwg-rs:RegionList[3]")));
+
+    for (ns, path) in removed {
+        assert!(!m.contains_property(ns, path), "{path}");
+    }
+
+    assert!(m.contains_property(xmp_ns::XMP, "Label"));
+    assert!(m.contains_property(xmp_ns::EXIF, "ExposureTime"));
+    assert!(m.contains_property(xmp_ns::EXIF_AUX, "Lens"));
+    assert!(m.contains_property(xmp_ns::PHOTOSHOP, "Country"));
+
+    // The focus region is kept.
+    assert_eq!(
+        m.array_len(xmp_ns::MWG_REGIONS, "Regions/mwg-rs:RegionList"),
+        1
+    );
+    assert_eq!(
+        m.property(
+            xmp_ns::MWG_REGIONS,
+            "Regions/mwg-rs:RegionList[1]/mwg-rs:Type"
+        )
+        .unwrap()
+        .value,
+        "Focus"
+    );
+}
+
+#[test]
+fn single_category() {
+    let mut m = XmpMeta::from_str(PERSONAL).unwrap();
+    let report = m
+        .redact(RedactionProfile::default().serial_numbers())
+        .unwrap();
+
+    let removed = removed(&report);
+    assert_eq!(removed.len(), 2);
+    assert!(removed.contains(&(xmp_ns::EXIF_EX, "BodySerialNumber")));
+    assert!(removed.contains(&(xmp_ns::EXIF_AUX, "LensSerialNumber")));
+
+    assert!(m.contains_property(xmp_ns::EXIF, "GPSLatitude"));
+    assert!(m.contains_property(xmp_ns::EXIF_EX, "CameraOwnerName"));
+}
+
+#[test]
+fn all_faces_removes_regions() {
+    let mut m = XmpMeta::from_str(PERSONAL).unwrap();
+    m.delete_property(xmp_ns::MWG_REGIONS, "Regions/mwg-rs:RegionList[2]")
+        .unwrap();
+
+    m.redact(RedactionProfile::default().face_regions())
+        .unwrap();
+
+    assert!(!m.contains_property(xmp_ns::MWG_REGIONS, "Regions"));
+}
+
+#[test]
+fn custom_property() {
+    let mut m = XmpMeta::from_str(PERSONAL).unwrap();
+    let report = m
+        .redact(RedactionProfile::default().property(xmp_ns::XMP, "Label"))
+        .unwrap();
+
+    assert_eq!(removed(&report), [(xmp_ns::XMP, "Label")]);
+    assert!(!m.contains_property(xmp_ns::XMP, "Label"));
+}
@@ -0,0 +1,302 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::{fs, path::PathBuf, str::FromStr};
+
+use tempfile::tempdir;
+
+use crate::{
+    tests::fixtures::*,
+    xmp_ns,
+    xmp_sidecar::{self, SyncPolicy, SyncSide},
+    OpenFileOptions, XmpFile, XmpMeta,
+};
+
+fn sidecar_with(metadata_date: &str, property: &str) -> String {
+    format!(
+        r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+            <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+                <rdf:Description rdf:about=""
+                    xmlns:xmp="http://ns.adobe.com/xap/1.0/"
+                    xmlns:photoshop="http://ns.adobe.com/photoshop/1.0/"
+                    xmp:MetadataDate="{metadata_date}"
+                    photoshop:City="{property}"/>
+            </rdf:RDF>
+        </x:xmpmeta>"#
+    )
+}
+
+fn embedded_xmp(path: &str) -> XmpMeta {
+    let mut f = XmpFile::new().unwrap();
+    f.open_file(path, OpenFileOptions::default().for_read())
+        .unwrap();
+    let xmp = f.xmp().unwrap();
+    f.close().unwrap();
+    xmp
+}
+
+fn sidecar_xmp(path: &str) -> XmpMeta {
+    let xmp = fs::read_to_string(xmp_sidecar::sidecar_path(path)).unwrap();
+    XmpMeta::from_str(&xmp).unwrap()
+}
+
+#[test]
+fn sidecar_path() {
+    assert_eq!(
+        xmp_sidecar::sidecar_path("photos/IMG_0001.CR2"),
+        PathBuf::from("photos/IMG_0001.xmp")
+    );
+
+    assert_eq!(
+        xmp_sidecar::sidecar_path("photos/no_extension"),
+        PathBuf::from("photos/no_extension.xmp")
+    );
+}
+
+#[test]
+fn creates_missing_sidecar() {
+    let tempdir = tempdir().unwrap();
+    let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+    let report = xmp_sidecar::sync(&purple_square, SyncPolicy::NewerWins).unwrap();
+
+    assert_eq!(report.newer, Some(SyncSide::Embedded));
+    assert!(report.sidecar_written);
+    assert!(!report.embedded_written);
+    assert!(report.sidecar_path.exists());
+
+    let sidecar = sidecar_xmp(&purple_square);
+    assert_eq!(
+        sidecar.property(xmp_ns::XMP, "CreatorTool").unwrap().value,
+        "Adobe Photoshop CS2 Windows"
+    );
+}
+
+#[test]
+fn newer_sidecar_wins() {
+    let tempdir = tempdir().unwrap();
+    let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+    fs::write(
+        xmp_sidecar::sidecar_path(&purple_square),
+        sidecar_with("2030-01-01T00:00:00Z", "Sidecar City"),
+    )
+    .unwrap();
+
+    let report = xmp_sidecar::sync(&purple_square, SyncPolicy::NewerWins).unwrap();
+
+    assert_eq!(report.newer, Some(SyncSide::Sidecar));
+    assert!(report.embedded_written);
+    assert!(!report.sidecar_written);
+
+    let embedded = embedded_xmp(&purple_square);
+    assert_eq!(
+        embedded.property(xmp_ns::PHOTOSHOP, "City").unwrap().value,
+        "Sidecar City"
+    );
+    assert!(!embedded.contains_property(xmp_ns::XMP, "CreatorTool"));
+}
+
+#[test]
+fn newer_embedded_wins() {
+    let tempdir = tempdir().unwrap();
+    let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+    fs::write(
+        xmp_sidecar::sidecar_path(&purple_square),
+        sidecar_with("1999-01-01T00:00:00Z", "Stale City"),
+    )
+    .unwrap();
+
+    let report = xmp_sidecar::sync(&purple_square, SyncPolicy::NewerWins).unwrap();
+
+    assert_eq!(report.newer, Some(SyncSide::Embedded));
+    assert!(!report.embedded_written);
+    assert!(report.sidecar_written);
+
+    let sidecar = sidecar_xmp(&purple_square);
+    assert!(!sidecar.contains_property(xmp_ns::PHOTOSHOP, "City"));
+    assert!(sidecar.contains_property(xmp_ns::XMP, "CreatorTool"));
+}
+
+#[test]
+fn merge_newer_wins() {
+    let tempdir = tempdir().unwrap();
+    let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+    fs::write(
+        xmp_sidecar::sidecar_path(&purple_square),
+        sidecar_with("2030-01-01T00:00:00Z", "Sidecar City"),
+    )
+    .unwrap();
+
+    let report = xmp_sidecar::sync(&purple_square, SyncPolicy::MergeNewerWins).unwrap();
+
+    assert_eq!(report.newer, Some(SyncSide::Sidecar));
+    assert!(report.embedded_written);
+    assert!(report.sidecar_written);
+
+    for xmp in [embedded_xmp(&purple_square), sidecar_xmp(&purple_square)] {
+        assert_eq!(
+            xmp.property(xmp_ns::PHOTOSHOP, "City").unwrap().value,
+            "Sidecar City"
+        );
+        assert_eq!(
+            xmp.property(xmp_ns::XMP, "CreatorTool").unwrap().value,
+            "Adobe Photoshop CS2 Windows"
+        );
+        assert_eq!(
+            xmp.property(xmp_ns::XMP, "MetadataDate").unwrap().value,
+            "2030-01-01T00:00:00Z"
+        );
+    }
+}
+
+#[test]
+fn embedded_wins_regardless_of_age() {
+    let tempdir = tempdir().unwrap();
+    let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+    fs::write(
+        xmp_sidecar::sidecar_path(&purple_square),
+        sidecar_with("2030-01-01T00:00:00Z", "Sidecar City"),
+    )
+    .unwrap();
+
+    let report = xmp_sidecar::sync(&purple_square, SyncPolicy::EmbeddedWins).unwrap();
+
+    assert_eq!(report.newer, Some(SyncSide::Sidecar));
+    assert!(!report.embedded_written);
+    assert!(report.sidecar_written);
+    assert!(!sidecar_xmp(&purple_square).contains_property(xmp_ns::PHOTOSHOP, "City"));
+}
+
+#[test]
+fn second_sync_writes_nothing() {
+    let tempdir = tempdir().unwrap();
+    let purple_square = temp_copy_of_fixture(tempdir.path(), "Purple Square.psd");
+
+    xmp_sidecar::sync(&purple_square, SyncPolicy::NewerWins).unwrap();
+    let report = xmp_sidecar::sync(&purple_square, SyncPolicy::NewerWins).unwrap();
+
+    assert!(!report.embedded_written);
+    assert!(!report.sidecar_written);
+}
+
+mod sidecar {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use crate::{
+        xmp_ns,
+        xmp_sidecar::{self, Sidecar},
+        ToStringOptions, XmpErrorType, XmpMeta,
+    };
+
+    #[test]
+    fn find_conventional() {
+        let tempdir = tempdir().unwrap();
+        let asset = tempdir.path().join("IMG_0001.CR2");
+
+        let sidecar = Sidecar::find(&asset);
+        assert_eq!(sidecar.path(), xmp_sidecar::sidecar_path(&asset));
+        assert!(!sidecar.exists());
+    }
+
+    #[test]
+    fn find_appended() {
+        let tempdir = tempdir().unwrap();
+        let asset = tempdir.path().join("IMG_0001.CR2");
+        let appended = tempdir.path().join("IMG_0001.CR2.xmp");
+        fs::write(&appended, "").unwrap();
+
+        assert_eq!(Sidecar::find(&asset).path(), appended);
+
+        // The conventional name is preferred if both exist.
+        fs::write(xmp_sidecar::sidecar_path(&asset), "").unwrap();
+        assert_eq!(
+            Sidecar::find(&asset).path(),
+            xmp_sidecar::sidecar_path(&asset)
+        );
+    }
+
+    #[test]
+    fn read_missing() {
+        let tempdir = tempdir().unwrap();
+        let sidecar = Sidecar::find(tempdir.path().join("IMG_0001.CR2"));
+
+        assert!(sidecar.read().unwrap().is_none());
+    }
+
+    #[test]
+    fn read_bare_rdf() {
+        let tempdir = tempdir().unwrap();
+        let sidecar = Sidecar::at(tempdir.path().join("bare.xmp"));
+
+        fs::write(
+            sidecar.path(),
+            r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+                <rdf:Description rdf:about=""
+                    xmlns:xmp="http://ns.adobe.com/xap/1.0/"
+                    xmp:Rating="3"/>
+            </rdf:RDF>"#,
+        )
+        .unwrap();
+
+        let xmp = sidecar.read().unwrap().unwrap();
+        assert_eq!(xmp.property(xmp_ns::XMP, "Rating").unwrap().value, "3");
+    }
+
+    #[test]
+    fn write_and_read() {
+        let tempdir = tempdir().unwrap();
+        let sidecar = Sidecar::find(tempdir.path().join("IMG_0001.CR2"));
+
+        let mut xmp = XmpMeta::new().unwrap();
+        xmp.set_property(xmp_ns::XMP, "Rating", &"5".into())
+            .unwrap();
+        sidecar.write(&xmp).unwrap();
+
+        let text = fs::read_to_string(sidecar.path()).unwrap();
+        assert!(!text.contains("<?xpacket"));
+
+        let read = sidecar.read().unwrap().unwrap();
+        assert_eq!(read.property(xmp_ns::XMP, "Rating").unwrap().value, "5");
+    }
+
+    #[test]
+    fn write_with_options() {
+        let tempdir = tempdir().unwrap();
+        let sidecar = Sidecar::find(tempdir.path().join("IMG_0001.CR2"));
+
+        let mut xmp = XmpMeta::new().unwrap();
+        xmp.set_property(xmp_ns::XMP, "Rating", &"5".into())
+            .unwrap();
+        sidecar
+            .write_with_options(&xmp, ToStringOptions::default())
+            .unwrap();
+
+        let text = fs::read_to_string(sidecar.path()).unwrap();
+        assert!(text.starts_with("<?xpacket begin="));
+    }
+
+    #[test]
+    fn write_to_missing_directory() {
+        let tempdir = tempdir().unwrap();
+        let sidecar = Sidecar::at(tempdir.path().join("missing/IMG_0001.xmp"));
+
+        let err = sidecar.write(&XmpMeta::new().unwrap()).unwrap_err();
+        assert_eq!(err.error_type, XmpErrorType::WriteError);
+    }
+}
@@ -0,0 +1,203 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::str::FromStr;
+
+use crate::{tests::fixtures::*, xmp_ns, XmpDateTime, XmpMeta, XmpStruct};
+
+#[derive(Debug, PartialEq, XmpStruct)]
+#[xmp(
+    ns = "http://iptc.org/std/Iptc4xmpCore/1.0/xmlns/",
+    name = "CreatorContactInfo"
+)]
+struct ContactInfo {
+    #[xmp(name = "CiAdrPcode")]
+    postal_code: String,
+
+    #[xmp(name = "CiAdrCtry")]
+    country: Option<String>,
+
+    #[xmp(name = "CiAdrCity")]
+    city: Option<String>,
+}
+
+#[derive(Debug, PartialEq, XmpStruct)]
+#[xmp(ns = "ns:test1/", name = "Struct")]
+struct CustomNames {
+    #[xmp(ns = "ns:test2/")]
+    field1: String,
+}
+
+#[derive(Debug, PartialEq, XmpStruct)]
+#[xmp(ns = "ns:test1/", name = "Typed")]
+struct TypedFields {
+    count: i32,
+    approved: bool,
+    ratio: Option<Rational>,
+    modified: Option<XmpDateTime>,
+}
+
+#[test]
+fn read_from() {
+    let m = XmpMeta::from_str(STRUCT_EXAMPLE).unwrap();
+
+    assert_eq!(
+        ContactInfo::read_from(&m).unwrap(),
+        ContactInfo {
+            postal_code: "98110".to_owned(),
+            country: Some("US".to_owned()),
+            city: None,
+        }
+    );
+}
+
+#[test]
+fn read_from_missing_struct() {
+    let m = XmpMeta::default();
+    assert!(ContactInfo::read_from(&m).is_none());
+}
+
+#[test]
+fn read_from_missing_required_field() {
+    let mut m = XmpMeta::default();
+    m.set_struct_field(
+        xmp_ns::IPTC_CORE,
+        "CreatorContactInfo",
+        xmp_ns::IPTC_CORE,
+        "CiAdrCity",
+        &"Seattle".into(),
+    )
+    .unwrap();
+
+    assert!(ContactInfo::read_from(&m).is_none());
+}
+
+#[test]
+fn write_to() {
+    let mut m = XmpMeta::from_str(STRUCT_EXAMPLE).unwrap();
+
+    let contact = ContactInfo {
+        postal_code: "98101".to_owned(),
+        country: None,
+        city: Some("Seattle".to_owned()),
+    };
+
+    contact.write_to(&mut m).unwrap();
+
+    assert_eq!(
+        m.struct_field(
+            xmp_ns::IPTC_CORE,
+            "CreatorContactInfo",
+            xmp_ns::IPTC_CORE,
+            "CiAdrCtry"
+        ),
+        None
+    );
+    assert_eq!(ContactInfo::read_from(&m).unwrap(), contact);
+}
+
+#[test]
+fn default_field_name() {
+    XmpMeta::register_namespace("ns:test1/", "ns1").unwrap();
+    XmpMeta::register_namespace("ns:test2/", "ns2").unwrap();
+
+    let mut m = XmpMeta::default();
+
+    let value = CustomNames {
+        field1: "x".to_owned(),
+    };
+    value.write_to(&mut m).unwrap();
+
+    assert_eq!(
+        m.struct_field("ns:test1/", "Struct", "ns:test2/", "field1")
+            .unwrap()
+            .value,
+        "x"
+    );
+    assert_eq!(CustomNames::read_from(&m).unwrap(), value);
+}
+
+#[test]
+fn typed_fields() {
+    XmpMeta::register_namespace("ns:test1/", "ns1").unwrap();
+
+    let mut m = XmpMeta::default();
+
+    let value = TypedFields {
+        count: 42,
+        approved: true,
+        ratio: Some(Rational { num: 3, den: 2 }),
+        modified: None,
+    };
+    value.write_to(&mut m).unwrap();
+
+    assert_eq!(
+        m.struct_field("ns:test1/", "Typed", "ns:test1/", "count")
+            .unwrap()
+            .value,
+        "42"
+    );
+    assert_eq!(
+        m.struct_field("ns:test1/", "Typed", "ns:test1/", "approved")
+            .unwrap()
+            .value,
+        "True"
+    );
+    assert_eq!(
+        m.struct_field("ns:test1/", "Typed", "ns:test1/", "ratio")
+            .unwrap()
+            .value,
+        "3/2"
+    );
+    assert_eq!(TypedFields::read_from(&m).unwrap(), value);
+}
+
+#[test]
+fn typed_field_bad_value() {
+    XmpMeta::register_namespace("ns:test1/", "ns1").unwrap();
+
+    let mut m = XmpMeta::default();
+
+    TypedFields {
+        count: 1,
+        approved: false,
+        ratio: None,
+        modified: None,
+    }
+    .write_to(&mut m)
+    .unwrap();
+
+    m.set_struct_field("ns:test1/", "Typed", "ns:test1/", "ratio", &"x".into())
+        .unwrap();
+    assert!(TypedFields::read_from(&m).is_none());
+
+    m.set_struct_field("ns:test1/", "Typed", "ns:test1/", "ratio", &"1/2".into())
+        .unwrap();
+    m.set_struct_field("ns:test1/", "Typed", "ns:test1/", "count", &"many".into())
+        .unwrap();
+    assert!(TypedFields::read_from(&m).is_none());
+}
+
+#[test]
+fn init_fail() {
+    let mut m = XmpMeta::new_fail();
+
+    assert!(ContactInfo::read_from(&m).is_none());
+
+    let contact = ContactInfo {
+        postal_code: "98101".to_owned(),
+        country: None,
+        city: None,
+    };
+    assert!(contact.write_to(&mut m).is_err());
+}
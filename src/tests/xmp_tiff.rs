@@ -0,0 +1,230 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::fs;
+
+use tempfile::tempdir;
+
+use crate::{tests::fixtures::*, xmp_ns, xmp_tiff::TiffPages, ToStringOptions, XmpErrorType};
+
+const IMAGE_WIDTH: u16 = 256;
+const XML_PACKET: u16 = 700;
+
+/// Returns a TIFF file with one IFD per entry of `pages`, each holding an
+/// `ImageWidth` tag and, if given, an XMP packet with that label. There is
+/// no image data.
+fn tiff(big_endian: bool, pages: &[Option<&str>]) -> Vec<u8> {
+    let u16_bytes = |v: u16| {
+        if big_endian {
+            v.to_be_bytes()
+        } else {
+            v.to_le_bytes()
+        }
+    };
+    let u32_bytes = |v: u32| {
+        if big_endian {
+            v.to_be_bytes()
+        } else {
+            v.to_le_bytes()
+        }
+    };
+
+    let mut file = if big_endian {
+        b"MM\0*".to_vec()
+    } else {
+        b"II*\0".to_vec()
+    };
+    file.extend_from_slice(&u32_bytes(8));
+
+    for (index, label) in pages.iter().enumerate() {
+        let packet = label.map(|label| {
+            meta_with_label(label)
+                .to_string_with_options(ToStringOptions::default())
+                .unwrap()
+        });
+
+        let count: u16 = if packet.is_some() { 2 } else { 1 };
+        let ifd_len = 2 + usize::from(count) * 12 + 4;
+        let packet_offset = (file.len() + ifd_len) as u32;
+
+        file.extend_from_slice(&u16_bytes(count));
+
+        file.extend_from_slice(&u16_bytes(IMAGE_WIDTH));
+        file.extend_from_slice(&u16_bytes(3));
+        file.extend_from_slice(&u32_bytes(1));
+        file.extend_from_slice(&u16_bytes(100));
+        file.extend_from_slice(&[0; 2]);
+
+        if let Some(packet) = &packet {
+            file.extend_from_slice(&u16_bytes(XML_PACKET));
+            file.extend_from_slice(&u16_bytes(7));
+            file.extend_from_slice(&u32_bytes(packet.len() as u32));
+            file.extend_from_slice(&u32_bytes(packet_offset));
+        }
+
+        let mut next = packet_offset as usize + packet.as_ref().map_or(0, String::len);
+        next += next % 2;
+        let next = if index + 1 == pages.len() { 0 } else { next };
+        file.extend_from_slice(&u32_bytes(next as u32));
+
+        if let Some(packet) = &packet {
+            file.extend_from_slice(packet.as_bytes());
+            if file.len() % 2 == 1 {
+                file.push(0);
+            }
+        }
+    }
+
+    file
+}
+
+fn labels(pages: &TiffPages) -> Vec<Option<String>> {
+    pages
+        .all_xmp()
+        .unwrap()
+        .into_iter()
+        .map(|meta| meta.and_then(|meta| meta.property(xmp_ns::XMP, "Label").map(|p| p.value)))
+        .collect()
+}
+
+#[test]
+fn read_pages() {
+    for big_endian in [false, true] {
+        let pages =
+            TiffPages::from_bytes(&tiff(big_endian, &[Some("one"), None, Some("three")])).unwrap();
+
+        assert_eq!(pages.len(), 3);
+        assert_eq!(
+            labels(&pages),
+            [Some("one".to_owned()), None, Some("three".to_owned())]
+        );
+        assert!(pages.raw_xmp_packet(1).unwrap().is_none());
+        assert!(pages
+            .raw_xmp_packet(2)
+            .unwrap()
+            .unwrap()
+            .starts_with(b"<?xpacket begin="));
+    }
+}
+
+#[test]
+fn replace_xmp() {
+    for big_endian in [false, true] {
+        let mut pages =
+            TiffPages::from_bytes(&tiff(big_endian, &[Some("one"), Some("two")])).unwrap();
+
+        pages.put_xmp(1, &meta_with_label("second")).unwrap();
+
+        let pages = TiffPages::from_bytes(&pages.into_bytes()).unwrap();
+        assert_eq!(
+            labels(&pages),
+            [Some("one".to_owned()), Some("second".to_owned())]
+        );
+    }
+}
+
+#[test]
+fn add_xmp() {
+    for big_endian in [false, true] {
+        let mut pages = TiffPages::from_bytes(&tiff(big_endian, &[None, None, None])).unwrap();
+
+        pages.put_xmp(0, &meta_with_label("first")).unwrap();
+        pages.put_xmp(1, &meta_with_label("second")).unwrap();
+
+        let pages = TiffPages::from_bytes(&pages.into_bytes()).unwrap();
+        assert_eq!(pages.len(), 3);
+        assert_eq!(
+            labels(&pages),
+            [Some("first".to_owned()), Some("second".to_owned()), None]
+        );
+    }
+}
+
+#[test]
+fn add_xmp_keeps_other_entries() {
+    let original = tiff(false, &[None, None]);
+    let mut pages = TiffPages::from_bytes(&original).unwrap();
+    pages.put_xmp(1, &meta_with_label("second")).unwrap();
+
+    let updated = pages.into_bytes();
+
+    // The original content is untouched, except for the pointer to the
+    // second IFD at the end of the first.
+    assert_eq!(updated[..22], original[..22]);
+    assert_eq!(updated[26..original.len()], original[26..]);
+
+    // The new IFD has the image width and the XMP, in tag order.
+    let ifd = u32::from_le_bytes(updated[22..26].try_into().unwrap()) as usize;
+    assert_eq!(u16::from_le_bytes([updated[ifd], updated[ifd + 1]]), 2);
+    assert_eq!(
+        u16::from_le_bytes([updated[ifd + 2], updated[ifd + 3]]),
+        IMAGE_WIDTH
+    );
+    assert_eq!(
+        u16::from_le_bytes([updated[ifd + 14], updated[ifd + 15]]),
+        XML_PACKET
+    );
+}
+
+#[test]
+fn bad_index() {
+    let mut pages = TiffPages::from_bytes(&tiff(false, &[None])).unwrap();
+
+    assert_eq!(pages.xmp(1).unwrap_err().error_type, XmpErrorType::BadIndex);
+    assert_eq!(
+        pages
+            .put_xmp(1, &meta_with_label("none"))
+            .unwrap_err()
+            .error_type,
+        XmpErrorType::BadIndex
+    );
+}
+
+#[test]
+fn not_tiff() {
+    let err = TiffPages::from_bytes(b"RIFF\0\0\0\0WEBP").err().unwrap();
+    assert_eq!(err.error_type, XmpErrorType::BadFileFormat);
+
+    let err = TiffPages::from_bytes(b"II+\0\x08\0\0\0").err().unwrap();
+    assert_eq!(err.error_type, XmpErrorType::BadFileFormat);
+}
+
+#[test]
+fn ifd_loop() {
+    let mut file = tiff(false, &[None]);
+    file[22..26].copy_from_slice(&8u32.to_le_bytes());
+
+    let err = TiffPages::from_bytes(&file).err().unwrap();
+    assert_eq!(err.error_type, XmpErrorType::BadFileFormat);
+}
+
+#[test]
+fn update_file() {
+    let tempdir = tempdir().unwrap();
+    let path = tempdir.path().join("scan.tif");
+    fs::write(&path, tiff(true, &[Some("one"), None])).unwrap();
+
+    let mut pages = TiffPages::open(&path).unwrap();
+    pages.put_xmp(1, &meta_with_label("two")).unwrap();
+
+    // Nothing is written until the pages are closed.
+    assert_eq!(labels(&TiffPages::open(&path).unwrap())[1], None);
+
+    pages.close().unwrap();
+
+    assert_eq!(
+        labels(&TiffPages::open(&path).unwrap()),
+        [Some("one".to_owned()), Some("two".to_owned())]
+    );
+    assert_eq!(fs::read_dir(tempdir.path()).unwrap().count(), 1);
+}
@@ -0,0 +1,210 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::fs;
+
+use tempfile::tempdir;
+
+use crate::{tests::fixtures::*, xmp_ns, FileFormat, OpenFileOptions, XmpFile, XmpMeta};
+
+/// 16-bit stereo PCM at 48 kHz.
+const FMT: &[u8] = &[1, 0, 2, 0, 0x80, 0xbb, 0, 0, 0, 0xee, 2, 0, 4, 0, 16, 0];
+
+/// Stand-in for audio samples.
+const DATA: &[u8] = &[1, 2, 3, 4, 5, 6, 7, 8];
+
+fn wav(chunks: &[(&[u8; 4], &[u8])]) -> Vec<u8> {
+    let mut file = b"RIFF\0\0\0\0WAVE".to_vec();
+
+    for (fourcc, data) in chunks {
+        file.extend_from_slice(*fourcc);
+        file.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        file.extend_from_slice(data);
+
+        if data.len() % 2 == 1 {
+            file.push(0);
+        }
+    }
+
+    let riff_len = (file.len() - 8) as u32;
+    file[4..8].copy_from_slice(&riff_len.to_le_bytes());
+    file
+}
+
+fn bext(description: &str, originator: &str, date: &str, time: &str) -> Vec<u8> {
+    let mut bext = vec![0; 602];
+
+    for (offset, text) in [
+        (0, description),
+        (256, originator),
+        (320, date),
+        (330, time),
+    ] {
+        bext[offset..offset + text.len()].copy_from_slice(text.as_bytes());
+    }
+
+    bext
+}
+
+/// Returns the chunk IDs in `file`.
+fn fourccs(file: &[u8]) -> Vec<String> {
+    assert_eq!(
+        u32::from_le_bytes(file[4..8].try_into().unwrap()) as usize,
+        file.len() - 8
+    );
+
+    let mut fourccs = Vec::new();
+    let mut pos = 12;
+
+    while pos < file.len() {
+        fourccs.push(String::from_utf8_lossy(&file[pos..pos + 4]).into_owned());
+        let len = u32::from_le_bytes(file[pos + 4..pos + 8].try_into().unwrap()) as usize;
+        pos += 8 + len + len % 2;
+    }
+
+    fourccs
+}
+
+fn options() -> OpenFileOptions {
+    OpenFileOptions::default().import_bwf_metadata()
+}
+
+fn xmp(wav: &[u8]) -> Option<XmpMeta> {
+    XmpFile::from_bytes(wav, FileFormat::Wav, options())
+        .unwrap()
+        .xmp()
+}
+
+fn dm(meta: &XmpMeta, property: &str) -> Option<String> {
+    meta.property(xmp_ns::DYNAMIC_MEDIA, property)
+        .map(|v| v.value)
+}
+
+#[test]
+fn no_metadata() {
+    assert!(xmp(&wav(&[(b"fmt ", FMT), (b"data", DATA)])).is_none());
+}
+
+#[test]
+fn add_xmp() {
+    let updated =
+        put_label_with_options(&wav(&[(b"fmt ", FMT), (b"data", DATA)]), "Added", options());
+    assert_eq!(fourccs(&updated), ["fmt ", "data", "_PMX"]);
+
+    let xmp = xmp(&updated).unwrap();
+    assert_eq!(xmp.property(xmp_ns::XMP, "Label").unwrap().value, "Added");
+
+    let mut f = XmpFile::from_bytes(&updated, FileFormat::Wav, options()).unwrap();
+    let info = f.packet_info().unwrap();
+    assert!(info.offset.is_some());
+}
+
+#[test]
+fn replace_xmp_in_place() {
+    let original = wav(&[(b"fmt ", FMT), (b"data", DATA), (b"LIST", b"INFO")]);
+
+    let updated = put_label_with_options(&original, "First", options());
+    let updated = put_label_with_options(&updated, "Second, longer", options());
+
+    assert_eq!(fourccs(&updated), ["fmt ", "data", "LIST", "_PMX"]);
+    // The other chunks don't move.
+    assert_eq!(&updated[12..original.len()], &original[12..]);
+
+    let xmp = xmp(&updated).unwrap();
+    assert_eq!(
+        xmp.property(xmp_ns::XMP, "Label").unwrap().value,
+        "Second, longer"
+    );
+}
+
+#[test]
+fn import_bext_and_ixml() {
+    let bext = bext("Take notes", "Recorder", "2026-03-04", "10:20:30");
+    let ixml = b"<?xml version=\"1.0\"?><BWFXML><SCENE>12A</SCENE>\
+        <TAKE>3</TAKE><TAPE>Day 1</TAPE><NOTE>Wind &amp; rain</NOTE></BWFXML>";
+
+    let xmp = xmp(&wav(&[
+        (b"bext", &bext),
+        (b"iXML", ixml),
+        (b"fmt ", FMT),
+        (b"data", DATA),
+    ]))
+    .unwrap();
+
+    assert_eq!(dm(&xmp, "scene").as_deref(), Some("12A"));
+    assert_eq!(dm(&xmp, "takeNumber").as_deref(), Some("3"));
+    assert_eq!(dm(&xmp, "tapeName").as_deref(), Some("Day 1"));
+    assert_eq!(dm(&xmp, "logComment").as_deref(), Some("Wind & rain"));
+    assert_eq!(dm(&xmp, "engineer").as_deref(), Some("Recorder"));
+    assert!(dm(&xmp, "shotDate")
+        .unwrap()
+        .starts_with("2026-03-04T10:20:30"));
+}
+
+#[test]
+fn bext_description_without_ixml() {
+    let bext = bext("Take notes", "", "2026:03:04", "");
+    let xmp = xmp(&wav(&[(b"bext", &bext), (b"fmt ", FMT), (b"data", DATA)])).unwrap();
+
+    assert_eq!(dm(&xmp, "logComment").as_deref(), Some("Take notes"));
+    assert_eq!(dm(&xmp, "engineer"), None);
+    assert!(dm(&xmp, "shotDate").unwrap().starts_with("2026-03-04"));
+}
+
+#[test]
+fn xmp_takes_precedence() {
+    let bext = bext("From bext", "", "", "");
+    let original = wav(&[(b"bext", &bext), (b"fmt ", FMT), (b"data", DATA)]);
+
+    let mut meta = XmpMeta::new().unwrap();
+    meta.set_property(xmp_ns::DYNAMIC_MEDIA, "logComment", &"From XMP".into())
+        .unwrap();
+
+    let mut f = XmpFile::from_bytes(&original, FileFormat::Wav, options().for_update()).unwrap();
+    f.put_xmp(&meta).unwrap();
+    let updated = f.into_bytes().unwrap();
+
+    // The bext chunk isn't modified.
+    assert_eq!(
+        &updated[12..12 + 8 + bext.len()],
+        &original[12..12 + 8 + bext.len()]
+    );
+
+    let xmp = xmp(&updated).unwrap();
+    assert_eq!(dm(&xmp, "logComment").as_deref(), Some("From XMP"));
+}
+
+#[test]
+fn truncated_chunk() {
+    let mut wav = wav(&[(b"fmt ", FMT), (b"data", DATA)]);
+    wav.truncate(30);
+
+    assert!(XmpFile::from_bytes(&wav, FileFormat::Wav, options()).is_err());
+}
+
+#[test]
+fn sample_file() {
+    let tempdir = tempdir().unwrap();
+    let sample = temp_copy_of_fixture(tempdir.path(), "sample.wav");
+
+    assert_eq!(label_of_file(&sample).as_deref(), Some("Sample"));
+
+    put_label_in_file(&sample, "Updated");
+    assert_eq!(label_of_file(&sample).as_deref(), Some("Updated"));
+
+    // The format and audio data are unchanged.
+    assert_eq!(
+        fs::read(&sample).unwrap()[..60],
+        fs::read(fixture_path("sample.wav")).unwrap()[..60]
+    );
+}
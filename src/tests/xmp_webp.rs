@@ -0,0 +1,586 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::fs;
+
+use tempfile::tempdir;
+
+use crate::{
+    tests::fixtures::*, FileFormat, OpenFileOptions, PacketPadding, PutXmpBlocker, ToStringOptions,
+    XmpErrorType, XmpFile,
+};
+
+/// Returns a WebP file in the simple lossless format. The image data is
+/// not valid, but the header describes a 100 x 1 image.
+fn simple_webp(alpha: bool) -> Vec<u8> {
+    let payload = [0x2f, 0x63, 0x00, 0x00, if alpha { 0x10 } else { 0x00 }];
+    riff(&[(b"VP8L", &payload)])
+}
+
+fn riff(chunks: &[(&[u8; 4], &[u8])]) -> Vec<u8> {
+    let mut body = b"WEBP".to_vec();
+
+    for (fourcc, data) in chunks {
+        body.extend_from_slice(*fourcc);
+        body.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        body.extend_from_slice(data);
+        if data.len() % 2 == 1 {
+            body.push(0);
+        }
+    }
+
+    let mut file = b"RIFF".to_vec();
+    file.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    file.extend_from_slice(&body);
+    file
+}
+
+/// Returns the four-character codes and payloads of the chunks in `file`.
+fn chunks(file: &[u8]) -> Vec<(String, Vec<u8>)> {
+    assert_eq!(
+        u32::from_le_bytes(file[4..8].try_into().unwrap()) as usize,
+        file.len() - 8
+    );
+
+    let mut chunks = Vec::new();
+    let mut pos = 12;
+
+    while pos < file.len() {
+        let fourcc = String::from_utf8_lossy(&file[pos..pos + 4]).into_owned();
+        let len = u32::from_le_bytes(file[pos + 4..pos + 8].try_into().unwrap()) as usize;
+        chunks.push((fourcc, file[pos + 8..pos + 8 + len].to_vec()));
+        pos += 8 + len + len % 2;
+    }
+
+    chunks
+}
+
+#[test]
+fn check_format() {
+    assert_eq!(
+        XmpFile::check_buffer_format(&simple_webp(false)).unwrap(),
+        Some(FileFormat::WebP)
+    );
+}
+
+#[test]
+fn no_xmp() {
+    assert_eq!(label(&simple_webp(false), FileFormat::WebP), None);
+}
+
+#[test]
+fn add_xmp_to_simple_format() {
+    let webp = put_label(&simple_webp(false), "Added");
+    let chunks = chunks(&webp);
+
+    let names: Vec<&str> = chunks.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["VP8X", "VP8L", "XMP "]);
+
+    // XMP flag set; canvas is 100 x 1 (stored as width - 1 and height - 1).
+    assert_eq!(chunks[0].1, [0x04, 0, 0, 0, 99, 0, 0, 0, 0, 0]);
+
+    assert_eq!(label(&webp, FileFormat::WebP).as_deref(), Some("Added"));
+}
+
+#[test]
+fn alpha_flag_from_lossless_header() {
+    let webp = put_label(&simple_webp(true), "Alpha");
+    assert_eq!(chunks(&webp)[0].1[0], 0x14);
+}
+
+#[test]
+fn replace_xmp() {
+    let webp = put_label(&simple_webp(false), "First");
+    let webp = put_label(&webp, "Second");
+
+    let xmp_chunks = chunks(&webp)
+        .into_iter()
+        .filter(|(name, _)| name == "XMP ")
+        .count();
+
+    assert_eq!(xmp_chunks, 1);
+    assert_eq!(label(&webp, FileFormat::WebP).as_deref(), Some("Second"));
+}
+
+#[test]
+fn extended_format_keeps_other_chunks() {
+    let vp8x = [0x10, 0, 0, 0, 9, 0, 0, 9, 0, 0];
+    let webp = riff(&[
+        (b"VP8X", &vp8x),
+        (b"ALPH", &[1, 2, 3]),
+        (b"VP8 ", &[0; 10]),
+        (b"EXIF", &[4, 5]),
+    ]);
+
+    let updated = put_label(&webp, "Extended");
+    let chunks = chunks(&updated);
+
+    let names: Vec<&str> = chunks.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["VP8X", "ALPH", "VP8 ", "EXIF", "XMP "]);
+    assert_eq!(chunks[0].1, [0x14, 0, 0, 0, 9, 0, 0, 9, 0, 0]);
+    assert_eq!(chunks[1].1, [1, 2, 3]);
+}
+
+#[test]
+fn packet_info() {
+    let webp = put_label(&simple_webp(false), "Info");
+
+    let mut f = XmpFile::from_bytes(&webp, FileFormat::WebP, OpenFileOptions::default()).unwrap();
+    let info = f.packet_info().unwrap();
+    let range = info.range().unwrap();
+
+    assert!(webp[range.start as usize..].starts_with(b"<?xpacket begin="));
+    assert_eq!(range.end as usize, webp.len());
+    assert!(info.has_wrapper);
+    assert!(info.writable);
+}
+
+#[test]
+fn raw_xmp_packet() {
+    let webp = put_label(&simple_webp(false), "Raw");
+
+    let mut f = XmpFile::from_bytes(&webp, FileFormat::WebP, OpenFileOptions::default()).unwrap();
+    assert_eq!(f.raw_xmp_packet().unwrap(), xmp_chunk(&webp).into_bytes());
+}
+
+#[test]
+fn put_raw_packet() {
+    // Hand-written, so that serializing it again would change it.
+    let packet = "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\
+        <x:xmpmeta xmlns:x=\"adobe:ns:meta/\"><rdf:RDF \
+        xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\
+        <rdf:Description rdf:about=\"\" xmlns:xmp=\"http://ns.adobe.com/xap/1.0/\" \
+        xmp:Label=\"Signed\"/></rdf:RDF></x:xmpmeta>   <?xpacket end=\"r\"?>";
+
+    let mut f = XmpFile::from_bytes(
+        &simple_webp(false),
+        FileFormat::WebP,
+        OpenFileOptions::default().for_update(),
+    )
+    .unwrap();
+
+    f.put_raw_packet(packet.as_bytes()).unwrap();
+    assert_eq!(f.raw_xmp_packet().unwrap(), packet.as_bytes());
+
+    let webp = f.into_bytes().unwrap();
+    assert_eq!(xmp_chunk(&webp), packet);
+    assert_eq!(label(&webp, FileFormat::WebP).as_deref(), Some("Signed"));
+}
+
+#[test]
+fn put_raw_packet_in_place() {
+    let webp = put_label(&simple_webp(false), "First");
+    let existing = xmp_chunk(&webp);
+
+    let mut f = XmpFile::from_bytes(
+        &webp,
+        FileFormat::WebP,
+        OpenFileOptions::default()
+            .for_update()
+            .update_xmp_in_place(),
+    )
+    .unwrap();
+
+    let shorter = existing.replacen("First", "1st", 1);
+    assert_eq!(
+        f.put_raw_packet(shorter.as_bytes()).unwrap_err().error_type,
+        XmpErrorType::XmpUpdateNotInPlace
+    );
+
+    let same_length = existing.replacen("First", "Other", 1);
+    f.put_raw_packet(same_length.as_bytes()).unwrap();
+
+    let updated = f.into_bytes().unwrap();
+    assert_eq!(updated.len(), webp.len());
+    assert_eq!(xmp_chunk(&updated), same_length);
+}
+
+#[test]
+fn put_raw_packet_without_wrapper() {
+    let mut f = XmpFile::from_bytes(
+        &simple_webp(false),
+        FileFormat::WebP,
+        OpenFileOptions::default().for_update(),
+    )
+    .unwrap();
+
+    let unwrapped = meta_with_label("Bare")
+        .to_string_with_options(ToStringOptions::default().omit_packet_wrapper())
+        .unwrap();
+
+    assert_eq!(
+        f.put_raw_packet(unwrapped.as_bytes())
+            .unwrap_err()
+            .error_type,
+        XmpErrorType::BadXmp
+    );
+
+    let wrapped = meta_with_label("Trailing").to_string();
+    let trailing = format!("{wrapped}\0");
+    assert_eq!(
+        f.put_raw_packet(trailing.as_bytes())
+            .unwrap_err()
+            .error_type,
+        XmpErrorType::BadXmp
+    );
+}
+
+#[test]
+fn put_xmp_from_str() {
+    let packet = "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\
+        <x:xmpmeta xmlns:x=\"adobe:ns:meta/\"><rdf:RDF \
+        xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\
+        <rdf:Description rdf:about=\"\" xmlns:xmp=\"http://ns.adobe.com/xap/1.0/\" \
+        xmp:Label=\"Canonical\"/></rdf:RDF></x:xmpmeta>   <?xpacket end=\"w\"?>";
+
+    let mut f = XmpFile::from_bytes(
+        &simple_webp(false),
+        FileFormat::WebP,
+        OpenFileOptions::default().for_update(),
+    )
+    .unwrap();
+
+    // A complete packet is written as is.
+    f.put_xmp_from_str(packet).unwrap();
+    let webp = f.into_bytes().unwrap();
+    assert_eq!(xmp_chunk(&webp), packet);
+
+    // Other XMP is serialized again.
+    let mut f = XmpFile::from_bytes(
+        &webp,
+        FileFormat::WebP,
+        OpenFileOptions::default().for_update(),
+    )
+    .unwrap();
+
+    let bare = meta_with_label("Bare")
+        .to_string_with_options(ToStringOptions::default().omit_packet_wrapper())
+        .unwrap();
+    f.put_xmp_from_str(&bare).unwrap();
+
+    let webp = f.into_bytes().unwrap();
+    assert!(xmp_chunk(&webp).starts_with("<?xpacket begin="));
+    assert_eq!(label(&webp, FileFormat::WebP).as_deref(), Some("Bare"));
+}
+
+#[test]
+fn put_xmp_from_str_in_place() {
+    let webp = put_label(&simple_webp(false), "First");
+
+    let mut f = XmpFile::from_bytes(
+        &webp,
+        FileFormat::WebP,
+        OpenFileOptions::default()
+            .for_update()
+            .update_xmp_in_place(),
+    )
+    .unwrap();
+
+    // The packet doesn't have the existing length, so it is serialized
+    // again to fit.
+    f.put_xmp_from_str(&meta_with_label("Second").to_string())
+        .unwrap();
+
+    let updated = f.into_bytes().unwrap();
+    assert_eq!(updated.len(), webp.len());
+    assert_eq!(label(&updated, FileFormat::WebP).as_deref(), Some("Second"));
+}
+
+#[test]
+fn read_only() {
+    let mut f = XmpFile::from_bytes(
+        &simple_webp(false),
+        FileFormat::Unknown,
+        OpenFileOptions::default(),
+    )
+    .unwrap();
+
+    let meta = meta_with_label("Nope");
+    assert!(!f.can_put_xmp(&meta));
+    assert_eq!(
+        f.put_xmp(&meta).unwrap_err().error_type,
+        XmpErrorType::BadParam
+    );
+}
+
+#[test]
+fn truncated_chunk() {
+    let mut webp = simple_webp(false);
+    webp.truncate(webp.len() - 3);
+    let riff_len = webp.len() as u32 - 8;
+    webp[4..8].copy_from_slice(&riff_len.to_le_bytes());
+
+    let err = XmpFile::from_bytes(&webp, FileFormat::Unknown, OpenFileOptions::default())
+        .err()
+        .unwrap();
+
+    assert_eq!(err.error_type, XmpErrorType::BadFileFormat);
+}
+
+#[test]
+fn truncated_vp8x() {
+    for vp8x in [&[][..], &[0x10, 0, 0, 0]] {
+        let webp = riff(&[(b"VP8X", vp8x), (b"VP8 ", &[0; 10])]);
+
+        let mut f = XmpFile::from_bytes(
+            &webp,
+            FileFormat::Unknown,
+            OpenFileOptions::default().for_update(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            f.put_xmp(&meta_with_label("Truncated"))
+                .unwrap_err()
+                .error_type,
+            XmpErrorType::BadFileFormat
+        );
+    }
+}
+
+#[test]
+fn update_file() {
+    let tempdir = tempdir().unwrap();
+    let path = tempdir.path().join("image.webp");
+    fs::write(&path, simple_webp(false)).unwrap();
+
+    assert_eq!(
+        XmpFile::check_file_format(&path).unwrap(),
+        Some(FileFormat::WebP)
+    );
+
+    let mut f = XmpFile::new().unwrap();
+    f.open_file(&path, OpenFileOptions::default().for_update())
+        .unwrap();
+    assert!(f.xmp().is_none());
+
+    f.put_xmp(&meta_with_label("On disk")).unwrap();
+    f.close().unwrap();
+
+    assert_eq!(
+        label(&fs::read(&path).unwrap(), FileFormat::WebP).as_deref(),
+        Some("On disk")
+    );
+    assert_eq!(fs::read_dir(tempdir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn read_only_file() {
+    let tempdir = tempdir().unwrap();
+    let path = tempdir.path().join("image.webp");
+    fs::write(&path, simple_webp(false)).unwrap();
+
+    let meta = meta_with_label("Blocked");
+
+    let mut f = XmpFile::new().unwrap();
+    f.open_file(&path, OpenFileOptions::default()).unwrap();
+    assert_eq!(
+        f.put_xmp_blocker(&meta),
+        Some(PutXmpBlocker::NotOpenedForUpdate)
+    );
+
+    let mut permissions = fs::metadata(&path).unwrap().permissions();
+    permissions.set_readonly(true);
+    fs::set_permissions(&path, permissions).unwrap();
+
+    let mut f = XmpFile::new().unwrap();
+    f.open_file(&path, OpenFileOptions::default().for_update())
+        .unwrap();
+    assert_eq!(f.put_xmp_blocker(&meta), Some(PutXmpBlocker::ReadOnlyFile));
+    assert!(!f.can_put_xmp(&meta));
+}
+
+#[test]
+fn update_file_with_temp_folder() {
+    let scratch = tempdir().unwrap();
+    let tempdir = tempdir().unwrap();
+    let path = tempdir.path().join("image.webp");
+    fs::write(&path, simple_webp(false)).unwrap();
+
+    let mut f = XmpFile::new().unwrap();
+    f.open_file(
+        &path,
+        OpenFileOptions::default()
+            .for_update()
+            .temp_folder(scratch.path()),
+    )
+    .unwrap();
+
+    f.put_xmp(&meta_with_label("Via scratch")).unwrap();
+    f.close().unwrap();
+
+    assert_eq!(
+        label(&fs::read(&path).unwrap(), FileFormat::WebP).as_deref(),
+        Some("Via scratch")
+    );
+    assert_eq!(fs::read_dir(tempdir.path()).unwrap().count(), 1);
+    assert_eq!(fs::read_dir(scratch.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn update_in_place() {
+    let webp = put_label(&simple_webp(false), "First");
+
+    let mut f = XmpFile::from_bytes(
+        &webp,
+        FileFormat::Unknown,
+        OpenFileOptions::default()
+            .for_update()
+            .update_xmp_in_place(),
+    )
+    .unwrap();
+
+    let range = f.packet_info().unwrap().range().unwrap();
+    f.put_xmp(&meta_with_label("Second")).unwrap();
+    let updated = f.into_bytes().unwrap();
+
+    assert_eq!(updated.len(), webp.len());
+    assert_eq!(
+        updated[..range.start as usize],
+        webp[..range.start as usize]
+    );
+    assert_eq!(label(&updated, FileFormat::WebP).as_deref(), Some("Second"));
+}
+
+#[test]
+fn update_in_place_without_xmp() {
+    let mut f = XmpFile::from_bytes(
+        &simple_webp(false),
+        FileFormat::Unknown,
+        OpenFileOptions::default()
+            .for_update()
+            .update_xmp_in_place(),
+    )
+    .unwrap();
+
+    let meta = meta_with_label("Nowhere");
+    assert_eq!(
+        f.put_xmp_blocker(&meta),
+        Some(PutXmpBlocker::ReadOnlyFormat)
+    );
+    assert_eq!(
+        f.put_xmp(&meta).unwrap_err().error_type,
+        XmpErrorType::XmpUpdateNotInPlace
+    );
+}
+
+#[test]
+fn update_in_place_too_large() {
+    let webp = put_label(&simple_webp(false), "First");
+
+    let mut f = XmpFile::from_bytes(
+        &webp,
+        FileFormat::Unknown,
+        OpenFileOptions::default()
+            .for_update()
+            .update_xmp_in_place(),
+    )
+    .unwrap();
+
+    assert!(f.can_put_xmp(&meta_with_label("Second")));
+
+    let meta = meta_with_label(&"x".repeat(100_000));
+    assert_eq!(
+        f.put_xmp_blocker(&meta),
+        Some(PutXmpBlocker::PacketTooLarge)
+    );
+    assert_eq!(
+        f.put_xmp(&meta).unwrap_err().error_type,
+        XmpErrorType::XmpUpdateNotInPlace
+    );
+}
+
+fn put_label_with_padding(webp: &[u8], label: &str, padding: PacketPadding) -> Vec<u8> {
+    let mut f = XmpFile::from_bytes(
+        webp,
+        FileFormat::Unknown,
+        OpenFileOptions::default().for_update(),
+    )
+    .unwrap();
+
+    f.put_xmp_with_padding(&meta_with_label(label), padding)
+        .unwrap();
+    f.into_bytes().unwrap()
+}
+
+fn xmp_chunk(webp: &[u8]) -> String {
+    let (_, packet) = chunks(webp)
+        .into_iter()
+        .find(|(name, _)| name == "XMP ")
+        .unwrap();
+
+    String::from_utf8(packet).unwrap()
+}
+
+#[test]
+fn exact_padding() {
+    let webp = put_label_with_padding(&simple_webp(false), "Exact", PacketPadding::Exact);
+    let packet = xmp_chunk(&webp);
+
+    assert!(packet.ends_with("</x:xmpmeta>\n<?xpacket end=\"w\"?>"));
+    assert_eq!(label(&webp, FileFormat::WebP).as_deref(), Some("Exact"));
+}
+
+#[test]
+fn fixed_padding() {
+    let exact = xmp_chunk(&put_label_with_padding(
+        &simple_webp(false),
+        "Fixed",
+        PacketPadding::Exact,
+    ));
+
+    let webp = put_label_with_padding(&simple_webp(false), "Fixed", PacketPadding::Bytes(250));
+    let packet = xmp_chunk(&webp);
+
+    assert_eq!(packet.len(), exact.len() + 250);
+    assert!(packet
+        .lines()
+        .all(|line| !line.trim().is_empty() || line.len() <= 100));
+    assert_eq!(label(&webp, FileFormat::WebP).as_deref(), Some("Fixed"));
+
+    // The same XMP always produces the same file.
+    assert_eq!(
+        webp,
+        put_label_with_padding(&simple_webp(false), "Fixed", PacketPadding::Bytes(250))
+    );
+}
+
+#[test]
+fn percent_padding() {
+    let exact = xmp_chunk(&put_label_with_padding(
+        &simple_webp(false),
+        "Headroom",
+        PacketPadding::Exact,
+    ));
+
+    let webp = put_label_with_padding(&simple_webp(false), "Headroom", PacketPadding::Percent(50));
+
+    assert_eq!(xmp_chunk(&webp).len(), exact.len() + exact.len() / 2);
+    assert_eq!(label(&webp, FileFormat::WebP).as_deref(), Some("Headroom"));
+}
+
+#[test]
+fn sample_file() {
+    let tempdir = tempdir().unwrap();
+    let sample = temp_copy_of_fixture(tempdir.path(), "sample.webp");
+
+    assert_eq!(label_of_file(&sample).as_deref(), Some("Sample"));
+
+    put_label_in_file(&sample, "Updated");
+    assert_eq!(label_of_file(&sample).as_deref(), Some("Updated"));
+
+    // The image data is unchanged.
+    let original = chunks(&fs::read(fixture_path("sample.webp")).unwrap());
+    let updated = chunks(&fs::read(&sample).unwrap());
+    assert_eq!(updated[..2], original[..2]);
+}
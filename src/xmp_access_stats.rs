@@ -0,0 +1,110 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::{collections::BTreeMap, fmt};
+
+use crate::XmpMeta;
+
+/// Counts of how often each property was read or written through an
+/// [`XmpMeta`] instance.
+///
+/// Enable collection with [`XmpMeta::enable_access_stats`] and retrieve
+/// the counts with [`XmpMeta::access_stats`].
+///
+/// Properties are identified by the namespace URI and path that were passed
+/// to the accessor. For accessors that address a part of a property (struct
+/// fields, array items, qualifiers, and localized text), the access is
+/// counted against the containing property.
+///
+/// The [`Display`](fmt::Display) implementation produces a human-readable
+/// report with the most frequently accessed properties first.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AccessStats {
+    counts: BTreeMap<(String, String), AccessCount>,
+}
+
+/// The number of reads and writes of a single property.
+///
+/// See [`AccessStats`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AccessCount {
+    /// The number of times the property was read.
+    pub reads: u64,
+
+    /// The number of times the property was created, modified, or deleted.
+    pub writes: u64,
+}
+
+impl AccessStats {
+    /// Returns the access counts for a property.
+    ///
+    /// Returns zero counts if the property was never accessed.
+    pub fn get(&self, namespace: &str, path: &str) -> AccessCount {
+        self.counts
+            .get(&(namespace.to_owned(), path.to_owned()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if no property accesses have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Iterates over all accessed properties as (namespace URI, path,
+    /// counts), ordered by namespace URI and then by path.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str, AccessCount)> {
+        self.counts
+            .iter()
+            .map(|((ns, path), count)| (ns.as_str(), path.as_str(), *count))
+    }
+
+    pub(crate) fn record_read(&mut self, namespace: &str, path: &str) {
+        self.entry(namespace, path).reads += 1;
+    }
+
+    pub(crate) fn record_write(&mut self, namespace: &str, path: &str) {
+        self.entry(namespace, path).writes += 1;
+    }
+
+    fn entry(&mut self, namespace: &str, path: &str) -> &mut AccessCount {
+        self.counts
+            .entry((namespace.to_owned(), path.to_owned()))
+            .or_default()
+    }
+}
+
+impl fmt::Display for AccessStats {
+    /// Writes one line per property, most frequently accessed first, in the
+    /// form `<reads> <writes> <prefix>:<path>`.
+    ///
+    /// If the namespace has no registered prefix, the namespace URI is
+    /// shown in braces instead.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut entries: Vec<(&str, &str, AccessCount)> = self.iter().collect();
+        entries.sort_by_key(|(_, _, count)| std::cmp::Reverse(count.reads + count.writes));
+
+        writeln!(f, "{:>10} {:>10}  property", "reads", "writes")?;
+
+        for (ns, path, count) in entries {
+            let prefix = XmpMeta::namespace_prefix(ns).unwrap_or_else(|| format!("{{{ns}}}"));
+            writeln!(
+                f,
+                "{:>10} {:>10}  {prefix}{path}",
+                count.reads, count.writes
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,234 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use crate::xmp_ns;
+
+const PNG: &str = "http://ns.adobe.com/png/1.0/";
+
+/// An alias registered with the C++ XMP Toolkit, as returned by
+/// [`XmpMeta::aliases`].
+///
+/// An alias is a property name that is stored under another name, its
+/// actual property. When XMP is parsed, aliased properties are moved to
+/// their actual property, and reading or writing an alias reads or writes
+/// the actual property instead. If a packet contains both an alias and its
+/// actual property, only one value is kept; see
+/// [`FromStrOptions::strict_aliasing`].
+///
+/// [`XmpMeta::aliases`]: crate::XmpMeta::aliases
+/// [`FromStrOptions::strict_aliasing`]: crate::FromStrOptions::strict_aliasing
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct XmpAlias {
+    /// The namespace URI of the alias.
+    pub alias_ns: &'static str,
+
+    /// The name of the alias.
+    pub alias_name: &'static str,
+
+    /// The namespace URI of the actual property.
+    pub actual_ns: &'static str,
+
+    /// The name of the actual property.
+    pub actual_name: &'static str,
+
+    /// Which part of the actual property the alias stands for.
+    pub form: AliasForm,
+}
+
+impl XmpAlias {
+    /// Returns the path, relative to [`XmpAlias::actual_ns`], of the value
+    /// that the alias stands for.
+    ///
+    /// This can be passed to [`XmpMeta::property`] to read the value.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use xmp_toolkit::{xmp_ns, XmpMeta};
+    /// let alias = XmpMeta::aliases()
+    ///     .find(|alias| alias.alias_ns == xmp_ns::PDF && alias.alias_name == "Title")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(alias.actual_path(), "title[?xml:lang=\"x-default\"]");
+    /// ```
+    ///
+    /// [`XmpMeta::property`]: crate::XmpMeta::property
+    pub fn actual_path(&self) -> String {
+        match self.form {
+            AliasForm::Direct => self.actual_name.to_owned(),
+            AliasForm::FirstItem => format!("{}[1]", self.actual_name),
+            AliasForm::DefaultLanguage => {
+                format!("{}[?xml:lang=\"x-default\"]", self.actual_name)
+            }
+        }
+    }
+}
+
+/// Which part of its actual property an [`XmpAlias`] stands for.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum AliasForm {
+    /// The whole actual property, which has the same form as the alias.
+    Direct,
+
+    /// The first item of the actual property, which is an ordered array.
+    FirstItem,
+
+    /// The `x-default` item of the actual property, which is an
+    /// alternative-text array.
+    DefaultLanguage,
+}
+
+const fn alias(
+    alias_ns: &'static str,
+    alias_name: &'static str,
+    actual_ns: &'static str,
+    actual_name: &'static str,
+    form: AliasForm,
+) -> XmpAlias {
+    XmpAlias {
+        alias_ns,
+        alias_name,
+        actual_ns,
+        actual_name,
+        form,
+    }
+}
+
+/// The aliases that the C++ XMP Toolkit registers when it is initialized.
+/// See `RegisterStandardAliases` in `XMPMeta.cpp`.
+pub(crate) const STANDARD_ALIASES: &[XmpAlias] = {
+    use AliasForm::*;
+
+    &[
+        // XMP to Dublin Core.
+        alias(xmp_ns::XMP, "Author", xmp_ns::DC, "creator", FirstItem),
+        alias(xmp_ns::XMP, "Authors", xmp_ns::DC, "creator", Direct),
+        alias(
+            xmp_ns::XMP,
+            "Description",
+            xmp_ns::DC,
+            "description",
+            Direct,
+        ),
+        alias(xmp_ns::XMP, "Format", xmp_ns::DC, "format", Direct),
+        alias(xmp_ns::XMP, "Keywords", xmp_ns::DC, "subject", Direct),
+        alias(xmp_ns::XMP, "Locale", xmp_ns::DC, "language", Direct),
+        alias(xmp_ns::XMP, "Title", xmp_ns::DC, "title", Direct),
+        alias(
+            xmp_ns::XMP_RIGHTS,
+            "Copyright",
+            xmp_ns::DC,
+            "rights",
+            Direct,
+        ),
+        // PDF to Dublin Core and XMP.
+        alias(xmp_ns::PDF, "Author", xmp_ns::DC, "creator", FirstItem),
+        alias(xmp_ns::PDF, "BaseURL", xmp_ns::XMP, "BaseURL", Direct),
+        alias(
+            xmp_ns::PDF,
+            "CreationDate",
+            xmp_ns::XMP,
+            "CreateDate",
+            Direct,
+        ),
+        alias(xmp_ns::PDF, "Creator", xmp_ns::XMP, "CreatorTool", Direct),
+        alias(xmp_ns::PDF, "ModDate", xmp_ns::XMP, "ModifyDate", Direct),
+        alias(
+            xmp_ns::PDF,
+            "Subject",
+            xmp_ns::DC,
+            "description",
+            DefaultLanguage,
+        ),
+        alias(xmp_ns::PDF, "Title", xmp_ns::DC, "title", DefaultLanguage),
+        // Photoshop to Dublin Core and XMP.
+        alias(
+            xmp_ns::PHOTOSHOP,
+            "Author",
+            xmp_ns::DC,
+            "creator",
+            FirstItem,
+        ),
+        alias(
+            xmp_ns::PHOTOSHOP,
+            "Caption",
+            xmp_ns::DC,
+            "description",
+            DefaultLanguage,
+        ),
+        alias(
+            xmp_ns::PHOTOSHOP,
+            "Copyright",
+            xmp_ns::DC,
+            "rights",
+            DefaultLanguage,
+        ),
+        alias(xmp_ns::PHOTOSHOP, "Keywords", xmp_ns::DC, "subject", Direct),
+        alias(
+            xmp_ns::PHOTOSHOP,
+            "Marked",
+            xmp_ns::XMP_RIGHTS,
+            "Marked",
+            Direct,
+        ),
+        alias(
+            xmp_ns::PHOTOSHOP,
+            "Title",
+            xmp_ns::DC,
+            "title",
+            DefaultLanguage,
+        ),
+        alias(
+            xmp_ns::PHOTOSHOP,
+            "WebStatement",
+            xmp_ns::XMP_RIGHTS,
+            "WebStatement",
+            Direct,
+        ),
+        // TIFF and Exif to Dublin Core and XMP.
+        alias(xmp_ns::TIFF, "Artist", xmp_ns::DC, "creator", FirstItem),
+        alias(xmp_ns::TIFF, "Copyright", xmp_ns::DC, "rights", Direct),
+        alias(xmp_ns::TIFF, "DateTime", xmp_ns::XMP, "ModifyDate", Direct),
+        alias(
+            xmp_ns::EXIF,
+            "DateTimeDigitized",
+            xmp_ns::XMP,
+            "CreateDate",
+            Direct,
+        ),
+        alias(
+            xmp_ns::TIFF,
+            "ImageDescription",
+            xmp_ns::DC,
+            "description",
+            Direct,
+        ),
+        alias(xmp_ns::TIFF, "Software", xmp_ns::XMP, "CreatorTool", Direct),
+        // PNG to Dublin Core and XMP.
+        alias(PNG, "Author", xmp_ns::DC, "creator", FirstItem),
+        alias(PNG, "Copyright", xmp_ns::DC, "rights", DefaultLanguage),
+        alias(PNG, "CreationTime", xmp_ns::XMP, "CreateDate", Direct),
+        alias(
+            PNG,
+            "Description",
+            xmp_ns::DC,
+            "description",
+            DefaultLanguage,
+        ),
+        alias(PNG, "ModificationTime", xmp_ns::XMP, "ModifyDate", Direct),
+        alias(PNG, "Software", xmp_ns::XMP, "CreatorTool", Direct),
+        alias(PNG, "Title", xmp_ns::DC, "title", DefaultLanguage),
+    ]
+};
@@ -0,0 +1,219 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+// Implementations of `arbitrary::Arbitrary` for use by fuzz targets.
+//
+// All generated values are valid: dates are real calendar dates, strings
+// contain only characters that survive an XML round trip, and metadata
+// trees use only registered namespaces and well-formed property names.
+
+use arbitrary::{Arbitrary, Error, Result, Unstructured};
+
+use crate::{xmp_ns, XmpDate, XmpDateTime, XmpMeta, XmpResult, XmpTime, XmpTimeZone, XmpValue};
+
+/// Namespaces used for generated properties. These have no aliases, so any
+/// property name is safe to set.
+const NAMESPACES: &[&str] = &[
+    xmp_ns::DC,
+    xmp_ns::XMP_RIGHTS,
+    xmp_ns::XMP_MM,
+    xmp_ns::EXIF,
+    xmp_ns::IPTC_CORE,
+    xmp_ns::IPTC_EXT,
+    xmp_ns::CAMERA_RAW,
+    xmp_ns::DYNAMIC_MEDIA,
+];
+
+const LANGUAGES: &[&str] = &["en-US", "en-GB", "de-DE", "fr-FR", "ja-JP", "pt-BR"];
+
+const MAX_PROPERTIES: usize = 16;
+const MAX_ITEMS: usize = 8;
+const MAX_DEPTH: u32 = 3;
+
+impl<'a> Arbitrary<'a> for XmpDate {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let year = u.int_in_range(0..=9999)?;
+        let month = u.int_in_range(1..=12)?;
+        let day = u.int_in_range(1..=days_in_month(year, month))?;
+
+        Ok(Self { year, month, day })
+    }
+}
+
+impl<'a> Arbitrary<'a> for XmpTimeZone {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            hour: u.int_in_range(-23..=23)?,
+            minute: u.int_in_range(0..=59)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for XmpTime {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            hour: u.int_in_range(0..=23)?,
+            minute: u.int_in_range(0..=59)?,
+            second: u.int_in_range(0..=59)?,
+            nanosecond: u.int_in_range(0..=999_999_999)?,
+            time_zone: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for XmpDateTime {
+    /// Generates a date with an optional time. A time is only generated
+    /// together with a date, since XMP can not express a time alone.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let date: Option<XmpDate> = u.arbitrary()?;
+        let time = match date {
+            Some(_) => u.arbitrary()?,
+            None => None,
+        };
+
+        Ok(Self { date, time })
+    }
+}
+
+impl<'a> Arbitrary<'a> for XmpValue<String> {
+    /// Generates a simple property value, which may be flagged as a URI.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(XmpValue::new(xml_text(u)?).set_is_uri(u.arbitrary()?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for XmpMeta {
+    /// Generates a tree of up to 16 top-level properties, including
+    /// simple values, arrays, language alternatives, and nested structs.
+    ///
+    /// Returns [`Error::IncorrectFormat`] if the C++ XMP Toolkit is
+    /// unavailable.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut meta = XmpMeta::new().map_err(|_| Error::IncorrectFormat)?;
+
+        let count = u.int_in_range(0..=MAX_PROPERTIES)?;
+        for _ in 0..count {
+            let ns = *u.choose(NAMESPACES)?;
+            let name = xml_name(u)?;
+            add_node(&mut meta, u, ns, &name, 0)?;
+        }
+
+        Ok(meta)
+    }
+}
+
+fn add_node(
+    meta: &mut XmpMeta,
+    u: &mut Unstructured,
+    ns: &str,
+    path: &str,
+    depth: u32,
+) -> Result<()> {
+    // A generated name may repeat; replace rather than extend the earlier
+    // node, which might be of a different kind.
+    check(meta.delete_property(ns, path))?;
+
+    let max_kind = if depth < MAX_DEPTH { 3 } else { 2 };
+
+    match u.int_in_range(0..=max_kind)? {
+        0 => {
+            let value: XmpValue<String> = u.arbitrary()?;
+            check(meta.set_property(ns, path, &value))
+        }
+
+        1 => {
+            let array_name = match u.int_in_range(0..=2)? {
+                0 => XmpValue::from(path).set_is_array(true),
+                1 => XmpValue::from(path).set_is_array(true).set_is_ordered(true),
+                _ => XmpValue::from(path)
+                    .set_is_array(true)
+                    .set_is_ordered(true)
+                    .set_is_alternate(true),
+            };
+
+            let count = u.int_in_range(1..=MAX_ITEMS)?;
+            for _ in 0..count {
+                let item: XmpValue<String> = u.arbitrary()?;
+                check(meta.append_array_item(ns, &array_name, &item))?;
+            }
+
+            Ok(())
+        }
+
+        2 => {
+            let count = u.int_in_range(1..=LANGUAGES.len())?;
+            for lang in &LANGUAGES[..count] {
+                let text = xml_text(u)?;
+                check(meta.set_localized_text(ns, path, None, lang, &text))?;
+            }
+
+            Ok(())
+        }
+
+        _ => {
+            let count = u.int_in_range(1..=MAX_ITEMS)?;
+            for _ in 0..count {
+                let field_name = xml_name(u)?;
+                let field_path = check(XmpMeta::compose_struct_field_path(
+                    ns,
+                    path,
+                    ns,
+                    &field_name,
+                ))?;
+                add_node(meta, u, ns, &field_path, depth + 1)?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// Generates a name that is valid as an XML local name.
+fn xml_name(u: &mut Unstructured) -> Result<String> {
+    const FIRST: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+    const REST: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789_-";
+
+    let len = u.int_in_range(0..=11)?;
+    let mut name = String::with_capacity(len + 1);
+
+    name.push(char::from(*u.choose(FIRST)?));
+    for _ in 0..len {
+        name.push(char::from(*u.choose(REST)?));
+    }
+
+    Ok(name)
+}
+
+/// Generates text that survives a round trip through RDF/XML.
+fn xml_text(u: &mut Unstructured) -> Result<String> {
+    let s: String = u.arbitrary()?;
+
+    Ok(s.chars()
+        .filter(|&c| {
+            (c == '\t' || c == '\n' || !c.is_control()) && c != '\u{fffe}' && c != '\u{ffff}'
+        })
+        .collect())
+}
+
+fn days_in_month(year: i32, month: i32) -> i32 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+fn check<T>(result: XmpResult<T>) -> Result<T> {
+    result.map_err(|_| Error::IncorrectFormat)
+}
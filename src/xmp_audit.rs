@@ -0,0 +1,299 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Contains an append-only, hash-chained log of metadata operations.
+//!
+//! Each entry in an [`AuditLog`] records a file that was touched, the
+//! operation performed, and a hash of the XMP before and after the
+//! operation. Each entry also includes the hash of the previous entry, so
+//! removing, reordering, or altering any entry breaks the chain. Use
+//! [`AuditLog::verify`] to check a log.
+//!
+//! The hash chain detects accidental or partial edits to the log, such as
+//! a truncated write or a line changed by hand. It doesn't protect against
+//! tampering: the hashes aren't keyed, so anyone who can write the log can
+//! also rewrite the chain to match.
+//!
+//! Attach a log to an [`XmpFile`] with [`XmpFile::set_audit_log`], or to
+//! an [`XmpFileBatch`] with [`XmpFileBatch::audit_log`], to have updates
+//! recorded automatically when each file is closed.
+//!
+//! ## File format
+//!
+//! The log is a UTF-8 text file with one entry per line. Each line
+//! contains the following fields, separated by tab characters:
+//!
+//! 1. Sequence number, starting at 1
+//! 2. Time stamp (see [`XmpDateTime::current`])
+//! 3. Operation
+//! 4. File path
+//! 5. SHA-256 hash of the XMP before the operation, or `-` if none
+//! 6. SHA-256 hash of the XMP after the operation, or `-` if none
+//! 7. Hash of the previous entry (64 zeros for the first entry)
+//! 8. SHA-256 hash of fields 1 through 7 as they appear in the line, including
+//!    the separating tabs
+//!
+//! Tab, carriage return, newline, and `%` characters in the operation and
+//! path fields are percent-encoded.
+//!
+//! This module is available only when crate feature `audit` is enabled.
+//!
+//! [`XmpFile`]: crate::XmpFile
+//! [`XmpFile::set_audit_log`]: crate::XmpFile::set_audit_log
+//! [`XmpFileBatch`]: crate::XmpFileBatch
+//! [`XmpFileBatch::audit_log`]: crate::XmpFileBatch::audit_log
+
+use std::{
+    fmt::Write as _,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::{ToStringOptions, XmpDateTime, XmpError, XmpErrorType, XmpMeta, XmpResult};
+
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Describes one metadata operation to be recorded in an [`AuditLog`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AuditEntry {
+    /// A short description of the operation performed, such as `"put_xmp"`.
+    pub operation: String,
+
+    /// The file that was touched.
+    pub path: PathBuf,
+
+    /// Hash of the XMP before the operation (see [`xmp_hash`]), or `None`
+    /// if the file had no XMP.
+    pub before: Option<String>,
+
+    /// Hash of the XMP after the operation (see [`xmp_hash`]), or `None`
+    /// if the file has no XMP afterwards.
+    pub after: Option<String>,
+}
+
+/// An append-only, hash-chained log of metadata operations.
+///
+/// Cloning an `AuditLog` is cheap; all clones append to the same file, and
+/// entries from multiple threads are serialized.
+///
+/// ## Example
+///
+/// ```
+/// # use xmp_toolkit::xmp_audit::{xmp_hash, AuditEntry, AuditLog};
+/// # use xmp_toolkit::XmpMeta;
+/// # let dir = std::env::temp_dir().join(format!("xmp_audit_doc_{}", std::process::id()));
+/// # std::fs::create_dir_all(&dir).unwrap();
+/// # let log_path = dir.join("audit.log");
+/// let log = AuditLog::open(&log_path).unwrap();
+///
+/// log.record(&AuditEntry {
+///     operation: "put_xmp".to_owned(),
+///     path: "image.jpg".into(),
+///     before: None,
+///     after: Some(xmp_hash(&XmpMeta::new().unwrap()).unwrap()),
+/// })
+/// .unwrap();
+///
+/// assert_eq!(AuditLog::verify(&log_path).unwrap(), 1);
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct AuditLog {
+    inner: Arc<Mutex<AuditLogInner>>,
+}
+
+#[derive(Debug)]
+struct AuditLogInner {
+    file: File,
+    last_seq: u64,
+    last_hash: String,
+}
+
+impl AuditLog {
+    /// Opens an audit log for appending, creating the file if it does not
+    /// exist.
+    ///
+    /// If the file exists, its hash chain is verified first and new entries
+    /// continue the chain.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::BadFileFormat`] if the
+    /// existing log is malformed or its hash chain is broken, or
+    /// [`XmpErrorType::ReadError`] or [`XmpErrorType::WriteError`] if the
+    /// file can not be read or opened for appending.
+    pub fn open<P: AsRef<Path>>(path: P) -> XmpResult<Self> {
+        let path = path.as_ref();
+
+        let (last_seq, last_hash) = if path.exists() {
+            read_chain(path)?
+        } else {
+            (0, GENESIS_HASH.to_owned())
+        };
+
+        let file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)
+            .map_err(|e| io_error(XmpErrorType::WriteError, e))?;
+
+        Ok(Self {
+            inner: Arc::new(Mutex::new(AuditLogInner {
+                file,
+                last_seq,
+                last_hash,
+            })),
+        })
+    }
+
+    /// Appends an entry to the log and flushes it to disk.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::WriteError`] if the entry
+    /// can not be written.
+    pub fn record(&self, entry: &AuditEntry) -> XmpResult<()> {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+
+        let seq = inner.last_seq + 1;
+        let timestamp = XmpDateTime::current()
+            .map(|dt| dt.to_string())
+            .unwrap_or_default();
+
+        let body = [
+            seq.to_string(),
+            timestamp,
+            escape(&entry.operation),
+            escape(&entry.path.to_string_lossy()),
+            entry.before.clone().unwrap_or_else(|| "-".to_owned()),
+            entry.after.clone().unwrap_or_else(|| "-".to_owned()),
+            inner.last_hash.clone(),
+        ]
+        .join("\t");
+
+        let hash = sha256_hex(body.as_bytes());
+        let line = format!("{body}\t{hash}\n");
+
+        inner
+            .file
+            .write_all(line.as_bytes())
+            .and_then(|_| inner.file.sync_data())
+            .map_err(|e| io_error(XmpErrorType::WriteError, e))?;
+
+        inner.last_seq = seq;
+        inner.last_hash = hash;
+
+        Ok(())
+    }
+
+    /// Verifies the hash chain of an audit log file and returns the number
+    /// of entries in it.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::BadFileFormat`] if any
+    /// entry is malformed, out of sequence, or does not match its hash or
+    /// the hash of the entry before it, or [`XmpErrorType::ReadError`] if
+    /// the file can not be read.
+    pub fn verify<P: AsRef<Path>>(path: P) -> XmpResult<u64> {
+        read_chain(path.as_ref()).map(|(last_seq, _)| last_seq)
+    }
+}
+
+/// Returns a SHA-256 hash (as lowercase hexadecimal digits) of the
+/// serialized XMP.
+///
+/// The XMP is serialized in the C++ XMP Toolkit's canonical format, without
+/// packet wrapper or formatting, so that the hash depends only on the
+/// content of the data model.
+pub fn xmp_hash(meta: &XmpMeta) -> XmpResult<String> {
+    let xmp = meta.to_string_with_options(
+        ToStringOptions::default()
+            .omit_packet_wrapper()
+            .use_canonical_format()
+            .omit_all_formatting(),
+    )?;
+
+    Ok(sha256_hex(xmp.as_bytes()))
+}
+
+/// Reads and verifies an existing log; returns the last sequence number
+/// and hash.
+fn read_chain(path: &Path) -> XmpResult<(u64, String)> {
+    let file = File::open(path).map_err(|e| io_error(XmpErrorType::ReadError, e))?;
+
+    let mut last_seq = 0;
+    let mut last_hash = GENESIS_HASH.to_owned();
+
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| io_error(XmpErrorType::ReadError, e))?;
+        let expected_seq = last_seq + 1;
+
+        let broken = || XmpError {
+            error_type: XmpErrorType::BadFileFormat,
+            debug_message: format!("Audit log hash chain is broken at entry {expected_seq}"),
+        };
+
+        let (body, hash) = line.rsplit_once('\t').ok_or_else(broken)?;
+        let fields: Vec<&str> = body.split('\t').collect();
+
+        if fields.len() != 7
+            || fields[0] != expected_seq.to_string()
+            || fields[6] != last_hash
+            || sha256_hex(body.as_bytes()) != hash
+        {
+            return Err(broken());
+        }
+
+        last_seq = expected_seq;
+        last_hash = hash.to_owned();
+    }
+
+    Ok((last_seq, last_hash))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .fold(String::with_capacity(64), |mut s, b| {
+            let _ = write!(s, "{b:02x}");
+            s
+        })
+}
+
+fn escape(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '%' => result.push_str("%25"),
+            '\t' => result.push_str("%09"),
+            '\n' => result.push_str("%0A"),
+            '\r' => result.push_str("%0D"),
+            c => result.push(c),
+        }
+    }
+
+    result
+}
+
+fn io_error(error_type: XmpErrorType, err: std::io::Error) -> XmpError {
+    XmpError {
+        error_type,
+        debug_message: err.to_string(),
+    }
+}
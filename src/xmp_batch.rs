@@ -0,0 +1,237 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::ffi::CString;
+
+use crate::{
+    ffi::{self, CXmpString},
+    xmp_meta::no_cpp_toolkit,
+    ChangeKind, XmpError, XmpMeta, XmpResult, XmpValue,
+};
+
+/// A queue of property operations to be applied to an [`XmpMeta`] in a
+/// single call into the C++ XMP Toolkit.
+///
+/// Create via [`XmpMeta::batch`]. Each call into the C++ XMP Toolkit has a
+/// fixed cost (argument conversion, exception handling, and locking of the
+/// data model). When applying hundreds of properties at once, as when
+/// applying a metadata template, that cost can dominate. A batch converts
+/// all arguments up front and then crosses into C++ once.
+///
+/// Operations are performed in the order in which they were queued, and
+/// each produces one entry in the result of [`XmpBatch::execute`]. An
+/// operation that fails does not prevent later operations from running,
+/// and operations that succeeded are not undone.
+///
+/// ## Example
+///
+/// ```
+/// # use xmp_toolkit::{xmp_ns, XmpMeta, XmpValue};
+/// let mut meta = XmpMeta::new().unwrap();
+///
+/// let results = meta
+///     .batch()
+///     .set_property(xmp_ns::XMP, "CreatorTool", &"Example".into())
+///     .set_property(xmp_ns::XMP, "Label", &"Red".into())
+///     .property(xmp_ns::XMP, "CreatorTool")
+///     .execute()
+///     .unwrap();
+///
+/// assert_eq!(results.len(), 3);
+/// assert_eq!(results[2], Ok(Some(XmpValue::from("Example"))));
+/// ```
+pub struct XmpBatch<'a> {
+    meta: &'a mut XmpMeta,
+    ops: Vec<QueuedOp>,
+}
+
+struct QueuedOp {
+    kind: u32,
+    args: XmpResult<OpArgs>,
+}
+
+struct OpArgs {
+    ns: CString,
+    name: CString,
+    value: Option<CString>,
+    options: u32,
+}
+
+impl<'a> XmpBatch<'a> {
+    pub(crate) fn new(meta: &'a mut XmpMeta) -> Self {
+        Self {
+            meta,
+            ops: Vec::new(),
+        }
+    }
+
+    /// Queues a read of a simple string property value.
+    ///
+    /// The corresponding result is `Ok(Some(value))` if the property
+    /// exists or `Ok(None)` if it does not. See [`XmpMeta::try_property`].
+    pub fn property(&mut self, namespace: &str, path: &str) -> &mut Self {
+        self.push(ffi::BATCH_GET_PROPERTY, namespace, path, None)
+    }
+
+    /// Queues creating or setting a simple property value.
+    ///
+    /// The corresponding result is `Ok(None)` on success. See
+    /// [`XmpMeta::set_property`].
+    pub fn set_property(
+        &mut self,
+        namespace: &str,
+        path: &str,
+        new_value: &XmpValue<String>,
+    ) -> &mut Self {
+        self.push(ffi::BATCH_SET_PROPERTY, namespace, path, Some(new_value))
+    }
+
+    /// Queues deleting a property.
+    ///
+    /// The corresponding result is `Ok(None)` on success, including when
+    /// the property did not exist. See [`XmpMeta::delete_property`].
+    pub fn delete_property(&mut self, namespace: &str, path: &str) -> &mut Self {
+        self.push(ffi::BATCH_DELETE_PROPERTY, namespace, path, None)
+    }
+
+    /// Returns the number of operations queued.
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Returns `true` if no operations are queued.
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Performs all queued operations, in order.
+    ///
+    /// Returns one result per queued operation. Read operations return
+    /// `Ok(Some(value))` or `Ok(None)`; write operations return `Ok(None)`.
+    /// An operation whose arguments could not be converted (for instance,
+    /// because a string contains a NUL byte) or that the C++ XMP Toolkit
+    /// rejects returns `Err(...)` without affecting the other operations.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error only if the C++ XMP Toolkit is unavailable, in
+    /// which case no operations are performed.
+    pub fn execute(&mut self) -> XmpResult<Vec<XmpResult<Option<XmpValue<String>>>>> {
+        let Some(m) = self.meta.m else {
+            return Err(no_cpp_toolkit());
+        };
+
+        let ops = std::mem::take(&mut self.ops);
+
+        // Operations whose arguments could not be converted are passed as
+        // no-ops so that results correspond one-to-one with `ops`.
+        let c_ops: Vec<ffi::CXmpBatchOp> = ops
+            .iter()
+            .map(|op| match &op.args {
+                Ok(args) => ffi::CXmpBatchOp {
+                    kind: op.kind,
+                    schema_ns: args.ns.as_ptr(),
+                    prop_name: args.name.as_ptr(),
+                    prop_value: args
+                        .value
+                        .as_ref()
+                        .map_or(std::ptr::null(), |value| value.as_ptr()),
+                    options: args.options,
+                },
+                Err(_) => ffi::CXmpBatchOp {
+                    kind: ffi::BATCH_SKIP,
+                    schema_ns: std::ptr::null(),
+                    prop_name: std::ptr::null(),
+                    prop_value: std::ptr::null(),
+                    options: 0,
+                },
+            })
+            .collect();
+
+        let mut c_results: Vec<ffi::CXmpBatchResult> = std::iter::repeat_with(Default::default)
+            .take(c_ops.len())
+            .collect();
+
+        unsafe {
+            ffi::CXmpMetaBatch(
+                m,
+                c_ops.as_ptr(),
+                c_results.as_mut_ptr(),
+                c_ops.len() as u32,
+            );
+        }
+
+        Ok(ops
+            .into_iter()
+            .zip(c_results)
+            .map(|(op, c_result)| {
+                let value = CXmpString::from_ptr(c_result.value);
+
+                let args = op.args?;
+                XmpError::raise_from_c(&c_result.error)?;
+
+                let change = match op.kind {
+                    ffi::BATCH_SET_PROPERTY => Some(ChangeKind::Set),
+                    ffi::BATCH_DELETE_PROPERTY => Some(ChangeKind::Delete),
+                    _ => None,
+                };
+
+                if let Some(change) = change {
+                    self.meta.notify_change(
+                        change,
+                        &args.ns.to_string_lossy(),
+                        &args.name.to_string_lossy(),
+                    );
+                }
+
+                Ok(value.map(|value| XmpValue {
+                    value,
+                    options: c_result.options,
+                }))
+            })
+            .collect())
+    }
+
+    fn push(
+        &mut self,
+        kind: u32,
+        namespace: &str,
+        path: &str,
+        new_value: Option<&XmpValue<String>>,
+    ) -> &mut Self {
+        // Access statistics are recorded when the operation is queued.
+        if kind == ffi::BATCH_GET_PROPERTY {
+            self.meta.record_read(namespace, path);
+        } else {
+            self.meta.record_write(namespace, path);
+        }
+
+        let args = (|| -> XmpResult<OpArgs> {
+            Ok(OpArgs {
+                ns: CString::new(namespace)?,
+                name: CString::new(path)?,
+                value: match new_value {
+                    // As in `XmpMeta::set_property`, an empty value is
+                    // passed as a null pointer.
+                    Some(v) if !v.value.is_empty() => Some(CString::new(v.value.as_bytes())?),
+                    _ => None,
+                },
+                options: new_value.map_or(0, |v| v.options),
+            })
+        })();
+
+        self.ops.push(QueuedOp { kind, args });
+
+        self
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+// Walks the top-level boxes of files based on the ISO base media file
+// format (ISO/IEC 14496-12) and similar box structures.
+
+use std::ops::Range;
+
+use crate::{xmp_file_handler::bad_file, XmpError, XmpErrorType, XmpResult};
+
+const UUID: &[u8; 4] = b"uuid";
+const FREE: &[u8; 4] = b"free";
+
+const PACKET_TRAILER: &[u8] = b"<?xpacket end=";
+
+pub(crate) struct BmffBox {
+    pub(crate) box_type: [u8; 4],
+
+    // The whole box, including its header.
+    pub(crate) range: Range<usize>,
+
+    // The box's payload.
+    pub(crate) data: Range<usize>,
+}
+
+/// Returns the top-level boxes of `file`, starting at `start`.
+///
+/// `format` names the format in error messages.
+pub(crate) fn boxes(file: &[u8], start: usize, format: &str) -> XmpResult<Vec<BmffBox>> {
+    let mut boxes = Vec::new();
+    let mut pos = start;
+
+    while pos < file.len() {
+        let header = file
+            .get(pos..pos + 8)
+            .ok_or_else(|| bad_file(format, "Truncated box header"))?;
+
+        let size = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as u64;
+        let box_type = [header[4], header[5], header[6], header[7]];
+
+        let (header_len, size) = match size {
+            // The box extends to the end of the file.
+            0 => (8, (file.len() - pos) as u64),
+
+            // A 64-bit size follows the type.
+            1 => {
+                let large = file
+                    .get(pos + 8..pos + 16)
+                    .ok_or_else(|| bad_file(format, "Truncated box header"))?;
+                let mut size = [0; 8];
+                size.copy_from_slice(large);
+                (16, u64::from_be_bytes(size))
+            }
+
+            size => (8, size),
+        };
+
+        let end = usize::try_from(size)
+            .ok()
+            .and_then(|size| pos.checked_add(size))
+            .filter(|&end| end >= pos + header_len && end <= file.len())
+            .ok_or_else(|| bad_file(format, "Box extends past the end of the file"))?;
+
+        boxes.push(BmffBox {
+            box_type,
+            range: pos..end,
+            data: pos + header_len..end,
+        });
+
+        pos = end;
+    }
+
+    Ok(boxes)
+}
+
+/// Returns the boxes contained in `parent`.
+pub(crate) fn child_boxes(file: &[u8], parent: &BmffBox, format: &str) -> XmpResult<Vec<BmffBox>> {
+    boxes(&file[..parent.data.end], parent.data.start, format)
+}
+
+/// Appends a box with a 32-bit size to `out`.
+pub(crate) fn push_box(out: &mut Vec<u8>, box_type: &[u8; 4], data: &[&[u8]]) -> XmpResult<()> {
+    let len: usize = 8 + data.iter().map(|d| d.len()).sum::<usize>();
+    let len = u32::try_from(len).map_err(|_| XmpError {
+        error_type: XmpErrorType::BadParam,
+        debug_message: "Box is too large".to_owned(),
+    })?;
+
+    out.extend_from_slice(&len.to_be_bytes());
+    out.extend_from_slice(box_type);
+    for d in data {
+        out.extend_from_slice(d);
+    }
+
+    Ok(())
+}
+
+/// Returns a copy of `file` in which the top-level `uuid` box `boxes[index]`
+/// (if any) is replaced by one with `uuid` and `packet` as its payload.
+///
+/// The packet is written in place (padded to the old length) if it fits;
+/// otherwise, see [`replace_box`].
+pub(crate) fn put_uuid_box(
+    file: &[u8],
+    boxes: &[BmffBox],
+    index: Option<usize>,
+    uuid: &[u8; 16],
+    packet: &[u8],
+    format: &str,
+) -> XmpResult<Vec<u8>> {
+    if let Some(old) = index.map(|index| &boxes[index]) {
+        let old_packet = old.data.start + uuid.len()..old.data.end;
+
+        if index != Some(boxes.len() - 1) {
+            if let Some(padded) = pad_packet(packet, old_packet.len()) {
+                let mut out = file.to_vec();
+                out[old_packet].copy_from_slice(&padded);
+                return Ok(out);
+            }
+        }
+    }
+
+    let mut new_box = Vec::with_capacity(packet.len() + 24);
+    push_box(&mut new_box, UUID, &[uuid, packet])?;
+    replace_box(file, boxes, index, &new_box, format)
+}
+
+/// Returns a copy of `file` in which the top-level box `boxes[index]` (if
+/// any) is replaced by `new_box`.
+///
+/// Sample tables refer to media data by absolute file offset, so no box
+/// that precedes the last one is moved. If the old box is the last one, it
+/// is simply replaced. Otherwise, it becomes a `free` box of the same size
+/// and the new box is appended at the end of the file.
+pub(crate) fn replace_box(
+    file: &[u8],
+    boxes: &[BmffBox],
+    index: Option<usize>,
+    new_box: &[u8],
+    format: &str,
+) -> XmpResult<Vec<u8>> {
+    let mut out = Vec::with_capacity(file.len() + new_box.len());
+    out.extend_from_slice(file);
+
+    if let Some(index) = index {
+        let old = &boxes[index];
+
+        if index + 1 == boxes.len() {
+            out.truncate(old.range.start);
+            out.extend_from_slice(new_box);
+            return Ok(out);
+        }
+
+        out[old.range.start + 4..old.range.start + 8].copy_from_slice(FREE);
+        out[old.data.clone()].fill(0);
+    }
+
+    // A last box that extends to the end of the file needs an explicit
+    // size before anything can follow it.
+    if let Some(last) = boxes.last() {
+        if out[last.range.start..last.range.start + 4] == [0; 4] {
+            let len = u32::try_from(last.range.len())
+                .map_err(|_| bad_file(format, "Last box is too large to append XMP"))?;
+            out[last.range.start..last.range.start + 4].copy_from_slice(&len.to_be_bytes());
+        }
+    }
+
+    out.extend_from_slice(new_box);
+    Ok(out)
+}
+
+/// Returns `packet` padded with spaces to exactly `len` bytes, or `None`
+/// if it is longer than that.
+///
+/// The padding goes just before the packet trailer, where the XMP
+/// specification expects it.
+pub(crate) fn pad_packet(packet: &[u8], len: usize) -> Option<Vec<u8>> {
+    let padding = len.checked_sub(packet.len())?;
+
+    let at = packet
+        .windows(PACKET_TRAILER.len())
+        .rposition(|w| w == PACKET_TRAILER)
+        .unwrap_or(packet.len());
+
+    let mut padded = Vec::with_capacity(len);
+    padded.extend_from_slice(&packet[..at]);
+    padded.resize(at + padding, b' ');
+    padded.extend_from_slice(&packet[at..]);
+    Some(padded)
+}
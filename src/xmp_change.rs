@@ -0,0 +1,59 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::fmt;
+
+/// The kind of modification described by a [`ChangeEvent`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ChangeKind {
+    /// A node was created or its value was replaced.
+    Set,
+
+    /// A node was removed.
+    Delete,
+
+    /// An item was added to the end of an array.
+    Append,
+}
+
+/// Describes a modification of an [`XmpMeta`](crate::XmpMeta) data model.
+///
+/// Register a listener for these events with
+/// [`XmpMeta::on_change`](crate::XmpMeta::on_change).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ChangeEvent {
+    /// The kind of modification.
+    pub kind: ChangeKind,
+
+    /// The namespace URI of the top-level property that contains the
+    /// affected node.
+    pub namespace: String,
+
+    /// The path of the affected node.
+    ///
+    /// For struct fields, array items, and qualifiers, this is the full
+    /// path to that node (for example, `creator[2]`), as composed by
+    /// functions such as [`XmpMeta::compose_array_item_path`].
+    ///
+    /// [`XmpMeta::compose_array_item_path`]: crate::XmpMeta::compose_array_item_path
+    pub path: String,
+}
+
+impl fmt::Display for ChangeEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} {{{}}}{}", self.kind, self.namespace, self.path)
+    }
+}
+
+pub(crate) type ChangeListener = Box<dyn Fn(&ChangeEvent) + Send + Sync>;
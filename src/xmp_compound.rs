@@ -0,0 +1,284 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+// Finds the XMP embedded in compound documents such as InDesign (INDD) and
+// Illustrator (AI) files, which hold XMP for placed images, stories, or
+// sub-documents in addition to the document's own packet. Nothing here
+// writes to the file.
+//
+// Packets are found with `xmp_packet::scan` and then attributed to the
+// structure that holds them:
+//
+// * InDesign stores each block of XMP in a contiguous object, which starts with
+//   a 32-byte header: a GUID, the object's UID, and the length of its stream,
+//   both little-endian.
+//
+// * Illustrator files are PDF files, which store XMP in metadata stream objects
+//   (`/Type /Metadata`). Such streams are normally left uncompressed so that
+//   they can be found by scanning, but sub-documents sometimes compress them
+//   with `/FlateDecode`; those streams are decompressed and scanned as well.
+
+use std::{ops::Range, os::raw::c_ulong, str::FromStr};
+
+use crate::{ffi, xmp_packet, XmpMeta, XmpResult};
+
+const INDD_OBJECT_HEADER: &[u8; 16] =
+    b"\xde\x39\x39\x79\x51\x88\x4b\x6c\x8e\x63\xee\xf8\xae\xe0\xdd\x38";
+const INDD_OBJECT_HEADER_LEN: usize = 32;
+
+// Decompressed metadata streams larger than this are skipped.
+const MAX_INFLATED_LEN: usize = 16 << 20;
+
+/// An XMP packet embedded in a file, as returned by
+/// [`XmpFile::embedded_xmp`].
+///
+/// [`XmpFile::embedded_xmp`]: crate::XmpFile::embedded_xmp
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct EmbeddedXmp {
+    /// The location of the packet, in bytes from the start of the file.
+    ///
+    /// For a packet in a compressed stream, this is the location of the
+    /// compressed stream data.
+    pub range: Range<u64>,
+
+    /// The text of the packet.
+    pub text: String,
+
+    /// The structure of the file that holds the packet.
+    pub container: XmpContainer,
+
+    /// `true` if this is the packet that [`XmpFile::xmp`] reads.
+    ///
+    /// [`XmpFile::xmp`]: crate::XmpFile::xmp
+    pub main: bool,
+}
+
+impl EmbeddedXmp {
+    /// Parses the packet.
+    pub fn parse(&self) -> XmpResult<XmpMeta> {
+        XmpMeta::from_str(&self.text)
+    }
+}
+
+/// The structure of a file that holds an [`EmbeddedXmp`] packet.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum XmpContainer {
+    /// The packet isn't inside any of the structures below.
+    Unknown,
+
+    /// The packet is stored in the InDesign contiguous object with this
+    /// UID.
+    InDesignObject {
+        /// The object's UID.
+        uid: u32,
+    },
+
+    /// The packet is stored in the stream of the PDF object with this
+    /// object number.
+    PdfObject {
+        /// The object number.
+        number: u32,
+
+        /// `true` if the stream is compressed.
+        compressed: bool,
+    },
+}
+
+/// Returns every XMP packet in `file`, in the order in which they appear.
+/// None of them is marked as the main packet.
+pub(crate) fn embedded_xmp(file: &[u8]) -> Vec<EmbeddedXmp> {
+    let is_pdf = find(&file[..file.len().min(1024)], b"%PDF-", 0).is_some();
+
+    let mut packets: Vec<EmbeddedXmp> = xmp_packet::scan(file)
+        .into_iter()
+        .map(|packet| {
+            let start = packet.range.start;
+
+            let container = indd_object(file, &packet.range)
+                .or_else(|| is_pdf.then(|| pdf_object(file, start)).flatten())
+                .unwrap_or(XmpContainer::Unknown);
+
+            embedded(packet, container)
+        })
+        .collect();
+
+    if is_pdf {
+        for stream in compressed_metadata_streams(file) {
+            let Some(inflated) = inflate(&file[stream.data.clone()]) else {
+                continue;
+            };
+
+            packets.extend(
+                xmp_packet::scan(&inflated)
+                    .into_iter()
+                    .map(|packet| EmbeddedXmp {
+                        range: stream.data.start as u64..stream.data.end as u64,
+                        text: packet.text,
+                        container: XmpContainer::PdfObject {
+                            number: stream.number,
+                            compressed: true,
+                        },
+                        main: false,
+                    }),
+            );
+        }
+
+        packets.sort_by_key(|packet| packet.range.start);
+    }
+
+    packets
+}
+
+fn embedded(packet: xmp_packet::ScannedPacket, container: XmpContainer) -> EmbeddedXmp {
+    EmbeddedXmp {
+        range: packet.range.start as u64..packet.range.end as u64,
+        text: packet.text,
+        container,
+        main: false,
+    }
+}
+
+/// Returns the InDesign contiguous object whose stream holds `packet`.
+fn indd_object(file: &[u8], packet: &Range<usize>) -> Option<XmpContainer> {
+    let header = rfind(&file[..packet.start], INDD_OBJECT_HEADER)?;
+    let fields = file.get(header + 16..header + INDD_OBJECT_HEADER_LEN)?;
+
+    let uid = u32::from_le_bytes([fields[0], fields[1], fields[2], fields[3]]);
+    let len = u32::from_le_bytes([fields[4], fields[5], fields[6], fields[7]]) as usize;
+
+    let stream_end = (header + INDD_OBJECT_HEADER_LEN).checked_add(len)?;
+    (packet.end <= stream_end).then_some(XmpContainer::InDesignObject { uid })
+}
+
+/// Returns the PDF object whose stream holds the byte at `offset`.
+fn pdf_object(file: &[u8], offset: usize) -> Option<XmpContainer> {
+    let before = &file[..offset];
+    let keyword = rfind(before, b" obj")?;
+
+    if find(&before[keyword..], b"endobj", 0).is_some() {
+        return None;
+    }
+
+    Some(XmpContainer::PdfObject {
+        number: object_number(file, keyword)?,
+        compressed: false,
+    })
+}
+
+/// Parses the object number in the `N G obj` header whose ` obj` keyword
+/// starts at `keyword`.
+fn object_number(file: &[u8], keyword: usize) -> Option<u32> {
+    let header = &file[keyword.saturating_sub(24)..keyword];
+    let text = std::str::from_utf8(header).ok()?;
+
+    let mut words = text.split_ascii_whitespace().rev();
+    let _generation: u32 = words.next()?.parse().ok()?;
+    words.next()?.parse().ok()
+}
+
+struct PdfStream {
+    number: u32,
+    data: Range<usize>,
+}
+
+/// Returns the data of the Flate-compressed metadata streams of `file`.
+fn compressed_metadata_streams(file: &[u8]) -> Vec<PdfStream> {
+    let mut streams = Vec::new();
+    let mut pos = 0;
+
+    while let Some(keyword) = find(file, b" obj", pos) {
+        pos = keyword + 4;
+
+        let Some(stream) = find(file, b"stream", pos) else {
+            break;
+        };
+
+        // The dictionary runs up to the `stream` keyword; objects without
+        // a stream end before it.
+        let dict = &file[pos..stream];
+        if find(dict, b"endobj", 0).is_some()
+            || find(dict, b"/Metadata", 0).is_none()
+            || find(dict, b"/FlateDecode", 0).is_none()
+        {
+            continue;
+        }
+
+        let mut start = stream + 6;
+        if file.get(start) == Some(&b'\r') {
+            start += 1;
+        }
+        if file.get(start) == Some(&b'\n') {
+            start += 1;
+        }
+
+        let Some(end) = find(file, b"endstream", start) else {
+            break;
+        };
+
+        if let Some(number) = object_number(file, keyword) {
+            streams.push(PdfStream {
+                number,
+                data: start..end,
+            });
+        }
+
+        pos = end;
+    }
+
+    streams
+}
+
+/// Decompresses zlib data, or returns `None` if it is damaged or
+/// decompresses to more than `MAX_INFLATED_LEN` bytes.
+fn inflate(data: &[u8]) -> Option<Vec<u8>> {
+    const Z_OK: i32 = 0;
+    const Z_BUF_ERROR: i32 = -5;
+
+    let source_len = c_ulong::try_from(data.len()).ok()?;
+    let mut capacity = (data.len() * 4).clamp(4096, MAX_INFLATED_LEN);
+
+    loop {
+        let mut out = vec![0; capacity];
+        let mut out_len = c_ulong::try_from(capacity).ok()?;
+
+        let result =
+            unsafe { ffi::uncompress(out.as_mut_ptr(), &mut out_len, data.as_ptr(), source_len) };
+
+        match result {
+            Z_OK => {
+                out.truncate(usize::try_from(out_len).ok()?);
+                return Some(out);
+            }
+            Z_BUF_ERROR if capacity < MAX_INFLATED_LEN => {
+                capacity = (capacity * 2).min(MAX_INFLATED_LEN);
+            }
+            _ => return None,
+        }
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|pos| pos + from)
+}
+
+fn rfind(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .rposition(|window| window == needle)
+}
@@ -0,0 +1,82 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+// Reads and writes the XMP of Canon CR3 raw files.
+//
+// CR3 is based on the ISO base media file format: the file type box has
+// major brand `crx `, and the XMP packet is the payload of a top-level
+// `uuid` box with a Canon-specific UUID.
+//
+// The sample tables in the `moov` box refer to the image data by absolute
+// file offset, so the update never moves any box that precedes the last
+// one (see `xmp_bmff::put_uuid_box`).
+
+use std::ops::Range;
+
+use crate::{
+    xmp_bmff::{boxes, put_uuid_box, BmffBox},
+    xmp_file_handler::{bad_file, FileHandler},
+    FileFormat, XmpError, XmpResult,
+};
+
+const FTYP: &[u8; 4] = b"ftyp";
+const CRX: &[u8; 4] = b"crx ";
+const UUID: &[u8; 4] = b"uuid";
+
+/// The UUID of the box that contains the XMP packet
+/// (`be7acfcb-97a9-42e8-9c71-999491e3afac`).
+const XMP_UUID: [u8; 16] = [
+    0xbe, 0x7a, 0xcf, 0xcb, 0x97, 0xa9, 0x42, 0xe8, 0x9c, 0x71, 0x99, 0x94, 0x91, 0xe3, 0xaf, 0xac,
+];
+
+pub(crate) struct Cr3Handler;
+
+impl FileHandler for Cr3Handler {
+    fn format(&self) -> FileFormat {
+        FileFormat::Cr3
+    }
+
+    fn check_format(&self, header: &[u8]) -> bool {
+        header.len() >= 12 && &header[4..8] == FTYP && &header[8..12] == CRX
+    }
+
+    fn read_xmp(&self, file: &[u8]) -> XmpResult<Option<Range<usize>>> {
+        Ok(cr3_boxes(file)?
+            .iter()
+            .find(|b| is_xmp_box(file, b))
+            .map(|b| b.data.start + XMP_UUID.len()..b.data.end))
+    }
+
+    fn write_xmp(&self, file: &[u8], packet: &[u8]) -> XmpResult<Vec<u8>> {
+        let boxes = cr3_boxes(file)?;
+        let xmp_box = boxes.iter().position(|b| is_xmp_box(file, b));
+
+        put_uuid_box(file, &boxes, xmp_box, &XMP_UUID, packet, "CR3")
+    }
+}
+
+fn cr3_boxes(file: &[u8]) -> XmpResult<Vec<BmffBox>> {
+    if !Cr3Handler.check_format(file) {
+        return Err(bad_cr3("Not a CR3 file"));
+    }
+
+    boxes(file, 0, "CR3")
+}
+
+fn is_xmp_box(file: &[u8], b: &BmffBox) -> bool {
+    &b.box_type == UUID && file[b.data.clone()].starts_with(&XMP_UUID)
+}
+
+fn bad_cr3(message: &str) -> XmpError {
+    bad_file("CR3", message)
+}
@@ -0,0 +1,208 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Contains typed, read-only access to Camera Raw develop settings (the
+//! `crs` schema), and a function to copy those settings between XMP
+//! objects.
+//!
+//! Camera Raw and Lightroom store develop settings in the `crs` namespace,
+//! typically in a sidecar `.xmp` file next to the raw image.
+
+use crate::{xmp_ns, IterOptions, XmpMeta, XmpPropertyType, XmpResult};
+
+/// A point on a tone curve, as (input, output) levels from 0 to 255.
+pub type ToneCurvePoint = (i32, i32);
+
+/// Commonly used Camera Raw develop settings.
+///
+/// Each field corresponds to the `crs` property with the same name (for
+/// example, `exposure_2012` is `crs:Exposure2012`) and is `None` if that
+/// property is absent or can not be interpreted as the field's type.
+///
+/// This is a read-only view of the most commonly inspected settings. Use
+/// [`copy_settings`] to transfer the complete set of settings, including
+/// those not represented here.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CameraRawSettings {
+    /// `crs:Version`: the version of Camera Raw that wrote the settings.
+    pub version: Option<String>,
+
+    /// `crs:ProcessVersion`: the image processing version, such as
+    /// `"11.0"`.
+    pub process_version: Option<String>,
+
+    /// `crs:HasSettings`: `true` if the image has been edited.
+    pub has_settings: Option<bool>,
+
+    /// `crs:HasCrop`: `true` if the image is cropped.
+    pub has_crop: Option<bool>,
+
+    /// `crs:RawFileName`: the name of the raw file that the settings apply
+    /// to.
+    pub raw_file_name: Option<String>,
+
+    /// `crs:WhiteBalance`: the white balance preset, such as `"As Shot"`
+    /// or `"Custom"`.
+    pub white_balance: Option<String>,
+
+    /// `crs:Temperature`: white balance color temperature in kelvins.
+    pub temperature: Option<i32>,
+
+    /// `crs:Tint`: white balance tint.
+    pub tint: Option<i32>,
+
+    /// `crs:Exposure2012`: exposure adjustment in stops.
+    pub exposure_2012: Option<f64>,
+
+    /// `crs:Contrast2012`.
+    pub contrast_2012: Option<i32>,
+
+    /// `crs:Highlights2012`.
+    pub highlights_2012: Option<i32>,
+
+    /// `crs:Shadows2012`.
+    pub shadows_2012: Option<i32>,
+
+    /// `crs:Whites2012`.
+    pub whites_2012: Option<i32>,
+
+    /// `crs:Blacks2012`.
+    pub blacks_2012: Option<i32>,
+
+    /// `crs:Clarity2012`.
+    pub clarity_2012: Option<i32>,
+
+    /// `crs:Vibrance`.
+    pub vibrance: Option<i32>,
+
+    /// `crs:Saturation`.
+    pub saturation: Option<i32>,
+
+    /// `crs:ToneCurveName2012`: the name of the tone curve preset, such as
+    /// `"Linear"` or `"Custom"`.
+    pub tone_curve_name_2012: Option<String>,
+
+    /// `crs:ToneCurve`: the tone curve used by process versions before
+    /// 2012.
+    pub tone_curve: Option<Vec<ToneCurvePoint>>,
+
+    /// `crs:ToneCurvePV2012`: the composite tone curve.
+    pub tone_curve_pv2012: Option<Vec<ToneCurvePoint>>,
+
+    /// `crs:ToneCurvePV2012Red`: the red channel tone curve.
+    pub tone_curve_pv2012_red: Option<Vec<ToneCurvePoint>>,
+
+    /// `crs:ToneCurvePV2012Green`: the green channel tone curve.
+    pub tone_curve_pv2012_green: Option<Vec<ToneCurvePoint>>,
+
+    /// `crs:ToneCurvePV2012Blue`: the blue channel tone curve.
+    pub tone_curve_pv2012_blue: Option<Vec<ToneCurvePoint>>,
+}
+
+impl CameraRawSettings {
+    /// Reads Camera Raw settings from `meta`.
+    ///
+    /// Returns `None` if none of the properties are present.
+    pub fn read_from(meta: &XmpMeta) -> Option<Self> {
+        let result = Self {
+            version: read(meta, "Version"),
+            process_version: read(meta, "ProcessVersion"),
+            has_settings: read(meta, "HasSettings"),
+            has_crop: read(meta, "HasCrop"),
+            raw_file_name: read(meta, "RawFileName"),
+            white_balance: read(meta, "WhiteBalance"),
+            temperature: read(meta, "Temperature"),
+            tint: read(meta, "Tint"),
+            exposure_2012: read(meta, "Exposure2012"),
+            contrast_2012: read(meta, "Contrast2012"),
+            highlights_2012: read(meta, "Highlights2012"),
+            shadows_2012: read(meta, "Shadows2012"),
+            whites_2012: read(meta, "Whites2012"),
+            blacks_2012: read(meta, "Blacks2012"),
+            clarity_2012: read(meta, "Clarity2012"),
+            vibrance: read(meta, "Vibrance"),
+            saturation: read(meta, "Saturation"),
+            tone_curve_name_2012: read(meta, "ToneCurveName2012"),
+            tone_curve: read_tone_curve(meta, "ToneCurve"),
+            tone_curve_pv2012: read_tone_curve(meta, "ToneCurvePV2012"),
+            tone_curve_pv2012_red: read_tone_curve(meta, "ToneCurvePV2012Red"),
+            tone_curve_pv2012_green: read_tone_curve(meta, "ToneCurvePV2012Green"),
+            tone_curve_pv2012_blue: read_tone_curve(meta, "ToneCurvePV2012Blue"),
+        };
+
+        if result == Self::default() {
+            None
+        } else {
+            Some(result)
+        }
+    }
+}
+
+/// Returns `true` if `meta` contains any Camera Raw settings.
+pub fn has_settings(meta: &XmpMeta) -> bool {
+    meta.iter(IterOptions::default().schema_ns(xmp_ns::CAMERA_RAW))
+        .next()
+        .is_some()
+}
+
+/// Replaces all Camera Raw settings in `to` with those in `from`.
+///
+/// Every property in the `crs` namespace is copied, including structs,
+/// arrays, and qualifiers, whether or not it is represented in
+/// [`CameraRawSettings`]. Existing `crs` properties in `to` that are not
+/// present in `from` are removed. Properties in other namespaces are not
+/// affected.
+///
+/// The update is atomic: If an error occurs, `to` is left unchanged.
+pub fn copy_settings(from: &XmpMeta, to: &mut XmpMeta) -> XmpResult<()> {
+    let mut staged = to.stage();
+
+    let existing = top_level_properties(&staged);
+    for name in existing {
+        staged.delete_property(xmp_ns::CAMERA_RAW, &name)?;
+    }
+
+    for name in top_level_properties(from) {
+        from.duplicate_subtree(&mut staged, xmp_ns::CAMERA_RAW, &name, &name)?;
+    }
+
+    to.commit_staged(staged);
+    Ok(())
+}
+
+fn top_level_properties(meta: &XmpMeta) -> Vec<String> {
+    meta.iter(
+        IterOptions::default()
+            .schema_ns(xmp_ns::CAMERA_RAW)
+            .immediate_children_only(),
+    )
+    .map(|prop| prop.name)
+    .collect()
+}
+
+fn read<T: XmpPropertyType>(meta: &XmpMeta, name: &str) -> Option<T> {
+    meta.get::<T>(xmp_ns::CAMERA_RAW, name).map(|v| v.value)
+}
+
+fn read_tone_curve(meta: &XmpMeta, name: &str) -> Option<Vec<ToneCurvePoint>> {
+    if !meta.contains_property(xmp_ns::CAMERA_RAW, name) {
+        return None;
+    }
+
+    meta.property_array(xmp_ns::CAMERA_RAW, name)
+        .map(|item| {
+            let (input, output) = item.value.split_once(',')?;
+            Some((input.trim().parse().ok()?, output.trim().parse().ok()?))
+        })
+        .collect()
+}
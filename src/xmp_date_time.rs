@@ -11,11 +11,16 @@
 // specific language governing permissions and limitations under
 // each license.
 
-use std::fmt;
+use std::{
+    ffi::CString,
+    fmt,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use crate::{
     ffi::{self, CXmpString},
-    XmpError, XmpResult,
+    xmp_id, XmpError, XmpResult,
 };
 
 /// Represents the concept of date and time as expressed in XMP.
@@ -89,7 +94,16 @@ pub struct XmpTimeZone {
 
 impl XmpDateTime {
     /// Creates a new date-time struct reflecting the current time.
+    ///
+    /// If a [`DeterministicMode`] guard is active on the current thread,
+    /// returns the frozen time from that guard instead.
+    ///
+    /// [`DeterministicMode`]: crate::xmp_id::DeterministicMode
     pub fn current() -> XmpResult<Self> {
+        if let Some(now) = xmp_id::frozen_now() {
+            return Ok(now);
+        }
+
         let mut dt = ffi::CXmpDateTime::default();
         let mut err = ffi::CXmpError::default();
 
@@ -202,6 +216,41 @@ impl XmpDateTime {
         };
     }
 
+    /// Converts a file system timestamp to a UTC date and time. Returns
+    /// `None` for times before 1970 or too far in the future.
+    pub(crate) fn from_system_time(t: SystemTime) -> Option<Self> {
+        let since_epoch = t.duration_since(UNIX_EPOCH).ok()?;
+        let secs = i64::try_from(since_epoch.as_secs()).ok()?;
+        let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+
+        // Civil-from-days conversion in the proleptic Gregorian calendar.
+        // See https://howardhinnant.github.io/date_algorithms.html.
+        let z = days + 719_468;
+        let era = z / 146_097;
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + i64::from(month <= 2);
+
+        Some(Self {
+            date: Some(XmpDate {
+                year: i32::try_from(year).ok()?,
+                month: month as i32,
+                day: day as i32,
+            }),
+            time: Some(XmpTime {
+                hour: (secs_of_day / 3600) as i32,
+                minute: (secs_of_day % 3600 / 60) as i32,
+                second: (secs_of_day % 60) as i32,
+                nanosecond: since_epoch.subsec_nanos() as i32,
+                time_zone: Some(XmpTimeZone { hour: 0, minute: 0 }),
+            }),
+        })
+    }
+
     pub(crate) fn as_ffi(&self) -> ffi::CXmpDateTime {
         let mut result = ffi::CXmpDateTime::default();
 
@@ -292,6 +341,27 @@ impl fmt::Display for XmpDateTime {
     }
 }
 
+impl FromStr for XmpDateTime {
+    type Err = XmpError;
+
+    /// Parses a date-time string in the ISO 8601 profile accepted by
+    /// XMP. This is the inverse of the [`Display`](fmt::Display)
+    /// implementation.
+    ///
+    /// Returns an error if the string can not be parsed as a date-time.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let c_str = CString::new(s)?;
+        let mut dt = ffi::CXmpDateTime::default();
+        let mut err = ffi::CXmpError::default();
+
+        unsafe { ffi::CXmpDateTimeFromString(c_str.as_ptr(), &mut dt, &mut err) };
+
+        XmpError::raise_from_c(&err)?;
+
+        Ok(Self::from_ffi(&dt))
+    }
+}
+
 #[cfg(feature = "chrono")]
 use chrono::{DateTime, Datelike, FixedOffset, LocalResult, NaiveDate, Timelike};
 #[cfg(feature = "chrono")]
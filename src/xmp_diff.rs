@@ -0,0 +1,359 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::{cmp::Ordering, collections::HashMap, fmt, fmt::Write};
+
+use crate::{IterOptions, XmpMeta};
+
+/// The kind of difference described by a [`PropertyDiff`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DiffKind {
+    /// The node exists only in the new version.
+    Added,
+
+    /// The node exists only in the old version.
+    Removed,
+
+    /// The node exists in both versions with different values.
+    Changed,
+}
+
+/// A difference in a single node between two versions of metadata.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct PropertyDiff {
+    /// The kind of difference.
+    pub kind: DiffKind,
+
+    /// The namespace URI of the top-level property that contains the node.
+    pub namespace: String,
+
+    /// The path of the node.
+    ///
+    /// Items of alt-text arrays are identified by language rather than by
+    /// index (for example, `dc:title[?xml:lang="en-US"]`), so reordering
+    /// the languages of a localized property is not reported as a
+    /// difference.
+    pub path: String,
+
+    /// The value in the old version, or `None` if the node was added.
+    pub old_value: Option<String>,
+
+    /// The value in the new version, or `None` if the node was removed.
+    pub new_value: Option<String>,
+}
+
+/// The differences between two versions of metadata.
+///
+/// Create via [`XmpMeta::diff`].
+///
+/// Differences are reported for simple values (including qualifiers) and
+/// for empty arrays and structs. A property that was added or removed
+/// entirely is reported as one difference for each of its values.
+///
+/// The [`Display`](fmt::Display) implementation produces the same plain
+/// text summary as [`XmpDiff::to_text`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct XmpDiff {
+    /// The differences, ordered by namespace URI and then path.
+    pub differences: Vec<PropertyDiff>,
+}
+
+impl XmpDiff {
+    /// Returns `true` if the two versions are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.differences.is_empty()
+    }
+
+    /// Returns a plain text summary of the differences, grouped by schema.
+    ///
+    /// Each difference is written on its own line, prefixed with `+`
+    /// (added), `-` (removed), or `~` (changed).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use xmp_toolkit::{xmp_ns, XmpMeta};
+    /// let old = XmpMeta::new().unwrap();
+    ///
+    /// let mut new = XmpMeta::new().unwrap();
+    /// new.set_property(xmp_ns::XMP, "Label", &"Red".into())
+    ///     .unwrap();
+    ///
+    /// let text = old.diff(&new).to_text();
+    /// assert_eq!(
+    ///     text,
+    ///     "1 difference\n\nxmp (http://ns.adobe.com/xap/1.0/)\n  + xmp:Label = \"Red\"\n"
+    /// );
+    /// ```
+    pub fn to_text(&self) -> String {
+        let mut text = self.summary();
+
+        for (namespace, diffs) in self.by_schema() {
+            let _ = write!(text, "\n{}\n", schema_heading(namespace));
+
+            for diff in diffs {
+                let _ = match (&diff.old_value, &diff.new_value) {
+                    (Some(old), Some(new)) => {
+                        writeln!(text, "  ~ {} = {old:?} -> {new:?}", diff.path)
+                    }
+                    (None, Some(new)) => writeln!(text, "  + {} = {new:?}", diff.path),
+                    (Some(old), None) => writeln!(text, "  - {} = {old:?}", diff.path),
+                    (None, None) => Ok(()),
+                };
+            }
+        }
+
+        text
+    }
+
+    /// Returns a Markdown summary of the differences, with a table for
+    /// each schema.
+    ///
+    /// This is suitable for attaching to a test report or code review.
+    pub fn to_markdown(&self) -> String {
+        let mut md = self.summary();
+
+        for (namespace, diffs) in self.by_schema() {
+            let prefix = schema_prefix(namespace);
+            let _ = write!(
+                md,
+                "\n### {} (`{namespace}`)\n\n\
+                 | Change | Path | Old value | New value |\n\
+                 |--------|------|-----------|-----------|\n",
+                prefix.as_deref().unwrap_or("Unknown schema")
+            );
+
+            for diff in diffs {
+                let _ = writeln!(
+                    md,
+                    "| {:?} | `{}` | {} | {} |",
+                    diff.kind,
+                    diff.path,
+                    markdown_value(&diff.old_value),
+                    markdown_value(&diff.new_value)
+                );
+            }
+        }
+
+        md
+    }
+
+    fn summary(&self) -> String {
+        match self.differences.len() {
+            0 => "No differences\n".to_owned(),
+            1 => "1 difference\n".to_owned(),
+            n => format!("{n} differences\n"),
+        }
+    }
+
+    /// Groups consecutive differences by namespace. `differences` is
+    /// sorted by namespace, so each namespace appears once.
+    fn by_schema(&self) -> impl Iterator<Item = (&str, &[PropertyDiff])> {
+        let mut rest = self.differences.as_slice();
+
+        std::iter::from_fn(move || {
+            let namespace = rest.first()?.namespace.as_str();
+            let len = rest.iter().take_while(|d| d.namespace == namespace).count();
+
+            let (group, tail) = rest.split_at(len);
+            rest = tail;
+            Some((namespace, group))
+        })
+    }
+}
+
+impl fmt::Display for XmpDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_text())
+    }
+}
+
+pub(crate) fn diff(old: &XmpMeta, new: &XmpMeta) -> XmpDiff {
+    let old_nodes = flatten(old);
+    let new_nodes = flatten(new);
+
+    let mut differences: Vec<PropertyDiff> = old_nodes
+        .iter()
+        .filter_map(|(key, old_value)| {
+            let new_value = new_nodes.get(key);
+            if new_value == Some(old_value) {
+                return None;
+            }
+
+            Some(PropertyDiff {
+                kind: if new_value.is_some() {
+                    DiffKind::Changed
+                } else {
+                    DiffKind::Removed
+                },
+                namespace: key.0.clone(),
+                path: key.1.clone(),
+                old_value: Some(old_value.clone()),
+                new_value: new_value.cloned(),
+            })
+        })
+        .collect();
+
+    differences.extend(
+        new_nodes
+            .iter()
+            .filter(|(key, _)| !old_nodes.contains_key(key))
+            .map(|((namespace, path), new_value)| PropertyDiff {
+                kind: DiffKind::Added,
+                namespace: namespace.clone(),
+                path: path.clone(),
+                old_value: None,
+                new_value: Some(new_value.clone()),
+            }),
+    );
+
+    differences.sort_by(|a, b| {
+        a.namespace
+            .cmp(&b.namespace)
+            .then_with(|| natural_cmp(&a.path, &b.path))
+    });
+
+    XmpDiff { differences }
+}
+
+/// Returns the value of every simple node and empty container, keyed by
+/// namespace and path, with alt-text items identified by language.
+fn flatten(meta: &XmpMeta) -> HashMap<(String, String), String> {
+    let props: Vec<_> = meta
+        .iter(IterOptions::default())
+        .filter(|prop| !prop.value.is_schema_node() && !prop.name.is_empty())
+        .collect();
+
+    // Maps the index-based path of each alt-text item to its
+    // language-based path.
+    let mut lang_paths: Vec<(String, String)> = Vec::new();
+    let mut nodes = HashMap::new();
+
+    for (index, prop) in props.iter().enumerate() {
+        if prop.value.is_alt_text() {
+            let items = meta.array_len(&prop.schema_ns, &prop.name);
+            for item in 1..=items {
+                let item_path = format!("{}[{item}]", prop.name);
+                if let Some(lang) =
+                    meta.property(&prop.schema_ns, &format!("{item_path}/?xml:lang"))
+                {
+                    let lang_path = format!("{}[?xml:lang={:?}]", prop.name, lang.value);
+                    lang_paths.push((item_path, lang_path));
+                }
+            }
+        }
+
+        let is_container = prop.value.is_array() || prop.value.is_struct();
+        if is_container {
+            let has_children = props
+                .get(index + 1)
+                .is_some_and(|next| is_descendant(&next.name, &prop.name));
+
+            if has_children {
+                continue;
+            }
+        }
+
+        let mut path = prop.name.clone();
+        if let Some((item_path, lang_path)) = lang_paths
+            .iter()
+            .find(|(item_path, _)| is_descendant(&path, item_path) || path == *item_path)
+        {
+            if path == format!("{item_path}/?xml:lang") {
+                continue;
+            }
+
+            path = format!("{lang_path}{}", &path[item_path.len()..]);
+        }
+
+        let value = if prop.value.is_array() {
+            "[]".to_owned()
+        } else if is_container {
+            "{}".to_owned()
+        } else {
+            prop.value.value.clone()
+        };
+
+        nodes.insert((prop.schema_ns.clone(), path), value);
+    }
+
+    nodes
+}
+
+fn is_descendant(path: &str, ancestor: &str) -> bool {
+    path.strip_prefix(ancestor)
+        .is_some_and(|rest| rest.starts_with('/') || rest.starts_with('['))
+}
+
+/// Compares paths so that array indices are ordered numerically.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a = a.as_bytes();
+    let mut b = b.as_bytes();
+
+    loop {
+        match (a.first(), b.first()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let a_len = a.iter().take_while(|c| c.is_ascii_digit()).count();
+                let b_len = b.iter().take_while(|c| c.is_ascii_digit()).count();
+
+                let (a_num, a_rest) = a.split_at(a_len);
+                let (b_num, b_rest) = b.split_at(b_len);
+
+                let ord = a_len.cmp(&b_len).then_with(|| a_num.cmp(b_num));
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+
+                a = a_rest;
+                b = b_rest;
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(y);
+                }
+
+                a = &a[1..];
+                b = &b[1..];
+            }
+        }
+    }
+}
+
+fn schema_prefix(namespace: &str) -> Option<String> {
+    XmpMeta::namespace_prefix(namespace).map(|p| p.trim_end_matches(':').to_owned())
+}
+
+fn schema_heading(namespace: &str) -> String {
+    match schema_prefix(namespace) {
+        Some(prefix) => format!("{prefix} ({namespace})"),
+        None => namespace.to_owned(),
+    }
+}
+
+fn markdown_value(value: &Option<String>) -> String {
+    match value {
+        Some(value) => {
+            let escaped = value
+                .replace('\\', "\\\\")
+                .replace('|', "\\|")
+                .replace('\n', "<br>");
+            format!("\"{escaped}\"")
+        }
+        None => String::new(),
+    }
+}
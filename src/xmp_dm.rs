@@ -0,0 +1,525 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Contains typed access to the compound types of the XMP Dynamic Media
+//! (`xmpDM`) schema, which describes audio and video.
+//!
+//! The functions in this module read and write timecodes, durations,
+//! tracks and markers, tempo, and musical key with the field names and
+//! value formats required by the schema.
+
+use std::{fmt, str::FromStr};
+
+use crate::{xmp_ns, XmpError, XmpErrorType, XmpMeta, XmpResult, XmpValue};
+
+/// The frame rate and counting convention of a [`Timecode`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TimecodeFormat {
+    /// 23.976 frames per second (`23976Timecode`).
+    Fps23976,
+
+    /// 24 frames per second (`24Timecode`).
+    Fps24,
+
+    /// 25 frames per second (`25Timecode`).
+    Fps25,
+
+    /// 29.97 frames per second, drop-frame (`2997DropTimecode`).
+    Fps2997Drop,
+
+    /// 29.97 frames per second, non-drop-frame (`2997NonDropTimecode`).
+    Fps2997NonDrop,
+
+    /// 30 frames per second (`30Timecode`).
+    Fps30,
+
+    /// 50 frames per second (`50Timecode`).
+    Fps50,
+
+    /// 59.94 frames per second, drop-frame (`5994DropTimecode`).
+    Fps5994Drop,
+
+    /// 59.94 frames per second, non-drop-frame (`5994NonDropTimecode`).
+    Fps5994NonDrop,
+
+    /// 60 frames per second (`60Timecode`).
+    Fps60,
+}
+
+impl TimecodeFormat {
+    const ALL: [Self; 10] = [
+        Self::Fps23976,
+        Self::Fps24,
+        Self::Fps25,
+        Self::Fps2997Drop,
+        Self::Fps2997NonDrop,
+        Self::Fps30,
+        Self::Fps50,
+        Self::Fps5994Drop,
+        Self::Fps5994NonDrop,
+        Self::Fps60,
+    ];
+
+    /// Returns the value used for this format in `xmpDM:timeFormat`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Fps23976 => "23976Timecode",
+            Self::Fps24 => "24Timecode",
+            Self::Fps25 => "25Timecode",
+            Self::Fps2997Drop => "2997DropTimecode",
+            Self::Fps2997NonDrop => "2997NonDropTimecode",
+            Self::Fps30 => "30Timecode",
+            Self::Fps50 => "50Timecode",
+            Self::Fps5994Drop => "5994DropTimecode",
+            Self::Fps5994NonDrop => "5994NonDropTimecode",
+            Self::Fps60 => "60Timecode",
+        }
+    }
+
+    /// Returns `true` if this is a drop-frame format.
+    pub fn is_drop_frame(self) -> bool {
+        matches!(self, Self::Fps2997Drop | Self::Fps5994Drop)
+    }
+}
+
+impl FromStr for TimecodeFormat {
+    type Err = XmpError;
+
+    fn from_str(s: &str) -> XmpResult<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.as_str() == s)
+            .ok_or_else(|| bad_value(format!("Unknown timecode format {s:?}")))
+    }
+}
+
+impl fmt::Display for TimecodeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An `xmpDM:Timecode` value, such as `xmpDM:startTimecode`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Timecode {
+    time_value: String,
+    time_format: TimecodeFormat,
+}
+
+impl Timecode {
+    /// Creates a timecode from a time value in the form `hh:mm:ss:ff`.
+    ///
+    /// Drop-frame formats may use `;` instead of `:` as the separator, as
+    /// in `hh;mm;ss;ff`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::BadValue`] if
+    /// `time_value` is not in that form, or if a `;` separator is used
+    /// with a non-drop-frame format.
+    pub fn new(time_value: &str, time_format: TimecodeFormat) -> XmpResult<Self> {
+        let groups: Vec<&str> = time_value.split([':', ';']).collect();
+
+        let well_formed = groups.len() == 4
+            && groups
+                .iter()
+                .all(|g| g.len() == 2 && g.bytes().all(|b| b.is_ascii_digit()));
+
+        if !well_formed {
+            return Err(bad_value(format!(
+                "Timecode {time_value:?} is not in the form hh:mm:ss:ff"
+            )));
+        }
+
+        if time_value.contains(';') && !time_format.is_drop_frame() {
+            return Err(bad_value(format!(
+                "Timecode {time_value:?} uses drop-frame separators with {time_format}"
+            )));
+        }
+
+        Ok(Self {
+            time_value: time_value.to_owned(),
+            time_format,
+        })
+    }
+
+    /// Returns the time value (`xmpDM:timeValue`).
+    pub fn time_value(&self) -> &str {
+        &self.time_value
+    }
+
+    /// Returns the time format (`xmpDM:timeFormat`).
+    pub fn time_format(&self) -> TimecodeFormat {
+        self.time_format
+    }
+}
+
+/// An `xmpDM:Time` value, such as `xmpDM:duration`: a count of units of
+/// `scale_numerator / scale_denominator` seconds.
+///
+/// For example, 2 seconds of 48 kHz audio is a `value` of 96000 with a
+/// scale of 1/48000.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Time {
+    /// The number of units (`xmpDM:value`).
+    pub value: i64,
+
+    /// The numerator of the unit size in seconds (`xmpDM:scale`).
+    pub scale_numerator: i64,
+
+    /// The denominator of the unit size in seconds (`xmpDM:scale`).
+    pub scale_denominator: i64,
+}
+
+impl Time {
+    /// Returns this time in seconds.
+    ///
+    /// Returns `None` if the scale denominator is zero.
+    pub fn seconds(&self) -> Option<f64> {
+        if self.scale_denominator == 0 {
+            None
+        } else {
+            Some(self.value as f64 * self.scale_numerator as f64 / self.scale_denominator as f64)
+        }
+    }
+}
+
+/// An `xmpDM:Marker` value.
+///
+/// Each field corresponds to the `xmpDM` struct field of the same name and
+/// is `None` if that field is absent. Times are frame counts in the form
+/// used by the schema (for example, `"120"` or `"15f24"`), and are kept as
+/// strings.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Marker {
+    /// `xmpDM:name`.
+    pub name: Option<String>,
+
+    /// `xmpDM:comment`.
+    pub comment: Option<String>,
+
+    /// `xmpDM:startTime`.
+    pub start_time: Option<String>,
+
+    /// `xmpDM:duration`.
+    pub duration: Option<String>,
+
+    /// `xmpDM:location`: a URL for this marker's target.
+    pub location: Option<String>,
+
+    /// `xmpDM:target`: a frame or anchor within `location`.
+    pub target: Option<String>,
+
+    /// `xmpDM:type`: the kind of marker, such as `"Chapter"` or `"Cue"`.
+    pub marker_type: Option<String>,
+}
+
+/// An `xmpDM:Track` value: a named set of markers.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Track {
+    /// `xmpDM:trackName`.
+    pub track_name: Option<String>,
+
+    /// `xmpDM:trackType`: the kind of markers in this track, such as
+    /// `"Chapter"` or `"Cue"`.
+    pub track_type: Option<String>,
+
+    /// `xmpDM:frameRate`: the frame rate for the marker times, such as
+    /// `"f30"`.
+    pub frame_rate: Option<String>,
+
+    /// `xmpDM:markers`, in order.
+    pub markers: Vec<Marker>,
+}
+
+/// The values allowed for `xmpDM:key`.
+pub const KEYS: [&str; 12] = [
+    "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+];
+
+/// Reads the `xmpDM:startTimecode` property.
+///
+/// Returns `None` if the property is absent, or if either field is missing
+/// or invalid.
+pub fn start_timecode(meta: &XmpMeta) -> Option<Timecode> {
+    let time_value = field(meta, "startTimecode", "timeValue")?;
+    let time_format = field(meta, "startTimecode", "timeFormat")?.parse().ok()?;
+    Timecode::new(&time_value, time_format).ok()
+}
+
+/// Writes the `xmpDM:startTimecode` property, replacing any existing value.
+pub fn set_start_timecode(meta: &mut XmpMeta, timecode: &Timecode) -> XmpResult<()> {
+    let mut staged = meta.stage();
+
+    staged.delete_property(xmp_ns::DYNAMIC_MEDIA, "startTimecode")?;
+    set_field(
+        &mut staged,
+        "startTimecode",
+        "timeValue",
+        &timecode.time_value,
+    )?;
+    set_field(
+        &mut staged,
+        "startTimecode",
+        "timeFormat",
+        timecode.time_format.as_str(),
+    )?;
+
+    meta.commit_staged(staged);
+    Ok(())
+}
+
+/// Reads the `xmpDM:duration` property.
+///
+/// A missing scale is treated as 1/1 (seconds). Returns `None` if the
+/// property is absent or either field is invalid.
+pub fn duration(meta: &XmpMeta) -> Option<Time> {
+    let value = field(meta, "duration", "value")?.trim().parse().ok()?;
+
+    let (scale_numerator, scale_denominator) = match field(meta, "duration", "scale") {
+        Some(scale) => {
+            let (num, den) = scale.split_once('/')?;
+            (num.trim().parse().ok()?, den.trim().parse().ok()?)
+        }
+        None => (1, 1),
+    };
+
+    Some(Time {
+        value,
+        scale_numerator,
+        scale_denominator,
+    })
+}
+
+/// Writes the `xmpDM:duration` property, replacing any existing value.
+///
+/// ## Errors
+///
+/// Returns an error of type [`XmpErrorType::BadValue`] if the scale
+/// denominator is zero.
+pub fn set_duration(meta: &mut XmpMeta, duration: &Time) -> XmpResult<()> {
+    if duration.scale_denominator == 0 {
+        return Err(bad_value("Duration scale denominator is zero".to_owned()));
+    }
+
+    let mut staged = meta.stage();
+
+    staged.delete_property(xmp_ns::DYNAMIC_MEDIA, "duration")?;
+    set_field(
+        &mut staged,
+        "duration",
+        "value",
+        &duration.value.to_string(),
+    )?;
+    set_field(
+        &mut staged,
+        "duration",
+        "scale",
+        &format!(
+            "{}/{}",
+            duration.scale_numerator, duration.scale_denominator
+        ),
+    )?;
+
+    meta.commit_staged(staged);
+    Ok(())
+}
+
+/// Reads the `xmpDM:Tracks` property.
+///
+/// Returns an empty `Vec` if the property is absent.
+pub fn tracks(meta: &XmpMeta) -> Vec<Track> {
+    (1..=meta.array_len(xmp_ns::DYNAMIC_MEDIA, "Tracks") as i32)
+        .filter_map(|index| {
+            XmpMeta::compose_array_item_path(xmp_ns::DYNAMIC_MEDIA, "Tracks", index).ok()
+        })
+        .map(|path| read_track(meta, &path))
+        .collect()
+}
+
+/// Appends a track to the `xmpDM:Tracks` property, creating the property
+/// if necessary.
+///
+/// The update is atomic: If an error occurs, `meta` is left unchanged.
+pub fn append_track(meta: &mut XmpMeta, track: &Track) -> XmpResult<()> {
+    let mut staged = meta.stage();
+
+    staged.append_array_item(
+        xmp_ns::DYNAMIC_MEDIA,
+        &XmpValue::from("Tracks").set_is_array(true),
+        &XmpValue::default().set_is_struct(true),
+    )?;
+
+    let path =
+        XmpMeta::compose_array_item_path(xmp_ns::DYNAMIC_MEDIA, "Tracks", XmpMeta::LAST_ITEM)?;
+
+    set_optional_field(&mut staged, &path, "trackName", &track.track_name)?;
+    set_optional_field(&mut staged, &path, "trackType", &track.track_type)?;
+    set_optional_field(&mut staged, &path, "frameRate", &track.frame_rate)?;
+
+    if !track.markers.is_empty() {
+        let markers_path = XmpMeta::compose_struct_field_path(
+            xmp_ns::DYNAMIC_MEDIA,
+            &path,
+            xmp_ns::DYNAMIC_MEDIA,
+            "markers",
+        )?;
+
+        for marker in &track.markers {
+            append_marker(&mut staged, &markers_path, marker)?;
+        }
+    }
+
+    meta.commit_staged(staged);
+    Ok(())
+}
+
+/// Reads the `xmpDM:tempo` property, in beats per minute.
+pub fn tempo(meta: &XmpMeta) -> Option<f64> {
+    meta.property_f64(xmp_ns::DYNAMIC_MEDIA, "tempo")
+        .map(|v| v.value)
+}
+
+/// Writes the `xmpDM:tempo` property, in beats per minute.
+///
+/// ## Errors
+///
+/// Returns an error of type [`XmpErrorType::BadValue`] if `tempo` is not
+/// a positive, finite number.
+pub fn set_tempo(meta: &mut XmpMeta, tempo: f64) -> XmpResult<()> {
+    if !(tempo.is_finite() && tempo > 0.0) {
+        return Err(bad_value(format!("Invalid tempo {tempo}")));
+    }
+
+    meta.set_property_f64(xmp_ns::DYNAMIC_MEDIA, "tempo", &tempo.into())
+}
+
+/// Reads the `xmpDM:key` property.
+///
+/// Returns `None` if the property is absent or is not one of [`KEYS`].
+pub fn key(meta: &XmpMeta) -> Option<String> {
+    meta.property(xmp_ns::DYNAMIC_MEDIA, "key")
+        .map(|v| v.value)
+        .filter(|key| KEYS.contains(&key.as_str()))
+}
+
+/// Writes the `xmpDM:key` property.
+///
+/// ## Errors
+///
+/// Returns an error of type [`XmpErrorType::BadValue`] if `key` is not one
+/// of [`KEYS`].
+pub fn set_key(meta: &mut XmpMeta, key: &str) -> XmpResult<()> {
+    if !KEYS.contains(&key) {
+        return Err(bad_value(format!("Invalid musical key {key:?}")));
+    }
+
+    meta.set_property(xmp_ns::DYNAMIC_MEDIA, "key", &key.into())
+}
+
+fn read_track(meta: &XmpMeta, path: &str) -> Track {
+    let markers_path = XmpMeta::compose_struct_field_path(
+        xmp_ns::DYNAMIC_MEDIA,
+        path,
+        xmp_ns::DYNAMIC_MEDIA,
+        "markers",
+    )
+    .unwrap_or_default();
+
+    let markers = (1..=meta.array_len(xmp_ns::DYNAMIC_MEDIA, &markers_path) as i32)
+        .filter_map(|index| {
+            XmpMeta::compose_array_item_path(xmp_ns::DYNAMIC_MEDIA, &markers_path, index).ok()
+        })
+        .map(|marker_path| Marker {
+            name: field(meta, &marker_path, "name"),
+            comment: field(meta, &marker_path, "comment"),
+            start_time: field(meta, &marker_path, "startTime"),
+            duration: field(meta, &marker_path, "duration"),
+            location: field(meta, &marker_path, "location"),
+            target: field(meta, &marker_path, "target"),
+            marker_type: field(meta, &marker_path, "type"),
+        })
+        .collect();
+
+    Track {
+        track_name: field(meta, path, "trackName"),
+        track_type: field(meta, path, "trackType"),
+        frame_rate: field(meta, path, "frameRate"),
+        markers,
+    }
+}
+
+fn append_marker(meta: &mut XmpMeta, markers_path: &str, marker: &Marker) -> XmpResult<()> {
+    meta.append_array_item(
+        xmp_ns::DYNAMIC_MEDIA,
+        &XmpValue::from(markers_path)
+            .set_is_array(true)
+            .set_is_ordered(true),
+        &XmpValue::default().set_is_struct(true),
+    )?;
+
+    let path =
+        XmpMeta::compose_array_item_path(xmp_ns::DYNAMIC_MEDIA, markers_path, XmpMeta::LAST_ITEM)?;
+
+    set_optional_field(meta, &path, "name", &marker.name)?;
+    set_optional_field(meta, &path, "comment", &marker.comment)?;
+    set_optional_field(meta, &path, "startTime", &marker.start_time)?;
+    set_optional_field(meta, &path, "duration", &marker.duration)?;
+    set_optional_field(meta, &path, "location", &marker.location)?;
+    set_optional_field(meta, &path, "target", &marker.target)?;
+    set_optional_field(meta, &path, "type", &marker.marker_type)
+}
+
+fn field(meta: &XmpMeta, struct_path: &str, field_name: &str) -> Option<String> {
+    meta.struct_field(
+        xmp_ns::DYNAMIC_MEDIA,
+        struct_path,
+        xmp_ns::DYNAMIC_MEDIA,
+        field_name,
+    )
+    .map(|v| v.value)
+}
+
+fn set_field(
+    meta: &mut XmpMeta,
+    struct_path: &str,
+    field_name: &str,
+    value: &str,
+) -> XmpResult<()> {
+    meta.set_struct_field(
+        xmp_ns::DYNAMIC_MEDIA,
+        struct_path,
+        xmp_ns::DYNAMIC_MEDIA,
+        field_name,
+        &value.into(),
+    )
+}
+
+fn set_optional_field(
+    meta: &mut XmpMeta,
+    struct_path: &str,
+    field_name: &str,
+    value: &Option<String>,
+) -> XmpResult<()> {
+    match value {
+        Some(value) => set_field(meta, struct_path, field_name, value),
+        None => Ok(()),
+    }
+}
+
+fn bad_value(debug_message: String) -> XmpError {
+    XmpError {
+        error_type: XmpErrorType::BadValue,
+        debug_message,
+    }
+}
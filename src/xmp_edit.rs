@@ -0,0 +1,122 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use crate::XmpMeta;
+
+/// A set of edits to an [`XmpMeta`] that can be committed or rolled back
+/// as a unit.
+///
+/// Create via [`XmpMeta::begin_edit`]. The guard dereferences to an
+/// [`XmpMeta`], so all of the usual accessors and mutators are available
+/// through it. Edits are made to a working copy of the data model and
+/// become visible in the original object only when
+/// [`EditGuard::commit`] is called. Dropping the guard without committing
+/// discards the edits, as does [`EditGuard::rollback`].
+///
+/// Starting an edit makes one copy of the data model, regardless of how
+/// many changes are made before it is committed.
+///
+/// Listeners registered with [`XmpMeta::on_change`] are notified of the
+/// edits when they are committed; edits that are rolled back are never
+/// reported. Access statistics (see [`XmpMeta::enable_access_stats`])
+/// include accesses made through the guard whether or not the edits are
+/// committed.
+///
+/// Guards may be nested by calling [`XmpMeta::begin_edit`] on a guard.
+///
+/// ## Example
+///
+/// ```
+/// # use xmp_toolkit::{xmp_ns, XmpMeta};
+/// let mut meta = XmpMeta::new().unwrap();
+///
+/// let mut edit = meta.begin_edit();
+/// edit.set_property(xmp_ns::XMP, "Label", &"Red".into())
+///     .unwrap();
+/// edit.rollback();
+///
+/// assert!(!meta.contains_property(xmp_ns::XMP, "Label"));
+///
+/// let mut edit = meta.begin_edit();
+/// edit.set_property(xmp_ns::XMP, "Label", &"Green".into())
+///     .unwrap();
+/// edit.commit();
+///
+/// assert_eq!(meta.property(xmp_ns::XMP, "Label").unwrap().value, "Green");
+/// ```
+#[must_use = "edits are discarded unless EditGuard::commit is called"]
+pub struct EditGuard<'a> {
+    meta: &'a mut XmpMeta,
+    working: Option<XmpMeta>,
+}
+
+impl<'a> EditGuard<'a> {
+    pub(crate) fn new(meta: &'a mut XmpMeta) -> Self {
+        let working = meta.stage_for_edit();
+
+        Self {
+            meta,
+            working: Some(working),
+        }
+    }
+
+    /// Applies the edits to the original object.
+    pub fn commit(mut self) {
+        if let Some(working) = self.working.take() {
+            self.meta.commit_staged(working);
+        }
+    }
+
+    /// Discards the edits, leaving the original object unchanged.
+    ///
+    /// This is equivalent to dropping the guard.
+    pub fn rollback(self) {}
+}
+
+impl Deref for EditGuard<'_> {
+    type Target = XmpMeta;
+
+    fn deref(&self) -> &XmpMeta {
+        // `working` is only taken by `commit` and `drop`, which consume
+        // the guard.
+        self.working.as_ref().unwrap_or(self.meta)
+    }
+}
+
+impl DerefMut for EditGuard<'_> {
+    fn deref_mut(&mut self) -> &mut XmpMeta {
+        match &mut self.working {
+            Some(working) => working,
+            None => self.meta,
+        }
+    }
+}
+
+impl Drop for EditGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(working) = self.working.take() {
+            self.meta.abandon_staged(working);
+        }
+    }
+}
+
+impl fmt::Debug for EditGuard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("EditGuard").field(self.deref()).finish()
+    }
+}
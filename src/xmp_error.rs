@@ -53,6 +53,33 @@ impl XmpError {
             Ok(())
         }
     }
+
+    /// Returns the broad category for this error.
+    ///
+    /// Shorthand for `self.error_type.category()`.
+    pub fn category(&self) -> XmpErrorCategory {
+        self.error_type.category()
+    }
+
+    /// Returns `true` if retrying the same operation later might succeed.
+    ///
+    /// See [`XmpErrorCategory::Transient`].
+    pub fn is_transient(&self) -> bool {
+        self.category() == XmpErrorCategory::Transient
+    }
+
+    /// Returns `true` if the process can reasonably continue with other
+    /// work after this error.
+    ///
+    /// This is `false` only for [`XmpErrorCategory::ToolkitUnavailable`]
+    /// and [`XmpErrorCategory::Internal`], which suggest that further
+    /// calls into the toolkit are likely to fail as well.
+    pub fn is_recoverable(&self) -> bool {
+        !matches!(
+            self.category(),
+            XmpErrorCategory::ToolkitUnavailable | XmpErrorCategory::Internal
+        )
+    }
 }
 
 impl From<NulError> for XmpError {
@@ -300,6 +327,180 @@ pub enum XmpErrorType {
     /// [`FromStrOptions::require_xmp_meta()`]: crate::FromStrOptions::require_xmp_meta
     #[error("x:xmpmeta element not found")]
     XmpMetaElementMissing = -434,
+
+    /// A file is larger than the limit set with
+    /// [`OpenFileOptions::max_file_size()`].
+    ///
+    /// [`OpenFileOptions::max_file_size()`]: crate::OpenFileOptions::max_file_size
+    #[error("File exceeds maximum size")]
+    FileTooLarge = -435,
+
+    /// The content of a file does not match the format implied by its
+    /// extension.
+    ///
+    /// This error can only occur if you open a file with
+    /// [`OpenFileOptions::check_file_signature()`].
+    ///
+    /// [`OpenFileOptions::check_file_signature()`]: crate::OpenFileOptions::check_file_signature
+    #[error("File content does not match its extension")]
+    FileSignatureMismatch = -436,
+
+    /// An SVG file has no `<metadata>` element to hold the XMP.
+    ///
+    /// This error can only occur if you open a file with
+    /// [`OpenFileOptions::create_svg_metadata(false)`].
+    ///
+    /// [`OpenFileOptions::create_svg_metadata(false)`]: crate::OpenFileOptions::create_svg_metadata
+    #[error("SVG metadata element not found")]
+    SvgMetadataElementMissing = -437,
+
+    /// The C++ XMP Toolkit was already initialized when a setting that
+    /// only takes effect at initialization was requested.
+    ///
+    /// This error can only occur if you call
+    /// [`XmpFile::load_plugins()`] after the toolkit was first used.
+    ///
+    /// [`XmpFile::load_plugins()`]: crate::XmpFile::load_plugins
+    #[error("C++ XMP toolkit is already initialized")]
+    ToolkitAlreadyInitialized = -438,
+
+    /// An update would have changed a file outside of its XMP packet.
+    ///
+    /// This error can only occur if you open a file with
+    /// [`OpenFileOptions::update_xmp_in_place()`].
+    ///
+    /// [`OpenFileOptions::update_xmp_in_place()`]: crate::OpenFileOptions::update_xmp_in_place
+    #[error("XMP update can not be made in place")]
+    XmpUpdateNotInPlace = -439,
+
+    /// A file could not be opened because another process has it open
+    /// without sharing it.
+    ///
+    /// This error is only reported on Windows. Retrying later is likely to
+    /// succeed; see [`OpenFileOptions::retry_on_sharing_violation()`].
+    ///
+    /// [`OpenFileOptions::retry_on_sharing_violation()`]: crate::OpenFileOptions::retry_on_sharing_violation
+    #[error("File is in use by another process")]
+    FileSharingViolation = -440,
+
+    /// Another process holds a lock on a file that conflicts with the lock
+    /// requested.
+    ///
+    /// This error can only occur if you open a file with
+    /// [`OpenFileOptions::lock_file(FileLocking::FailIfLocked)`].
+    ///
+    /// [`OpenFileOptions::lock_file(FileLocking::FailIfLocked)`]: crate::OpenFileOptions::lock_file
+    #[error("File is locked by another process")]
+    FileLocked = -441,
+}
+
+impl XmpErrorType {
+    /// Returns the broad category for this error type.
+    ///
+    /// Batch processing code can use this to decide whether to retry,
+    /// skip the offending input, or stop altogether, without maintaining its
+    /// own mapping of every error type.
+    pub fn category(&self) -> XmpErrorCategory {
+        match self {
+            Self::BadObject
+            | Self::BadParam
+            | Self::BadValue
+            | Self::BadSchema
+            | Self::BadXPath
+            | Self::BadOptions
+            | Self::BadIndex
+            | Self::BadIterPosition
+            | Self::EmptyIterator
+            | Self::NoFileHandler
+            | Self::TooLargeForJpeg
+            | Self::NoFile
+            | Self::FilePathNotAFile
+            | Self::RejectedFileExtension
+            | Self::NulInRustString
+            | Self::XmpMetaElementMissing
+            | Self::FileTooLarge
+            | Self::FilePermission
+            | Self::SvgMetadataElementMissing
+            | Self::ToolkitAlreadyInitialized => XmpErrorCategory::UserError,
+
+            Self::ExternalFailure
+            | Self::NoMemory
+            | Self::DiskSpace
+            | Self::ReadError
+            | Self::WriteError
+            | Self::FileSharingViolation
+            | Self::FileLocked => XmpErrorCategory::Transient,
+
+            Self::BadParse
+            | Self::BadFileFormat
+            | Self::BadBlockFormat
+            | Self::BadXml
+            | Self::BadRdf
+            | Self::BadXmp
+            | Self::BadUnicode
+            | Self::BadTiff
+            | Self::BadJpeg
+            | Self::BadPsd
+            | Self::BadPsir
+            | Self::BadIptc
+            | Self::BadMpeg
+            | Self::BadPng
+            | Self::FileSignatureMismatch => XmpErrorCategory::CorruptData,
+
+            Self::Unimplemented
+            | Self::Deprecated
+            | Self::HeifConstructionMethodNotSupported
+            | Self::XmpUpdateNotInPlace => XmpErrorCategory::Unsupported,
+
+            Self::UserAbort | Self::ProgressAbort => XmpErrorCategory::Cancelled,
+
+            Self::Unavailable | Self::NoCppToolkit => XmpErrorCategory::ToolkitUnavailable,
+
+            Self::Unknown
+            | Self::Tbd
+            | Self::AssertFailure
+            | Self::EnforceFailure
+            | Self::InternalFailure
+            | Self::StdException
+            | Self::UnknownException
+            | Self::BadSerialize => XmpErrorCategory::Internal,
+        }
+    }
+}
+
+/// Broad classification of [`XmpErrorType`] values, intended to guide
+/// retry policies.
+///
+/// See [`XmpErrorType::category()`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum XmpErrorCategory {
+    /// The caller supplied an invalid parameter, path, or value, or lacks
+    /// permission to access the file. Retrying the same call will fail
+    /// again.
+    UserError,
+
+    /// The error may be caused by a temporary condition, such as a file
+    /// that is locked by another process, a full disk, or low memory.
+    /// Retrying later might succeed.
+    Transient,
+
+    /// The input file or XMP packet is malformed. Retrying will fail
+    /// again; the input should be skipped or repaired.
+    CorruptData,
+
+    /// The requested operation is not supported for this input or by
+    /// this build of the toolkit.
+    Unsupported,
+
+    /// The operation was cancelled at the request of the caller.
+    Cancelled,
+
+    /// The C++ XMP Toolkit is not available or did not initialize.
+    ToolkitUnavailable,
+
+    /// An unexpected failure occurred inside the toolkit.
+    Internal,
 }
 
 /// A specialized `Result` type for XMP Toolkit operations.
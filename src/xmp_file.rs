@@ -11,9 +11,40 @@
 // specific language governing permissions and limitations under
 // each license.
 
-use std::{ffi::CString, path::Path};
+use std::{
+    ffi::CString,
+    fs::{self, File},
+    io::{self, BufReader, Read, Seek, SeekFrom, Write},
+    ops::Range,
+    os::raw::{c_int, c_void},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
 
-use crate::{ffi, XmpError, XmpErrorType, XmpMeta, XmpResult};
+use filetime::FileTime;
+
+#[cfg(feature = "audit")]
+use crate::xmp_audit::{self, AuditEntry, AuditLog};
+use crate::{
+    ffi,
+    xmp_compound::{self, EmbeddedXmp},
+    xmp_dm::{self, Timecode},
+    xmp_file_format,
+    xmp_file_handler::{self, NativeFile, RepairHandler, XmpFileHandler},
+    xmp_file_lock::FileLock,
+    xmp_jpeg, xmp_mp3, xmp_mpeg4, xmp_ns,
+    xmp_packet::{self, PacketRepair},
+    xmp_psd::{self, PsdImageResource, PsdLayer},
+    xmp_sidecar::Sidecar,
+    FileFormat, ToStringOptions, XmpDateTime, XmpError, XmpErrorCategory, XmpErrorType, XmpMeta,
+    XmpResult,
+};
 
 /// Provides access to the main (document-level) metadata in many file formats.
 ///
@@ -29,15 +60,142 @@ use crate::{ffi, XmpError, XmpErrorType, XmpMeta, XmpResult};
 ///
 /// A file can be opened for read-only or read-write access, with typical
 /// exclusion for both modes.
+///
+/// Most file formats are handled by the C++ XMP Toolkit. A few formats that
+/// it doesn't support, such as WebP ([`FileFormat::WebP`]), JPEG XL
+/// ([`FileFormat::JpegXl`]), and Canon CR3 ([`FileFormat::Cr3`]), are
+/// handled by this crate; such files are read into memory when opened and,
+/// if updated, rewritten in full when closed.
+///
+/// ## Closing files
+///
+/// Updates made with [`XmpFile::put_xmp`] are only written when
+/// [`XmpFile::close`] is called, and any error writing them is returned
+/// from that call. Dropping the struct instead discards the updates,
+/// since `Drop` can't report an error; a warning is logged through the
+/// [`log`] crate when that happens. Use [`XmpFile::has_pending_update`] to
+/// check for unwritten updates, for example before returning early from a
+/// function on an error path.
 pub struct XmpFile {
     f: *mut ffi::CXmpFile,
+    path: Option<PathBuf>,
+    pending_update: bool,
+
+    // The stream passed to `open_stream` or `open_stream_for_update`, if
+    // any. It is boxed twice so that the C++ toolkit can hold a thin
+    // pointer to it.
+    stream: Option<Box<Box<dyn ffi::StreamIo>>>,
+
+    // True if the file was opened by `from_bytes`.
+    in_memory: bool,
+
+    // Set if the open file is processed by one of this crate's own format
+    // handlers rather than by the C++ toolkit.
+    native: Option<NativeFile>,
+
+    // The callback passed to `set_progress_callback`, if any. Boxed twice
+    // for the same reason as `stream`.
+    progress: Option<Box<Box<ffi::FileProgressFn>>>,
+
+    // The token passed to `set_cancellation_token`, if any. The C++
+    // toolkit holds a pointer to its flag.
+    cancellation: Option<CancellationToken>,
+
+    // True if the file is an SVG file without a `<metadata>` element and
+    // `OpenFileOptions::create_svg_metadata(false)` was requested.
+    svg_metadata_missing: bool,
+
+    // The temporary copy of `path` that the C++ toolkit updates instead of
+    // the file itself if `OpenFileOptions::update_safely` was requested.
+    safe_save_path: Option<PathBuf>,
+
+    // Where temporary files are written, if
+    // `OpenFileOptions::temp_folder` was requested.
+    temp_folder: Option<PathBuf>,
+
+    // The access and modification times of `path` when it was opened, if
+    // `OpenFileOptions::preserve_file_times` was requested.
+    file_times: Option<(FileTime, FileTime)>,
+
+    // The EXIF and IPTC segments of a JPEG file when it was opened, if
+    // `OpenFileOptions::keep_legacy_metadata` was requested. They replace
+    // whatever the C++ toolkit writes when the file is closed.
+    legacy_segments: Option<Vec<Vec<u8>>>,
+
+    // Set if `OpenFileOptions::update_xmp_in_place` was requested for a
+    // file opened for update by the C++ toolkit.
+    in_place: Option<InPlaceUpdate>,
+
+    // The problems fixed in the file's XMP packet, if it was damaged and
+    // `OpenFileOptions::repair_xmp` was requested.
+    repairs: Vec<PacketRepair>,
+
+    // True if `OpenFileOptions::sidecar_fallback` was requested.
+    sidecar_fallback: bool,
+
+    // The XMP to be written to the file's sidecar when it is closed, if
+    // `put_xmp` fell back to the sidecar. Once set, later updates go to the
+    // sidecar as well.
+    sidecar_xmp: Option<XmpMeta>,
+
+    // True if an update is to be written to the file itself when it is
+    // closed. `pending_update` also covers updates to the sidecar.
+    embedded_update: bool,
+
+    // The lock taken with `OpenFileOptions::lock_file` on a file opened
+    // for update, released when the file is closed.
+    lock: Option<FileLock>,
+
+    // The start of the timecode track of an MPEG-4 or QuickTime file
+    // opened with `OpenFileOptions::reconcile_quicktime(false)`, which the
+    // C++ toolkit then doesn't read.
+    start_timecode: Option<Timecode>,
+
+    #[cfg(feature = "audit")]
+    audit: Option<FileAudit>,
+}
+
+struct InPlaceUpdate {
+    // The location of the XMP packet when the file was opened, which is
+    // the only part of the file that may change.
+    packet: Option<Range<u64>>,
+
+    // The content of a file opened by `from_bytes`, to be compared with
+    // the updated content. Files opened from a path are updated in a
+    // temporary copy and compared with the original instead.
+    original: Option<Vec<u8>>,
+}
+
+#[cfg(feature = "audit")]
+struct FileAudit {
+    log: AuditLog,
+    path: std::path::PathBuf,
+    before: Option<String>,
+    after: Option<String>,
+    pending: bool,
 }
 
 impl Drop for XmpFile {
+    /// Releases the file without writing it.
+    ///
+    /// Logs a warning if [`XmpFile::put_xmp`] was called without a
+    /// subsequent [`XmpFile::close`], since those updates are lost.
     fn drop(&mut self) {
+        if self.pending_update {
+            log::warn!(
+                "XmpFile dropped without calling close() after put_xmp(); updates to {} were lost",
+                self.path
+                    .as_deref()
+                    .unwrap_or_else(|| Path::new("(unknown)"))
+                    .display()
+            );
+        }
+
         unsafe {
             ffi::CXmpFileDrop(self.f);
         }
+
+        self.discard_safe_save();
     }
 }
 
@@ -52,7 +210,30 @@ impl XmpFile {
         let f = unsafe { ffi::CXmpFileNew(&mut err) };
         XmpError::raise_from_c(&err)?;
 
-        Ok(XmpFile { f })
+        Ok(XmpFile {
+            f,
+            path: None,
+            pending_update: false,
+            stream: None,
+            in_memory: false,
+            native: None,
+            progress: None,
+            cancellation: None,
+            svg_metadata_missing: false,
+            safe_save_path: None,
+            temp_folder: None,
+            file_times: None,
+            legacy_segments: None,
+            in_place: None,
+            repairs: Vec::new(),
+            sidecar_fallback: false,
+            sidecar_xmp: None,
+            embedded_update: false,
+            lock: None,
+            start_timecode: None,
+            #[cfg(feature = "audit")]
+            audit: None,
+        })
     }
 
     /// Opens a file for the requested forms of metadata access.
@@ -87,16 +268,230 @@ impl XmpFile {
     ///   read-only access and the format handler decides on the level of
     ///   reconciliation that will be performed. See [`OpenFileOptions`] for
     ///   other options.
+    ///
+    /// ## Errors
+    ///
+    /// In addition to errors reported by the C++ XMP Toolkit, returns an
+    /// error of type [`XmpErrorType::FileTooLarge`] or
+    /// [`XmpErrorType::FileSignatureMismatch`] if the file fails one of the
+    /// checks requested with [`OpenFileOptions::max_file_size`] or
+    /// [`OpenFileOptions::check_file_signature`]. These checks are made
+    /// before the file is handed to the C++ XMP Toolkit.
+    ///
+    /// On Windows, returns an error of type
+    /// [`XmpErrorType::FileSharingViolation`] if another process has the
+    /// file open without sharing it, as image browsers and catalog
+    /// applications often do for a short while. Such errors can be retried
+    /// automatically with [`OpenFileOptions::retry_on_sharing_violation`].
+    ///
+    /// Returns an error of type [`XmpErrorType::FileLocked`] if the file is
+    /// locked by another process and [`FileLocking::FailIfLocked`] was
+    /// passed to [`OpenFileOptions::lock_file`].
     pub fn open_file<P: AsRef<Path>>(&mut self, path: P, flags: OpenFileOptions) -> XmpResult<()> {
-        if let Some(c_path) = path_to_cstr(path.as_ref()) {
+        let path = path.as_ref();
+        let mut retries = 0;
+
+        loop {
+            // Release any lock on a file opened earlier, which might be the
+            // same file.
+            self.lock = None;
+
+            let opened = flags
+                .lock(path)
+                .and_then(|lock| self.open_file_once(path, &flags).map(|()| lock));
+
+            let err = match opened {
+                Ok(lock) => {
+                    if flags.opens_for_update() {
+                        self.lock = lock;
+                    }
+                    return Ok(());
+                }
+                Err(err) => sharing_violation(err, path, flags.opens_for_update()),
+            };
+
+            let delay = match flags.sharing_retry() {
+                Some(retry) if err.error_type == XmpErrorType::FileSharingViolation => {
+                    retry.delay(retries)
+                }
+                _ => None,
+            };
+
+            let cancelled = self
+                .cancellation
+                .as_ref()
+                .is_some_and(CancellationToken::is_cancelled);
+
+            match delay {
+                Some(delay) if !cancelled => {
+                    thread::sleep(delay);
+                    retries += 1;
+                }
+                _ => return Err(err),
+            }
+        }
+    }
+
+    fn open_file_once(&mut self, path: &Path, flags: &OpenFileOptions) -> XmpResult<()> {
+        flags.check_before_open(path)?;
+
+        self.reset_open_state();
+        self.temp_folder = flags.temp_folder_path().map(Path::to_path_buf);
+        self.sidecar_fallback = flags.falls_back_to_sidecar();
+
+        self.file_times = if flags.preserves_file_times() && flags.opens_for_update() {
+            fs::metadata(path).ok().map(|m| {
+                (
+                    FileTime::from_last_access_time(&m),
+                    FileTime::from_last_modification_time(&m),
+                )
+            })
+        } else {
+            None
+        };
+
+        if let Some(handler) = xmp_file_handler::handler_for_path(path, flags) {
+            self.native = Some(NativeFile::open(handler, path, flags)?);
+
+            self.path = Some(path.to_path_buf());
+            self.in_memory = false;
+
+            #[cfg(feature = "audit")]
+            self.start_audit(path);
+
+            return Ok(());
+        }
+
+        if flags.keeps_legacy_metadata() && flags.opens_for_update() {
+            let file = fs::read(path).map_err(|err| XmpError {
+                error_type: XmpErrorType::ReadError,
+                debug_message: err.to_string(),
+            })?;
+
+            self.legacy_segments = Some(legacy_segments_to_keep(&file)?);
+        }
+
+        if let Some(limit) = flags.scan_limit(path) {
+            let mut head = Vec::new();
+            File::open(path)
+                .and_then(|file| file.take(limit).read_to_end(&mut head))
+                .map_err(|err| XmpError {
+                    error_type: XmpErrorType::ReadError,
+                    debug_message: err.to_string(),
+                })?;
+
+            let format = xmp_file_format::registered_format(path);
+            return self.open_file_in_memory(path, &head, format, flags);
+        }
+
+        if flags.reads_buffered() {
+            let file = BufferedFile::open(path).map_err(|err| XmpError {
+                error_type: XmpErrorType::ReadError,
+                debug_message: err.to_string(),
+            })?;
+
+            let format = xmp_file_format::registered_format(path);
+            self.open_stream_io(
+                Box::new(Box::new(ffi::ReadOnlyStream(file))),
+                false,
+                format,
+                flags.clone(),
+            )?;
+
+            if flags.keeps_svg_without_metadata() && format == FileFormat::Svg {
+                self.svg_metadata_missing = fs::read(path)
+                    .map(|svg| !has_svg_metadata_element(&svg))
+                    .unwrap_or(false);
+            }
+
+            self.path = Some(path.to_path_buf());
+            self.in_memory = false;
+
+            #[cfg(feature = "audit")]
+            self.start_audit(path);
+
+            return Ok(());
+        }
+
+        let update_in_place = flags.updates_xmp_in_place() && flags.opens_for_update();
+
+        // The C++ toolkit opens a copy of the file, which replaces the file
+        // itself when closed.
+        let open_path = if update_in_place || (flags.updates_safely() && flags.opens_for_update()) {
+            let temp_path = xmp_file_handler::temp_path(path, self.temp_folder.as_deref());
+            xmp_file_handler::copy_to_temp_file(path, &temp_path).map_err(|err| XmpError {
+                error_type: XmpErrorType::WriteError,
+                debug_message: err.to_string(),
+            })?;
+
+            self.safe_save_path = Some(temp_path.clone());
+            temp_path
+        } else {
+            path.to_path_buf()
+        };
+
+        let header = xmp_file_handler::read_header(path).unwrap_or_default();
+
+        if let Some(c_path) = path_to_cstr(&open_path) {
             let mut err = ffi::CXmpError::default();
 
             unsafe {
-                ffi::CXmpFileOpen(self.f, &mut err, c_path.as_ptr(), flags.options);
+                ffi::CXmpFileOpen(
+                    self.f,
+                    &mut err,
+                    c_path.as_ptr(),
+                    xmp_file_format::registered_format(path) as u32,
+                    flags.c_options(&header),
+                );
+            }
+
+            if let Err(err) = XmpError::raise_from_c(&err) {
+                self.discard_safe_save();
+
+                if flags.repairs_xmp()
+                    && err.error_type.category() == XmpErrorCategory::CorruptData
+                    && self.open_repaired_file(path, flags)
+                {
+                    return Ok(());
+                }
+
+                return Err(err);
+            }
+
+            if flags.repairs_xmp() && self.xmp().is_none() && self.open_repaired_file(path, flags) {
+                return Ok(());
             }
 
-            XmpError::raise_from_c(&err)
+            if update_in_place {
+                self.in_place = Some(InPlaceUpdate {
+                    packet: self.packet_info().and_then(|info| info.range()),
+                    original: None,
+                });
+            }
+
+            if flags.reads_timecode_track(&header) {
+                self.start_timecode = File::open(path)
+                    .ok()
+                    .and_then(|mut file| xmp_mpeg4::start_timecode(&mut file).ok().flatten());
+            }
+
+            if flags.keeps_svg_without_metadata()
+                && Self::check_file_format(path).ok().flatten() == Some(FileFormat::Svg)
+            {
+                self.svg_metadata_missing = fs::read(path)
+                    .map(|svg| !has_svg_metadata_element(&svg))
+                    .unwrap_or(false);
+            }
+
+            self.path = Some(path.to_path_buf());
+            self.in_memory = false;
+
+            #[cfg(feature = "audit")]
+            self.start_audit(path);
+
+            Ok(())
         } else {
+            self.discard_safe_save();
             Err(XmpError {
                 error_type: XmpErrorType::BadParam,
                 debug_message: "Could not convert path to C string".to_owned(),
@@ -104,198 +499,2502 @@ impl XmpFile {
         }
     }
 
-    /// Retrieves the XMP metadata from an open file.
-    ///
-    /// If no XMP is present, will return `None`.
-    pub fn xmp(&mut self) -> Option<XmpMeta> {
+    /// Opens `bytes`, read from the file at `path`, for reading.
+    fn open_file_in_memory(
+        &mut self,
+        path: &Path,
+        bytes: &[u8],
+        format: FileFormat,
+        flags: &OpenFileOptions,
+    ) -> XmpResult<()> {
+        let mut err = ffi::CXmpError::default();
+
         unsafe {
-            let m = ffi::CXmpFileGetXmp(self.f);
-            if m.is_null() {
-                None
-            } else {
-                Some(XmpMeta { m: Some(m) })
-            }
+            ffi::CXmpFileOpenBytes(
+                self.f,
+                &mut err,
+                bytes.as_ptr(),
+                bytes.len(),
+                format as u32,
+                flags.c_options(bytes),
+            );
+        }
+
+        XmpError::raise_from_c(&err)?;
+
+        if flags.reads_timecode_track(bytes) {
+            self.start_timecode = read_start_timecode(bytes);
         }
+
+        self.path = Some(path.to_path_buf());
+        self.in_memory = false;
+
+        #[cfg(feature = "audit")]
+        self.start_audit(path);
+
+        Ok(())
     }
 
-    /// Reports whether this file can be updated with a specific XMP packet.
-    ///
-    /// Use this function to determine if the file can probably be updated with
-    /// a given set of XMP metadata. This depends on the size of the packet,
-    /// the options with which the file was opened, and the capabilities of
-    /// the handler for the file format. The function obtains the length of
-    /// the serialized packet for the provided XMP, but does not keep it or
-    /// modify it, and does not cause the file to be written when closed.
-    pub fn can_put_xmp(&self, meta: &XmpMeta) -> bool {
-        if let Some(m) = meta.m {
-            unsafe { ffi::CXmpFileCanPutXmp(self.f, m) != 0 }
-        } else {
-            false
+    /// Falls back to reading the damaged XMP packet of the file at `path`
+    /// in Rust. Returns `false` if no packet can be recovered.
+    fn open_repaired_file(&mut self, path: &Path, flags: &OpenFileOptions) -> bool {
+        let Ok(bytes) = fs::read(path) else {
+            return false;
+        };
+
+        if !self.open_repaired(bytes, Some(path), flags) {
+            return false;
         }
+
+        #[cfg(feature = "audit")]
+        self.start_audit(path);
+
+        true
     }
 
-    /// Updates the XMP metadata in this object without writing out the file.
-    ///
-    /// This function supplies new XMP for the file. However, the disk file is
-    /// not written until the struct is closed with [`XmpFile::close`].
-    /// The options provided when the file was opened determine if
-    /// reconciliation is done with other forms of metadata.
-    pub fn put_xmp(&mut self, meta: &XmpMeta) -> XmpResult<()> {
-        if let Some(m) = meta.m {
-            let mut err = ffi::CXmpError::default();
-            unsafe { ffi::CXmpFilePutXmp(self.f, &mut err, m) };
-            XmpError::raise_from_c(&err)
-        } else {
-            Err(crate::xmp_meta::no_cpp_toolkit())
+    /// Falls back to reading the damaged XMP packet in `bytes`, the content
+    /// of the file at `path` (if any), for `OpenFileOptions::repair_xmp`.
+    /// Returns `false` if no packet can be recovered, in which case the file
+    /// is left as the C++ toolkit opened it.
+    fn open_repaired(
+        &mut self,
+        bytes: Vec<u8>,
+        path: Option<&Path>,
+        flags: &OpenFileOptions,
+    ) -> bool {
+        let Ok(Some(packet)) = xmp_packet::repair(&bytes) else {
+            return false;
+        };
+
+        let native = match path {
+            Some(path) => NativeFile::from_file_bytes(&RepairHandler, path, bytes, flags),
+            None => NativeFile::from_bytes(&RepairHandler, bytes, flags),
+        };
+
+        let Ok(native) = native else {
+            return false;
+        };
+
+        // Release the file if the C++ toolkit opened it.
+        let mut err = ffi::CXmpError::default();
+        unsafe { ffi::CXmpFileClose(self.f, &mut err) };
+        self.discard_safe_save();
+        self.in_place = None;
+
+        self.native = Some(native);
+        self.repairs = packet.repairs;
+
+        if let Some(path) = path {
+            self.path = Some(path.to_path_buf());
+            self.in_memory = false;
         }
+
+        true
     }
 
-    /// Explicitly closes an opened file.
+    /// Opens a stream, such as an object in remote storage or a member of
+    /// an archive, for reading its metadata.
     ///
-    /// Performs any necessary output to the file and closes it. Files that are
-    /// opened for update are written to only when closing.
+    /// This behaves like [`XmpFile::open_file`], but the C++ XMP Toolkit
+    /// reads the file's content through `reader` instead of from the local
+    /// file system. `reader` is kept until another file or stream is
+    /// opened or this struct is dropped.
     ///
-    /// If the file is opened for read-only access (passing
-    /// [`OpenFileOptions::for_read`]), the disk file is closed
-    /// immediately after reading the data from it; the `XMPFile`
-    /// struct, however, remains in the open state. You must call
-    /// [`XmpFile::close`] when finished using it. Other methods, such as
-    /// [`XmpFile::xmp`], can only be used between the
-    /// [`XmpFile::open_file`] and [`XmpFile::close`] calls. The `XMPFile`
-    /// destructor does not call [`XmpFile::close`]; if the struct is
-    /// dropped without closing, any pending updates are lost.
+    /// Since there is no file name, the format can't be inferred from a
+    /// file extension. Pass the format in `format_hint` if it is known;
+    /// otherwise pass [`FileFormat::Unknown`] and the format handlers will
+    /// inspect the content.
     ///
-    /// If the file is opened for update (passing
-    /// [`OpenFileOptions::for_update`]), the disk file remains open until
-    /// [`XmpFile::close`] is called. The disk file is only updated once,
-    /// when [`XmpFile::close`] is called, regardless of how many calls are
-    /// made to [`XmpFile::put_xmp`].
+    /// ## Errors
     ///
-    /// IMPORTANT: The original design of this API did not include any
-    /// error reporting, so this function ignores any error that may be
-    /// reported by the C++ XMP Toolkit. Use [`XmpFile::try_close`] to
-    /// receive any error reporting.
-    pub fn close(&mut self) {
-        let _ = self.try_close();
-        // Ignore error for backward compatibility.
-        // If you want to see the error, use try_close().
-    }
-
-    /// Explicitly closes an opened file.
+    /// Returns an error of type [`XmpErrorType::BadParam`] if `flags`
+    /// includes [`OpenFileOptions::for_update`]. Use
+    /// [`XmpFile::open_stream_for_update`] to update a stream.
     ///
-    /// Performs any necessary output to the file and closes it. Files that are
-    /// opened for update are written to only when closing.
+    /// [`OpenFileOptions::max_file_size`] is checked against the length of
+    /// the stream. [`OpenFileOptions::check_file_signature`] has no effect.
     ///
-    /// If the file is opened for read-only access (passing
-    /// [`OpenFileOptions::for_read`]), the disk file is closed
-    /// immediately after reading the data from it; the `XMPFile`
-    /// struct, however, remains in the open state. You must call
-    /// [`XmpFile::close`] when finished using it. Other methods, such as
-    /// [`XmpFile::xmp`], can only be used between the
-    /// [`XmpFile::open_file`] and [`XmpFile::close`] calls. The `XMPFile`
-    /// destructor does not call [`XmpFile::close`]; if the struct is
-    /// dropped without closing, any pending updates are lost.
+    /// ## Example
     ///
-    /// If the file is opened for update (passing
-    /// [`OpenFileOptions::for_update`]), the disk file remains open until
-    /// [`XmpFile::close`] is called. The disk file is only updated once,
-    /// when [`XmpFile::close`] is called, regardless of how many calls are
-    /// made to [`XmpFile::put_xmp`].
-    pub fn try_close(&mut self) -> XmpResult<()> {
-        let mut err = ffi::CXmpError::default();
-        unsafe { ffi::CXmpFileClose(self.f, &mut err) };
-        XmpError::raise_from_c(&err)
-    }
-}
-
-/// Option flags for [`XmpFile::open_file`].
-///
-/// Invoke by calling [`OpenFileOptions::default`] and then calling methods
-/// on this struct to add options as needed.
-#[derive(Default)]
-pub struct OpenFileOptions {
-    pub(crate) options: u32,
-}
-
-impl OpenFileOptions {
-    /// Open for read-only access.
+    /// ```no_run
+    /// # use std::io::Cursor;
+    /// # use xmp_toolkit::{FileFormat, OpenFileOptions, XmpFile};
+    /// # let bytes: Vec<u8> = Vec::new();
+    /// let mut f = XmpFile::new().unwrap();
+    /// f.open_stream(
+    ///     Cursor::new(bytes),
+    ///     FileFormat::Jpeg,
+    ///     OpenFileOptions::default().for_read(),
+    /// )
+    /// .unwrap();
     ///
-    /// See `kXMPFiles_OpenForRead` constant in C++ SDK.
-    pub fn for_read(mut self) -> Self {
-        self.options |= 0x00000001;
-        self
-    }
+    /// let xmp = f.xmp();
+    /// ```
+    pub fn open_stream<R: Read + Seek + Send + 'static>(
+        &mut self,
+        reader: R,
+        format_hint: FileFormat,
+        flags: OpenFileOptions,
+    ) -> XmpResult<()> {
+        if flags.opens_for_update() {
+            return Err(XmpError {
+                error_type: XmpErrorType::BadParam,
+                debug_message: "Use open_stream_for_update to update a stream".to_owned(),
+            });
+        }
 
-    /// Open for reading and writing.
-    ///
-    /// See `kXMPFiles_OpenForUpdate` constant in C++ SDK.
-    pub fn for_update(mut self) -> Self {
-        self.options |= 0x00000002;
-        self
+        self.reset_open_state();
+        self.open_stream_io(
+            Box::new(Box::new(ffi::ReadOnlyStream(reader))),
+            false,
+            format_hint,
+            flags,
+        )
     }
 
-    /// Only the XMP is wanted.
+    /// Opens a stream, such as a file in a virtual file system, for
+    /// reading and updating its metadata.
     ///
-    /// This allows space/time optimizations.
+    /// This behaves like [`XmpFile::open_stream`], except that the stream
+    /// is opened for update, whether or not `flags` includes
+    /// [`OpenFileOptions::for_update`]. Updates made with
+    /// [`XmpFile::put_xmp`] are written to `stream` when
+    /// [`XmpFile::close`] is called, which then flushes the stream.
     ///
-    /// See `kXMPFiles_OpenOnlyXMP` constant in C++ SDK.
-    pub fn only_xmp(mut self) -> Self {
-        self.options |= 0x00000004;
-        self
-    }
-
-    /// Force use of the given handler (format).
+    /// Format handlers that need to rewrite the whole file assemble the new
+    /// content in memory and then copy it to the stream, truncating the
+    /// stream if the content got shorter. Use [`XmpFile::from_bytes`]
+    /// instead if the content is already held in memory.
     ///
-    /// Do not even verify the format.
+    /// ## Example
     ///
-    /// See `kXMPFiles_ForceGivenHandler` constant in C++ SDK.
-    pub fn force_given_handler(mut self) -> Self {
-        self.options |= 0x00000008;
-        self
-    }
-
-    /// Be strict about only attempting to use the designated file handler.
+    /// ```no_run
+    /// # use std::fs::OpenOptions;
+    /// # use xmp_toolkit::{xmp_ns, FileFormat, OpenFileOptions, XmpFile};
+    /// # fn example() -> xmp_toolkit::XmpResult<()> {
+    /// let stream = OpenOptions::new()
+    ///     .read(true)
+    ///     .write(true)
+    ///     .open("image.jpg")
+    ///     .unwrap();
     ///
-    /// Do not fall back to other handlers.
+    /// let mut f = XmpFile::new()?;
+    /// f.open_stream_for_update(stream, FileFormat::Jpeg, OpenFileOptions::default())?;
     ///
-    /// See `kXMPFiles_OpenStrictly` constant in C++ SDK.
-    pub fn strict(mut self) -> Self {
-        self.options |= 0x00000010;
-        self
+    /// let mut m = f.xmp().unwrap_or_default();
+    /// m.set_property(xmp_ns::XMP, "Label", &"Reviewed".into())?;
+    /// f.put_xmp(&m)?;
+    /// f.close()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn open_stream_for_update<S: UpdatableStream + 'static>(
+        &mut self,
+        stream: S,
+        format_hint: FileFormat,
+        flags: OpenFileOptions,
+    ) -> XmpResult<()> {
+        self.reset_open_state();
+        self.open_stream_io(
+            Box::new(Box::new(ffi::UpdatableStreamIo(stream))),
+            true,
+            format_hint,
+            flags.for_update(),
+        )
     }
 
-    /// Require the use of a smart handler.
-    ///
-    /// See `kXMPFiles_OpenUseSmartHandler` constant in C++ SDK.
-    pub fn use_smart_handler(mut self) -> Self {
-        self.options |= 0x00000020;
-        self
-    }
+    fn open_stream_io(
+        &mut self,
+        mut stream: Box<Box<dyn ffi::StreamIo>>,
+        writable: bool,
+        format_hint: FileFormat,
+        flags: OpenFileOptions,
+    ) -> XmpResult<()> {
+        flags.check_stream_before_open(stream.as_mut().as_mut())?;
 
-    /// Force packet scanning.
-    ///
-    /// Do not use a smart handler.
-    ///
-    /// See `kXMPFiles_OpenUsePacketScanning` constant in C++ SDK.
-    pub fn use_packet_scanning(mut self) -> Self {
-        self.options |= 0x00000040;
-        self
+        let mut reader = ffi::StreamReader(stream.as_mut().as_mut());
+        let header = read_stream_header(&mut reader)?;
+
+        let start_timecode = if flags.reads_timecode_track(&header) {
+            let timecode = xmp_mpeg4::start_timecode(&mut reader).ok().flatten();
+            reader.rewind().map_err(|err| XmpError {
+                error_type: XmpErrorType::ReadError,
+                debug_message: err.to_string(),
+            })?;
+            timecode
+        } else {
+            None
+        };
+
+        let (write_proc, truncate_proc) = if writable {
+            (
+                Some(ffi::xmp_stream_write as _),
+                Some(ffi::xmp_stream_truncate as _),
+            )
+        } else {
+            (None, None)
+        };
+
+        let mut err = ffi::CXmpError::default();
+
+        unsafe {
+            ffi::CXmpFileOpenStream(
+                self.f,
+                &mut err,
+                (stream.as_mut() as *mut Box<dyn ffi::StreamIo>).cast::<c_void>(),
+                ffi::xmp_stream_read,
+                ffi::xmp_stream_seek,
+                write_proc,
+                truncate_proc,
+                format_hint as u32,
+                flags.c_options(&header),
+            );
+        }
+
+        XmpError::raise_from_c(&err)?;
+
+        self.stream = Some(stream);
+        self.start_timecode = start_timecode;
+        Ok(())
     }
 
-    /// Only packet scan files "known" to need scanning.
-    ///
-    /// See `kXMPFiles_OpenLimitedScanning` constant in C++ SDK.
-    pub fn limited_scanning(mut self) -> Self {
-        self.options |= 0x00000080;
-        self
+    // Forgets everything about the file opened before, if any, so that
+    // nothing carries over from a file that wasn't closed or from an open
+    // that failed. Called at the start of every open.
+    fn reset_open_state(&mut self) {
+        if self.stream.take().is_some() {
+            // The C++ toolkit refers to the stream until the file is
+            // closed.
+            let mut err = ffi::CXmpError::default();
+            unsafe { ffi::CXmpFileClose(self.f, &mut err) };
+        }
+
+        self.path = None;
+        self.in_memory = false;
+        self.native = None;
+        self.lock = None;
+        self.pending_update = false;
+        self.embedded_update = false;
+        self.svg_metadata_missing = false;
+        self.discard_safe_save();
+        self.temp_folder = None;
+        self.file_times = None;
+        self.legacy_segments = None;
+        self.in_place = None;
+        self.repairs.clear();
+        self.sidecar_fallback = false;
+        self.sidecar_xmp = None;
+        self.start_timecode = None;
     }
 
-    /// Attempt to repair a file opened for update.
+    /// Opens a file whose entire content is held in memory.
     ///
-    /// Default is to not open (throw an exception).
+    /// This behaves like [`XmpFile::open_file`], but the format handlers
+    /// work on a copy of `bytes` rather than on the file system, so no
+    /// temporary files are written even when the file is updated. Use
+    /// [`XmpFile::into_bytes`] to retrieve the (possibly updated) content.
     ///
-    /// See `kXMPFiles_OpenRepairFile` constant in C++ SDK.
-    pub fn repair_file(mut self) -> Self {
+    /// Since there is no file name, the format can't be inferred from a
+    /// file extension. Pass the format if it is known; otherwise pass
+    /// [`FileFormat::Unknown`] and the format handlers will inspect the
+    /// content. Formats that are stored as folders of files (such as P2 or
+    /// XDCAM) can't be opened this way.
+    ///
+    /// [`OpenFileOptions::max_file_size`] is checked against the length of
+    /// `bytes`. [`OpenFileOptions::check_file_signature`] has no effect.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use xmp_toolkit::{xmp_ns, FileFormat, OpenFileOptions, XmpFile};
+    /// # let jpeg: Vec<u8> = Vec::new();
+    /// let mut f = XmpFile::from_bytes(
+    ///     &jpeg,
+    ///     FileFormat::Jpeg,
+    ///     OpenFileOptions::default().for_update(),
+    /// )
+    /// .unwrap();
+    ///
+    /// let mut xmp = f.xmp().unwrap();
+    /// xmp.set_property(xmp_ns::XMP, "Label", &"Approved".into())
+    ///     .unwrap();
+    /// f.put_xmp(&xmp).unwrap();
+    ///
+    /// let updated_jpeg = f.into_bytes().unwrap();
+    /// ```
+    pub fn from_bytes(bytes: &[u8], format: FileFormat, flags: OpenFileOptions) -> XmpResult<Self> {
+        flags.check_size(bytes.len() as u64)?;
+
+        let mut f = Self::new()?;
+        f.in_memory = true;
+
+        if let Some(handler) = xmp_file_handler::handler_for(bytes, format, &flags) {
+            f.native = Some(NativeFile::from_bytes(handler, bytes.to_vec(), &flags)?);
+
+            return Ok(f);
+        }
+
+        if flags.keeps_legacy_metadata() && flags.opens_for_update() {
+            f.legacy_segments = Some(legacy_segments_to_keep(bytes)?);
+        }
+
+        let mut err = ffi::CXmpError::default();
+
+        unsafe {
+            ffi::CXmpFileOpenBytes(
+                f.f,
+                &mut err,
+                bytes.as_ptr(),
+                bytes.len(),
+                format as u32,
+                flags.c_options(bytes),
+            );
+        }
+
+        if let Err(err) = XmpError::raise_from_c(&err) {
+            if flags.repairs_xmp()
+                && err.error_type.category() == XmpErrorCategory::CorruptData
+                && f.open_repaired(bytes.to_vec(), None, &flags)
+            {
+                return Ok(f);
+            }
+
+            return Err(err);
+        }
+
+        if flags.repairs_xmp() && f.xmp().is_none() && f.open_repaired(bytes.to_vec(), None, &flags)
+        {
+            return Ok(f);
+        }
+
+        if flags.updates_xmp_in_place() && flags.opens_for_update() {
+            f.in_place = Some(InPlaceUpdate {
+                packet: f.packet_info().and_then(|info| info.range()),
+                original: Some(bytes.to_vec()),
+            });
+        }
+
+        if flags.reads_timecode_track(bytes) {
+            f.start_timecode = read_start_timecode(bytes);
+        }
+
+        if flags.keeps_svg_without_metadata() {
+            let format = match format {
+                FileFormat::Unknown => Self::check_buffer_format(bytes).ok().flatten(),
+                format => Some(format),
+            };
+
+            f.svg_metadata_missing =
+                format == Some(FileFormat::Svg) && !has_svg_metadata_element(bytes);
+        }
+
+        Ok(f)
+    }
+
+    /// Closes a file opened by [`XmpFile::from_bytes`] and returns its
+    /// content, including any update made via [`XmpFile::put_xmp`].
+    ///
+    /// ## Errors
+    ///
+    /// In addition to errors reported by [`XmpFile::close`], returns an
+    /// error of type [`XmpErrorType::BadParam`] if the file wasn't opened
+    /// by [`XmpFile::from_bytes`].
+    pub fn into_bytes(mut self) -> XmpResult<Vec<u8>> {
+        if !self.in_memory {
+            return Err(XmpError {
+                error_type: XmpErrorType::BadParam,
+                debug_message: "XmpFile was not opened from bytes".to_owned(),
+            });
+        }
+
+        let updated = self.pending_update;
+        let legacy_segments = self.legacy_segments.take();
+        let in_place = self.in_place.take();
+
+        self.close()?;
+
+        if let Some(native) = self.native.take() {
+            return Ok(native.into_bytes());
+        }
+
+        let mut len: usize = 0;
+        let result = unsafe {
+            let bytes = ffi::CXmpFileGetBytes(self.f, &mut len);
+            if bytes.is_null() {
+                return Err(crate::xmp_meta::no_cpp_toolkit());
+            }
+
+            let result = std::slice::from_raw_parts(bytes, len).to_vec();
+            ffi::CXmpStringDrop(bytes.cast());
+            result
+        };
+
+        let result = match legacy_segments {
+            Some(legacy_segments) if updated => {
+                xmp_jpeg::replace_legacy_segments(&result, &legacy_segments)?
+            }
+            _ => result,
+        };
+
+        if let Some(InPlaceUpdate {
+            packet,
+            original: Some(original),
+        }) = in_place
+        {
+            // Reading from slices can't fail.
+            if updated
+                && changed_outside_packet(&original[..], &result[..], packet.as_ref())
+                    .unwrap_or(true)
+            {
+                return Err(not_in_place());
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Determines which smart handler the C++ XMP Toolkit would use for the
+    /// file at `path`, without opening it for metadata access.
+    ///
+    /// Returns `None` if no smart handler recognizes the file, in which case
+    /// [`XmpFile::open_file`] can only find XMP by packet scanning. Returns
+    /// `Some(FileFormat::Unknown)` if a handler recognizes the file but its
+    /// format isn't one that [`FileFormat`] names.
+    ///
+    /// Only the start of the file is examined, so a recognized file may
+    /// still fail to open if it is damaged.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use xmp_toolkit::{FileFormat, XmpFile};
+    /// # fn example() -> xmp_toolkit::XmpResult<()> {
+    /// if XmpFile::check_file_format("image.jpg")? == Some(FileFormat::Jpeg) {
+    ///     // ...
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn check_file_format<P: AsRef<Path>>(path: P) -> XmpResult<Option<FileFormat>> {
+        let Some(c_path) = path_to_cstr(path.as_ref()) else {
+            return Err(XmpError {
+                error_type: XmpErrorType::BadParam,
+                debug_message: "Could not convert path to C string".to_owned(),
+            });
+        };
+
+        if let Some(handler) =
+            xmp_file_handler::handler_for_path(path.as_ref(), &OpenFileOptions::default())
+        {
+            return Ok(Some(handler.format()));
+        }
+
+        let mut err = ffi::CXmpError::default();
+        let format = unsafe { ffi::CXmpFileCheckFormat(&mut err, c_path.as_ptr()) };
+        XmpError::raise_from_c(&err)?;
+
+        Ok(FileFormat::from_c(format))
+    }
+
+    /// Determines which smart handler the C++ XMP Toolkit would use for a
+    /// file whose content is `bytes`.
+    ///
+    /// This is the in-memory equivalent of [`XmpFile::check_file_format`].
+    /// Since the C++ XMP Toolkit can only check the format of a file on
+    /// disk, `bytes` is opened as with [`XmpFile::from_bytes`] (requiring a
+    /// smart handler and skipping legacy metadata) and then closed; its XMP
+    /// isn't parsed.
+    pub fn check_buffer_format(bytes: &[u8]) -> XmpResult<Option<FileFormat>> {
+        if let Some(handler) =
+            xmp_file_handler::handler_for(bytes, FileFormat::Unknown, &OpenFileOptions::default())
+        {
+            return Ok(Some(handler.format()));
+        }
+
+        let mut err = ffi::CXmpError::default();
+        let format =
+            unsafe { ffi::CXmpFileCheckBufferFormat(&mut err, bytes.as_ptr(), bytes.len()) };
+        XmpError::raise_from_c(&err)?;
+
+        Ok(FileFormat::from_c(format))
+    }
+
+    /// Describes what the handler for `format` can do, or returns `None` if
+    /// no smart handler supports the format.
+    ///
+    /// Use this to find out, before opening any file, whether XMP can be
+    /// written to files of a format: for example, to disable a "write
+    /// metadata" command. For formats that this crate handles itself (see
+    /// [`XmpFile`]), this describes that handler as used with the default
+    /// [`OpenFileOptions`]. Whether a given file can be updated also
+    /// depends on the file; see [`XmpFile::put_xmp_blocker`].
+    ///
+    /// See `TXMPFiles::GetFormatInfo` in the C++ SDK.
+    pub fn format_info(format: FileFormat) -> XmpResult<Option<FormatCapabilities>> {
+        if let Some(capabilities) = xmp_file_handler::format_capabilities(format) {
+            return Ok(Some(capabilities));
+        }
+
+        let mut flags: u32 = 0;
+        let mut err = ffi::CXmpError::default();
+        let found = unsafe { ffi::CXmpFileGetFormatInfo(&mut err, format as u32, &mut flags) };
+        XmpError::raise_from_c(&err)?;
+
+        Ok((found != 0).then(|| FormatCapabilities::from_c(flags)))
+    }
+
+    /// Returns the time at which the file at `path` was last modified.
+    ///
+    /// Compare this to the `xmp:MetadataDate` property to find out whether
+    /// a file was changed by a tool that didn't update its XMP.
+    ///
+    /// If `include_associated_resources` is `true`, the C++ XMP Toolkit's
+    /// smart handler for the file determines the date: it is the latest
+    /// modification time of all the files that contribute to the metadata,
+    /// such as a sidecar `.xmp` file or the other files of a folder-based
+    /// video clip. In this case, returns `None` if no smart handler
+    /// recognizes the file.
+    ///
+    /// Otherwise, only the file at `path` is considered and the date is
+    /// taken from the file system, in UTC.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::ReadError`] if
+    /// `include_associated_resources` is `false` and the file system
+    /// doesn't report a modification time for the file.
+    pub fn file_mod_date<P: AsRef<Path>>(
+        path: P,
+        include_associated_resources: bool,
+    ) -> XmpResult<Option<XmpDateTime>> {
+        let path = path.as_ref();
+
+        if !include_associated_resources {
+            let modified = fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .map_err(|err| XmpError {
+                    error_type: XmpErrorType::ReadError,
+                    debug_message: err.to_string(),
+                })?;
+
+            return Ok(XmpDateTime::from_system_time(modified));
+        }
+
+        let Some(c_path) = path_to_cstr(path) else {
+            return Err(XmpError {
+                error_type: XmpErrorType::BadParam,
+                debug_message: "Could not convert path to C string".to_owned(),
+            });
+        };
+
+        let mut err = ffi::CXmpError::default();
+        let mut dt = ffi::CXmpDateTime::default();
+        let found = unsafe { ffi::CXmpFileGetModDate(&mut err, c_path.as_ptr(), &mut dt) };
+        XmpError::raise_from_c(&err)?;
+
+        Ok((found != 0).then(|| XmpDateTime::from_ffi(&dt)))
+    }
+
+    /// Reports whether the metadata of the file at `path` can be updated
+    /// in place.
+    ///
+    /// Use this to decide up front between opening the file for update and
+    /// writing a sidecar file instead. The answer depends on the smart
+    /// handler for the file's format and on whether the file (or, for
+    /// folder-based formats, the files that make it up) can be written.
+    ///
+    /// Returns `false` if no smart handler recognizes the file.
+    pub fn is_metadata_writable<P: AsRef<Path>>(path: P) -> XmpResult<bool> {
+        let Some(c_path) = path_to_cstr(path.as_ref()) else {
+            return Err(XmpError {
+                error_type: XmpErrorType::BadParam,
+                debug_message: "Could not convert path to C string".to_owned(),
+            });
+        };
+
+        if xmp_file_handler::handler_for_path(path.as_ref(), &OpenFileOptions::default()).is_some()
+        {
+            return Ok(fs::metadata(path.as_ref()).is_ok_and(|m| !m.permissions().readonly()));
+        }
+
+        let mut err = ffi::CXmpError::default();
+        let writable = unsafe { ffi::CXmpFileIsMetadataWritable(&mut err, c_path.as_ptr()) };
+        XmpError::raise_from_c(&err)?;
+
+        Ok(writable != 0)
+    }
+
+    /// Returns the paths of all the files that make up the asset at `path`.
+    ///
+    /// For most formats this is just `path`. Other formats spread an asset
+    /// across several files, all of which should be kept together when the
+    /// asset is copied, moved, or backed up: for example, a camera raw file
+    /// and its `.xmp` sidecar file, or the clip files of a folder-based
+    /// video format such as P2 or XDCAM (see
+    /// [`FileFormat::is_folder_based`]). The list includes `path` itself.
+    ///
+    /// Returns an empty list if no smart handler recognizes the file.
+    pub fn associated_resources<P: AsRef<Path>>(path: P) -> XmpResult<Vec<PathBuf>> {
+        let Some(c_path) = path_to_cstr(path.as_ref()) else {
+            return Err(XmpError {
+                error_type: XmpErrorType::BadParam,
+                debug_message: "Could not convert path to C string".to_owned(),
+            });
+        };
+
+        if xmp_file_handler::handler_for_path(path.as_ref(), &OpenFileOptions::default()).is_some()
+        {
+            return Ok(vec![path.as_ref().to_path_buf()]);
+        }
+
+        let mut resources: Vec<String> = Vec::new();
+        let mut err = ffi::CXmpError::default();
+
+        unsafe {
+            ffi::CXmpFileGetAssociatedResources(
+                &mut err,
+                c_path.as_ptr(),
+                &mut resources as *mut Vec<String> as *mut c_void,
+                ffi::xmp_collect_string,
+            );
+        }
+
+        XmpError::raise_from_c(&err)?;
+
+        Ok(resources.into_iter().map(PathBuf::from).collect())
+    }
+
+    /// Loads the XMPFiles plugin handlers in `folder`.
+    ///
+    /// Plugins built with the XMP Toolkit's plugin SDK add file handlers to
+    /// those compiled into the C++ XMP Toolkit, or replace them: for
+    /// example, a PDF plugin makes PDF files writable, which the built-in
+    /// handler doesn't support. Once loaded, plugins are used by every
+    /// [`XmpFile`] in the process.
+    ///
+    /// The C++ XMP Toolkit only loads plugins when it is initialized, which
+    /// happens the first time any part of it is used (including
+    /// [`XmpMeta`]). This function must therefore be called before any
+    /// other use of this crate, typically at the start of `main`. Plugins
+    /// that fail to load are skipped.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::NoFile`] if `folder` isn't
+    /// a directory, or of type [`XmpErrorType::ToolkitAlreadyInitialized`]
+    /// if the C++ XMP Toolkit has already been initialized.
+    pub fn load_plugins<P: AsRef<Path>>(folder: P) -> XmpResult<()> {
+        let folder = folder.as_ref();
+
+        if !folder.is_dir() {
+            return Err(XmpError {
+                error_type: XmpErrorType::NoFile,
+                debug_message: format!("Plugin folder {} not found", folder.display()),
+            });
+        }
+
+        let Some(c_folder) = path_to_cstr(folder) else {
+            return Err(XmpError {
+                error_type: XmpErrorType::BadParam,
+                debug_message: "Could not convert path to C string".to_owned(),
+            });
+        };
+
+        let mut err = ffi::CXmpError::default();
+        let loaded = unsafe { ffi::CXmpFileLoadPlugins(&mut err, c_folder.as_ptr()) };
+        XmpError::raise_from_c(&err)?;
+
+        if loaded == 0 {
+            return Err(XmpError {
+                error_type: XmpErrorType::ToolkitAlreadyInitialized,
+                debug_message: "Plugins must be loaded before the XMP Toolkit is first used"
+                    .to_owned(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Registers a file format handler implemented in Rust.
+    ///
+    /// From then on, [`XmpFile::open_file`], [`XmpFile::from_bytes`], and
+    /// the format checks use `handler` for every file it recognizes, in
+    /// preference to the C++ XMP Toolkit and this crate's own handlers.
+    /// Handlers are tried in the order they were registered. They can't be
+    /// unregistered.
+    ///
+    /// ## Example
+    ///
+    /// A handler for a format whose files start with a 6-byte signature
+    /// followed by the XMP packet:
+    ///
+    /// ```
+    /// use std::ops::Range;
+    ///
+    /// use xmp_toolkit::{XmpFile, XmpFileHandler, XmpResult};
+    ///
+    /// struct MyFormatHandler;
+    ///
+    /// impl XmpFileHandler for MyFormatHandler {
+    ///     fn check_format(&self, header: &[u8]) -> bool {
+    ///         header.starts_with(b"MYFMT\0")
+    ///     }
+    ///
+    ///     fn read_xmp(&self, file: &[u8]) -> XmpResult<Option<Range<usize>>> {
+    ///         Ok((file.len() > 6).then_some(6..file.len()))
+    ///     }
+    ///
+    ///     fn write_xmp(&self, file: &[u8], packet: &[u8]) -> XmpResult<Vec<u8>> {
+    ///         Ok([&file[..6], packet].concat())
+    ///     }
+    /// }
+    ///
+    /// XmpFile::register_handler(MyFormatHandler);
+    /// ```
+    pub fn register_handler<H: XmpFileHandler + 'static>(handler: H) {
+        xmp_file_handler::register_handler(handler);
+    }
+
+    /// Registers `extension` as an extension of files in `format`.
+    ///
+    /// The C++ XMP Toolkit chooses a file's handler by its extension, and
+    /// may not recognize a file whose extension it doesn't know, such as a
+    /// JPEG file renamed to `.jfif` or a TIFF file with a proprietary
+    /// extension. From then on, [`XmpFile::open_file`] asks for the handler
+    /// for `format` when opening a file with `extension`. The file's
+    /// content must still be in that format.
+    ///
+    /// `extension` is matched without regard to case, with or without a
+    /// leading dot. Registering an extension again replaces the previous
+    /// registration; registering it for [`FileFormat::Unknown`] removes it.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use xmp_toolkit::{FileFormat, XmpFile};
+    ///
+    /// XmpFile::register_extension("jfif", FileFormat::Jpeg);
+    /// ```
+    pub fn register_extension(extension: &str, format: FileFormat) {
+        xmp_file_format::register_extension(extension, format);
+    }
+
+    /// Returns `true` if [`XmpFile::put_xmp`] was called since the file was
+    /// opened and the update hasn't been written by [`XmpFile::close`] yet.
+    ///
+    /// Such updates are lost if the struct is dropped; see
+    /// [Closing files](XmpFile#closing-files).
+    pub fn has_pending_update(&self) -> bool {
+        self.pending_update
+    }
+
+    /// Returns the sidecar file that the pending update will be written to,
+    /// or `None` if it will be embedded in the file or there is no pending
+    /// update.
+    ///
+    /// This is only ever `Some` for a file opened with
+    /// [`OpenFileOptions::sidecar_fallback`] whose XMP couldn't be
+    /// embedded.
+    pub fn sidecar_for_update(&self) -> Option<PathBuf> {
+        self.sidecar_xmp.as_ref()?;
+        let path = self.path.as_deref()?;
+        Some(Sidecar::find(path).path().to_path_buf())
+    }
+
+    /// Reports the problems that were fixed in the file's XMP packet.
+    ///
+    /// This is only ever non-empty for a file opened with
+    /// [`OpenFileOptions::repair_xmp`] whose packet was damaged.
+    pub fn repairs(&self) -> &[PacketRepair] {
+        &self.repairs
+    }
+
+    /// Retrieves the XMP metadata from an open file.
+    ///
+    /// If no XMP is present, will return `None`.
+    pub fn xmp(&mut self) -> Option<XmpMeta> {
+        if let Some(meta) = &self.sidecar_xmp {
+            return Some(meta.clone());
+        }
+
+        if let Some(native) = &self.native {
+            return native.xmp();
+        }
+
+        let meta = unsafe {
+            let m = ffi::CXmpFileGetXmp(self.f);
+            if m.is_null() {
+                None
+            } else {
+                Some(XmpMeta::from_ptr(m))
+            }
+        };
+
+        match &self.start_timecode {
+            Some(timecode) => add_start_timecode(meta, timecode),
+            None => meta,
+        }
+    }
+
+    /// Retrieves the XMP packet of an open file exactly as it is stored in
+    /// the file, without parsing it.
+    ///
+    /// Use this when the original bytes matter, for example to verify a
+    /// signature over the packet or to archive it; [`XmpFile::xmp`]
+    /// followed by serialization doesn't reproduce them. The packet
+    /// includes its wrapper and padding, if any, and keeps its character
+    /// encoding (see [`PacketInfo::encoding`]). Legacy metadata that the
+    /// file format handler reconciles with the XMP is not reflected in it.
+    /// For file formats that split the XMP, such as JPEG files with
+    /// extended XMP, this is only the main packet.
+    ///
+    /// After [`XmpFile::put_xmp`], this returns the packet that will be
+    /// written. If no XMP is present, returns `None`.
+    pub fn raw_xmp_packet(&mut self) -> Option<Vec<u8>> {
+        if let Some(native) = &self.native {
+            return native.raw_packet().map(<[u8]>::to_vec);
+        }
+
+        let mut len: usize = 0;
+
+        unsafe {
+            let bytes = ffi::CXmpFileGetXmpPacket(self.f, &mut len);
+            if bytes.is_null() {
+                return None;
+            }
+
+            let packet = std::slice::from_raw_parts(bytes, len).to_vec();
+            ffi::CXmpStringDrop(bytes.cast());
+            Some(packet)
+        }
+    }
+
+    /// Returns the image resource blocks of an open Photoshop (PSD or PSB)
+    /// file.
+    ///
+    /// These hold the file's non-pixel data, including its XMP packet
+    /// (ID 1060) and IPTC record (ID 1028), as they are stored in the file.
+    /// Updates made with [`XmpFile::put_xmp`] are not reflected until the
+    /// file is closed and opened again.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::BadParam`] if the file
+    /// wasn't opened with [`XmpFile::open_file`], of type
+    /// [`XmpErrorType::ReadError`] if it can't be read, and of type
+    /// [`XmpErrorType::BadFileFormat`] if it isn't a Photoshop file or is
+    /// damaged.
+    pub fn psd_image_resources(&self) -> XmpResult<Vec<PsdImageResource>> {
+        xmp_psd::image_resources(&mut self.open_for_reading()?)
+    }
+
+    /// Returns the layers of an open Photoshop (PSD or PSB) file, from
+    /// bottom to top, with the XMP attached to each layer.
+    ///
+    /// Photoshop stores a layer's XMP in its metadata setting (`shmd`)
+    /// block, separately from the file's XMP returned by [`XmpFile::xmp`].
+    /// Layers without XMP are included, so that the position of each
+    /// layer can be told. A file with a single, flattened image has no
+    /// layers.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the errors described for [`XmpFile::psd_image_resources`],
+    /// and a parse error if the XMP of a layer isn't valid.
+    pub fn psd_layers(&self) -> XmpResult<Vec<PsdLayer>> {
+        xmp_psd::layers(&mut self.open_for_reading()?)
+    }
+
+    /// Returns every XMP packet embedded in an open file, in the order in
+    /// which they appear, including the one that [`XmpFile::xmp`] reads.
+    ///
+    /// Compound documents hold XMP besides the document's own packet: an
+    /// InDesign (INDD) file has a packet for each placed image and
+    /// story that carries metadata, and an Illustrator (AI) file has one
+    /// for each sub-document and placed file. Each packet is reported with
+    /// the [`XmpContainer`](crate::XmpContainer) that holds it, and
+    /// [`EmbeddedXmp::main`] marks the document's own packet. Compressed
+    /// PDF metadata streams are decompressed and scanned as well.
+    ///
+    /// Other file formats are scanned for packets as described in
+    /// [`xmp_packet::scan`], which also finds stale packets left behind
+    /// by earlier edits. Only packets encoded as UTF-8 are found. The file
+    /// is read again from disk, so updates made with
+    /// [`XmpFile::put_xmp`] aren't reflected until the file is closed and
+    /// opened again.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::BadParam`] if the file
+    /// wasn't opened with [`XmpFile::open_file`], and of type
+    /// [`XmpErrorType::ReadError`] if it can't be read.
+    pub fn embedded_xmp(&mut self) -> XmpResult<Vec<EmbeddedXmp>> {
+        let mut file = Vec::new();
+        self.open_for_reading()?
+            .read_to_end(&mut file)
+            .map_err(|err| XmpError {
+                error_type: XmpErrorType::ReadError,
+                debug_message: err.to_string(),
+            })?;
+
+        let mut packets = xmp_compound::embedded_xmp(&file);
+
+        // Not every handler knows where its packet is stored; the packet's
+        // text is compared for those.
+        let main_offset = self.packet_info().and_then(|info| info.offset);
+        let main_packet = match main_offset {
+            Some(_) => None,
+            None => self.raw_xmp_packet(),
+        };
+
+        if let Some(main) = packets.iter_mut().find(|packet| match main_offset {
+            Some(offset) => packet.range.start == offset,
+            None => main_packet.as_deref() == Some(packet.text.as_bytes()),
+        }) {
+            main.main = true;
+        }
+
+        Ok(packets)
+    }
+
+    // Opens the file for reading by this crate's own parsers.
+    fn open_for_reading(&self) -> XmpResult<BufReader<File>> {
+        let Some(path) = &self.path else {
+            return Err(XmpError {
+                error_type: XmpErrorType::BadParam,
+                debug_message: "XmpFile was not opened from a path".to_owned(),
+            });
+        };
+
+        File::open(path)
+            .map(BufReader::new)
+            .map_err(|err| XmpError {
+                error_type: XmpErrorType::ReadError,
+                debug_message: err.to_string(),
+            })
+    }
+
+    /// Describes where the XMP packet is stored in an open file.
+    ///
+    /// Use this to patch the packet in place or to verify a signature over
+    /// its bytes. Typically this is called after [`XmpFile::xmp`]; if the
+    /// file has no XMP, returns `None`.
+    ///
+    /// Some file format handlers don't read the packet from a single
+    /// location (for example, when it is reconciled from legacy metadata);
+    /// in that case, [`PacketInfo::offset`] is `None`.
+    pub fn packet_info(&mut self) -> Option<PacketInfo> {
+        if let Some(native) = &self.native {
+            let packet = native.packet()?;
+            let range = native.packet_range();
+
+            return Some(PacketInfo {
+                offset: range.as_ref().map(|range| range.start as u64),
+                length: range.and_then(|range| u32::try_from(range.len()).ok()),
+                ..PacketInfo::from_packet(packet)
+            });
+        }
+
+        let mut info = ffi::CXmpPacketInfo::default();
+
+        if unsafe { ffi::CXmpFileGetPacketInfo(self.f, &mut info) } == 0 {
+            return None;
+        }
+
+        Some(PacketInfo {
+            offset: u64::try_from(info.offset).ok(),
+            length: u32::try_from(info.length).ok(),
+            padding: u32::try_from(info.pad_size).unwrap_or_default(),
+            encoding: PacketEncoding::from_c(info.char_form),
+            writable: info.writeable != 0,
+            has_wrapper: info.has_wrapper != 0,
+        })
+    }
+
+    /// Retrieves the XMP metadata of an open file together with the packet
+    /// it was parsed from.
+    ///
+    /// This is the same as calling [`XmpFile::xmp`],
+    /// [`XmpFile::raw_xmp_packet`], and [`XmpFile::packet_info`] in turn,
+    /// for pipelines that need both the parsed XMP and the exact bytes, for
+    /// example to verify a signature over the packet and then inspect its
+    /// properties. The file is read only once, when it is opened.
+    ///
+    /// Returns `None` if the file has no XMP packet. This includes files
+    /// whose XMP is only reconciled from legacy metadata; use
+    /// [`XmpFile::xmp`] for those.
+    pub fn xmp_with_packet(&mut self) -> Option<XmpWithPacket> {
+        let packet = self.raw_xmp_packet()?;
+        let info = self.packet_info()?;
+        let xmp = self.xmp()?;
+
+        Some(XmpWithPacket { xmp, packet, info })
+    }
+
+    /// Reports whether this file can be updated with a specific XMP packet.
+    ///
+    /// Use this function to determine if the file can probably be updated with
+    /// a given set of XMP metadata. This depends on the size of the packet,
+    /// the options with which the file was opened, and the capabilities of
+    /// the handler for the file format. The function obtains the length of
+    /// the serialized packet for the provided XMP, but does not keep it or
+    /// modify it, and does not cause the file to be written when closed.
+    ///
+    /// Use [`XmpFile::put_xmp_blocker`] to find out why the file can't be
+    /// updated.
+    pub fn can_put_xmp(&self, meta: &XmpMeta) -> bool {
+        self.put_xmp_blocker(meta).is_none()
+    }
+
+    /// Reports why this file can't be updated with a specific XMP packet.
+    ///
+    /// This makes the same checks as [`XmpFile::can_put_xmp`], but returns
+    /// the reason the update isn't possible, so that the caller can choose
+    /// a fallback such as writing a sidecar file or trying again later.
+    /// Returns `None` if the file can probably be updated.
+    pub fn put_xmp_blocker(&self, meta: &XmpMeta) -> Option<PutXmpBlocker> {
+        let for_update = match &self.native {
+            Some(native) => native.opens_for_update(),
+            None => {
+                let mut open_flags = 0;
+                let open = unsafe { ffi::CXmpFileGetOpenFlags(self.f, &mut open_flags) != 0 };
+                open && open_flags & OpenFileOptions::FOR_UPDATE != 0
+            }
+        };
+
+        if !for_update {
+            return Some(PutXmpBlocker::NotOpenedForUpdate);
+        }
+
+        if self.svg_metadata_missing {
+            return Some(PutXmpBlocker::ReadOnlyFormat);
+        }
+
+        if let Some(path) = &self.path {
+            if fs::metadata(path).is_ok_and(|metadata| metadata.permissions().readonly()) {
+                return Some(PutXmpBlocker::ReadOnlyFile);
+            }
+
+            if self.is_locked_elsewhere(path) {
+                return Some(PutXmpBlocker::FileLocked);
+            }
+        }
+
+        let Some(m) = meta.m else {
+            return Some(PutXmpBlocker::Other);
+        };
+
+        if let Some(native) = &self.native {
+            return native.put_xmp_blocker(meta);
+        }
+
+        if let Some(in_place) = &self.in_place {
+            let Some(packet) = &in_place.packet else {
+                return Some(PutXmpBlocker::ReadOnlyFormat);
+            };
+
+            let fits = u32::try_from(packet.end - packet.start).is_ok_and(|len| {
+                meta.to_string_with_options(
+                    ToStringOptions::default()
+                        .set_padding(len)
+                        .exact_packet_length(),
+                )
+                .is_ok_and(|packet| packet.len() == len as usize)
+            });
+
+            if !fits {
+                return Some(PutXmpBlocker::PacketTooLarge);
+            }
+        }
+
+        if unsafe { ffi::CXmpFileCanPutXmp(self.f, m) != 0 } {
+            return None;
+        }
+
+        // The handler can only update an existing packet: either there is
+        // none, or the XMP doesn't fit in it.
+        let mut info = ffi::CXmpPacketInfo::default();
+        let writable_packet =
+            unsafe { ffi::CXmpFileGetPacketInfo(self.f, &mut info) != 0 } && info.writeable != 0;
+
+        Some(if writable_packet {
+            PutXmpBlocker::PacketTooLarge
+        } else {
+            PutXmpBlocker::ReadOnlyFormat
+        })
+    }
+
+    /// Updates the XMP metadata in this object without writing out the file.
+    ///
+    /// This function supplies new XMP for the file. However, the disk file is
+    /// not written until the struct is closed with [`XmpFile::close`].
+    /// Dropping the struct without closing it discards the update and logs
+    /// a warning.
+    /// The options provided when the file was opened determine if
+    /// reconciliation is done with other forms of metadata.
+    ///
+    /// If the file was opened with [`OpenFileOptions::sidecar_fallback`]
+    /// and the XMP can't be embedded in it, the XMP is written to the
+    /// file's sidecar instead; see [`XmpFile::sidecar_for_update`].
+    ///
+    /// ## Errors
+    ///
+    /// In addition to errors reported by the C++ XMP Toolkit, returns an
+    /// error of type [`XmpErrorType::SvgMetadataElementMissing`] for an SVG
+    /// file without a `<metadata>` element that was opened with
+    /// [`OpenFileOptions::create_svg_metadata`]`(false)`.
+    pub fn put_xmp(&mut self, meta: &XmpMeta) -> XmpResult<()> {
+        self.put_xmp_with_padding(meta, PacketPadding::Default)
+    }
+
+    /// Updates the XMP metadata in this object, choosing how much padding
+    /// the written packet has.
+    ///
+    /// This is otherwise the same as [`XmpFile::put_xmp`]. Use it when the
+    /// size of the written file must be predictable, for example because
+    /// files are stored by content address. See [`PacketPadding`] for the
+    /// available policies.
+    ///
+    /// Padding can only be chosen for files that are handled by this crate
+    /// rather than by the C++ XMP Toolkit (see [`XmpFile`]), including
+    /// formats handled by an [`XmpFileHandler`]. It is ignored when the
+    /// file was opened with [`OpenFileOptions::update_xmp_in_place`],
+    /// since the packet then keeps its existing length.
+    ///
+    /// ## Errors
+    ///
+    /// In addition to the errors reported by [`XmpFile::put_xmp`], returns
+    /// an error of type [`XmpErrorType::Unimplemented`] if `padding` is not
+    /// [`PacketPadding::Default`] and the file is handled by the C++ XMP
+    /// Toolkit.
+    pub fn put_xmp_with_padding(
+        &mut self,
+        meta: &XmpMeta,
+        padding: PacketPadding,
+    ) -> XmpResult<()> {
+        if self.falls_back_to_sidecar(meta) {
+            self.sidecar_xmp = Some(meta.clone());
+            self.pending_update = true;
+
+            #[cfg(feature = "audit")]
+            if let Some(audit) = &mut self.audit {
+                audit.after = xmp_audit::xmp_hash(meta).ok();
+                audit.pending = true;
+            }
+
+            return Ok(());
+        }
+
+        if self.svg_metadata_missing {
+            return Err(XmpError {
+                error_type: XmpErrorType::SvgMetadataElementMissing,
+                debug_message: "SVG file has no metadata element".to_owned(),
+            });
+        }
+
+        if self
+            .in_place
+            .as_ref()
+            .is_some_and(|in_place| in_place.packet.is_none())
+        {
+            return Err(XmpError {
+                error_type: XmpErrorType::XmpUpdateNotInPlace,
+                debug_message: "File has no XMP packet to update in place".to_owned(),
+            });
+        }
+
+        if let Some(native) = &mut self.native {
+            native.put_xmp(meta, padding)?;
+        } else if padding != PacketPadding::Default {
+            return Err(XmpError {
+                error_type: XmpErrorType::Unimplemented,
+                debug_message: "Packet padding can't be chosen for this file format".to_owned(),
+            });
+        } else if let Some(m) = meta.m {
+            let mut err = ffi::CXmpError::default();
+            unsafe { ffi::CXmpFilePutXmp(self.f, &mut err, m) };
+            XmpError::raise_from_c(&err)?;
+        } else {
+            return Err(crate::xmp_meta::no_cpp_toolkit());
+        }
+
+        self.pending_update = true;
+        self.embedded_update = true;
+
+        #[cfg(feature = "audit")]
+        if let Some(audit) = &mut self.audit {
+            audit.after = xmp_audit::xmp_hash(meta).ok();
+            audit.pending = true;
+        }
+
+        Ok(())
+    }
+
+    // Returns true if another process (or another `XmpFile`) holds a lock
+    // on the file at `path` that would keep it from being written.
+    fn is_locked_elsewhere(&self, path: &Path) -> bool {
+        if self.lock.is_none() && FileLock::is_locked(path) {
+            return true;
+        }
+
+        // The C++ toolkit keeps a file it updates in place open, and that
+        // handle would be reported as a sharing violation as well.
+        let closed = self.native.is_some() || self.safe_save_path.is_some();
+        closed && sharing_violation_on_open(path, true).is_some()
+    }
+
+    // Returns true if `meta` is to be written to the sidecar rather than
+    // embedded in the file.
+    fn falls_back_to_sidecar(&self, meta: &XmpMeta) -> bool {
+        if !self.sidecar_fallback || self.path.is_none() {
+            return false;
+        }
+
+        self.sidecar_xmp.is_some()
+            || matches!(
+                self.put_xmp_blocker(meta),
+                Some(
+                    PutXmpBlocker::ReadOnlyFormat
+                        | PutXmpBlocker::PacketTooLarge
+                        | PutXmpBlocker::ReadOnlyFile
+                        | PutXmpBlocker::FileLocked
+                )
+            )
+    }
+
+    /// Updates the XMP packet in this object with `packet`, which is
+    /// written to the file byte for byte when the file is closed.
+    ///
+    /// Use this for packets whose exact bytes matter, such as signed
+    /// packets: [`XmpFile::put_xmp`] serializes an [`XmpMeta`], which
+    /// doesn't preserve them. `packet` must be a single UTF-8 XMP packet
+    /// with a `<?xpacket begin=` header at its start and a `<?xpacket end=`
+    /// trailer at its end, and its XMP must parse.
+    ///
+    /// For a file opened with [`OpenFileOptions::update_xmp_in_place`],
+    /// `packet` replaces the existing packet and must have exactly the same
+    /// length, including padding.
+    ///
+    /// Raw packets can only be written to files that are handled by this
+    /// crate rather than by the C++ XMP Toolkit (see [`XmpFile`]),
+    /// including formats handled by an [`XmpFileHandler`], since the C++
+    /// XMP Toolkit always serializes the packet itself.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::BadXmp`] if `packet` is
+    /// not a single wrapped packet or its XMP doesn't parse, of type
+    /// [`XmpErrorType::Unimplemented`] if the file is handled by the C++
+    /// XMP Toolkit, and of type [`XmpErrorType::XmpUpdateNotInPlace`] if
+    /// the packet can't be updated in place as requested.
+    pub fn put_raw_packet(&mut self, packet: &[u8]) -> XmpResult<()> {
+        if !is_wrapped_packet(packet) {
+            return Err(XmpError {
+                error_type: XmpErrorType::BadXmp,
+                debug_message: "Not a single wrapped XMP packet".to_owned(),
+            });
+        }
+
+        let meta = XmpMeta::from_str(&String::from_utf8_lossy(packet))?;
+        self.put_parsed_packet(packet, &meta)
+    }
+
+    /// Updates the XMP metadata in this object with `xmp`, a serialized
+    /// XMP packet or `x:xmpmeta` element.
+    ///
+    /// Use this when the XMP is already at hand in serialized form, to
+    /// avoid parsing it into an [`XmpMeta`] only to pass it to
+    /// [`XmpFile::put_xmp`]. `xmp` is always parsed to validate it.
+    /// For files handled by this crate rather than by the C++ XMP Toolkit
+    /// (see [`XmpFile`]), a complete packet is then written as is, as with
+    /// [`XmpFile::put_raw_packet`]; otherwise, the parsed XMP is written as
+    /// with [`XmpFile::put_xmp`], which serializes it again.
+    ///
+    /// ## Errors
+    ///
+    /// Returns a parse error if `xmp` isn't valid XMP, and otherwise the
+    /// errors reported by [`XmpFile::put_xmp`].
+    pub fn put_xmp_from_str(&mut self, xmp: &str) -> XmpResult<()> {
+        let meta = XmpMeta::from_str(xmp)?;
+
+        let as_is = is_wrapped_packet(xmp.as_bytes())
+            && self
+                .native
+                .as_ref()
+                .is_some_and(|native| native.fits_raw_packet(xmp.len()))
+            && !self.falls_back_to_sidecar(&meta);
+
+        if as_is {
+            self.put_parsed_packet(xmp.as_bytes(), &meta)
+        } else {
+            self.put_xmp(&meta)
+        }
+    }
+
+    // Writes `packet`, whose XMP is `meta`, as is.
+    #[cfg_attr(not(feature = "audit"), allow(unused_variables))]
+    fn put_parsed_packet(&mut self, packet: &[u8], meta: &XmpMeta) -> XmpResult<()> {
+        let Some(native) = &mut self.native else {
+            return Err(XmpError {
+                error_type: XmpErrorType::Unimplemented,
+                debug_message: "Raw packets can't be written to this file format".to_owned(),
+            });
+        };
+
+        native.put_raw_packet(packet)?;
+        self.pending_update = true;
+        self.embedded_update = true;
+
+        #[cfg(feature = "audit")]
+        if let Some(audit) = &mut self.audit {
+            audit.after = xmp_audit::xmp_hash(meta).ok();
+            audit.pending = true;
+        }
+
+        Ok(())
+    }
+
+    /// Explicitly closes an opened file.
+    ///
+    /// Performs any necessary output to the file and closes it. Files that are
+    /// opened for update are written to only when closing.
+    ///
+    /// If the file is opened for read-only access (passing
+    /// [`OpenFileOptions::for_read`]), the disk file is closed
+    /// immediately after reading the data from it; the `XMPFile`
+    /// struct, however, remains in the open state. You must call
+    /// [`XmpFile::close`] when finished using it. Other methods, such as
+    /// [`XmpFile::xmp`], can only be used between the
+    /// [`XmpFile::open_file`] and [`XmpFile::close`] calls. The `XMPFile`
+    /// destructor does not call [`XmpFile::close`]; if the struct is
+    /// dropped without closing, any pending updates are lost and a warning
+    /// is logged.
+    ///
+    /// If the file is opened for update (passing
+    /// [`OpenFileOptions::for_update`]), the disk file remains open until
+    /// [`XmpFile::close`] is called. The disk file is only updated once,
+    /// when [`XmpFile::close`] is called, regardless of how many calls are
+    /// made to [`XmpFile::put_xmp`]. See [`OpenFileOptions::update_safely`]
+    /// for making that update crash-safe.
+    ///
+    /// If an audit log is attached (see `XmpFile::set_audit_log`, available
+    /// with crate feature `audit`) and the XMP was updated, an entry is recorded after the file is written.
+    /// An error writing that entry is reported from this function.
+    ///
+    /// If [`XmpFile::put_xmp`] fell back to the file's sidecar (see
+    /// [`OpenFileOptions::sidecar_fallback`]), the sidecar is written after
+    /// the file is closed.
+    ///
+    /// Use [`XmpFile::try_close_into_report`] to find out what was written.
+    pub fn close(&mut self) -> XmpResult<()> {
+        // Released once the file has been written, whether or not that
+        // succeeded.
+        let _lock = self.lock.take();
+
+        let updated = self.embedded_update;
+        self.pending_update = false;
+        self.embedded_update = false;
+        let sidecar_xmp = self.sidecar_xmp.take();
+
+        if let Some(native) = &mut self.native {
+            native.close()?;
+        } else {
+            let mut err = ffi::CXmpError::default();
+            unsafe { ffi::CXmpFileClose(self.f, &mut err) };
+
+            if let Err(err) = XmpError::raise_from_c(&err)
+                .and_then(|()| self.flush_stream(updated))
+                .and_then(|()| self.restore_legacy_segments(updated))
+                .and_then(|()| self.verify_in_place_update(updated))
+            {
+                self.discard_safe_save();
+                return Err(err);
+            }
+
+            self.finish_safe_save(updated)?;
+        }
+
+        self.restore_file_times(updated)?;
+
+        if let (Some(meta), Some(path)) = (sidecar_xmp, &self.path) {
+            Sidecar::find(path).write(&meta)?;
+        }
+
+        #[cfg(feature = "audit")]
+        self.finish_audit()?;
+
+        Ok(())
+    }
+
+    /// Explicitly closes an opened file.
+    ///
+    /// This is the same as [`XmpFile::close`]. It was added when `close`
+    /// did not report errors and is retained for compatibility.
+    pub fn try_close(&mut self) -> XmpResult<()> {
+        self.close()
+    }
+
+    fn flush_stream(&mut self, updated: bool) -> XmpResult<()> {
+        match &mut self.stream {
+            Some(stream) if updated => stream.flush().map_err(|e| XmpError {
+                error_type: XmpErrorType::WriteError,
+                debug_message: e.to_string(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Closes an opened file and reports what was written.
+    ///
+    /// This behaves like [`XmpFile::close`], but on success returns a
+    /// [`CloseReport`] describing the file that was closed and the XMP, if
+    /// any, that was written to it.
+    pub fn try_close_into_report(&mut self) -> XmpResult<CloseReport> {
+        let written_xmp = if self.pending_update {
+            self.xmp()
+        } else {
+            None
+        };
+
+        let sidecar_path = self.sidecar_for_update();
+
+        self.close()?;
+
+        Ok(CloseReport {
+            path: self.path.take(),
+            written_xmp,
+            sidecar_path,
+        })
+    }
+
+    /// Reports the progress of long-running operations to `callback`.
+    ///
+    /// Updating metadata in large files, especially video files, can take
+    /// a long time when the file has to be rewritten. While
+    /// [`XmpFile::put_xmp`] or [`XmpFile::close`] is running, `callback` is
+    /// called roughly once every `interval` with a [`Progress`] describing
+    /// the work done so far. If `send_start_stop` is `true`, it is also
+    /// called once when the operation starts and once when it ends.
+    ///
+    /// Return `true` from `callback` to continue. Returning `false` (or
+    /// panicking) aborts the operation, which then fails with an error of
+    /// type [`XmpErrorType::ProgressAbort`].
+    ///
+    /// Only some file format handlers report progress; for others the
+    /// callback is never called. The callback stays in effect for files
+    /// opened later by this struct until it is replaced or removed with
+    /// [`XmpFile::clear_progress_callback`]. It replaces any default set by
+    /// [`XmpFile::set_default_progress_callback`].
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use xmp_toolkit::{OpenFileOptions, XmpFile};
+    /// # fn example() -> xmp_toolkit::XmpResult<()> {
+    /// let mut f = XmpFile::new()?;
+    /// f.set_progress_callback(Duration::from_secs(1), false, |progress| {
+    ///     println!("{:.0}% done", progress.fraction_done * 100.0);
+    ///     true
+    /// })?;
+    ///
+    /// f.open_file("movie.mov", OpenFileOptions::default().for_update())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_progress_callback<F>(
+        &mut self,
+        interval: Duration,
+        send_start_stop: bool,
+        callback: F,
+    ) -> XmpResult<()>
+    where
+        F: FnMut(&Progress) -> bool + Send + 'static,
+    {
+        let mut callback: Box<Box<ffi::FileProgressFn>> = Box::new(Box::new(callback));
+
+        let mut err = ffi::CXmpError::default();
+        unsafe {
+            ffi::CXmpFileSetProgressCallback(
+                self.f,
+                &mut err,
+                callback.as_mut() as *mut Box<ffi::FileProgressFn> as *mut c_void,
+                Some(ffi::xmp_file_progress),
+                interval.as_secs_f32(),
+                send_start_stop as c_int,
+            );
+        }
+        XmpError::raise_from_c(&err)?;
+
+        // Only drop the previous callback once the C++ toolkit no longer
+        // refers to it.
+        self.progress = Some(callback);
+        Ok(())
+    }
+
+    /// Removes a callback set by [`XmpFile::set_progress_callback`].
+    ///
+    /// Progress is no longer reported for this struct, even if a default
+    /// callback was set by [`XmpFile::set_default_progress_callback`].
+    pub fn clear_progress_callback(&mut self) -> XmpResult<()> {
+        let mut err = ffi::CXmpError::default();
+        unsafe {
+            ffi::CXmpFileSetProgressCallback(self.f, &mut err, std::ptr::null_mut(), None, 0.0, 0);
+        }
+        XmpError::raise_from_c(&err)?;
+
+        self.progress = None;
+        Ok(())
+    }
+
+    /// Sets a progress callback for every [`XmpFile`] created after this
+    /// call.
+    ///
+    /// This is the process-wide equivalent of
+    /// [`XmpFile::set_progress_callback`]. Because it may be called from
+    /// several threads at once, `callback` must be `Fn` and `Sync`.
+    ///
+    /// Structs created before this call keep using the previous default,
+    /// so the previous callback is never released. This function is meant
+    /// to be called once, when an application starts.
+    pub fn set_default_progress_callback<F>(
+        interval: Duration,
+        send_start_stop: bool,
+        callback: F,
+    ) -> XmpResult<()>
+    where
+        F: Fn(&Progress) -> bool + Send + Sync + 'static,
+    {
+        let callback: Box<ffi::DefaultProgressFn> = Box::new(callback);
+        let context = Box::into_raw(Box::new(callback));
+
+        let mut err = ffi::CXmpError::default();
+        unsafe {
+            ffi::CXmpFileSetDefaultProgressCallback(
+                &mut err,
+                context as *mut c_void,
+                Some(ffi::xmp_default_progress),
+                interval.as_secs_f32(),
+                send_start_stop as c_int,
+            );
+        }
+        XmpError::raise_from_c(&err)
+    }
+
+    /// Allows long-running operations to be cancelled with `token`.
+    ///
+    /// Once [`CancellationToken::cancel`] is called (typically from
+    /// another thread), the C++ XMP Toolkit stops the operation in progress
+    /// at the next opportunity, and [`XmpFile::open_file`],
+    /// [`XmpFile::put_xmp`], or [`XmpFile::close`] fails with an error of
+    /// type [`XmpErrorType::UserAbort`]. Later operations on this struct
+    /// fail the same way until the token is replaced or removed with
+    /// [`XmpFile::clear_cancellation_token`].
+    ///
+    /// How quickly an operation stops depends on the file format handler;
+    /// some only check for cancellation between major steps. If the file
+    /// was opened with [`OpenFileOptions::safe_update`], a cancelled
+    /// [`XmpFile::close`] leaves the original file unchanged.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use xmp_toolkit::{CancellationToken, OpenFileOptions, XmpFile};
+    /// # fn wait_for_cancel_button() {}
+    /// # fn example() -> xmp_toolkit::XmpResult<()> {
+    /// let token = CancellationToken::new();
+    ///
+    /// let ui_token = token.clone();
+    /// std::thread::spawn(move || {
+    ///     wait_for_cancel_button();
+    ///     ui_token.cancel();
+    /// });
+    ///
+    /// let mut f = XmpFile::new()?;
+    /// f.set_cancellation_token(&token)?;
+    /// f.open_file("movie.mov", OpenFileOptions::safe_update())?;
+    ///
+    /// if let Some(xmp) = f.xmp() {
+    ///     f.put_xmp(&xmp)?;
+    /// }
+    /// f.close()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_cancellation_token(&mut self, token: &CancellationToken) -> XmpResult<()> {
+        let token = token.clone();
+
+        let mut err = ffi::CXmpError::default();
+        unsafe {
+            ffi::CXmpFileSetAbortProc(
+                self.f,
+                &mut err,
+                Some(ffi::xmp_file_abort),
+                Arc::as_ptr(&token.cancelled) as *mut c_void,
+            );
+        }
+        XmpError::raise_from_c(&err)?;
+
+        self.cancellation = Some(token);
+        Ok(())
+    }
+
+    /// Removes a token set by [`XmpFile::set_cancellation_token`].
+    pub fn clear_cancellation_token(&mut self) -> XmpResult<()> {
+        let mut err = ffi::CXmpError::default();
+        unsafe {
+            ffi::CXmpFileSetAbortProc(self.f, &mut err, None, std::ptr::null_mut());
+        }
+        XmpError::raise_from_c(&err)?;
+
+        self.cancellation = None;
+        Ok(())
+    }
+
+    /// Returns the token set by [`XmpFile::set_cancellation_token`], if any.
+    #[cfg(feature = "async")]
+    pub(crate) fn cancellation_token(&self) -> Option<&CancellationToken> {
+        self.cancellation.as_ref()
+    }
+
+    /// Attaches an audit log to this struct.
+    ///
+    /// Each time a file opened by this struct is closed after
+    /// [`XmpFile::put_xmp`] was called, an entry with operation `put_xmp`
+    /// and hashes of the XMP as it was when the file was opened and as it
+    /// was last put is appended to the log. Nothing is recorded for files
+    /// that are only read.
+    ///
+    /// Call this before [`XmpFile::open_file`] so that the XMP can be
+    /// hashed when the file is opened.
+    ///
+    /// This function is available only when crate feature `audit` is
+    /// enabled. See the [`xmp_audit`](crate::xmp_audit) module for details.
+    #[cfg(feature = "audit")]
+    pub fn set_audit_log(&mut self, log: &AuditLog) {
+        self.audit = Some(FileAudit {
+            log: log.clone(),
+            path: Default::default(),
+            before: None,
+            after: None,
+            pending: false,
+        });
+    }
+
+    #[cfg(feature = "audit")]
+    fn start_audit(&mut self, path: &Path) {
+        if self.audit.is_none() {
+            return;
+        }
+
+        let before = self.xmp().and_then(|m| xmp_audit::xmp_hash(&m).ok());
+
+        if let Some(audit) = &mut self.audit {
+            audit.path = path.to_path_buf();
+            audit.before = before;
+            audit.after = None;
+            audit.pending = false;
+        }
+    }
+
+    /// Replaces the file with the copy updated by the C++ toolkit, if
+    /// `OpenFileOptions::update_safely` was requested and the XMP was
+    /// updated.
+    fn finish_safe_save(&mut self, updated: bool) -> XmpResult<()> {
+        let (Some(temp_path), Some(path)) = (&self.safe_save_path, &self.path) else {
+            return Ok(());
+        };
+
+        if !updated {
+            self.discard_safe_save();
+            return Ok(());
+        }
+
+        let result = xmp_file_handler::replace_file(temp_path, path, true);
+        if result.is_ok() {
+            self.safe_save_path = None;
+        } else {
+            self.discard_safe_save();
+        }
+
+        result.map_err(|err| XmpError {
+            error_type: XmpErrorType::WriteError,
+            debug_message: err.to_string(),
+        })
+    }
+
+    /// Restores the EXIF and IPTC segments of a JPEG file, if
+    /// `OpenFileOptions::keep_legacy_metadata` was requested and the XMP was
+    /// updated. The C++ toolkit has written the file (or its safe-save
+    /// copy) by now. Files opened by `from_bytes` are restored by
+    /// `into_bytes` instead.
+    fn restore_legacy_segments(&mut self, updated: bool) -> XmpResult<()> {
+        let Some(legacy_segments) = self.legacy_segments.take() else {
+            return Ok(());
+        };
+
+        let Some(path) = self.safe_save_path.as_ref().or(self.path.as_ref()) else {
+            return Ok(());
+        };
+
+        if !updated || self.in_memory {
+            return Ok(());
+        }
+
+        let jpeg = fs::read(path).map_err(|err| XmpError {
+            error_type: XmpErrorType::ReadError,
+            debug_message: err.to_string(),
+        })?;
+
+        let jpeg = xmp_jpeg::replace_legacy_segments(&jpeg, &legacy_segments)?;
+        let temp_path = xmp_file_handler::temp_path(path, self.temp_folder.as_deref());
+
+        xmp_file_handler::write_temp_file(&temp_path, &jpeg)
+            .and_then(|()| xmp_file_handler::replace_file(&temp_path, path, false))
+            .map_err(|err| {
+                let _ = fs::remove_file(&temp_path);
+                XmpError {
+                    error_type: XmpErrorType::WriteError,
+                    debug_message: err.to_string(),
+                }
+            })
+    }
+
+    /// Checks that the C++ toolkit changed nothing but the XMP packet, if
+    /// `OpenFileOptions::update_xmp_in_place` was requested and the XMP was
+    /// updated. The toolkit has updated a copy of the file by now, which
+    /// is discarded by the caller if this fails. Files opened by
+    /// `from_bytes` are checked by `into_bytes` instead.
+    fn verify_in_place_update(&mut self, updated: bool) -> XmpResult<()> {
+        let Some(in_place) = self.in_place.take() else {
+            return Ok(());
+        };
+
+        let (Some(temp_path), Some(path)) = (&self.safe_save_path, &self.path) else {
+            return Ok(());
+        };
+
+        if !updated || self.in_memory {
+            return Ok(());
+        }
+
+        let changed = File::open(path)
+            .and_then(|original| {
+                let updated = File::open(temp_path)?;
+                changed_outside_packet(original, updated, in_place.packet.as_ref())
+            })
+            .map_err(|err| XmpError {
+                error_type: XmpErrorType::ReadError,
+                debug_message: err.to_string(),
+            })?;
+
+        if changed {
+            return Err(not_in_place());
+        }
+
+        Ok(())
+    }
+
+    /// Restores the access and modification times of the file, if
+    /// `OpenFileOptions::preserve_file_times` was requested and the XMP was
+    /// updated.
+    fn restore_file_times(&mut self, updated: bool) -> XmpResult<()> {
+        let (Some((accessed, modified)), Some(path)) = (self.file_times.take(), &self.path) else {
+            return Ok(());
+        };
+
+        if !updated {
+            return Ok(());
+        }
+
+        filetime::set_file_times(path, accessed, modified).map_err(|err| XmpError {
+            error_type: XmpErrorType::WriteError,
+            debug_message: err.to_string(),
+        })
+    }
+
+    fn discard_safe_save(&mut self) {
+        if let Some(temp_path) = self.safe_save_path.take() {
+            let _ = fs::remove_file(temp_path);
+        }
+    }
+
+    #[cfg(feature = "audit")]
+    fn finish_audit(&mut self) -> XmpResult<()> {
+        let Some(audit) = &mut self.audit else {
+            return Ok(());
+        };
+
+        if !audit.pending {
+            return Ok(());
+        }
+
+        audit.pending = false;
+        audit.log.record(&AuditEntry {
+            operation: "put_xmp".to_owned(),
+            path: audit.path.clone(),
+            before: audit.before.take(),
+            after: audit.after.take(),
+        })
+    }
+}
+
+/// A stream whose metadata can be updated by
+/// [`XmpFile::open_stream_for_update`].
+///
+/// This is implemented for [`File`]. Implement it for other types, such as
+/// handles to files in a virtual file system, to update their metadata.
+pub trait UpdatableStream: Read + Write + Seek + Send {
+    /// Truncates or extends the stream to exactly `len` bytes.
+    fn set_len(&mut self, len: u64) -> io::Result<()>;
+}
+
+impl UpdatableStream for File {
+    fn set_len(&mut self, len: u64) -> io::Result<()> {
+        File::set_len(self, len)
+    }
+}
+
+/// Describes the outcome of [`XmpFile::try_close_into_report`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CloseReport {
+    /// The path of the file that was closed, or `None` if no file was open.
+    pub path: Option<PathBuf>,
+
+    /// The XMP that was written to the file, or `None` if
+    /// [`XmpFile::put_xmp`] was not called and the file was left unchanged.
+    pub written_xmp: Option<XmpMeta>,
+
+    /// The sidecar file that the XMP was written to instead of the file, if
+    /// it couldn't be embedded; see [`OpenFileOptions::sidecar_fallback`].
+    pub sidecar_path: Option<PathBuf>,
+}
+
+impl CloseReport {
+    /// Returns `true` if the file, or its sidecar, was updated.
+    pub fn was_updated(&self) -> bool {
+        self.written_xmp.is_some()
+    }
+}
+
+/// Describes the progress of a long-running file operation.
+///
+/// Passed to the callback set by [`XmpFile::set_progress_callback`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Progress {
+    /// Time elapsed since the operation started.
+    pub elapsed: Duration,
+
+    /// Fraction of the work done so far, from `0.0` to `1.0`, or `0.0` if
+    /// the file format handler can't estimate it.
+    pub fraction_done: f32,
+
+    /// Estimated time until the operation finishes, or zero if the file
+    /// format handler can't estimate it.
+    pub remaining: Duration,
+}
+
+/// The XMP of a file together with its packet, as returned by
+/// [`XmpFile::xmp_with_packet`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct XmpWithPacket {
+    /// The parsed XMP, as returned by [`XmpFile::xmp`].
+    pub xmp: XmpMeta,
+
+    /// The packet exactly as it is stored in the file, as returned by
+    /// [`XmpFile::raw_xmp_packet`].
+    pub packet: Vec<u8>,
+
+    /// Where the packet is stored, as returned by
+    /// [`XmpFile::packet_info`].
+    pub info: PacketInfo,
+}
+
+/// Describes the XMP packet in a file, as returned by
+/// [`XmpFile::packet_info`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct PacketInfo {
+    /// The offset of the packet, in bytes from the start of the file, or
+    /// `None` if the location is unknown.
+    pub offset: Option<u64>,
+
+    /// The length of the packet in bytes, including any padding and the
+    /// packet wrapper, or `None` if unknown.
+    pub length: Option<u32>,
+
+    /// The number of bytes of padding at the end of the packet, which can
+    /// be used to grow the packet in place.
+    pub padding: u32,
+
+    /// The character encoding of the packet.
+    pub encoding: PacketEncoding,
+
+    /// `true` if the packet trailer says the packet may be updated in
+    /// place (`end="w"`).
+    pub writable: bool,
+
+    /// `true` if the packet has an `<?xpacket` wrapper.
+    pub has_wrapper: bool,
+}
+
+impl PacketInfo {
+    // Describes a UTF-8 packet found by one of this crate's own format
+    // handlers. The offset and length are left unknown.
+    fn from_packet(packet: &[u8]) -> Self {
+        let trailer = packet
+            .windows(14)
+            .rposition(|window| window == b"<?xpacket end=");
+
+        let padding = trailer.map_or(0, |trailer| {
+            packet[..trailer]
+                .iter()
+                .rev()
+                .take_while(|b| b.is_ascii_whitespace())
+                .count()
+        });
+
+        Self {
+            offset: None,
+            length: None,
+            padding: padding as u32,
+            encoding: PacketEncoding::Utf8,
+            writable: trailer.is_some_and(|trailer| packet.get(trailer + 15) == Some(&b'w')),
+            has_wrapper: packet.starts_with(b"<?xpacket begin="),
+        }
+    }
+
+    /// Returns the range of bytes occupied by the packet, if known.
+    pub fn range(&self) -> Option<Range<u64>> {
+        let offset = self.offset?;
+        Some(offset..offset + u64::from(self.length?))
+    }
+}
+
+/// The reason a file can't be updated with an XMP packet, as returned by
+/// [`XmpFile::put_xmp_blocker`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum PutXmpBlocker {
+    /// The file was not opened with [`OpenFileOptions::for_update`].
+    NotOpenedForUpdate,
+
+    /// The file format handler can't write XMP to this file. For example,
+    /// the file has no XMP packet and the handler can only update existing
+    /// packets, or the packet is marked read-only.
+    ReadOnlyFormat,
+
+    /// The XMP must be written into the file's existing packet, but it
+    /// doesn't fit. This happens for handlers that can't move or expand the
+    /// packet, and for files opened with
+    /// [`OpenFileOptions::update_xmp_in_place`].
+    PacketTooLarge,
+
+    /// The file's permissions don't allow writing it, so it can't be
+    /// written when closed.
+    ReadOnlyFile,
+
+    /// Another process holds a lock on the file (see
+    /// [`OpenFileOptions::lock_file`]) or, on Windows, has it open without
+    /// sharing it, so it can't be written when closed. Unlike the other
+    /// reasons, this may go away if the update is tried again later.
+    FileLocked,
+
+    /// The update isn't possible for another reason, such as the C++ XMP
+    /// Toolkit being unavailable.
+    Other,
+}
+
+/// Describes the capabilities of the handler for a file format, as
+/// returned by [`XmpFile::format_info`].
+///
+/// Each field corresponds to one of the `kXMPFiles_*` handler flags of the
+/// C++ SDK (for example, `kXMPFiles_CanInjectXMP`).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct FormatCapabilities {
+    /// XMP can be added to a file that has none.
+    pub can_inject_xmp: bool,
+
+    /// The XMP packet can grow beyond its existing size, including its
+    /// padding.
+    pub can_expand: bool,
+
+    /// The handler can rewrite the whole file to update the XMP.
+    pub can_rewrite: bool,
+
+    /// The handler prefers to update the XMP packet in place.
+    pub prefers_in_place: bool,
+
+    /// The handler reconciles the XMP with the file's legacy (non-XMP)
+    /// metadata.
+    pub can_reconcile: bool,
+
+    /// The handler only reads and writes XMP, ignoring any other
+    /// metadata in the file.
+    pub allows_only_xmp: bool,
+
+    /// [`XmpFile::raw_xmp_packet`] returns the packet as it is stored in
+    /// the file.
+    pub returns_raw_packet: bool,
+
+    /// The handler opens and writes the file itself, rather than through
+    /// the C++ XMP Toolkit's I/O layer.
+    pub handler_owns_file: bool,
+
+    /// The handler supports crash-safe updates, as requested with
+    /// [`OpenFileOptions::update_safely`].
+    pub allows_safe_update: bool,
+
+    /// The handler only works with XMP packets that are marked read-only.
+    pub needs_read_only_packet: bool,
+
+    /// The XMP is stored in a sidecar file rather than in the file itself.
+    pub uses_sidecar_xmp: bool,
+
+    /// The format is folder-based; see [`FileFormat::is_folder_based`].
+    pub folder_based_format: bool,
+
+    /// The handler reports progress to the callback set by
+    /// [`XmpFile::set_progress_callback`].
+    pub can_notify_progress: bool,
+
+    /// The handler reads the whole file when it is opened.
+    pub needs_preloading: bool,
+}
+
+impl FormatCapabilities {
+    fn from_c(flags: u32) -> Self {
+        let has = |flag: u32| flags & flag != 0;
+
+        Self {
+            can_inject_xmp: has(0x0001),
+            can_expand: has(0x0002),
+            can_rewrite: has(0x0004),
+            prefers_in_place: has(0x0008),
+            can_reconcile: has(0x0010),
+            allows_only_xmp: has(0x0020),
+            returns_raw_packet: has(0x0040),
+            handler_owns_file: has(0x0100),
+            allows_safe_update: has(0x0200),
+            needs_read_only_packet: has(0x0400),
+            uses_sidecar_xmp: has(0x0800),
+            folder_based_format: has(0x1000),
+            can_notify_progress: has(0x2000),
+            needs_preloading: has(0x4000),
+        }
+    }
+
+    /// Returns `true` if XMP can be written to files that don't already
+    /// have a packet with room for it.
+    ///
+    /// If this is `false`, the handler can at most update an existing
+    /// packet in place.
+    pub fn can_add_xmp(&self) -> bool {
+        self.can_inject_xmp && self.can_expand
+    }
+}
+
+/// How much padding to leave at the end of an XMP packet written by
+/// [`XmpFile::put_xmp_with_padding`].
+///
+/// Padding is whitespace between the XMP and the packet trailer that lets
+/// later updates rewrite the packet in place. It is written as lines of at
+/// most 100 spaces, each ending with a newline.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum PacketPadding {
+    /// Padding chosen by the file format handler, which is what
+    /// [`XmpFile::put_xmp`] writes.
+    #[default]
+    Default,
+
+    /// No padding: the packet is as short as possible.
+    Exact,
+
+    /// Exactly this many bytes of padding.
+    Bytes(u32),
+
+    /// Padding of this percentage of the length of the unpadded packet,
+    /// rounded down.
+    Percent(u32),
+}
+
+impl PacketPadding {
+    /// Serializes `meta` as a packet with this padding.
+    pub(crate) fn serialize(self, meta: &XmpMeta) -> XmpResult<String> {
+        let packet = meta.to_string_with_options(ToStringOptions::default())?;
+
+        // The packet normally ends with `</x:xmpmeta>\n`, the padding, and
+        // the trailer.
+        let Some(trailer) = packet.rfind("<?xpacket end=") else {
+            return Ok(packet);
+        };
+
+        let content = format!("{}\n", packet[..trailer].trim_end());
+        let trailer = &packet[trailer..];
+
+        let padding = match self {
+            Self::Default => return Ok(packet),
+            Self::Exact => 0,
+            Self::Bytes(bytes) => bytes as usize,
+            Self::Percent(percent) => {
+                (content.len() + trailer.len()).saturating_mul(percent as usize) / 100
+            }
+        };
+
+        let mut padded = String::with_capacity(content.len() + padding + trailer.len());
+        padded.push_str(&content);
+        padded.extend((1..=padding).map(|i| {
+            if i % 101 == 0 || i == padding {
+                '\n'
+            } else {
+                ' '
+            }
+        }));
+        padded.push_str(trailer);
+        Ok(padded)
+    }
+}
+
+/// The character encoding of an XMP packet.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum PacketEncoding {
+    /// UTF-8.
+    Utf8,
+
+    /// UTF-16, big-endian.
+    Utf16BigEndian,
+
+    /// UTF-16, little-endian.
+    Utf16LittleEndian,
+
+    /// UTF-32, big-endian.
+    Utf32BigEndian,
+
+    /// UTF-32, little-endian.
+    Utf32LittleEndian,
+}
+
+impl PacketEncoding {
+    // Converts one of the C++ SDK's `kXMP_Char*` constants. The toolkit
+    // reports 8-bit packets as UTF-8.
+    fn from_c(char_form: u32) -> Self {
+        match char_form {
+            2 => Self::Utf16BigEndian,
+            3 => Self::Utf16LittleEndian,
+            4 => Self::Utf32BigEndian,
+            5 => Self::Utf32LittleEndian,
+            _ => Self::Utf8,
+        }
+    }
+}
+
+/// A handle for cancelling long-running [`XmpFile`] operations.
+///
+/// Attach a token to a file with [`XmpFile::set_cancellation_token`]. The
+/// token can be cloned cheaply; clones share the same state, so any of
+/// them can be sent to another thread and used to cancel the operation.
+///
+/// Once cancelled, a token stays cancelled. Use a new token for the next
+/// operation.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a token that has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests that operations on files using this token stop.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Returns `true` if [`CancellationToken::cancel`] has been called on
+    /// this token or one of its clones.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+}
+
+/// How [`XmpFile::open_file`] retries opening a file that another process
+/// has open without sharing it.
+///
+/// See [`OpenFileOptions::retry_on_sharing_violation`]. The delay before
+/// the first retry is [`SharingRetry::initial_delay`]; each later delay is
+/// twice the one before it, up to [`SharingRetry::max_delay`].
+///
+/// The default makes 5 retries, starting with a delay of 100 milliseconds
+/// and waiting at most 2 seconds between retries.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SharingRetry {
+    retries: u32,
+    initial_delay: Duration,
+    max_delay: Duration,
+}
+
+impl Default for SharingRetry {
+    fn default() -> Self {
+        Self {
+            retries: 5,
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(2),
+        }
+    }
+}
+
+impl SharingRetry {
+    /// Creates a policy that retries up to `retries` times, waiting
+    /// `initial_delay` before the first retry.
+    pub fn new(retries: u32, initial_delay: Duration) -> Self {
+        Self {
+            retries,
+            initial_delay,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the number of retries after the first attempt.
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Returns the delay before the first retry.
+    pub fn initial_delay(&self) -> Duration {
+        self.initial_delay
+    }
+
+    /// Returns the longest delay between two retries.
+    pub fn max_delay(&self) -> Duration {
+        self.max_delay
+    }
+
+    /// Limits the delay between two retries to `max_delay`.
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Returns the delay before retry number `retry` (counting from 0),
+    /// or `None` if no more retries should be made.
+    pub(crate) fn delay(&self, retry: u32) -> Option<Duration> {
+        if retry >= self.retries {
+            return None;
+        }
+
+        let delay = self
+            .initial_delay
+            .checked_mul(1 << retry.min(31))
+            .unwrap_or(Duration::MAX);
+
+        Some(delay.min(self.max_delay))
+    }
+}
+
+/// Whether and how [`XmpFile::open_file`] locks a file.
+///
+/// See [`OpenFileOptions::lock_file`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum FileLocking {
+    /// Don't lock the file. This is the behavior of the C++ XMP Toolkit.
+    #[default]
+    None,
+
+    /// Lock the file, waiting for as long as another process holds a
+    /// conflicting lock.
+    Wait,
+
+    /// Lock the file, or fail with an error of type
+    /// [`XmpErrorType::FileLocked`] if another process holds a
+    /// conflicting lock.
+    FailIfLocked,
+}
+
+/// Option flags for [`XmpFile::open_file`].
+///
+/// Invoke by calling [`OpenFileOptions::default`] and then calling methods
+/// on this struct to add options as needed.
+///
+/// For common use cases, prefer one of the presets
+/// ([`OpenFileOptions::read_only_fast_scan`],
+/// [`OpenFileOptions::safe_update`], or [`OpenFileOptions::repair_mode`]),
+/// which combine flags that are known to work well together. Further options
+/// may be added to a preset as needed.
+#[derive(Clone, Default)]
+pub struct OpenFileOptions {
+    pub(crate) options: u32,
+    max_file_size: Option<u64>,
+    max_scan_bytes: Option<u64>,
+    buffered_read: bool,
+    check_file_signature: bool,
+    keep_svg_without_metadata: bool,
+    skip_id3_reconciliation: bool,
+    import_vorbis_comments: bool,
+    import_bwf_metadata: bool,
+    skip_quicktime_reconciliation: bool,
+    skip_quicktime_timecode: bool,
+    update_safely: bool,
+    preserve_file_times: bool,
+    keep_legacy_metadata: bool,
+    update_xmp_in_place: bool,
+    repair_xmp: bool,
+    temp_folder: Option<PathBuf>,
+    sidecar_fallback: bool,
+    sharing_retry: Option<SharingRetry>,
+    file_locking: FileLocking,
+}
+
+impl OpenFileOptions {
+    const FOR_UPDATE: u32 = 0x00000002;
+    const ONLY_XMP: u32 = 0x00000004;
+    const USE_PACKET_SCANNING: u32 = 0x00000040;
+    const USE_SMART_HANDLER: u32 = 0x00000020;
+
+    /// Preset for quickly reading the XMP from a file.
+    ///
+    /// Opens the file read-only, asks only for the XMP (skipping legacy
+    /// metadata reconciliation), and only falls back to packet scanning for
+    /// file formats that are known to need it.
+    ///
+    /// The trade-off is that XMP in files without a smart handler may be
+    /// missed, and values derived from legacy metadata (Exif, IPTC, etc.)
+    /// will not be reflected in the result.
+    ///
+    /// Equivalent to `OpenFileOptions::default().for_read().only_xmp()
+    /// .limited_scanning()`.
+    pub fn read_only_fast_scan() -> Self {
+        Self::default().for_read().only_xmp().limited_scanning()
+    }
+
+    /// Preset for updating the XMP in a file.
+    ///
+    /// Opens the file for update and requires a smart handler for the file
+    /// format. Smart handlers understand the file structure and can grow the
+    /// XMP packet or reconcile legacy metadata as needed. Updating through
+    /// packet scanning, by contrast, can only rewrite a packet in place and
+    /// is likely to fail or lose data when the new packet does not fit.
+    ///
+    /// The trade-off is that opening a file whose format has no smart handler
+    /// fails rather than falling back to packet scanning. Callers that want to
+    /// support such files should retry with
+    /// `OpenFileOptions::default().for_update().use_packet_scanning()`
+    /// and check [`XmpFile::can_put_xmp`] before writing.
+    ///
+    /// Equivalent to `OpenFileOptions::default().for_update()
+    /// .use_smart_handler()`.
+    pub fn safe_update() -> Self {
+        Self::default().for_update().use_smart_handler()
+    }
+
+    /// Preset for opening a damaged file with the intent of repairing it.
+    ///
+    /// Opens the file for update and asks the handler to attempt a repair
+    /// of file structure problems rather than fail.
+    ///
+    /// The trade-off is that the repair is a best effort: Portions of the
+    /// file that the handler can not make sense of may be discarded when the
+    /// file is written. Consider working on a copy of the file.
+    ///
+    /// Equivalent to `OpenFileOptions::default().for_update().repair_file()`.
+    pub fn repair_mode() -> Self {
+        Self::default().for_update().repair_file()
+    }
+
+    /// Open for read-only access.
+    ///
+    /// See `kXMPFiles_OpenForRead` constant in C++ SDK.
+    pub fn for_read(mut self) -> Self {
+        self.options |= 0x00000001;
+        self
+    }
+
+    /// Open for reading and writing.
+    ///
+    /// See `kXMPFiles_OpenForUpdate` constant in C++ SDK.
+    pub fn for_update(mut self) -> Self {
+        self.options |= Self::FOR_UPDATE;
+        self
+    }
+
+    /// Only the XMP is wanted.
+    ///
+    /// This allows space/time optimizations.
+    ///
+    /// Legacy metadata, such as EXIF and IPTC, is not reconciled into the
+    /// XMP. To keep the legacy metadata from being updated when the XMP is
+    /// written, use [`OpenFileOptions::keep_legacy_metadata`].
+    ///
+    /// See `kXMPFiles_OpenOnlyXMP` constant in C++ SDK.
+    pub fn only_xmp(mut self) -> Self {
+        self.options |= Self::ONLY_XMP;
+        self
+    }
+
+    /// Force use of the given handler (format).
+    ///
+    /// Do not even verify the format.
+    ///
+    /// See `kXMPFiles_ForceGivenHandler` constant in C++ SDK.
+    pub fn force_given_handler(mut self) -> Self {
+        self.options |= 0x00000008;
+        self
+    }
+
+    /// Be strict about only attempting to use the designated file handler.
+    ///
+    /// Do not fall back to other handlers.
+    ///
+    /// See `kXMPFiles_OpenStrictly` constant in C++ SDK.
+    pub fn strict(mut self) -> Self {
+        self.options |= 0x00000010;
+        self
+    }
+
+    /// Require the use of a smart handler.
+    ///
+    /// See `kXMPFiles_OpenUseSmartHandler` constant in C++ SDK.
+    pub fn use_smart_handler(mut self) -> Self {
+        self.options |= Self::USE_SMART_HANDLER;
+        self
+    }
+
+    /// Force packet scanning.
+    ///
+    /// Do not use a smart handler. Use [`OpenFileOptions::max_scan_bytes`]
+    /// to bound the time spent scanning large files.
+    ///
+    /// See `kXMPFiles_OpenUsePacketScanning` constant in C++ SDK.
+    pub fn use_packet_scanning(mut self) -> Self {
+        self.options |= Self::USE_PACKET_SCANNING;
+        self
+    }
+
+    /// Only packet scan files "known" to need scanning.
+    ///
+    /// Files of other formats without a smart handler are opened without
+    /// XMP instead of being scanned.
+    ///
+    /// See `kXMPFiles_OpenLimitedScanning` constant in C++ SDK.
+    pub fn limited_scanning(mut self) -> Self {
+        self.options |= 0x00000080;
+        self
+    }
+
+    /// Attempt to repair a file opened for update.
+    ///
+    /// Default is to not open (throw an exception).
+    ///
+    /// See `kXMPFiles_OpenRepairFile` constant in C++ SDK.
+    pub fn repair_file(mut self) -> Self {
         self.options |= 0x00000100;
         self
     }
@@ -303,11 +3002,892 @@ impl OpenFileOptions {
     /// When updating a file, spend the effort necessary to optimize file
     /// layout.
     ///
+    /// A handler that supports this may rewrite the whole file when it is
+    /// closed instead of updating the XMP in place, for example to move
+    /// the metadata of an MPEG-4 or QuickTime file ahead of its media data.
+    /// That makes saving slower, so use it for final deliverables and keep
+    /// the default for working files that are saved often. Handlers that
+    /// don't support it, including those implemented by this crate (see
+    /// [`XmpFile`]), ignore it.
+    ///
+    /// Since the file may be rewritten, this option defeats
+    /// [`OpenFileOptions::update_xmp_in_place`]: such an update fails with
+    /// an error of type [`XmpErrorType::XmpUpdateNotInPlace`] if anything
+    /// outside the packet changed.
+    ///
     /// See `kXMPFiles_OptimizeFileLayout` constant in C++ SDK.
     pub fn optimize_file_layout(mut self) -> Self {
         self.options |= 0x00000200;
         self
     }
+
+    /// When a file opened for update is closed, write the new version to
+    /// a temporary file in the same folder, flush it to disk, and then
+    /// rename it over the original.
+    ///
+    /// The rename is atomic on local file systems, so if the process or
+    /// the system crashes while the file is written, the file is left
+    /// either unchanged or completely updated, never partially written.
+    /// Once [`XmpFile::close`] returns successfully, the update is on disk.
+    /// (On Unix, the folder is flushed as well, so that the rename itself
+    /// survives a crash.) Network file systems may not provide these
+    /// guarantees.
+    ///
+    /// The trade-offs are that the whole file is copied, which takes time
+    /// and disk space for large files, and that the updated file is a new
+    /// file: hard links to the original are not updated, and the owner
+    /// and other file system attributes that [`std::fs::copy`] doesn't
+    /// preserve may change.
+    ///
+    /// Without this option, most C++ file handlers update the file in
+    /// place, which is faster but can leave a damaged file behind if
+    /// interrupted.
+    ///
+    /// See [`OpenFileOptions::temp_folder`] for writing the temporary file
+    /// to another folder.
+    ///
+    /// This takes the place of the `kXMPFiles_UpdateSafely` flag of
+    /// `CloseFile` in the C++ SDK, adding the flush to disk.
+    pub fn update_safely(mut self) -> Self {
+        self.update_safely = true;
+        self
+    }
+
+    pub(crate) fn updates_safely(&self) -> bool {
+        self.update_safely
+    }
+
+    /// Write temporary files to `folder` instead of to the folder of the
+    /// file being updated.
+    ///
+    /// Temporary files hold a copy of the file while it is updated with
+    /// [`OpenFileOptions::update_safely`] or
+    /// [`OpenFileOptions::update_xmp_in_place`], and the new version of
+    /// files that are rewritten in full, such as those handled by this
+    /// crate rather than by the C++ XMP Toolkit (see [`XmpFile`]). Use this
+    /// option when the file's volume is short of space, so that the
+    /// copies go to scratch storage instead.
+    ///
+    /// If `folder` is on the same file system as the file, the updated
+    /// file still replaces the original with an atomic rename. Otherwise,
+    /// the updated file is copied over the original, which is not atomic:
+    /// if the process or the system crashes during the copy, the file may
+    /// be left partially written. `folder` must exist; an error of type
+    /// [`XmpErrorType::WriteError`] is returned when the temporary file
+    /// can't be created.
+    pub fn temp_folder<P: AsRef<Path>>(mut self, folder: P) -> Self {
+        self.temp_folder = Some(folder.as_ref().to_path_buf());
+        self
+    }
+
+    pub(crate) fn temp_folder_path(&self) -> Option<&Path> {
+        self.temp_folder.as_deref()
+    }
+
+    /// Write the XMP to the file's `.xmp` sidecar when it can't be embedded
+    /// in the file.
+    ///
+    /// Without this option, [`XmpFile::put_xmp`] fails when the file format
+    /// handler can't write XMP to the file, when the XMP doesn't fit in a
+    /// packet that can't grow, and when the file is read-only. With it,
+    /// `put_xmp` succeeds in those cases and [`XmpFile::close`] writes the
+    /// XMP to the sidecar found by [`Sidecar::find`] instead, leaving the
+    /// file unchanged. [`XmpFile::sidecar_for_update`] and
+    /// [`CloseReport::sidecar_path`] report which route was taken. Once an
+    /// update has gone to the sidecar, later updates go there as well.
+    ///
+    /// The file must still be opened with [`OpenFileOptions::for_update`].
+    /// This option has no effect for files opened with
+    /// [`XmpFile::open_stream`] or [`XmpFile::from_bytes`], which have no
+    /// path for a sidecar.
+    pub fn sidecar_fallback(mut self) -> Self {
+        self.sidecar_fallback = true;
+        self
+    }
+
+    pub(crate) fn falls_back_to_sidecar(&self) -> bool {
+        self.sidecar_fallback
+    }
+
+    /// Retry opening a file that another process has open without sharing
+    /// it.
+    ///
+    /// On Windows, applications such as photo catalogs and the Explorer
+    /// preview pane may briefly open a file without allowing others to
+    /// read or write it. [`XmpFile::open_file`] reports an error of type
+    /// [`XmpErrorType::FileSharingViolation`] for such files. With this
+    /// option, it instead waits and tries again as described by `retry`,
+    /// and only reports the error once the retries are used up. Retrying
+    /// stops early if the token set with
+    /// [`XmpFile::set_cancellation_token`] is cancelled.
+    ///
+    /// Other platforms don't lock files in this way, so this option has no
+    /// effect there.
+    pub fn retry_on_sharing_violation(mut self, retry: SharingRetry) -> Self {
+        self.sharing_retry = Some(retry);
+        self
+    }
+
+    pub(crate) fn sharing_retry(&self) -> Option<&SharingRetry> {
+        self.sharing_retry.as_ref()
+    }
+
+    /// Lock the file while it is being read or updated.
+    ///
+    /// By default, [`XmpFile::open_file`] takes no locks, so a process
+    /// reading a file can see it half-written by another. With this
+    /// option, the file is locked as follows:
+    ///
+    /// * A file opened for reading is locked for shared access while its
+    ///   metadata is read, that is, until `open_file` returns.
+    ///
+    /// * A file opened with [`OpenFileOptions::for_update`] is locked for
+    ///   exclusive access until [`XmpFile::close`] is called or the `XmpFile`
+    ///   is dropped.
+    ///
+    /// Any number of shared locks can be held at once, but an exclusive
+    /// lock excludes all other locks. `locking` decides whether
+    /// `open_file` waits for a conflicting lock to be released.
+    ///
+    /// The locks are advisory: they only coordinate processes that take
+    /// them, typically other programs using this option, and don't keep
+    /// other programs from reading or writing the file. Readers and writers
+    /// choose their locking independently; for example, writers can lock
+    /// files among themselves without slowing down readers that don't.
+    /// This option has no effect for files opened with
+    /// [`XmpFile::open_stream`] or [`XmpFile::from_bytes`].
+    pub fn lock_file(mut self, locking: FileLocking) -> Self {
+        self.file_locking = locking;
+        self
+    }
+
+    /// Locks the file at `path` as requested with
+    /// [`OpenFileOptions::lock_file`].
+    fn lock(&self, path: &Path) -> XmpResult<Option<FileLock>> {
+        let exclusive = self.opens_for_update();
+
+        match self.file_locking {
+            FileLocking::None => Ok(None),
+            FileLocking::Wait => FileLock::acquire(path, exclusive, true),
+            FileLocking::FailIfLocked => FileLock::acquire(path, exclusive, false),
+        }
+    }
+
+    /// Recover the XMP of a file whose XMP packet is damaged.
+    ///
+    /// The C++ XMP Toolkit fails to open a file whose packet is cut off or
+    /// has a broken wrapper, or opens it without XMP. With this option,
+    /// [`XmpFile::open_file`] and [`XmpFile::from_bytes`] then look for the
+    /// packet themselves and repair it as described in
+    /// [`xmp_packet::repair`]. [`XmpFile::repairs`] reports what was fixed.
+    ///
+    /// Since the file's container may be damaged as well, a repaired packet
+    /// can only be updated in place: [`XmpFile::put_xmp`] overwrites the
+    /// damaged packet with a well-formed one of the same length, as with
+    /// [`OpenFileOptions::update_xmp_in_place`]. Legacy metadata is not
+    /// reconciled for such files.
+    ///
+    /// Compare [`OpenFileOptions::repair_file`], which asks the C++ file
+    /// handler to repair the file's structure.
+    pub fn repair_xmp(mut self) -> Self {
+        self.repair_xmp = true;
+        self
+    }
+
+    pub(crate) fn repairs_xmp(&self) -> bool {
+        self.repair_xmp
+    }
+
+    /// Only let an update change the bytes of the file's existing XMP
+    /// packet.
+    ///
+    /// Use this option when the rest of the file (such as pixel data and
+    /// EXIF) must stay byte for byte as it is. The new XMP must fit in the
+    /// space of the existing packet, including its padding; the file's
+    /// length and every byte outside the packet are left unchanged.
+    ///
+    /// This is verified rather than assumed: the C++ XMP Toolkit updates a
+    /// temporary copy of the file, which [`XmpFile::close`] compares with
+    /// the original before replacing it. (Files opened by
+    /// [`XmpFile::from_bytes`] are compared in memory by
+    /// [`XmpFile::into_bytes`].) If anything outside the packet changed,
+    /// for instance because the packet had to grow or the format handler
+    /// rewrites other parts of the file, the update is discarded and an
+    /// error of type [`XmpErrorType::XmpUpdateNotInPlace`] is returned.
+    /// [`XmpFile::put_xmp`] returns the same error right away if the file
+    /// has no XMP packet.
+    pub fn update_xmp_in_place(mut self) -> Self {
+        self.update_xmp_in_place = true;
+        self
+    }
+
+    pub(crate) fn updates_xmp_in_place(&self) -> bool {
+        self.update_xmp_in_place
+    }
+
+    /// Leave the legacy metadata of a file opened for update unchanged.
+    ///
+    /// When the XMP is updated, the C++ file handlers normally also write
+    /// reconciled properties (such as `exif:DateTimeOriginal` or
+    /// `dc:rights`) to the file's EXIF and IPTC blocks. Use this option when
+    /// those blocks are authoritative and must not be touched: when the
+    /// file is closed, its EXIF and IPTC (Photoshop) blocks are restored,
+    /// byte for byte, to what they were when it was opened.
+    ///
+    /// This is currently supported for JPEG files only. [`XmpFile::open_file`]
+    /// and [`XmpFile::from_bytes`] return an error of type
+    /// [`XmpErrorType::Unimplemented`] if a file of another format is
+    /// opened for update with this option, unless this crate handles that
+    /// format itself (its handlers never write legacy metadata).
+    ///
+    /// Combine with [`OpenFileOptions::only_xmp`] to also keep the legacy
+    /// metadata from being reconciled into the XMP when the file is read.
+    pub fn keep_legacy_metadata(mut self) -> Self {
+        self.keep_legacy_metadata = true;
+        self
+    }
+
+    pub(crate) fn keeps_legacy_metadata(&self) -> bool {
+        self.keep_legacy_metadata
+    }
+
+    /// Keep the access and modification times of a file opened for update.
+    ///
+    /// When the file is closed after the XMP was updated, its access and
+    /// modification times are reset to what they were when it was opened,
+    /// so that a metadata-only edit isn't mistaken for a content change by
+    /// backup or archive tools that compare modification times. The file
+    /// system may still update the change time (`ctime` on Unix), which
+    /// can't be set.
+    pub fn preserve_file_times(mut self) -> Self {
+        self.preserve_file_times = true;
+        self
+    }
+
+    pub(crate) fn preserves_file_times(&self) -> bool {
+        self.preserve_file_times
+    }
+
+    /// Read the file through a large buffer.
+    ///
+    /// By default, the C++ file handlers read the parts of the file they
+    /// need with many small reads, each of which costs a round trip on
+    /// network storage. With this option, [`XmpFile::open_file`] reads the
+    /// file in chunks of 4 MiB and serves the handlers' small reads from
+    /// the last chunk read. This is usually much faster for files on
+    /// network storage; at most one chunk is held in memory, whatever the
+    /// size of the file.
+    ///
+    /// This only applies to files opened for reading; files opened with
+    /// [`OpenFileOptions::for_update`] are read by the C++ file handlers
+    /// as usual.
+    pub fn buffered_read(mut self) -> Self {
+        self.buffered_read = true;
+        self
+    }
+
+    fn reads_buffered(&self) -> bool {
+        self.buffered_read && !self.opens_for_update()
+    }
+
+    /// Limit packet scanning to the first `bytes` bytes of a file.
+    ///
+    /// Packet scanning is used for files that no smart handler recognizes,
+    /// or for all files with [`OpenFileOptions::use_packet_scanning`]. It
+    /// reads the whole file, which can take minutes for a large video
+    /// file. With this option, [`XmpFile::open_file`] reads at most `bytes`
+    /// bytes of such a file into memory and scans only those, so XMP that
+    /// is stored further into the file is not found.
+    ///
+    /// This only applies to files opened for reading; files opened with
+    /// [`OpenFileOptions::for_update`] are always scanned in full, since a
+    /// packet found by scanning is updated in place.
+    pub fn max_scan_bytes(mut self, bytes: u64) -> Self {
+        self.max_scan_bytes = Some(bytes);
+        self
+    }
+
+    /// Returns the number of bytes to which packet scanning of the file at
+    /// `path` is limited, if the limit applies.
+    fn scan_limit(&self, path: &Path) -> Option<u64> {
+        let limit = self.max_scan_bytes?;
+
+        if self.opens_for_update() || self.options & Self::USE_SMART_HANDLER != 0 {
+            return None;
+        }
+
+        if fs::metadata(path).ok()?.len() <= limit {
+            return None;
+        }
+
+        let scans = self.options & Self::USE_PACKET_SCANNING != 0
+            || XmpFile::check_file_format(path).ok()?.is_none();
+
+        scans.then_some(limit)
+    }
+
+    /// Refuse to open files larger than `bytes`.
+    ///
+    /// The size is taken from the file system before the file is handed to
+    /// the C++ XMP Toolkit, so an oversized (possibly sparse) file is
+    /// rejected without being read. [`XmpFile::open_file`] reports an error
+    /// of type [`XmpErrorType::FileTooLarge`] for such files.
+    pub fn max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = Some(bytes);
+        self
+    }
+
+    /// Verify that the first bytes of the file match the format implied by
+    /// its extension before opening it.
+    ///
+    /// This rejects mislabeled files (for example, a text file named
+    /// `*.jpg`) before any format handler sees them.
+    /// [`XmpFile::open_file`] reports an error of type
+    /// [`XmpErrorType::FileSignatureMismatch`] for such files.
+    ///
+    /// Signatures are known for common image (JPEG, PNG, GIF, TIFF, PSD,
+    /// WebP, JPEG XL, CR3), document (PDF, PostScript), audio (MP3, WAV,
+    /// FLAC, Ogg), and video (AVI, MP4 and related ISO media formats)
+    /// extensions. Files with other extensions are not checked.
+    pub fn check_file_signature(mut self) -> Self {
+        self.check_file_signature = true;
+        self
+    }
+
+    /// Choose whether the XMP may be written to an SVG file that has no
+    /// `<metadata>` element.
+    ///
+    /// The XMP in an SVG file is stored in the `<metadata>` element. By
+    /// default, the element is created if the file doesn't have one. If
+    /// `create` is `false`, such files are left untouched:
+    /// [`XmpFile::can_put_xmp`] returns `false` for them, and
+    /// [`XmpFile::put_xmp`] reports an error of type
+    /// [`XmpErrorType::SvgMetadataElementMissing`]. This has no effect on
+    /// other file formats.
+    pub fn create_svg_metadata(mut self, create: bool) -> Self {
+        self.keep_svg_without_metadata = !create;
+        self
+    }
+
+    /// Choose whether ID3 tags in MP3 files are reconciled with the XMP.
+    ///
+    /// By default, the C++ XMP Toolkit's MP3 handler reads the XMP from the
+    /// `PRIV` frame of the file's ID3v2 tag and reconciles it with the basic
+    /// ID3 frames: for example, the title (`TIT2`) with `dc:title`, the
+    /// artist (`TPE1`) with `xmpDM:artist`, and the album (`TALB`) with
+    /// `xmpDM:album`. Values from these frames appear in [`XmpFile::xmp`],
+    /// and [`XmpFile::put_xmp`] updates the frames along with the XMP.
+    ///
+    /// If `reconcile` is `false`, MP3 files are opened as with
+    /// [`OpenFileOptions::only_xmp`], so that the XMP is read from and
+    /// written to the `PRIV` frame as is. This has no effect on other file
+    /// formats.
+    pub fn reconcile_id3(mut self, reconcile: bool) -> Self {
+        self.skip_id3_reconciliation = !reconcile;
+        self
+    }
+
+    pub(crate) fn reconciles_id3(&self) -> bool {
+        !self.skip_id3_reconciliation
+    }
+
+    /// Returns the option flags to pass to the C++ XMP Toolkit for a file
+    /// that starts with `header`.
+    pub(crate) fn c_options(&self, header: &[u8]) -> u32 {
+        let only_xmp = (!self.reconciles_id3() && xmp_mp3::is_mp3(header))
+            || (!self.reconciles_quicktime() && xmp_mpeg4::is_mpeg4(header));
+
+        if only_xmp {
+            self.options | Self::ONLY_XMP
+        } else {
+            self.options
+        }
+    }
+
+    /// Add properties derived from the Vorbis comments of FLAC and Ogg
+    /// files to the XMP.
+    ///
+    /// The following comments are mapped to Dublin Core properties when
+    /// the XMP is read. The XMP takes precedence: a comment is only used if
+    /// the XMP doesn't have the corresponding property.
+    ///
+    /// | Vorbis comment | XMP property |
+    /// |---|---|
+    /// | `TITLE` | `dc:title` |
+    /// | `ARTIST` | `dc:creator` |
+    /// | `DESCRIPTION` | `dc:description` |
+    /// | `COPYRIGHT` | `dc:rights` |
+    /// | `DATE` | `dc:date` |
+    /// | `ORGANIZATION` | `dc:publisher` |
+    ///
+    /// The mapped properties are only written to the file if the XMP is
+    /// updated; the Vorbis comments themselves are never modified. This
+    /// has no effect on other file formats.
+    pub fn import_vorbis_comments(mut self) -> Self {
+        self.import_vorbis_comments = true;
+        self
+    }
+
+    pub(crate) fn imports_vorbis_comments(&self) -> bool {
+        self.import_vorbis_comments
+    }
+
+    /// Handle WAV files in Rust and add properties derived from their
+    /// Broadcast Wave (`bext`) and `iXML` chunks to the XMP.
+    ///
+    /// The following production fields are mapped to XMP Dynamic Media
+    /// properties when the XMP is read. The XMP takes precedence: a field
+    /// is only used if the XMP doesn't have the corresponding property,
+    /// and an `iXML` field is preferred over a `bext` field.
+    ///
+    /// | Chunk field | XMP property |
+    /// |---|---|
+    /// | `iXML` `SCENE` | `xmpDM:scene` |
+    /// | `iXML` `TAKE` | `xmpDM:takeNumber` |
+    /// | `iXML` `TAPE` | `xmpDM:tapeName` |
+    /// | `iXML` `NOTE` | `xmpDM:logComment` |
+    /// | `bext` `Description` | `xmpDM:logComment` |
+    /// | `bext` `Originator` | `xmpDM:engineer` |
+    /// | `bext` `OriginationDate` and `OriginationTime` | `xmpDM:shotDate` |
+    ///
+    /// The XMP is read from and written to the `_PMX` chunk, and the other
+    /// chunks are never modified. The mapped properties are only written to
+    /// the file if the XMP is updated. Unlike the C++ XMP Toolkit's WAV
+    /// handler, which is used without this option, the `INFO`, `cart`, and
+    /// `DISP` chunks are not reconciled with the XMP. This has no effect on
+    /// other file formats.
+    pub fn import_bwf_metadata(mut self) -> Self {
+        self.import_bwf_metadata = true;
+        self
+    }
+
+    pub(crate) fn imports_bwf_metadata(&self) -> bool {
+        self.import_bwf_metadata
+    }
+
+    /// Reconcile QuickTime metadata with the XMP of MPEG-4 and QuickTime
+    /// files (default: `true`).
+    ///
+    /// By default, the C++ XMP Toolkit reconciles the `udta` and `keys`
+    /// metadata of these files with the XMP: for example, the title
+    /// (`©nam`) with `dc:title` and the copyright (`cprt`) with
+    /// `dc:rights`. It also reads `xmpDM:startTimecode` from the first
+    /// timecode track.
+    ///
+    /// If `reconcile` is `false`, these files are opened as with
+    /// [`OpenFileOptions::only_xmp`], so that the XMP is read from and
+    /// written to the XMP box as is, and no other metadata is read or
+    /// modified, except for the timecode track (see
+    /// [`OpenFileOptions::quicktime_timecode`]). This has no effect on
+    /// other file formats.
+    pub fn reconcile_quicktime(mut self, reconcile: bool) -> Self {
+        self.skip_quicktime_reconciliation = !reconcile;
+        self
+    }
+
+    pub(crate) fn reconciles_quicktime(&self) -> bool {
+        !self.skip_quicktime_reconciliation
+    }
+
+    /// Derive `xmpDM:startTimecode` from the timecode (`tmcd`) track of
+    /// MPEG-4 and QuickTime files (default: `true`).
+    ///
+    /// The timecode of the first sample of the first timecode track is
+    /// added to the XMP when it is read, unless the XMP already has an
+    /// `xmpDM:startTimecode`. The timecode is only written to the file if
+    /// the XMP is updated; the timecode track itself is never modified.
+    ///
+    /// This only applies if QuickTime reconciliation is turned off with
+    /// [`OpenFileOptions::reconcile_quicktime`]; the C++ XMP Toolkit always
+    /// derives the timecode. This has no effect on other file formats.
+    pub fn quicktime_timecode(mut self, import: bool) -> Self {
+        self.skip_quicktime_timecode = !import;
+        self
+    }
+
+    /// Returns `true` if `xmpDM:startTimecode` is to be read from the
+    /// timecode track of a file that starts with `header` in Rust, because
+    /// the C++ XMP Toolkit won't.
+    pub(crate) fn reads_timecode_track(&self, header: &[u8]) -> bool {
+        !self.reconciles_quicktime() && !self.skip_quicktime_timecode && xmp_mpeg4::is_mpeg4(header)
+    }
+
+    pub(crate) fn opens_for_update(&self) -> bool {
+        self.options & Self::FOR_UPDATE != 0
+    }
+
+    /// Returns `true` if SVG files without a `<metadata>` element must not
+    /// be updated.
+    fn keeps_svg_without_metadata(&self) -> bool {
+        self.keep_svg_without_metadata && self.opens_for_update()
+    }
+
+    fn check_before_open(&self, path: &Path) -> XmpResult<()> {
+        if self.max_file_size.is_some() {
+            // If the size can't be determined, let the C++ XMP Toolkit
+            // report whatever is wrong with the file.
+            if let Ok(metadata) = fs::metadata(path) {
+                self.check_size(metadata.len())?;
+            }
+        }
+
+        if self.check_file_signature {
+            check_file_signature(path)?;
+        }
+
+        Ok(())
+    }
+
+    fn check_stream_before_open(&self, stream: &mut dyn ffi::StreamIo) -> XmpResult<()> {
+        if self.max_file_size.is_none() {
+            return Ok(());
+        }
+
+        let len = stream
+            .seek(SeekFrom::End(0))
+            .and_then(|len| stream.seek(SeekFrom::Start(0)).map(|_| len))
+            .map_err(|e| XmpError {
+                error_type: XmpErrorType::ReadError,
+                debug_message: e.to_string(),
+            })?;
+
+        self.check_size(len)
+    }
+
+    fn check_size(&self, len: u64) -> XmpResult<()> {
+        match self.max_file_size {
+            Some(max_file_size) if len > max_file_size => Err(XmpError {
+                error_type: XmpErrorType::FileTooLarge,
+                debug_message: format!("File is {len} bytes; maximum is {max_file_size} bytes"),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Returns `true` if `updated` has a different length than `original` or
+/// differs from it anywhere outside `packet`.
+fn changed_outside_packet(
+    mut original: impl Read,
+    mut updated: impl Read,
+    packet: Option<&Range<u64>>,
+) -> std::io::Result<bool> {
+    const CHUNK_LEN: usize = 64 * 1024;
+
+    let mut original_chunk = vec![0; CHUNK_LEN];
+    let mut updated_chunk = vec![0; CHUNK_LEN];
+    let mut pos = 0;
+
+    loop {
+        let len = read_chunk(&mut original, &mut original_chunk)?;
+        if read_chunk(&mut updated, &mut updated_chunk)? != len {
+            return Ok(true);
+        }
+
+        if len == 0 {
+            return Ok(false);
+        }
+
+        // The part of the packet within this chunk, relative to its start.
+        let end = pos + len as u64;
+        let (skip_start, skip_end) = match packet {
+            Some(packet) => (
+                (packet.start.clamp(pos, end) - pos) as usize,
+                (packet.end.clamp(pos, end) - pos) as usize,
+            ),
+            None => (0, 0),
+        };
+
+        if original_chunk[..skip_start] != updated_chunk[..skip_start]
+            || original_chunk[skip_end..len] != updated_chunk[skip_end..len]
+        {
+            return Ok(true);
+        }
+
+        pos = end;
+    }
+}
+
+/// Fills `chunk` from `reader`, unless the end is reached first. Returns
+/// the number of bytes read.
+fn read_chunk(reader: &mut impl Read, chunk: &mut [u8]) -> std::io::Result<usize> {
+    let mut len = 0;
+
+    while len < chunk.len() {
+        match reader.read(&mut chunk[len..])? {
+            0 => break,
+            n => len += n,
+        }
+    }
+
+    Ok(len)
+}
+
+fn not_in_place() -> XmpError {
+    XmpError {
+        error_type: XmpErrorType::XmpUpdateNotInPlace,
+        debug_message: "Update would change the file outside its XMP packet; file left unchanged"
+            .to_owned(),
+    }
+}
+
+/// Reports `err`, returned when opening the file at `path`, as an error of
+/// type `FileSharingViolation` if the file is open in another process that
+/// doesn't share it.
+///
+/// Neither the C++ XMP Toolkit nor `std::io::ErrorKind` tell sharing
+/// violations apart from other failures, so this tries to open the file
+/// again and looks at the Windows error code. A sharing violation may be
+/// reported as a permission error, so those are checked as well.
+fn sharing_violation(err: XmpError, path: &Path, for_update: bool) -> XmpError {
+    if err.category() != XmpErrorCategory::Transient
+        && err.error_type != XmpErrorType::FilePermission
+    {
+        return err;
+    }
+
+    match sharing_violation_on_open(path, for_update) {
+        Some(io_err) => XmpError {
+            error_type: XmpErrorType::FileSharingViolation,
+            debug_message: format!("{} ({io_err})", err.debug_message),
+        },
+        None => err,
+    }
+}
+
+/// Opens the file at `path` and returns the error if that fails because
+/// another process has it open without sharing it. This only happens on
+/// Windows.
+#[cfg(windows)]
+fn sharing_violation_on_open(path: &Path, for_update: bool) -> Option<io::Error> {
+    const ERROR_SHARING_VIOLATION: i32 = 32;
+    const ERROR_LOCK_VIOLATION: i32 = 33;
+
+    fs::OpenOptions::new()
+        .read(true)
+        .write(for_update)
+        .open(path)
+        .err()
+        .filter(|err| {
+            matches!(
+                err.raw_os_error(),
+                Some(ERROR_SHARING_VIOLATION | ERROR_LOCK_VIOLATION)
+            )
+        })
+}
+
+#[cfg(not(windows))]
+fn sharing_violation_on_open(_path: &Path, _for_update: bool) -> Option<io::Error> {
+    None
+}
+
+/// Returns the legacy metadata segments of `file` that
+/// `OpenFileOptions::keep_legacy_metadata` restores when it is closed.
+fn legacy_segments_to_keep(file: &[u8]) -> XmpResult<Vec<Vec<u8>>> {
+    if !xmp_jpeg::is_jpeg(file) {
+        return Err(XmpError {
+            error_type: XmpErrorType::Unimplemented,
+            debug_message: "Legacy metadata can only be kept unchanged in JPEG files".to_owned(),
+        });
+    }
+
+    xmp_jpeg::legacy_segments(file)
+}
+
+/// Returns `true` if `svg` contains a `<metadata>` element, with or
+/// without a namespace prefix.
+/// Returns the first bytes of `stream` (up to the length that file format
+/// handlers inspect) and rewinds it.
+fn read_stream_header<R: Read + Seek>(stream: &mut R) -> XmpResult<Vec<u8>> {
+    let mut header = Vec::with_capacity(xmp_file_handler::HEADER_LEN);
+
+    let result = stream
+        .by_ref()
+        .take(xmp_file_handler::HEADER_LEN as u64)
+        .read_to_end(&mut header);
+
+    result
+        .and_then(|_| stream.rewind())
+        .map_err(|err| XmpError {
+            error_type: XmpErrorType::ReadError,
+            debug_message: err.to_string(),
+        })?;
+
+    Ok(header)
+}
+
+// Size of the buffer used by `OpenFileOptions::buffered_read`.
+const BUFFERED_READ_CAPACITY: usize = 4 << 20;
+
+/// A file read through a large buffer, for `OpenFileOptions::buffered_read`.
+///
+/// `BufReader` discards its buffer on every seek, and the C++ file handlers
+/// seek before most reads. This keeps the buffer when the new position is
+/// within it.
+struct BufferedFile {
+    reader: BufReader<File>,
+    pos: u64,
+    len: u64,
+}
+
+impl BufferedFile {
+    fn open(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+
+        Ok(Self {
+            reader: BufReader::with_capacity(BUFFERED_READ_CAPACITY, file),
+            pos: 0,
+            len,
+        })
+    }
+}
+
+impl Read for BufferedFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.reader.read(buf)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl Seek for BufferedFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+        };
+
+        let Some(target) = target else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            ));
+        };
+
+        self.reader
+            .seek_relative(target.wrapping_sub(self.pos) as i64)?;
+        self.pos = target;
+        Ok(target)
+    }
+}
+
+/// Returns the start of the timecode track of the MPEG-4 or QuickTime file
+/// `bytes`, if any.
+fn read_start_timecode(bytes: &[u8]) -> Option<Timecode> {
+    xmp_mpeg4::start_timecode(&mut io::Cursor::new(bytes))
+        .ok()
+        .flatten()
+}
+
+/// Adds `timecode` to `meta` as `xmpDM:startTimecode`, unless it already
+/// has one. Creates the XMP if the file has none.
+fn add_start_timecode(meta: Option<XmpMeta>, timecode: &Timecode) -> Option<XmpMeta> {
+    let mut meta = match meta {
+        Some(meta) => meta,
+        None => XmpMeta::new().ok()?,
+    };
+
+    if !meta.contains_property(xmp_ns::DYNAMIC_MEDIA, "startTimecode") {
+        xmp_dm::set_start_timecode(&mut meta, timecode).ok()?;
+    }
+
+    Some(meta)
+}
+
+fn has_svg_metadata_element(svg: &[u8]) -> bool {
+    svg.split(|&b| b == b'<').skip(1).any(|tag| {
+        let name_len = tag
+            .iter()
+            .position(|&b| b.is_ascii_whitespace() || b == b'>' || b == b'/')
+            .unwrap_or(tag.len());
+
+        let name = &tag[..name_len];
+        let local_name = match name.iter().position(|&b| b == b':') {
+            Some(colon) => &name[colon + 1..],
+            None => name,
+        };
+
+        local_name == b"metadata"
+    })
+}
+
+/// Number of bytes read from the start of the file for signature checks.
+///
+/// PDF permits the `%PDF-` header anywhere in the first 1024 bytes.
+const SIGNATURE_LEN: usize = 1024;
+
+fn check_file_signature(path: &Path) -> XmpResult<()> {
+    let Some(ext) = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+    else {
+        return Ok(());
+    };
+
+    let Ok(file) = File::open(path) else {
+        return Ok(());
+    };
+
+    let mut header = Vec::with_capacity(SIGNATURE_LEN);
+    file.take(SIGNATURE_LEN as u64)
+        .read_to_end(&mut header)
+        .map_err(|e| XmpError {
+            error_type: XmpErrorType::ReadError,
+            debug_message: e.to_string(),
+        })?;
+
+    let h = header.as_slice();
+    let riff = |form: &[u8]| h.starts_with(b"RIFF") && h.get(8..12) == Some(form);
+    let iso_media = || {
+        matches!(
+            h.get(4..8),
+            Some(b"ftyp" | b"moov" | b"mdat" | b"free" | b"skip" | b"wide" | b"pnot")
+        )
+    };
+
+    let matches = match ext.as_str() {
+        "jpg" | "jpeg" | "jpe" => h.starts_with(&[0xff, 0xd8, 0xff]),
+        "png" => h.starts_with(b"\x89PNG\r\n\x1a\n"),
+        "gif" => h.starts_with(b"GIF87a") || h.starts_with(b"GIF89a"),
+        "tif" | "tiff" | "dng" => [b"II*\0", b"MM\0*", b"II+\0", b"MM\0+"]
+            .iter()
+            .any(|sig| h.starts_with(*sig)),
+        "psd" | "psb" => h.starts_with(b"8BPS"),
+        "webp" => riff(b"WEBP"),
+        "jxl" => h.starts_with(b"\0\0\0\x0cJXL \r\n\x87\n") || h.starts_with(&[0xff, 0x0a]),
+        "wav" => riff(b"WAVE"),
+        "avi" => riff(b"AVI "),
+        "pdf" | "ai" => h.windows(5).any(|w| w == b"%PDF-"),
+        "eps" | "ps" => h.starts_with(b"%!PS") || h.starts_with(&[0xc5, 0xd0, 0xd3, 0xc6]),
+        "mp3" => h.starts_with(b"ID3") || (h.len() >= 2 && h[0] == 0xff && h[1] & 0xe0 == 0xe0),
+        "flac" => h.starts_with(b"fLaC"),
+        "ogg" | "oga" | "ogv" => h.starts_with(b"OggS"),
+        "mp4" | "m4a" | "m4v" | "mov" | "3gp" | "3g2" | "heic" | "heif" | "avif" | "cr3" => {
+            iso_media()
+        }
+        _ => true,
+    };
+
+    if matches {
+        Ok(())
+    } else {
+        Err(XmpError {
+            error_type: XmpErrorType::FileSignatureMismatch,
+            debug_message: format!("File content does not look like a .{ext} file"),
+        })
+    }
+}
+
+/// Returns `true` if `bytes` is a single XMP packet with a complete
+/// wrapper, from its header to its trailer.
+fn is_wrapped_packet(bytes: &[u8]) -> bool {
+    matches!(
+        xmp_packet::scan(bytes).as_slice(),
+        [scanned] if scanned.range == (0..bytes.len()) && scanned.writable.is_some()
+    )
 }
 
 fn path_to_cstr(path: &Path) -> Option<CString> {
@@ -0,0 +1,232 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    CancellationToken, OpenFileOptions, XmpError, XmpErrorType, XmpFile, XmpMeta, XmpResult,
+};
+
+/// An asynchronous wrapper for [`XmpFile`].
+///
+/// The C++ XMP Toolkit does blocking file I/O. Each method of this struct
+/// runs the corresponding [`XmpFile`] method on Tokio's blocking thread
+/// pool (via [`tokio::task::spawn_blocking`]) and returns a future for the
+/// result, so it can be used from async code without stalling the
+/// executor. The methods must be called from within a Tokio runtime.
+///
+/// Operations on the same file run one at a time. The struct can be
+/// cloned cheaply; clones refer to the same file.
+///
+/// ## Cancellation
+///
+/// Dropping one of the returned futures cancels the operation. If it
+/// hasn't started yet (for example, because another operation on the same
+/// file is still running), it is skipped. If it has, the C++ XMP Toolkit
+/// stops it at the next opportunity, as if the [`CancellationToken`] set
+/// with [`XmpFile::set_cancellation_token`] had been cancelled. In either
+/// case, the file remains in a consistent state and can be used for
+/// further operations.
+///
+/// Each operation installs its own cancellation token on the wrapped
+/// [`XmpFile`] while it runs, and then restores the token the file had
+/// before. A token set on the file before it was wrapped therefore has no
+/// effect on operations started through this struct.
+///
+/// This struct is available only when crate feature `async` is enabled.
+///
+/// ## Example
+///
+/// ```no_run
+/// # use xmp_toolkit::{xmp_ns, OpenFileOptions, XmpFileAsync};
+/// # async fn example() -> xmp_toolkit::XmpResult<()> {
+/// let f = XmpFileAsync::open("image.jpg", OpenFileOptions::default().for_update()).await?;
+///
+/// if let Some(mut xmp) = f.xmp().await? {
+///     xmp.set_property(xmp_ns::XMP, "Label", &"Reviewed".into())?;
+///     f.put_xmp(&xmp).await?;
+/// }
+///
+/// f.close().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct XmpFileAsync {
+    file: Arc<Mutex<SendFile>>,
+}
+
+// `XmpFile` isn't `Send` because it holds a pointer to a C++ object. The
+// C++ object isn't tied to the thread that created it, and access to it is
+// serialized by the `Mutex` in `XmpFileAsync`.
+struct SendFile(XmpFile);
+
+unsafe impl Send for SendFile {}
+
+impl XmpFileAsync {
+    /// Opens a file for the requested forms of metadata access.
+    ///
+    /// See [`XmpFile::open_file`].
+    pub async fn open<P: AsRef<Path>>(path: P, flags: OpenFileOptions) -> XmpResult<Self> {
+        let path = path.as_ref().to_path_buf();
+
+        let file = run_blocking(move |token| {
+            let mut f = XmpFile::new()?;
+            with_token(&mut f, token, |f| f.open_file(path, flags))?;
+            Ok(SendFile(f))
+        })
+        .await?;
+
+        Ok(Self {
+            file: Arc::new(Mutex::new(file)),
+        })
+    }
+
+    /// Retrieves the XMP metadata from the open file.
+    ///
+    /// See [`XmpFile::xmp`].
+    pub async fn xmp(&self) -> XmpResult<Option<XmpMeta>> {
+        self.with_file(|f| Ok(f.xmp())).await
+    }
+
+    /// Updates the XMP metadata in this object without writing out the
+    /// file.
+    ///
+    /// See [`XmpFile::put_xmp`].
+    pub async fn put_xmp(&self, meta: &XmpMeta) -> XmpResult<()> {
+        let meta = meta.clone();
+        self.with_file(move |f| f.put_xmp(&meta)).await
+    }
+
+    /// Writes any pending update and closes the file.
+    ///
+    /// See [`XmpFile::close`].
+    pub async fn close(&self) -> XmpResult<()> {
+        self.with_file(XmpFile::close).await
+    }
+
+    /// Runs `op` on the blocking thread pool with exclusive access to the
+    /// file.
+    async fn with_file<T, F>(&self, op: F) -> XmpResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut XmpFile) -> XmpResult<T> + Send + 'static,
+    {
+        let file = self.file.clone();
+        run_blocking(move |token| {
+            let mut guard = file.lock().map_err(|_| XmpError {
+                error_type: XmpErrorType::InternalFailure,
+                debug_message: "A previous operation on this file panicked".to_owned(),
+            })?;
+
+            // The future may have been dropped while waiting for another
+            // operation to finish.
+            if token.is_cancelled() {
+                return Err(aborted());
+            }
+
+            with_token(&mut guard.0, token, op)
+        })
+        .await
+    }
+}
+
+impl From<XmpFile> for XmpFileAsync {
+    /// Wraps a file that was opened (or created) synchronously.
+    fn from(f: XmpFile) -> Self {
+        Self {
+            file: Arc::new(Mutex::new(SendFile(f))),
+        }
+    }
+}
+
+/// Runs `op` on `f` with `token` as its cancellation token, then restores
+/// the token `f` had before.
+fn with_token<T>(
+    f: &mut XmpFile,
+    token: &CancellationToken,
+    op: impl FnOnce(&mut XmpFile) -> XmpResult<T>,
+) -> XmpResult<T> {
+    let previous = f.cancellation_token().cloned();
+    f.set_cancellation_token(token)?;
+
+    let result = op(f);
+
+    let restored = match &previous {
+        Some(previous) => f.set_cancellation_token(previous),
+        None => f.clear_cancellation_token(),
+    };
+
+    result.and_then(|value| restored.map(|()| value))
+}
+
+/// Runs `op` via [`tokio::task::spawn_blocking`].
+///
+/// `op` is passed a token that is cancelled if the returned future is
+/// dropped. The operation is skipped if the token is already cancelled
+/// when it starts.
+pub(crate) async fn run_blocking<T, F>(op: F) -> XmpResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&CancellationToken) -> XmpResult<T> + Send + 'static,
+{
+    let mut cancel = CancelOnDrop {
+        token: CancellationToken::new(),
+        armed: true,
+    };
+    let token = cancel.token.clone();
+
+    let result = tokio::task::spawn_blocking(move || {
+        if token.is_cancelled() {
+            return Err(aborted());
+        }
+
+        op(&token)
+    })
+    .await;
+
+    cancel.armed = false;
+
+    match result {
+        Ok(result) => result,
+        Err(err) if err.is_cancelled() => Err(aborted()),
+        Err(_) => Err(XmpError {
+            error_type: XmpErrorType::InternalFailure,
+            debug_message: "XMP file operation panicked".to_owned(),
+        }),
+    }
+}
+
+/// Cancels `token` when dropped while `armed`.
+struct CancelOnDrop {
+    token: CancellationToken,
+    armed: bool,
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if self.armed {
+            self.token.cancel();
+        }
+    }
+}
+
+fn aborted() -> XmpError {
+    XmpError {
+        error_type: XmpErrorType::UserAbort,
+        debug_message: "Operation was cancelled".to_owned(),
+    }
+}
@@ -0,0 +1,286 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::{
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+#[cfg(feature = "audit")]
+use crate::xmp_audit::AuditLog;
+use crate::{OpenFileOptions, XmpError, XmpFile, XmpMeta, XmpResult};
+
+/// Updates the XMP of many files with the same edit.
+///
+/// Each file is opened for update, the edit is applied to its XMP (or to
+/// empty metadata if the file has none), and the file is written back if
+/// the XMP changed. Files are processed on several threads at once; see
+/// [`XmpFileBatch::parallelism`].
+///
+/// A failure affects only the file where it happens: the remaining files
+/// are still processed, and every file's outcome is listed, in the order
+/// in which the files were given, in the returned [`FileBatchReport`].
+///
+/// ## Example
+///
+/// ```no_run
+/// # use xmp_toolkit::{xmp_ns, OpenFileOptions, XmpFileBatch};
+/// let report = XmpFileBatch::new(["a.jpg", "b.jpg", "c.jpg"])
+///     .options(OpenFileOptions::default().update_safely())
+///     .parallelism(2)
+///     .run(|_path, meta| meta.set_property(xmp_ns::XMP, "Label", &"Reviewed".into()));
+///
+/// for (path, err) in report.errors() {
+///     eprintln!("{}: {err}", path.display());
+/// }
+/// ```
+pub struct XmpFileBatch {
+    paths: Vec<PathBuf>,
+    flags: OpenFileOptions,
+    parallelism: usize,
+
+    #[cfg(feature = "audit")]
+    audit_log: Option<AuditLog>,
+}
+
+impl XmpFileBatch {
+    /// Creates a batch for the files at `paths`.
+    ///
+    /// By default, files are opened with [`OpenFileOptions::default`] and
+    /// processed on as many threads as
+    /// [`std::thread::available_parallelism`] suggests.
+    pub fn new<I, P>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        Self {
+            paths: paths
+                .into_iter()
+                .map(|path| path.as_ref().to_path_buf())
+                .collect(),
+            flags: OpenFileOptions::default(),
+            parallelism: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            #[cfg(feature = "audit")]
+            audit_log: None,
+        }
+    }
+
+    /// Sets the options with which each file is opened.
+    ///
+    /// [`OpenFileOptions::for_update`] is always added.
+    pub fn options(mut self, flags: OpenFileOptions) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Sets the maximum number of files that are processed at once.
+    ///
+    /// A value of 0 is treated as 1, which processes the files one after
+    /// the other on the calling thread.
+    pub fn parallelism(mut self, threads: usize) -> Self {
+        self.parallelism = threads.max(1);
+        self
+    }
+
+    /// Records each file that is written in `log`.
+    ///
+    /// The log is attached to each file as by [`XmpFile::set_audit_log`],
+    /// so one entry is appended for every file whose outcome is
+    /// [`FileBatchOutcome::Updated`]. An error writing that entry is
+    /// reported for the file.
+    ///
+    /// This function is available only when crate feature `audit` is
+    /// enabled.
+    #[cfg(feature = "audit")]
+    pub fn audit_log(mut self, log: &AuditLog) -> Self {
+        self.audit_log = Some(log.clone());
+        self
+    }
+
+    /// Applies `edit` to the XMP of each file and writes back the files
+    /// whose XMP changed.
+    ///
+    /// `edit` receives the path of the file and its XMP. If it returns an
+    /// error, the file is closed without being updated and the error is
+    /// reported for that file.
+    pub fn run<F>(&self, edit: F) -> FileBatchReport
+    where
+        F: Fn(&Path, &mut XmpMeta) -> XmpResult<()> + Sync,
+    {
+        let threads = self.parallelism.min(self.paths.len());
+
+        if threads <= 1 {
+            return FileBatchReport {
+                files: self
+                    .paths
+                    .iter()
+                    .map(|path| self.update_file(path, &edit))
+                    .collect(),
+            };
+        }
+
+        let next = AtomicUsize::new(0);
+        let results: Mutex<Vec<Option<FileBatchEntry>>> =
+            Mutex::new((0..self.paths.len()).map(|_| None).collect());
+
+        thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(path) = self.paths.get(index) else {
+                        break;
+                    };
+
+                    let entry = self.update_file(path, &edit);
+                    results.lock().unwrap_or_else(|e| e.into_inner())[index] = Some(entry);
+                });
+            }
+        });
+
+        FileBatchReport {
+            files: results
+                .into_inner()
+                .unwrap_or_else(|e| e.into_inner())
+                .into_iter()
+                .flatten()
+                .collect(),
+        }
+    }
+
+    /// Copies every top-level property of `template` into the XMP of each
+    /// file, replacing any existing value, and writes back the files whose
+    /// XMP changed.
+    ///
+    /// Properties of the files that `template` doesn't have are kept.
+    pub fn apply_template(&self, template: &XmpMeta) -> FileBatchReport {
+        let properties = template.top_level_properties();
+
+        self.run(|_path, meta| {
+            for (ns, name) in &properties {
+                meta.delete_property(ns, name)?;
+                template.duplicate_subtree(meta, ns, name, name)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn update_file<F>(&self, path: &Path, edit: &F) -> FileBatchEntry
+    where
+        F: Fn(&Path, &mut XmpMeta) -> XmpResult<()>,
+    {
+        let outcome = (|| {
+            let mut f = XmpFile::new()?;
+
+            #[cfg(feature = "audit")]
+            if let Some(log) = &self.audit_log {
+                f.set_audit_log(log);
+            }
+
+            f.open_file(path, self.flags.clone().for_update())?;
+
+            let original = f.xmp();
+            let mut meta = match &original {
+                Some(meta) => meta.clone(),
+                None => XmpMeta::new()?,
+            };
+
+            if let Err(err) = edit(path, &mut meta) {
+                f.close()?;
+                return Err(err);
+            }
+
+            let changed = match &original {
+                Some(original) => !original.diff(&meta).is_empty(),
+                None => !meta.top_level_properties().is_empty(),
+            };
+
+            if changed {
+                f.put_xmp(&meta)?;
+            }
+
+            f.close()?;
+
+            Ok(if changed {
+                FileBatchOutcome::Updated
+            } else {
+                FileBatchOutcome::Unchanged
+            })
+        })();
+
+        FileBatchEntry {
+            path: path.to_path_buf(),
+            outcome,
+        }
+    }
+}
+
+/// The outcome of [`XmpFileBatch::run`] for each file.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct FileBatchReport {
+    /// One entry per file, in the order in which the files were given to
+    /// [`XmpFileBatch::new`].
+    pub files: Vec<FileBatchEntry>,
+}
+
+impl FileBatchReport {
+    /// Returns `true` if every file was processed without error.
+    pub fn is_ok(&self) -> bool {
+        self.files.iter().all(|file| file.outcome.is_ok())
+    }
+
+    /// Returns the files that failed, with their errors.
+    pub fn errors(&self) -> impl Iterator<Item = (&Path, &XmpError)> {
+        self.files
+            .iter()
+            .filter_map(|file| Some((file.path.as_path(), file.outcome.as_ref().err()?)))
+    }
+
+    /// Returns the number of files that were written.
+    pub fn updated(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|file| matches!(file.outcome, Ok(FileBatchOutcome::Updated)))
+            .count()
+    }
+}
+
+/// The outcome of [`XmpFileBatch::run`] for one file.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct FileBatchEntry {
+    /// The path of the file.
+    pub path: PathBuf,
+
+    /// What happened to the file, or the error that stopped it from being
+    /// updated.
+    pub outcome: XmpResult<FileBatchOutcome>,
+}
+
+/// What happened to a file that was processed without error by
+/// [`XmpFileBatch::run`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum FileBatchOutcome {
+    /// The XMP changed and the file was written.
+    Updated,
+
+    /// The XMP didn't change, so the file was left untouched.
+    Unchanged,
+}
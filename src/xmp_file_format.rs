@@ -0,0 +1,259 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::{path::Path, sync::RwLock};
+
+use num_enum::TryFromPrimitive;
+
+/// Identifies a file format that [`XmpFile`] can recognize.
+///
+/// Most values correspond to one of the `XMP_FileFormat` constants in the
+/// C++ SDK (for example, `kXMP_JPEGFile`), which are four-character codes.
+/// Formats that the C++ XMP Toolkit doesn't handle, such as WebP, JPEG XL,
+/// CR3, FLAC, Ogg, and MXF, are handled by this crate instead; they use
+/// four-character codes of the same kind. `FileFormat::try_from` converts
+/// such a code back to a value of this type.
+///
+/// [`XmpFile`]: crate::XmpFile
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, TryFromPrimitive)]
+#[non_exhaustive]
+#[repr(u32)]
+pub enum FileFormat {
+    /// The format is not known. The C++ XMP Toolkit will determine it
+    /// from the file's content.
+    #[default]
+    Unknown = u32::from_be_bytes(*b"    "),
+
+    /// Adobe Portable Document Format.
+    Pdf = u32::from_be_bytes(*b"PDF "),
+
+    /// PostScript.
+    PostScript = u32::from_be_bytes(*b"PS  "),
+
+    /// Encapsulated PostScript.
+    Eps = u32::from_be_bytes(*b"EPS "),
+
+    /// JPEG image.
+    Jpeg = u32::from_be_bytes(*b"JPEG"),
+
+    /// JPEG 2000 image.
+    Jpeg2000 = u32::from_be_bytes(*b"JPX "),
+
+    /// JPEG XL image.
+    ///
+    /// The C++ XMP Toolkit doesn't handle this format; [`XmpFile`] uses
+    /// its own handler for it.
+    ///
+    /// [`XmpFile`]: crate::XmpFile
+    JpegXl = u32::from_be_bytes(*b"JXL "),
+
+    /// TIFF image.
+    Tiff = u32::from_be_bytes(*b"TIFF"),
+
+    /// GIF image.
+    Gif = u32::from_be_bytes(*b"GIF "),
+
+    /// PNG image.
+    Png = u32::from_be_bytes(*b"PNG "),
+
+    /// SVG image.
+    Svg = u32::from_be_bytes(*b"SVG "),
+
+    /// HEIF image.
+    Heif = u32::from_be_bytes(*b"HEIF"),
+
+    /// WebP image.
+    ///
+    /// The C++ XMP Toolkit doesn't handle this format; [`XmpFile`] uses
+    /// its own handler for it.
+    ///
+    /// [`XmpFile`]: crate::XmpFile
+    WebP = u32::from_be_bytes(*b"WEBP"),
+
+    /// Canon raw image (CR3).
+    ///
+    /// The C++ XMP Toolkit doesn't handle this format; [`XmpFile`] uses
+    /// its own handler for it. The embedded XMP can be read and updated;
+    /// use [`Sidecar`] to keep the XMP in a sidecar file instead.
+    ///
+    /// [`XmpFile`]: crate::XmpFile
+    /// [`Sidecar`]: crate::xmp_sidecar::Sidecar
+    Cr3 = u32::from_be_bytes(*b"CR3 "),
+
+    /// Adobe Photoshop document.
+    Photoshop = u32::from_be_bytes(*b"PSD "),
+
+    /// Adobe Illustrator document.
+    Illustrator = u32::from_be_bytes(*b"AI  "),
+
+    /// Adobe InDesign document.
+    InDesign = u32::from_be_bytes(*b"INDD"),
+
+    /// Universal Container Format (such as an InDesign package).
+    Ucf = u32::from_be_bytes(*b"UCF "),
+
+    /// Adobe Flash (SWF) file.
+    Swf = u32::from_be_bytes(*b"SWF "),
+
+    /// Flash video.
+    Flv = u32::from_be_bytes(*b"FLV "),
+
+    /// QuickTime movie.
+    Mov = u32::from_be_bytes(*b"MOV "),
+
+    /// AVI video.
+    Avi = u32::from_be_bytes(*b"AVI "),
+
+    /// MPEG video.
+    Mpeg = u32::from_be_bytes(*b"MPEG"),
+
+    /// MPEG-2 video.
+    Mpeg2 = u32::from_be_bytes(*b"MP2 "),
+
+    /// MPEG-4 video or audio.
+    Mpeg4 = u32::from_be_bytes(*b"MP4 "),
+
+    /// Material Exchange Format video.
+    ///
+    /// The C++ XMP Toolkit only handles MXF files that are part of a
+    /// folder-based format, such as [`FileFormat::P2`]; [`XmpFile`] uses
+    /// its own handler for other MXF files.
+    ///
+    /// [`XmpFile`]: crate::XmpFile
+    Mxf = u32::from_be_bytes(*b"MXF "),
+
+    /// Panasonic P2 video clip (folder-based).
+    P2 = u32::from_be_bytes(*b"P2  "),
+
+    /// Sony XDCAM video clip in the file access mode folder structure.
+    XdcamFam = u32::from_be_bytes(*b"XDCF"),
+
+    /// Sony XDCAM video clip in the SxS access mode folder structure.
+    XdcamSam = u32::from_be_bytes(*b"XDCS"),
+
+    /// Sony XDCAM EX video clip (folder-based).
+    XdcamEx = u32::from_be_bytes(*b"XDCX"),
+
+    /// Sony HDV video clip (folder-based).
+    SonyHdv = u32::from_be_bytes(*b"SHDV"),
+
+    /// AVCHD video clip (folder-based).
+    Avchd = u32::from_be_bytes(*b"AVHD"),
+
+    /// Canon XF video clip (folder-based).
+    CanonXf = u32::from_be_bytes(*b"CNXF"),
+
+    /// Windows Media audio or video.
+    WindowsMedia = u32::from_be_bytes(*b"WMAV"),
+
+    /// WAV audio.
+    Wav = u32::from_be_bytes(*b"WAV "),
+
+    /// AIFF audio.
+    Aiff = u32::from_be_bytes(*b"AIFF"),
+
+    /// MP3 audio.
+    Mp3 = u32::from_be_bytes(*b"MP3 "),
+
+    /// FLAC audio.
+    ///
+    /// The C++ XMP Toolkit doesn't handle this format; [`XmpFile`] uses
+    /// its own handler for it.
+    ///
+    /// [`XmpFile`]: crate::XmpFile
+    Flac = u32::from_be_bytes(*b"FLAC"),
+
+    /// Ogg Vorbis or Ogg Opus audio.
+    ///
+    /// The C++ XMP Toolkit doesn't handle this format; [`XmpFile`] uses
+    /// its own handler for it.
+    ///
+    /// [`XmpFile`]: crate::XmpFile
+    Ogg = u32::from_be_bytes(*b"OGG "),
+
+    /// HTML document.
+    Html = u32::from_be_bytes(*b"HTML"),
+
+    /// XML document.
+    Xml = u32::from_be_bytes(*b"XML "),
+
+    /// Plain text.
+    Text = u32::from_be_bytes(*b"text"),
+}
+
+impl FileFormat {
+    /// Returns `true` for formats whose assets are spread across a folder
+    /// structure, such as the video clips recorded by professional
+    /// cameras.
+    ///
+    /// The C++ XMP Toolkit reads and writes the XMP of such a clip in its
+    /// own files (often a sidecar or an XML file next to the media files),
+    /// given the path of any of the clip's media files.
+    /// [`XmpFile::associated_resources`] lists all of the clip's files.
+    ///
+    /// [`XmpFile::associated_resources`]: crate::XmpFile::associated_resources
+    pub fn is_folder_based(self) -> bool {
+        matches!(
+            self,
+            Self::P2
+                | Self::XdcamFam
+                | Self::XdcamSam
+                | Self::XdcamEx
+                | Self::SonyHdv
+                | Self::Avchd
+                | Self::CanonXf
+        )
+    }
+
+    /// Converts a format code returned by the C++ XMP Toolkit.
+    ///
+    /// Returns `None` for `kXMP_UnknownFile`. Codes that this crate doesn't
+    /// name are mapped to `Some(FileFormat::Unknown)`.
+    pub(crate) fn from_c(code: u32) -> Option<Self> {
+        match Self::try_from(code) {
+            Ok(Self::Unknown) => None,
+            Ok(format) => Some(format),
+            Err(_) => Some(Self::Unknown),
+        }
+    }
+}
+
+// Extensions registered with `XmpFile::register_extension`, in lowercase
+// and without a leading dot.
+static EXTENSION_FORMATS: RwLock<Vec<(String, FileFormat)>> = RwLock::new(Vec::new());
+
+pub(crate) fn register_extension(extension: &str, format: FileFormat) {
+    let extension = extension.trim_start_matches('.').to_lowercase();
+    let mut formats = EXTENSION_FORMATS.write().unwrap_or_else(|e| e.into_inner());
+
+    formats.retain(|(registered, _)| *registered != extension);
+    if format != FileFormat::Unknown {
+        formats.push((extension, format));
+    }
+}
+
+/// Returns the format registered for the extension of `path`, or
+/// `FileFormat::Unknown` if there is none.
+pub(crate) fn registered_format(path: &Path) -> FileFormat {
+    let Some(extension) = path.extension().and_then(|ext| ext.to_str()) else {
+        return FileFormat::Unknown;
+    };
+
+    let extension = extension.to_lowercase();
+    let formats = EXTENSION_FORMATS.read().unwrap_or_else(|e| e.into_inner());
+
+    formats
+        .iter()
+        .find(|(registered, _)| *registered == extension)
+        .map_or(FileFormat::Unknown, |(_, format)| *format)
+}
@@ -0,0 +1,681 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+// File format handlers implemented in Rust, for formats that the C++ XMP
+// Toolkit doesn't support (or, on request, handles in a way that doesn't
+// suit the caller), and those registered by the crate's users through
+// `XmpFileHandler`. `XmpFile` tries these before handing a file to the C++
+// XMP Toolkit.
+
+use std::{
+    ffi::OsString,
+    fs::{self, File},
+    io::{self, Read, Write},
+    ops::Range,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        RwLock,
+    },
+};
+
+use crate::{
+    xmp_cr3::Cr3Handler, xmp_flac::FlacHandler, xmp_jxl::JxlHandler, xmp_mxf::MxfHandler,
+    xmp_ogg::OggHandler, xmp_packet, xmp_wav::WavHandler, xmp_webp::WebPHandler, FileFormat,
+    FormatCapabilities, OpenFileOptions, PacketPadding, PutXmpBlocker, ToStringOptions, XmpError,
+    XmpErrorType, XmpMeta, XmpResult,
+};
+
+/// The number of bytes passed to [`FileHandler::check_format`].
+pub(crate) const HEADER_LEN: usize = 64;
+
+/// A file format handler implemented in Rust, for formats that neither the
+/// C++ XMP Toolkit nor this crate support.
+///
+/// Once registered with [`XmpFile::register_handler`], the handler is used
+/// by [`XmpFile`] for every file it recognizes, before any built-in
+/// handler is considered. [`XmpFile`] reads the whole file into memory,
+/// asks the handler where the XMP packet is, and, when the file was opened
+/// for update and new XMP is put, asks the handler for the updated file,
+/// which is written when the file is closed.
+///
+/// [`XmpFile`]: crate::XmpFile
+/// [`XmpFile::register_handler`]: crate::XmpFile::register_handler
+pub trait XmpFileHandler: Send + Sync {
+    /// The format handled by this handler.
+    ///
+    /// This is what [`XmpFile::check_file_format`] reports for files that
+    /// this handler recognizes. The default, [`FileFormat::Unknown`], is
+    /// appropriate for formats that [`FileFormat`] doesn't name.
+    ///
+    /// [`XmpFile::check_file_format`]: crate::XmpFile::check_file_format
+    fn format(&self) -> FileFormat {
+        FileFormat::Unknown
+    }
+
+    /// Returns `true` if `header`, the first bytes of a file, identifies
+    /// the file as one this handler can process.
+    ///
+    /// `header` holds at least the first 64 bytes of the file, or the
+    /// whole file if it is shorter.
+    fn check_format(&self, header: &[u8]) -> bool;
+
+    /// Returns the location of the XMP packet in `file`, if any.
+    fn read_xmp(&self, file: &[u8]) -> XmpResult<Option<Range<usize>>>;
+
+    /// Returns a copy of `file` with its XMP replaced (or added) by
+    /// `packet`, a serialized XMP packet.
+    fn write_xmp(&self, file: &[u8], packet: &[u8]) -> XmpResult<Vec<u8>>;
+}
+
+// Adapts a registered `XmpFileHandler` to the internal trait.
+struct CustomHandler<H>(H);
+
+impl<H: XmpFileHandler> FileHandler for CustomHandler<H> {
+    fn format(&self) -> FileFormat {
+        self.0.format()
+    }
+
+    fn check_format(&self, header: &[u8]) -> bool {
+        self.0.check_format(header)
+    }
+
+    fn read_xmp(&self, file: &[u8]) -> XmpResult<Option<Range<usize>>> {
+        let xmp = self.0.read_xmp(file)?;
+
+        if let Some(range) = &xmp {
+            if range.start > range.end || range.end > file.len() {
+                return Err(XmpError {
+                    error_type: XmpErrorType::InternalFailure,
+                    debug_message: format!(
+                        "File handler returned packet location {range:?} outside of a {}-byte \
+                         file",
+                        file.len()
+                    ),
+                });
+            }
+        }
+
+        Ok(xmp)
+    }
+
+    fn write_xmp(&self, file: &[u8], packet: &[u8]) -> XmpResult<Vec<u8>> {
+        self.0.write_xmp(file, packet)
+    }
+}
+
+// Handlers registered with `XmpFile::register_handler`, in order of
+// registration. They are leaked so that open files can refer to them
+// without holding the lock.
+static CUSTOM_HANDLERS: RwLock<Vec<&'static dyn FileHandler>> = RwLock::new(Vec::new());
+
+/// Adds `handler` to the handlers that are tried before the built-in ones.
+pub(crate) fn register_handler<H: XmpFileHandler + 'static>(handler: H) {
+    let handler: &'static dyn FileHandler = Box::leak(Box::new(CustomHandler(handler)));
+
+    CUSTOM_HANDLERS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(handler);
+}
+
+pub(crate) trait FileHandler: Sync {
+    /// The format handled by this handler.
+    fn format(&self) -> FileFormat;
+
+    /// Returns `false` if this handler must not be used for a file opened
+    /// with `flags`. Handlers that take over a format from the C++ XMP
+    /// Toolkit use this to do so only on request.
+    fn is_enabled(&self, _flags: &OpenFileOptions) -> bool {
+        true
+    }
+
+    /// Returns `false` if this handler must not be used for the file at
+    /// `path`, even though its content is recognized. Handlers use this to
+    /// leave files to the C++ XMP Toolkit depending on where they are.
+    fn accepts_path(&self, _path: &Path) -> bool {
+        true
+    }
+
+    /// Returns `true` if `header`, the first bytes of a file (up to
+    /// `HEADER_LEN`), identifies the file as one this handler can process.
+    fn check_format(&self, header: &[u8]) -> bool;
+
+    /// Returns the location of the XMP packet in `file`, if any.
+    ///
+    /// Returns `None` for a packet that isn't stored contiguously; such
+    /// handlers must also implement [`FileHandler::read_packet`].
+    fn read_xmp(&self, file: &[u8]) -> XmpResult<Option<Range<usize>>>;
+
+    /// Returns the XMP packet of `file`, if any.
+    fn read_packet(&self, file: &[u8]) -> XmpResult<Option<Vec<u8>>> {
+        Ok(self.read_xmp(file)?.map(|range| file[range].to_vec()))
+    }
+
+    /// Returns `true` if the XMP can only be updated by overwriting the
+    /// existing packet, as with `OpenFileOptions::update_xmp_in_place`.
+    fn updates_in_place_only(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` if the file's legacy (non-XMP) metadata must be
+    /// imported by [`FileHandler::import_legacy`] for a file opened with
+    /// `flags`.
+    fn imports_legacy(&self, _flags: &OpenFileOptions) -> bool {
+        false
+    }
+
+    /// Adds properties derived from the file's legacy (non-XMP) metadata
+    /// to `meta`, without replacing existing properties. Returns `true` if
+    /// any property was added.
+    ///
+    /// This is only called if [`FileHandler::imports_legacy`] returns
+    /// `true`.
+    fn import_legacy(&self, _file: &[u8], _meta: &mut XmpMeta) -> XmpResult<bool> {
+        Ok(false)
+    }
+
+    /// Returns a copy of `file` with its XMP replaced (or added) by
+    /// `packet`.
+    fn write_xmp(&self, file: &[u8], packet: &[u8]) -> XmpResult<Vec<u8>>;
+}
+
+static HANDLERS: &[&dyn FileHandler] = &[
+    &WebPHandler,
+    &Cr3Handler,
+    &JxlHandler,
+    &FlacHandler,
+    &OggHandler,
+    &WavHandler,
+    &MxfHandler,
+];
+
+/// Returns the handler for a file that starts with `header`, if any. If
+/// `format` isn't `Unknown`, only a handler for that format is considered.
+pub(crate) fn handler_for(
+    header: &[u8],
+    format: FileFormat,
+    flags: &OpenFileOptions,
+) -> Option<&'static dyn FileHandler> {
+    find_handler(header, format, flags, None)
+}
+
+/// Returns the handler for the file at `path`, if any.
+pub(crate) fn handler_for_path(
+    path: &Path,
+    flags: &OpenFileOptions,
+) -> Option<&'static dyn FileHandler> {
+    let header = read_header(path).ok()?;
+    find_handler(&header, FileFormat::Unknown, flags, Some(path))
+}
+
+/// Returns the first bytes of the file at `path` (up to `HEADER_LEN`).
+pub(crate) fn read_header(path: &Path) -> io::Result<Vec<u8>> {
+    let mut header = Vec::with_capacity(HEADER_LEN);
+    File::open(path)?
+        .take(HEADER_LEN as u64)
+        .read_to_end(&mut header)?;
+
+    Ok(header)
+}
+
+/// Describes the handler used for files of `format` opened with the
+/// default options, if it is one of the handlers in this module.
+pub(crate) fn format_capabilities(format: FileFormat) -> Option<FormatCapabilities> {
+    if format == FileFormat::Unknown {
+        return None;
+    }
+
+    let flags = OpenFileOptions::default();
+    let custom_handlers = CUSTOM_HANDLERS.read().unwrap_or_else(|e| e.into_inner());
+
+    let handler = custom_handlers
+        .iter()
+        .chain(HANDLERS)
+        .copied()
+        .find(|handler| handler.format() == format && handler.is_enabled(&flags))?;
+
+    // Files are read into memory when opened and, unless the handler can
+    // only overwrite the existing packet, rewritten in full when closed.
+    let in_place_only = handler.updates_in_place_only();
+
+    Some(FormatCapabilities {
+        can_inject_xmp: !in_place_only,
+        can_expand: !in_place_only,
+        can_rewrite: !in_place_only,
+        prefers_in_place: in_place_only,
+        can_reconcile: handler.imports_legacy(&flags),
+        returns_raw_packet: true,
+        handler_owns_file: true,
+        allows_safe_update: true,
+        needs_preloading: true,
+        ..FormatCapabilities::default()
+    })
+}
+
+fn find_handler(
+    header: &[u8],
+    format: FileFormat,
+    flags: &OpenFileOptions,
+    path: Option<&Path>,
+) -> Option<&'static dyn FileHandler> {
+    let custom_handlers = CUSTOM_HANDLERS.read().unwrap_or_else(|e| e.into_inner());
+
+    custom_handlers
+        .iter()
+        .chain(HANDLERS)
+        .copied()
+        .find(|handler| {
+            (format == FileFormat::Unknown || format == handler.format())
+                && handler.is_enabled(flags)
+                && handler.check_format(header)
+                && path.map_or(true, |path| handler.accepts_path(path))
+        })
+}
+
+/// A file opened by [`XmpFile`](crate::XmpFile) that is processed by one
+/// of the handlers in this module.
+pub(crate) struct NativeFile {
+    handler: &'static dyn FileHandler,
+
+    // `None` if the file was opened from bytes.
+    path: Option<PathBuf>,
+
+    bytes: Vec<u8>,
+
+    // The location of the XMP packet, if it is stored contiguously.
+    xmp: Option<Range<usize>>,
+
+    packet: Option<Vec<u8>>,
+    for_update: bool,
+    import_legacy: bool,
+    update_safely: bool,
+    update_in_place: bool,
+    temp_folder: Option<PathBuf>,
+    updated: bool,
+}
+
+impl NativeFile {
+    pub(crate) fn open(
+        handler: &'static dyn FileHandler,
+        path: &Path,
+        flags: &OpenFileOptions,
+    ) -> XmpResult<Self> {
+        let bytes = fs::read(path).map_err(|err| XmpError {
+            error_type: XmpErrorType::ReadError,
+            debug_message: err.to_string(),
+        })?;
+
+        Self::from_file_bytes(handler, path, bytes, flags)
+    }
+
+    /// Like [`NativeFile::open`], for a file that was already read into
+    /// `bytes`.
+    pub(crate) fn from_file_bytes(
+        handler: &'static dyn FileHandler,
+        path: &Path,
+        bytes: Vec<u8>,
+        flags: &OpenFileOptions,
+    ) -> XmpResult<Self> {
+        let mut file = Self::from_bytes(handler, bytes, flags)?;
+        file.path = Some(path.to_path_buf());
+        Ok(file)
+    }
+
+    pub(crate) fn from_bytes(
+        handler: &'static dyn FileHandler,
+        bytes: Vec<u8>,
+        flags: &OpenFileOptions,
+    ) -> XmpResult<Self> {
+        let xmp = handler.read_xmp(&bytes)?;
+        let packet = handler.read_packet(&bytes)?;
+
+        Ok(Self {
+            handler,
+            path: None,
+            bytes,
+            xmp,
+            packet,
+            for_update: flags.opens_for_update(),
+            import_legacy: handler.imports_legacy(flags),
+            update_safely: flags.updates_safely(),
+            update_in_place: flags.updates_xmp_in_place() || handler.updates_in_place_only(),
+            temp_folder: flags.temp_folder_path().map(Path::to_path_buf),
+            updated: false,
+        })
+    }
+
+    pub(crate) fn xmp(&self) -> Option<XmpMeta> {
+        let meta = match self.packet() {
+            Some(packet) => XmpMeta::from_str(&String::from_utf8_lossy(packet)).ok(),
+            None if self.import_legacy => None,
+            None => return None,
+        };
+
+        if !self.import_legacy {
+            return meta;
+        }
+
+        let had_xmp = meta.is_some();
+        let mut meta = match meta {
+            Some(meta) => meta,
+            None => XmpMeta::new().ok()?,
+        };
+
+        let imported = self
+            .handler
+            .import_legacy(&self.bytes, &mut meta)
+            .unwrap_or(false);
+
+        (had_xmp || imported).then_some(meta)
+    }
+
+    pub(crate) fn packet(&self) -> Option<&[u8]> {
+        self.packet.as_deref()
+    }
+
+    /// Returns the packet as stored in the file, which differs from
+    /// [`NativeFile::packet`] for repaired packets.
+    pub(crate) fn raw_packet(&self) -> Option<&[u8]> {
+        match &self.xmp {
+            Some(range) => self.bytes.get(range.clone()),
+            None => self.packet(),
+        }
+    }
+
+    pub(crate) fn packet_range(&self) -> Option<Range<usize>> {
+        self.xmp.clone()
+    }
+
+    pub(crate) fn opens_for_update(&self) -> bool {
+        self.for_update
+    }
+
+    /// Returns the reason `meta` can't be written, other than the file
+    /// not being open for update.
+    pub(crate) fn put_xmp_blocker(&self, meta: &XmpMeta) -> Option<PutXmpBlocker> {
+        if !self.update_in_place {
+            None
+        } else if self.xmp.is_none() {
+            Some(PutXmpBlocker::ReadOnlyFormat)
+        } else if self.serialize_in_place(meta).is_err() {
+            Some(PutXmpBlocker::PacketTooLarge)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn put_xmp(&mut self, meta: &XmpMeta, padding: PacketPadding) -> XmpResult<()> {
+        self.check_for_update()?;
+
+        let packet = if self.update_in_place {
+            self.serialize_in_place(meta)?.1
+        } else {
+            padding.serialize(meta)?
+        };
+
+        self.put_raw_packet(packet.as_bytes())
+    }
+
+    /// Returns `true` if a packet of `len` bytes can be written as is by
+    /// [`NativeFile::put_raw_packet`].
+    pub(crate) fn fits_raw_packet(&self, len: usize) -> bool {
+        !self.update_in_place || self.xmp.as_ref().is_some_and(|range| range.len() == len)
+    }
+
+    /// Writes `packet` to the file as is.
+    ///
+    /// For `OpenFileOptions::update_xmp_in_place`, `packet` overwrites the
+    /// existing packet and must have exactly the same length.
+    pub(crate) fn put_raw_packet(&mut self, packet: &[u8]) -> XmpResult<()> {
+        self.check_for_update()?;
+
+        if self.update_in_place {
+            let not_in_place = |message: &str| XmpError {
+                error_type: XmpErrorType::XmpUpdateNotInPlace,
+                debug_message: message.to_owned(),
+            };
+
+            let Some(range) = self.xmp.clone() else {
+                return Err(not_in_place("File has no XMP packet to update in place"));
+            };
+
+            if range.len() != packet.len() {
+                return Err(not_in_place(
+                    "Packet doesn't have the same length as the existing packet",
+                ));
+            }
+
+            self.bytes[range].copy_from_slice(packet);
+        } else {
+            self.bytes = self.handler.write_xmp(&self.bytes, packet)?;
+            self.xmp = self.handler.read_xmp(&self.bytes)?;
+        }
+
+        self.packet = self.handler.read_packet(&self.bytes)?;
+        self.updated = true;
+        Ok(())
+    }
+
+    fn check_for_update(&self) -> XmpResult<()> {
+        if self.for_update {
+            Ok(())
+        } else {
+            Err(XmpError {
+                error_type: XmpErrorType::BadParam,
+                debug_message: "File was not opened for update".to_owned(),
+            })
+        }
+    }
+
+    /// Serializes `meta` as a packet of the same length as the existing
+    /// one, and returns it with the location of the existing one.
+    fn serialize_in_place(&self, meta: &XmpMeta) -> XmpResult<(Range<usize>, String)> {
+        let not_in_place = |message: &str| XmpError {
+            error_type: XmpErrorType::XmpUpdateNotInPlace,
+            debug_message: message.to_owned(),
+        };
+
+        let Some(range) = self.xmp.clone() else {
+            return Err(not_in_place("File has no XMP packet to update in place"));
+        };
+
+        let packet = u32::try_from(range.len())
+            .ok()
+            .and_then(|len| {
+                meta.to_string_with_options(
+                    ToStringOptions::default()
+                        .set_padding(len)
+                        .exact_packet_length(),
+                )
+                .ok()
+            })
+            .filter(|packet| packet.len() == range.len())
+            .ok_or_else(|| not_in_place("XMP does not fit in the existing packet"))?;
+
+        Ok((range, packet))
+    }
+
+    /// Writes the file if it was updated and opened from a path.
+    ///
+    /// The new content is written to a temporary file in the same folder
+    /// (or the one chosen with `OpenFileOptions::temp_folder`), which then
+    /// replaces the original, so that the original is left intact if
+    /// writing fails.
+    pub(crate) fn close(&mut self) -> XmpResult<()> {
+        if !self.updated {
+            return Ok(());
+        }
+
+        self.updated = false;
+
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let temp_path = temp_path(path, self.temp_folder.as_deref());
+
+        write_temp_file(&temp_path, &self.bytes)
+            .and_then(|()| replace_file(&temp_path, path, self.update_safely))
+            .map_err(|err| {
+                let _ = fs::remove_file(&temp_path);
+                XmpError {
+                    error_type: XmpErrorType::WriteError,
+                    debug_message: err.to_string(),
+                }
+            })
+    }
+
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Reads and updates a damaged XMP packet that the C++ XMP Toolkit couldn't
+/// read, for `OpenFileOptions::repair_xmp`. `XmpFile` falls back to this
+/// handler explicitly; `handler_for` never chooses it.
+pub(crate) struct RepairHandler;
+
+impl FileHandler for RepairHandler {
+    fn format(&self) -> FileFormat {
+        FileFormat::Unknown
+    }
+
+    fn check_format(&self, _header: &[u8]) -> bool {
+        false
+    }
+
+    fn read_xmp(&self, file: &[u8]) -> XmpResult<Option<Range<usize>>> {
+        Ok(xmp_packet::repair(file)?.map(|packet| packet.range))
+    }
+
+    fn read_packet(&self, file: &[u8]) -> XmpResult<Option<Vec<u8>>> {
+        Ok(xmp_packet::repair(file)?.map(|packet| packet.text.into_bytes()))
+    }
+
+    fn updates_in_place_only(&self) -> bool {
+        true
+    }
+
+    fn write_xmp(&self, _file: &[u8], _packet: &[u8]) -> XmpResult<Vec<u8>> {
+        Err(XmpError {
+            error_type: XmpErrorType::InternalFailure,
+            debug_message: "Repaired packets can only be updated in place".to_owned(),
+        })
+    }
+}
+
+/// Returns the path of a temporary file with the same extension as `path`,
+/// for writing a new version of that file.
+///
+/// The file is in `folder` if given (see `OpenFileOptions::temp_folder`),
+/// and otherwise in the same folder as `path`. Names are made unique per
+/// process and call, so that concurrent updates of the same file, or of
+/// files from different folders sharing a temporary folder, don't collide.
+pub(crate) fn temp_path(path: &Path, folder: Option<&Path>) -> PathBuf {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let mut temp_name = OsString::from(format!(".xmptmp-{}-{id}-", std::process::id()));
+    temp_name.push(path.file_name().unwrap_or_default());
+
+    match folder {
+        Some(folder) => folder.join(temp_name),
+        None => path.with_file_name(temp_name),
+    }
+}
+
+/// Creates the file at `temp_path` (see [`temp_path`]) for writing.
+///
+/// Fails if anything already exists at `temp_path`, so that a file or
+/// symbolic link planted there is never written through.
+pub(crate) fn create_temp_file(temp_path: &Path) -> io::Result<File> {
+    File::options().write(true).create_new(true).open(temp_path)
+}
+
+/// Writes `bytes` to a new file at `temp_path` (see [`create_temp_file`]).
+pub(crate) fn write_temp_file(temp_path: &Path, bytes: &[u8]) -> io::Result<()> {
+    create_temp_file(temp_path)?.write_all(bytes)
+}
+
+/// Copies the file at `path`, including its permissions, to a new file at
+/// `temp_path` (see [`create_temp_file`]).
+///
+/// The new file is removed again if the copy fails.
+pub(crate) fn copy_to_temp_file(path: &Path, temp_path: &Path) -> io::Result<()> {
+    let mut source = File::open(path)?;
+    let mut temp = create_temp_file(temp_path)?;
+
+    let copied = io::copy(&mut source, &mut temp)
+        .and_then(|_| source.metadata())
+        .and_then(|metadata| temp.set_permissions(metadata.permissions()));
+
+    if copied.is_err() {
+        drop(temp);
+        let _ = fs::remove_file(temp_path);
+    }
+
+    copied
+}
+
+/// Replaces the file at `path` with `temp`.
+///
+/// The rename is atomic on local file systems. If `sync` is `true`, `temp`
+/// is flushed to disk before the rename, and (on Unix) the folder after
+/// it, so that the file at `path` is either the original or the complete
+/// new version even if the system crashes.
+///
+/// If `temp` is in another folder and can't be renamed, for example
+/// because it is on another file system, it is copied over `path` and
+/// then removed instead. That isn't atomic.
+pub(crate) fn replace_file(temp: &Path, path: &Path, sync: bool) -> io::Result<()> {
+    if sync {
+        File::options().write(true).open(temp)?.sync_all()?;
+    }
+
+    if let Err(err) = fs::rename(temp, path) {
+        if temp.parent() == path.parent() {
+            return Err(err);
+        }
+
+        fs::copy(temp, path)?;
+        if sync {
+            File::options().write(true).open(path)?.sync_all()?;
+        }
+        fs::remove_file(temp)?;
+    }
+
+    #[cfg(unix)]
+    if sync {
+        if let Some(folder) = path.parent() {
+            let folder = if folder.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                folder
+            };
+            File::open(folder)?.sync_all()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns an error for a file that doesn't have the structure expected
+/// for `format`.
+pub(crate) fn bad_file(format: &str, message: &str) -> XmpError {
+    XmpError {
+        error_type: XmpErrorType::BadFileFormat,
+        debug_message: format!("{format}: {message}"),
+    }
+}
@@ -0,0 +1,193 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+// Advisory locks taken by `XmpFile::open_file` when requested with
+// `OpenFileOptions::lock_file`.
+//
+// On Unix, the whole file is locked with `flock`, which only affects other
+// callers of `flock`. Such locks belong to the open file description, so
+// the C++ XMP Toolkit's own file handles neither conflict with them nor
+// release them when closed.
+//
+// On Windows, `LockFileEx` locks are enforced on reads and writes of the
+// locked range. Locking the file's contents would keep the C++ XMP Toolkit
+// from reading it through its own handle, so the lock covers a single byte
+// far beyond the end of any real file instead. Other processes that lock
+// the same byte, which includes other users of this crate, are still
+// excluded.
+
+use std::{fs::File, path::Path};
+
+use crate::{XmpError, XmpErrorType, XmpResult};
+
+/// A lock on a file, released when dropped.
+pub(crate) struct FileLock {
+    // Closing the file releases the lock.
+    _file: File,
+}
+
+impl FileLock {
+    /// Locks the file at `path`, for exclusive access if `exclusive` is
+    /// `true` and for shared access otherwise.
+    ///
+    /// If `wait` is `false` and another process holds a conflicting lock,
+    /// returns an error of type `FileLocked`. Returns `Ok(None)` if the
+    /// file can't be opened, leaving it to the caller to report why.
+    pub(crate) fn acquire(path: &Path, exclusive: bool, wait: bool) -> XmpResult<Option<Self>> {
+        let Ok(file) = File::open(path) else {
+            return Ok(None);
+        };
+
+        match sys::lock(&file, exclusive, wait) {
+            Ok(()) => Ok(Some(Self { _file: file })),
+            Err(err) if sys::is_contended(&err) => Err(XmpError {
+                error_type: XmpErrorType::FileLocked,
+                debug_message: format!("{} is locked by another process", path.display()),
+            }),
+            Err(err) => Err(XmpError {
+                error_type: XmpErrorType::ExternalFailure,
+                debug_message: format!("Unable to lock {}: {err}", path.display()),
+            }),
+        }
+    }
+
+    /// Returns `true` if another process holds a lock on the file at `path`
+    /// that conflicts with updating it. If there is none, the lock taken to
+    /// find out is released at once.
+    pub(crate) fn is_locked(path: &Path) -> bool {
+        matches!(
+            Self::acquire(path, true, false),
+            Err(err) if err.error_type == XmpErrorType::FileLocked
+        )
+    }
+}
+
+#[cfg(unix)]
+mod sys {
+    use std::{
+        fs::File,
+        io,
+        os::{raw::c_int, unix::io::AsRawFd},
+    };
+
+    const LOCK_SH: c_int = 1;
+    const LOCK_EX: c_int = 2;
+    const LOCK_NB: c_int = 4;
+
+    extern "C" {
+        fn flock(fd: c_int, operation: c_int) -> c_int;
+    }
+
+    pub(super) fn lock(file: &File, exclusive: bool, wait: bool) -> io::Result<()> {
+        let mut operation = if exclusive { LOCK_EX } else { LOCK_SH };
+        if !wait {
+            operation |= LOCK_NB;
+        }
+
+        loop {
+            if unsafe { flock(file.as_raw_fd(), operation) } == 0 {
+                return Ok(());
+            }
+
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::Interrupted {
+                return Err(err);
+            }
+        }
+    }
+
+    pub(super) fn is_contended(err: &io::Error) -> bool {
+        err.kind() == io::ErrorKind::WouldBlock
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use std::{
+        fs::File,
+        io,
+        os::{raw::c_void, windows::io::AsRawHandle},
+        ptr,
+    };
+
+    const LOCKFILE_FAIL_IMMEDIATELY: u32 = 0x1;
+    const LOCKFILE_EXCLUSIVE_LOCK: u32 = 0x2;
+    const ERROR_LOCK_VIOLATION: i32 = 33;
+
+    // The byte that is locked. See the comment at the top of this file.
+    const LOCK_OFFSET: u64 = 0x7fff_ffff_ffff_fffe;
+
+    #[repr(C)]
+    struct Overlapped {
+        internal: usize,
+        internal_high: usize,
+        offset: u32,
+        offset_high: u32,
+        event: *mut c_void,
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn LockFileEx(
+            file: *mut c_void,
+            flags: u32,
+            reserved: u32,
+            bytes_low: u32,
+            bytes_high: u32,
+            overlapped: *mut Overlapped,
+        ) -> i32;
+    }
+
+    pub(super) fn lock(file: &File, exclusive: bool, wait: bool) -> io::Result<()> {
+        let mut flags = 0;
+        if exclusive {
+            flags |= LOCKFILE_EXCLUSIVE_LOCK;
+        }
+        if !wait {
+            flags |= LOCKFILE_FAIL_IMMEDIATELY;
+        }
+
+        let mut overlapped = Overlapped {
+            internal: 0,
+            internal_high: 0,
+            offset: LOCK_OFFSET as u32,
+            offset_high: (LOCK_OFFSET >> 32) as u32,
+            event: ptr::null_mut(),
+        };
+
+        let locked = unsafe { LockFileEx(file.as_raw_handle(), flags, 0, 1, 0, &mut overlapped) };
+
+        if locked != 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    pub(super) fn is_contended(err: &io::Error) -> bool {
+        err.raw_os_error() == Some(ERROR_LOCK_VIOLATION)
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod sys {
+    use std::{fs::File, io};
+
+    pub(super) fn lock(_file: &File, _exclusive: bool, _wait: bool) -> io::Result<()> {
+        Ok(())
+    }
+
+    pub(super) fn is_contended(_err: &io::Error) -> bool {
+        false
+    }
+}
@@ -0,0 +1,201 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+// Reads and writes the XMP of FLAC files.
+//
+// A FLAC file starts with `fLaC` and a sequence of metadata blocks, the
+// last of which is flagged. The XMP packet is stored in an `APPLICATION`
+// block with application ID `XMP `, which goes before any `PADDING` block.
+// If the rewritten metadata is no larger than before, the padding is
+// shrunk so that the audio data doesn't move.
+//
+// See https://xiph.org/flac/format.html#metadata_block.
+
+use std::ops::Range;
+
+use crate::{
+    xmp_file_handler::{bad_file, FileHandler},
+    xmp_vorbis_comment::Comments,
+    FileFormat, OpenFileOptions, XmpError, XmpMeta, XmpResult,
+};
+
+const FLAC: &[u8; 4] = b"fLaC";
+const XMP_APPLICATION_ID: &[u8; 4] = b"XMP ";
+
+const PADDING: u8 = 1;
+const APPLICATION: u8 = 2;
+const VORBIS_COMMENT: u8 = 4;
+
+const LAST_BLOCK_FLAG: u8 = 0x80;
+const BLOCK_HEADER_LEN: usize = 4;
+const MAX_BLOCK_LEN: usize = (1 << 24) - 1;
+
+pub(crate) struct FlacHandler;
+
+impl FileHandler for FlacHandler {
+    fn format(&self) -> FileFormat {
+        FileFormat::Flac
+    }
+
+    fn check_format(&self, header: &[u8]) -> bool {
+        header.starts_with(FLAC)
+    }
+
+    fn read_xmp(&self, file: &[u8]) -> XmpResult<Option<Range<usize>>> {
+        Ok(blocks(file)?
+            .0
+            .into_iter()
+            .find(|block| is_xmp_block(file, block))
+            .map(|block| block.data.start + XMP_APPLICATION_ID.len()..block.data.end))
+    }
+
+    fn write_xmp(&self, file: &[u8], packet: &[u8]) -> XmpResult<Vec<u8>> {
+        let (blocks, audio_start) = blocks(file)?;
+
+        let xmp_len = XMP_APPLICATION_ID.len() + packet.len();
+        if xmp_len > MAX_BLOCK_LEN {
+            return Err(bad_flac("XMP packet is too large"));
+        }
+
+        let mut metadata: Vec<(u8, &[u8], &[u8])> = blocks
+            .iter()
+            .filter(|block| block.block_type != PADDING && !is_xmp_block(file, block))
+            .map(|block| (block.block_type, &file[block.data.clone()], &[][..]))
+            .collect();
+
+        if metadata.is_empty() {
+            return Err(bad_flac("Missing STREAMINFO block"));
+        }
+
+        metadata.push((APPLICATION, XMP_APPLICATION_ID, packet));
+
+        // Fill the space of the old metadata if possible. Otherwise, keep
+        // the old amount of padding.
+        let old_len = audio_start - FLAC.len();
+        let old_padding: usize = blocks
+            .iter()
+            .filter(|block| block.block_type == PADDING)
+            .map(|block| block.data.len())
+            .sum();
+
+        let new_len: usize = metadata
+            .iter()
+            .map(|(_, a, b)| BLOCK_HEADER_LEN + a.len() + b.len())
+            .sum();
+
+        let padding = if new_len == old_len {
+            None
+        } else if new_len + BLOCK_HEADER_LEN <= old_len {
+            Some(old_len - new_len - BLOCK_HEADER_LEN)
+        } else if old_padding > 0 {
+            Some(old_padding.min(MAX_BLOCK_LEN))
+        } else {
+            None
+        };
+
+        let mut out = Vec::with_capacity(file.len() + packet.len());
+        out.extend_from_slice(FLAC);
+
+        let last = metadata.len() - 1;
+        for (index, (block_type, a, b)) in metadata.iter().enumerate() {
+            let is_last = index == last && padding.is_none();
+            push_block_header(&mut out, *block_type, is_last, a.len() + b.len());
+            out.extend_from_slice(a);
+            out.extend_from_slice(b);
+        }
+
+        if let Some(padding) = padding {
+            push_block_header(&mut out, PADDING, true, padding);
+            out.resize(out.len() + padding, 0);
+        }
+
+        out.extend_from_slice(&file[audio_start..]);
+        Ok(out)
+    }
+
+    fn imports_legacy(&self, flags: &OpenFileOptions) -> bool {
+        flags.imports_vorbis_comments()
+    }
+
+    fn import_legacy(&self, file: &[u8], meta: &mut XmpMeta) -> XmpResult<bool> {
+        let Some(block) = blocks(file)?
+            .0
+            .into_iter()
+            .find(|block| block.block_type == VORBIS_COMMENT)
+        else {
+            return Ok(false);
+        };
+
+        let data = &file[block.data];
+        match Comments::parse(data) {
+            Some(comments) => comments.import(data, meta),
+            None => Err(bad_flac("Invalid VORBIS_COMMENT block")),
+        }
+    }
+}
+
+struct Block {
+    block_type: u8,
+
+    // The block's payload.
+    data: Range<usize>,
+}
+
+/// Returns the metadata blocks of `file` and the offset of the audio data
+/// that follows them.
+fn blocks(file: &[u8]) -> XmpResult<(Vec<Block>, usize)> {
+    if !file.starts_with(FLAC) {
+        return Err(bad_flac("Not a FLAC file"));
+    }
+
+    let mut blocks = Vec::new();
+    let mut pos = FLAC.len();
+
+    loop {
+        let header = file
+            .get(pos..pos + BLOCK_HEADER_LEN)
+            .ok_or_else(|| bad_flac("Truncated metadata block header"))?;
+
+        let len = u32::from_be_bytes([0, header[1], header[2], header[3]]) as usize;
+        let data = pos + BLOCK_HEADER_LEN..pos + BLOCK_HEADER_LEN + len;
+        if data.end > file.len() {
+            return Err(bad_flac("Metadata block extends past the end of the file"));
+        }
+
+        pos = data.end;
+        blocks.push(Block {
+            block_type: header[0] & !LAST_BLOCK_FLAG,
+            data,
+        });
+
+        if header[0] & LAST_BLOCK_FLAG != 0 {
+            return Ok((blocks, pos));
+        }
+    }
+}
+
+fn push_block_header(out: &mut Vec<u8>, block_type: u8, is_last: bool, len: usize) {
+    let flag = if is_last { LAST_BLOCK_FLAG } else { 0 };
+    let len = (len as u32).to_be_bytes();
+
+    out.push(block_type | flag);
+    out.extend_from_slice(&len[1..]);
+}
+
+fn is_xmp_block(file: &[u8], block: &Block) -> bool {
+    block.block_type == APPLICATION && file[block.data.clone()].starts_with(XMP_APPLICATION_ID)
+}
+
+fn bad_flac(message: &str) -> XmpError {
+    bad_file("FLAC", message)
+}
@@ -0,0 +1,330 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Contains typed access to the Google Photo Sphere (`GPano`) schema, which
+//! describes 360° panoramas.
+//!
+//! See the [Photo Sphere XMP metadata] reference for the meaning of each
+//! property.
+//!
+//! [Photo Sphere XMP metadata]: https://developers.google.com/streetview/spherical-metadata
+
+use crate::{xmp_ns, XmpError, XmpErrorType, XmpMeta, XmpPropertyType, XmpResult};
+
+/// The only projection type defined by the Photo Sphere schema.
+pub const EQUIRECTANGULAR: &str = "equirectangular";
+
+/// The Photo Sphere properties of an image.
+///
+/// Each field corresponds to the `GPano` property of the same name and is
+/// `None` if that property is absent.
+///
+/// ## Example
+///
+/// ```
+/// # use xmp_toolkit::{xmp_gpano::PhotoSphere, XmpMeta};
+/// # fn main() -> xmp_toolkit::XmpResult<()> {
+/// let mut meta = XmpMeta::new()?;
+///
+/// let sphere = PhotoSphere {
+///     projection_type: Some("equirectangular".to_owned()),
+///     use_panorama_viewer: Some(true),
+///     full_pano_width_pixels: Some(8000),
+///     full_pano_height_pixels: Some(4000),
+///     ..Default::default()
+/// };
+///
+/// sphere.validate()?;
+/// sphere.write_to(&mut meta)?;
+///
+/// assert_eq!(PhotoSphere::read_from(&meta), Some(sphere));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PhotoSphere {
+    /// `GPano:ProjectionType`. Must be `"equirectangular"`.
+    pub projection_type: Option<String>,
+
+    /// `GPano:UsePanoramaViewer`.
+    pub use_panorama_viewer: Option<bool>,
+
+    /// `GPano:CaptureSoftware`.
+    pub capture_software: Option<String>,
+
+    /// `GPano:StitchingSoftware`.
+    pub stitching_software: Option<String>,
+
+    /// `GPano:PoseHeadingDegrees`: compass heading of the image center,
+    /// from 0 to 360.
+    pub pose_heading_degrees: Option<f64>,
+
+    /// `GPano:PosePitchDegrees`: pitch of the image center, from -90 to
+    /// 90.
+    pub pose_pitch_degrees: Option<f64>,
+
+    /// `GPano:PoseRollDegrees`: roll of the image, from -180 to 180.
+    pub pose_roll_degrees: Option<f64>,
+
+    /// `GPano:InitialViewHeadingDegrees`: heading of the initial view,
+    /// from 0 to 360.
+    pub initial_view_heading_degrees: Option<f64>,
+
+    /// `GPano:InitialViewPitchDegrees`: pitch of the initial view, from
+    /// -90 to 90.
+    pub initial_view_pitch_degrees: Option<f64>,
+
+    /// `GPano:InitialViewRollDegrees`: roll of the initial view, from -180
+    /// to 180.
+    pub initial_view_roll_degrees: Option<f64>,
+
+    /// `GPano:InitialHorizontalFOVDegrees`: horizontal field of view of the
+    /// initial view.
+    pub initial_horizontal_fov_degrees: Option<f64>,
+
+    /// `GPano:FullPanoWidthPixels`: width of the full panorama.
+    pub full_pano_width_pixels: Option<i32>,
+
+    /// `GPano:FullPanoHeightPixels`: height of the full panorama.
+    pub full_pano_height_pixels: Option<i32>,
+
+    /// `GPano:CroppedAreaImageWidthPixels`: width of the area of the full
+    /// panorama that the image covers.
+    pub cropped_area_image_width_pixels: Option<i32>,
+
+    /// `GPano:CroppedAreaImageHeightPixels`: height of the area of the full
+    /// panorama that the image covers.
+    pub cropped_area_image_height_pixels: Option<i32>,
+
+    /// `GPano:CroppedAreaLeftPixels`: column where the cropped area begins.
+    pub cropped_area_left_pixels: Option<i32>,
+
+    /// `GPano:CroppedAreaTopPixels`: row where the cropped area begins.
+    pub cropped_area_top_pixels: Option<i32>,
+}
+
+impl PhotoSphere {
+    /// Reads the Photo Sphere properties from `meta`.
+    ///
+    /// Properties whose values can not be interpreted as the field's type
+    /// are treated as absent. Returns `None` if none of the properties are
+    /// present.
+    pub fn read_from(meta: &XmpMeta) -> Option<Self> {
+        let result = Self {
+            projection_type: read(meta, "ProjectionType"),
+            use_panorama_viewer: read(meta, "UsePanoramaViewer"),
+            capture_software: read(meta, "CaptureSoftware"),
+            stitching_software: read(meta, "StitchingSoftware"),
+            pose_heading_degrees: read(meta, "PoseHeadingDegrees"),
+            pose_pitch_degrees: read(meta, "PosePitchDegrees"),
+            pose_roll_degrees: read(meta, "PoseRollDegrees"),
+            initial_view_heading_degrees: read(meta, "InitialViewHeadingDegrees"),
+            initial_view_pitch_degrees: read(meta, "InitialViewPitchDegrees"),
+            initial_view_roll_degrees: read(meta, "InitialViewRollDegrees"),
+            initial_horizontal_fov_degrees: read(meta, "InitialHorizontalFOVDegrees"),
+            full_pano_width_pixels: read(meta, "FullPanoWidthPixels"),
+            full_pano_height_pixels: read(meta, "FullPanoHeightPixels"),
+            cropped_area_image_width_pixels: read(meta, "CroppedAreaImageWidthPixels"),
+            cropped_area_image_height_pixels: read(meta, "CroppedAreaImageHeightPixels"),
+            cropped_area_left_pixels: read(meta, "CroppedAreaLeftPixels"),
+            cropped_area_top_pixels: read(meta, "CroppedAreaTopPixels"),
+        };
+
+        if result == Self::default() {
+            None
+        } else {
+            Some(result)
+        }
+    }
+
+    /// Writes the Photo Sphere properties to `meta`.
+    ///
+    /// Fields with value `None` remove the corresponding property, so that
+    /// afterwards `meta` holds exactly the properties described by `self`.
+    /// The `GPano` namespace is registered if necessary.
+    pub fn write_to(&self, meta: &mut XmpMeta) -> XmpResult<()> {
+        XmpMeta::register_namespace(xmp_ns::GPANO, "GPano")?;
+
+        write(meta, "ProjectionType", &self.projection_type)?;
+        write(meta, "UsePanoramaViewer", &self.use_panorama_viewer)?;
+        write(meta, "CaptureSoftware", &self.capture_software)?;
+        write(meta, "StitchingSoftware", &self.stitching_software)?;
+        write(meta, "PoseHeadingDegrees", &self.pose_heading_degrees)?;
+        write(meta, "PosePitchDegrees", &self.pose_pitch_degrees)?;
+        write(meta, "PoseRollDegrees", &self.pose_roll_degrees)?;
+        write(
+            meta,
+            "InitialViewHeadingDegrees",
+            &self.initial_view_heading_degrees,
+        )?;
+        write(
+            meta,
+            "InitialViewPitchDegrees",
+            &self.initial_view_pitch_degrees,
+        )?;
+        write(
+            meta,
+            "InitialViewRollDegrees",
+            &self.initial_view_roll_degrees,
+        )?;
+        write(
+            meta,
+            "InitialHorizontalFOVDegrees",
+            &self.initial_horizontal_fov_degrees,
+        )?;
+        write(meta, "FullPanoWidthPixels", &self.full_pano_width_pixels)?;
+        write(meta, "FullPanoHeightPixels", &self.full_pano_height_pixels)?;
+        write(
+            meta,
+            "CroppedAreaImageWidthPixels",
+            &self.cropped_area_image_width_pixels,
+        )?;
+        write(
+            meta,
+            "CroppedAreaImageHeightPixels",
+            &self.cropped_area_image_height_pixels,
+        )?;
+        write(
+            meta,
+            "CroppedAreaLeftPixels",
+            &self.cropped_area_left_pixels,
+        )?;
+        write(meta, "CroppedAreaTopPixels", &self.cropped_area_top_pixels)
+    }
+
+    /// Checks that the properties are consistent with the Photo Sphere
+    /// specification.
+    ///
+    /// The following are checked:
+    ///
+    /// * `ProjectionType`, `FullPanoWidthPixels`, and `FullPanoHeightPixels`
+    ///   are present.
+    /// * `ProjectionType` is `"equirectangular"`.
+    /// * All pixel dimensions are positive and all pixel offsets are
+    ///   non-negative.
+    /// * The cropped area lies within the full panorama.
+    /// * Angles are within the ranges described on each field.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::BadValue`] describing the
+    /// first problem found.
+    pub fn validate(&self) -> XmpResult<()> {
+        match self.projection_type.as_deref() {
+            Some(EQUIRECTANGULAR) => {}
+            Some(other) => return Err(bad_value(format!("Unsupported ProjectionType {other:?}"))),
+            None => return Err(bad_value("ProjectionType is missing".to_owned())),
+        }
+
+        let Some(full_width) = self.full_pano_width_pixels else {
+            return Err(bad_value("FullPanoWidthPixels is missing".to_owned()));
+        };
+
+        let Some(full_height) = self.full_pano_height_pixels else {
+            return Err(bad_value("FullPanoHeightPixels is missing".to_owned()));
+        };
+
+        check_positive("FullPanoWidthPixels", Some(full_width))?;
+        check_positive("FullPanoHeightPixels", Some(full_height))?;
+        check_positive(
+            "CroppedAreaImageWidthPixels",
+            self.cropped_area_image_width_pixels,
+        )?;
+        check_positive(
+            "CroppedAreaImageHeightPixels",
+            self.cropped_area_image_height_pixels,
+        )?;
+
+        let left = self.cropped_area_left_pixels.unwrap_or(0);
+        let top = self.cropped_area_top_pixels.unwrap_or(0);
+
+        if left < 0 || top < 0 {
+            return Err(bad_value(
+                "Cropped area offsets must not be negative".to_owned(),
+            ));
+        }
+
+        let width = self.cropped_area_image_width_pixels.unwrap_or(full_width);
+        let height = self.cropped_area_image_height_pixels.unwrap_or(full_height);
+
+        if i64::from(left) + i64::from(width) > i64::from(full_width)
+            || i64::from(top) + i64::from(height) > i64::from(full_height)
+        {
+            return Err(bad_value(
+                "Cropped area extends beyond the full panorama".to_owned(),
+            ));
+        }
+
+        check_range("PoseHeadingDegrees", self.pose_heading_degrees, 0.0, 360.0)?;
+        check_range("PosePitchDegrees", self.pose_pitch_degrees, -90.0, 90.0)?;
+        check_range("PoseRollDegrees", self.pose_roll_degrees, -180.0, 180.0)?;
+        check_range(
+            "InitialViewHeadingDegrees",
+            self.initial_view_heading_degrees,
+            0.0,
+            360.0,
+        )?;
+        check_range(
+            "InitialViewPitchDegrees",
+            self.initial_view_pitch_degrees,
+            -90.0,
+            90.0,
+        )?;
+        check_range(
+            "InitialViewRollDegrees",
+            self.initial_view_roll_degrees,
+            -180.0,
+            180.0,
+        )?;
+        check_range(
+            "InitialHorizontalFOVDegrees",
+            self.initial_horizontal_fov_degrees,
+            0.0,
+            360.0,
+        )
+    }
+}
+
+fn read<T: XmpPropertyType>(meta: &XmpMeta, name: &str) -> Option<T> {
+    meta.get::<T>(xmp_ns::GPANO, name).map(|v| v.value)
+}
+
+fn write<T: XmpPropertyType>(meta: &mut XmpMeta, name: &str, value: &Option<T>) -> XmpResult<()> {
+    match value {
+        Some(value) => meta.set::<T>(xmp_ns::GPANO, name, value.clone()),
+        None => meta.delete_property(xmp_ns::GPANO, name),
+    }
+}
+
+fn check_positive(name: &str, value: Option<i32>) -> XmpResult<()> {
+    match value {
+        Some(value) if value <= 0 => Err(bad_value(format!("{name} must be positive"))),
+        _ => Ok(()),
+    }
+}
+
+fn check_range(name: &str, value: Option<f64>, min: f64, max: f64) -> XmpResult<()> {
+    match value {
+        Some(value) if !(min..=max).contains(&value) => {
+            Err(bad_value(format!("{name} must be between {min} and {max}")))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn bad_value(debug_message: String) -> XmpError {
+    XmpError {
+        error_type: XmpErrorType::BadValue,
+        debug_message,
+    }
+}
@@ -0,0 +1,201 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Contains utility functions for generating the unique identifiers
+//! typically stored in `xmpMM:DocumentID`, `xmpMM:InstanceID`, and
+//! related properties.
+//!
+//! Use [`DeterministicMode`] in tests to make these functions (and
+//! [`XmpDateTime::current`]) return predictable values.
+
+use std::{
+    cell::RefCell,
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    marker::PhantomData,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::XmpDateTime;
+
+/// Generates a new random (version 4) UUID, formatted as
+/// lowercase hexadecimal digits in the usual 8-4-4-4-12 grouping.
+///
+/// When a [`DeterministicMode`] guard is active on the current thread,
+/// this instead returns the next UUID in a predictable sequence.
+///
+/// # Example
+/// ```
+/// # use xmp_toolkit::xmp_id;
+/// let id = xmp_id::new_uuid();
+/// assert_eq!(id.len(), 36);
+/// assert_eq!(&id[14..15], "4");
+/// ```
+pub fn new_uuid() -> String {
+    let bits = DETERMINISTIC
+        .with(|state| {
+            state.borrow_mut().as_mut().map(|state| {
+                state.next_id += 1;
+                state.next_id as u128
+            })
+        })
+        .unwrap_or_else(random_bits);
+
+    format_uuid(bits)
+}
+
+/// Generates a new value suitable for `xmpMM:DocumentID`, in the form
+/// `xmp.did:<uuid>`.
+///
+/// # Example
+/// ```
+/// # use xmp_toolkit::xmp_id;
+/// assert!(xmp_id::new_document_id().starts_with("xmp.did:"));
+/// ```
+pub fn new_document_id() -> String {
+    format!("xmp.did:{}", new_uuid())
+}
+
+/// Generates a new value suitable for `xmpMM:InstanceID`, in the form
+/// `xmp.iid:<uuid>`.
+///
+/// # Example
+/// ```
+/// # use xmp_toolkit::xmp_id;
+/// assert!(xmp_id::new_instance_id().starts_with("xmp.iid:"));
+/// ```
+pub fn new_instance_id() -> String {
+    format!("xmp.iid:{}", new_uuid())
+}
+
+/// While an instance of this struct is alive, the current thread generates
+/// deterministic time stamps and identifiers.
+///
+/// Specifically:
+///
+/// * [`XmpDateTime::current`] returns the frozen time given when the guard was
+///   created.
+/// * [`new_uuid`], [`new_document_id`], and [`new_instance_id`] return values
+///   from a sequence that starts over for each guard
+///   (`00000000-0000-4000-8000-000000000001`,
+///   `00000000-0000-4000-8000-000000000002`, and so on).
+///
+/// This is intended for snapshot tests of serialized XMP, which would
+/// otherwise differ on each run. The override is per-thread, so tests
+/// running in parallel do not interfere with each other. Guards may be
+/// nested; dropping a guard restores whatever mode was in effect before it
+/// was created.
+///
+/// # Example
+/// ```
+/// # use xmp_toolkit::{xmp_id::{self, DeterministicMode}, XmpDate, XmpDateTime};
+/// let frozen = XmpDateTime {
+///     date: Some(XmpDate {
+///         year: 2026,
+///         month: 1,
+///         day: 2,
+///     }),
+///     time: None,
+/// };
+/// let _guard = DeterministicMode::new(frozen.clone());
+///
+/// assert_eq!(XmpDateTime::current().unwrap(), frozen);
+/// assert_eq!(
+///     xmp_id::new_document_id(),
+///     "xmp.did:00000000-0000-4000-8000-000000000001"
+/// );
+/// ```
+#[must_use = "deterministic mode ends as soon as the guard is dropped"]
+pub struct DeterministicMode {
+    previous: Option<DeterministicState>,
+
+    // Thread-local state; the guard must be dropped on the thread
+    // that created it.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl DeterministicMode {
+    /// Enables deterministic mode on the current thread, with
+    /// [`XmpDateTime::current`] returning `now`.
+    pub fn new(now: XmpDateTime) -> Self {
+        let previous = DETERMINISTIC.with(|state| {
+            state
+                .borrow_mut()
+                .replace(DeterministicState { now, next_id: 0 })
+        });
+
+        Self {
+            previous,
+            _not_send: PhantomData,
+        }
+    }
+}
+
+impl Drop for DeterministicMode {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        DETERMINISTIC.with(|state| *state.borrow_mut() = previous);
+    }
+}
+
+struct DeterministicState {
+    now: XmpDateTime,
+    next_id: u64,
+}
+
+thread_local! {
+    static DETERMINISTIC: RefCell<Option<DeterministicState>> = const { RefCell::new(None) };
+}
+
+/// Returns the frozen time if deterministic mode is active on this thread.
+pub(crate) fn frozen_now() -> Option<XmpDateTime> {
+    DETERMINISTIC.with(|state| state.borrow().as_ref().map(|state| state.now.clone()))
+}
+
+fn random_bits() -> u128 {
+    // Avoid taking a dependency on a random number crate: `RandomState` is
+    // seeded randomly per process and perturbed per instance, which we
+    // further mix with the clock and a counter.
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+
+    let mut halves = [0u64; 2];
+    for half in halves.iter_mut() {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u128(nanos);
+        hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+        *half = hasher.finish();
+    }
+
+    ((halves[0] as u128) << 64) | halves[1] as u128
+}
+
+fn format_uuid(bits: u128) -> String {
+    // Stamp version 4 and the RFC 4122 variant.
+    let bits = (bits & !(0xf << 76) & !(0x3 << 62)) | (0x4 << 76) | (0x2 << 62);
+    let hex = format!("{bits:032x}");
+
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
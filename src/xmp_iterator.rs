@@ -21,6 +21,42 @@ use crate::{
 /// Iterator over an XMP data model or a subset thereof.
 ///
 /// Create via [`XmpMeta::iter`].
+///
+/// The subtree below the node just returned, or that subtree along with
+/// the rest of its siblings, can be pruned from the iteration with
+/// [`XmpIterator::skip_subtree`] and [`XmpIterator::skip_siblings`]. The
+/// skipped nodes are not visited at all, so this is much cheaper than
+/// filtering them out afterwards. Since a `for` loop borrows the iterator,
+/// use `while let` to call these while iterating:
+///
+/// ```
+/// # use xmp_toolkit::{xmp_ns, IterOptions, XmpMeta, XmpValue};
+/// let mut meta = XmpMeta::new().unwrap();
+/// meta.set_property(xmp_ns::XMP, "Label", &"Final".into())
+///     .unwrap();
+/// for i in 0..1000 {
+///     let id = format!("xmp.did:{i}");
+///     meta.append_array_item(
+///         xmp_ns::PHOTOSHOP,
+///         &XmpValue::from("DocumentAncestors").set_is_array(true),
+///         &id.into(),
+///     )
+///     .unwrap();
+/// }
+///
+/// let mut iter = meta.iter(IterOptions::default());
+/// let mut visited = 0;
+///
+/// while let Some(prop) = iter.next() {
+///     if prop.name == "photoshop:DocumentAncestors" {
+///         iter.skip_subtree();
+///     }
+///     visited += 1;
+/// }
+///
+/// // Two schema nodes and two properties; none of the array items.
+/// assert_eq!(visited, 4);
+/// ```
 pub struct XmpIterator<'a> {
     #[allow(dead_code)]
     pub(crate) m: &'a XmpMeta,
@@ -44,6 +80,7 @@ impl<'a> XmpIterator<'a> {
                         c_schema_ns.as_ptr(),
                         c_prop_name.as_ptr(),
                         options.options,
+                        options.kinds,
                     ),
                 }
             }
@@ -55,7 +92,13 @@ impl<'a> XmpIterator<'a> {
         }
     }
 
-    /// Skip the subtree below the current node.
+    /// Skip the subtree below the current node, that is, the node most
+    /// recently returned by [`Iterator::next`].
+    ///
+    /// The next node returned is the current node's next sibling, or the
+    /// next node after its parent if it has none.
+    ///
+    /// See `kXMP_IterSkipSubtree` in the C++ SDK.
     pub fn skip_subtree(&mut self) {
         if !self.i.is_null() {
             unsafe {
@@ -65,7 +108,14 @@ impl<'a> XmpIterator<'a> {
         }
     }
 
-    /// Skip the subtree below and remaining siblings of the current node.
+    /// Skip the subtree below and remaining siblings of the current node,
+    /// that is, the node most recently returned by [`Iterator::next`].
+    ///
+    /// Skipping the siblings of a top-level property prunes the rest of
+    /// its schema. The next node returned is the next node after the
+    /// current node's parent.
+    ///
+    /// See `kXMP_IterSkipSiblings` in the C++ SDK.
     pub fn skip_siblings(&mut self) {
         if !self.i.is_null() {
             unsafe {
@@ -138,12 +188,20 @@ pub struct IterOptions {
     pub(crate) schema_ns: String,
     pub(crate) prop_name: String,
     pub(crate) options: u32,
+
+    // Node kind filters, applied in `CXmpIteratorNext`. These bits must
+    // match the `kIterKind...` values in ffi.cpp.
+    pub(crate) kinds: u32,
 }
 
 impl IterOptions {
     const JUST_CHILDREN: u32 = 0x0100;
     const JUST_LEAF_NAME: u32 = 0x0400;
     const JUST_LEAF_NODES: u32 = 0x0200;
+    const KIND_ARRAYS: u32 = 0x1;
+    const KIND_QUALIFIED: u32 = 0x8;
+    const KIND_SIMPLE_VALUES: u32 = 0x4;
+    const KIND_STRUCTS: u32 = 0x2;
     const OMIT_QUALIFIERS: u32 = 0x1000;
 
     /// Restrict iteration to those properties that are part of the named
@@ -184,6 +242,53 @@ impl IterOptions {
         self.options |= Self::OMIT_QUALIFIERS;
         self
     }
+
+    /// Return only array nodes.
+    ///
+    /// This and the other kind filters ([`IterOptions::structs_only`],
+    /// [`IterOptions::simple_values_only`], and
+    /// [`IterOptions::qualified_only`]) can be combined; nodes of any of
+    /// the requested kinds are then returned. Schema nodes are not
+    /// returned when a kind filter is set.
+    ///
+    /// The other nodes are still visited, but skipped without being
+    /// copied from the C++ XMP Toolkit, which is cheaper than filtering
+    /// the iterator. Their children are still visited; use
+    /// [`XmpIterator::skip_subtree`] to prune them.
+    pub fn arrays_only(mut self) -> Self {
+        self.kinds |= Self::KIND_ARRAYS;
+        self
+    }
+
+    /// Return only struct nodes.
+    ///
+    /// See [`IterOptions::arrays_only`] for how kind filters combine.
+    pub fn structs_only(mut self) -> Self {
+        self.kinds |= Self::KIND_STRUCTS;
+        self
+    }
+
+    /// Return only simple values, including array items and struct
+    /// fields, but not arrays, structs, or schema nodes.
+    ///
+    /// Unlike [`IterOptions::leaf_nodes_only`], this includes simple
+    /// values that have qualifiers, and excludes empty arrays and structs.
+    /// Qualifiers are simple values as well; combine with
+    /// [`IterOptions::omit_qualifiers`] to leave them out.
+    ///
+    /// See [`IterOptions::arrays_only`] for how kind filters combine.
+    pub fn simple_values_only(mut self) -> Self {
+        self.kinds |= Self::KIND_SIMPLE_VALUES;
+        self
+    }
+
+    /// Return only nodes that have qualifiers.
+    ///
+    /// See [`IterOptions::arrays_only`] for how kind filters combine.
+    pub fn qualified_only(mut self) -> Self {
+        self.kinds |= Self::KIND_QUALIFIED;
+        self
+    }
 }
 
 /// Value of a single property found via iterating the XMP data model.
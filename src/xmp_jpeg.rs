@@ -0,0 +1,306 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::ops::Range;
+
+use crate::{XmpError, XmpErrorType, XmpResult};
+
+/// The signature that starts a JPEG `APP1` segment containing the
+/// standard XMP packet.
+pub(crate) const STANDARD_SIGNATURE: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+
+/// The signature that starts a JPEG `APP1` segment containing a portion
+/// of the extended XMP packet.
+pub(crate) const EXTENDED_SIGNATURE: &[u8] = b"http://ns.adobe.com/xmp/extension/\0";
+
+/// The signature that starts a JPEG `APP1` segment containing EXIF.
+const EXIF_SIGNATURE: &[u8] = b"Exif\0\0";
+
+/// The signature that starts a JPEG `APP13` segment containing Photoshop
+/// image resources, including IPTC.
+const PHOTOSHOP_SIGNATURE: &[u8] = b"Photoshop 3.0\0";
+
+/// Length of the GUID that identifies the extended XMP packet.
+const GUID_LEN: usize = 32;
+
+/// Length of the header that precedes each portion of the extended XMP
+/// packet: signature, GUID, full length, and offset.
+const EXTENDED_HEADER_LEN: usize = EXTENDED_SIGNATURE.len() + GUID_LEN + 4 + 4;
+
+/// Largest portion of the extended XMP packet written to one segment.
+///
+/// An `APP1` segment holds at most 65,533 bytes after its length field,
+/// which leaves 65,458 bytes after the extended XMP header.
+const MAX_EXTENDED_PORTION: usize = 65000;
+
+/// The XMP packets to be written to a JPEG file, as described in part 3
+/// of the XMP specification.
+///
+/// A JPEG `APP1` segment can hold at most 64 KB, so metadata that does
+/// not fit is split into a _standard_ packet and an _extended_ packet. The
+/// extended packet is identified by a GUID (the MD5 digest of the extended
+/// packet) which is also stored in the `xmpNote:HasExtendedXMP` property
+/// of the standard packet.
+///
+/// Create via [`XmpMeta::split_for_jpeg`]. Rejoin the packets via
+/// [`XmpMeta::join_extended`].
+///
+/// [`XmpMeta::split_for_jpeg`]: crate::XmpMeta::split_for_jpeg
+/// [`XmpMeta::join_extended`]: crate::XmpMeta::join_extended
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct JpegXmpPackets {
+    /// The standard XMP packet, including the packet wrapper.
+    pub standard: String,
+
+    /// The extended XMP packet, without a packet wrapper, or `None` if all
+    /// of the metadata fits in the standard packet.
+    pub extended: Option<String>,
+
+    /// The GUID of the extended XMP packet: 32 uppercase hexadecimal
+    /// digits. `None` if there is no extended packet.
+    pub guid: Option<String>,
+}
+
+impl JpegXmpPackets {
+    /// Returns the content of the `APP1` segment holding the standard
+    /// packet, not including the segment marker and length.
+    pub fn standard_segment(&self) -> Vec<u8> {
+        let mut segment = Vec::with_capacity(STANDARD_SIGNATURE.len() + self.standard.len());
+        segment.extend_from_slice(STANDARD_SIGNATURE);
+        segment.extend_from_slice(self.standard.as_bytes());
+        segment
+    }
+
+    /// Returns the content of the `APP1` segments holding the extended
+    /// packet, not including the segment markers and lengths.
+    ///
+    /// Each segment starts with the extended XMP signature, the GUID, the
+    /// full length of the extended packet, and the offset of this portion
+    /// (both as big-endian 32-bit integers).
+    ///
+    /// Returns an empty `Vec` if there is no extended packet.
+    pub fn extended_segments(&self) -> Vec<Vec<u8>> {
+        let (Some(extended), Some(guid)) = (&self.extended, &self.guid) else {
+            return Vec::new();
+        };
+
+        let extended = extended.as_bytes();
+        let full_len = extended.len() as u32;
+
+        extended
+            .chunks(MAX_EXTENDED_PORTION)
+            .enumerate()
+            .map(|(index, portion)| {
+                let offset = (index * MAX_EXTENDED_PORTION) as u32;
+
+                let mut segment = Vec::with_capacity(EXTENDED_HEADER_LEN + portion.len());
+                segment.extend_from_slice(EXTENDED_SIGNATURE);
+                segment.extend_from_slice(guid.as_bytes());
+                segment.extend_from_slice(&full_len.to_be_bytes());
+                segment.extend_from_slice(&offset.to_be_bytes());
+                segment.extend_from_slice(portion);
+                segment
+            })
+            .collect()
+    }
+
+    /// Reassembles the extended XMP packet from the content of a JPEG
+    /// file's `APP1` segments.
+    ///
+    /// `guid` is the value of the `xmpNote:HasExtendedXMP` property in the
+    /// standard packet. Segments that aren't extended XMP, or that belong
+    /// to an extended packet with a different GUID, are ignored. The
+    /// portions may appear in any order.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::BadJpeg`] if no portion of
+    /// the extended packet is found, if the portions don't cover the full
+    /// length of the packet, or if they disagree about its length.
+    pub fn reassemble_extended<'a>(
+        guid: &str,
+        segments: impl IntoIterator<Item = &'a [u8]>,
+    ) -> XmpResult<String> {
+        let mut full_len: Option<usize> = None;
+        let mut portions: Vec<(usize, &[u8])> = Vec::new();
+
+        for segment in segments {
+            let Some(header) = segment.get(..EXTENDED_HEADER_LEN) else {
+                continue;
+            };
+
+            let (signature, rest) = header.split_at(EXTENDED_SIGNATURE.len());
+            let (segment_guid, rest) = rest.split_at(GUID_LEN);
+            if signature != EXTENDED_SIGNATURE || segment_guid != guid.as_bytes() {
+                continue;
+            }
+
+            let (len, offset) = rest.split_at(4);
+            let len = u32::from_be_bytes([len[0], len[1], len[2], len[3]]) as usize;
+            let offset = u32::from_be_bytes([offset[0], offset[1], offset[2], offset[3]]) as usize;
+
+            if full_len.is_some_and(|full_len| full_len != len) {
+                return Err(bad_jpeg("extended XMP portions disagree about length"));
+            }
+
+            full_len = Some(len);
+            portions.push((offset, &segment[EXTENDED_HEADER_LEN..]));
+        }
+
+        let Some(full_len) = full_len else {
+            return Err(bad_jpeg("extended XMP not found"));
+        };
+
+        portions.sort_by_key(|(offset, _)| *offset);
+
+        let mut extended = Vec::with_capacity(full_len);
+        for (offset, portion) in portions {
+            if offset != extended.len() {
+                return Err(bad_jpeg("extended XMP is incomplete"));
+            }
+
+            extended.extend_from_slice(portion);
+        }
+
+        if extended.len() != full_len {
+            return Err(bad_jpeg("extended XMP is incomplete"));
+        }
+
+        String::from_utf8(extended).map_err(|_| XmpError {
+            error_type: XmpErrorType::BadUnicode,
+            debug_message: "extended XMP is not valid UTF-8".to_owned(),
+        })
+    }
+}
+
+/// Returns `true` if `file` starts like a JPEG file.
+pub(crate) fn is_jpeg(file: &[u8]) -> bool {
+    file.starts_with(&[0xff, 0xd8, 0xff])
+}
+
+/// Returns the EXIF and Photoshop (IPTC) segments of `jpeg`, including
+/// their markers and lengths, in file order.
+pub(crate) fn legacy_segments(jpeg: &[u8]) -> XmpResult<Vec<Vec<u8>>> {
+    Ok(header_segments(jpeg)?
+        .into_iter()
+        .filter(|segment| is_legacy_segment(jpeg, segment))
+        .map(|segment| jpeg[segment].to_vec())
+        .collect())
+}
+
+/// Returns a copy of `jpeg` in which the EXIF and Photoshop (IPTC)
+/// segments are replaced by `legacy`, as returned by [`legacy_segments`].
+///
+/// `legacy` is inserted where the first of the replaced segments was or,
+/// if there was none, after the leading `APP0` (JFIF) segments. Other
+/// segments are kept in place.
+pub(crate) fn replace_legacy_segments(jpeg: &[u8], legacy: &[Vec<u8>]) -> XmpResult<Vec<u8>> {
+    let segments = header_segments(jpeg)?;
+
+    let insert_at = match segments
+        .iter()
+        .find(|segment| is_legacy_segment(jpeg, segment))
+    {
+        Some(segment) => segment.start,
+        None => segments
+            .iter()
+            .take_while(|segment| jpeg[segment.start + 1] == 0xe0)
+            .last()
+            .map_or(2, |segment| segment.end),
+    };
+
+    let mut out = Vec::with_capacity(jpeg.len() + legacy.iter().map(Vec::len).sum::<usize>());
+    out.extend_from_slice(&jpeg[..insert_at]);
+    legacy
+        .iter()
+        .for_each(|segment| out.extend_from_slice(segment));
+
+    let mut pos = insert_at;
+    for segment in &segments {
+        if segment.start >= insert_at && is_legacy_segment(jpeg, segment) {
+            out.extend_from_slice(&jpeg[pos..segment.start]);
+            pos = segment.end;
+        }
+    }
+
+    out.extend_from_slice(&jpeg[pos..]);
+    Ok(out)
+}
+
+/// Returns the location of each marker segment of `jpeg` that precedes
+/// the image data, including its marker and length.
+fn header_segments(jpeg: &[u8]) -> XmpResult<Vec<Range<usize>>> {
+    if !is_jpeg(jpeg) {
+        return Err(bad_jpeg("not a JPEG file"));
+    }
+
+    let mut segments = Vec::new();
+    let mut pos = 2;
+
+    loop {
+        // A marker may be preceded by any number of fill bytes.
+        while jpeg.get(pos..pos + 2) == Some(&[0xff, 0xff][..]) {
+            pos += 1;
+        }
+
+        let (Some(0xff), Some(&marker)) = (jpeg.get(pos), jpeg.get(pos + 1)) else {
+            return Err(bad_jpeg("marker expected before the image data"));
+        };
+
+        match marker {
+            // Start of scan or end of image.
+            0xda | 0xd9 => return Ok(segments),
+
+            // Markers without a length.
+            0x01 | 0xd0..=0xd7 => {
+                segments.push(pos..pos + 2);
+                pos += 2;
+            }
+
+            _ => {
+                let Some(&[high, low]) = jpeg.get(pos + 2..pos + 4) else {
+                    return Err(bad_jpeg("segment length missing"));
+                };
+
+                let len = u16::from_be_bytes([high, low]) as usize;
+                let end = pos + 2 + len;
+                if len < 2 || end > jpeg.len() {
+                    return Err(bad_jpeg("segment extends past the end of the file"));
+                }
+
+                segments.push(pos..end);
+                pos = end;
+            }
+        }
+    }
+}
+
+fn is_legacy_segment(jpeg: &[u8], segment: &Range<usize>) -> bool {
+    let Some(data) = jpeg.get(segment.start + 4..segment.end) else {
+        return false;
+    };
+
+    match jpeg[segment.start + 1] {
+        0xe1 => data.starts_with(EXIF_SIGNATURE),
+        0xed => data.starts_with(PHOTOSHOP_SIGNATURE),
+        _ => false,
+    }
+}
+
+fn bad_jpeg(message: &str) -> XmpError {
+    XmpError {
+        error_type: XmpErrorType::BadJpeg,
+        debug_message: message.to_owned(),
+    }
+}
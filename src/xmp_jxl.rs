@@ -0,0 +1,119 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+// Reads and writes the XMP of JPEG XL files.
+//
+// A JPEG XL file is either a bare codestream or an ISO BMFF-style
+// container (ISO/IEC 18181-2) that starts with a `JXL ` signature box.
+// Only the container can hold metadata: the XMP packet is the payload of
+// an `xml ` box. A bare codestream is wrapped in a container (as a `jxlc`
+// box) when XMP is added.
+//
+// Metadata boxes may also be Brotli-compressed (`brob` boxes). This
+// handler doesn't read compressed XMP, but replaces it when writing.
+
+use std::ops::Range;
+
+use crate::{
+    xmp_bmff::{boxes, push_box, BmffBox},
+    xmp_file_handler::{bad_file, FileHandler},
+    FileFormat, XmpResult,
+};
+
+const SIGNATURE_BOX: &[u8] = b"\0\0\0\x0cJXL \x0d\x0a\x87\x0a";
+const CODESTREAM: &[u8] = &[0xff, 0x0a];
+
+const FTYP: &[u8; 4] = b"ftyp";
+const XML: &[u8; 4] = b"xml ";
+const BROB: &[u8; 4] = b"brob";
+const JXLC: &[u8; 4] = b"jxlc";
+const JXLP: &[u8; 4] = b"jxlp";
+
+pub(crate) struct JxlHandler;
+
+impl FileHandler for JxlHandler {
+    fn format(&self) -> FileFormat {
+        FileFormat::JpegXl
+    }
+
+    fn check_format(&self, header: &[u8]) -> bool {
+        header.starts_with(SIGNATURE_BOX) || header.starts_with(CODESTREAM)
+    }
+
+    fn read_xmp(&self, file: &[u8]) -> XmpResult<Option<Range<usize>>> {
+        if file.starts_with(CODESTREAM) {
+            return Ok(None);
+        }
+
+        Ok(jxl_boxes(file)?
+            .into_iter()
+            .find(|b| &b.box_type == XML)
+            .map(|b| b.data))
+    }
+
+    fn write_xmp(&self, file: &[u8], packet: &[u8]) -> XmpResult<Vec<u8>> {
+        let mut out = Vec::with_capacity(file.len() + packet.len() + 64);
+
+        if file.starts_with(CODESTREAM) {
+            out.extend_from_slice(SIGNATURE_BOX);
+            push_box(&mut out, FTYP, &[b"jxl ", &[0; 4], b"jxl "])?;
+            push_box(&mut out, XML, &[packet])?;
+            push_box(&mut out, JXLC, &[file])?;
+            return Ok(out);
+        }
+
+        let boxes = jxl_boxes(file)?;
+
+        // Put the new packet where the old one was or, failing that, ahead
+        // of the image data so that it can be read without reading the
+        // whole file.
+        let insert_at = boxes
+            .iter()
+            .position(|b| is_xmp_box(file, b))
+            .or_else(|| {
+                boxes
+                    .iter()
+                    .position(|b| &b.box_type == JXLC || &b.box_type == JXLP)
+            })
+            .unwrap_or(boxes.len());
+
+        for (index, b) in boxes.iter().enumerate() {
+            if index == insert_at {
+                push_box(&mut out, XML, &[packet])?;
+            }
+
+            if !is_xmp_box(file, b) {
+                out.extend_from_slice(&file[b.range.clone()]);
+            }
+        }
+
+        if insert_at == boxes.len() {
+            push_box(&mut out, XML, &[packet])?;
+        }
+
+        Ok(out)
+    }
+}
+
+fn jxl_boxes(file: &[u8]) -> XmpResult<Vec<BmffBox>> {
+    if !file.starts_with(SIGNATURE_BOX) {
+        return Err(bad_file("JPEG XL", "Not a JPEG XL file"));
+    }
+
+    boxes(file, 0, "JPEG XL")
+}
+
+/// Returns `true` for an `xml ` box, compressed or not.
+fn is_xmp_box(file: &[u8], b: &BmffBox) -> bool {
+    &b.box_type == XML || (&b.box_type == BROB && file[b.data.clone()].starts_with(XML))
+}
@@ -0,0 +1,203 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::fmt;
+
+/// A tree of hierarchical keywords, as stored by Lightroom and other
+/// applications in the `lr:hierarchicalSubject` property.
+///
+/// Each entry in `lr:hierarchicalSubject` is a path of keywords separated
+/// by `|`, such as `Animals|Birds|Heron`. A `KeywordTree` merges these
+/// paths into a tree with one node per distinct keyword at each level.
+///
+/// Read and write keyword trees with [`XmpMeta::hierarchical_keywords`]
+/// and [`XmpMeta::set_hierarchical_keywords`].
+///
+/// ## Example
+///
+/// ```
+/// use xmp_toolkit::KeywordTree;
+///
+/// let tree = KeywordTree::from_paths(["Animals|Birds|Heron", "Animals|Mammals"]);
+///
+/// assert_eq!(tree.roots().len(), 1);
+/// assert_eq!(tree.roots()[0].name, "Animals");
+/// assert_eq!(tree.roots()[0].children.len(), 2);
+/// assert_eq!(tree.paths(), ["Animals|Birds|Heron", "Animals|Mammals"]);
+/// ```
+///
+/// [`XmpMeta::hierarchical_keywords`]: crate::XmpMeta::hierarchical_keywords
+/// [`XmpMeta::set_hierarchical_keywords`]: crate::XmpMeta::set_hierarchical_keywords
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct KeywordTree {
+    roots: Vec<KeywordNode>,
+}
+
+/// A single keyword in a [`KeywordTree`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct KeywordNode {
+    /// The keyword.
+    pub name: String,
+
+    /// More specific keywords beneath this one, in the order in which they
+    /// were first seen.
+    pub children: Vec<KeywordNode>,
+}
+
+impl KeywordTree {
+    /// The character that separates the levels of a keyword path.
+    pub const SEPARATOR: char = '|';
+
+    /// Creates an empty keyword tree.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a keyword tree from `|`-separated keyword paths.
+    ///
+    /// See [`KeywordTree::insert`].
+    pub fn from_paths<I, S>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut tree = Self::new();
+        for path in paths {
+            tree.insert(path.as_ref());
+        }
+        tree
+    }
+
+    /// Adds a `|`-separated keyword path to the tree.
+    ///
+    /// Keywords are trimmed of surrounding whitespace and empty levels are
+    /// ignored, so `" Animals || Birds"` is the same as `"Animals|Birds"`.
+    /// Keywords that already exist at the same level are reused.
+    pub fn insert(&mut self, path: &str) {
+        let mut level = &mut self.roots;
+
+        for name in split_path(path) {
+            let index = match level.iter().position(|node| node.name == name) {
+                Some(index) => index,
+                None => {
+                    level.push(KeywordNode {
+                        name: name.to_owned(),
+                        children: vec![],
+                    });
+                    level.len() - 1
+                }
+            };
+
+            level = &mut level[index].children;
+        }
+    }
+
+    /// Returns `true` if the tree contains `path` (either as a leaf or as
+    /// an ancestor of a more specific keyword).
+    pub fn contains(&self, path: &str) -> bool {
+        let mut level = &self.roots;
+        let mut found = false;
+
+        for name in split_path(path) {
+            match level.iter().find(|node| node.name == name) {
+                Some(node) => {
+                    level = &node.children;
+                    found = true;
+                }
+                None => return false,
+            }
+        }
+
+        found
+    }
+
+    /// Returns the top-level keywords.
+    pub fn roots(&self) -> &[KeywordNode] {
+        &self.roots
+    }
+
+    /// Returns `true` if the tree contains no keywords.
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Returns the `|`-separated path to each leaf of the tree, in
+    /// depth-first order.
+    ///
+    /// This is the form in which the tree is stored in
+    /// `lr:hierarchicalSubject`.
+    pub fn paths(&self) -> Vec<String> {
+        let mut paths = vec![];
+        for node in &self.roots {
+            node.collect_paths("", &mut paths);
+        }
+        paths
+    }
+
+    /// Returns every distinct keyword in the tree, at any level, in
+    /// depth-first order.
+    ///
+    /// This is the form in which the tree is mirrored into the flat
+    /// `dc:subject` property.
+    pub fn keywords(&self) -> Vec<String> {
+        let mut keywords: Vec<String> = vec![];
+        for node in &self.roots {
+            node.collect_keywords(&mut keywords);
+        }
+        keywords
+    }
+}
+
+impl KeywordNode {
+    fn collect_paths(&self, prefix: &str, paths: &mut Vec<String>) {
+        let path = if prefix.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{prefix}{}{}", KeywordTree::SEPARATOR, self.name)
+        };
+
+        if self.children.is_empty() {
+            paths.push(path);
+        } else {
+            for child in &self.children {
+                child.collect_paths(&path, paths);
+            }
+        }
+    }
+
+    fn collect_keywords(&self, keywords: &mut Vec<String>) {
+        if !keywords.contains(&self.name) {
+            keywords.push(self.name.clone());
+        }
+
+        for child in &self.children {
+            child.collect_keywords(keywords);
+        }
+    }
+}
+
+impl fmt::Display for KeywordTree {
+    /// Writes one leaf path per line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for path in self.paths() {
+            writeln!(f, "{path}")?;
+        }
+        Ok(())
+    }
+}
+
+fn split_path(path: &str) -> impl Iterator<Item = &str> {
+    path.split(KeywordTree::SEPARATOR)
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+}
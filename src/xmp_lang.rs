@@ -0,0 +1,243 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Contains functions for checking and normalizing the language tags used
+//! in `xml:lang` qualifiers.
+//!
+//! XMP uses [BCP 47](https://www.rfc-editor.org/info/bcp47) language tags
+//! to identify the language of localized text. This module checks that a
+//! tag is well-formed according to the syntax in
+//! [RFC 5646](https://www.rfc-editor.org/rfc/rfc5646#section-2.1); it does
+//! not check that the subtags are registered.
+//!
+//! By default, the C++ XMP Toolkit accepts any tag and only normalizes its
+//! capitalization. Use [`XmpMeta::set_strict_lang_tags`] to have malformed
+//! tags rejected instead.
+//!
+//! [`XmpMeta::set_strict_lang_tags`]: crate::XmpMeta::set_strict_lang_tags
+
+use crate::{XmpError, XmpErrorType, XmpResult};
+
+/// The default language of an alt-text array, as defined by the XMP
+/// specification.
+pub const X_DEFAULT: &str = "x-default";
+
+/// Tags from RFC 5646 that predate its syntax but remain well-formed.
+const GRANDFATHERED: &[&str] = &[
+    "en-GB-oed",
+    "i-ami",
+    "i-bnn",
+    "i-default",
+    "i-enochian",
+    "i-hak",
+    "i-klingon",
+    "i-lux",
+    "i-mingo",
+    "i-navajo",
+    "i-pwn",
+    "i-tao",
+    "i-tay",
+    "i-tsu",
+    "sgn-BE-FR",
+    "sgn-BE-NL",
+    "sgn-CH-DE",
+    "art-lojban",
+    "cel-gaulish",
+    "no-bok",
+    "no-nyn",
+    "zh-guoyu",
+    "zh-hakka",
+    "zh-min",
+    "zh-min-nan",
+    "zh-xiang",
+];
+
+/// Returns `true` if `tag` is a well-formed BCP 47 language tag or
+/// [`X_DEFAULT`].
+///
+/// Case is not significant.
+///
+/// ## Example
+///
+/// ```
+/// use xmp_toolkit::xmp_lang;
+///
+/// assert!(xmp_lang::is_well_formed("de-CH"));
+/// assert!(xmp_lang::is_well_formed("zh-Hant-TW"));
+/// assert!(xmp_lang::is_well_formed("x-default"));
+///
+/// assert!(!xmp_lang::is_well_formed("en_US"));
+/// assert!(!xmp_lang::is_well_formed("e"));
+/// assert!(!xmp_lang::is_well_formed("en-"));
+/// ```
+pub fn is_well_formed(tag: &str) -> bool {
+    if GRANDFATHERED.iter().any(|g| g.eq_ignore_ascii_case(tag)) {
+        return true;
+    }
+
+    let subtags: Vec<&str> = tag.split('-').collect();
+    if subtags
+        .iter()
+        .any(|s| s.is_empty() || s.len() > 8 || !s.bytes().all(|b| b.is_ascii_alphanumeric()))
+    {
+        return false;
+    }
+
+    let n = subtags.len();
+    let len_is = |i: usize, len: usize| i < n && subtags[i].len() == len;
+
+    // A tag may consist of a private use sequence alone.
+    if subtags[0].eq_ignore_ascii_case("x") {
+        return n > 1;
+    }
+
+    let language = subtags[0];
+    if !is_alpha(language) || language.len() < 2 {
+        return false;
+    }
+
+    let mut i = 1;
+
+    // Extended language subtags may follow a 2- or 3-letter language.
+    if language.len() <= 3 {
+        let start = i;
+        while i - start < 3 && len_is(i, 3) && is_alpha(subtags[i]) {
+            i += 1;
+        }
+    }
+
+    // Script
+    if len_is(i, 4) && is_alpha(subtags[i]) {
+        i += 1;
+    }
+
+    // Region
+    if (len_is(i, 2) && is_alpha(subtags[i])) || (len_is(i, 3) && is_digit(subtags[i])) {
+        i += 1;
+    }
+
+    // Variants
+    while i < n && is_variant(subtags[i]) {
+        i += 1;
+    }
+
+    // Extensions
+    while len_is(i, 1) && !subtags[i].eq_ignore_ascii_case("x") {
+        i += 1;
+        let start = i;
+        while i < n && subtags[i].len() >= 2 {
+            i += 1;
+        }
+
+        if i == start {
+            return false;
+        }
+    }
+
+    // Private use
+    if len_is(i, 1) && subtags[i].eq_ignore_ascii_case("x") {
+        return i + 1 < n;
+    }
+
+    i == n
+}
+
+/// Checks that `tag` is well-formed (see [`is_well_formed`]) and returns
+/// it with the capitalization used by the C++ XMP Toolkit.
+///
+/// The XMP convention is that the primary subtag is lower case, a 2-letter
+/// second subtag (typically a region) is upper case, and all other subtags
+/// are lower case. This is how the toolkit stores `xml:lang` values, so
+/// the result of this function matches the language reported by functions
+/// such as [`XmpMeta::localized_text`].
+///
+/// ## Errors
+///
+/// Returns an error of type [`XmpErrorType::BadValue`] if `tag` is not
+/// well-formed.
+///
+/// ## Example
+///
+/// ```
+/// use xmp_toolkit::xmp_lang;
+///
+/// assert_eq!(xmp_lang::normalize("EN-us").unwrap(), "en-US");
+/// assert_eq!(xmp_lang::normalize("X-Default").unwrap(), "x-default");
+/// assert!(xmp_lang::normalize("en_US").is_err());
+/// ```
+///
+/// [`XmpMeta::localized_text`]: crate::XmpMeta::localized_text
+pub fn normalize(tag: &str) -> XmpResult<String> {
+    if !is_well_formed(tag) {
+        return Err(malformed(tag));
+    }
+
+    Ok(tag
+        .split('-')
+        .enumerate()
+        .map(|(index, subtag)| {
+            if index == 1 && subtag.len() == 2 {
+                subtag.to_ascii_uppercase()
+            } else {
+                subtag.to_ascii_lowercase()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("-"))
+}
+
+/// Returns an error of type [`XmpErrorType::BadValue`] if `tag` is not
+/// well-formed.
+pub(crate) fn check(tag: &str) -> XmpResult<()> {
+    if is_well_formed(tag) {
+        Ok(())
+    } else {
+        Err(malformed(tag))
+    }
+}
+
+/// Returns the next, less specific language range to try when performing
+/// an RFC 4647 lookup, or `None` if `range` has only one subtag.
+///
+/// The last subtag is removed, along with a single-character subtag (such
+/// as an extension singleton or `x`) left at the end as a result.
+pub(crate) fn lookup_fallback(range: &str) -> Option<&str> {
+    let (mut rest, _) = range.rsplit_once('-')?;
+
+    if let Some((prefix, last)) = rest.rsplit_once('-') {
+        if last.len() == 1 {
+            rest = prefix;
+        }
+    }
+
+    Some(rest)
+}
+
+fn malformed(tag: &str) -> XmpError {
+    XmpError {
+        error_type: XmpErrorType::BadValue,
+        debug_message: format!("Malformed language tag {tag:?}"),
+    }
+}
+
+fn is_alpha(s: &str) -> bool {
+    s.bytes().all(|b| b.is_ascii_alphabetic())
+}
+
+fn is_digit(s: &str) -> bool {
+    s.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn is_variant(s: &str) -> bool {
+    s.len() >= 5 || (s.len() == 4 && s.as_bytes()[0].is_ascii_digit())
+}
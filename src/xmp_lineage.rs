@@ -0,0 +1,290 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Contains a builder for document lineage graphs.
+//!
+//! Every XMP-aware application records the identity of a document in
+//! `xmpMM:DocumentID` and `xmpMM:InstanceID`, and records where it came
+//! from in `xmpMM:DerivedFrom` and `xmpMM:Ingredients`. A
+//! [`LineageGraph`] stitches those references together across a set of
+//! documents, so that you can see which document was derived from which,
+//! and which references point to documents that are not in the set.
+
+use std::{borrow::Borrow, collections::HashMap};
+
+use crate::{xmp_ns, XmpMeta};
+
+/// The identity of one document in a [`LineageGraph`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LineageNode {
+    /// The value of `xmpMM:DocumentID`, if any.
+    pub document_id: Option<String>,
+
+    /// The value of `xmpMM:InstanceID`, if any.
+    pub instance_id: Option<String>,
+
+    /// The value of `xmpMM:OriginalDocumentID`, if any.
+    pub original_document_id: Option<String>,
+}
+
+/// The kind of reference that produced a [`LineageEdge`] or
+/// [`UnresolvedReference`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LineageRelation {
+    /// The document was derived from the source, as recorded in
+    /// `xmpMM:DerivedFrom`.
+    DerivedFrom,
+
+    /// The source was used as an ingredient of the document, as recorded in
+    /// `xmpMM:Ingredients`.
+    Ingredient,
+}
+
+/// A resolved reference from one document to another.
+///
+/// `derived` and `source` are indices into [`LineageGraph::nodes`], which
+/// are the same as the positions of the documents in the input.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct LineageEdge {
+    /// The document that contains the reference.
+    pub derived: usize,
+
+    /// The document that was referenced.
+    pub source: usize,
+
+    /// The kind of reference.
+    pub relation: LineageRelation,
+}
+
+/// A reference to a document that is not in the graph.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnresolvedReference {
+    /// Index into [`LineageGraph::nodes`] of the document that contains the
+    /// reference.
+    pub derived: usize,
+
+    /// The kind of reference.
+    pub relation: LineageRelation,
+
+    /// The referenced `stRef:documentID`, if any.
+    pub document_id: Option<String>,
+
+    /// The referenced `stRef:instanceID`, if any.
+    pub instance_id: Option<String>,
+}
+
+/// A graph of derivation relationships among a set of documents.
+///
+/// ## Example
+///
+/// ```
+/// # use xmp_toolkit::{xmp_lineage::LineageGraph, xmp_ns, XmpMeta};
+/// # fn main() -> xmp_toolkit::XmpResult<()> {
+/// let mut original = XmpMeta::new()?;
+/// original.set_property(xmp_ns::XMP_MM, "DocumentID", &"xmp.did:1".into())?;
+/// original.set_property(xmp_ns::XMP_MM, "InstanceID", &"xmp.iid:1".into())?;
+///
+/// let mut copy = XmpMeta::new()?;
+/// copy.set_property(xmp_ns::XMP_MM, "DocumentID", &"xmp.did:2".into())?;
+/// copy.set_struct_field(
+///     xmp_ns::XMP_MM,
+///     "DerivedFrom",
+///     xmp_ns::RESOURCE_REF,
+///     "instanceID",
+///     &"xmp.iid:1".into(),
+/// )?;
+///
+/// let graph = LineageGraph::build([&original, &copy]);
+///
+/// assert_eq!(graph.sources_of(1).collect::<Vec<_>>(), [0]);
+/// assert!(graph.unresolved().is_empty());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LineageGraph {
+    nodes: Vec<LineageNode>,
+    edges: Vec<LineageEdge>,
+    unresolved: Vec<UnresolvedReference>,
+}
+
+impl LineageGraph {
+    /// Builds a lineage graph from a set of documents.
+    ///
+    /// References are resolved by `stRef:instanceID` if present and found
+    /// in the set; otherwise by `stRef:documentID`. If several documents
+    /// share the referenced document ID, the reference resolves to the
+    /// first of them. A reference that can not be resolved is recorded as
+    /// an [`UnresolvedReference`]. A document's reference to itself is
+    /// ignored.
+    pub fn build<I, M>(metas: I) -> Self
+    where
+        I: IntoIterator<Item = M>,
+        M: Borrow<XmpMeta>,
+    {
+        let mut nodes = vec![];
+        let mut references = vec![];
+
+        for (index, meta) in metas.into_iter().enumerate() {
+            let meta = meta.borrow();
+
+            nodes.push(LineageNode {
+                document_id: mm_property(meta, "DocumentID"),
+                instance_id: mm_property(meta, "InstanceID"),
+                original_document_id: mm_property(meta, "OriginalDocumentID"),
+            });
+
+            if meta.contains_property(xmp_ns::XMP_MM, "DerivedFrom") {
+                references.push(read_reference(
+                    meta,
+                    index,
+                    "DerivedFrom",
+                    LineageRelation::DerivedFrom,
+                ));
+            }
+
+            for item in 1..=meta.array_len(xmp_ns::XMP_MM, "Ingredients") as i32 {
+                if let Ok(path) =
+                    XmpMeta::compose_array_item_path(xmp_ns::XMP_MM, "Ingredients", item)
+                {
+                    references.push(read_reference(
+                        meta,
+                        index,
+                        &path,
+                        LineageRelation::Ingredient,
+                    ));
+                }
+            }
+        }
+
+        let mut by_instance: HashMap<&str, usize> = HashMap::new();
+        let mut by_document: HashMap<&str, usize> = HashMap::new();
+
+        for (index, node) in nodes.iter().enumerate() {
+            if let Some(id) = &node.instance_id {
+                by_instance.entry(id).or_insert(index);
+            }
+            if let Some(id) = &node.document_id {
+                by_document.entry(id).or_insert(index);
+            }
+        }
+
+        let mut edges = vec![];
+        let mut unresolved = vec![];
+
+        for reference in references {
+            let source = reference
+                .instance_id
+                .as_deref()
+                .and_then(|id| by_instance.get(id))
+                .or_else(|| {
+                    reference
+                        .document_id
+                        .as_deref()
+                        .and_then(|id| by_document.get(id))
+                })
+                .copied();
+
+            match source {
+                Some(source) if source == reference.derived => {}
+                Some(source) => edges.push(LineageEdge {
+                    derived: reference.derived,
+                    source,
+                    relation: reference.relation,
+                }),
+                None => unresolved.push(reference),
+            }
+        }
+
+        Self {
+            nodes,
+            edges,
+            unresolved,
+        }
+    }
+
+    /// Returns the identity of each document, in input order.
+    pub fn nodes(&self) -> &[LineageNode] {
+        &self.nodes
+    }
+
+    /// Returns all resolved references.
+    pub fn edges(&self) -> &[LineageEdge] {
+        &self.edges
+    }
+
+    /// Returns references to documents that are not in the graph.
+    pub fn unresolved(&self) -> &[UnresolvedReference] {
+        &self.unresolved
+    }
+
+    /// Returns the indices of the documents that `node` was derived from or
+    /// used as ingredients.
+    pub fn sources_of(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.edges
+            .iter()
+            .filter(move |edge| edge.derived == node)
+            .map(|edge| edge.source)
+    }
+
+    /// Returns the indices of the documents that were derived from `node`
+    /// or use it as an ingredient.
+    pub fn derivatives_of(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.edges
+            .iter()
+            .filter(move |edge| edge.source == node)
+            .map(|edge| edge.derived)
+    }
+
+    /// Returns the indices of documents that reference no source at all,
+    /// whether resolved or not. These are the originals in the set.
+    pub fn roots(&self) -> Vec<usize> {
+        (0..self.nodes.len())
+            .filter(|&node| {
+                !self.edges.iter().any(|edge| edge.derived == node)
+                    && !self.unresolved.iter().any(|r| r.derived == node)
+            })
+            .collect()
+    }
+
+    /// Returns the indices of documents that have at least one reference
+    /// to a source that is not in the graph.
+    pub fn orphans(&self) -> Vec<usize> {
+        let mut orphans: Vec<usize> = self.unresolved.iter().map(|r| r.derived).collect();
+        orphans.dedup();
+        orphans
+    }
+}
+
+fn mm_property(meta: &XmpMeta, name: &str) -> Option<String> {
+    meta.property(xmp_ns::XMP_MM, name).map(|v| v.value)
+}
+
+fn read_reference(
+    meta: &XmpMeta,
+    derived: usize,
+    path: &str,
+    relation: LineageRelation,
+) -> UnresolvedReference {
+    let field = |name: &str| {
+        meta.struct_field(xmp_ns::XMP_MM, path, xmp_ns::RESOURCE_REF, name)
+            .map(|v| v.value)
+    };
+
+    UnresolvedReference {
+        derived,
+        relation,
+        document_id: field("documentID"),
+        instance_id: field("instanceID"),
+    }
+}
@@ -0,0 +1,217 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::collections::BTreeSet;
+
+use crate::{ArrayForm, IterOptions, XmpMeta, XmpProperty, XmpResult, XmpValue};
+
+/// Provides options for configuring the three-way merge performed by
+/// [`XmpMeta::merge3`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MergePolicy {
+    prefer_theirs: bool,
+    union_unordered_arrays: bool,
+}
+
+impl MergePolicy {
+    /// Resolve conflicts by taking the property from `theirs`.
+    ///
+    /// By default, conflicts are resolved by keeping the property from
+    /// `ours`.
+    pub fn prefer_theirs(mut self) -> Self {
+        self.prefer_theirs = true;
+        self
+    }
+
+    /// Merge unordered arrays (`rdf:Bag`) of simple values item by item
+    /// instead of treating a change on both sides as a conflict.
+    ///
+    /// Items added on either side are kept and items removed on either side
+    /// are removed. This is appropriate for set-like properties such as
+    /// `dc:subject`. Arrays whose items have qualifiers or are not simple
+    /// values are still merged as a whole.
+    pub fn union_unordered_arrays(mut self) -> Self {
+        self.union_unordered_arrays = true;
+        self
+    }
+}
+
+/// Identifies one of the two edited versions passed to
+/// [`XmpMeta::merge3`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MergeSide {
+    /// The version passed as `ours`.
+    Ours,
+
+    /// The version passed as `theirs`.
+    Theirs,
+}
+
+/// Describes a property that was changed differently in both versions
+/// passed to [`XmpMeta::merge3`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MergeConflict {
+    /// The namespace URI of the property.
+    pub namespace: String,
+
+    /// The name of the top-level property.
+    pub name: String,
+
+    /// The version from which the merged property was taken.
+    pub resolution: MergeSide,
+}
+
+/// The outcome of [`XmpMeta::merge3`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct MergeResult {
+    /// The merged metadata.
+    pub merged: XmpMeta,
+
+    /// Properties that were changed differently in both versions, in
+    /// order of namespace URI and then name.
+    ///
+    /// Each conflict was resolved according to the [`MergePolicy`]; the
+    /// merge succeeds even if this list is not empty.
+    pub conflicts: Vec<MergeConflict>,
+}
+
+pub(crate) fn merge3(
+    base: &XmpMeta,
+    ours: &XmpMeta,
+    theirs: &XmpMeta,
+    policy: MergePolicy,
+) -> XmpResult<MergeResult> {
+    let mut merged = ours.clone();
+    let mut conflicts = Vec::new();
+
+    let properties: BTreeSet<(String, String)> = [base, ours, theirs]
+        .into_iter()
+        .flat_map(XmpMeta::top_level_properties)
+        .collect();
+
+    for (ns, name) in properties {
+        let base_prop = subtree(base, &ns, &name);
+        let our_prop = subtree(ours, &ns, &name);
+        let their_prop = subtree(theirs, &ns, &name);
+
+        // `merged` starts as a copy of `ours`, so only a change on their
+        // side requires any work.
+        if our_prop == their_prop || their_prop == base_prop {
+            continue;
+        }
+
+        if our_prop == base_prop {
+            take_theirs(theirs, &mut merged, &ns, &name)?;
+            continue;
+        }
+
+        if policy.union_unordered_arrays {
+            if let Some(items) = union_items(base, ours, theirs, &ns, &name) {
+                merged.set_array(&ns, &name, ArrayForm::Bag, &items)?;
+                continue;
+            }
+        }
+
+        let resolution = if policy.prefer_theirs {
+            take_theirs(theirs, &mut merged, &ns, &name)?;
+            MergeSide::Theirs
+        } else {
+            MergeSide::Ours
+        };
+
+        conflicts.push(MergeConflict {
+            namespace: ns,
+            name,
+            resolution,
+        });
+    }
+
+    Ok(MergeResult { merged, conflicts })
+}
+
+/// Returns every node of a top-level property, or an empty list if the
+/// property does not exist.
+fn subtree(meta: &XmpMeta, ns: &str, name: &str) -> Vec<XmpProperty> {
+    meta.iter(IterOptions::default().property(ns, name))
+        .collect()
+}
+
+fn take_theirs(theirs: &XmpMeta, merged: &mut XmpMeta, ns: &str, name: &str) -> XmpResult<()> {
+    merged.delete_property(ns, name)?;
+
+    if theirs.contains_property(ns, name) {
+        theirs.duplicate_subtree(merged, ns, name, name)?;
+    }
+
+    Ok(())
+}
+
+/// Merges an unordered array item by item, or returns `None` if the
+/// property is not an unordered array of simple values on both sides.
+fn union_items(
+    base: &XmpMeta,
+    ours: &XmpMeta,
+    theirs: &XmpMeta,
+    ns: &str,
+    name: &str,
+) -> Option<Vec<XmpValue<String>>> {
+    let base_items = if base.contains_property(ns, name) {
+        simple_bag_items(base, ns, name)?
+    } else {
+        Vec::new()
+    };
+
+    let our_items = simple_bag_items(ours, ns, name)?;
+    let their_items = simple_bag_items(theirs, ns, name)?;
+
+    let contains = |items: &[XmpValue<String>], item: &XmpValue<String>| {
+        items.iter().any(|other| other.value == item.value)
+    };
+
+    let removed_by = |side: &[XmpValue<String>], item: &XmpValue<String>| {
+        contains(&base_items, item) && !contains(side, item)
+    };
+
+    let mut result: Vec<XmpValue<String>> = our_items
+        .iter()
+        .filter(|item| !removed_by(&their_items, item))
+        .cloned()
+        .collect();
+
+    for item in &their_items {
+        if !contains(&result, item) && !removed_by(&our_items, item) {
+            result.push(item.clone());
+        }
+    }
+
+    Some(result)
+}
+
+fn simple_bag_items(meta: &XmpMeta, ns: &str, name: &str) -> Option<Vec<XmpValue<String>>> {
+    let array = meta.property(ns, name)?;
+    if !array.is_array() || array.is_ordered() {
+        return None;
+    }
+
+    let items: Vec<XmpValue<String>> = meta.property_array(ns, name).collect();
+    if items
+        .iter()
+        .any(|item| item.has_qualifiers() || item.is_array() || item.is_struct())
+    {
+        return None;
+    }
+
+    Some(items)
+}
@@ -12,17 +12,27 @@
 // each license.
 
 use std::{
+    collections::BTreeMap,
     ffi::CString,
-    fmt,
+    fmt, io,
+    ops::Deref,
     os::raw::{c_char, c_void},
+    panic,
     path::Path,
     str::FromStr,
+    sync::{Arc, Mutex},
 };
 
 use crate::{
     ffi::{self, CXmpString},
-    IterOptions, OpenFileOptions, XmpDateTime, XmpError, XmpErrorType, XmpFile, XmpIterator,
-    XmpProperty, XmpResult, XmpValue,
+    rdf_canonical, rdf_scan, rdf_triples, xmp_alias,
+    xmp_change::ChangeListener,
+    xmp_diff, xmp_lang, xmp_merge, xmp_ns, xmp_redact,
+    xmp_value::xmp_prop,
+    AccessStats, ChangeEvent, ChangeKind, EditGuard, IterOptions, JpegXmpPackets, KeywordTree,
+    MergePolicy, MergeResult, OpenFileOptions, OutputOrdering, ParseWarning, RedactionProfile,
+    RedactionReport, XmpAlias, XmpBatch, XmpDateTime, XmpDiff, XmpError, XmpErrorType, XmpFile,
+    XmpIterator, XmpMetaBuilder, XmpProperty, XmpPropertyType, XmpResult, XmpValue,
 };
 
 /// Represents the data model of an XMP packet.
@@ -131,6 +141,10 @@ use crate::{
 /// ```
 pub struct XmpMeta {
     pub(crate) m: Option<*mut ffi::CXmpMeta>,
+    access_stats: Option<Mutex<AccessStats>>,
+    change_listeners: Vec<ChangeListener>,
+    staged_changes: Option<Vec<ChangeEvent>>,
+    strict_lang_tags: bool,
 }
 
 impl Drop for XmpMeta {
@@ -158,14 +172,36 @@ impl XmpMeta {
         let m = unsafe { ffi::CXmpMetaNew(&mut err) };
         XmpError::raise_from_c(&err)?;
 
-        Ok(Self { m: Some(m) })
+        Ok(Self::from_ptr(m))
+    }
+
+    /// Starts constructing a new metadata struct through a chain of method
+    /// calls.
+    ///
+    /// See [`XmpMetaBuilder`].
+    pub fn builder() -> XmpMetaBuilder {
+        XmpMetaBuilder::new()
+    }
+
+    /// Wraps a pointer to a C++ `SXMPMeta` object, taking ownership of it.
+    ///
+    /// A null pointer results in a struct that behaves as if the C++
+    /// XMP Toolkit were unavailable.
+    pub(crate) fn from_ptr(m: *mut ffi::CXmpMeta) -> Self {
+        Self {
+            m: if m.is_null() { None } else { Some(m) },
+            access_stats: None,
+            change_listeners: Vec::new(),
+            staged_changes: None,
+            strict_lang_tags: false,
+        }
     }
 
     /// Use only for testing. Simulates failure to initialize
     /// C++ XMP Toolkit.
     #[allow(dead_code)] // used only in test code
     pub(crate) fn new_fail() -> Self {
-        Self { m: None }
+        Self::from_ptr(std::ptr::null_mut())
     }
 
     /// Reads the XMP from a file without keeping the file open.
@@ -185,6 +221,74 @@ impl XmpMeta {
         })
     }
 
+    /// Creates a new metadata struct from the output of an
+    /// [`XmpIterator`].
+    ///
+    /// Arrays, structs, and qualifiers are recreated from the flags on each
+    /// property, so this is the inverse of iterating over an entire data
+    /// model. It enables filter-then-rebuild workflows:
+    ///
+    /// ```
+    /// # use std::str::FromStr;
+    /// # use xmp_toolkit::{xmp_ns, IterOptions, XmpMeta};
+    /// # fn main() -> xmp_toolkit::XmpResult<()> {
+    /// # let original = XmpMeta::from_str(r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    /// #   <rdf:Description rdf:about="" xmlns:xmp="http://ns.adobe.com/xap/1.0/"
+    /// #     xmlns:photoshop="http://ns.adobe.com/photoshop/1.0/"
+    /// #     xmp:CreatorTool="Example" photoshop:ICCProfile="sRGB"/>
+    /// # </rdf:RDF>"#)?;
+    /// let clean = XmpMeta::from_properties(
+    ///     original
+    ///         .iter(IterOptions::default())
+    ///         .filter(|prop| prop.schema_ns != xmp_ns::PHOTOSHOP),
+    /// )?;
+    ///
+    /// assert!(clean.contains_property(xmp_ns::XMP, "CreatorTool"));
+    /// assert!(!clean.contains_property(xmp_ns::PHOTOSHOP, "ICCProfile"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Properties must appear in the order produced by the iterator (each
+    /// parent before its children) and must be named by full path, so
+    /// iteration options such as [`IterOptions::leaf_name_only`] can not be
+    /// used. Schema nodes are ignored, as are flags that describe a
+    /// property's relationship to other nodes (such as
+    /// [`XmpValue::has_qualifiers`]), since those are recreated
+    /// automatically.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the first error reported while adding a property; the
+    /// partially built metadata is discarded in that case.
+    pub fn from_properties(iter: impl IntoIterator<Item = XmpProperty>) -> XmpResult<Self> {
+        const SET_OPTIONS: u32 = xmp_prop::VALUE_IS_URI
+            | xmp_prop::VALUE_IS_STRUCT
+            | xmp_prop::VALUE_IS_ARRAY
+            | xmp_prop::ARRAY_IS_ORDERED
+            | xmp_prop::ARRAY_IS_ALTERNATE
+            | xmp_prop::ARRAY_IS_ALT_TEXT;
+
+        let mut result = Self::new()?;
+
+        for prop in iter {
+            if prop.value.is_schema_node() || prop.name.is_empty() {
+                continue;
+            }
+
+            result.set_property(
+                &prop.schema_ns,
+                &prop.name,
+                &XmpValue {
+                    value: prop.value.value,
+                    options: prop.value.options & SET_OPTIONS,
+                },
+            )?;
+        }
+
+        Ok(result)
+    }
+
     /// Registers a namespace URI with a suggested prefix.
     ///
     /// If the URI is not registered but the suggested prefix
@@ -266,9 +370,79 @@ impl XmpMeta {
         }
     }
 
+    /// Returns the registered namespaces as `(prefix, uri)` pairs, sorted
+    /// by prefix.
+    ///
+    /// Each prefix ends with a colon, as returned by
+    /// [`XmpMeta::namespace_prefix`]. The namespaces are read when this is
+    /// called; namespaces registered later, whether by
+    /// [`XmpMeta::register_namespace`] or by parsing XMP that uses a new
+    /// namespace, are not included.
+    ///
+    /// **IMPORTANT:** Namespace registrations are global state in
+    /// the C++ XMP Toolkit and not related to any single data model.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use xmp_toolkit::{xmp_ns, XmpMeta};
+    /// let prefix = XmpMeta::register_namespace("ns:example/", "example").unwrap();
+    ///
+    /// let namespaces: Vec<(String, String)> = XmpMeta::namespaces().collect();
+    /// assert!(namespaces.contains(&("xmp:".to_owned(), xmp_ns::XMP.to_owned())));
+    /// assert!(namespaces.contains(&(prefix, "ns:example/".to_owned())));
+    /// ```
+    pub fn namespaces() -> impl Iterator<Item = (String, String)> {
+        // The C++ XMP Toolkit has no API to enumerate the namespace table
+        // other than its dump, which lists one `prefix => uri` pair per
+        // line. Each pair is checked against the table, so that a change
+        // in the dump format drops entries rather than reporting wrong
+        // ones.
+        Self::debug_dump_namespaces()
+            .lines()
+            .filter_map(|line| {
+                let (prefix, uri) = line.trim().split_once(" => ")?;
+
+                (Self::namespace_uri(prefix).as_deref() == Some(uri))
+                    .then(|| (prefix.to_owned(), uri.to_owned()))
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Returns the registered aliases, grouped by the namespace of the
+    /// alias.
+    ///
+    /// These explain why properties don't always end up where a packet put
+    /// them: when XMP is parsed, a property that is an alias (for example,
+    /// `photoshop:Caption`) is moved to its actual property (the
+    /// `x-default` item of `dc:description`).
+    ///
+    /// The C++ XMP Toolkit registers a fixed set of standard aliases when
+    /// it is initialized, and this crate doesn't register others, so the
+    /// list never changes.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use xmp_toolkit::{xmp_ns, XmpMeta};
+    /// for alias in XmpMeta::aliases().filter(|alias| alias.alias_ns == xmp_ns::PHOTOSHOP) {
+    ///     println!(
+    ///         "photoshop:{} => {} {}",
+    ///         alias.alias_name,
+    ///         alias.actual_ns,
+    ///         alias.actual_path()
+    ///     );
+    /// }
+    /// ```
+    pub fn aliases() -> impl Iterator<Item = XmpAlias> {
+        xmp_alias::STANDARD_ALIASES.iter().copied()
+    }
+
     /// Returns a list of registered namespaces as a string.
     ///
-    /// Intended for debugging/logging use.
+    /// Intended for debugging/logging use. Use [`XmpMeta::namespaces`] to
+    /// inspect the registered namespaces programmatically.
     ///
     /// **IMPORTANT:** Namespace registrations are global state in
     /// the C++ XMP Toolkit and not related to any single data model.
@@ -286,6 +460,64 @@ impl XmpMeta {
         result
     }
 
+    /// Writes a detailed dump of the XMP data model to `writer`.
+    ///
+    /// Unlike the [`Debug`](fmt::Debug) and alternate
+    /// [`Display`](fmt::Display) output, which are formatted to be
+    /// readable, this is the C++ XMP Toolkit's own dump of every node,
+    /// including the raw option bits, qualifiers, and alias information.
+    /// This is often the quickest way to learn why a property has
+    /// unexpected flags.
+    ///
+    /// Intended for debugging/logging use. The format is defined by the
+    /// C++ XMP Toolkit and may change between versions.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use xmp_toolkit::{xmp_ns, XmpMeta};
+    /// let mut meta = XmpMeta::new().unwrap();
+    /// meta.set_property(xmp_ns::XMP, "CreatorTool", &"Example".into())
+    ///     .unwrap();
+    ///
+    /// meta.dump(&mut std::io::stderr()).unwrap();
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns the first error reported by `writer`; the dump stops at that
+    /// point.
+    ///
+    /// ## Panics
+    ///
+    /// If `writer` panics, the dump stops and the panic is resumed once the
+    /// C++ XMP Toolkit has returned.
+    pub fn dump(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        let Some(m) = self.m else {
+            return writeln!(writer, "(C++ XMP Toolkit unavailable)");
+        };
+
+        let mut dump_writer = ffi::DumpWriter {
+            writer,
+            error: None,
+            panic: None,
+        };
+
+        unsafe {
+            let dump_writer: *mut ffi::DumpWriter = &mut dump_writer;
+            ffi::CXmpMetaDumpObj(m, dump_writer.cast::<c_void>(), ffi::xmp_dump_to_writer);
+        }
+
+        if let Some(payload) = dump_writer.panic {
+            panic::resume_unwind(payload);
+        }
+
+        match dump_writer.error {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
     /// Returns `true` if the metadata block contains a property by this name.
     ///
     /// ## Arguments
@@ -298,6 +530,8 @@ impl XmpMeta {
     /// Any errors (for instance, empty or invalid namespace or property name)
     /// are ignored; the function will return `false` in such cases.
     pub fn contains_property(&self, namespace: &str, path: &str) -> bool {
+        self.record_read(namespace, path);
+
         if let Some(m) = self.m {
             let c_ns = CString::new(namespace).unwrap_or_default();
             let c_name = CString::new(path).unwrap_or_default();
@@ -330,6 +564,8 @@ impl XmpMeta {
         field_ns: &str,
         field_name: &str,
     ) -> bool {
+        self.record_read(struct_ns, struct_path);
+
         if let Some(m) = self.m {
             let c_struct_ns = CString::new(struct_ns).unwrap_or_default();
             let c_struct_name = CString::new(struct_path).unwrap_or_default();
@@ -370,6 +606,8 @@ impl XmpMeta {
         qual_ns: &str,
         qual_name: &str,
     ) -> bool {
+        self.record_read(prop_ns, prop_path);
+
         if let Some(m) = self.m {
             let c_prop_ns = CString::new(prop_ns).unwrap_or_default();
             let c_prop_name = CString::new(prop_path).unwrap_or_default();
@@ -402,14 +640,34 @@ impl XmpMeta {
     /// Any errors (for instance, empty or invalid namespace or property name)
     /// are ignored; the function will return `None` in such cases.
     pub fn property(&self, namespace: &str, path: &str) -> Option<XmpValue<String>> {
+        self.try_property(namespace, path).ok().flatten()
+    }
+
+    /// Gets a simple string property value, reporting any errors.
+    ///
+    /// Unlike [`XmpMeta::property`], this distinguishes a property that
+    /// does not exist (`Ok(None)`) from an error.
+    ///
+    /// ## Arguments
+    ///
+    /// * `namespace` and `path`: See [Accessing
+    ///   properties](#accessing-properties).
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the C++ XMP Toolkit is unavailable, if an argument
+    /// contains a NUL byte, or if the toolkit rejects the namespace or path.
+    pub fn try_property(&self, namespace: &str, path: &str) -> XmpResult<Option<XmpValue<String>>> {
+        self.record_read(namespace, path);
+
         if let Some(m) = self.m {
-            let c_ns = CString::new(namespace).unwrap_or_default();
-            let c_name = CString::new(path).unwrap_or_default();
+            let c_ns = CString::new(namespace)?;
+            let c_name = CString::new(path)?;
 
             let mut options: u32 = 0;
             let mut err = ffi::CXmpError::default();
 
-            unsafe {
+            let result = unsafe {
                 CXmpString::from_ptr(ffi::CXmpMetaGetProperty(
                     m,
                     &mut err,
@@ -417,7 +675,87 @@ impl XmpMeta {
                     c_name.as_ptr(),
                     &mut options,
                 ))
-                .map(|value| XmpValue { value, options })
+            };
+
+            XmpError::raise_from_c(&err)?;
+            Ok(result.map(|value| XmpValue { value, options }))
+        } else {
+            Err(no_cpp_toolkit())
+        }
+    }
+
+    /// Gets a simple string property value without allocating a new
+    /// `String`.
+    ///
+    /// The value is written into `buf`, replacing its previous contents,
+    /// and the returned [`XmpValue`] borrows from it. Reusing the same
+    /// buffer across calls avoids an allocation per property, which can be
+    /// significant in loops that read many properties.
+    ///
+    /// If the property does not exist, `buf` is left empty and `None` is
+    /// returned.
+    ///
+    /// ## Arguments
+    ///
+    /// * `namespace` and `path`: See [Accessing
+    ///   properties](#accessing-properties).
+    /// * `buf`: Scratch buffer that receives the value.
+    ///
+    /// ## Error handling
+    ///
+    /// Any errors (for instance, empty or invalid namespace or property name)
+    /// are ignored; the function will return `None` in such cases.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use xmp_toolkit::{xmp_ns, XmpMeta};
+    /// # let mut meta = XmpMeta::new().unwrap();
+    /// # meta.set_property(xmp_ns::XMP, "CreatorTool", &"Example".into()).unwrap();
+    /// let mut buf = String::new();
+    ///
+    /// for name in ["CreatorTool", "Label", "Rating"] {
+    ///     if let Some(value) = meta.property_into(xmp_ns::XMP, name, &mut buf) {
+    ///         println!("{name} = {}", value.value);
+    ///     }
+    /// }
+    /// ```
+    pub fn property_into<'a>(
+        &self,
+        namespace: &str,
+        path: &str,
+        buf: &'a mut String,
+    ) -> Option<XmpValue<&'a str>> {
+        self.record_read(namespace, path);
+        buf.clear();
+
+        if let Some(m) = self.m {
+            let c_ns = CString::new(namespace).unwrap_or_default();
+            let c_name = CString::new(path).unwrap_or_default();
+
+            let mut options: u32 = 0;
+            let mut err = ffi::CXmpError::default();
+
+            let found = unsafe {
+                let buf: *mut String = &mut *buf;
+                ffi::CXmpMetaGetPropertyInto(
+                    m,
+                    &mut err,
+                    c_ns.as_ptr(),
+                    c_name.as_ptr(),
+                    buf.cast::<c_void>(),
+                    ffi::xmp_set_string,
+                    &mut options,
+                )
+            };
+
+            if found {
+                Some(XmpValue {
+                    value: buf.as_str(),
+                    options,
+                })
+            } else {
+                None
             }
         } else {
             None
@@ -431,6 +769,8 @@ impl XmpMeta {
     /// * `namespace` and `path`: See [Accessing
     ///   properties](#accessing-properties).
     pub fn property_array(&self, namespace: &str, path: &str) -> ArrayProperty {
+        self.record_read(namespace, path);
+
         ArrayProperty {
             meta: self,
             ns: CString::new(namespace).unwrap_or_default(),
@@ -454,6 +794,8 @@ impl XmpMeta {
     /// If the value can not be parsed as a boolean (for example, it is
     /// an unrecognizable string), the function will return `None`.
     pub fn property_bool(&self, namespace: &str, path: &str) -> Option<XmpValue<bool>> {
+        self.record_read(namespace, path);
+
         if let Some(m) = self.m {
             let c_ns = CString::new(namespace).unwrap_or_default();
             let c_name = CString::new(path).unwrap_or_default();
@@ -496,6 +838,8 @@ impl XmpMeta {
     /// If the value can not be parsed as a number, the function will
     /// return `None`.
     pub fn property_i32(&self, namespace: &str, path: &str) -> Option<XmpValue<i32>> {
+        self.record_read(namespace, path);
+
         if let Some(m) = self.m {
             let c_ns = CString::new(namespace).unwrap_or_default();
             let c_name = CString::new(path).unwrap_or_default();
@@ -538,6 +882,8 @@ impl XmpMeta {
     /// If the value can not be parsed as a number, the function will
     /// return `None`.
     pub fn property_i64(&self, namespace: &str, path: &str) -> Option<XmpValue<i64>> {
+        self.record_read(namespace, path);
+
         if let Some(m) = self.m {
             let c_ns = CString::new(namespace).unwrap_or_default();
             let c_name = CString::new(path).unwrap_or_default();
@@ -581,6 +927,8 @@ impl XmpMeta {
     /// return `None`. Note that ratio values, such as those found in
     /// TIFF and Exif blocks, are not parsed.
     pub fn property_f64(&self, namespace: &str, path: &str) -> Option<XmpValue<f64>> {
+        self.record_read(namespace, path);
+
         if let Some(m) = self.m {
             let c_ns = CString::new(namespace).unwrap_or_default();
             let c_name = CString::new(path).unwrap_or_default();
@@ -623,6 +971,8 @@ impl XmpMeta {
     /// If the value can not be parsed as a date (for example, it is
     /// an unrecognizable string), the function will return `None`.
     pub fn property_date(&self, namespace: &str, path: &str) -> Option<XmpValue<XmpDateTime>> {
+        self.record_read(namespace, path);
+
         if let Some(m) = self.m {
             let c_ns = CString::new(namespace).unwrap_or_default();
             let c_name = CString::new(path).unwrap_or_default();
@@ -673,16 +1023,47 @@ impl XmpMeta {
         field_ns: &str,
         field_name: &str,
     ) -> Option<XmpValue<String>> {
+        self.try_struct_field(struct_ns, struct_path, field_ns, field_name)
+            .ok()
+            .flatten()
+    }
+
+    /// Gets a field value from within an nested structure, reporting any
+    /// errors.
+    ///
+    /// Unlike [`XmpMeta::struct_field`], this distinguishes a field that
+    /// does not exist (`Ok(None)`) from an error.
+    ///
+    /// ## Arguments
+    ///
+    /// * `struct_ns` and `struct_path`: See [Accessing
+    ///   properties](#accessing-properties).
+    /// * `field_ns` and `field_name` take the same form (i.e. see [Accessing
+    ///   properties](#accessing-properties) again.)
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the C++ XMP Toolkit is unavailable, if an argument
+    /// contains a NUL byte, or if the toolkit rejects a namespace or path.
+    pub fn try_struct_field(
+        &self,
+        struct_ns: &str,
+        struct_path: &str,
+        field_ns: &str,
+        field_name: &str,
+    ) -> XmpResult<Option<XmpValue<String>>> {
+        self.record_read(struct_ns, struct_path);
+
         if let Some(m) = self.m {
-            let c_struct_ns = CString::new(struct_ns).unwrap_or_default();
-            let c_struct_name = CString::new(struct_path).unwrap_or_default();
-            let c_field_ns = CString::new(field_ns).unwrap_or_default();
-            let c_field_name = CString::new(field_name).unwrap_or_default();
+            let c_struct_ns = CString::new(struct_ns)?;
+            let c_struct_name = CString::new(struct_path)?;
+            let c_field_ns = CString::new(field_ns)?;
+            let c_field_name = CString::new(field_name)?;
 
             let mut options: u32 = 0;
             let mut err = ffi::CXmpError::default();
 
-            unsafe {
+            let result = unsafe {
                 CXmpString::from_ptr(ffi::CXmpMetaGetStructField(
                     m,
                     &mut err,
@@ -692,11 +1073,61 @@ impl XmpMeta {
                     c_field_name.as_ptr(),
                     &mut options,
                 ))
-                .map(|value| XmpValue { value, options })
-            }
+            };
+
+            XmpError::raise_from_c(&err)?;
+            Ok(result.map(|value| XmpValue { value, options }))
         } else {
-            None
+            Err(no_cpp_toolkit())
+        }
+    }
+
+    /// Returns all fields of a structure as a map.
+    ///
+    /// The map is keyed by `(field_ns, field_name)`, where `field_ns` is the
+    /// namespace URI of the field and `field_name` is its local name
+    /// (without namespace prefix). This is intended for flat structures; a
+    /// field that is itself a struct or array appears with an empty value
+    /// and the corresponding flags.
+    ///
+    /// ## Arguments
+    ///
+    /// * `struct_ns` and `struct_path`: See [Accessing
+    ///   properties](#accessing-properties).
+    ///
+    /// ## Error handling
+    ///
+    /// Returns `None` if the property does not exist or is not a
+    /// structure. Any other errors are ignored.
+    pub fn struct_as_map(
+        &self,
+        struct_ns: &str,
+        struct_path: &str,
+    ) -> Option<BTreeMap<(String, String), XmpValue<String>>> {
+        self.record_read(struct_ns, struct_path);
+
+        if !self.property(struct_ns, struct_path)?.is_struct() {
+            return None;
         }
+
+        Some(
+            self.iter(
+                IterOptions::default()
+                    .property(struct_ns, struct_path)
+                    .immediate_children_only(),
+            )
+            .filter(|prop| !prop.value.is_qualifier())
+            .filter_map(|prop| {
+                let field_path = match prop.name.rsplit_once('/') {
+                    Some((_, field_path)) => field_path,
+                    None => &prop.name,
+                };
+                let (prefix, name) = field_path.split_once(':')?;
+                let ns = Self::namespace_uri(prefix)?;
+                Some(((ns, name.to_owned()), prop.value))
+            })
+            .collect(),
+        )
     }
 
     /// Creates or sets a property value.
@@ -715,6 +1146,8 @@ impl XmpMeta {
         path: &str,
         new_value: &XmpValue<String>,
     ) -> XmpResult<()> {
+        self.record_write(namespace, path);
+
         if let Some(m) = self.m {
             let c_ns = CString::new(namespace)?;
             let c_name = CString::new(path)?;
@@ -736,7 +1169,9 @@ impl XmpMeta {
                 );
             }
 
-            XmpError::raise_from_c(&err)
+            XmpError::raise_from_c(&err)?;
+            self.notify_change(ChangeKind::Set, namespace, path);
+            Ok(())
         } else {
             Err(no_cpp_toolkit())
         }
@@ -758,6 +1193,8 @@ impl XmpMeta {
         path: &str,
         new_value: &XmpValue<bool>,
     ) -> XmpResult<()> {
+        self.record_write(namespace, path);
+
         if let Some(m) = self.m {
             let c_ns = CString::new(namespace)?;
             let c_name = CString::new(path)?;
@@ -774,7 +1211,9 @@ impl XmpMeta {
                 );
             }
 
-            XmpError::raise_from_c(&err)
+            XmpError::raise_from_c(&err)?;
+            self.notify_change(ChangeKind::Set, namespace, path);
+            Ok(())
         } else {
             Err(no_cpp_toolkit())
         }
@@ -796,6 +1235,8 @@ impl XmpMeta {
         path: &str,
         new_value: &XmpValue<i32>,
     ) -> XmpResult<()> {
+        self.record_write(namespace, path);
+
         if let Some(m) = self.m {
             let c_ns = CString::new(namespace)?;
             let c_name = CString::new(path)?;
@@ -812,7 +1253,9 @@ impl XmpMeta {
                 );
             }
 
-            XmpError::raise_from_c(&err)
+            XmpError::raise_from_c(&err)?;
+            self.notify_change(ChangeKind::Set, namespace, path);
+            Ok(())
         } else {
             Err(no_cpp_toolkit())
         }
@@ -834,6 +1277,8 @@ impl XmpMeta {
         path: &str,
         new_value: &XmpValue<i64>,
     ) -> XmpResult<()> {
+        self.record_write(namespace, path);
+
         if let Some(m) = self.m {
             let c_ns = CString::new(namespace)?;
             let c_name = CString::new(path)?;
@@ -850,7 +1295,9 @@ impl XmpMeta {
                 );
             }
 
-            XmpError::raise_from_c(&err)
+            XmpError::raise_from_c(&err)?;
+            self.notify_change(ChangeKind::Set, namespace, path);
+            Ok(())
         } else {
             Err(no_cpp_toolkit())
         }
@@ -872,6 +1319,8 @@ impl XmpMeta {
         path: &str,
         new_value: &XmpValue<f64>,
     ) -> XmpResult<()> {
+        self.record_write(namespace, path);
+
         if let Some(m) = self.m {
             let c_ns = CString::new(namespace)?;
             let c_name = CString::new(path)?;
@@ -888,7 +1337,9 @@ impl XmpMeta {
                 );
             }
 
-            XmpError::raise_from_c(&err)
+            XmpError::raise_from_c(&err)?;
+            self.notify_change(ChangeKind::Set, namespace, path);
+            Ok(())
         } else {
             Err(no_cpp_toolkit())
         }
@@ -910,6 +1361,8 @@ impl XmpMeta {
         path: &str,
         new_value: &XmpValue<XmpDateTime>,
     ) -> XmpResult<()> {
+        self.record_write(namespace, path);
+
         if let Some(m) = self.m {
             let c_ns = CString::new(namespace)?;
             let c_name = CString::new(path)?;
@@ -926,12 +1379,61 @@ impl XmpMeta {
                 );
             }
 
-            XmpError::raise_from_c(&err)
+            XmpError::raise_from_c(&err)?;
+            self.notify_change(ChangeKind::Set, namespace, path);
+            Ok(())
         } else {
             Err(no_cpp_toolkit())
         }
     }
 
+    /// Gets a simple property value and interprets it as type `T`.
+    ///
+    /// This is a generic form of [`XmpMeta::property`],
+    /// [`XmpMeta::property_bool`], and related functions. It can also be
+    /// used with your own types; see [`XmpPropertyType`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `namespace` and `path`: See [Accessing
+    ///   properties](#accessing-properties).
+    ///
+    /// ## Error handling
+    ///
+    /// Any errors (for instance, empty or invalid namespace or property name)
+    /// are ignored; the function will return `None` in such cases.
+    ///
+    /// If the value can not be interpreted as type `T`, the function will
+    /// return `None`.
+    pub fn get<T: XmpPropertyType>(&self, namespace: &str, path: &str) -> Option<XmpValue<T>> {
+        T::get_property(self, namespace, path)
+    }
+
+    /// Creates or sets a simple property value of type `T`.
+    ///
+    /// This is a generic form of [`XmpMeta::set_property`],
+    /// [`XmpMeta::set_property_bool`], and related functions. It can also be
+    /// used with your own types; see [`XmpPropertyType`].
+    ///
+    /// `new_value` may be a plain value (for example, `42i64`) or an
+    /// [`XmpValue<T>`] if you need to set property flags. In the latter
+    /// case, you may need to name the type explicitly
+    /// (`meta.set::<String>(...)`).
+    ///
+    /// ## Arguments
+    ///
+    /// * `namespace` and `path`: See [Accessing
+    ///   properties](#accessing-properties).
+    /// * `new_value`: The new value.
+    pub fn set<T: XmpPropertyType>(
+        &mut self,
+        namespace: &str,
+        path: &str,
+        new_value: impl Into<XmpValue<T>>,
+    ) -> XmpResult<()> {
+        T::set_property(self, namespace, path, &new_value.into())
+    }
+
     /// Deletes an XMP subtree rooted at a given property.
     ///
     /// It is not an error if the qualifier does not exist.
@@ -941,6 +1443,8 @@ impl XmpMeta {
     /// * `namespace` and `path`: See [Accessing
     ///   properties](#accessing-properties).
     pub fn delete_property(&mut self, namespace: &str, path: &str) -> XmpResult<()> {
+        self.record_write(namespace, path);
+
         if let Some(m) = self.m {
             let c_ns = CString::new(namespace)?;
             let c_name = CString::new(path)?;
@@ -950,7 +1454,9 @@ impl XmpMeta {
                 ffi::CXmpMetaDeleteProperty(m, &mut err, c_ns.as_ptr(), c_name.as_ptr());
             }
 
-            XmpError::raise_from_c(&err)
+            XmpError::raise_from_c(&err)?;
+            self.notify_change(ChangeKind::Delete, namespace, path);
+            Ok(())
         } else {
             Err(no_cpp_toolkit())
         }
@@ -973,14 +1479,45 @@ impl XmpMeta {
         array_name: &str,
         item_index: i32,
     ) -> Option<XmpValue<String>> {
-        if let Some(m) = self.m {
-            let c_ns = CString::new(namespace).unwrap_or_default();
-            let c_array_name = CString::new(array_name).unwrap_or_default();
-
-            let mut options: u32 = 0;
-            let mut err = ffi::CXmpError::default();
+        self.try_array_item(namespace, array_name, item_index)
+            .ok()
+            .flatten()
+    }
 
-            unsafe {
+    /// Provides access to items within an array, reporting any errors.
+    ///
+    /// Unlike [`XmpMeta::array_item`], this distinguishes an item that does
+    /// not exist (`Ok(None)`) from an error.
+    ///
+    /// ## Arguments
+    ///
+    /// * `namespace` and `array_name`: See [Accessing
+    ///   properties](#accessing-properties).
+    /// * `item_index`: Index into the array. **IMPORTANT:** Indices in XMP are
+    ///   1-based, unlike Rust where indices are typically 0-based.  Use
+    ///   [`XmpMeta::LAST_ITEM`] to specify the last existing array item.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the C++ XMP Toolkit is unavailable, if an argument
+    /// contains a NUL byte, or if the toolkit rejects the namespace, path, or
+    /// index.
+    pub fn try_array_item(
+        &self,
+        namespace: &str,
+        array_name: &str,
+        item_index: i32,
+    ) -> XmpResult<Option<XmpValue<String>>> {
+        self.record_read(namespace, array_name);
+
+        if let Some(m) = self.m {
+            let c_ns = CString::new(namespace)?;
+            let c_array_name = CString::new(array_name)?;
+
+            let mut options: u32 = 0;
+            let mut err = ffi::CXmpError::default();
+
+            let result = unsafe {
                 CXmpString::from_ptr(ffi::CXmpMetaGetArrayItem(
                     m,
                     &mut err,
@@ -989,7 +1526,73 @@ impl XmpMeta {
                     item_index,
                     &mut options,
                 ))
-                .map(|value| XmpValue { value, options })
+            };
+
+            XmpError::raise_from_c(&err)?;
+            Ok(result.map(|value| XmpValue { value, options }))
+        } else {
+            Err(no_cpp_toolkit())
+        }
+    }
+
+    /// Provides access to items within an array without allocating a new
+    /// `String`.
+    ///
+    /// The value is written into `buf`, replacing its previous contents,
+    /// and the returned [`XmpValue`] borrows from it. See
+    /// [`XmpMeta::property_into`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `namespace` and `array_name`: See [Accessing
+    ///   properties](#accessing-properties).
+    /// * `item_index`: Index into the array. **IMPORTANT:** Indices in XMP are
+    ///   1-based, unlike Rust where indices are typically 0-based.  Use
+    ///   [`XmpMeta::LAST_ITEM`] to specify the last existing array item.
+    /// * `buf`: Scratch buffer that receives the value.
+    ///
+    /// ## Error handling
+    ///
+    /// Any errors (for instance, empty or invalid namespace or property name)
+    /// are ignored; the function will return `None` in such cases.
+    pub fn array_item_into<'a>(
+        &self,
+        namespace: &str,
+        array_name: &str,
+        item_index: i32,
+        buf: &'a mut String,
+    ) -> Option<XmpValue<&'a str>> {
+        self.record_read(namespace, array_name);
+        buf.clear();
+
+        if let Some(m) = self.m {
+            let c_ns = CString::new(namespace).unwrap_or_default();
+            let c_array_name = CString::new(array_name).unwrap_or_default();
+
+            let mut options: u32 = 0;
+            let mut err = ffi::CXmpError::default();
+
+            let found = unsafe {
+                let buf: *mut String = &mut *buf;
+                ffi::CXmpMetaGetArrayItemInto(
+                    m,
+                    &mut err,
+                    c_ns.as_ptr(),
+                    c_array_name.as_ptr(),
+                    item_index,
+                    buf.cast::<c_void>(),
+                    ffi::xmp_set_string,
+                    &mut options,
+                )
+            };
+
+            if found {
+                Some(XmpValue {
+                    value: buf.as_str(),
+                    options,
+                })
+            } else {
+                None
             }
         } else {
             None
@@ -1022,6 +1625,8 @@ impl XmpMeta {
         item_placement: ItemPlacement,
         item_value: &XmpValue<String>,
     ) -> XmpResult<()> {
+        self.record_write(namespace, array_name);
+
         if let Some(m) = self.m {
             let c_ns = CString::new(namespace)?;
             let c_array_name = CString::new(array_name)?;
@@ -1053,7 +1658,18 @@ impl XmpMeta {
                 );
             }
 
-            XmpError::raise_from_c(&err)
+            XmpError::raise_from_c(&err)?;
+
+            let item_index = match item_placement {
+                ItemPlacement::InsertAfterIndex(index) => index + 1,
+                ItemPlacement::InsertBeforeIndex(index)
+                | ItemPlacement::ReplaceItemAtIndex(index) => index,
+            };
+
+            self.notify_change_at(ChangeKind::Set, namespace, || {
+                Self::compose_array_item_path(namespace, array_name, item_index as i32)
+            });
+            Ok(())
         } else {
             Err(no_cpp_toolkit())
         }
@@ -1083,6 +1699,8 @@ impl XmpMeta {
         array_name: &XmpValue<String>,
         item_value: &XmpValue<String>,
     ) -> XmpResult<()> {
+        self.record_write(namespace, &array_name.value);
+
         if let Some(m) = self.m {
             let c_ns = CString::new(namespace)?;
             let c_array_name = CString::new(array_name.value.as_bytes())?;
@@ -1101,12 +1719,168 @@ impl XmpMeta {
                 );
             }
 
-            XmpError::raise_from_c(&err)
+            XmpError::raise_from_c(&err)?;
+
+            if self.observes_changes() {
+                let mut len: u32 = 0;
+
+                unsafe {
+                    ffi::CXmpMetaCountArrayItems(
+                        m,
+                        &mut err,
+                        c_ns.as_ptr(),
+                        c_array_name.as_ptr(),
+                        &mut len,
+                    );
+                }
+
+                self.notify_change_at(ChangeKind::Append, namespace, || {
+                    Self::compose_array_item_path(namespace, &array_name.value, len as i32)
+                });
+            }
+
+            Ok(())
         } else {
             Err(no_cpp_toolkit())
         }
     }
 
+    /// Replaces an entire array with new contents.
+    ///
+    /// Any existing property at `array_name` is deleted, then a new array of
+    /// the given form is created and `items` are appended to it in order.
+    ///
+    /// The update is atomic: The changes are staged on a copy of this
+    /// object, which replaces `self` only if every item was written
+    /// successfully. If an error occurs, `self` is left unchanged.
+    ///
+    /// An empty `items` slice leaves an empty array in place.
+    ///
+    /// ## Arguments
+    ///
+    /// * `namespace` and `array_name`: See [Accessing
+    ///   properties](#accessing-properties).
+    /// * `form`: The kind of array to create. See [`ArrayForm`].
+    /// * `items`: Values and flags for the items to be written to the array.
+    pub fn set_array(
+        &mut self,
+        namespace: &str,
+        array_name: &str,
+        form: ArrayForm,
+        items: &[XmpValue<String>],
+    ) -> XmpResult<()> {
+        self.record_write(namespace, array_name);
+
+        if self.m.is_none() {
+            return Err(no_cpp_toolkit());
+        }
+
+        let mut staged = self.stage();
+        staged.delete_property(namespace, array_name)?;
+
+        let array = XmpValue {
+            value: array_name.to_owned(),
+            options: form.options(),
+        };
+
+        staged.set_property(
+            namespace,
+            array_name,
+            &XmpValue {
+                value: String::new(),
+                options: array.options,
+            },
+        )?;
+
+        for item in items {
+            staged.append_array_item(namespace, &array, item)?;
+        }
+
+        self.commit_staged(staged);
+        Ok(())
+    }
+
+    /// Changes the form of an existing array (for instance, from
+    /// `rdf:Bag` to `rdf:Seq`) while preserving its items and qualifiers.
+    ///
+    /// This is useful for normalizing metadata from writers that use a
+    /// different array form than the one required by the schema.
+    ///
+    /// As with [`XmpMeta::set_array`], the update is atomic: If an error
+    /// occurs, `self` is left unchanged.
+    ///
+    /// ## Arguments
+    ///
+    /// * `namespace` and `array_name`: See [Accessing
+    ///   properties](#accessing-properties).
+    /// * `form`: The new form for the array. See [`ArrayForm`].
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::BadXPath`] if the property
+    /// does not exist or is not an array.
+    pub fn convert_array_form(
+        &mut self,
+        namespace: &str,
+        array_name: &str,
+        form: ArrayForm,
+    ) -> XmpResult<()> {
+        self.record_write(namespace, array_name);
+
+        if self.m.is_none() {
+            return Err(no_cpp_toolkit());
+        }
+
+        // Read through the staged copy, which does not count accesses,
+        // so that only the conversion itself is recorded.
+        let mut staged = self.stage();
+
+        let Some(array) = staged.property(namespace, array_name) else {
+            return Err(XmpError {
+                error_type: XmpErrorType::BadXPath,
+                debug_message: "Array does not exist".to_owned(),
+            });
+        };
+
+        if !array.is_array() {
+            return Err(XmpError {
+                error_type: XmpErrorType::BadXPath,
+                debug_message: "Property is not an array".to_owned(),
+            });
+        }
+
+        let len = staged.array_len(namespace, array_name);
+        staged.delete_property(namespace, array_name)?;
+        staged.set_property(
+            namespace,
+            array_name,
+            &XmpValue {
+                value: String::new(),
+                options: form.options(),
+            },
+        )?;
+
+        let qualifiers = self
+            .iter(
+                IterOptions::default()
+                    .property(namespace, array_name)
+                    .immediate_children_only(),
+            )
+            .filter(|prop| prop.value.is_qualifier());
+
+        for qual in qualifiers {
+            self.duplicate_subtree(&mut staged, namespace, &qual.name, &qual.name)?;
+        }
+
+        for index in 1..=len {
+            let item_path = Self::compose_array_item_path(namespace, array_name, index as i32)?;
+            self.duplicate_subtree(&mut staged, namespace, &item_path, &item_path)?;
+        }
+
+        self.commit_staged(staged);
+        Ok(())
+    }
+
     /// Deletes an XMP subtree rooted at a given array item.
     ///
     /// It is not an error if the array item does not exist. Use
@@ -1127,6 +1901,8 @@ impl XmpMeta {
         array_name: &str,
         item_index: i32,
     ) -> XmpResult<()> {
+        self.record_write(namespace, array_name);
+
         if let Some(m) = self.m {
             let c_ns = CString::new(namespace)?;
             let c_array_name = CString::new(array_name)?;
@@ -1142,7 +1918,11 @@ impl XmpMeta {
                 );
             }
 
-            XmpError::raise_from_c(&err)
+            XmpError::raise_from_c(&err)?;
+            self.notify_change_at(ChangeKind::Delete, namespace, || {
+                Self::compose_array_item_path(namespace, array_name, item_index)
+            });
+            Ok(())
         } else {
             Err(no_cpp_toolkit())
         }
@@ -1158,6 +1938,8 @@ impl XmpMeta {
     /// If any error occurs (for instance, the array does not exist),
     /// this function will return 0.
     pub fn array_len(&self, array_ns: &str, array_name: &str) -> usize {
+        self.record_read(array_ns, array_name);
+
         let mut result: u32 = 0;
 
         if let Some(m) = self.m {
@@ -1205,6 +1987,8 @@ impl XmpMeta {
         field_name: &str,
         item_value: &XmpValue<String>,
     ) -> XmpResult<()> {
+        self.record_write(namespace, struct_name);
+
         if let Some(m) = self.m {
             let c_struct_ns = CString::new(namespace)?;
             let c_struct_name = CString::new(struct_name.as_bytes())?;
@@ -1226,7 +2010,11 @@ impl XmpMeta {
                 );
             }
 
-            XmpError::raise_from_c(&err)
+            XmpError::raise_from_c(&err)?;
+            self.notify_change_at(ChangeKind::Set, namespace, || {
+                Self::compose_struct_field_path(namespace, struct_name, field_ns, field_name)
+            });
+            Ok(())
         } else {
             Err(no_cpp_toolkit())
         }
@@ -1251,6 +2039,8 @@ impl XmpMeta {
         field_ns: &str,
         field_name: &str,
     ) -> XmpResult<()> {
+        self.record_write(namespace, struct_name);
+
         if let Some(m) = self.m {
             let c_struct_ns = CString::new(namespace)?;
             let c_struct_name = CString::new(struct_name.as_bytes())?;
@@ -1269,12 +2059,62 @@ impl XmpMeta {
                 );
             }
 
-            XmpError::raise_from_c(&err)
+            XmpError::raise_from_c(&err)?;
+            self.notify_change_at(ChangeKind::Delete, namespace, || {
+                Self::compose_struct_field_path(namespace, struct_name, field_ns, field_name)
+            });
+            Ok(())
         } else {
             Err(no_cpp_toolkit())
         }
     }
 
+    /// Replaces a structure with the fields in `fields`.
+    ///
+    /// Any existing property at `struct_name` is deleted, then a new
+    /// structure is created containing exactly the given fields. The map is
+    /// keyed by `(field_ns, field_name)`, in the same form as returned by
+    /// [`XmpMeta::struct_as_map`].
+    ///
+    /// As with [`XmpMeta::set_array`], the update is atomic: If an error
+    /// occurs, `self` is left unchanged.
+    ///
+    /// ## Arguments
+    ///
+    /// * `namespace` and `struct_name`: See [Accessing
+    ///   properties](#accessing-properties).
+    /// * `fields`: Values and flags for each field.
+    pub fn set_struct_from_map(
+        &mut self,
+        namespace: &str,
+        struct_name: &str,
+        fields: &BTreeMap<(String, String), XmpValue<String>>,
+    ) -> XmpResult<()> {
+        self.record_write(namespace, struct_name);
+
+        if self.m.is_none() {
+            return Err(no_cpp_toolkit());
+        }
+
+        let mut staged = self.stage();
+        staged.delete_property(namespace, struct_name)?;
+        staged.set_property(
+            namespace,
+            struct_name,
+            &XmpValue {
+                value: String::new(),
+                options: xmp_prop::VALUE_IS_STRUCT,
+            },
+        )?;
+
+        for ((field_ns, field_name), value) in fields {
+            staged.set_struct_field(namespace, struct_name, field_ns, field_name, value)?;
+        }
+
+        self.commit_staged(staged);
+        Ok(())
+    }
+
     /// Provides access to a qualifier attached to a property.
     ///
     /// ## Arguments
@@ -1295,16 +2135,47 @@ impl XmpMeta {
         qual_ns: &str,
         qual_name: &str,
     ) -> Option<XmpValue<String>> {
+        self.try_qualifier(prop_ns, prop_path, qual_ns, qual_name)
+            .ok()
+            .flatten()
+    }
+
+    /// Provides access to a qualifier attached to a property, reporting any
+    /// errors.
+    ///
+    /// Unlike [`XmpMeta::qualifier`], this distinguishes a qualifier that
+    /// does not exist (`Ok(None)`) from an error.
+    ///
+    /// ## Arguments
+    ///
+    /// * `prop_ns` and `prop_path`: See [Accessing
+    ///   properties](#accessing-properties).
+    /// * `qual_ns` and `qual_name` take the same form (i.e. see [Accessing
+    ///   properties](#accessing-properties) again.)
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the C++ XMP Toolkit is unavailable, if an argument
+    /// contains a NUL byte, or if the toolkit rejects a namespace or path.
+    pub fn try_qualifier(
+        &self,
+        prop_ns: &str,
+        prop_path: &str,
+        qual_ns: &str,
+        qual_name: &str,
+    ) -> XmpResult<Option<XmpValue<String>>> {
+        self.record_read(prop_ns, prop_path);
+
         if let Some(m) = self.m {
-            let c_prop_ns = CString::new(prop_ns).unwrap_or_default();
-            let c_prop_name = CString::new(prop_path).unwrap_or_default();
-            let c_qual_ns = CString::new(qual_ns).unwrap_or_default();
-            let c_qual_name = CString::new(qual_name).unwrap_or_default();
+            let c_prop_ns = CString::new(prop_ns)?;
+            let c_prop_name = CString::new(prop_path)?;
+            let c_qual_ns = CString::new(qual_ns)?;
+            let c_qual_name = CString::new(qual_name)?;
 
             let mut options: u32 = 0;
             let mut err = ffi::CXmpError::default();
 
-            unsafe {
+            let result = unsafe {
                 CXmpString::from_ptr(ffi::CXmpMetaGetQualifier(
                     m,
                     &mut err,
@@ -1314,13 +2185,51 @@ impl XmpMeta {
                     c_qual_name.as_ptr(),
                     &mut options,
                 ))
-                .map(|value| XmpValue { value, options })
-            }
+            };
+
+            XmpError::raise_from_c(&err)?;
+            Ok(result.map(|value| XmpValue { value, options }))
         } else {
-            None
+            Err(no_cpp_toolkit())
         }
     }
 
+    /// Returns an iterator over all qualifiers attached to a property.
+    ///
+    /// Each item is a tuple of the qualifier's namespace URI, its local name
+    /// (without namespace prefix), and its value. These can be passed directly
+    /// to [`XmpMeta::qualifier`] and related functions.
+    ///
+    /// ## Arguments
+    ///
+    /// * `prop_ns` and `prop_path`: See [Accessing
+    ///   properties](#accessing-properties).
+    ///
+    /// ## Error handling
+    ///
+    /// Any errors (for instance, empty or invalid namespace or property name)
+    /// are ignored; the iterator will be empty in such cases.
+    pub fn qualifiers<'a>(
+        &'a self,
+        prop_ns: &str,
+        prop_path: &str,
+    ) -> impl Iterator<Item = (String, String, XmpValue<String>)> + 'a {
+        self.record_read(prop_ns, prop_path);
+
+        self.iter(
+            IterOptions::default()
+                .property(prop_ns, prop_path)
+                .immediate_children_only(),
+        )
+        .filter(|prop| prop.value.is_qualifier())
+        .filter_map(|prop| {
+            let (_, qual_path) = prop.name.rsplit_once("/?")?;
+            let (prefix, name) = qual_path.split_once(':')?;
+            let ns = Self::namespace_uri(prefix)?;
+            Some((ns, name.to_owned(), prop.value))
+        })
+    }
+
     /// Creates or sets a qualifier attached to a property.
     ///
     /// Use this to set a value for an existing qualifier, or create a new
@@ -1337,6 +2246,11 @@ impl XmpMeta {
     ///   properties](#accessing-properties) again.) Specifies the qualifier.
     /// * `qual_value`: Contains value and flags for the qualifier to be added
     ///   to the property.
+    ///
+    /// If strict language tags are enabled (see
+    /// [`XmpMeta::set_strict_lang_tags`]), setting an `xml:lang` qualifier
+    /// to a malformed language tag fails with an error of type
+    /// [`XmpErrorType::BadValue`].
     pub fn set_qualifier(
         &mut self,
         namespace: &str,
@@ -1345,6 +2259,12 @@ impl XmpMeta {
         qual_name: &str,
         qual_value: &XmpValue<String>,
     ) -> XmpResult<()> {
+        self.record_write(namespace, prop_name);
+
+        if self.strict_lang_tags && qual_ns == xmp_ns::XML && qual_name == "lang" {
+            xmp_lang::check(&qual_value.value)?;
+        }
+
         if let Some(m) = self.m {
             let c_struct_ns = CString::new(namespace)?;
             let c_prop_name = CString::new(prop_name.as_bytes())?;
@@ -1366,7 +2286,11 @@ impl XmpMeta {
                 );
             }
 
-            XmpError::raise_from_c(&err)
+            XmpError::raise_from_c(&err)?;
+            self.notify_change_at(ChangeKind::Set, namespace, || {
+                Self::compose_qualifier_path(namespace, prop_name, qual_ns, qual_name)
+            });
+            Ok(())
         } else {
             Err(no_cpp_toolkit())
         }
@@ -1389,6 +2313,8 @@ impl XmpMeta {
         qual_ns: &str,
         qual_name: &str,
     ) -> XmpResult<()> {
+        self.record_write(prop_ns, prop_name);
+
         if let Some(m) = self.m {
             let c_prop_ns = CString::new(prop_ns)?;
             let c_prop_name = CString::new(prop_name.as_bytes())?;
@@ -1407,7 +2333,11 @@ impl XmpMeta {
                 );
             }
 
-            XmpError::raise_from_c(&err)
+            XmpError::raise_from_c(&err)?;
+            self.notify_change_at(ChangeKind::Delete, prop_ns, || {
+                Self::compose_qualifier_path(prop_ns, prop_name, qual_ns, qual_name)
+            });
+            Ok(())
         } else {
             Err(no_cpp_toolkit())
         }
@@ -1496,6 +2426,8 @@ impl XmpMeta {
         generic_lang: Option<&str>,
         specific_lang: &str,
     ) -> Option<(XmpValue<String>, String)> {
+        self.record_read(namespace, path);
+
         if let Some(m) = self.m {
             let c_ns = CString::new(namespace).unwrap_or_default();
             let c_name = CString::new(path).unwrap_or_default();
@@ -1533,6 +2465,125 @@ impl XmpMeta {
         }
     }
 
+    /// Retrieves the most appropriate item from an alt-text array for a
+    /// prioritized list of languages, using the "lookup" scheme of
+    /// [RFC 4647](https://www.rfc-editor.org/rfc/rfc4647#section-3.4).
+    ///
+    /// Each language range in `languages` is tried in turn. If no item's
+    /// language matches a range exactly (ignoring case), the range is
+    /// progressively shortened by removing subtags from the end and tried
+    /// again, so that a request for `de-CH-1996` tries `de-CH-1996`, then
+    /// `de-CH`, then `de`. Only then is the next range in `languages` tried.
+    /// The wildcard range `*` is ignored.
+    ///
+    /// If no range matches, the `x-default` item is returned if there is
+    /// one, and otherwise the first item.
+    ///
+    /// Unlike [`XmpMeta::localized_text`], the lookup never matches an item
+    /// whose language is more specific than the requested range: a request
+    /// for `de` does not select a `de-DE` item. List such fallbacks in
+    /// `languages` explicitly if they are wanted.
+    ///
+    /// If strict language tags are enabled (see
+    /// [`XmpMeta::set_strict_lang_tags`]), malformed language tags are
+    /// rejected with an error of type [`XmpErrorType::BadValue`].
+    /// Otherwise, any tag is accepted and only its capitalization is
+    /// normalized.
+    ///
+    /// ## Arguments
+    ///
+    /// * `namespace` and `path`: See [Accessing
+    ///   properties](#accessing-properties).
+    /// * `languages`: Language ranges in order of preference, such as
+    ///   `["de-CH", "fr-CH", "en"]`.
+    ///
+    /// ## Return value
+    ///
+    /// If the property is a non-empty alt-text array, returns
+    /// `Some(XmpValue<String>, String)` where the second string is the
+    /// language of the selected item.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use xmp_toolkit::{xmp_ns, XmpMeta};
+    /// let mut meta = XmpMeta::new().unwrap();
+    /// meta.set_localized_text(xmp_ns::DC, "title", None, "x-default", "Sunset")
+    ///     .unwrap();
+    /// meta.set_localized_text(xmp_ns::DC, "title", None, "de", "Sonnenuntergang")
+    ///     .unwrap();
+    ///
+    /// let (title, lang) = meta
+    ///     .localized_text_lookup(xmp_ns::DC, "title", &["de-CH", "en-US"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(title.value, "Sonnenuntergang");
+    /// assert_eq!(lang, "de");
+    /// ```
+    pub fn localized_text_lookup(
+        &self,
+        namespace: &str,
+        path: &str,
+        languages: &[&str],
+    ) -> Option<(XmpValue<String>, String)> {
+        self.record_read(namespace, path);
+
+        if !self.property(namespace, path)?.is_alt_text() {
+            return None;
+        }
+
+        let items: Vec<(String, XmpValue<String>)> = (1..=self.array_len(namespace, path))
+            .filter_map(|index| {
+                let item_path =
+                    Self::compose_array_item_path(namespace, path, index as i32).ok()?;
+                let lang = self.qualifier(namespace, &item_path, xmp_ns::XML, "lang")?;
+                let value = self.property(namespace, &item_path)?;
+                Some((lang.value, value))
+            })
+            .collect();
+
+        let find = |range: &str| {
+            items
+                .iter()
+                .find(|(lang, _)| lang.eq_ignore_ascii_case(range))
+        };
+
+        let matched = languages
+            .iter()
+            .map(|range| range.trim())
+            .filter(|range| !range.is_empty() && *range != "*")
+            .find_map(|range| {
+                std::iter::successors(Some(range), |range| xmp_lang::lookup_fallback(range))
+                    .find_map(find)
+            })
+            .or_else(|| find(xmp_lang::X_DEFAULT))
+            .or_else(|| items.first())?;
+
+        Some((matched.1.clone(), matched.0.clone()))
+    }
+
+    /// Enables or disables strict checking of language tags.
+    ///
+    /// When enabled, [`XmpMeta::set_localized_text`] and
+    /// [`XmpMeta::set_qualifier`] (for `xml:lang` qualifiers) reject
+    /// language tags that are not well-formed BCP 47 tags, as checked by
+    /// [`xmp_lang::is_well_formed`]. When disabled (the default), any tag
+    /// is accepted.
+    ///
+    /// This setting is copied when this object is cloned. It does not
+    /// affect parsing; use [`xmp_lang::is_well_formed`] to check existing
+    /// metadata.
+    pub fn set_strict_lang_tags(&mut self, strict: bool) {
+        self.strict_lang_tags = strict;
+    }
+
+    /// Returns `true` if strict checking of language tags is enabled.
+    ///
+    /// See [`XmpMeta::set_strict_lang_tags`].
+    pub fn strict_lang_tags(&self) -> bool {
+        self.strict_lang_tags
+    }
+
     /// Modifies the value of a selected item in an alt-text array using a
     /// string object.
     ///
@@ -1584,6 +2635,15 @@ impl XmpMeta {
         specific_lang: &str,
         item_value: &str,
     ) -> XmpResult<()> {
+        self.record_write(namespace, path);
+
+        if self.strict_lang_tags {
+            if let Some(generic_lang) = generic_lang.filter(|lang| !lang.is_empty()) {
+                xmp_lang::check(generic_lang)?;
+            }
+            xmp_lang::check(specific_lang)?;
+        }
+
         if let Some(m) = self.m {
             let c_ns = CString::new(namespace).unwrap_or_default();
             let c_name = CString::new(path).unwrap_or_default();
@@ -1610,6 +2670,7 @@ impl XmpMeta {
             };
 
             XmpError::raise_from_c(&err)?;
+            self.notify_change(ChangeKind::Set, namespace, path);
             Ok(())
         } else {
             Err(no_cpp_toolkit())
@@ -1887,6 +2948,9 @@ impl XmpMeta {
     /// value. Language Alternative arrays are sorted by the `xml:lang`
     /// qualifiers, with the `x-default` item placed first.
     ///
+    /// To control the order in serialized output without modifying this
+    /// struct, see [`XmpMeta::to_string_with_ordering`].
+    ///
     /// If this function is not called, the data model will typically appear
     /// in order of construction. In other words, content parsed from a file
     /// or string will appear in the order that it did in the source material.
@@ -1896,46 +2960,647 @@ impl XmpMeta {
         if let Some(m) = self.m {
             let mut err = ffi::CXmpError::default();
 
-            unsafe {
-                ffi::CXmpMetaSort(m, &mut err);
-            }
+            unsafe {
+                ffi::CXmpMetaSort(m, &mut err);
+            }
+
+            XmpError::raise_from_c(&err)
+        } else {
+            Err(no_cpp_toolkit())
+        }
+    }
+
+    /// Returns the client-assigned name of this XMP object.
+    ///
+    /// This name is the empty string by default.
+    ///
+    /// See also [`XmpMeta::set_name`].
+    pub fn name(&self) -> String {
+        if let Some(m) = self.m {
+            let mut err = ffi::CXmpError::default();
+            unsafe { CXmpString::from_ptr(ffi::CXmpMetaGetObjectName(m, &mut err)).as_string() }
+        } else {
+            String::default()
+        }
+    }
+
+    /// Assigns a name to this XMP object.
+    ///
+    /// This name can be retrieved via [`XmpMeta::name`].
+    ///
+    /// This name is for client use only and it not interpreted by
+    /// the XMP Toolkit.
+    pub fn set_name(&mut self, name: &str) -> XmpResult<()> {
+        if let Some(m) = self.m {
+            let c_name = CString::new(name.as_bytes())?;
+            let mut err = ffi::CXmpError::default();
+
+            unsafe {
+                ffi::CXmpMetaSetObjectName(m, &mut err, c_name.as_ptr());
+            }
+
+            XmpError::raise_from_c(&err)
+        } else {
+            Err(no_cpp_toolkit())
+        }
+    }
+
+    /// Returns the value of the `xmp:Rating` property.
+    ///
+    /// By convention, ratings range from 1 to 5 stars, with 0 meaning
+    /// unrated and -1 meaning rejected. The XMP specification defines this
+    /// property as a real number; fractional values are rounded to the
+    /// nearest integer. Values outside the conventional range are returned
+    /// as is.
+    ///
+    /// Returns `None` if the property does not exist or can not be
+    /// interpreted as a number.
+    pub fn rating(&self) -> Option<i32> {
+        self.property_f64(xmp_ns::XMP, "Rating")
+            .filter(|v| v.value.is_finite())
+            .map(|v| v.value.round() as i32)
+    }
+
+    /// Sets the value of the `xmp:Rating` property.
+    ///
+    /// Values outside the range -1 (rejected) to 5 are clamped to that
+    /// range. See [`XmpMeta::rating`].
+    pub fn set_rating(&mut self, rating: i32) -> XmpResult<()> {
+        self.set_property_i32(xmp_ns::XMP, "Rating", &rating.clamp(-1, 5).into())
+    }
+
+    /// Returns the value of the `xmp:Label` property.
+    ///
+    /// This is typically the name of a color label such as `"Red"`, as
+    /// assigned in a culling workflow. Returns `None` if the property does
+    /// not exist.
+    pub fn label(&self) -> Option<String> {
+        self.property(xmp_ns::XMP, "Label").map(|v| v.value)
+    }
+
+    /// Sets the value of the `xmp:Label` property.
+    ///
+    /// An empty string removes the property, which is how most
+    /// applications represent "no label."
+    pub fn set_label(&mut self, label: &str) -> XmpResult<()> {
+        if label.is_empty() {
+            self.delete_property(xmp_ns::XMP, "Label")
+        } else {
+            self.set_property(xmp_ns::XMP, "Label", &label.into())
+        }
+    }
+
+    /// Returns the hierarchical keywords stored in the
+    /// `lr:hierarchicalSubject` property as a tree.
+    ///
+    /// Returns an empty tree if the property does not exist.
+    pub fn hierarchical_keywords(&self) -> KeywordTree {
+        KeywordTree::from_paths(
+            self.property_array(xmp_ns::LIGHTROOM, "hierarchicalSubject")
+                .map(|item| item.value),
+        )
+    }
+
+    /// Replaces the hierarchical keywords in the `lr:hierarchicalSubject`
+    /// property and keeps the flat `dc:subject` property in sync.
+    ///
+    /// `lr:hierarchicalSubject` is rewritten with one entry per leaf of
+    /// `keywords` (see [`KeywordTree::paths`]). Every keyword in the tree,
+    /// at any level, is added to `dc:subject` if not already present.
+    /// Keywords that were part of the previous hierarchy but are not part
+    /// of the new one are removed from `dc:subject`; other `dc:subject`
+    /// entries are left alone.
+    ///
+    /// An empty tree removes `lr:hierarchicalSubject`.
+    ///
+    /// The update is atomic: If an error occurs, `self` is left unchanged.
+    pub fn set_hierarchical_keywords(&mut self, keywords: &KeywordTree) -> XmpResult<()> {
+        self.record_write(xmp_ns::LIGHTROOM, "hierarchicalSubject");
+        self.record_write(xmp_ns::DC, "subject");
+
+        if self.m.is_none() {
+            return Err(no_cpp_toolkit());
+        }
+
+        XmpMeta::register_namespace(xmp_ns::LIGHTROOM, "lr")?;
+
+        // Read through the staged copy, which does not count accesses.
+        let mut staged = self.stage();
+
+        let old_keywords = staged.hierarchical_keywords().keywords();
+        let new_keywords = keywords.keywords();
+
+        let mut subjects: Vec<XmpValue<String>> = staged
+            .property_array(xmp_ns::DC, "subject")
+            .filter(|item| {
+                !old_keywords.contains(&item.value) || new_keywords.contains(&item.value)
+            })
+            .collect();
+
+        for keyword in new_keywords {
+            if !subjects.iter().any(|item| item.value == keyword) {
+                subjects.push(keyword.into());
+            }
+        }
+
+        let paths: Vec<XmpValue<String>> =
+            keywords.paths().into_iter().map(XmpValue::new).collect();
+
+        if paths.is_empty() {
+            staged.delete_property(xmp_ns::LIGHTROOM, "hierarchicalSubject")?;
+        } else {
+            staged.set_array(
+                xmp_ns::LIGHTROOM,
+                "hierarchicalSubject",
+                ArrayForm::Bag,
+                &paths,
+            )?;
+        }
+
+        if subjects.is_empty() {
+            staged.delete_property(xmp_ns::DC, "subject")?;
+        } else {
+            staged.set_array(xmp_ns::DC, "subject", ArrayForm::Bag, &subjects)?;
+        }
+
+        self.commit_staged(staged);
+        Ok(())
+    }
+
+    /// Adds a `|`-separated keyword path such as `Animals|Birds|Heron` to
+    /// the `lr:hierarchicalSubject` property and adds each keyword in the
+    /// path to the flat `dc:subject` property.
+    ///
+    /// Existing entries are left as they are, and nothing is added to
+    /// either property if an identical entry already exists. Keywords are
+    /// normalized as described in [`KeywordTree::insert`].
+    ///
+    /// The update is atomic: If an error occurs, `self` is left unchanged.
+    pub fn add_hierarchical_keyword(&mut self, path: &str) -> XmpResult<()> {
+        self.record_write(xmp_ns::LIGHTROOM, "hierarchicalSubject");
+        self.record_write(xmp_ns::DC, "subject");
+
+        if self.m.is_none() {
+            return Err(no_cpp_toolkit());
+        }
+
+        let normalized = KeywordTree::from_paths([path]);
+        let Some(path) = normalized.paths().pop() else {
+            return Ok(());
+        };
+
+        XmpMeta::register_namespace(xmp_ns::LIGHTROOM, "lr")?;
+
+        let mut staged = self.stage();
+
+        let lr_bag = XmpValue::from("hierarchicalSubject").set_is_array(true);
+        if !staged
+            .property_array(xmp_ns::LIGHTROOM, "hierarchicalSubject")
+            .any(|item| item.value == path)
+        {
+            staged.append_array_item(xmp_ns::LIGHTROOM, &lr_bag, &path.as_str().into())?;
+        }
+
+        let dc_bag = XmpValue::from("subject").set_is_array(true);
+        for keyword in normalized.keywords() {
+            if !staged
+                .property_array(xmp_ns::DC, "subject")
+                .any(|item| item.value == keyword)
+            {
+                staged.append_array_item(xmp_ns::DC, &dc_bag, &keyword.into())?;
+            }
+        }
+
+        self.commit_staged(staged);
+        Ok(())
+    }
+
+    /// Starts counting how often each property is read or written through
+    /// this object.
+    ///
+    /// This is intended as a diagnostic aid, for instance, to learn which
+    /// properties a metadata-processing pass actually uses. Counting is off
+    /// by default and adds a small cost to each accessor call while enabled.
+    /// Calling this function while counting is already enabled has no
+    /// effect.
+    ///
+    /// Counts are kept per object; a clone of this object starts without
+    /// counting enabled.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use xmp_toolkit::{xmp_ns, XmpMeta};
+    /// let mut meta = XmpMeta::new().unwrap();
+    /// meta.enable_access_stats();
+    ///
+    /// meta.set_property(xmp_ns::XMP, "CreatorTool", &"Example".into())
+    ///     .unwrap();
+    /// meta.property(xmp_ns::XMP, "CreatorTool");
+    /// meta.property(xmp_ns::XMP, "CreatorTool");
+    ///
+    /// let stats = meta.access_stats().unwrap();
+    /// let count = stats.get(xmp_ns::XMP, "CreatorTool");
+    /// assert_eq!(count.reads, 2);
+    /// assert_eq!(count.writes, 1);
+    ///
+    /// println!("{stats}");
+    /// ```
+    pub fn enable_access_stats(&mut self) {
+        if self.access_stats.is_none() {
+            self.access_stats = Some(Mutex::new(AccessStats::default()));
+        }
+    }
+
+    /// Returns a snapshot of the property access counts collected so far,
+    /// or `None` if counting is not enabled.
+    ///
+    /// See [`XmpMeta::enable_access_stats`].
+    pub fn access_stats(&self) -> Option<AccessStats> {
+        self.access_stats
+            .as_ref()
+            .map(|stats| stats.lock().unwrap_or_else(|e| e.into_inner()).clone())
+    }
+
+    /// Stops counting property accesses and returns the counts collected
+    /// so far, or `None` if counting was not enabled.
+    pub fn disable_access_stats(&mut self) -> Option<AccessStats> {
+        self.access_stats
+            .take()
+            .map(|stats| stats.into_inner().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    pub(crate) fn record_read(&self, namespace: &str, path: &str) {
+        if let Some(stats) = &self.access_stats {
+            stats
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .record_read(namespace, path);
+        }
+    }
+
+    pub(crate) fn record_write(&self, namespace: &str, path: &str) {
+        if let Some(stats) = &self.access_stats {
+            stats
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .record_write(namespace, path);
+        }
+    }
+
+    /// Registers a function to be called after each modification of this
+    /// data model.
+    ///
+    /// The listener receives a [`ChangeEvent`] describing the kind of
+    /// modification and the affected path. Events are reported for the
+    /// functions that set, append, or delete properties, array items,
+    /// struct fields, qualifiers, and localized text. Functions that make
+    /// several modifications at once (such as [`XmpMeta::set_array`])
+    /// report each of them, but only after all have succeeded; if such a
+    /// function fails, no events are reported.
+    ///
+    /// Listeners are called in the order in which they were registered.
+    /// They belong to this object; a clone of this object starts without
+    /// listeners.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use std::sync::{atomic::{AtomicBool, Ordering}, Arc};
+    /// # use xmp_toolkit::{xmp_ns, XmpMeta};
+    /// let dirty = Arc::new(AtomicBool::new(false));
+    ///
+    /// let mut meta = XmpMeta::new().unwrap();
+    /// let listener_dirty = dirty.clone();
+    /// meta.on_change(move |event| {
+    ///     println!("{event}");
+    ///     listener_dirty.store(true, Ordering::Relaxed);
+    /// });
+    ///
+    /// meta.set_property(xmp_ns::XMP, "CreatorTool", &"Example".into())
+    ///     .unwrap();
+    /// assert!(dirty.load(Ordering::Relaxed));
+    /// ```
+    pub fn on_change<F>(&mut self, listener: F)
+    where
+        F: Fn(&ChangeEvent) + Send + Sync + 'static,
+    {
+        self.change_listeners.push(Box::new(listener));
+    }
+
+    /// Removes all listeners registered with [`XmpMeta::on_change`].
+    pub fn clear_change_listeners(&mut self) {
+        self.change_listeners.clear();
+    }
+
+    /// Returns `true` if a modification would be reported to a listener,
+    /// either directly or after a staged update is committed.
+    pub(crate) fn observes_changes(&self) -> bool {
+        !self.change_listeners.is_empty() || self.staged_changes.is_some()
+    }
+
+    pub(crate) fn notify_change(&mut self, kind: ChangeKind, namespace: &str, path: &str) {
+        if !self.observes_changes() {
+            return;
+        }
+
+        self.emit_change(ChangeEvent {
+            kind,
+            namespace: namespace.to_owned(),
+            path: path.to_owned(),
+        });
+    }
+
+    /// Like [`XmpMeta::notify_change`], but composes the path only if there
+    /// is a listener.
+    fn notify_change_at<F>(&mut self, kind: ChangeKind, namespace: &str, compose_path: F)
+    where
+        F: FnOnce() -> XmpResult<String>,
+    {
+        if self.observes_changes() {
+            if let Ok(path) = compose_path() {
+                self.notify_change(kind, namespace, &path);
+            }
+        }
+    }
+
+    fn emit_change(&mut self, event: ChangeEvent) {
+        if let Some(staged_changes) = &mut self.staged_changes {
+            staged_changes.push(event);
+        } else {
+            for listener in &self.change_listeners {
+                listener(&event);
+            }
+        }
+    }
+
+    /// Returns a copy of this object on which to stage an update that will
+    /// later be applied with [`XmpMeta::commit_staged`].
+    ///
+    /// If this object has change listeners, modifications of the copy are
+    /// recorded so that they can be reported when the update is committed.
+    pub(crate) fn stage(&self) -> XmpMeta {
+        let mut staged = self.clone();
+
+        if self.observes_changes() {
+            staged.staged_changes = Some(Vec::new());
+        }
+
+        staged
+    }
+
+    /// Replaces the contents of this object with `staged`, which was
+    /// created by [`XmpMeta::stage`] and then modified.
+    ///
+    /// Unlike plain assignment, this preserves state that belongs to this
+    /// Rust object rather than to the XMP data model.
+    pub(crate) fn commit_staged(&mut self, mut staged: XmpMeta) {
+        let changes = staged.staged_changes.take().unwrap_or_default();
+
+        staged.access_stats = self
+            .access_stats
+            .take()
+            .or_else(|| staged.access_stats.take());
+        staged.change_listeners = std::mem::take(&mut self.change_listeners);
+        staged.staged_changes = self.staged_changes.take();
+        *self = staged;
+
+        for event in changes {
+            self.emit_change(event);
+        }
+    }
+
+    /// Like [`XmpMeta::stage`], but also lends this object's access
+    /// statistics to the staged copy so that accesses made through it are
+    /// counted. Pass the copy to [`XmpMeta::commit_staged`] or
+    /// [`XmpMeta::abandon_staged`] to return them.
+    pub(crate) fn stage_for_edit(&mut self) -> XmpMeta {
+        let mut staged = self.stage();
+        staged.access_stats = self.access_stats.take();
+        staged
+    }
 
-            XmpError::raise_from_c(&err)
-        } else {
-            Err(no_cpp_toolkit())
+    /// Discards `staged`, which was created by [`XmpMeta::stage_for_edit`],
+    /// and recovers the access statistics lent to it.
+    pub(crate) fn abandon_staged(&mut self, mut staged: XmpMeta) {
+        if self.access_stats.is_none() {
+            self.access_stats = staged.access_stats.take();
         }
     }
 
-    /// Returns the client-assigned name of this XMP object.
+    /// Starts a set of edits that can be committed or rolled back as a
+    /// unit.
     ///
-    /// This name is the empty string by default.
+    /// See [`EditGuard`].
+    pub fn begin_edit(&mut self) -> EditGuard<'_> {
+        EditGuard::new(self)
+    }
+
+    /// Starts a batch of property operations to be applied to this object
+    /// in a single call into the C++ XMP Toolkit.
     ///
-    /// See also [`XmpMeta::set_name`].
-    pub fn name(&self) -> String {
-        if let Some(m) = self.m {
+    /// See [`XmpBatch`].
+    pub fn batch(&mut self) -> XmpBatch<'_> {
+        XmpBatch::new(self)
+    }
+
+    /// Converts this data model into an immutable snapshot that can be
+    /// shared cheaply across threads, without copying it.
+    ///
+    /// Taking `self` by value guarantees that nothing else can modify the
+    /// data model afterwards. To keep a mutable copy as well, clone this
+    /// object first, or use [`XmpMeta::read_handle`] for a view that shares
+    /// this object and sees later changes. See [`XmpMetaSnapshot`].
+    pub fn into_snapshot(self) -> XmpMetaSnapshot {
+        XmpMetaSnapshot {
+            meta: Arc::new(self),
+        }
+    }
+
+    /// Returns a read-only handle that shares this data model without
+    /// copying it.
+    ///
+    /// The handle and all of its clones refer to the same C++ object as
+    /// this `XmpMeta`. The C++ XMP Toolkit reference-counts that object and
+    /// takes a lock for each call, so the handle can be shared freely
+    /// across threads. This makes it suitable for parsing a packet once and
+    /// answering many concurrent queries against it.
+    ///
+    /// Because no copy is made, changes made later through this `XmpMeta`
+    /// are visible through the handle. Use [`XmpMeta::into_snapshot`]
+    /// instead if you need a view that is guaranteed not to change. See
+    /// [`XmpMetaReadHandle`].
+    pub fn read_handle(&self) -> XmpMetaReadHandle {
+        let result = if let Some(m) = self.m {
             let mut err = ffi::CXmpError::default();
-            unsafe { CXmpString::from_ptr(ffi::CXmpMetaGetObjectName(m, &mut err)).as_string() }
+            Self::from_ptr(unsafe { ffi::CXmpMetaShare(m, &mut err) })
         } else {
-            String::default()
+            Self::from_ptr(std::ptr::null_mut())
+        };
+
+        XmpMetaReadHandle {
+            meta: Arc::new(result),
         }
     }
 
-    /// Assigns a name to this XMP object.
+    /// Returns the namespace URI and name of every top-level property.
+    pub(crate) fn top_level_properties(&self) -> Vec<(String, String)> {
+        let namespaces: Vec<String> = self
+            .iter(IterOptions::default().immediate_children_only())
+            .map(|schema| schema.schema_ns)
+            .collect();
+
+        namespaces
+            .into_iter()
+            .flat_map(|ns| {
+                self.iter(
+                    IterOptions::default()
+                        .schema_ns(&ns)
+                        .immediate_children_only(),
+                )
+                .map(move |prop| (ns.clone(), prop.name))
+                .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Compares this object with a newer version of the same metadata.
     ///
-    /// This name can be retrieved via [`XmpMeta::name`].
+    /// The result lists each value that was added, removed, or changed in
+    /// `other`, and can be rendered as a plain text or Markdown report via
+    /// [`XmpDiff::to_text`] or [`XmpDiff::to_markdown`]. Items of alt-text
+    /// arrays are matched by language rather than by position.
     ///
-    /// This name is for client use only and it not interpreted by
-    /// the XMP Toolkit.
-    pub fn set_name(&mut self, name: &str) -> XmpResult<()> {
-        if let Some(m) = self.m {
-            let c_name = CString::new(name.as_bytes())?;
+    /// ## Example
+    ///
+    /// ```
+    /// # use xmp_toolkit::{xmp_ns, DiffKind, XmpMeta};
+    /// let mut old = XmpMeta::new().unwrap();
+    /// old.set_localized_text(xmp_ns::DC, "title", None, "en-US", "Sunset")
+    ///     .unwrap();
+    ///
+    /// let mut new = old.clone();
+    /// new.set_localized_text(xmp_ns::DC, "title", None, "en-US", "Sunrise")
+    ///     .unwrap();
+    ///
+    /// let diff = old.diff(&new);
+    /// let title = diff
+    ///     .differences
+    ///     .iter()
+    ///     .find(|d| d.path == "dc:title[?xml:lang=\"en-US\"]")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(title.kind, DiffKind::Changed);
+    /// assert_eq!(title.new_value.as_deref(), Some("Sunrise"));
+    /// ```
+    pub fn diff(&self, other: &XmpMeta) -> XmpDiff {
+        xmp_diff::diff(self, other)
+    }
+
+    /// Performs a three-way merge of two versions of metadata that were
+    /// edited independently from a common ancestor.
+    ///
+    /// Each top-level property is merged as a unit. A property that was
+    /// changed (or added, or deleted) in only one of `ours` and `theirs` is
+    /// taken from that version. A property that was changed in both
+    /// versions, but not in the same way, is a conflict: it is resolved
+    /// according to `policy` and reported in [`MergeResult::conflicts`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use xmp_toolkit::{xmp_ns, MergePolicy, XmpMeta};
+    /// let mut base = XmpMeta::new().unwrap();
+    /// base.set_property(xmp_ns::XMP, "Label", &"Red".into())
+    ///     .unwrap();
+    ///
+    /// let mut ours = base.clone();
+    /// ours.set_property(xmp_ns::XMP, "Rating", &"4".into())
+    ///     .unwrap();
+    ///
+    /// let mut theirs = base.clone();
+    /// theirs
+    ///     .set_property(xmp_ns::XMP, "Label", &"Green".into())
+    ///     .unwrap();
+    ///
+    /// let result = XmpMeta::merge3(&base, &ours, &theirs, MergePolicy::default()).unwrap();
+    /// assert!(result.conflicts.is_empty());
+    ///
+    /// let merged = result.merged;
+    /// assert_eq!(
+    ///     merged.property(xmp_ns::XMP, "Label").unwrap().value,
+    ///     "Green"
+    /// );
+    /// assert_eq!(merged.property(xmp_ns::XMP, "Rating").unwrap().value, "4");
+    /// ```
+    pub fn merge3(
+        base: &XmpMeta,
+        ours: &XmpMeta,
+        theirs: &XmpMeta,
+        policy: MergePolicy,
+    ) -> XmpResult<MergeResult> {
+        xmp_merge::merge3(base, ours, theirs, policy)
+    }
+
+    /// Removes personal data described by `profile`, such as the location
+    /// where an image was captured or the serial number of the camera.
+    ///
+    /// Returns a report listing each node that was removed.
+    ///
+    /// The update is atomic: If an error occurs, this object is left
+    /// unchanged.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use xmp_toolkit::{xmp_ns, RedactionProfile, XmpMeta};
+    /// let mut meta = XmpMeta::new().unwrap();
+    /// meta.set_property(xmp_ns::EXIF, "GPSLatitude", &"47,0N".into())
+    ///     .unwrap();
+    /// meta.set_property(xmp_ns::XMP, "Label", &"Red".into())
+    ///     .unwrap();
+    ///
+    /// let report = meta.redact(RedactionProfile::personal_data()).unwrap();
+    /// assert_eq!(report.removed.len(), 1);
+    ///
+    /// assert!(!meta.contains_property(xmp_ns::EXIF, "GPSLatitude"));
+    /// assert!(meta.contains_property(xmp_ns::XMP, "Label"));
+    /// ```
+    pub fn redact(&mut self, profile: RedactionProfile) -> XmpResult<RedactionReport> {
+        xmp_redact::redact(self, &profile)
+    }
+
+    /// Copies the subtree at `source_path` in this object to `dest_path` in
+    /// `dest`, replacing any existing node there.
+    pub(crate) fn duplicate_subtree(
+        &self,
+        dest: &mut XmpMeta,
+        namespace: &str,
+        source_path: &str,
+        dest_path: &str,
+    ) -> XmpResult<()> {
+        if let (Some(source_m), Some(dest_m)) = (self.m, dest.m) {
+            let c_ns = CString::new(namespace)?;
+            let c_source_path = CString::new(source_path)?;
+            let c_dest_path = CString::new(dest_path)?;
             let mut err = ffi::CXmpError::default();
 
             unsafe {
-                ffi::CXmpMetaSetObjectName(m, &mut err, c_name.as_ptr());
+                ffi::CXmpMetaDuplicateSubtree(
+                    source_m,
+                    dest_m,
+                    &mut err,
+                    c_ns.as_ptr(),
+                    c_source_path.as_ptr(),
+                    c_ns.as_ptr(),
+                    c_dest_path.as_ptr(),
+                    0,
+                );
             }
 
-            XmpError::raise_from_c(&err)
+            XmpError::raise_from_c(&err)?;
+            dest.notify_change(ChangeKind::Set, namespace, dest_path);
+            Ok(())
         } else {
             Err(no_cpp_toolkit())
         }
@@ -1945,21 +3610,76 @@ impl XmpMeta {
     /// string containing serialized RDF. This string must be a complete RDF
     /// parse stream.
     pub fn from_str_with_options(s: &str, options: FromStrOptions) -> XmpResult<Self> {
+        Self::parse(s, options, None)
+    }
+
+    /// Creates a new `XmpMeta` struct from a string containing serialized
+    /// RDF, as [`XmpMeta::from_str_with_options`] does, and also reports
+    /// any problems that the parser repaired or dropped along the way.
+    ///
+    /// The C++ XMP Toolkit is lenient: it reconciles inconsistent alias
+    /// values, ignores RDF constructs it does not understand, and may
+    /// return an empty data model for input that contains no XMP. None of
+    /// this is reported by [`XmpMeta::from_str_with_options`]. This
+    /// function returns the same metadata together with a
+    /// [`ParseWarning`] for each such problem, so that validators can
+    /// surface data-quality issues.
+    ///
+    /// If the input is not empty but yields no properties, a warning of
+    /// type [`XmpErrorType::BadXmp`] is included.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the same errors as [`XmpMeta::from_str_with_options`].
+    /// Problems that prevent parsing altogether are returned as errors,
+    /// not warnings.
+    pub fn from_str_with_report(
+        s: &str,
+        options: FromStrOptions,
+    ) -> XmpResult<(Self, Vec<ParseWarning>)> {
+        let mut warnings: Vec<ParseWarning> = vec![];
+        let result = Self::parse(s, options, Some(&mut warnings))?;
+
+        if !s.trim().is_empty() && result.iter(IterOptions::default()).next().is_none() {
+            warnings.push(ParseWarning {
+                error_type: XmpErrorType::BadXmp,
+                message: "No XMP properties found in input".to_owned(),
+            });
+        }
+
+        Ok((result, warnings))
+    }
+
+    fn parse(
+        s: &str,
+        options: FromStrOptions,
+        warnings: Option<&mut Vec<ParseWarning>>,
+    ) -> XmpResult<Self> {
         let mut err = ffi::CXmpError::default();
         let bytes = s.as_bytes();
 
         let m = unsafe {
-            ffi::CXmpMetaParseFromBuffer(
-                &mut err,
-                bytes.as_ptr(),
-                bytes.len() as u32,
-                options.options,
-            )
+            match warnings {
+                Some(warnings) => ffi::CXmpMetaParseFromBufferWithWarnings(
+                    &mut err,
+                    bytes.as_ptr(),
+                    bytes.len() as u32,
+                    options.options,
+                    (warnings as *mut Vec<ParseWarning>).cast(),
+                    ffi::xmp_collect_parse_warning,
+                ),
+                None => ffi::CXmpMetaParseFromBuffer(
+                    &mut err,
+                    bytes.as_ptr(),
+                    bytes.len() as u32,
+                    options.options,
+                ),
+            }
         };
 
         XmpError::raise_from_c(&err)?;
 
-        let result = XmpMeta { m: Some(m) };
+        let result = XmpMeta::from_ptr(m);
 
         if options.options & 0x01 != 0 {
             // Caller has asked that we require an `<x:xmpmeta>` element
@@ -1981,6 +3701,81 @@ impl XmpMeta {
         Ok(result)
     }
 
+    /// Creates a new `XmpMeta` struct and populates it with metadata from a
+    /// string containing JSON-LD.
+    ///
+    /// The subset of JSON-LD that corresponds to the XMP data model is
+    /// supported, which covers what is typically produced by digital asset
+    /// management systems:
+    ///
+    /// * Property names may be terms or compact IRIs defined by an inline
+    ///   `@context`, full IRIs, or compact IRIs using a prefix already
+    ///   registered with the XMP Toolkit (such as `dc:title`).
+    /// * Strings, numbers, and booleans become simple properties. `{"@id":
+    ///   ...}` becomes a URI property.
+    /// * Nested node objects become XMP structs.
+    /// * JSON arrays and `@set` become unordered arrays (`rdf:Bag`); `@list`
+    ///   and terms with `"@container": "@list"` become ordered arrays
+    ///   (`rdf:Seq`).
+    /// * Language-tagged values and language maps become alternative-language
+    ///   text (`rdf:Alt` with `xml:lang`).
+    /// * Nodes in a top-level array or `@graph` are merged, as the XMP Toolkit
+    ///   does with multiple `rdf:Description` elements.
+    ///
+    /// Namespaces that are not yet registered are registered using the
+    /// prefix given in the `@context`, if any.
+    ///
+    /// This function is available only when crate feature `json_ld` is
+    /// enabled.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::BadRdf`] if the input is
+    /// not valid JSON, uses a remote `@context`, or contains constructs
+    /// that can not be mapped to XMP, or [`XmpErrorType::BadSchema`] if a
+    /// property name can not be resolved to a namespace.
+    #[cfg(feature = "json_ld")]
+    pub fn from_json_ld(s: &str) -> XmpResult<Self> {
+        crate::json_ld::parse(s)
+    }
+
+    /// Parses each top-level `rdf:Description` block in a string
+    /// containing RDF into a separate `XmpMeta` struct, in the order in
+    /// which the blocks appear.
+    ///
+    /// The C++ XMP Toolkit merges all `rdf:Description` blocks in a packet
+    /// into a single data model, which is what
+    /// [`XmpMeta::from_str_with_options`] returns. Tooling that validates
+    /// packets written by other software sometimes needs to know which
+    /// properties appeared in which block; this function provides that
+    /// information without changing the merged data model.
+    ///
+    /// `options` are applied to each block, except that
+    /// [`FromStrOptions::require_xmp_meta`] only applies to the packet as
+    /// a whole.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the same errors as [`XmpMeta::from_str_with_options`] for
+    /// the packet as a whole or for any of its blocks.
+    pub fn description_blocks_from_str(
+        s: &str,
+        options: FromStrOptions,
+    ) -> XmpResult<Vec<XmpMeta>> {
+        // Parse the whole packet first so that malformed input is reported
+        // by the C++ XMP Toolkit rather than silently yielding fewer blocks.
+        Self::from_str_with_options(s, options.clone())?;
+
+        let block_options = FromStrOptions {
+            options: options.options & !FromStrOptions::REQUIRE_XMP_META,
+        };
+
+        rdf_scan::description_blocks(s)
+            .iter()
+            .map(|block| Self::from_str_with_options(block, block_options.clone()))
+            .collect()
+    }
+
     /// Converts metadata in this XMP object into a string as RDF.
     ///
     /// In many cases, this struct's implementation of [`Display`]
@@ -1993,6 +3788,10 @@ impl XmpMeta {
     ///
     /// [`Display`]: std::fmt::Display
     pub fn to_string_with_options(&self, options: ToStringOptions) -> XmpResult<String> {
+        if options.canonical {
+            return rdf_canonical::to_string(self);
+        }
+
         if let Some(m) = self.m {
             let c_newline = CString::new(options.newline).unwrap_or_default();
             let c_indent = CString::new(options.indent).unwrap_or_default();
@@ -2018,6 +3817,209 @@ impl XmpMeta {
             Err(no_cpp_toolkit())
         }
     }
+
+    /// Converts metadata in this XMP object into RDF triples in
+    /// [N-Triples](https://www.w3.org/TR/n-triples/) format, for loading
+    /// into a triple store.
+    ///
+    /// The triples are those described by the RDF/XML form of this object,
+    /// as defined in part 1 of the XMP specification:
+    ///
+    /// * The subject of top-level properties is the IRI given by
+    ///   [`XmpMeta::name`] (the `rdf:about` attribute), or a blank node if that
+    ///   is empty.
+    /// * Structs and arrays are blank nodes. Arrays have an `rdf:type` of
+    ///   `rdf:Bag`, `rdf:Seq`, or `rdf:Alt` and items `rdf:_1`, `rdf:_2`, and
+    ///   so on.
+    /// * `xml:lang` qualifiers become language tags on literals. Values with
+    ///   other qualifiers become blank nodes with an `rdf:value`.
+    /// * URI values are IRIs. All other values are plain literals.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use xmp_toolkit::{xmp_ns, XmpMeta};
+    /// let mut meta = XmpMeta::new().unwrap();
+    /// meta.set_property(xmp_ns::XMP, "Label", &"Red".into())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     meta.to_ntriples().unwrap(),
+    ///     "_:b0 <http://ns.adobe.com/xap/1.0/Label> \"Red\" .\n"
+    /// );
+    /// ```
+    pub fn to_ntriples(&self) -> XmpResult<String> {
+        if self.m.is_none() {
+            return Err(no_cpp_toolkit());
+        }
+
+        Ok(rdf_triples::to_ntriples(self))
+    }
+
+    /// Converts metadata in this XMP object into RDF triples in
+    /// [Turtle](https://www.w3.org/TR/turtle/) format.
+    ///
+    /// The triples are the same as for [`XmpMeta::to_ntriples`]. IRIs in
+    /// namespaces registered with the XMP Toolkit are abbreviated using the
+    /// registered prefix, and the triples are grouped by subject.
+    pub fn to_turtle(&self) -> XmpResult<String> {
+        if self.m.is_none() {
+            return Err(no_cpp_toolkit());
+        }
+
+        Ok(rdf_triples::to_turtle(self))
+    }
+
+    /// Serializes this XMP object for embedding in a JPEG file, splitting
+    /// it into a standard and an extended packet if it is too large for a
+    /// single `APP1` segment.
+    ///
+    /// This follows the algorithm in part 3 of the XMP specification. If
+    /// the metadata doesn't fit, thumbnails (`xmp:Thumbnails`) are
+    /// dropped, then the Camera Raw settings, `photoshop:History`, and
+    /// other large top-level properties are moved to the extended packet
+    /// until the standard packet fits. The standard packet then records
+    /// the GUID of the extended packet in `xmpNote:HasExtendedXMP`.
+    ///
+    /// See [`JpegXmpPackets`] for how to write the result to `APP1`
+    /// segments.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::TooLargeForJpeg`] if the
+    /// standard packet is still too large after moving every eligible
+    /// property.
+    pub fn split_for_jpeg(&self) -> XmpResult<JpegXmpPackets> {
+        if let Some(m) = self.m {
+            let mut err = ffi::CXmpError::default();
+
+            unsafe {
+                let mut c_extended: *const c_char = std::ptr::null();
+                let mut c_digest: *const c_char = std::ptr::null();
+
+                let standard = CXmpString::from_ptr(ffi::CXmpMetaPackageForJPEG(
+                    m,
+                    &mut err,
+                    &mut c_extended,
+                    &mut c_digest,
+                ));
+                let extended = CXmpString::from_ptr(c_extended);
+                let guid = CXmpString::from_ptr(c_digest);
+
+                XmpError::raise_from_c(&err)?;
+
+                Ok(JpegXmpPackets {
+                    standard: standard.as_string(),
+                    extended: extended.map(|s| s),
+                    guid: guid.map(|s| s),
+                })
+            }
+        } else {
+            Err(no_cpp_toolkit())
+        }
+    }
+
+    /// Parses the standard and extended XMP packets read from a JPEG file
+    /// and combines them into a single XMP object.
+    ///
+    /// The properties of the extended packet replace those of the same
+    /// name in the standard packet, and `xmpNote:HasExtendedXMP` is
+    /// removed. If the extended packet was stored in several `APP1`
+    /// segments, use [`JpegXmpPackets::reassemble_extended`] to rebuild it
+    /// first. If `extended` is empty, this simply parses `main`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use xmp_toolkit::{xmp_ns, XmpMeta};
+    /// let mut meta = XmpMeta::new().unwrap();
+    /// meta.set_property(xmp_ns::DC, "format", &"image/jpeg".into())
+    ///     .unwrap();
+    ///
+    /// let packets = meta.split_for_jpeg().unwrap();
+    /// assert!(packets.extended.is_none());
+    ///
+    /// let joined = XmpMeta::join_extended(&packets.standard, "").unwrap();
+    /// assert!(joined.contains_property(xmp_ns::DC, "format"));
+    /// ```
+    pub fn join_extended(main: &str, extended: &str) -> XmpResult<XmpMeta> {
+        let full = XmpMeta::from_str(main)?;
+        if extended.is_empty() {
+            return Ok(full);
+        }
+
+        let extended = XmpMeta::from_str(extended)?;
+
+        if let (Some(full_m), Some(extended_m)) = (full.m, extended.m) {
+            let mut err = ffi::CXmpError::default();
+
+            unsafe {
+                ffi::CXmpMetaMergeFromJPEG(full_m, extended_m, &mut err);
+            }
+
+            XmpError::raise_from_c(&err)?;
+            Ok(full)
+        } else {
+            Err(no_cpp_toolkit())
+        }
+    }
+
+    /// Converts metadata in this XMP object into a string as RDF, with
+    /// namespaces and top-level properties arranged according to
+    /// `ordering`.
+    ///
+    /// Unlike [`XmpMeta::sort`], this does not modify `self` and lets the
+    /// caller decide on the order. Nested content (struct fields, array
+    /// items, and qualifiers) is written in its existing order.
+    ///
+    /// See [`OutputOrdering`] and [`NamespaceOrder`].
+    ///
+    /// [`NamespaceOrder`]: crate::NamespaceOrder
+    pub fn to_string_with_ordering(
+        &self,
+        options: ToStringOptions,
+        ordering: &impl OutputOrdering,
+    ) -> XmpResult<String> {
+        if self.m.is_none() {
+            return Err(no_cpp_toolkit());
+        }
+
+        let mut ordered = XmpMeta::new()?;
+        ordered.set_name(&self.name())?;
+
+        let mut schemas: Vec<String> = self
+            .iter(IterOptions::default().immediate_children_only())
+            .map(|schema| schema.schema_ns)
+            .collect();
+
+        schemas.sort_by(|a, b| ordering.compare_namespaces(a, b));
+
+        for ns in schemas {
+            let mut props: Vec<String> = self
+                .iter(
+                    IterOptions::default()
+                        .schema_ns(&ns)
+                        .immediate_children_only(),
+                )
+                .map(|prop| prop.name)
+                .collect();
+
+            let local_name = |name: &str| -> String {
+                match name.split_once(':') {
+                    Some((_, local)) => local.to_owned(),
+                    None => name.to_owned(),
+                }
+            };
+
+            props.sort_by(|a, b| ordering.compare_properties(&ns, &local_name(a), &local_name(b)));
+
+            for prop in props {
+                self.duplicate_subtree(&mut ordered, &ns, &prop, &prop)?;
+            }
+        }
+
+        ordered.to_string_with_options(options)
+    }
 }
 
 impl<'a> XmpMeta {
@@ -2053,17 +4055,15 @@ impl Clone for XmpMeta {
     /// underlying C++ XMP Toolkit operation, this function will
     /// fail silently and generate an empty XMP data model.
     fn clone(&self) -> Self {
-        if let Some(m) = self.m {
+        let mut result = if let Some(m) = self.m {
             let mut err = ffi::CXmpError::default();
-            let m = unsafe { ffi::CXmpMetaClone(m, &mut err) };
-            if m.is_null() {
-                Self { m: None }
-            } else {
-                Self { m: Some(m) }
-            }
+            Self::from_ptr(unsafe { ffi::CXmpMetaClone(m, &mut err) })
         } else {
-            Self { m: None }
-        }
+            Self::from_ptr(std::ptr::null_mut())
+        };
+
+        result.strict_lang_tags = self.strict_lang_tags;
+        result
     }
 }
 
@@ -2237,11 +4237,7 @@ impl Default for XmpMeta {
     fn default() -> Self {
         let mut err = ffi::CXmpError::default();
         let m = unsafe { ffi::CXmpMetaNew(&mut err) };
-        if m.is_null() {
-            XmpMeta { m: None }
-        } else {
-            XmpMeta { m: Some(m) }
-        }
+        XmpMeta::from_ptr(m)
     }
 }
 
@@ -2263,7 +4259,7 @@ impl FromStr for XmpMeta {
         };
         XmpError::raise_from_c(&err)?;
 
-        Ok(XmpMeta { m: Some(m) })
+        Ok(XmpMeta::from_ptr(m))
     }
 }
 
@@ -2276,6 +4272,149 @@ impl FromStr for XmpMeta {
 /// > single-writer locking model. All locking is automatic and transparent.
 unsafe impl Send for XmpMeta {}
 
+/// The C++ XMP Toolkit takes a read lock on the underlying `SXMPMeta`
+/// object for the duration of each read-only call and a write lock for each
+/// modifying call (see the note on `Send` above). Every function that
+/// modifies the data model takes `&mut self`, so `&XmpMeta` only permits
+/// calls that take a read lock.
+///
+/// The only state on the Rust side that can change through `&XmpMeta` is
+/// the optional access statistics, which are guarded by a mutex.
+unsafe impl Sync for XmpMeta {}
+
+/// An immutable, reference-counted view of an XMP data model that can be
+/// shared across threads.
+///
+/// Create via [`XmpMeta::into_snapshot`].
+/// Cloning a snapshot is cheap: all clones share the same underlying C++
+/// object.
+///
+/// A snapshot dereferences to [`XmpMeta`], so all read-only accessors are
+/// available. There is no way to modify a snapshot; call
+/// [`XmpMetaSnapshot::to_meta`] to get a mutable copy.
+///
+/// ## Example
+///
+/// ```
+/// # use std::thread;
+/// # use xmp_toolkit::{xmp_ns, XmpMeta};
+/// let mut meta = XmpMeta::new().unwrap();
+/// meta.set_property(xmp_ns::XMP, "CreatorTool", &"Example".into())
+///     .unwrap();
+///
+/// let snapshot = meta.into_snapshot();
+///
+/// let readers: Vec<_> = (0..4)
+///     .map(|_| {
+///         let snapshot = snapshot.clone();
+///         thread::spawn(move || snapshot.property(xmp_ns::XMP, "CreatorTool"))
+///     })
+///     .collect();
+///
+/// for reader in readers {
+///     assert_eq!(reader.join().unwrap().unwrap().value, "Example");
+/// }
+/// ```
+#[derive(Clone)]
+pub struct XmpMetaSnapshot {
+    meta: Arc<XmpMeta>,
+}
+
+impl XmpMetaSnapshot {
+    /// Returns a mutable deep copy of the data model.
+    pub fn to_meta(&self) -> XmpMeta {
+        XmpMeta::clone(&self.meta)
+    }
+}
+
+impl Deref for XmpMetaSnapshot {
+    type Target = XmpMeta;
+
+    fn deref(&self) -> &XmpMeta {
+        &self.meta
+    }
+}
+
+impl fmt::Debug for XmpMetaSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        fmt::Debug::fmt(&*self.meta, f)
+    }
+}
+
+impl fmt::Display for XmpMetaSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        fmt::Display::fmt(&*self.meta, f)
+    }
+}
+
+/// A read-only, reference-counted handle to an XMP data model that is
+/// shared with the [`XmpMeta`] it was created from.
+///
+/// Create via [`XmpMeta::read_handle`]. Cloning a handle is cheap: all
+/// clones share the same underlying C++ object, and no copy of the data
+/// model is ever made.
+///
+/// A handle dereferences to [`XmpMeta`], so all read-only accessors are
+/// available. Call [`XmpMetaReadHandle::to_meta`] to get a mutable copy.
+///
+/// ## Example
+///
+/// ```
+/// # use std::{str::FromStr, thread};
+/// # use xmp_toolkit::{xmp_ns, XmpMeta};
+/// let meta = XmpMeta::from_str(
+///     r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+///          <rdf:Description rdf:about="" xmlns:xmp="http://ns.adobe.com/xap/1.0/"
+///              xmp:CreatorTool="Example"/>
+///        </rdf:RDF>"#,
+/// )
+/// .unwrap();
+///
+/// let handle = meta.read_handle();
+///
+/// let readers: Vec<_> = (0..4)
+///     .map(|_| {
+///         let handle = handle.clone();
+///         thread::spawn(move || handle.property(xmp_ns::XMP, "CreatorTool"))
+///     })
+///     .collect();
+///
+/// for reader in readers {
+///     assert_eq!(reader.join().unwrap().unwrap().value, "Example");
+/// }
+/// ```
+#[derive(Clone)]
+pub struct XmpMetaReadHandle {
+    meta: Arc<XmpMeta>,
+}
+
+impl XmpMetaReadHandle {
+    /// Returns a mutable deep copy of the data model.
+    pub fn to_meta(&self) -> XmpMeta {
+        XmpMeta::clone(&self.meta)
+    }
+}
+
+impl Deref for XmpMetaReadHandle {
+    type Target = XmpMeta;
+
+    fn deref(&self) -> &XmpMeta {
+        &self.meta
+    }
+}
+
+impl fmt::Debug for XmpMetaReadHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        fmt::Debug::fmt(&*self.meta, f)
+    }
+}
+
+impl fmt::Display for XmpMetaReadHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        fmt::Display::fmt(&*self.meta, f)
+    }
+}
+
 /// An iterator that provides access to items within a property array.
 ///
 /// Create via [`XmpMeta::property_array`].
@@ -2361,6 +4500,7 @@ pub struct ToStringOptions {
     pub(crate) indent: String,
     pub(crate) base_indent: u32,
     pub(crate) options: u32,
+    pub(crate) canonical: bool,
 }
 
 impl ToStringOptions {
@@ -2376,6 +4516,53 @@ impl ToStringOptions {
 
     // NOTE: Not exposing API for non-UTF8 serializations for now.
 
+    /// Creates options for a byte-stable serialization, suitable for
+    /// computing a hash or signature of the metadata (for example, in
+    /// content authenticity workflows).
+    ///
+    /// Two data models with the same content serialize to the same bytes,
+    /// regardless of the order in which properties were added, the process
+    /// in which they are serialized, or the version of the XMP Toolkit:
+    ///
+    /// * Schemas, properties, struct fields, and qualifiers are sorted, as by
+    ///   [`XmpMeta::sort`]. The order of array items is preserved.
+    /// * Well-known namespaces are written with their customary prefix; all
+    ///   other namespaces are written as `ns1`, `ns2`, and so on, in order of
+    ///   namespace URI.
+    /// * The `x:xmptk` attribute has a fixed value that does not identify the
+    ///   XMP Toolkit version.
+    /// * There is no packet wrapper or padding. Lines end with `\n`.
+    ///
+    /// This is a preset: other options can not be combined with it.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use xmp_toolkit::{xmp_ns, ToStringOptions, XmpMeta};
+    /// let mut a = XmpMeta::new().unwrap();
+    /// a.set_property(xmp_ns::XMP, "Label", &"Red".into()).unwrap();
+    /// a.set_property(xmp_ns::DC, "format", &"image/jpeg".into())
+    ///     .unwrap();
+    ///
+    /// let mut b = XmpMeta::new().unwrap();
+    /// b.set_property(xmp_ns::DC, "format", &"image/jpeg".into())
+    ///     .unwrap();
+    /// b.set_property(xmp_ns::XMP, "Label", &"Red".into()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     a.to_string_with_options(ToStringOptions::canonical_for_hashing())
+    ///         .unwrap(),
+    ///     b.to_string_with_options(ToStringOptions::canonical_for_hashing())
+    ///         .unwrap()
+    /// );
+    /// ```
+    pub fn canonical_for_hashing() -> Self {
+        Self {
+            canonical: true,
+            ..Default::default()
+        }
+    }
+
     /// Set the amount of padding to be added if a writeable XML packet is
     /// created.
     ///
@@ -2505,3 +4692,30 @@ pub enum ItemPlacement {
     /// Replace the item currently at the specified index.
     ReplaceItemAtIndex(u32),
 }
+
+/// Describes the form of an array property.
+///
+/// Use with [`XmpMeta::set_array`] and [`XmpMeta::convert_array_form`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ArrayForm {
+    /// An unordered array (`rdf:Bag`).
+    Bag,
+
+    /// An ordered array (`rdf:Seq`).
+    Seq,
+
+    /// An array of alternatives (`rdf:Alt`).
+    Alt,
+}
+
+impl ArrayForm {
+    pub(crate) fn options(self) -> u32 {
+        match self {
+            Self::Bag => xmp_prop::VALUE_IS_ARRAY,
+            Self::Seq => xmp_prop::VALUE_IS_ARRAY | xmp_prop::ARRAY_IS_ORDERED,
+            Self::Alt => {
+                xmp_prop::VALUE_IS_ARRAY | xmp_prop::ARRAY_IS_ORDERED | xmp_prop::ARRAY_IS_ALTERNATE
+            }
+        }
+    }
+}
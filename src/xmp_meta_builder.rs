@@ -0,0 +1,109 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use crate::{xmp_ns, XmpDateTime, XmpMeta, XmpPropertyType, XmpResult, XmpValue};
+
+/// Constructs a new [`XmpMeta`] through a chain of method calls.
+///
+/// Create via [`XmpMeta::builder`]. Each method sets one property and
+/// returns the builder, so a document can be described in a single
+/// expression. Errors are deferred: the first operation that fails is
+/// reported by [`XmpMetaBuilder::build`], and later operations are skipped.
+///
+/// ## Example
+///
+/// ```
+/// # use xmp_toolkit::{xmp_ns, XmpDateTime, XmpMeta};
+/// let meta = XmpMeta::builder()
+///     .creator("Alice")
+///     .creator("Bob")
+///     .title("en-US", "Sunset")
+///     .create_date("2026-05-01T18:30:00Z".parse::<XmpDateTime>().unwrap())
+///     .property(xmp_ns::XMP, "Rating", 4)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(meta.array_len(xmp_ns::DC, "creator"), 2);
+/// assert_eq!(meta.get::<i32>(xmp_ns::XMP, "Rating").unwrap().value, 4);
+/// ```
+#[derive(Debug)]
+pub struct XmpMetaBuilder {
+    meta: XmpResult<XmpMeta>,
+}
+
+impl XmpMetaBuilder {
+    pub(crate) fn new() -> Self {
+        Self {
+            meta: XmpMeta::new(),
+        }
+    }
+
+    /// Appends a name to the ordered `dc:creator` array.
+    pub fn creator(self, name: &str) -> Self {
+        self.apply(|meta| {
+            meta.append_array_item(
+                xmp_ns::DC,
+                &XmpValue::from("creator")
+                    .set_is_array(true)
+                    .set_is_ordered(true),
+                &name.into(),
+            )
+        })
+    }
+
+    /// Sets the `dc:title` alternative for a language.
+    ///
+    /// The first title that is set also becomes the `x-default` title (see
+    /// [`XmpMeta::set_localized_text`]).
+    pub fn title(self, lang: &str, title: &str) -> Self {
+        self.apply(|meta| meta.set_localized_text(xmp_ns::DC, "title", None, lang, title))
+    }
+
+    /// Sets `xmp:CreateDate`.
+    pub fn create_date(self, date: XmpDateTime) -> Self {
+        self.apply(|meta| meta.set(xmp_ns::XMP, "CreateDate", date))
+    }
+
+    /// Sets a simple property to any value that implements
+    /// [`XmpPropertyType`].
+    ///
+    /// See [`XmpMeta::set`].
+    pub fn property<T: XmpPropertyType>(
+        self,
+        namespace: &str,
+        path: &str,
+        value: impl Into<XmpValue<T>>,
+    ) -> Self {
+        let value = value.into();
+        self.apply(|meta| meta.set::<T>(namespace, path, value))
+    }
+
+    /// Returns the constructed metadata, or the first error encountered
+    /// while building it.
+    pub fn build(self) -> XmpResult<XmpMeta> {
+        self.meta
+    }
+
+    fn apply<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut XmpMeta) -> XmpResult<()>,
+    {
+        if let Ok(meta) = &mut self.meta {
+            if let Err(err) = f(meta) {
+                self.meta = Err(err);
+            }
+        }
+
+        self
+    }
+}
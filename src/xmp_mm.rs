@@ -0,0 +1,235 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Contains typed access to the version history kept in the XMP Media
+//! Management (`xmpMM`) schema.
+//!
+//! `xmpMM:Versions` is an ordered array of `Version` structs, each of which
+//! may contain a nested `ResourceEvent` struct. This module reads and
+//! appends to that array without requiring callers to compose the nested
+//! struct paths themselves.
+
+use std::str::FromStr;
+
+use crate::{xmp_ns, XmpDateTime, XmpMeta, XmpResult, XmpValue};
+
+/// An `stEvt:ResourceEvent` value: a high-level event that occurred in the
+/// processing of a resource.
+///
+/// Each field corresponds to the `stEvt` field of the same name and is
+/// `None` if that field is absent.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ResourceEvent {
+    /// `stEvt:action`, such as `"saved"` or `"converted"`.
+    pub action: Option<String>,
+
+    /// `stEvt:changed`: a semicolon-separated list of the parts of the
+    /// resource that were changed, such as `"/metadata"`.
+    pub changed: Option<String>,
+
+    /// `stEvt:instanceID`: the value of `xmpMM:InstanceID` for the
+    /// modified resource.
+    pub instance_id: Option<String>,
+
+    /// `stEvt:parameters`: additional description of the action.
+    pub parameters: Option<String>,
+
+    /// `stEvt:softwareAgent`: the software that performed the action.
+    pub software_agent: Option<String>,
+
+    /// `stEvt:when`: the time at which the action occurred.
+    pub when: Option<XmpDateTime>,
+}
+
+/// An `stVer:Version` value: one entry in the version history of a
+/// resource.
+///
+/// Each field corresponds to the `stVer` field of the same name and is
+/// `None` if that field is absent.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Version {
+    /// `stVer:comments`.
+    pub comments: Option<String>,
+
+    /// `stVer:event`: the event that created this version.
+    pub event: Option<ResourceEvent>,
+
+    /// `stVer:modifier`: the person who created this version.
+    pub modifier: Option<String>,
+
+    /// `stVer:modifyDate`: the time at which this version was saved.
+    pub modify_date: Option<XmpDateTime>,
+
+    /// `stVer:version`: the version number.
+    pub version: Option<String>,
+}
+
+/// Reads the `xmpMM:Versions` property, oldest version first.
+///
+/// Fields that can not be interpreted as the expected type are treated as
+/// absent. Returns an empty `Vec` if the property is absent.
+pub fn versions(meta: &XmpMeta) -> Vec<Version> {
+    (1..=meta.array_len(xmp_ns::XMP_MM, "Versions") as i32)
+        .filter_map(|index| {
+            XmpMeta::compose_array_item_path(xmp_ns::XMP_MM, "Versions", index).ok()
+        })
+        .map(|path| read_version(meta, &path))
+        .collect()
+}
+
+/// Appends a version to the `xmpMM:Versions` property, creating the
+/// property if necessary.
+///
+/// Fields with value `None` are omitted.
+///
+/// The update is atomic: If an error occurs, `meta` is left unchanged.
+pub fn append_version(meta: &mut XmpMeta, version: &Version) -> XmpResult<()> {
+    let mut staged = meta.stage();
+
+    staged.append_array_item(
+        xmp_ns::XMP_MM,
+        &XmpValue::from("Versions")
+            .set_is_array(true)
+            .set_is_ordered(true),
+        &XmpValue::default().set_is_struct(true),
+    )?;
+
+    let path = XmpMeta::compose_array_item_path(xmp_ns::XMP_MM, "Versions", XmpMeta::LAST_ITEM)?;
+
+    set_field(
+        &mut staged,
+        &path,
+        xmp_ns::ST_VERSION,
+        "comments",
+        &version.comments,
+    )?;
+    set_field(
+        &mut staged,
+        &path,
+        xmp_ns::ST_VERSION,
+        "modifier",
+        &version.modifier,
+    )?;
+    set_field(
+        &mut staged,
+        &path,
+        xmp_ns::ST_VERSION,
+        "modifyDate",
+        &version.modify_date.as_ref().map(|d| d.to_string()),
+    )?;
+    set_field(
+        &mut staged,
+        &path,
+        xmp_ns::ST_VERSION,
+        "version",
+        &version.version,
+    )?;
+
+    if let Some(event) = &version.event {
+        let event_path =
+            XmpMeta::compose_struct_field_path(xmp_ns::XMP_MM, &path, xmp_ns::ST_VERSION, "event")?;
+
+        staged.set_property(
+            xmp_ns::XMP_MM,
+            &event_path,
+            &XmpValue::default().set_is_struct(true),
+        )?;
+
+        let ns = xmp_ns::RESOURCE_EVENT;
+        set_field(&mut staged, &event_path, ns, "action", &event.action)?;
+        set_field(&mut staged, &event_path, ns, "changed", &event.changed)?;
+        set_field(
+            &mut staged,
+            &event_path,
+            ns,
+            "instanceID",
+            &event.instance_id,
+        )?;
+        set_field(
+            &mut staged,
+            &event_path,
+            ns,
+            "parameters",
+            &event.parameters,
+        )?;
+        set_field(
+            &mut staged,
+            &event_path,
+            ns,
+            "softwareAgent",
+            &event.software_agent,
+        )?;
+        set_field(
+            &mut staged,
+            &event_path,
+            ns,
+            "when",
+            &event.when.as_ref().map(|d| d.to_string()),
+        )?;
+    }
+
+    meta.commit_staged(staged);
+    Ok(())
+}
+
+fn read_version(meta: &XmpMeta, path: &str) -> Version {
+    let event =
+        XmpMeta::compose_struct_field_path(xmp_ns::XMP_MM, path, xmp_ns::ST_VERSION, "event")
+            .ok()
+            .filter(|event_path| meta.contains_property(xmp_ns::XMP_MM, event_path))
+            .map(|event_path| {
+                let ns = xmp_ns::RESOURCE_EVENT;
+                ResourceEvent {
+                    action: field(meta, &event_path, ns, "action"),
+                    changed: field(meta, &event_path, ns, "changed"),
+                    instance_id: field(meta, &event_path, ns, "instanceID"),
+                    parameters: field(meta, &event_path, ns, "parameters"),
+                    software_agent: field(meta, &event_path, ns, "softwareAgent"),
+                    when: field(meta, &event_path, ns, "when")
+                        .and_then(|s| XmpDateTime::from_str(&s).ok()),
+                }
+            });
+
+    Version {
+        comments: field(meta, path, xmp_ns::ST_VERSION, "comments"),
+        event,
+        modifier: field(meta, path, xmp_ns::ST_VERSION, "modifier"),
+        modify_date: field(meta, path, xmp_ns::ST_VERSION, "modifyDate")
+            .and_then(|s| XmpDateTime::from_str(&s).ok()),
+        version: field(meta, path, xmp_ns::ST_VERSION, "version"),
+    }
+}
+
+fn field(meta: &XmpMeta, struct_path: &str, field_ns: &str, field_name: &str) -> Option<String> {
+    meta.struct_field(xmp_ns::XMP_MM, struct_path, field_ns, field_name)
+        .map(|v| v.value)
+}
+
+fn set_field(
+    meta: &mut XmpMeta,
+    struct_path: &str,
+    field_ns: &str,
+    field_name: &str,
+    value: &Option<String>,
+) -> XmpResult<()> {
+    match value {
+        Some(value) => meta.set_struct_field(
+            xmp_ns::XMP_MM,
+            struct_path,
+            field_ns,
+            field_name,
+            &value.as_str().into(),
+        ),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,27 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+// Supports turning off ID3 reconciliation for MP3 files.
+//
+// The C++ XMP Toolkit's MP3 handler reconciles ID3 frames (title, artist,
+// album, and so on) with the XMP. When that is turned off with
+// `OpenFileOptions::reconcile_id3(false)`, the handler is still used, but
+// the file is opened with `kXMPFiles_OpenOnlyXMP`.
+
+const ID3: &[u8; 3] = b"ID3";
+
+/// Returns `true` if `header`, the first bytes of a file, identifies the
+/// file as an MP3 file.
+pub(crate) fn is_mp3(header: &[u8]) -> bool {
+    header.starts_with(ID3) || (header.len() >= 2 && header[0] == 0xff && header[1] & 0xe0 == 0xe0)
+}
@@ -0,0 +1,343 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+// Supports turning off QuickTime reconciliation for MPEG-4 and QuickTime
+// files.
+//
+// The C++ XMP Toolkit's MPEG-4 handler reconciles the QuickTime `udta`
+// and `keys` metadata with the XMP. When that is turned off with
+// `OpenFileOptions::reconcile_quicktime(false)`, the handler is still used,
+// but the file is opened with `kXMPFiles_OpenOnlyXMP`, which also keeps
+// the handler from deriving `xmpDM:startTimecode`.
+//
+// Unless turned off with `OpenFileOptions::quicktime_timecode(false)`, the
+// start of the first timecode (`tmcd`) track is then read here instead.
+// Only the box headers and the `moov` box are read, so this is cheap even
+// for very large files.
+
+use std::io::{self, Read, Seek, SeekFrom};
+
+use crate::{
+    xmp_bmff::{boxes, child_boxes, BmffBox},
+    xmp_dm::{Timecode, TimecodeFormat},
+    xmp_file_handler::bad_file,
+    XmpError, XmpErrorType, XmpResult,
+};
+
+const FTYP: &[u8; 4] = b"ftyp";
+const MOOV: &[u8; 4] = b"moov";
+const TRAK: &[u8; 4] = b"trak";
+const MDIA: &[u8; 4] = b"mdia";
+const HDLR: &[u8; 4] = b"hdlr";
+const MINF: &[u8; 4] = b"minf";
+const STBL: &[u8; 4] = b"stbl";
+const STSD: &[u8; 4] = b"stsd";
+const STCO: &[u8; 4] = b"stco";
+const CO64: &[u8; 4] = b"co64";
+const TMCD: &[u8; 4] = b"tmcd";
+
+const QUICKTIME_BRAND: &[u8; 4] = b"qt  ";
+
+/// Major brands of MPEG-4 files other than QuickTime movies. Files with
+/// other brands (HEIF, CR3, and so on) are left to other handlers.
+const MPEG4_BRANDS: &[&[u8; 4]] = &[
+    b"isom", b"iso2", b"iso3", b"iso4", b"iso5", b"iso6", b"mp41", b"mp42", b"avc1", b"M4A ",
+    b"M4B ", b"M4P ", b"M4V ", b"f4v ", b"dash", b"XAVC", b"MSNV",
+];
+
+/// Top-level boxes that may start a QuickTime movie without a file type
+/// box.
+const QUICKTIME_FIRST_BOXES: &[&[u8; 4]] = &[b"moov", b"mdat", b"free", b"skip", b"wide", b"pnot"];
+
+/// The largest `moov` box that is read to find the timecode track.
+const MAX_MOOV_LEN: u64 = 256 << 20;
+
+// Flags of a timecode sample description.
+const DROP_FRAME_FLAG: u32 = 0x01;
+const COUNTER_FLAG: u32 = 0x08;
+
+/// Returns `true` if `header`, the first bytes of a file, identifies the
+/// file as an MPEG-4 file or a QuickTime movie.
+pub(crate) fn is_mpeg4(header: &[u8]) -> bool {
+    let Some(box_type) = header.get(4..8) else {
+        return false;
+    };
+
+    if box_type != FTYP {
+        return QUICKTIME_FIRST_BOXES.iter().any(|b| b == &box_type);
+    }
+
+    match header.get(8..12) {
+        Some(brand) => {
+            brand == QUICKTIME_BRAND
+                || MPEG4_BRANDS.iter().any(|b| b == &brand)
+                || brand.starts_with(b"3gp")
+                || brand.starts_with(b"3g2")
+        }
+        None => false,
+    }
+}
+
+/// Returns the timecode of the first sample of the first timecode track.
+pub(crate) fn start_timecode<R: Read + Seek>(file: &mut R) -> XmpResult<Option<Timecode>> {
+    let Some(moov) = read_moov(file)? else {
+        return Ok(None);
+    };
+
+    for trak in boxes(&moov, 0, "MPEG-4")?
+        .iter()
+        .filter(|b| &b.box_type == TRAK)
+    {
+        let Some(mdia) = child(&moov, trak, MDIA)? else {
+            continue;
+        };
+
+        // The handler type follows the version, flags, and a predefined
+        // field.
+        let is_timecode = child(&moov, &mdia, HDLR)?
+            .and_then(|hdlr| moov.get(hdlr.data.start + 8..hdlr.data.start + 12))
+            .is_some_and(|handler_type| handler_type == TMCD);
+        if !is_timecode {
+            continue;
+        }
+
+        let Some(stbl) = child(&moov, &mdia, MINF)?
+            .map(|minf| child(&moov, &minf, STBL))
+            .transpose()?
+            .flatten()
+        else {
+            continue;
+        };
+
+        return timecode_track_start(file, &moov, &stbl);
+    }
+
+    Ok(None)
+}
+
+/// Returns the payload of the first top-level `moov` box, if any.
+fn read_moov<R: Read + Seek>(file: &mut R) -> XmpResult<Option<Vec<u8>>> {
+    let mut pos = 0;
+
+    loop {
+        file.seek(SeekFrom::Start(pos)).map_err(read_error)?;
+
+        let mut header = [0; 8];
+        match file.read_exact(&mut header) {
+            Ok(()) => (),
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(read_error(err)),
+        }
+
+        let size = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as u64;
+
+        let (header_len, size) = match size {
+            // The box extends to the end of the file.
+            0 => (8, None),
+
+            // A 64-bit size follows the type.
+            1 => {
+                let mut large = [0; 8];
+                file.read_exact(&mut large).map_err(read_error)?;
+                (16, Some(u64::from_be_bytes(large)))
+            }
+
+            size => (8, Some(size)),
+        };
+
+        if size.is_some_and(|size| size < header_len) {
+            return Err(bad_mpeg4("Invalid box size"));
+        }
+
+        if &header[4..8] != MOOV {
+            match size {
+                Some(size) => pos += size,
+                None => return Ok(None),
+            }
+            continue;
+        }
+
+        let len = size.map_or(MAX_MOOV_LEN, |size| size - header_len);
+        if len > MAX_MOOV_LEN {
+            return Err(bad_mpeg4("moov box is too large"));
+        }
+
+        let mut moov = Vec::new();
+        file.take(len).read_to_end(&mut moov).map_err(read_error)?;
+
+        if size.is_some() && (moov.len() as u64) < len {
+            return Err(bad_mpeg4("Box extends past the end of the file"));
+        }
+
+        return Ok(Some(moov));
+    }
+}
+
+/// Returns the first box of type `box_type` in `parent`.
+fn child(moov: &[u8], parent: &BmffBox, box_type: &[u8; 4]) -> XmpResult<Option<BmffBox>> {
+    Ok(child_boxes(moov, parent, "MPEG-4")?
+        .into_iter()
+        .find(|b| &b.box_type == box_type))
+}
+
+/// Returns the timecode of the first sample of the timecode track with
+/// sample table `stbl`.
+fn timecode_track_start<R: Read + Seek>(
+    file: &mut R,
+    moov: &[u8],
+    stbl: &BmffBox,
+) -> XmpResult<Option<Timecode>> {
+    let boxes = child_boxes(moov, stbl, "MPEG-4")?;
+    let find = |box_type: &[u8; 4]| {
+        boxes
+            .iter()
+            .find(|b| &b.box_type == box_type)
+            .map(|b| &moov[b.data.clone()])
+    };
+
+    // The first sample description follows the version, flags, and entry
+    // count. After its size, type, reserved bytes, and data reference
+    // index come the reserved bytes, flags, time scale, frame duration,
+    // and number of frames of the timecode description.
+    let Some(stsd) = find(STSD) else {
+        return Ok(None);
+    };
+
+    let Some(entry) = stsd.get(8..8 + 34).filter(|e| &e[4..8] == TMCD) else {
+        return Ok(None);
+    };
+
+    let flags = read_u32(&entry[20..24]);
+    let time_scale = read_u32(&entry[24..28]);
+    let frame_duration = read_u32(&entry[28..32]);
+    let frames_per_second = entry[32];
+
+    if flags & COUNTER_FLAG != 0 {
+        return Ok(None);
+    }
+
+    let Some(format) = timecode_format(
+        time_scale,
+        frame_duration,
+        frames_per_second,
+        flags & DROP_FRAME_FLAG != 0,
+    ) else {
+        return Ok(None);
+    };
+
+    // The chunk offset table starts after the version, flags, and entry
+    // count.
+    let sample_offset = if let Some(stco) = find(STCO) {
+        stco.get(8..12).map(|o| read_u32(o) as u64)
+    } else {
+        find(CO64)
+            .and_then(|co64| co64.get(8..16))
+            .map(|o| u64::from_be_bytes([o[0], o[1], o[2], o[3], o[4], o[5], o[6], o[7]]))
+    };
+
+    let Some(sample_offset) = sample_offset else {
+        return Ok(None);
+    };
+
+    let mut sample = [0; 4];
+    if file
+        .seek(SeekFrom::Start(sample_offset))
+        .and_then(|_| file.read_exact(&mut sample))
+        .is_err()
+    {
+        return Ok(None);
+    }
+
+    let time_value = frames_to_time_value(read_u32(&sample), u32::from(frames_per_second), format);
+    Ok(Timecode::new(&time_value, format).ok())
+}
+
+/// Returns the timecode format for a timecode track, if the XMP Dynamic
+/// Media schema has one.
+fn timecode_format(
+    time_scale: u32,
+    frame_duration: u32,
+    frames_per_second: u8,
+    drop_frame: bool,
+) -> Option<TimecodeFormat> {
+    if frame_duration == 0 {
+        return None;
+    }
+
+    // NTSC rates are 1000/1001 of the nominal frame count.
+    let is_ntsc = u64::from(time_scale) * 1001
+        == u64::from(frame_duration) * 1000 * u64::from(frames_per_second);
+
+    Some(match (frames_per_second, is_ntsc, drop_frame) {
+        (24, true, false) => TimecodeFormat::Fps23976,
+        (24, false, false) => TimecodeFormat::Fps24,
+        (25, _, false) => TimecodeFormat::Fps25,
+        (30, _, true) => TimecodeFormat::Fps2997Drop,
+        (30, true, false) => TimecodeFormat::Fps2997NonDrop,
+        (30, false, false) => TimecodeFormat::Fps30,
+        (50, _, false) => TimecodeFormat::Fps50,
+        (60, _, true) => TimecodeFormat::Fps5994Drop,
+        (60, true, false) => TimecodeFormat::Fps5994NonDrop,
+        (60, false, false) => TimecodeFormat::Fps60,
+        _ => return None,
+    })
+}
+
+/// Converts a frame number to a time value in the form `hh:mm:ss:ff`, or
+/// `hh;mm;ss;ff` for drop-frame formats.
+fn frames_to_time_value(frame: u32, frames_per_second: u32, format: TimecodeFormat) -> String {
+    let mut frame = u64::from(frame);
+    let fps = u64::from(frames_per_second);
+
+    let separator = if format.is_drop_frame() {
+        // Frame numbers 0 and 1 (0 to 3 at 59.94 fps) are skipped at the
+        // start of each minute, except every tenth minute.
+        let dropped = fps / 15;
+        let per_ten_minutes = fps * 600 - dropped * 9;
+        let per_minute = fps * 60 - dropped;
+
+        let tens = frame / per_ten_minutes;
+        let rest = frame % per_ten_minutes;
+
+        frame += dropped * 9 * tens;
+        if rest > dropped {
+            frame += dropped * ((rest - dropped) / per_minute);
+        }
+
+        ';'
+    } else {
+        ':'
+    };
+
+    let hours = frame / (fps * 3600) % 24;
+    let minutes = frame / (fps * 60) % 60;
+    let seconds = frame / fps % 60;
+    let frames = frame % fps;
+
+    format!("{hours:02}{separator}{minutes:02}{separator}{seconds:02}{separator}{frames:02}")
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn bad_mpeg4(message: &str) -> XmpError {
+    bad_file("MPEG-4", message)
+}
+
+fn read_error(err: io::Error) -> XmpError {
+    XmpError {
+        error_type: XmpErrorType::ReadError,
+        debug_message: err.to_string(),
+    }
+}
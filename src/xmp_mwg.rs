@@ -0,0 +1,386 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Contains the reconciliation rules from the Metadata Working Group's
+//! [Guidelines for Handling Image Metadata](https://web.archive.org/web/20180919181934/http://www.metadataworkinggroup.org/pdf/mwg_guidance.pdf).
+//!
+//! Many image formats can carry the same information in Exif, IPTC-IIM,
+//! and XMP. When these disagree, the MWG guidance describes which value a
+//! consumer should use. [`XmpFile`] applies these rules internally when it
+//! reads a file, but tools that read the native metadata themselves can
+//! use this module to reconcile it with XMP.
+//!
+//! The rules applied are:
+//!
+//! * **Exif** values are preferred over XMP whenever they are present.
+//! * **IPTC-IIM** values are preferred over XMP only if the IIM block has been
+//!   changed since the XMP was written. This is detected by comparing
+//!   [`NativeMetadata::iptc_digest`] to the `photoshop:LegacyIPTCDigest`
+//!   property. If either digest is unknown, XMP is preferred and IIM is used
+//!   only when the XMP value is absent.
+//!
+//! [`XmpFile`]: crate::XmpFile
+
+use std::collections::HashMap;
+
+use crate::{xmp_lang, xmp_ns, XmpDate, XmpDateTime, XmpMeta, XmpTime, XmpTimeZone};
+
+/// Exif and IPTC-IIM values read from an asset, keyed by tag name.
+///
+/// The following keys are used:
+///
+/// | Map | Key | Example value |
+/// |-----|-----|---------------|
+/// | `exif` | `DateTimeOriginal` | `2026:03:01 10:15:30` |
+/// | `exif` | `SubSecTimeOriginal` | `25` |
+/// | `exif` | `OffsetTimeOriginal` | `+01:00` |
+/// | `exif` | `ImageDescription` | `Sunset over the bay` |
+/// | `exif` | `Artist` | `Alice; Bob` |
+/// | `exif` | `Orientation` | `6` |
+/// | `iptc` | `DateCreated` | `20260301` |
+/// | `iptc` | `TimeCreated` | `101530+0100` |
+/// | `iptc` | `Caption-Abstract` | `Sunset over the bay` |
+/// | `iptc` | `By-line` | `Alice` |
+/// | `iptc` | `Keywords` | `sunset` |
+///
+/// IPTC-IIM datasets may be repeated, so each `iptc` key maps to a list
+/// of values. Other keys are ignored.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct NativeMetadata {
+    /// Exif tag values, formatted as described above.
+    pub exif: HashMap<String, String>,
+
+    /// IPTC-IIM dataset values, formatted as described above.
+    pub iptc: HashMap<String, Vec<String>>,
+
+    /// The MD5 digest of the IPTC-IIM block, as a hexadecimal string.
+    ///
+    /// This is compared with the `photoshop:LegacyIPTCDigest` property to
+    /// decide whether the IIM block was changed after the XMP was written.
+    pub iptc_digest: Option<String>,
+}
+
+/// The values of the MWG properties after reconciling Exif, IPTC-IIM, and
+/// XMP.
+///
+/// Create via [`reconcile`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct Reconciled {
+    /// The date the image was created. See [`date`].
+    pub date: Option<XmpDateTime>,
+
+    /// A description of the image. See [`description`].
+    pub description: Option<String>,
+
+    /// Keywords describing the image. See [`keywords`].
+    pub keywords: Vec<String>,
+
+    /// The people who created the image. See [`creator`].
+    pub creator: Vec<String>,
+
+    /// The Exif/TIFF orientation code. See [`orientation`].
+    pub orientation: Option<u8>,
+}
+
+/// Reconciles all of the properties supported by this module.
+///
+/// ## Example
+///
+/// ```
+/// # use xmp_toolkit::{xmp_mwg::{self, NativeMetadata}, xmp_ns, XmpMeta};
+/// let mut meta = XmpMeta::new().unwrap();
+/// meta.set_property(xmp_ns::TIFF, "Orientation", &"1".into())
+///     .unwrap();
+///
+/// let mut native = NativeMetadata::default();
+/// native.exif.insert("Orientation".to_owned(), "6".to_owned());
+///
+/// let reconciled = xmp_mwg::reconcile(&meta, &native);
+/// assert_eq!(reconciled.orientation, Some(6));
+/// ```
+pub fn reconcile(meta: &XmpMeta, native: &NativeMetadata) -> Reconciled {
+    Reconciled {
+        date: date(meta, native),
+        description: description(meta, native),
+        keywords: keywords(meta, native),
+        creator: creator(meta, native),
+        orientation: orientation(meta, native),
+    }
+}
+
+/// Reconciles the MWG `CreateDate` property.
+///
+/// The sources are Exif `DateTimeOriginal` (with `SubSecTimeOriginal` and
+/// `OffsetTimeOriginal`), IPTC-IIM `DateCreated` and `TimeCreated`, and
+/// XMP `photoshop:DateCreated` (or `exif:DateTimeOriginal` if that is
+/// absent).
+///
+/// Exif dates often lack a time zone. If the XMP date is the same instant
+/// to the second, it is returned instead so that its time zone and
+/// fractional seconds are preserved.
+pub fn date(meta: &XmpMeta, native: &NativeMetadata) -> Option<XmpDateTime> {
+    let xmp = meta
+        .property_date(xmp_ns::PHOTOSHOP, "DateCreated")
+        .or_else(|| meta.property_date(xmp_ns::EXIF, "DateTimeOriginal"))
+        .map(|v| v.value);
+
+    if let Some(exif) = exif_date(native) {
+        return match xmp {
+            Some(xmp) if same_second(&exif, &xmp) => Some(xmp),
+            _ => Some(exif),
+        };
+    }
+
+    prefer_xmp_or_iptc(meta, native, xmp, iptc_date(native))
+}
+
+/// Reconciles the MWG `Description` property.
+///
+/// The sources are Exif `ImageDescription`, IPTC-IIM `Caption-Abstract`,
+/// and the `x-default` item of XMP `dc:description`. Empty or
+/// whitespace-only values are treated as absent.
+pub fn description(meta: &XmpMeta, native: &NativeMetadata) -> Option<String> {
+    if let Some(exif) = exif_text(native, "ImageDescription") {
+        return Some(exif.to_owned());
+    }
+
+    let xmp = meta
+        .localized_text(xmp_ns::DC, "description", None, xmp_lang::X_DEFAULT)
+        .map(|(v, _)| v.value)
+        .filter(|v| !v.trim().is_empty());
+
+    let iptc = iptc_values(native, "Caption-Abstract").into_iter().next();
+
+    prefer_xmp_or_iptc(meta, native, xmp, iptc)
+}
+
+/// Reconciles the MWG `Keywords` property.
+///
+/// The sources are IPTC-IIM `Keywords` and XMP `dc:subject`. There is no
+/// Exif equivalent. Returns an empty `Vec` if neither is present.
+pub fn keywords(meta: &XmpMeta, native: &NativeMetadata) -> Vec<String> {
+    let xmp = xmp_array(meta, xmp_ns::DC, "subject");
+    let iptc = Some(iptc_values(native, "Keywords")).filter(|v| !v.is_empty());
+
+    prefer_xmp_or_iptc(meta, native, xmp, iptc).unwrap_or_default()
+}
+
+/// Reconciles the MWG `Creator` property.
+///
+/// The sources are Exif `Artist`, IPTC-IIM `By-line`, and XMP
+/// `dc:creator`. As recommended by the MWG, the Exif value is split into
+/// separate names at each semicolon. Returns an empty `Vec` if none of
+/// these is present.
+pub fn creator(meta: &XmpMeta, native: &NativeMetadata) -> Vec<String> {
+    if let Some(exif) = exif_text(native, "Artist") {
+        return exif
+            .split(';')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_owned)
+            .collect();
+    }
+
+    let xmp = xmp_array(meta, xmp_ns::DC, "creator");
+    let iptc = Some(iptc_values(native, "By-line")).filter(|v| !v.is_empty());
+
+    prefer_xmp_or_iptc(meta, native, xmp, iptc).unwrap_or_default()
+}
+
+/// Reconciles the MWG `Orientation` property.
+///
+/// The sources are Exif `Orientation` and XMP `tiff:Orientation`. Values
+/// outside the range 1..=8 defined by the Exif specification are treated
+/// as absent.
+pub fn orientation(meta: &XmpMeta, native: &NativeMetadata) -> Option<u8> {
+    let valid = |v: &u8| (1..=8).contains(v);
+
+    native
+        .exif
+        .get("Orientation")
+        .and_then(|v| v.trim().parse().ok())
+        .filter(valid)
+        .or_else(|| {
+            meta.property_i32(xmp_ns::TIFF, "Orientation")
+                .and_then(|v| u8::try_from(v.value).ok())
+                .filter(valid)
+        })
+}
+
+/// Chooses between an XMP value and the corresponding IPTC-IIM value
+/// according to the IPTC digest.
+fn prefer_xmp_or_iptc<T>(
+    meta: &XmpMeta,
+    native: &NativeMetadata,
+    xmp: Option<T>,
+    iptc: Option<T>,
+) -> Option<T> {
+    if iptc_changed(meta, native) {
+        iptc.or(xmp)
+    } else {
+        xmp.or(iptc)
+    }
+}
+
+/// Returns `true` if both digests are known and they differ.
+fn iptc_changed(meta: &XmpMeta, native: &NativeMetadata) -> bool {
+    let Some(digest) = &native.iptc_digest else {
+        return false;
+    };
+
+    meta.property(xmp_ns::PHOTOSHOP, "LegacyIPTCDigest")
+        .is_some_and(|xmp| !xmp.value.eq_ignore_ascii_case(digest))
+}
+
+fn exif_text<'a>(native: &'a NativeMetadata, key: &str) -> Option<&'a str> {
+    native
+        .exif
+        .get(key)
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+}
+
+fn iptc_values(native: &NativeMetadata, key: &str) -> Vec<String> {
+    native
+        .iptc
+        .get(key)
+        .into_iter()
+        .flatten()
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+fn xmp_array(meta: &XmpMeta, ns: &str, name: &str) -> Option<Vec<String>> {
+    let items: Vec<String> = meta.property_array(ns, name).map(|v| v.value).collect();
+    Some(items).filter(|items| !items.is_empty())
+}
+
+fn same_second(a: &XmpDateTime, b: &XmpDateTime) -> bool {
+    let (Some(a_time), Some(b_time)) = (&a.time, &b.time) else {
+        return false;
+    };
+
+    a.date == b.date
+        && a_time.hour == b_time.hour
+        && a_time.minute == b_time.minute
+        && a_time.second == b_time.second
+}
+
+/// Parses Exif `DateTimeOriginal` (`YYYY:MM:DD HH:MM:SS`) and its
+/// companion tags.
+fn exif_date(native: &NativeMetadata) -> Option<XmpDateTime> {
+    let value = native.exif.get("DateTimeOriginal")?.trim();
+    let (date, time) = value.split_once(' ')?;
+
+    let mut date_parts = date.split(':');
+    let date = XmpDate {
+        year: number(date_parts.next()?, 4)?,
+        month: number(date_parts.next()?, 2)?,
+        day: number(date_parts.next()?, 2)?,
+    };
+
+    let mut time_parts = time.split(':');
+    let mut time = XmpTime {
+        hour: number(time_parts.next()?, 2)?,
+        minute: number(time_parts.next()?, 2)?,
+        second: number(time_parts.next()?, 2)?,
+        nanosecond: 0,
+        time_zone: None,
+    };
+
+    // Exif writers that don't know the date fill the field with zeros or
+    // spaces.
+    if date.year == 0 || date.month == 0 || date.day == 0 {
+        return None;
+    }
+
+    if let Some(sub_sec) = native.exif.get("SubSecTimeOriginal") {
+        let digits: String = sub_sec.trim().chars().take(9).collect();
+        if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+            time.nanosecond = format!("{digits:0<9}").parse().ok()?;
+        }
+    }
+
+    if let Some(offset) = native.exif.get("OffsetTimeOriginal") {
+        time.time_zone = time_zone(&offset.trim().replace(':', ""));
+    }
+
+    Some(XmpDateTime {
+        date: Some(date),
+        time: Some(time),
+    })
+}
+
+/// Parses IPTC-IIM `DateCreated` (`CCYYMMDD`) and `TimeCreated`
+/// (`HHMMSS±HHMM`).
+fn iptc_date(native: &NativeMetadata) -> Option<XmpDateTime> {
+    let date = iptc_values(native, "DateCreated").into_iter().next()?;
+    if date.len() != 8 {
+        return None;
+    }
+
+    let date = XmpDate {
+        year: number(date.get(0..4)?, 4)?,
+        month: number(date.get(4..6)?, 2)?,
+        day: number(date.get(6..8)?, 2)?,
+    };
+
+    let time = iptc_values(native, "TimeCreated")
+        .into_iter()
+        .next()
+        .and_then(|time| {
+            Some(XmpTime {
+                hour: number(time.get(0..2)?, 2)?,
+                minute: number(time.get(2..4)?, 2)?,
+                second: number(time.get(4..6)?, 2)?,
+                nanosecond: 0,
+                time_zone: time_zone(time.get(6..)?),
+            })
+        });
+
+    Some(XmpDateTime {
+        date: Some(date),
+        time,
+    })
+}
+
+/// Parses a time zone offset of the form `±HHMM`.
+fn time_zone(offset: &str) -> Option<XmpTimeZone> {
+    let sign = match offset.get(0..1)? {
+        "+" => 1,
+        "-" => -1,
+        _ => return None,
+    };
+
+    let hour = number(offset.get(1..3)?, 2)?;
+    let minute = number(offset.get(3..5)?, 2)?;
+    if offset.len() != 5 {
+        return None;
+    }
+
+    Some(XmpTimeZone {
+        hour: sign * hour,
+        minute,
+    })
+}
+
+fn number(s: &str, len: usize) -> Option<i32> {
+    if s.len() == len && s.bytes().all(|b| b.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+// Reads and updates the XMP of standalone MXF files.
+//
+// The C++ XMP Toolkit only handles MXF files that are part of a
+// folder-based format such as P2 or XDCAM, whose XMP is kept in the clip's
+// other files; those are left to it. Other MXF files carry XMP, if at all,
+// as text in the file (see SMPTE RP 2057), which is found by packet
+// scanning.
+//
+// Rewriting the KLV structure of an MXF file (partitions, index tables,
+// and the header metadata's strong references) to add or grow a packet is
+// out of scope, so an existing packet is only ever overwritten in place.
+// Use `OpenFileOptions::sidecar_fallback` to write a sidecar for files
+// without room for the XMP.
+
+use std::{ffi::CString, ops::Range, path::Path};
+
+use crate::{
+    ffi, xmp_file_handler::FileHandler, xmp_packet, FileFormat, XmpError, XmpErrorType, XmpResult,
+};
+
+// The start of the key of a header partition pack. The following bytes
+// give the partition's status and a reserved byte.
+const HEADER_PARTITION_KEY: &[u8] = &[
+    0x06, 0x0e, 0x2b, 0x34, 0x02, 0x05, 0x01, 0x01, 0x0d, 0x01, 0x02, 0x01, 0x01, 0x02,
+];
+
+pub(crate) struct MxfHandler;
+
+impl FileHandler for MxfHandler {
+    fn format(&self) -> FileFormat {
+        FileFormat::Mxf
+    }
+
+    fn accepts_path(&self, path: &Path) -> bool {
+        !is_folder_based_clip(path)
+    }
+
+    fn check_format(&self, header: &[u8]) -> bool {
+        // The key may follow a short run-in.
+        header
+            .windows(HEADER_PARTITION_KEY.len())
+            .any(|window| window == HEADER_PARTITION_KEY)
+    }
+
+    fn read_xmp(&self, file: &[u8]) -> XmpResult<Option<Range<usize>>> {
+        Ok(xmp_packet::scan(file)
+            .into_iter()
+            .next()
+            .map(|packet| packet.range))
+    }
+
+    fn updates_in_place_only(&self) -> bool {
+        true
+    }
+
+    fn write_xmp(&self, _file: &[u8], _packet: &[u8]) -> XmpResult<Vec<u8>> {
+        Err(XmpError {
+            error_type: XmpErrorType::InternalFailure,
+            debug_message: "MXF packets can only be updated in place".to_owned(),
+        })
+    }
+}
+
+/// Returns `true` if the C++ XMP Toolkit recognizes `path` as a media file
+/// of a folder-based format.
+fn is_folder_based_clip(path: &Path) -> bool {
+    let Some(c_path) = path.to_str().and_then(|path| CString::new(path).ok()) else {
+        return false;
+    };
+
+    let mut err = ffi::CXmpError::default();
+    let format = unsafe { ffi::CXmpFileCheckFormat(&mut err, c_path.as_ptr()) };
+
+    XmpError::raise_from_c(&err).is_ok()
+        && FileFormat::from_c(format).is_some_and(FileFormat::is_folder_based)
+}
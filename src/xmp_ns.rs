@@ -22,6 +22,10 @@ pub const XMP_RIGHTS: &str = "http://ns.adobe.com/xap/1.0/rights/";
 /// The XML namespace for the XMP digital asset management schema.
 pub const XMP_MM: &str = "http://ns.adobe.com/xap/1.0/mm/";
 
+/// The XML namespace for the XMP note schema, which holds the
+/// `xmpNote:HasExtendedXMP` property of JPEG files.
+pub const XMP_NOTE: &str = "http://ns.adobe.com/xmp/note/";
+
 /// The XML namespace for the job management schema.
 pub const XMP_BJ: &str = "http://ns.adobe.com/xap/1.0/bj/";
 
@@ -34,9 +38,17 @@ pub const PHOTOSHOP: &str = "http://ns.adobe.com/photoshop/1.0/";
 /// The XML namespace for Adobe's Exif schema.
 pub const EXIF: &str = "http://ns.adobe.com/exif/1.0/";
 
+/// The XML namespace for the Exif 2.3 and later properties defined by
+/// CIPA.
+pub const EXIF_EX: &str = "http://cipa.jp/exif/1.0/";
+
 /// The XML namespace for Adobe's TIFF schema.
 pub const TIFF: &str = "http://ns.adobe.com/tiff/1.0/";
 
+/// The XML namespace for Adobe's auxiliary Exif schema, which holds camera
+/// and lens details not covered by Exif 2.2.
+pub const EXIF_AUX: &str = "http://ns.adobe.com/exif/1.0/aux/";
+
 // --- XML namespace constants for qualifiers and structured property fields ---
 
 /// The XML namespace for qualifiers of the `xmp:Identifier` property.
@@ -61,6 +73,18 @@ pub const ST_VERSION: &str = "http://ns.adobe.com/xap/1.0/sType/Version#";
 /// The XML namespace for fields of the `JobRef` type.
 pub const ST_JOB: &str = "http://ns.adobe.com/xap/1.0/sType/Job#";
 
+/// The XML namespace for Camera Raw develop settings. See
+/// [`xmp_crs`](crate::xmp_crs).
+pub const CAMERA_RAW: &str = "http://ns.adobe.com/camera-raw-settings/1.0/";
+
+/// The XML namespace for the XMP Dynamic Media schema. See
+/// [`xmp_dm`](crate::xmp_dm).
+pub const DYNAMIC_MEDIA: &str = "http://ns.adobe.com/xmp/1.0/DynamicMedia/";
+
+/// The XML namespace for the Lightroom schema, which holds hierarchical
+/// keywords.
+pub const LIGHTROOM: &str = "http://ns.adobe.com/lightroom/1.0/";
+
 // --- XML namespace constants from outside Adobe ---
 
 /// The XML namespace for the Dublin Core schema.
@@ -72,6 +96,18 @@ pub const IPTC_CORE: &str = "http://iptc.org/std/Iptc4xmpCore/1.0/xmlns/";
 /// The XML namespace for the IPTC Extension schema.
 pub const IPTC_EXT: &str = "http://iptc.org/std/Iptc4xmpExt/2008-02-29/";
 
+/// The XML namespace for the Metadata Working Group's image regions
+/// schema.
+pub const MWG_REGIONS: &str = "http://www.metadataworkinggroup.com/schemas/regions/";
+
+/// The XML namespace for the Microsoft Photo 1.2 schema, which holds
+/// Windows Photo Gallery people tags.
+pub const MICROSOFT_PHOTO: &str = "http://ns.microsoft.com/photo/1.2/";
+
+/// The XML namespace for the Google Photo Sphere schema. See
+/// [`xmp_gpano`](crate::xmp_gpano).
+pub const GPANO: &str = "http://ns.google.com/photos/1.0/panorama/";
+
 /// The XML namespace for RDF.
 pub const RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
 
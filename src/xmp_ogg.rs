@@ -0,0 +1,416 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+// Reads and writes the XMP of Ogg Vorbis and Ogg Opus files.
+//
+// An Ogg file is a sequence of pages, each carrying segments of one or
+// more packets of a logical stream. The first packets of a stream are its
+// headers; one of them holds Vorbis comments. The XMP packet is stored in
+// a comment named `XMP`. Since the comment header may span several pages,
+// the packet isn't necessarily stored contiguously.
+//
+// When the XMP is updated, the header pages are rebuilt. The pages that
+// follow are renumbered (which changes their checksums) if the number of
+// header pages changes. Files with more than one logical stream are only
+// supported if the other streams start after the headers.
+//
+// See https://xiph.org/ogg/doc/framing.html,
+// https://xiph.org/vorbis/doc/Vorbis_I_spec.html, and RFC 7845 (Opus).
+
+use std::ops::Range;
+
+use crate::{
+    xmp_file_handler::{bad_file, FileHandler},
+    xmp_vorbis_comment::Comments,
+    FileFormat, OpenFileOptions, XmpError, XmpMeta, XmpResult,
+};
+
+const OGGS: &[u8; 4] = b"OggS";
+const PAGE_HEADER_LEN: usize = 27;
+const MAX_SEGMENTS: usize = 255;
+
+const CONTINUED_FLAG: u8 = 0x01;
+const BOS_FLAG: u8 = 0x02;
+
+/// The granule position of a page on which no packet ends.
+const NO_GRANULE: [u8; 8] = [0xff; 8];
+
+const XMP_KEY: &str = "XMP";
+
+pub(crate) struct OggHandler;
+
+impl FileHandler for OggHandler {
+    fn format(&self) -> FileFormat {
+        FileFormat::Ogg
+    }
+
+    fn check_format(&self, header: &[u8]) -> bool {
+        header.starts_with(OGGS)
+    }
+
+    fn read_xmp(&self, _file: &[u8]) -> XmpResult<Option<Range<usize>>> {
+        Ok(None)
+    }
+
+    fn read_packet(&self, file: &[u8]) -> XmpResult<Option<Vec<u8>>> {
+        let pages = pages(file)?;
+        let headers = Headers::parse(file, &pages)?;
+        let (comments, data) = headers.comments()?;
+
+        Ok(comments
+            .find(data, XMP_KEY)
+            .map(|value| data[value].to_vec()))
+    }
+
+    fn write_xmp(&self, file: &[u8], packet: &[u8]) -> XmpResult<Vec<u8>> {
+        let pages = pages(file)?;
+        let headers = Headers::parse(file, &pages)?;
+        let (comments, data) = headers.comments()?;
+
+        let mut comment_packet = headers.codec.comment_prefix().to_vec();
+        comment_packet.extend_from_slice(
+            &comments
+                .replace(data, XMP_KEY, packet)
+                .ok_or_else(|| bad_ogg("XMP packet is too large"))?,
+        );
+
+        let serial = pages[0].serial;
+        let mut out = Vec::with_capacity(file.len() + packet.len() + 1024);
+
+        // The identification header must be alone on the first page.
+        let mut lacing = Vec::new();
+        push_lacing(&mut lacing, headers.packets[0].len());
+        write_page(
+            &mut out,
+            BOS_FLAG,
+            [0; 8],
+            serial,
+            0,
+            &lacing,
+            &headers.packets[0],
+        );
+
+        let mut others: Vec<&[u8]> = vec![&comment_packet];
+        others.extend(headers.packets[2..].iter().map(Vec::as_slice));
+        let new_header_pages = 1 + paginate(&mut out, serial, 1, &others);
+
+        // Renumber the pages of this stream that follow the headers.
+        let old_header_pages = headers.page_count;
+        for page in &pages[old_header_pages..] {
+            if page.serial != serial || new_header_pages == old_header_pages {
+                out.extend_from_slice(&file[page.range.clone()]);
+                continue;
+            }
+
+            let seq = page
+                .seq
+                .wrapping_add(new_header_pages as u32)
+                .wrapping_sub(old_header_pages as u32);
+
+            let start = out.len();
+            out.extend_from_slice(&file[page.range.clone()]);
+            out[start + 18..start + 22].copy_from_slice(&seq.to_le_bytes());
+            update_checksum(&mut out[start..]);
+        }
+
+        Ok(out)
+    }
+
+    fn imports_legacy(&self, flags: &OpenFileOptions) -> bool {
+        flags.imports_vorbis_comments()
+    }
+
+    fn import_legacy(&self, file: &[u8], meta: &mut XmpMeta) -> XmpResult<bool> {
+        let pages = pages(file)?;
+        let headers = Headers::parse(file, &pages)?;
+        let (comments, data) = headers.comments()?;
+        comments.import(data, meta)
+    }
+}
+
+struct Page {
+    header_type: u8,
+    serial: u32,
+    seq: u32,
+
+    // The segment table.
+    lacing: Range<usize>,
+
+    // The page's payload.
+    data: Range<usize>,
+
+    // The whole page, including its header.
+    range: Range<usize>,
+}
+
+fn pages(file: &[u8]) -> XmpResult<Vec<Page>> {
+    let mut pages = Vec::new();
+    let mut pos = 0;
+
+    while pos < file.len() {
+        let header = file
+            .get(pos..pos + PAGE_HEADER_LEN)
+            .filter(|header| header.starts_with(OGGS) && header[4] == 0)
+            .ok_or_else(|| bad_ogg("Invalid page header"))?;
+
+        let lacing = pos + PAGE_HEADER_LEN..pos + PAGE_HEADER_LEN + header[26] as usize;
+        let data_len: usize = file
+            .get(lacing.clone())
+            .ok_or_else(|| bad_ogg("Truncated page header"))?
+            .iter()
+            .map(|&len| len as usize)
+            .sum();
+
+        let data = lacing.end..lacing.end + data_len;
+        if data.end > file.len() {
+            return Err(bad_ogg("Page extends past the end of the file"));
+        }
+
+        let end = data.end;
+        pages.push(Page {
+            header_type: header[5],
+            serial: u32::from_le_bytes([header[14], header[15], header[16], header[17]]),
+            seq: u32::from_le_bytes([header[18], header[19], header[20], header[21]]),
+            lacing,
+            data,
+            range: pos..end,
+        });
+
+        pos = end;
+    }
+
+    Ok(pages)
+}
+
+#[derive(Clone, Copy)]
+enum Codec {
+    Vorbis,
+    Opus,
+}
+
+impl Codec {
+    fn detect(first_packet: &[u8]) -> Option<Self> {
+        if first_packet.starts_with(b"\x01vorbis") {
+            Some(Self::Vorbis)
+        } else if first_packet.starts_with(b"OpusHead") {
+            Some(Self::Opus)
+        } else {
+            None
+        }
+    }
+
+    fn header_count(self) -> usize {
+        match self {
+            Self::Vorbis => 3,
+            Self::Opus => 2,
+        }
+    }
+
+    fn comment_prefix(self) -> &'static [u8] {
+        match self {
+            Self::Vorbis => b"\x03vorbis",
+            Self::Opus => b"OpusTags",
+        }
+    }
+}
+
+struct Headers {
+    codec: Codec,
+    packets: Vec<Vec<u8>>,
+
+    // The number of pages occupied by the header packets.
+    page_count: usize,
+}
+
+impl Headers {
+    fn parse(file: &[u8], pages: &[Page]) -> XmpResult<Self> {
+        let first = pages.first().ok_or_else(|| bad_ogg("No pages"))?;
+        if first.header_type & BOS_FLAG == 0 {
+            return Err(bad_ogg("First page doesn't start a stream"));
+        }
+
+        let codec = Codec::detect(&file[first.data.clone()])
+            .ok_or_else(|| bad_ogg("Only Vorbis and Opus streams are supported"))?;
+
+        let mut packets = Vec::new();
+        let mut packet = Vec::new();
+
+        for (index, page) in pages.iter().enumerate() {
+            if page.serial != first.serial {
+                return Err(bad_ogg("Other streams are interleaved with the headers"));
+            }
+
+            let mut pos = page.data.start;
+            let lacing = &file[page.lacing.clone()];
+
+            for (segment, &len) in lacing.iter().enumerate() {
+                packet.extend_from_slice(&file[pos..pos + len as usize]);
+                pos += len as usize;
+
+                if len as usize == MAX_SEGMENTS {
+                    continue;
+                }
+
+                packets.push(std::mem::take(&mut packet));
+
+                if packets.len() == codec.header_count() {
+                    if segment + 1 != lacing.len() {
+                        return Err(bad_ogg("Headers don't end on a page boundary"));
+                    }
+
+                    return Ok(Self {
+                        codec,
+                        packets,
+                        page_count: index + 1,
+                    });
+                }
+            }
+        }
+
+        Err(bad_ogg("Truncated headers"))
+    }
+
+    /// Returns the comments and the data they refer to.
+    fn comments(&self) -> XmpResult<(Comments, &[u8])> {
+        let prefix = self.codec.comment_prefix();
+        let packet = &self.packets[1];
+
+        if !packet.starts_with(prefix) {
+            return Err(bad_ogg("Missing comment header"));
+        }
+
+        let data = &packet[prefix.len()..];
+        let comments = Comments::parse(data).ok_or_else(|| bad_ogg("Invalid comment header"))?;
+        Ok((comments, data))
+    }
+}
+
+fn push_lacing(lacing: &mut Vec<u8>, len: usize) {
+    lacing.resize(lacing.len() + len / MAX_SEGMENTS, MAX_SEGMENTS as u8);
+    lacing.push((len % MAX_SEGMENTS) as u8);
+}
+
+/// Writes `packets` to pages numbered from `first_seq`. Returns the number
+/// of pages written.
+fn paginate(out: &mut Vec<u8>, serial: u32, first_seq: u32, packets: &[&[u8]]) -> usize {
+    // Each segment, with a flag for the last segment of a packet.
+    let mut segments: Vec<(&[u8], bool)> = Vec::new();
+    for packet in packets {
+        let mut lacing = Vec::new();
+        push_lacing(&mut lacing, packet.len());
+
+        let mut pos = 0;
+        for (index, &len) in lacing.iter().enumerate() {
+            let len = len as usize;
+            segments.push((&packet[pos..pos + len], index + 1 == lacing.len()));
+            pos += len;
+        }
+    }
+
+    let mut continued = false;
+    let mut count = 0;
+
+    for chunk in segments.chunks(MAX_SEGMENTS) {
+        let lacing: Vec<u8> = chunk.iter().map(|(data, _)| data.len() as u8).collect();
+        let data: Vec<u8> = chunk
+            .iter()
+            .flat_map(|(data, _)| data.iter().copied())
+            .collect();
+
+        let packet_ends = chunk.iter().any(|(_, last)| *last);
+        let granule = if packet_ends { [0; 8] } else { NO_GRANULE };
+        let header_type = if continued { CONTINUED_FLAG } else { 0 };
+
+        write_page(
+            out,
+            header_type,
+            granule,
+            serial,
+            first_seq + count as u32,
+            &lacing,
+            &data,
+        );
+
+        continued = chunk.last().is_some_and(|(_, last)| !last);
+        count += 1;
+    }
+
+    count
+}
+
+fn write_page(
+    out: &mut Vec<u8>,
+    header_type: u8,
+    granule: [u8; 8],
+    serial: u32,
+    seq: u32,
+    lacing: &[u8],
+    data: &[u8],
+) {
+    let start = out.len();
+
+    out.extend_from_slice(OGGS);
+    out.push(0);
+    out.push(header_type);
+    out.extend_from_slice(&granule);
+    out.extend_from_slice(&serial.to_le_bytes());
+    out.extend_from_slice(&seq.to_le_bytes());
+    out.extend_from_slice(&[0; 4]);
+    out.push(lacing.len() as u8);
+    out.extend_from_slice(lacing);
+    out.extend_from_slice(data);
+
+    update_checksum(&mut out[start..]);
+}
+
+/// Recomputes the checksum of `page`.
+fn update_checksum(page: &mut [u8]) {
+    page[22..26].fill(0);
+
+    let crc = page.iter().fold(0u32, |crc, &b| {
+        (crc << 8) ^ CRC_TABLE[((crc >> 24) as u8 ^ b) as usize]
+    });
+
+    page[22..26].copy_from_slice(&crc.to_le_bytes());
+}
+
+/// CRC-32 with polynomial 0x04c11db7, as used by Ogg.
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut r = (i as u32) << 24;
+        let mut bit = 0;
+
+        while bit < 8 {
+            r = if r & 0x8000_0000 != 0 {
+                (r << 1) ^ 0x04c1_1db7
+            } else {
+                r << 1
+            };
+            bit += 1;
+        }
+
+        table[i] = r;
+        i += 1;
+    }
+
+    table
+}
+
+fn bad_ogg(message: &str) -> XmpError {
+    bad_file("Ogg", message)
+}
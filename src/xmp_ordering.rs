@@ -0,0 +1,107 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::XmpMeta;
+
+/// Describes the order in which namespaces and top-level properties
+/// should appear when serializing XMP.
+///
+/// The XMP specification does not assign any meaning to the order of
+/// properties, but some consumers are nonetheless sensitive to it.
+///
+/// Use with [`XmpMeta::to_string_with_ordering`]. Sorting is stable, so
+/// items that compare as [`Ordering::Equal`] keep their existing order.
+///
+/// See [`NamespaceOrder`] for a ready-made implementation.
+pub trait OutputOrdering {
+    /// Compares two schemas, given their namespace URIs.
+    fn compare_namespaces(&self, a: &str, b: &str) -> Ordering;
+
+    /// Compares two top-level properties within the schema with namespace
+    /// URI `ns`, given their local names (without namespace prefix).
+    ///
+    /// The default implementation preserves the existing order.
+    fn compare_properties(&self, ns: &str, a: &str, b: &str) -> Ordering {
+        let _ = (ns, a, b);
+        Ordering::Equal
+    }
+}
+
+/// An [`OutputOrdering`] that places a list of preferred namespaces first
+/// and optionally orders properties within a namespace.
+///
+/// Namespaces that are not listed follow the listed ones, sorted
+/// alphabetically by their registered prefix. Properties that are not listed
+/// follow the listed ones, in their existing order.
+///
+/// ## Example
+///
+/// ```
+/// # use xmp_toolkit::{xmp_ns, NamespaceOrder};
+/// // Dublin Core first, then basic XMP, then everything else alphabetically;
+/// // within Dublin Core, title and creator come first.
+/// let ordering = NamespaceOrder::default()
+///     .namespace(xmp_ns::DC)
+///     .namespace(xmp_ns::XMP)
+///     .properties(xmp_ns::DC, &["title", "creator"]);
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct NamespaceOrder {
+    namespaces: Vec<String>,
+    properties: HashMap<String, Vec<String>>,
+}
+
+impl NamespaceOrder {
+    /// Appends a namespace URI to the list of preferred namespaces.
+    pub fn namespace(mut self, ns: &str) -> Self {
+        self.namespaces.push(ns.to_owned());
+        self
+    }
+
+    /// Sets the preferred order for top-level properties within the
+    /// namespace `ns`, given as local names (without namespace prefix).
+    pub fn properties(mut self, ns: &str, names: &[&str]) -> Self {
+        self.properties.insert(
+            ns.to_owned(),
+            names.iter().map(|name| (*name).to_owned()).collect(),
+        );
+        self
+    }
+}
+
+impl OutputOrdering for NamespaceOrder {
+    fn compare_namespaces(&self, a: &str, b: &str) -> Ordering {
+        let rank = |ns: &str| self.namespaces.iter().position(|n| n == ns);
+
+        match (rank(a), rank(b)) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => {
+                let key = |ns: &str| XmpMeta::namespace_prefix(ns).unwrap_or_else(|| ns.to_owned());
+                key(a).cmp(&key(b))
+            }
+        }
+    }
+
+    fn compare_properties(&self, ns: &str, a: &str, b: &str) -> Ordering {
+        let Some(names) = self.properties.get(ns) else {
+            return Ordering::Equal;
+        };
+
+        let rank = |name: &str| names.iter().position(|n| n == name).unwrap_or(usize::MAX);
+        rank(a).cmp(&rank(b))
+    }
+}
@@ -0,0 +1,412 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Contains functions for finding every XMP packet in a buffer or file.
+//!
+//! [`XmpFile`] returns a single packet: the one the file format's handler
+//! considers current, or the first one found when scanning. Files that were
+//! edited by tools unaware of each other (or that were simply concatenated)
+//! can contain several packets, and stale ones are worth auditing. The
+//! functions in this module return all of them, with their locations.
+//!
+//! Packets are found by looking for `<?xpacket begin=` … `<?xpacket end=`
+//! wrappers and for `x:xmpmeta` elements outside of a wrapper. Only
+//! packets encoded as UTF-8 are recognized.
+//!
+//! [`repair`] recovers the XMP of a damaged packet, such as one that was
+//! cut off or whose wrapper is broken.
+//!
+//! [`XmpFile`]: crate::XmpFile
+
+use std::{borrow::Cow, fs, ops::Range, path::Path, str::FromStr};
+
+use crate::{XmpError, XmpErrorType, XmpMeta, XmpResult};
+
+const PACKET_HEADER: &[u8] = b"<?xpacket begin=";
+const PACKET_TRAILER: &[u8] = b"<?xpacket end=";
+
+/// Root elements recognized outside of a packet wrapper. `x:xapmeta` was
+/// written by early versions of the XMP Toolkit.
+const ROOT_ELEMENTS: &[(&[u8], &[u8])] = &[
+    (b"<x:xmpmeta", b"</x:xmpmeta>"),
+    (b"<x:xapmeta", b"</x:xapmeta>"),
+];
+
+/// An XMP packet found by [`scan`] or [`scan_file`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ScannedPacket {
+    /// The location of the packet, in bytes from the start of the buffer
+    /// or file.
+    ///
+    /// For wrapped packets, this runs from the start of the
+    /// `<?xpacket begin=` header to the end of the `<?xpacket end=`
+    /// trailer, including any padding.
+    pub range: Range<usize>,
+
+    /// The text of the packet.
+    pub text: String,
+
+    /// The value of the `end` attribute of the packet trailer: `true` for
+    /// `"w"` (the packet may be updated in place) and `false` for `"r"`.
+    ///
+    /// `None` if the packet has no wrapper.
+    pub writable: Option<bool>,
+}
+
+impl ScannedPacket {
+    /// Parses the packet.
+    pub fn parse(&self) -> XmpResult<XmpMeta> {
+        XmpMeta::from_str(&self.text)
+    }
+}
+
+/// Returns every XMP packet in `bytes`, in the order in which they
+/// appear.
+///
+/// A packet wrapper without a trailer is ignored, but an `x:xmpmeta`
+/// element inside it is still reported.
+///
+/// ## Example
+///
+/// ```
+/// # use xmp_toolkit::xmp_packet;
+/// let data = br#"<x:xmpmeta xmlns:x="adobe:ns:meta/"/>...<x:xmpmeta xmlns:x="adobe:ns:meta/"></x:xmpmeta>"#;
+///
+/// let packets = xmp_packet::scan(data);
+/// assert_eq!(packets.len(), 2);
+/// assert_eq!(packets[1].range, 40..88);
+/// ```
+pub fn scan(bytes: &[u8]) -> Vec<ScannedPacket> {
+    let mut packets = Vec::new();
+    let mut pos = 0;
+
+    while let Some((start, kind)) = next_start(bytes, pos) {
+        let found = match kind {
+            Start::Wrapper => wrapped_packet(bytes, start),
+            Start::Root(index) => root_element(bytes, start, index),
+        };
+
+        match found {
+            Some(packet) => {
+                pos = packet.range.end;
+                packets.push(packet);
+            }
+            None => pos = start + 1,
+        }
+    }
+
+    packets
+}
+
+/// Returns every XMP packet in the file at `path`, in the order in which
+/// they appear.
+///
+/// The whole file is read into memory. See [`scan`].
+///
+/// ## Errors
+///
+/// Returns an error of type [`XmpErrorType::ReadError`] if the file can't
+/// be read.
+pub fn scan_file<P: AsRef<Path>>(path: P) -> XmpResult<Vec<ScannedPacket>> {
+    let bytes = fs::read(path).map_err(|err| XmpError {
+        error_type: XmpErrorType::ReadError,
+        debug_message: err.to_string(),
+    })?;
+
+    Ok(scan(&bytes))
+}
+
+/// A problem that [`repair`] found and fixed in a damaged XMP packet.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum PacketRepair {
+    /// The `<?xpacket begin=` header or the `<?xpacket end=` trailer was
+    /// malformed or missing. The wrapper was dropped.
+    BrokenWrapper,
+
+    /// The padding between the XMP and the packet trailer contained
+    /// something other than whitespace. It was dropped.
+    BadPadding,
+
+    /// The packet was cut off before the end of its root element. The
+    /// partial markup at the end was dropped and the elements left open
+    /// were closed, so properties near the end may be lost.
+    Truncated,
+
+    /// The packet contained bytes that aren't valid UTF-8. They were
+    /// replaced by U+FFFD.
+    InvalidUtf8,
+}
+
+/// An XMP packet recovered by [`repair`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct RepairedPacket {
+    /// The location of the damaged packet, in bytes from the start of the
+    /// buffer, including its wrapper and padding where they could be
+    /// identified. A new packet of the same length can be written here.
+    pub range: Range<usize>,
+
+    /// The repaired XMP, without a packet wrapper.
+    pub text: String,
+
+    /// The problems that were fixed. Empty if the packet wasn't damaged.
+    pub repairs: Vec<PacketRepair>,
+}
+
+impl RepairedPacket {
+    /// Parses the repaired packet.
+    pub fn parse(&self) -> XmpResult<XmpMeta> {
+        XmpMeta::from_str(&self.text)
+    }
+}
+
+/// Finds the first XMP packet in `bytes` and repairs it if it is damaged.
+///
+/// Unlike [`scan`], this accepts packets whose wrapper is broken or that
+/// are cut off, such as the packet of a file that was truncated or a
+/// packet whose container segment was too short. A packet is assumed to
+/// end no later than the first byte that can't be part of UTF-8 XML
+/// text, such as the binary data that follows a packet embedded in an
+/// image file.
+///
+/// Returns `None` if `bytes` contains no `x:xmpmeta` (or bare `rdf:RDF`)
+/// element.
+///
+/// ## Example
+///
+/// ```
+/// # use xmp_toolkit::xmp_packet::{self, PacketRepair};
+/// let data = br#"<x:xmpmeta xmlns:x="adobe:ns:meta/"><rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"><rdf:Description"#;
+///
+/// let packet = xmp_packet::repair(data).unwrap().unwrap();
+/// assert_eq!(packet.repairs, [PacketRepair::Truncated]);
+/// assert!(packet.parse().is_ok());
+/// ```
+///
+/// ## Errors
+///
+/// Returns the parser's error if the packet can't be parsed even after
+/// repair.
+pub fn repair(bytes: &[u8]) -> XmpResult<Option<RepairedPacket>> {
+    const ROOTS: &[(&[u8], &[u8])] = &[
+        ROOT_ELEMENTS[0],
+        ROOT_ELEMENTS[1],
+        (b"<rdf:RDF", b"</rdf:RDF>"),
+    ];
+
+    let Some((start, kind)) = next_start(bytes, 0) else {
+        return Ok(None);
+    };
+
+    // The packet can't extend past a byte that never appears in XML text.
+    let limit = bytes[start..]
+        .iter()
+        .position(|&b| !is_text_byte(b))
+        .map_or(bytes.len(), |len| start + len);
+    let text = &bytes[..limit];
+
+    let Some((root_start, (_, close))) = ROOTS
+        .iter()
+        .filter_map(|root| Some((find(text, root.0, start)?, root)))
+        .min_by_key(|(root_start, _)| *root_start)
+    else {
+        return Ok(None);
+    };
+
+    let mut repairs = Vec::new();
+    let root_end = find(text, close, root_start).map(|end| end + close.len());
+
+    let end = match (kind, root_end) {
+        (_, None) => {
+            repairs.push(PacketRepair::Truncated);
+            limit
+        }
+
+        (Start::Root(_), Some(root_end)) => root_end,
+
+        (Start::Wrapper, Some(root_end)) => {
+            if find(text, b"?>", start).map_or(true, |header_end| header_end > root_start) {
+                repairs.push(PacketRepair::BrokenWrapper);
+            }
+
+            let trailer = find(text, PACKET_TRAILER, root_end)
+                .and_then(|trailer| Some((trailer, find(text, b"?>", trailer)? + 2)));
+
+            match trailer {
+                Some((trailer, trailer_end)) => {
+                    if !text[root_end..trailer].iter().all(u8::is_ascii_whitespace) {
+                        repairs.push(PacketRepair::BadPadding);
+                    }
+
+                    trailer_end
+                }
+
+                None => {
+                    repairs.push(PacketRepair::BrokenWrapper);
+                    root_end
+                        + text[root_end..]
+                            .iter()
+                            .take_while(|b| b.is_ascii_whitespace())
+                            .count()
+                }
+            }
+        }
+    };
+
+    let xml = match String::from_utf8_lossy(&text[root_start..root_end.unwrap_or(limit)]) {
+        Cow::Borrowed(xml) => xml.to_owned(),
+        Cow::Owned(xml) => {
+            repairs.push(PacketRepair::InvalidUtf8);
+            xml
+        }
+    };
+
+    let packet = RepairedPacket {
+        range: start..end,
+        text: if root_end.is_some() {
+            xml
+        } else {
+            close_open_elements(&xml)
+        },
+        repairs,
+    };
+
+    packet.parse()?;
+    Ok(Some(packet))
+}
+
+/// Returns `true` for bytes that may appear in UTF-8 encoded XML text.
+fn is_text_byte(b: u8) -> bool {
+    match b {
+        b'\t' | b'\n' | b'\r' => true,
+        0x00..=0x1f | 0xc0 | 0xc1 | 0xf5..=0xff => false,
+        _ => true,
+    }
+}
+
+/// Drops the partial markup at the end of `xml`, which was cut off, and
+/// closes the elements that are still open.
+fn close_open_elements(xml: &str) -> String {
+    let xml = &xml[..xml.rfind('>').map_or(0, |end| end + 1)];
+
+    let mut open: Vec<&str> = Vec::new();
+    let mut rest = xml;
+
+    while let Some(tag_start) = rest.find('<') {
+        let tag = &rest[tag_start + 1..];
+        let Some(tag_end) = tag.find('>') else {
+            break;
+        };
+
+        let body = &tag[..tag_end];
+        if let Some(name) = body.strip_prefix('/') {
+            if let Some(index) = open.iter().rposition(|&open| open == name.trim()) {
+                open.truncate(index);
+            }
+        } else if !body.starts_with(['?', '!']) && !body.ends_with('/') {
+            open.extend(body.split_whitespace().next());
+        }
+
+        rest = &tag[tag_end + 1..];
+    }
+
+    let mut closed = xml.to_owned();
+    for name in open.iter().rev() {
+        closed.push_str("</");
+        closed.push_str(name);
+        closed.push('>');
+    }
+
+    closed
+}
+
+enum Start {
+    Wrapper,
+    Root(usize),
+}
+
+/// Finds the next packet header or root element at or after `pos`.
+fn next_start(bytes: &[u8], pos: usize) -> Option<(usize, Start)> {
+    let wrapper = find(bytes, PACKET_HEADER, pos).map(|start| (start, Start::Wrapper));
+
+    let root = ROOT_ELEMENTS
+        .iter()
+        .enumerate()
+        .filter_map(|(index, (open, _))| {
+            find(bytes, open, pos)
+                .filter(|&start| is_tag_end(bytes, start + open.len()))
+                .map(|start| (start, Start::Root(index)))
+        })
+        .min_by_key(|(start, _)| *start);
+
+    match (wrapper, root) {
+        (Some(wrapper), Some(root)) if root.0 < wrapper.0 => Some(root),
+        (Some(wrapper), _) => Some(wrapper),
+        (None, root) => root,
+    }
+}
+
+fn wrapped_packet(bytes: &[u8], start: usize) -> Option<ScannedPacket> {
+    let trailer = find(bytes, PACKET_TRAILER, start + PACKET_HEADER.len())?;
+
+    // Don't let a packet without a trailer swallow the packet after it.
+    if find(bytes, PACKET_HEADER, start + PACKET_HEADER.len()).is_some_and(|next| next < trailer) {
+        return None;
+    }
+
+    let end = find(bytes, b"?>", trailer)? + 2;
+
+    // Skip the opening quote of the `end` attribute's value.
+    let writable = bytes.get(trailer + PACKET_TRAILER.len() + 1) == Some(&b'w');
+
+    Some(packet(bytes, start..end, Some(writable)))
+}
+
+fn root_element(bytes: &[u8], start: usize, index: usize) -> Option<ScannedPacket> {
+    let (open, close) = ROOT_ELEMENTS[index];
+
+    let tag_end = find(bytes, b">", start + open.len())?;
+    let end = if bytes[tag_end - 1] == b'/' {
+        tag_end + 1
+    } else {
+        find(bytes, close, tag_end)? + close.len()
+    };
+
+    Some(packet(bytes, start..end, None))
+}
+
+fn packet(bytes: &[u8], range: Range<usize>, writable: Option<bool>) -> ScannedPacket {
+    ScannedPacket {
+        text: String::from_utf8_lossy(&bytes[range.clone()]).into_owned(),
+        range,
+        writable,
+    }
+}
+
+/// Returns `true` if the element name that ends at `pos` isn't merely a
+/// prefix of a longer name.
+fn is_tag_end(bytes: &[u8], pos: usize) -> bool {
+    bytes
+        .get(pos)
+        .is_some_and(|b| b.is_ascii_whitespace() || matches!(b, b'>' | b'/'))
+}
+
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|offset| from + offset)
+}
@@ -0,0 +1,47 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::fmt;
+
+use crate::XmpErrorType;
+
+/// Describes a problem that was found and silently repaired or dropped
+/// while parsing XMP.
+///
+/// The C++ XMP Toolkit recovers from many kinds of malformed or
+/// inconsistent input, such as mismatched alias values or unrecognized
+/// RDF constructs, without reporting an error. Use
+/// [`XmpMeta::from_str_with_report`] to obtain a list of these problems.
+///
+/// [`XmpMeta::from_str_with_report`]: crate::XmpMeta::from_str_with_report
+#[derive(Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ParseWarning {
+    /// The kind of problem that was found.
+    pub error_type: XmpErrorType,
+
+    /// Descriptive string, for debugging use only. As with
+    /// [`XmpError::debug_message`](crate::XmpError::debug_message), it is
+    /// written for developers, not users, and never localized.
+    pub message: String,
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.message.is_empty() {
+            write!(f, "ParseWarning({})", self.error_type)
+        } else {
+            write!(f, "ParseWarning({}, {})", self.error_type, self.message)
+        }
+    }
+}
@@ -0,0 +1,274 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::fmt::Debug;
+
+use crate::{XmpDateTime, XmpMeta, XmpResult, XmpValue};
+
+/// A Rust type that can be stored as the value of a simple XMP property.
+///
+/// This trait enables the generic accessors [`XmpMeta::get`] and
+/// [`XmpMeta::set`]. It is implemented for `String`, `bool`, `i32`, `i64`,
+/// `f64`, and [`XmpDateTime`]; those implementations use the same
+/// conversions as [`XmpMeta::property_bool`], [`XmpMeta::set_property_bool`],
+/// and related functions.
+///
+/// Implement it for your own types by describing how to convert to and
+/// from the string form that XMP stores.
+///
+/// ## Example
+///
+/// ```
+/// use xmp_toolkit::{XmpMeta, XmpPropertyType};
+///
+/// #[derive(Clone, Debug, Default, PartialEq)]
+/// struct Rational {
+///     num: i64,
+///     den: i64,
+/// }
+///
+/// impl XmpPropertyType for Rational {
+///     fn from_xmp_str(s: &str) -> Option<Self> {
+///         let (num, den) = s.split_once('/')?;
+///         Some(Self {
+///             num: num.parse().ok()?,
+///             den: den.parse().ok()?,
+///         })
+///     }
+///
+///     fn to_xmp_string(&self) -> String {
+///         format!("{}/{}", self.num, self.den)
+///     }
+/// }
+///
+/// # fn main() -> xmp_toolkit::XmpResult<()> {
+/// let mut meta = XmpMeta::new()?;
+/// let exif = "http://ns.adobe.com/exif/1.0/";
+///
+/// meta.set(exif, "ExposureTime", Rational { num: 1, den: 250 })?;
+/// assert_eq!(
+///     meta.get::<Rational>(exif, "ExposureTime")
+///         .unwrap()
+///         .value
+///         .den,
+///     250
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub trait XmpPropertyType: Clone + Debug + Default + PartialEq {
+    /// Parses a value from its XMP string form.
+    ///
+    /// Returns `None` if the string can not be interpreted as this type.
+    fn from_xmp_str(s: &str) -> Option<Self>;
+
+    /// Converts a value to its XMP string form.
+    fn to_xmp_string(&self) -> String;
+
+    /// Reads a simple property value from `meta`.
+    ///
+    /// The default implementation reads the string value with
+    /// [`XmpMeta::property`] and converts it with
+    /// [`from_xmp_str`](Self::from_xmp_str). Override it only if the type
+    /// needs a different mechanism.
+    fn get_property(meta: &XmpMeta, namespace: &str, path: &str) -> Option<XmpValue<Self>> {
+        let prop = meta.property(namespace, path)?;
+
+        Some(XmpValue {
+            value: Self::from_xmp_str(&prop.value)?,
+            options: prop.options,
+        })
+    }
+
+    /// Creates or sets a simple property value in `meta`.
+    ///
+    /// The default implementation converts the value with
+    /// [`to_xmp_string`](Self::to_xmp_string) and writes it with
+    /// [`XmpMeta::set_property`]. Override it only if the type needs a
+    /// different mechanism.
+    fn set_property(
+        meta: &mut XmpMeta,
+        namespace: &str,
+        path: &str,
+        new_value: &XmpValue<Self>,
+    ) -> XmpResult<()> {
+        meta.set_property(
+            namespace,
+            path,
+            &XmpValue {
+                value: new_value.value.to_xmp_string(),
+                options: new_value.options,
+            },
+        )
+    }
+}
+
+impl XmpPropertyType for String {
+    fn from_xmp_str(s: &str) -> Option<Self> {
+        Some(s.to_owned())
+    }
+
+    fn to_xmp_string(&self) -> String {
+        self.clone()
+    }
+}
+
+impl XmpPropertyType for bool {
+    /// Accepts `true`, `t`, and `1` as true and `false`, `f`, and `0` as
+    /// false, ignoring case and surrounding whitespace.
+    fn from_xmp_str(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "true" | "t" | "1" => Some(true),
+            "false" | "f" | "0" => Some(false),
+            _ => None,
+        }
+    }
+
+    fn to_xmp_string(&self) -> String {
+        if *self { "True" } else { "False" }.to_owned()
+    }
+
+    fn get_property(meta: &XmpMeta, namespace: &str, path: &str) -> Option<XmpValue<Self>> {
+        meta.property_bool(namespace, path)
+    }
+
+    fn set_property(
+        meta: &mut XmpMeta,
+        namespace: &str,
+        path: &str,
+        new_value: &XmpValue<Self>,
+    ) -> XmpResult<()> {
+        meta.set_property_bool(namespace, path, new_value)
+    }
+}
+
+impl XmpPropertyType for i32 {
+    /// Accepts decimal or `0x`-prefixed hexadecimal values, ignoring
+    /// surrounding whitespace.
+    fn from_xmp_str(s: &str) -> Option<Self> {
+        parse_int(s).and_then(|value| Self::try_from(value).ok())
+    }
+
+    fn to_xmp_string(&self) -> String {
+        self.to_string()
+    }
+
+    fn get_property(meta: &XmpMeta, namespace: &str, path: &str) -> Option<XmpValue<Self>> {
+        meta.property_i32(namespace, path)
+    }
+
+    fn set_property(
+        meta: &mut XmpMeta,
+        namespace: &str,
+        path: &str,
+        new_value: &XmpValue<Self>,
+    ) -> XmpResult<()> {
+        meta.set_property_i32(namespace, path, new_value)
+    }
+}
+
+impl XmpPropertyType for i64 {
+    /// Accepts decimal or `0x`-prefixed hexadecimal values, ignoring
+    /// surrounding whitespace.
+    fn from_xmp_str(s: &str) -> Option<Self> {
+        parse_int(s)
+    }
+
+    fn to_xmp_string(&self) -> String {
+        self.to_string()
+    }
+
+    fn get_property(meta: &XmpMeta, namespace: &str, path: &str) -> Option<XmpValue<Self>> {
+        meta.property_i64(namespace, path)
+    }
+
+    fn set_property(
+        meta: &mut XmpMeta,
+        namespace: &str,
+        path: &str,
+        new_value: &XmpValue<Self>,
+    ) -> XmpResult<()> {
+        meta.set_property_i64(namespace, path, new_value)
+    }
+}
+
+impl XmpPropertyType for f64 {
+    fn from_xmp_str(s: &str) -> Option<Self> {
+        s.trim().parse().ok()
+    }
+
+    fn to_xmp_string(&self) -> String {
+        self.to_string()
+    }
+
+    fn get_property(meta: &XmpMeta, namespace: &str, path: &str) -> Option<XmpValue<Self>> {
+        meta.property_f64(namespace, path)
+    }
+
+    fn set_property(
+        meta: &mut XmpMeta,
+        namespace: &str,
+        path: &str,
+        new_value: &XmpValue<Self>,
+    ) -> XmpResult<()> {
+        meta.set_property_f64(namespace, path, new_value)
+    }
+}
+
+impl XmpPropertyType for XmpDateTime {
+    fn from_xmp_str(s: &str) -> Option<Self> {
+        s.parse().ok()
+    }
+
+    fn to_xmp_string(&self) -> String {
+        self.to_string()
+    }
+
+    fn get_property(meta: &XmpMeta, namespace: &str, path: &str) -> Option<XmpValue<Self>> {
+        meta.property_date(namespace, path)
+    }
+
+    fn set_property(
+        meta: &mut XmpMeta,
+        namespace: &str,
+        path: &str,
+        new_value: &XmpValue<Self>,
+    ) -> XmpResult<()> {
+        meta.set_property_date(namespace, path, new_value)
+    }
+}
+
+fn parse_int(s: &str) -> Option<i64> {
+    let s = s.trim();
+
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+
+    let (radix, digits) = match digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        Some(hex) => (16, hex),
+        None => (10, digits),
+    };
+
+    // `from_str_radix` would accept a second sign.
+    if !digits.starts_with(|c: char| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let magnitude = i128::from(u64::from_str_radix(digits, radix).ok()?);
+    i64::try_from(if negative { -magnitude } else { magnitude }).ok()
+}
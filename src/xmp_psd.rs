@@ -0,0 +1,395 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+// Reads the Photoshop-specific metadata of PSD and PSB files that the C++
+// XMP Toolkit doesn't expose: the image resource blocks and the XMP of
+// individual layers. Nothing here writes to the file.
+//
+// See the Adobe Photoshop File Formats Specification for the layout of
+// the sections parsed here.
+
+use std::{
+    io::{Read, Seek, SeekFrom},
+    str::FromStr,
+};
+
+use crate::{xmp_file_handler::bad_file, XmpError, XmpMeta, XmpResult};
+
+const SIGNATURE: &[u8; 4] = b"8BPS";
+
+// Signatures of image resource blocks and additional layer information.
+const RESOURCE_SIGNATURES: &[&[u8; 4]] = &[b"8BIM", b"MeSa", b"AgHg", b"PHUT", b"DCSR"];
+const LAYER_INFO_SIGNATURES: &[&[u8; 4]] = &[b"8BIM", b"8B64"];
+
+// Additional layer information keys.
+const UNICODE_NAME: &[u8; 4] = b"luni";
+const LAYER_ID: &[u8; 4] = b"lyid";
+const METADATA_SETTING: &[u8; 4] = b"shmd";
+
+// Additional layer information keys whose length is 8 bytes in PSB files.
+const LONG_KEYS: &[&[u8; 4]] = &[
+    b"LMsk", b"Lr16", b"Lr32", b"Layr", b"Mt16", b"Mt32", b"Mtrn", b"Alph", b"FMsk", b"lnk2",
+    b"FEid", b"FXid", b"PxSD",
+];
+
+/// An image resource block of a Photoshop (PSD or PSB) file, as returned by
+/// [`XmpFile::psd_image_resources`].
+///
+/// Image resources hold non-pixel data such as the resolution, the IPTC
+/// record (ID 1028), and the file's XMP packet (ID 1060).
+///
+/// [`XmpFile::psd_image_resources`]: crate::XmpFile::psd_image_resources
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct PsdImageResource {
+    /// The block's signature, which is `8BIM` for resources written by
+    /// Photoshop.
+    pub signature: [u8; 4],
+
+    /// The resource ID.
+    pub id: u16,
+
+    /// The resource name, which is usually empty.
+    pub name: String,
+
+    /// The resource data.
+    pub data: Vec<u8>,
+}
+
+/// A layer of a Photoshop (PSD or PSB) file, as returned by
+/// [`XmpFile::psd_layers`].
+///
+/// [`XmpFile::psd_layers`]: crate::XmpFile::psd_layers
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct PsdLayer {
+    /// The layer's name: its Unicode name if present, and otherwise its
+    /// legacy Pascal string name.
+    pub name: String,
+
+    /// The layer's ID, which is unique within the document and stays the
+    /// same when layers are reordered, if present.
+    pub id: Option<u32>,
+
+    /// The XMP attached to the layer, if any.
+    pub xmp: Option<XmpMeta>,
+}
+
+/// Returns the image resource blocks of a PSD or PSB file.
+pub(crate) fn image_resources<R: Read + Seek>(r: &mut R) -> XmpResult<Vec<PsdImageResource>> {
+    read_header(r)?;
+    skip_section(r)?;
+
+    let len = read_u32(r)?;
+    let mut section = vec![0; len as usize];
+    r.read_exact(&mut section).map_err(|_| truncated())?;
+
+    let mut resources = Vec::new();
+    let mut pos = 0;
+
+    while pos + 4 <= section.len() {
+        let mut p = Parser::new(&section[pos..]);
+
+        let signature = p.array::<4>()?;
+        if !RESOURCE_SIGNATURES.contains(&&signature) {
+            return Err(bad_psd("Invalid image resource signature"));
+        }
+
+        let id = p.u16()?;
+        let name = p.pascal_string(2)?;
+        let len = p.u32()? as usize;
+        let data = p.bytes(len)?.to_vec();
+        p.skip(len % 2)?;
+
+        resources.push(PsdImageResource {
+            signature,
+            id,
+            name,
+            data,
+        });
+
+        pos += p.pos;
+    }
+
+    Ok(resources)
+}
+
+/// Returns the layers of a PSD or PSB file, from bottom to top.
+pub(crate) fn layers<R: Read + Seek>(r: &mut R) -> XmpResult<Vec<PsdLayer>> {
+    let psb = read_header(r)?;
+    skip_section(r)?;
+    skip_section(r)?;
+
+    let layer_and_mask_len = read_length(r, psb)?;
+    if layer_and_mask_len == 0 {
+        return Ok(Vec::new());
+    }
+
+    let layer_info_len = read_length(r, psb)?;
+    if layer_info_len == 0 {
+        return Ok(Vec::new());
+    }
+
+    // A negative count means that the first alpha channel holds the
+    // merged result's transparency.
+    let count = read_u16(r)? as i16;
+    let count = count.unsigned_abs();
+
+    let mut layers = Vec::with_capacity(usize::from(count));
+
+    for _ in 0..count {
+        // Bounds, then the channel information.
+        let mut fixed = [0; 18];
+        r.read_exact(&mut fixed).map_err(|_| truncated())?;
+        let channels = u16::from_be_bytes([fixed[16], fixed[17]]);
+        let channel_len = if psb { 10 } else { 6 };
+        r.seek(SeekFrom::Current(i64::from(channels) * channel_len))
+            .map_err(|_| truncated())?;
+
+        // Blend mode signature and key, opacity, clipping, flags, filler.
+        let mut blend = [0; 12];
+        r.read_exact(&mut blend).map_err(|_| truncated())?;
+        if &blend[0..4] != b"8BIM" {
+            return Err(bad_psd("Invalid blend mode signature"));
+        }
+
+        let extra_len = read_u32(r)?;
+        let mut extra = vec![0; extra_len as usize];
+        r.read_exact(&mut extra).map_err(|_| truncated())?;
+
+        layers.push(layer(&extra, psb)?);
+    }
+
+    Ok(layers)
+}
+
+/// Parses the extra data of a layer record.
+fn layer(extra: &[u8], psb: bool) -> XmpResult<PsdLayer> {
+    let mut p = Parser::new(extra);
+
+    // Layer mask and blending ranges.
+    for _ in 0..2 {
+        let len = p.u32()? as usize;
+        p.skip(len)?;
+    }
+
+    let mut layer = PsdLayer {
+        name: p.pascal_string(4)?,
+        id: None,
+        xmp: None,
+    };
+
+    // Additional layer information. Writers don't agree on how these
+    // blocks are padded, so stop at the first block that doesn't start
+    // where expected rather than failing.
+    while p.remaining() >= 12 {
+        let signature = p.array::<4>()?;
+        if !LAYER_INFO_SIGNATURES.contains(&&signature) {
+            break;
+        }
+
+        let key = p.array::<4>()?;
+        let len = if psb && LONG_KEYS.contains(&&key) {
+            p.u64()? as usize
+        } else {
+            p.u32()? as usize
+        };
+
+        let Ok(data) = p.bytes(len) else {
+            break;
+        };
+
+        match &key {
+            UNICODE_NAME => {
+                if let Some(name) = unicode_string(data) {
+                    layer.name = name;
+                }
+            }
+            LAYER_ID if data.len() >= 4 => {
+                layer.id = Some(u32::from_be_bytes([data[0], data[1], data[2], data[3]]));
+            }
+            METADATA_SETTING => {
+                layer.xmp = metadata_setting_xmp(data)?;
+            }
+            _ => (),
+        }
+    }
+
+    Ok(layer)
+}
+
+/// Returns the XMP stored in the items of a layer's metadata setting
+/// (`shmd`) block, if any.
+fn metadata_setting_xmp(data: &[u8]) -> XmpResult<Option<XmpMeta>> {
+    let mut p = Parser::new(data);
+    let count = p.u32()?;
+
+    for _ in 0..count {
+        // Signature, key, copy-on-sheet-duplication flag, and padding.
+        p.skip(12)?;
+        let len = p.u32()? as usize;
+        let item = p.bytes(len)?;
+
+        if let Some(xmp) = find_xmpmeta(item) {
+            return XmpMeta::from_str(&String::from_utf8_lossy(xmp)).map(Some);
+        }
+    }
+
+    Ok(None)
+}
+
+/// Returns the `x:xmpmeta` element in `bytes`, if any.
+fn find_xmpmeta(bytes: &[u8]) -> Option<&[u8]> {
+    const START: &[u8] = b"<x:xmpmeta";
+    const END: &[u8] = b"</x:xmpmeta>";
+
+    let start = bytes.windows(START.len()).position(|w| w == START)?;
+    let len = bytes[start..].windows(END.len()).position(|w| w == END)? + END.len();
+
+    Some(&bytes[start..start + len])
+}
+
+/// Decodes a Unicode string: a 4-byte count of UTF-16 code units followed
+/// by the big-endian code units.
+fn unicode_string(data: &[u8]) -> Option<String> {
+    let mut p = Parser::new(data);
+    let len = p.u32().ok()? as usize;
+    let units: Vec<u16> = p
+        .bytes(len.checked_mul(2)?)
+        .ok()?
+        .chunks(2)
+        .map(|unit| u16::from_be_bytes([unit[0], unit[1]]))
+        .take_while(|&unit| unit != 0)
+        .collect();
+
+    Some(String::from_utf16_lossy(&units))
+}
+
+/// Reads the file header. Returns `true` for a PSB (large document format)
+/// file.
+fn read_header<R: Read + Seek>(r: &mut R) -> XmpResult<bool> {
+    let mut header = [0; 26];
+    r.rewind().map_err(|_| truncated())?;
+    r.read_exact(&mut header)
+        .map_err(|_| bad_psd("Not a Photoshop file"))?;
+
+    if &header[0..4] != SIGNATURE {
+        return Err(bad_psd("Not a Photoshop file"));
+    }
+
+    match u16::from_be_bytes([header[4], header[5]]) {
+        1 => Ok(false),
+        2 => Ok(true),
+        _ => Err(bad_psd("Unsupported version")),
+    }
+}
+
+/// Skips a section that starts with a 4-byte length.
+fn skip_section<R: Read + Seek>(r: &mut R) -> XmpResult<()> {
+    let len = read_u32(r)?;
+    r.seek(SeekFrom::Current(i64::from(len)))
+        .map_err(|_| truncated())?;
+    Ok(())
+}
+
+/// Reads a section length, which is 8 bytes in PSB files.
+fn read_length<R: Read>(r: &mut R, psb: bool) -> XmpResult<u64> {
+    if psb {
+        let mut b = [0; 8];
+        r.read_exact(&mut b).map_err(|_| truncated())?;
+        Ok(u64::from_be_bytes(b))
+    } else {
+        read_u32(r).map(u64::from)
+    }
+}
+
+fn read_u16<R: Read>(r: &mut R) -> XmpResult<u16> {
+    let mut b = [0; 2];
+    r.read_exact(&mut b).map_err(|_| truncated())?;
+    Ok(u16::from_be_bytes(b))
+}
+
+fn read_u32<R: Read>(r: &mut R) -> XmpResult<u32> {
+    let mut b = [0; 4];
+    r.read_exact(&mut b).map_err(|_| truncated())?;
+    Ok(u32::from_be_bytes(b))
+}
+
+/// Reads big-endian values from a section held in memory.
+struct Parser<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn remaining(&self) -> usize {
+        self.data.len() - self.pos
+    }
+
+    fn bytes(&mut self, len: usize) -> XmpResult<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .ok_or_else(truncated)?;
+
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, len: usize) -> XmpResult<()> {
+        self.bytes(len).map(|_| ())
+    }
+
+    fn array<const N: usize>(&mut self) -> XmpResult<[u8; N]> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.bytes(N)?);
+        Ok(array)
+    }
+
+    fn u16(&mut self) -> XmpResult<u16> {
+        self.array().map(u16::from_be_bytes)
+    }
+
+    fn u32(&mut self) -> XmpResult<u32> {
+        self.array().map(u32::from_be_bytes)
+    }
+
+    fn u64(&mut self) -> XmpResult<u64> {
+        self.array().map(u64::from_be_bytes)
+    }
+
+    /// Reads a Pascal string whose total length, including the length
+    /// byte, is padded to a multiple of `alignment`.
+    fn pascal_string(&mut self, alignment: usize) -> XmpResult<String> {
+        let len = usize::from(self.array::<1>()?[0]);
+        let name = String::from_utf8_lossy(self.bytes(len)?).into_owned();
+
+        let padded = (len + alignment) / alignment * alignment;
+        self.skip(padded - len - 1)?;
+        Ok(name)
+    }
+}
+
+fn truncated() -> XmpError {
+    bad_psd("File is truncated")
+}
+
+fn bad_psd(message: &str) -> XmpError {
+    bad_file("PSD", message)
+}
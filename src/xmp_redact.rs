@@ -0,0 +1,214 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use crate::{xmp_ns, XmpMeta, XmpResult};
+
+/// Detailed location fields removed by [`RedactionProfile::location`], in
+/// addition to the `exif:GPS*` properties.
+const LOCATION_PROPERTIES: &[(&str, &str)] = &[
+    (xmp_ns::IPTC_CORE, "Location"),
+    (xmp_ns::IPTC_EXT, "LocationCreated"),
+    (xmp_ns::IPTC_EXT, "LocationShown"),
+    (xmp_ns::PHOTOSHOP, "City"),
+];
+
+const SERIAL_NUMBER_PROPERTIES: &[(&str, &str)] = &[
+    (xmp_ns::EXIF_AUX, "SerialNumber"),
+    (xmp_ns::EXIF_AUX, "LensSerialNumber"),
+    (xmp_ns::EXIF_EX, "BodySerialNumber"),
+    (xmp_ns::EXIF_EX, "LensSerialNumber"),
+];
+
+const OWNER_NAME_PROPERTIES: &[(&str, &str)] = &[
+    (xmp_ns::EXIF_AUX, "OwnerName"),
+    (xmp_ns::EXIF_EX, "CameraOwnerName"),
+];
+
+/// Describes the personal data removed by [`XmpMeta::redact`].
+///
+/// The default profile removes nothing. Use
+/// [`RedactionProfile::personal_data`] to remove every category of
+/// personal data known to this crate, or enable categories individually.
+///
+/// ## Example
+///
+/// ```
+/// # use xmp_toolkit::RedactionProfile;
+/// // Keep the owner's name, but remove everything else.
+/// let profile = RedactionProfile::default()
+///     .location()
+///     .serial_numbers()
+///     .face_regions();
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RedactionProfile {
+    location: bool,
+    serial_numbers: bool,
+    owner_names: bool,
+    face_regions: bool,
+    properties: Vec<(String, String)>,
+}
+
+impl RedactionProfile {
+    /// Creates a profile that removes every category of personal data
+    /// supported by this type.
+    ///
+    /// This is appropriate before publishing an image.
+    pub fn personal_data() -> Self {
+        Self::default()
+            .location()
+            .serial_numbers()
+            .owner_names()
+            .face_regions()
+    }
+
+    /// Remove the location where the image was captured.
+    ///
+    /// This removes all `exif:GPS*` properties along with the detailed
+    /// location fields `Iptc4xmpCore:Location`, `Iptc4xmpExt:LocationCreated`,
+    /// `Iptc4xmpExt:LocationShown`, and `photoshop:City`. The state and
+    /// country are kept.
+    pub fn location(mut self) -> Self {
+        self.location = true;
+        self
+    }
+
+    /// Remove the serial numbers of the camera body and lens.
+    ///
+    /// This removes `aux:SerialNumber`, `aux:LensSerialNumber`,
+    /// `exifEX:BodySerialNumber`, and `exifEX:LensSerialNumber`.
+    pub fn serial_numbers(mut self) -> Self {
+        self.serial_numbers = true;
+        self
+    }
+
+    /// Remove the name of the camera's owner.
+    ///
+    /// This removes `aux:OwnerName` and `exifEX:CameraOwnerName`.
+    pub fn owner_names(mut self) -> Self {
+        self.owner_names = true;
+        self
+    }
+
+    /// Remove regions that identify people in the image.
+    ///
+    /// This removes the items of `mwg-rs:Regions/mwg-rs:RegionList` whose
+    /// `mwg-rs:Type` is `Face`, and the Windows Photo Gallery people tags
+    /// in `MP:RegionInfo`. Other MWG regions (such as focus areas) are
+    /// kept.
+    pub fn face_regions(mut self) -> Self {
+        self.face_regions = true;
+        self
+    }
+
+    /// Also remove the top-level property `name` in namespace
+    /// `namespace`.
+    pub fn property(mut self, namespace: &str, name: &str) -> Self {
+        self.properties
+            .push((namespace.to_owned(), name.to_owned()));
+        self
+    }
+}
+
+/// A node removed by [`XmpMeta::redact`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct RedactedProperty {
+    /// The namespace URI of the top-level property that contained the
+    /// node.
+    pub namespace: String,
+
+    /// The path of the removed node.
+    ///
+    /// This is usually the name of a top-level property. For face regions,
+    /// it is the path of the array item that was removed.
+    pub path: String,
+}
+
+/// The outcome of [`XmpMeta::redact`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct RedactionReport {
+    /// The nodes that were removed.
+    pub removed: Vec<RedactedProperty>,
+}
+
+pub(crate) fn redact(meta: &mut XmpMeta, profile: &RedactionProfile) -> XmpResult<RedactionReport> {
+    let mut staged = meta.stage();
+    let mut report = RedactionReport::default();
+
+    for (ns, name) in meta.top_level_properties() {
+        if should_remove(profile, &ns, &name) {
+            staged.delete_property(&ns, &name)?;
+            report.removed.push(RedactedProperty {
+                namespace: ns,
+                path: name,
+            });
+        }
+    }
+
+    if profile.face_regions {
+        remove_face_regions(&mut staged, &mut report)?;
+    }
+
+    meta.commit_staged(staged);
+    Ok(report)
+}
+
+fn should_remove(profile: &RedactionProfile, ns: &str, name: &str) -> bool {
+    let listed = |list: &[(&str, &str)]| list.iter().any(|&(n, p)| n == ns && p == name);
+
+    (profile.location && ns == xmp_ns::EXIF && name.starts_with("GPS"))
+        || (profile.location && listed(LOCATION_PROPERTIES))
+        || (profile.serial_numbers && listed(SERIAL_NUMBER_PROPERTIES))
+        || (profile.owner_names && listed(OWNER_NAME_PROPERTIES))
+        || (profile.face_regions && ns == xmp_ns::MICROSOFT_PHOTO && name == "RegionInfo")
+        || profile.properties.iter().any(|(n, p)| n == ns && p == name)
+}
+
+fn remove_face_regions(meta: &mut XmpMeta, report: &mut RedactionReport) -> XmpResult<()> {
+    let ns = xmp_ns::MWG_REGIONS;
+    if !meta.contains_property(ns, "Regions") {
+        return Ok(());
+    }
+
+    let list = XmpMeta::compose_struct_field_path(ns, "Regions", ns, "RegionList")?;
+    let len = meta.array_len(ns, &list) as i32;
+
+    // Remove items from the end so that the remaining indices (and the
+    // paths reported for earlier items) stay valid.
+    let mut removed = Vec::new();
+    for index in (1..=len).rev() {
+        let item = XmpMeta::compose_array_item_path(ns, &list, index)?;
+        let item_type = XmpMeta::compose_struct_field_path(ns, &item, ns, "Type")?;
+
+        if meta
+            .property(ns, &item_type)
+            .is_some_and(|t| t.value == "Face")
+        {
+            meta.delete_property(ns, &item)?;
+            removed.push(RedactedProperty {
+                namespace: ns.to_owned(),
+                path: item,
+            });
+        }
+    }
+
+    report.removed.extend(removed.into_iter().rev());
+
+    if len > 0 && meta.array_len(ns, &list) == 0 {
+        meta.delete_property(ns, "Regions")?;
+    }
+
+    Ok(())
+}
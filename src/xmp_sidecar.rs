@@ -0,0 +1,344 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Contains functions for reading and writing `.xmp` sidecar files and for
+//! keeping the XMP embedded in a file in sync with its sidecar.
+//!
+//! Applications that can not (or prefer not to) write into a file, such as
+//! raw image editors, store metadata in a sidecar file with the same name
+//! and a `.xmp` extension. [`Sidecar`] reads and writes such a file.
+//! [`sync`] reconciles the two copies: it decides which copy is newer by
+//! comparing their `xmp:MetadataDate` values, combines them according to a
+//! [`SyncPolicy`], and writes the result back to both places.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{
+    xmp_ns, OpenFileOptions, ToStringOptions, XmpDateTime, XmpError, XmpErrorType, XmpFile,
+    XmpMeta, XmpResult,
+};
+
+/// Describes how [`sync`] combines the embedded and sidecar XMP.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SyncPolicy {
+    /// The newer copy replaces the older copy.
+    #[default]
+    NewerWins,
+
+    /// Each top-level property of the newer copy replaces the same property
+    /// in the older copy; properties that exist only in the older copy are
+    /// kept.
+    ///
+    /// Without a common ancestor, a property that was deleted from the newer
+    /// copy can not be told apart from one that was added to the older copy,
+    /// so deletions are not propagated.
+    MergeNewerWins,
+
+    /// The sidecar XMP replaces the embedded XMP, regardless of age.
+    SidecarWins,
+
+    /// The embedded XMP replaces the sidecar XMP, regardless of age.
+    EmbeddedWins,
+}
+
+/// Identifies one of the two copies of XMP reconciled by [`sync`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SyncSide {
+    /// The XMP embedded in the file.
+    Embedded,
+
+    /// The XMP in the `.xmp` sidecar file.
+    Sidecar,
+}
+
+/// Describes the outcome of [`sync`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct SyncReport {
+    /// The path of the sidecar file.
+    pub sidecar_path: PathBuf,
+
+    /// The copy that was found to be newer, or `None` if neither file
+    /// contained XMP.
+    ///
+    /// A copy without `xmp:MetadataDate` is considered older than one with
+    /// it. If both copies have the same date (or neither has one), the
+    /// sidecar is considered newer, since applications that write sidecars
+    /// treat them as authoritative.
+    pub newer: Option<SyncSide>,
+
+    /// `true` if the XMP embedded in the file was updated.
+    pub embedded_written: bool,
+
+    /// `true` if the sidecar file was created or updated.
+    pub sidecar_written: bool,
+
+    /// The reconciled XMP.
+    pub xmp: XmpMeta,
+}
+
+/// The `.xmp` sidecar file of an asset, such as a camera raw file.
+///
+/// ## Example
+///
+/// ```no_run
+/// # use xmp_toolkit::{xmp_ns, xmp_sidecar::Sidecar, XmpMeta};
+/// # fn example() -> xmp_toolkit::XmpResult<()> {
+/// let sidecar = Sidecar::find("photos/IMG_0001.CR2");
+///
+/// let mut xmp = sidecar.read()?.unwrap_or(XmpMeta::new()?);
+/// xmp.set_property(xmp_ns::XMP, "Rating", &"5".into())?;
+/// sidecar.write(&xmp)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Sidecar {
+    path: PathBuf,
+}
+
+impl Sidecar {
+    /// Returns the sidecar file for the asset at `asset_path`.
+    ///
+    /// The conventional name is the asset's name with its extension
+    /// replaced by `xmp` (see [`sidecar_path`]). Some applications instead
+    /// append `.xmp` to the full name (`IMG_0001.CR2.xmp`); if only such a
+    /// file exists, it is used. Otherwise, the conventional name is used,
+    /// whether or not the file exists yet.
+    pub fn find<P: AsRef<Path>>(asset_path: P) -> Self {
+        let asset_path = asset_path.as_ref();
+        let conventional = sidecar_path(asset_path);
+
+        if !conventional.exists() && asset_path.extension().is_some() {
+            let mut appended = asset_path.as_os_str().to_owned();
+            appended.push(".xmp");
+            let appended = PathBuf::from(appended);
+
+            if appended.exists() {
+                return Self { path: appended };
+            }
+        }
+
+        Self { path: conventional }
+    }
+
+    /// Returns the sidecar file at `path`, regardless of its name.
+    pub fn at<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Returns the path of the sidecar file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns `true` if the sidecar file exists.
+    pub fn exists(&self) -> bool {
+        self.path.exists()
+    }
+
+    /// Reads and parses the sidecar file.
+    ///
+    /// The file may contain a complete XMP packet or just an `x:xmpmeta`
+    /// or `rdf:RDF` element. Returns `None` if the file doesn't exist.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::ReadError`] if the file
+    /// exists but can't be read, or a parse error if it isn't valid XMP.
+    pub fn read(&self) -> XmpResult<Option<XmpMeta>> {
+        read_sidecar(&self.path)
+    }
+
+    /// Writes `meta` to the sidecar file, creating or replacing it.
+    ///
+    /// The XMP is written without a packet wrapper, as is conventional for
+    /// sidecar files. Use [`Sidecar::write_with_options`] to choose other
+    /// serialization options.
+    pub fn write(&self, meta: &XmpMeta) -> XmpResult<()> {
+        self.write_with_options(meta, ToStringOptions::default().omit_packet_wrapper())
+    }
+
+    /// Writes `meta` to the sidecar file using the given serialization
+    /// options, creating or replacing it.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::WriteError`] if the file
+    /// can't be written, or an error from
+    /// [`XmpMeta::to_string_with_options`].
+    pub fn write_with_options(&self, meta: &XmpMeta, options: ToStringOptions) -> XmpResult<()> {
+        let xmp = meta.to_string_with_options(options)?;
+        fs::write(&self.path, xmp).map_err(|e| io_error(XmpErrorType::WriteError, e))
+    }
+}
+
+/// Returns the path of the sidecar file for `path`.
+///
+/// This is `path` with its extension replaced by (or, if it has none,
+/// extended with) `xmp`.
+pub fn sidecar_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    path.as_ref().with_extension("xmp")
+}
+
+/// Reconciles the XMP embedded in the file at `path` with the XMP in its
+/// sidecar file (see [`sidecar_path`]).
+///
+/// The two copies are combined according to `policy` and the result is
+/// written to both the sidecar and, if the file format supports it, the
+/// file itself. A copy that already matches the result is not rewritten.
+/// If the sidecar does not exist, it is created.
+///
+/// The file is opened with [`OpenFileOptions::for_update`]. If the file can
+/// only be opened for reading, the embedded XMP is still used as input, but
+/// is not updated.
+///
+/// ## Errors
+///
+/// Returns an error if the file can not be opened, if the sidecar exists
+/// but can not be read or parsed, or if either copy can not be written.
+/// If the sidecar is written but the file is not, the sidecar is left
+/// updated.
+pub fn sync<P: AsRef<Path>>(path: P, policy: SyncPolicy) -> XmpResult<SyncReport> {
+    let path = path.as_ref();
+    let sidecar_path = sidecar_path(path);
+
+    let mut f = XmpFile::new()?;
+    let writable = match f.open_file(path, OpenFileOptions::default().for_update()) {
+        Ok(()) => true,
+        Err(_) => {
+            f.open_file(path, OpenFileOptions::default().for_read())?;
+            false
+        }
+    };
+
+    let embedded = f.xmp();
+    let sidecar = read_sidecar(&sidecar_path)?;
+
+    let newer = match (&embedded, &sidecar) {
+        (None, None) => None,
+        (Some(_), None) => Some(SyncSide::Embedded),
+        (None, Some(_)) => Some(SyncSide::Sidecar),
+        (Some(embedded), Some(sidecar)) => {
+            if metadata_date_key(embedded) > metadata_date_key(sidecar) {
+                Some(SyncSide::Embedded)
+            } else {
+                Some(SyncSide::Sidecar)
+            }
+        }
+    };
+
+    let xmp = match (embedded.as_ref(), sidecar.as_ref()) {
+        (None, None) => XmpMeta::new()?,
+        (Some(only), None) | (None, Some(only)) => only.clone(),
+        (Some(embedded), Some(sidecar)) => match (policy, newer) {
+            (SyncPolicy::EmbeddedWins, _) | (SyncPolicy::NewerWins, Some(SyncSide::Embedded)) => {
+                embedded.clone()
+            }
+            (SyncPolicy::MergeNewerWins, Some(SyncSide::Embedded)) => merge(sidecar, embedded)?,
+            (SyncPolicy::MergeNewerWins, _) => merge(embedded, sidecar)?,
+            _ => sidecar.clone(),
+        },
+    };
+
+    let xmp_str = canonical(&xmp)?;
+
+    let sidecar_written = match &sidecar {
+        Some(sidecar) if canonical(sidecar)? == xmp_str => false,
+        _ => {
+            Sidecar::at(&sidecar_path).write(&xmp)?;
+            true
+        }
+    };
+
+    let embedded_written = match &embedded {
+        Some(embedded) if canonical(embedded)? == xmp_str => false,
+        _ if writable && f.can_put_xmp(&xmp) => {
+            f.put_xmp(&xmp)?;
+            true
+        }
+        _ => false,
+    };
+
+    f.close()?;
+
+    Ok(SyncReport {
+        sidecar_path,
+        newer,
+        embedded_written,
+        sidecar_written,
+        xmp,
+    })
+}
+
+fn read_sidecar(path: &Path) -> XmpResult<Option<XmpMeta>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let xmp = fs::read_to_string(path).map_err(|e| io_error(XmpErrorType::ReadError, e))?;
+    XmpMeta::from_str(&xmp).map(Some)
+}
+
+/// Copies each top-level property of `newer` over `older`.
+fn merge(older: &XmpMeta, newer: &XmpMeta) -> XmpResult<XmpMeta> {
+    let mut merged = older.clone();
+
+    for (ns, name) in newer.top_level_properties() {
+        newer.duplicate_subtree(&mut merged, &ns, &name, &name)?;
+    }
+
+    Ok(merged)
+}
+
+/// Returns a key that orders metadata dates chronologically, or `None` if
+/// the date is missing.
+fn metadata_date_key(meta: &XmpMeta) -> Option<(i32, i32, i32, i32, i32, i32, i32)> {
+    let mut dt: XmpDateTime = meta.property_date(xmp_ns::XMP, "MetadataDate")?.value;
+    dt.convert_to_utc().ok()?;
+
+    let date = dt.date.unwrap_or_default();
+    let time = dt.time.unwrap_or_default();
+
+    Some((
+        date.year,
+        date.month,
+        date.day,
+        time.hour,
+        time.minute,
+        time.second,
+        time.nanosecond,
+    ))
+}
+
+fn canonical(meta: &XmpMeta) -> XmpResult<String> {
+    meta.to_string_with_options(
+        ToStringOptions::default()
+            .omit_packet_wrapper()
+            .use_canonical_format()
+            .omit_all_formatting(),
+    )
+}
+
+fn io_error(error_type: XmpErrorType, err: std::io::Error) -> XmpError {
+    XmpError {
+        error_type,
+        debug_message: err.to_string(),
+    }
+}
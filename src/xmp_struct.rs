@@ -0,0 +1,82 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use crate::{XmpMeta, XmpResult};
+
+/// Maps a Rust struct to an XMP struct property.
+///
+/// You may implement this trait by hand, but it is typically derived.
+/// Enable crate feature `derive` to use `#[derive(XmpStruct)]`:
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # mod example {
+/// use xmp_toolkit::{xmp_ns, XmpMeta, XmpStruct};
+///
+/// #[derive(XmpStruct)]
+/// #[xmp(
+///     ns = "http://iptc.org/std/Iptc4xmpCore/1.0/xmlns/",
+///     name = "CreatorContactInfo"
+/// )]
+/// struct ContactInfo {
+///     #[xmp(name = "CiAdrCity")]
+///     city: String,
+///
+///     #[xmp(name = "CiEmailWork")]
+///     email: Option<String>,
+/// }
+///
+/// #[derive(XmpStruct)]
+/// #[xmp(ns = "http://ns.example.com/settings/1.0/", name = "Settings")]
+/// struct Settings {
+///     rating: i32,
+///     approved: Option<bool>,
+/// }
+///
+/// fn copy_contact(from: &XmpMeta, to: &mut XmpMeta) {
+///     if let Some(contact) = ContactInfo::read_from(from) {
+///         contact.write_to(to).unwrap();
+///     }
+/// }
+/// # }
+/// ```
+///
+/// The `#[xmp(...)]` attribute on the struct gives the namespace URI and
+/// path of the XMP struct property. The `#[xmp(...)]` attribute on each
+/// field may give the namespace URI (`ns`, defaulting to the struct's
+/// namespace) and name (`name`, defaulting to the Rust field name) of the
+/// corresponding XMP struct field.
+///
+/// Fields may be of any type `T` that implements [`XmpPropertyType`], or
+/// of type `Option<T>`. Values are converted with
+/// [`XmpPropertyType::from_xmp_str`] and
+/// [`XmpPropertyType::to_xmp_string`]. Fields of type `Option<T>` are
+/// optional in the XMP; all others are required.
+///
+/// [`XmpPropertyType`]: crate::XmpPropertyType
+/// [`XmpPropertyType::from_xmp_str`]: crate::XmpPropertyType::from_xmp_str
+/// [`XmpPropertyType::to_xmp_string`]: crate::XmpPropertyType::to_xmp_string
+pub trait XmpStruct: Sized {
+    /// Reads the struct from `meta`.
+    ///
+    /// Returns `None` if the struct property does not exist, is not a
+    /// struct, is missing any required field, or has a field whose value
+    /// can not be interpreted as the type of that field.
+    fn read_from(meta: &XmpMeta) -> Option<Self>;
+
+    /// Writes the struct to `meta`, replacing any existing property of the
+    /// same name.
+    ///
+    /// Optional fields with value `None` are omitted.
+    fn write_to(&self, meta: &mut XmpMeta) -> XmpResult<()>;
+}
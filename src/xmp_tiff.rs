@@ -0,0 +1,413 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Contains functions for reading and writing the XMP of each page of a
+//! multi-page TIFF file.
+//!
+//! A TIFF file stores its pages (images) in a chain of image file
+//! directories (IFDs), each of which may have its own XMP packet in the
+//! `XMLPacket` tag (700). [`XmpFile`] only reaches the XMP of the first
+//! page, the primary image. [`TiffPages`] reaches the XMP of every page, as
+//! attached by scanned-document pipelines, for example.
+//!
+//! Updating a page's XMP appends the new packet to the end of the file and
+//! points the page's `XMLPacket` tag at it; if the page had no XMP, a copy
+//! of its IFD with the tag added is appended as well. Image data is never
+//! moved, so offsets elsewhere in the file stay valid. The space occupied by
+//! the previous packet is not reclaimed.
+//!
+//! BigTIFF files are not supported.
+//!
+//! [`XmpFile`]: crate::XmpFile
+
+use std::{
+    fs,
+    ops::Range,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{
+    xmp_file_handler::{self, bad_file},
+    PacketPadding, XmpError, XmpErrorType, XmpMeta, XmpResult,
+};
+
+const XML_PACKET: u16 = 700;
+
+// The IFD entry types that may hold an XMP packet.
+const BYTE: u16 = 1;
+const UNDEFINED: u16 = 7;
+
+// The number of IFDs followed before the file is considered damaged.
+const MAX_PAGES: usize = 65536;
+
+/// The pages of a TIFF file and their XMP.
+///
+/// ## Example
+///
+/// ```no_run
+/// use xmp_toolkit::{xmp_ns, xmp_tiff::TiffPages};
+///
+/// let mut pages = TiffPages::open("scan.tif").unwrap();
+///
+/// for page in 0..pages.len() {
+///     let mut meta = pages.xmp(page).unwrap().unwrap_or_default();
+///     meta.set_property(xmp_ns::DC, "source", &"Scanner 3".into())
+///         .unwrap();
+///     pages.put_xmp(page, &meta).unwrap();
+/// }
+///
+/// pages.close().unwrap();
+/// ```
+pub struct TiffPages {
+    // `None` if the file was opened from bytes.
+    path: Option<PathBuf>,
+
+    bytes: Vec<u8>,
+    big_endian: bool,
+    pages: Vec<Page>,
+    updated: bool,
+}
+
+struct Page {
+    // The offset of the page's IFD.
+    ifd: usize,
+
+    // The offset of the pointer to the page's IFD: in the file header for
+    // the first page, and at the end of the previous IFD for the others.
+    pointer: usize,
+
+    // The location of the page's XMP packet, if any.
+    xmp: Option<Range<usize>>,
+}
+
+impl TiffPages {
+    /// Reads the TIFF file at `path`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::ReadError`] if the file
+    /// can't be read, or of type [`XmpErrorType::BadFileFormat`] if it
+    /// isn't a TIFF file or its IFDs are damaged.
+    pub fn open<P: AsRef<Path>>(path: P) -> XmpResult<Self> {
+        let bytes = fs::read(path.as_ref()).map_err(|err| XmpError {
+            error_type: XmpErrorType::ReadError,
+            debug_message: err.to_string(),
+        })?;
+
+        let mut pages = Self::from_bytes(&bytes)?;
+        pages.path = Some(path.as_ref().to_path_buf());
+        Ok(pages)
+    }
+
+    /// Reads a TIFF file from memory.
+    ///
+    /// Use [`TiffPages::into_bytes`] to retrieve the updated file.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::BadFileFormat`] if `bytes`
+    /// isn't a TIFF file or its IFDs are damaged.
+    pub fn from_bytes(bytes: &[u8]) -> XmpResult<Self> {
+        let big_endian = match bytes.get(0..4) {
+            Some(b"II*\0") => false,
+            Some(b"MM\0*") => true,
+            Some(b"II+\0" | b"MM\0+") => return Err(bad_tiff("BigTIFF is not supported")),
+            _ => return Err(bad_tiff("Not a TIFF file")),
+        };
+
+        let mut pages = Self {
+            path: None,
+            bytes: bytes.to_vec(),
+            big_endian,
+            pages: Vec::new(),
+            updated: false,
+        };
+
+        pages.pages = pages.read_pages()?;
+        Ok(pages)
+    }
+
+    /// Returns the number of pages.
+    pub fn len(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Returns `true` if the file has no pages.
+    pub fn is_empty(&self) -> bool {
+        self.pages.is_empty()
+    }
+
+    /// Returns the XMP packet of page `page` (counting from 0) exactly as
+    /// it is stored in the file, or `None` if the page has no XMP.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::BadIndex`] if there is no
+    /// such page.
+    pub fn raw_xmp_packet(&self, page: usize) -> XmpResult<Option<&[u8]>> {
+        Ok(self.page(page)?.xmp.clone().map(|range| &self.bytes[range]))
+    }
+
+    /// Returns the XMP of page `page` (counting from 0), or `None` if the
+    /// page has no XMP.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::BadIndex`] if there is no
+    /// such page, or a parse error if its XMP isn't valid.
+    pub fn xmp(&self, page: usize) -> XmpResult<Option<XmpMeta>> {
+        self.raw_xmp_packet(page)?
+            .map(|packet| XmpMeta::from_str(&String::from_utf8_lossy(packet)))
+            .transpose()
+    }
+
+    /// Returns the XMP of every page, in page order.
+    ///
+    /// ## Errors
+    ///
+    /// Returns a parse error if the XMP of any page isn't valid.
+    pub fn all_xmp(&self) -> XmpResult<Vec<Option<XmpMeta>>> {
+        (0..self.len()).map(|page| self.xmp(page)).collect()
+    }
+
+    /// Replaces (or adds) the XMP of page `page` (counting from 0).
+    ///
+    /// The file is not written until [`TiffPages::close`] is called.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::BadIndex`] if there is no
+    /// such page, or of type [`XmpErrorType::BadFileFormat`] if the updated
+    /// file would be too large for TIFF's 32-bit offsets.
+    pub fn put_xmp(&mut self, page: usize, meta: &XmpMeta) -> XmpResult<()> {
+        let packet = PacketPadding::Default.serialize(meta)?;
+        let (ifd, pointer) = {
+            let page = self.page(page)?;
+            (page.ifd, page.pointer)
+        };
+
+        let mut bytes = self.bytes.clone();
+        let packet_offset = self.append(&mut bytes, packet.as_bytes())?;
+        let packet_len =
+            u32::try_from(packet.len()).map_err(|_| bad_tiff("XMP packet is too large"))?;
+
+        let count = usize::from(self.u16_at(ifd)?);
+        let entries = ifd + 2..ifd + 2 + count * 12;
+
+        let existing = entries
+            .clone()
+            .step_by(12)
+            .find(|&entry| self.u16_at(entry).ok() == Some(XML_PACKET));
+
+        if let Some(entry) = existing {
+            self.put_u16(&mut bytes, entry + 2, UNDEFINED);
+            self.put_u32(&mut bytes, entry + 4, packet_len);
+            self.put_u32(&mut bytes, entry + 8, packet_offset);
+        } else {
+            let mut new_entry = [0; 12];
+            self.put_u16(&mut new_entry, 0, XML_PACKET);
+            self.put_u16(&mut new_entry, 2, UNDEFINED);
+            self.put_u32(&mut new_entry, 4, packet_len);
+            self.put_u32(&mut new_entry, 8, packet_offset);
+
+            let mut new_entries: Vec<&[u8]> = self.bytes[entries.clone()].chunks(12).collect();
+            let position = new_entries
+                .iter()
+                .position(|entry| self.u16_from(entry) > XML_PACKET)
+                .unwrap_or(new_entries.len());
+            new_entries.insert(position, &new_entry);
+
+            let new_count = u16::try_from(new_entries.len())
+                .map_err(|_| bad_tiff("IFD has too many entries"))?;
+
+            let mut new_ifd = Vec::with_capacity(new_entries.len() * 12 + 6);
+            new_ifd.extend_from_slice(&[0; 2]);
+            self.put_u16(&mut new_ifd, 0, new_count);
+            for entry in new_entries {
+                new_ifd.extend_from_slice(entry);
+            }
+            new_ifd.extend_from_slice(&self.bytes[entries.end..entries.end + 4]);
+
+            let ifd_offset = self.append(&mut bytes, &new_ifd)?;
+            self.put_u32(&mut bytes, pointer, ifd_offset);
+        }
+
+        self.bytes = bytes;
+        self.pages = self.read_pages()?;
+        self.updated = true;
+        Ok(())
+    }
+
+    /// Writes the file if it was updated and opened from a path.
+    ///
+    /// As with [`XmpFile::close`](crate::XmpFile::close), the new content
+    /// is written to a temporary file in the same folder, which then
+    /// replaces the original.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error of type [`XmpErrorType::WriteError`] if the file
+    /// can't be written.
+    pub fn close(&mut self) -> XmpResult<()> {
+        if !self.updated {
+            return Ok(());
+        }
+
+        self.updated = false;
+
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let temp_path = xmp_file_handler::temp_path(path, None);
+
+        xmp_file_handler::write_temp_file(&temp_path, &self.bytes)
+            .and_then(|()| xmp_file_handler::replace_file(&temp_path, path, false))
+            .map_err(|err| {
+                let _ = fs::remove_file(&temp_path);
+                XmpError {
+                    error_type: XmpErrorType::WriteError,
+                    debug_message: err.to_string(),
+                }
+            })
+    }
+
+    /// Returns the content of the file, including any update made via
+    /// [`TiffPages::put_xmp`].
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    fn page(&self, page: usize) -> XmpResult<&Page> {
+        self.pages.get(page).ok_or_else(|| XmpError {
+            error_type: XmpErrorType::BadIndex,
+            debug_message: format!("TIFF file has no page {page}"),
+        })
+    }
+
+    fn read_pages(&self) -> XmpResult<Vec<Page>> {
+        let mut pages = Vec::new();
+        let mut pointer = 4;
+
+        loop {
+            let ifd = self.u32_at(pointer)? as usize;
+            if ifd == 0 {
+                break;
+            }
+
+            if pages.len() == MAX_PAGES || pages.iter().any(|page: &Page| page.ifd == ifd) {
+                return Err(bad_tiff("IFDs form a loop"));
+            }
+
+            let count = usize::from(self.u16_at(ifd)?);
+            let entries = ifd + 2..ifd + 2 + count * 12;
+
+            let mut xmp = None;
+            for entry in entries.clone().step_by(12) {
+                if self.u16_at(entry)? != XML_PACKET {
+                    continue;
+                }
+
+                if !matches!(self.u16_at(entry + 2)?, BYTE | UNDEFINED) {
+                    return Err(bad_tiff("XMLPacket tag has the wrong type"));
+                }
+
+                let len = self.u32_at(entry + 4)? as usize;
+                let start = if len <= 4 {
+                    entry + 8
+                } else {
+                    self.u32_at(entry + 8)? as usize
+                };
+
+                let range = start..start.saturating_add(len);
+                if range.end > self.bytes.len() {
+                    return Err(bad_tiff("XMP packet extends past the end of the file"));
+                }
+
+                xmp = Some(range);
+            }
+
+            pages.push(Page { ifd, pointer, xmp });
+            pointer = entries.end;
+        }
+
+        Ok(pages)
+    }
+
+    /// Appends `data` to `bytes` at an even offset, as TIFF requires, and
+    /// returns that offset.
+    fn append(&self, bytes: &mut Vec<u8>, data: &[u8]) -> XmpResult<u32> {
+        if bytes.len() % 2 == 1 {
+            bytes.push(0);
+        }
+
+        let offset = bytes.len();
+        bytes.extend_from_slice(data);
+
+        u32::try_from(bytes.len())
+            .map(|_| offset as u32)
+            .map_err(|_| bad_tiff("File is too large for TIFF"))
+    }
+
+    fn u16_at(&self, pos: usize) -> XmpResult<u16> {
+        self.bytes
+            .get(pos..pos + 2)
+            .map(|b| self.u16_from(b))
+            .ok_or_else(|| bad_tiff("IFD extends past the end of the file"))
+    }
+
+    fn u32_at(&self, pos: usize) -> XmpResult<u32> {
+        let b = self
+            .bytes
+            .get(pos..pos + 4)
+            .ok_or_else(|| bad_tiff("IFD extends past the end of the file"))?;
+        let b = [b[0], b[1], b[2], b[3]];
+
+        Ok(if self.big_endian {
+            u32::from_be_bytes(b)
+        } else {
+            u32::from_le_bytes(b)
+        })
+    }
+
+    fn u16_from(&self, b: &[u8]) -> u16 {
+        if self.big_endian {
+            u16::from_be_bytes([b[0], b[1]])
+        } else {
+            u16::from_le_bytes([b[0], b[1]])
+        }
+    }
+
+    fn put_u16(&self, bytes: &mut [u8], pos: usize, value: u16) {
+        let b = if self.big_endian {
+            value.to_be_bytes()
+        } else {
+            value.to_le_bytes()
+        };
+        bytes[pos..pos + 2].copy_from_slice(&b);
+    }
+
+    fn put_u32(&self, bytes: &mut [u8], pos: usize, value: u32) {
+        let b = if self.big_endian {
+            value.to_be_bytes()
+        } else {
+            value.to_le_bytes()
+        };
+        bytes[pos..pos + 4].copy_from_slice(&b);
+    }
+}
+
+fn bad_tiff(message: &str) -> XmpError {
+    bad_file("TIFF", message)
+}
@@ -0,0 +1,169 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+// Reads and writes Vorbis comments, the `KEY=value` metadata of FLAC and
+// Ogg (Vorbis, Opus) files.
+//
+// A comment list is a little-endian, length-prefixed vendor string,
+// followed by a count and that many length-prefixed comments. Ogg codecs
+// may put further data after the list; it is kept as is.
+//
+// See https://xiph.org/vorbis/doc/v-comment.html.
+
+use std::ops::Range;
+
+use crate::{xmp_ns, XmpMeta, XmpResult, XmpValue};
+
+pub(crate) struct Comments {
+    // The vendor string, including its length.
+    vendor: Range<usize>,
+
+    // Each comment, without its length.
+    comments: Vec<Range<usize>>,
+
+    // The end of the comment list.
+    end: usize,
+}
+
+impl Comments {
+    /// Parses the comment list at the start of `data`.
+    pub(crate) fn parse(data: &[u8]) -> Option<Self> {
+        let vendor_len = read_u32(data, 0)?;
+        let vendor = 0..4usize.checked_add(vendor_len)?;
+
+        let count = read_u32(data, vendor.end)?;
+        let mut pos = vendor.end + 4;
+        let mut comments = Vec::new();
+
+        for _ in 0..count {
+            let len = read_u32(data, pos)?;
+            let comment = pos + 4..(pos + 4).checked_add(len)?;
+            if comment.end > data.len() {
+                return None;
+            }
+
+            pos = comment.end;
+            comments.push(comment);
+        }
+
+        Some(Self {
+            vendor,
+            comments,
+            end: pos,
+        })
+    }
+
+    /// Returns the location of the value of the first comment named `key`
+    /// (compared case-insensitively).
+    pub(crate) fn find(&self, data: &[u8], key: &str) -> Option<Range<usize>> {
+        self.comments
+            .iter()
+            .find(|c| has_key(&data[(*c).clone()], key))
+            .map(|c| c.start + key.len() + 1..c.end)
+    }
+
+    /// Returns a copy of `data` in which the comments named `key` are
+    /// replaced by a single comment with `value`.
+    pub(crate) fn replace(&self, data: &[u8], key: &str, value: &[u8]) -> Option<Vec<u8>> {
+        let kept: Vec<&Range<usize>> = self
+            .comments
+            .iter()
+            .filter(|c| !has_key(&data[(*c).clone()], key))
+            .collect();
+
+        let new_len = u32::try_from(key.len() + 1 + value.len()).ok()?;
+        let count = u32::try_from(kept.len() + 1).ok()?;
+
+        let mut out = Vec::with_capacity(data.len() + value.len());
+        out.extend_from_slice(&data[self.vendor.clone()]);
+        out.extend_from_slice(&count.to_le_bytes());
+
+        for comment in kept {
+            out.extend_from_slice(&data[comment.start - 4..comment.end]);
+        }
+
+        out.extend_from_slice(&new_len.to_le_bytes());
+        out.extend_from_slice(key.as_bytes());
+        out.push(b'=');
+        out.extend_from_slice(value);
+
+        out.extend_from_slice(&data[self.end..]);
+        Some(out)
+    }
+
+    /// Adds Dublin Core properties derived from the comments to `meta`,
+    /// unless `meta` already has them. Returns `true` if any were added.
+    ///
+    /// See [`OpenFileOptions::import_vorbis_comments`] for the mapping.
+    ///
+    /// [`OpenFileOptions::import_vorbis_comments`]: crate::OpenFileOptions::import_vorbis_comments
+    pub(crate) fn import(&self, data: &[u8], meta: &mut XmpMeta) -> XmpResult<bool> {
+        let mut imported = false;
+
+        for (key, property) in [
+            ("TITLE", "title"),
+            ("DESCRIPTION", "description"),
+            ("COPYRIGHT", "rights"),
+        ] {
+            if meta.contains_property(xmp_ns::DC, property) {
+                continue;
+            }
+
+            if let Some(value) = self.values(data, key).next() {
+                meta.set_localized_text(xmp_ns::DC, property, None, "x-default", &value)?;
+                imported = true;
+            }
+        }
+
+        for (key, property, ordered) in [
+            ("ARTIST", "creator", true),
+            ("DATE", "date", true),
+            ("ORGANIZATION", "publisher", false),
+        ] {
+            if meta.contains_property(xmp_ns::DC, property) {
+                continue;
+            }
+
+            let array = XmpValue::from(property)
+                .set_is_array(true)
+                .set_is_ordered(ordered);
+
+            for value in self.values(data, key) {
+                meta.append_array_item(xmp_ns::DC, &array, &value.into())?;
+                imported = true;
+            }
+        }
+
+        Ok(imported)
+    }
+
+    /// Returns the values of the comments named `key`.
+    fn values<'a>(&'a self, data: &'a [u8], key: &'a str) -> impl Iterator<Item = String> + 'a {
+        self.comments
+            .iter()
+            .map(|c| &data[c.clone()])
+            .filter(move |c| has_key(c, key))
+            .map(move |c| String::from_utf8_lossy(&c[key.len() + 1..]).into_owned())
+    }
+}
+
+fn has_key(comment: &[u8], key: &str) -> bool {
+    comment.len() > key.len()
+        && comment[key.len()] == b'='
+        && comment[..key.len()].eq_ignore_ascii_case(key.as_bytes())
+}
+
+fn read_u32(data: &[u8], pos: usize) -> Option<usize> {
+    let bytes = data.get(pos..pos.checked_add(4)?)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+}
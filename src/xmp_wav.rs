@@ -0,0 +1,276 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+// Reads and writes the XMP of WAV files, and imports the production
+// fields of Broadcast Wave (BWF) files.
+//
+// This handler is only used when requested with
+// `OpenFileOptions::import_bwf_metadata`; otherwise the C++ XMP Toolkit
+// handles WAV files.
+//
+// A WAV file is a RIFF container whose chunks are padded to an even
+// length. The XMP packet is stored in a `_PMX` chunk, which is replaced in
+// place or added at the end of the container. The `bext` chunk (see EBU
+// Tech 3285) and the `iXML` chunk (see http://www.gallery.co.uk/ixml/)
+// are read but never modified.
+
+use std::{ops::Range, str::FromStr};
+
+use crate::{
+    xmp_file_handler::{bad_file, FileHandler},
+    xmp_ns, FileFormat, OpenFileOptions, XmpDateTime, XmpError, XmpMeta, XmpResult, XmpValue,
+};
+
+const RIFF: &[u8; 4] = b"RIFF";
+const WAVE: &[u8; 4] = b"WAVE";
+const XMP: &[u8; 4] = b"_PMX";
+const BEXT: &[u8; 4] = b"bext";
+const IXML: &[u8; 4] = b"iXML";
+
+// Offsets of the fixed-length fields of the `bext` chunk.
+const BEXT_DESCRIPTION: Range<usize> = 0..256;
+const BEXT_ORIGINATOR: Range<usize> = 256..288;
+const BEXT_ORIGINATION_DATE: Range<usize> = 320..330;
+const BEXT_ORIGINATION_TIME: Range<usize> = 330..338;
+
+pub(crate) struct WavHandler;
+
+impl FileHandler for WavHandler {
+    fn format(&self) -> FileFormat {
+        FileFormat::Wav
+    }
+
+    fn is_enabled(&self, flags: &OpenFileOptions) -> bool {
+        flags.imports_bwf_metadata()
+    }
+
+    fn check_format(&self, header: &[u8]) -> bool {
+        header.len() >= 12 && &header[0..4] == RIFF && &header[8..12] == WAVE
+    }
+
+    fn read_xmp(&self, file: &[u8]) -> XmpResult<Option<Range<usize>>> {
+        Ok(chunks(file)?
+            .0
+            .into_iter()
+            .find(|chunk| &chunk.fourcc == XMP)
+            .map(|chunk| chunk.data))
+    }
+
+    fn write_xmp(&self, file: &[u8], packet: &[u8]) -> XmpResult<Vec<u8>> {
+        let (chunks, riff_end) = chunks(file)?;
+
+        let mut xmp_chunk = Vec::with_capacity(packet.len() + 9);
+        push_chunk(&mut xmp_chunk, XMP, packet)?;
+
+        let mut out = Vec::with_capacity(file.len() + xmp_chunk.len());
+        out.extend_from_slice(&file[..12]);
+
+        let mut replaced = false;
+        for chunk in &chunks {
+            if &chunk.fourcc != XMP {
+                out.extend_from_slice(&file[chunk.range.clone()]);
+            } else if !replaced {
+                out.extend_from_slice(&xmp_chunk);
+                replaced = true;
+            }
+        }
+
+        if !replaced {
+            out.extend_from_slice(&xmp_chunk);
+        }
+
+        let riff_len = u32::try_from(out.len() - 8)
+            .map_err(|_| bad_wav("File is too large for the RIFF container"))?;
+        out[4..8].copy_from_slice(&riff_len.to_le_bytes());
+
+        // Keep anything after the end of the RIFF container.
+        out.extend_from_slice(&file[riff_end..]);
+        Ok(out)
+    }
+
+    fn imports_legacy(&self, _flags: &OpenFileOptions) -> bool {
+        true
+    }
+
+    fn import_legacy(&self, file: &[u8], meta: &mut XmpMeta) -> XmpResult<bool> {
+        let (chunks, _) = chunks(file)?;
+        let chunk_data = |fourcc: &[u8; 4]| {
+            chunks
+                .iter()
+                .find(|chunk| &chunk.fourcc == fourcc)
+                .map(|chunk| &file[chunk.data.clone()])
+        };
+
+        let mut imported = false;
+
+        // iXML comes first: its note is more specific than the BWF
+        // description.
+        if let Some(ixml) = chunk_data(IXML) {
+            let ixml = String::from_utf8_lossy(ixml);
+
+            for (element, property) in [
+                ("SCENE", "scene"),
+                ("TAPE", "tapeName"),
+                ("NOTE", "logComment"),
+            ] {
+                if let Some(value) = element_text(&ixml, element) {
+                    imported |= set_if_absent(meta, property, &value)?;
+                }
+            }
+
+            if let Some(take) = element_text(&ixml, "TAKE").and_then(|t| t.parse::<i32>().ok()) {
+                if !meta.contains_property(xmp_ns::DYNAMIC_MEDIA, "takeNumber") {
+                    meta.set_property_i32(xmp_ns::DYNAMIC_MEDIA, "takeNumber", &take.into())?;
+                    imported = true;
+                }
+            }
+        }
+
+        if let Some(bext) = chunk_data(BEXT) {
+            for (field, property) in [
+                (BEXT_DESCRIPTION, "logComment"),
+                (BEXT_ORIGINATOR, "engineer"),
+            ] {
+                if let Some(value) = bext_text(bext, field) {
+                    imported |= set_if_absent(meta, property, &value)?;
+                }
+            }
+
+            if let Some(date) = origination_date(bext) {
+                if !meta.contains_property(xmp_ns::DYNAMIC_MEDIA, "shotDate") {
+                    meta.set_property_date(xmp_ns::DYNAMIC_MEDIA, "shotDate", &date.into())?;
+                    imported = true;
+                }
+            }
+        }
+
+        Ok(imported)
+    }
+}
+
+struct Chunk {
+    fourcc: [u8; 4],
+
+    // The whole chunk, including its header and padding.
+    range: Range<usize>,
+
+    // The chunk's payload.
+    data: Range<usize>,
+}
+
+/// Returns the chunks of `file` and the end of the RIFF container.
+fn chunks(file: &[u8]) -> XmpResult<(Vec<Chunk>, usize)> {
+    if !WavHandler.check_format(file) {
+        return Err(bad_wav("Not a WAV file"));
+    }
+
+    let riff_len = u32::from_le_bytes([file[4], file[5], file[6], file[7]]) as usize;
+    let end = file.len().min(riff_len.saturating_add(8));
+
+    let mut chunks = Vec::new();
+    let mut pos = 12;
+
+    while pos + 8 <= end {
+        let fourcc = [file[pos], file[pos + 1], file[pos + 2], file[pos + 3]];
+        let len = u32::from_le_bytes([file[pos + 4], file[pos + 5], file[pos + 6], file[pos + 7]])
+            as usize;
+
+        let data = pos + 8..pos + 8 + len;
+        if data.end > end {
+            return Err(bad_wav("Chunk extends past the end of the file"));
+        }
+
+        let padded_end = (data.end + len % 2).min(end);
+        chunks.push(Chunk {
+            fourcc,
+            range: pos..padded_end,
+            data,
+        });
+
+        pos = padded_end;
+    }
+
+    Ok((chunks, end))
+}
+
+fn push_chunk(out: &mut Vec<u8>, fourcc: &[u8; 4], data: &[u8]) -> XmpResult<()> {
+    let len = u32::try_from(data.len()).map_err(|_| bad_wav("XMP packet is too large"))?;
+
+    out.extend_from_slice(fourcc);
+    out.extend_from_slice(&len.to_le_bytes());
+    out.extend_from_slice(data);
+
+    if data.len() % 2 == 1 {
+        out.push(0);
+    }
+
+    Ok(())
+}
+
+fn set_if_absent(meta: &mut XmpMeta, property: &str, value: &str) -> XmpResult<bool> {
+    if meta.contains_property(xmp_ns::DYNAMIC_MEDIA, property) {
+        return Ok(false);
+    }
+
+    meta.set_property(xmp_ns::DYNAMIC_MEDIA, property, &XmpValue::from(value))?;
+    Ok(true)
+}
+
+/// Returns a NUL-padded ASCII field of the `bext` chunk, if not empty.
+fn bext_text(bext: &[u8], field: Range<usize>) -> Option<String> {
+    let bytes = bext.get(field)?;
+    let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    let text = String::from_utf8_lossy(&bytes[..len]).trim().to_owned();
+    (!text.is_empty()).then_some(text)
+}
+
+/// Returns the origination date and time of the `bext` chunk.
+///
+/// They are stored as `yyyy:mm:dd` and `hh:mm:ss`, where the separators
+/// may be any of `-`, `_`, `:`, space, or `.`. The time is optional.
+fn origination_date(bext: &[u8]) -> Option<XmpDateTime> {
+    let normalize = |text: String, separator: char| -> String {
+        text.chars()
+            .map(|c| if "-_: .".contains(c) { separator } else { c })
+            .collect()
+    };
+
+    let date = normalize(bext_text(bext, BEXT_ORIGINATION_DATE)?, '-');
+    let date_time = match bext_text(bext, BEXT_ORIGINATION_TIME) {
+        Some(time) => format!("{date}T{}", normalize(time, ':')),
+        None => date,
+    };
+
+    XmpDateTime::from_str(&date_time).ok()
+}
+
+/// Returns the unescaped text of the first `element` in an iXML document,
+/// if not empty.
+fn element_text(xml: &str, element: &str) -> Option<String> {
+    let start = xml.find(&format!("<{element}>"))? + element.len() + 2;
+    let len = xml[start..].find(&format!("</{element}>"))?;
+
+    let text = xml[start..start + len]
+        .trim()
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&");
+
+    (!text.is_empty()).then_some(text)
+}
+
+fn bad_wav(message: &str) -> XmpError {
+    bad_file("WAV", message)
+}
@@ -0,0 +1,204 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+// Reads and writes the XMP chunk of WebP files.
+//
+// A WebP file is a RIFF container whose chunks are padded to an even
+// length. XMP is stored in an `XMP ` chunk, which is only allowed in the
+// extended format: the first chunk must then be `VP8X`, with a flag
+// announcing the XMP. A file in the simple format (a single `VP8 ` or
+// `VP8L` chunk) is converted to the extended format when XMP is added.
+//
+// See https://developers.google.com/speed/webp/docs/riff_container.
+
+use std::ops::Range;
+
+use crate::{xmp_file_handler::FileHandler, FileFormat, XmpError, XmpErrorType, XmpResult};
+
+const RIFF: &[u8; 4] = b"RIFF";
+const WEBP: &[u8; 4] = b"WEBP";
+const VP8: &[u8; 4] = b"VP8 ";
+const VP8L: &[u8; 4] = b"VP8L";
+const VP8X: &[u8; 4] = b"VP8X";
+const XMP: &[u8; 4] = b"XMP ";
+
+const VP8X_LEN: usize = 10;
+const ALPHA_FLAG: u8 = 0x10;
+const XMP_FLAG: u8 = 0x04;
+
+pub(crate) struct WebPHandler;
+
+impl FileHandler for WebPHandler {
+    fn format(&self) -> FileFormat {
+        FileFormat::WebP
+    }
+
+    fn check_format(&self, header: &[u8]) -> bool {
+        header.len() >= 12 && &header[0..4] == RIFF && &header[8..12] == WEBP
+    }
+
+    fn read_xmp(&self, file: &[u8]) -> XmpResult<Option<Range<usize>>> {
+        Ok(chunks(file)?
+            .into_iter()
+            .find(|chunk| &chunk.fourcc == XMP)
+            .map(|chunk| chunk.data))
+    }
+
+    fn write_xmp(&self, file: &[u8], packet: &[u8]) -> XmpResult<Vec<u8>> {
+        let chunks = chunks(file)?;
+
+        let mut out = Vec::with_capacity(file.len() + packet.len() + 32);
+        out.extend_from_slice(RIFF);
+        out.extend_from_slice(&[0; 4]);
+        out.extend_from_slice(WEBP);
+
+        let first = chunks.first().ok_or_else(|| bad_webp("No image data"))?;
+        if &first.fourcc == VP8X && first.data.len() < VP8X_LEN {
+            return Err(bad_webp("Invalid VP8X chunk"));
+        }
+
+        let mut flags = match &first.fourcc {
+            VP8X => file[first.data.start],
+            VP8 => 0,
+            VP8L => {
+                if vp8l_has_alpha(&file[first.data.clone()]) {
+                    ALPHA_FLAG
+                } else {
+                    0
+                }
+            }
+            _ => return Err(bad_webp("Unexpected first chunk")),
+        };
+        flags |= XMP_FLAG;
+
+        if &first.fourcc == VP8X {
+            let mut vp8x = file[first.range.clone()].to_vec();
+            vp8x[8] = flags;
+            out.extend_from_slice(&vp8x);
+        } else {
+            let (width, height) = canvas_size(&first.fourcc, &file[first.data.clone()])?;
+            push_chunk(&mut out, VP8X, &vp8x_payload(flags, width, height));
+        }
+
+        for chunk in &chunks {
+            if &chunk.fourcc != VP8X && &chunk.fourcc != XMP {
+                out.extend_from_slice(&file[chunk.range.clone()]);
+            }
+        }
+
+        push_chunk(&mut out, XMP, packet);
+
+        let riff_len = u32::try_from(out.len() - 8)
+            .map_err(|_| bad_webp("File is too large for the RIFF container"))?;
+        out[4..8].copy_from_slice(&riff_len.to_le_bytes());
+
+        Ok(out)
+    }
+}
+
+struct Chunk {
+    fourcc: [u8; 4],
+
+    // The whole chunk, including its header and padding.
+    range: Range<usize>,
+
+    // The chunk's payload.
+    data: Range<usize>,
+}
+
+fn chunks(file: &[u8]) -> XmpResult<Vec<Chunk>> {
+    if !WebPHandler.check_format(file) {
+        return Err(bad_webp("Not a WebP file"));
+    }
+
+    // Ignore anything after the end of the RIFF container.
+    let riff_len = u32::from_le_bytes([file[4], file[5], file[6], file[7]]) as usize;
+    let end = file.len().min(riff_len.saturating_add(8));
+
+    let mut chunks = Vec::new();
+    let mut pos = 12;
+
+    while pos + 8 <= end {
+        let fourcc = [file[pos], file[pos + 1], file[pos + 2], file[pos + 3]];
+        let len = u32::from_le_bytes([file[pos + 4], file[pos + 5], file[pos + 6], file[pos + 7]])
+            as usize;
+
+        let data = pos + 8..pos + 8 + len;
+        if data.end > end {
+            return Err(bad_webp("Chunk extends past the end of the file"));
+        }
+
+        let padded_end = (data.end + len % 2).min(end);
+        chunks.push(Chunk {
+            fourcc,
+            range: pos..padded_end,
+            data,
+        });
+
+        pos = padded_end;
+    }
+
+    Ok(chunks)
+}
+
+/// Returns the width and height of a simple-format image.
+fn canvas_size(fourcc: &[u8; 4], data: &[u8]) -> XmpResult<(u32, u32)> {
+    if fourcc == VP8 {
+        // Frame tag (3 bytes), start code, then 14-bit width and height.
+        if data.len() < 10 || data[3..6] != [0x9d, 0x01, 0x2a] {
+            return Err(bad_webp("Invalid VP8 frame header"));
+        }
+
+        let width = u16::from_le_bytes([data[6], data[7]]) & 0x3fff;
+        let height = u16::from_le_bytes([data[8], data[9]]) & 0x3fff;
+        Ok((width.into(), height.into()))
+    } else {
+        // Signature, then 14-bit width - 1 and height - 1.
+        if data.len() < 5 || data[0] != 0x2f {
+            return Err(bad_webp("Invalid VP8L header"));
+        }
+
+        let bits = u32::from_le_bytes([data[1], data[2], data[3], data[4]]);
+        Ok(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1))
+    }
+}
+
+fn vp8l_has_alpha(data: &[u8]) -> bool {
+    data.len() >= 5 && data[4] & 0x10 != 0
+}
+
+fn vp8x_payload(flags: u8, width: u32, height: u32) -> [u8; VP8X_LEN] {
+    let width = width.saturating_sub(1).to_le_bytes();
+    let height = height.saturating_sub(1).to_le_bytes();
+
+    [
+        flags, 0, 0, 0, width[0], width[1], width[2], height[0], height[1], height[2],
+    ]
+}
+
+fn push_chunk(out: &mut Vec<u8>, fourcc: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(fourcc);
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out.extend_from_slice(data);
+
+    if data.len() % 2 == 1 {
+        out.push(0);
+    }
+}
+
+fn bad_webp(message: &str) -> XmpError {
+    XmpError {
+        error_type: XmpErrorType::BadFileFormat,
+        debug_message: format!("WebP: {message}"),
+    }
+}
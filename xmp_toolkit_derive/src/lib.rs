@@ -0,0 +1,203 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Derive macros for the [`xmp_toolkit`](https://docs.rs/xmp_toolkit)
+//! crate.
+//!
+//! Do not depend on this crate directly. Instead, enable the `derive`
+//! feature of `xmp_toolkit` and use the re-exported macros from there.
+
+#![deny(clippy::expect_used)]
+#![deny(clippy::panic)]
+#![deny(clippy::unwrap_used)]
+#![deny(missing_docs)]
+#![deny(warnings)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Error, Fields, GenericArgument, LitStr,
+    PathArguments, Type,
+};
+
+/// Implements `xmp_toolkit::XmpStruct` for a struct with named fields.
+///
+/// See the documentation for `xmp_toolkit::XmpStruct` for usage.
+#[proc_macro_derive(XmpStruct, attributes(xmp))]
+pub fn derive_xmp_struct(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct XmpAttr {
+    ns: Option<LitStr>,
+    name: Option<LitStr>,
+}
+
+fn parse_xmp_attr(attrs: &[Attribute]) -> syn::Result<XmpAttr> {
+    let mut result = XmpAttr::default();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("xmp")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("ns") {
+                result.ns = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("name") {
+                result.name = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `ns` or `name`"))
+            }
+        })?;
+    }
+
+    Ok(result)
+}
+
+/// Returns `Some(inner)` if `ty` is `Option<inner>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+
+    match args.args.first()? {
+        GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let struct_attr = parse_xmp_attr(&input.attrs)?;
+    let Some(struct_ns) = struct_attr.ns else {
+        return Err(Error::new(
+            Span::call_site(),
+            "missing `#[xmp(ns = \"...\", name = \"...\")]` on struct",
+        ));
+    };
+    let Some(struct_name) = struct_attr.name else {
+        return Err(Error::new(
+            Span::call_site(),
+            "missing `#[xmp(name = \"...\")]` on struct",
+        ));
+    };
+
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            Span::call_site(),
+            "`XmpStruct` can only be derived for structs",
+        ));
+    };
+
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new(
+            Span::call_site(),
+            "`XmpStruct` can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut reads = Vec::new();
+    let mut writes = Vec::new();
+
+    for field in &fields.named {
+        let Some(field_ident) = &field.ident else {
+            continue;
+        };
+
+        let field_attr = parse_xmp_attr(&field.attrs)?;
+        let field_ns = field_attr.ns.unwrap_or_else(|| struct_ns.clone());
+        let field_name = field_attr
+            .name
+            .unwrap_or_else(|| LitStr::new(&field_ident.to_string(), field_ident.span()));
+
+        if let Some(inner) = option_inner(&field.ty) {
+            reads.push(quote! {
+                #field_ident: match meta.struct_field(#struct_ns, #struct_name, #field_ns, #field_name) {
+                    ::std::option::Option::Some(v) => ::std::option::Option::Some(
+                        <#inner as ::xmp_toolkit::XmpPropertyType>::from_xmp_str(&v.value)?,
+                    ),
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            });
+
+            writes.push(quote! {
+                if let ::std::option::Option::Some(value) = &self.#field_ident {
+                    fields.insert(
+                        (::std::string::String::from(#field_ns), ::std::string::String::from(#field_name)),
+                        ::xmp_toolkit::XmpValue::from(
+                            ::xmp_toolkit::XmpPropertyType::to_xmp_string(value).as_str(),
+                        ),
+                    );
+                }
+            });
+        } else {
+            let ty = &field.ty;
+
+            reads.push(quote! {
+                #field_ident: <#ty as ::xmp_toolkit::XmpPropertyType>::from_xmp_str(
+                    &meta
+                        .struct_field(#struct_ns, #struct_name, #field_ns, #field_name)?
+                        .value,
+                )?
+            });
+
+            writes.push(quote! {
+                fields.insert(
+                    (::std::string::String::from(#field_ns), ::std::string::String::from(#field_name)),
+                    ::xmp_toolkit::XmpValue::from(
+                        ::xmp_toolkit::XmpPropertyType::to_xmp_string(&self.#field_ident).as_str(),
+                    ),
+                );
+            });
+        }
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::xmp_toolkit::XmpStruct for #ident #ty_generics #where_clause {
+            fn read_from(meta: &::xmp_toolkit::XmpMeta) -> ::std::option::Option<Self> {
+                if !meta.property(#struct_ns, #struct_name)?.is_struct() {
+                    return ::std::option::Option::None;
+                }
+
+                ::std::option::Option::Some(Self {
+                    #(#reads,)*
+                })
+            }
+
+            fn write_to(
+                &self,
+                meta: &mut ::xmp_toolkit::XmpMeta,
+            ) -> ::xmp_toolkit::XmpResult<()> {
+                let mut fields = ::std::collections::BTreeMap::new();
+                #(#writes)*
+                meta.set_struct_from_map(#struct_ns, #struct_name, &fields)
+            }
+        }
+    })
+}